@@ -96,6 +96,10 @@ export interface PublicKey {
    * ```
    */
   public_key: string;
+  /**
+   * The algorithm of the key pair, which determines how to seal secrets with this key
+   */
+  algorithm: string;
   /**
    * A created lamport clock, used to sort multiple generations of key pairs
    */
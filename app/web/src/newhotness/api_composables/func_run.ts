@@ -91,6 +91,14 @@ export interface FuncRun {
   resultValue: unknown;
   logs?: FuncRunLog;
   unprocessedResultValue: unknown;
+  timings?: FuncRunTimings | null;
+}
+/** How long each phase of a func run took, in microseconds */
+export interface FuncRunTimings {
+  prepareUs: number;
+  criticalSectionUs: number;
+  outputDrainUs: number;
+  postprocessUs: number;
 }
 export interface OutputLine {
   stream: string;
@@ -99,6 +107,7 @@ export interface OutputLine {
   group?: string;
   message: string;
   timestamp: string;
+  structured?: Record<string, unknown> | null;
 }
 
 export interface FuncRunLog {
@@ -18,6 +18,7 @@ export interface AdminWorkspace {
   name: string;
   defaultChangeSetId: ChangeSetId;
   componentConcurrencyLimit?: number;
+  schemaAllowlist?: string[] | null;
   snapshotVersion: string;
 }
 
@@ -119,6 +120,13 @@ export const useAdminStore = () => {
             params: { concurrencyLimit },
           });
         },
+        async SET_SCHEMA_ALLOWLIST(workspaceId: string, schemaAllowlist?: string[]) {
+          return new ApiRequest<{ schemaAllowlist?: string[] | null }, { schemaAllowlist?: string[] }>({
+            method: "post",
+            url: `${API_PREFIX}/workspaces/${workspaceId}/set_schema_allowlist`,
+            params: { schemaAllowlist },
+          });
+        },
         async KILL_EXECUTION(funcRunId: FuncRunId) {
           return new ApiRequest<null>({
             method: "put",
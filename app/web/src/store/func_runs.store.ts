@@ -63,6 +63,7 @@ export interface OutputLine {
   group?: string;
   message: string;
   timestamp: string;
+  structured?: Record<string, unknown> | null;
 }
 
 export interface FuncRunLog {
@@ -13,7 +13,7 @@ import { StatusUpdate } from "../status.store";
 import { CursorContainerKind } from "../presence.store";
 import { UserId } from "../auth.store";
 import { FuncRunId } from "../actions.store";
-import { FuncRunLogId } from "../func_runs.store";
+import { FuncRunLogId, OutputLine } from "../func_runs.store";
 
 export type SecretId = string;
 
@@ -93,6 +93,13 @@ export type WsEventPayloadMap = {
     toRebaseChangeSetId: ChangeSetId;
     userPk: UserId;
   };
+  ChangeSetApplyProgress: {
+    changeSetId: ChangeSetId;
+    processed: number;
+    total: number;
+    completed: boolean;
+    error: string | null;
+  };
   ChangeSetBeginApprovalProcess: {
     changeSetId: ChangeSetId;
     userPk: UserId;
@@ -366,6 +373,13 @@ export type WsEventPayloadMap = {
     funcId: FuncId;
     changeSetId: ChangeSetId;
   };
+  FuncRunLogStreamed: {
+    funcRunId: FuncRunId;
+    actionId?: ActionId;
+    sequence: number;
+    line: OutputLine | null;
+    done: boolean;
+  };
   FuncRunLogUpdated: {
     funcRunId: FuncRunId;
     funcRunLogId: FuncRunLogId;
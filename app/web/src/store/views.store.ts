@@ -772,7 +772,7 @@ export const useViewsStore = (forceChangeSetId?: ChangeSetId) => {
           });
         },
         async UPDATE_VIEW_NAME(view_id: ViewId, name: string) {
-          return new ApiRequest<null>({
+          return new ApiRequest<ViewDescription>({
             method: "put",
             url: API_PREFIX.concat([view_id]),
             params: { name, clientUlid },
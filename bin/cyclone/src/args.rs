@@ -137,6 +137,30 @@ pub(crate) struct Args {
     #[arg(long, group = "action_run")]
     pub(crate) disable_action_run: bool,
 
+    /// Enables validation endpoint.
+    #[arg(long, group = "validation")]
+    pub(crate) enable_validation: bool,
+
+    /// Disables validation endpoint.
+    #[arg(long, group = "validation")]
+    pub(crate) disable_validation: bool,
+
+    /// Enables schema variant definition endpoint.
+    #[arg(long, group = "schema_variant_definition")]
+    pub(crate) enable_schema_variant_definition: bool,
+
+    /// Disables schema variant definition endpoint.
+    #[arg(long, group = "schema_variant_definition")]
+    pub(crate) disable_schema_variant_definition: bool,
+
+    /// Enables management endpoint.
+    #[arg(long, group = "management")]
+    pub(crate) enable_management: bool,
+
+    /// Disables management endpoint.
+    #[arg(long, group = "management")]
+    pub(crate) disable_management: bool,
+
     /// Enables configuration endpoint.
     #[arg(long, group = "configuration")]
     pub(crate) enable_configuration: bool,
@@ -224,6 +248,30 @@ impl TryFrom<Args> for Config {
             builder.enable_resolver(false);
         }
 
+        if args.enable_action_run {
+            builder.enable_action_run(true);
+        } else if args.disable_action_run {
+            builder.enable_action_run(false);
+        }
+
+        if args.enable_validation {
+            builder.enable_validation(true);
+        } else if args.disable_validation {
+            builder.enable_validation(false);
+        }
+
+        if args.enable_schema_variant_definition {
+            builder.enable_schema_variant_definition(true);
+        } else if args.disable_schema_variant_definition {
+            builder.enable_schema_variant_definition(false);
+        }
+
+        if args.enable_management {
+            builder.enable_management(true);
+        } else if args.disable_management {
+            builder.enable_management(false);
+        }
+
         if args.oneshot {
             builder.limit_requests(1);
         } else if let Some(limit_requests) = args.limit_requests {
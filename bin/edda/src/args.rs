@@ -95,6 +95,14 @@ pub(crate) struct Args {
     #[arg(long)]
     pub(crate) concurrency_limit: Option<u32>,
 
+    /// Enables the veritech circuit breaker, opening after this many consecutive failures
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_failure_threshold: Option<u32>,
+
+    /// Seconds the veritech circuit breaker stays open before probing [default: 30]
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_cool_down_secs: Option<u64>,
+
     /// NATS connection URL [example: demo.nats.io]
     #[arg(long)]
     pub(crate) nats_url: Option<String>,
@@ -115,6 +123,15 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     if let Some(concurrency_limit) = args.concurrency_limit {
         config_map.set("concurrency_limit", i64::from(concurrency_limit));
     }
+    if let Some(failure_threshold) = args.veritech_circuit_breaker_failure_threshold {
+        config_map.set(
+            "veritech_circuit_breaker.failure_threshold",
+            i64::from(failure_threshold),
+        );
+        if let Some(cool_down_secs) = args.veritech_circuit_breaker_cool_down_secs {
+            config_map.set("veritech_circuit_breaker.cool_down", cool_down_secs);
+        }
+    }
     if let Some(url) = args.nats_url {
         config_map.set("nats.url", url.clone());
     }
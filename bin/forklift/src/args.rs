@@ -114,6 +114,18 @@ pub(crate) struct Args {
     /// Enables the audit logs app
     #[arg(long)]
     pub(crate) enable_audit_logs_app: Option<bool>,
+
+    /// Maximum number of billing events buffered while waiting to be delivered [default: 100]
+    #[arg(long)]
+    pub(crate) billing_events_queue_capacity: Option<u32>,
+
+    /// What to do with a billing event when the delivery queue is full [default: block]
+    #[arg(long, value_parser = ["block", "drop_oldest"])]
+    pub(crate) billing_events_backpressure_policy: Option<String>,
+
+    /// Maximum number of billing events being delivered at once [default: 8]
+    #[arg(long)]
+    pub(crate) billing_events_delivery_concurrency: Option<u32>,
 }
 
 fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
@@ -139,6 +151,21 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     if let Some(enable_audit_logs_app) = args.enable_audit_logs_app {
         config_map.set("enable_audit_logs_app", enable_audit_logs_app);
     }
+    if let Some(capacity) = args.billing_events_queue_capacity {
+        config_map.set(
+            "billing_events_delivery.queue_capacity",
+            i64::from(capacity),
+        );
+    }
+    if let Some(policy) = args.billing_events_backpressure_policy {
+        config_map.set("billing_events_delivery.backpressure_policy", policy);
+    }
+    if let Some(concurrency) = args.billing_events_delivery_concurrency {
+        config_map.set(
+            "billing_events_delivery.delivery_concurrency",
+            i64::from(concurrency),
+        );
+    }
     config_map
 }
 
@@ -14,6 +14,7 @@ use luminork_server::{
     ConfigFile,
     ConfigMap,
     FeatureFlag,
+    KeyPairAlgorithm,
     ParameterProvider,
     StandardConfigFile,
     WorkspacePermissions,
@@ -235,6 +236,10 @@ pub(crate) struct Args {
     #[arg(long, env = "SI_CREATE_WORKSPACE_PERMISSIONS", value_parser = PossibleValuesParser::new(WorkspacePermissionsMode::variants()))]
     pub(crate) create_workspace_permissions: Option<String>,
 
+    /// Algorithm of the key pairs created for new workspaces [default: curve25519xsalsa20poly1305]
+    #[arg(long, env = "SI_DEFAULT_KEY_PAIR_ALGORITHM", value_parser = PossibleValuesParser::new(KeyPairAlgorithm::variants()))]
+    pub(crate) default_key_pair_algorithm: Option<String>,
+
     /// List of emails that can create workspaces
     #[arg(
         long,
@@ -254,6 +259,14 @@ pub(crate) struct Args {
     /// back to an instance of this service.
     #[arg(long)]
     pub(crate) instance_id: Option<String>,
+
+    /// Enables the veritech circuit breaker, opening after this many consecutive failures
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_failure_threshold: Option<u32>,
+
+    /// Seconds the veritech circuit breaker stays open before probing [default: 30]
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_cool_down_secs: Option<u64>,
 }
 
 impl Args {
@@ -378,7 +391,10 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     }
 
     if let Some(layer_cache_disk_path) = args.layer_db_disk_path {
-        config_map.set("layer_db_config.disk_path", layer_cache_disk_path);
+        config_map.set(
+            "layer_db_config.cache_config.disk_path",
+            layer_cache_disk_path,
+        );
     }
     if let Some(pkgs_path) = args.pkgs_path {
         config_map.set("pkgs_path", pkgs_path);
@@ -397,6 +413,10 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
         config_map.set("create_workspace_permissions", create_workspace_permissions);
     }
 
+    if let Some(default_key_pair_algorithm) = args.default_key_pair_algorithm {
+        config_map.set("default_key_pair_algorithm", default_key_pair_algorithm);
+    }
+
     if !args.create_workspace_allowlist.is_empty() {
         config_map.set(
             "create_workspace_allowlist",
@@ -404,6 +424,16 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
         );
     }
 
+    if let Some(failure_threshold) = args.veritech_circuit_breaker_failure_threshold {
+        config_map.set(
+            "veritech_circuit_breaker.failure_threshold",
+            i64::from(failure_threshold),
+        );
+        if let Some(cool_down_secs) = args.veritech_circuit_breaker_cool_down_secs {
+            config_map.set("veritech_circuit_breaker.cool_down", cool_down_secs);
+        }
+    }
+
     config_map.set("nats.connection_name", NAME);
     config_map.set("pg.application_name", NAME);
     config_map.set("layer_db_config.pg_pool_config.application_name", NAME);
@@ -158,6 +158,14 @@ pub(crate) struct Args {
     #[arg(long)]
     pub(crate) max_deliver: Option<i64>,
 
+    /// Enables the veritech circuit breaker, opening after this many consecutive failures
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_failure_threshold: Option<u32>,
+
+    /// Seconds the veritech circuit breaker stays open before probing [default: 30]
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_cool_down_secs: Option<u64>,
+
     /// The path at which the layer db cache is created/used on disk [e.g. /banana/]
     #[arg(long)]
     pub(crate) layer_db_disk_path: Option<String>,
@@ -263,8 +271,20 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     if let Some(max_deliver) = args.max_deliver {
         config_map.set("max_deliver", max_deliver);
     }
+    if let Some(failure_threshold) = args.veritech_circuit_breaker_failure_threshold {
+        config_map.set(
+            "veritech_circuit_breaker.failure_threshold",
+            i64::from(failure_threshold),
+        );
+        if let Some(cool_down_secs) = args.veritech_circuit_breaker_cool_down_secs {
+            config_map.set("veritech_circuit_breaker.cool_down", cool_down_secs);
+        }
+    }
     if let Some(layer_cache_disk_path) = args.layer_db_disk_path {
-        config_map.set("layer_db_config.disk_path", layer_cache_disk_path);
+        config_map.set(
+            "layer_db_config.cache_config.disk_path",
+            layer_cache_disk_path,
+        );
     }
     if let Some(layer_cache_seconds_to_idle) = args.layer_db_seconds_to_idle {
         config_map.set(
@@ -14,6 +14,7 @@ use sdf_server::{
     ConfigFile,
     ConfigMap,
     FeatureFlag,
+    KeyPairAlgorithm,
     MigrationMode,
     ParameterProvider,
     StandardConfigFile,
@@ -35,7 +36,7 @@ pub(crate) fn parse() -> Args {
 ///
 /// Super Dimension Fortress (SDF) is the central and primary API surface which handles front end
 /// calls and dispatches function executions, among other great things.
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(name = NAME, version = VERSION, max_term_width = 100)]
 pub(crate) struct Args {
     /// Sets the verbosity mode.
@@ -188,6 +189,55 @@ pub(crate) struct Args {
     #[arg(long, env = "SI_BACKFILL_FUNC_RUN_LOGS_CUTOFF_ID")]
     pub(crate) backfill_func_run_logs_cutoff_id: Option<String>,
 
+    /// Maximum number of workspace snapshots to migrate at the same time. Each snapshot being
+    /// migrated is held in memory, so raising this trades memory for a faster migration
+    /// [default: 4]
+    #[arg(long, env = "SI_SNAPSHOT_MIGRATION_CONCURRENCY_LIMIT")]
+    pub(crate) snapshot_migration_concurrency_limit: Option<usize>,
+
+    /// Number of attempts made to fetch each builtin module from the module index [default: 5]
+    #[arg(long, env = "SI_MODULE_FETCH_MAX_ATTEMPTS")]
+    pub(crate) module_fetch_max_attempts: Option<u32>,
+
+    /// Maximum input size in bytes of size-checked compute executor tasks [default: unlimited]
+    #[arg(long, env = "SI_COMPUTE_EXECUTOR_MAX_TASK_INPUT_SIZE")]
+    pub(crate) compute_executor_max_task_input_size: Option<usize>,
+
+    /// Seconds of client inactivity after which a WebSocket session is closed [default: never]
+    #[arg(long, env = "SI_WS_IDLE_TIMEOUT_SECS")]
+    pub(crate) ws_idle_timeout_secs: Option<u64>,
+
+    /// Requests allowed to wait for a database connection before further requests are rejected
+    /// with a 429 [default: no limit]
+    #[arg(long, env = "SI_PG_POOL_MAX_WAITING")]
+    pub(crate) pg_pool_max_waiting: Option<usize>,
+
+    /// Seconds to wait for a blocking job to finish before giving up on it [default: 900]
+    #[arg(long, env = "SI_BLOCKING_JOB_TIMEOUT_SECS")]
+    pub(crate) blocking_job_timeout_secs: Option<u64>,
+
+    /// Largest request body, in bytes, accepted by routes without their own limit [default: 2MiB]
+    #[arg(long, env = "SI_MAX_REQUEST_BODY_BYTES")]
+    pub(crate) max_request_body_bytes: Option<usize>,
+
+    /// Change set applies a workspace may make per minute, counted per sdf replica [default: no
+    /// limit]
+    #[arg(long, env = "SI_APPLY_RATE_LIMIT")]
+    pub(crate) apply_rate_limit: Option<u32>,
+
+    /// Func executions a workspace may request per minute, counted per sdf replica [default: no
+    /// limit]
+    #[arg(long, env = "SI_EXECUTION_RATE_LIMIT")]
+    pub(crate) execution_rate_limit: Option<u32>,
+
+    /// Enables the veritech circuit breaker, opening after this many consecutive failures
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_failure_threshold: Option<u32>,
+
+    /// Seconds the veritech circuit breaker stays open before probing [default: 30]
+    #[arg(long)]
+    pub(crate) veritech_circuit_breaker_cool_down_secs: Option<u64>,
+
     /// Veritech encryption key file location [default: /run/sdf/veritech_encryption.key]
     #[arg(long)]
     pub(crate) veritech_encryption_key_path: Option<PathBuf>,
@@ -278,6 +328,10 @@ pub(crate) struct Args {
     #[arg(long, env = "SI_CREATE_WORKSPACE_PERMISSIONS", value_parser = PossibleValuesParser::new(WorkspacePermissionsMode::variants()))]
     pub(crate) create_workspace_permissions: Option<String>,
 
+    /// Algorithm of the key pairs created for new workspaces [default: curve25519xsalsa20poly1305]
+    #[arg(long, env = "SI_DEFAULT_KEY_PAIR_ALGORITHM", value_parser = PossibleValuesParser::new(KeyPairAlgorithm::variants()))]
+    pub(crate) default_key_pair_algorithm: Option<String>,
+
     /// List of emails that can create workspaces
     #[arg(
         long,
@@ -445,7 +499,10 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     }
 
     if let Some(layer_cache_disk_path) = args.layer_db_disk_path {
-        config_map.set("layer_db_config.disk_path", layer_cache_disk_path);
+        config_map.set(
+            "layer_db_config.cache_config.disk_path",
+            layer_cache_disk_path,
+        );
     }
     if let Some(pkgs_path) = args.pkgs_path {
         config_map.set("pkgs_path", pkgs_path);
@@ -484,6 +541,10 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
         config_map.set("create_workspace_permissions", create_workspace_permissions);
     }
 
+    if let Some(default_key_pair_algorithm) = args.default_key_pair_algorithm {
+        config_map.set("default_key_pair_algorithm", default_key_pair_algorithm);
+    }
+
     if !args.create_workspace_allowlist.is_empty() {
         config_map.set(
             "create_workspace_allowlist",
@@ -521,6 +582,67 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
         );
     }
 
+    if let Some(limit) = args.snapshot_migration_concurrency_limit {
+        config_map.set(
+            "snapshot_migration_concurrency_limit",
+            i64::try_from(limit).unwrap_or(i64::MAX),
+        );
+    }
+
+    if let Some(max_attempts) = args.module_fetch_max_attempts {
+        config_map.set("module_fetch_max_attempts", i64::from(max_attempts));
+    }
+
+    if let Some(size) = args.compute_executor_max_task_input_size {
+        config_map.set(
+            "compute_executor_max_task_input_size",
+            i64::try_from(size).unwrap_or(i64::MAX),
+        );
+    }
+
+    if let Some(secs) = args.ws_idle_timeout_secs {
+        config_map.set(
+            "ws_idle_timeout_secs",
+            i64::try_from(secs).unwrap_or(i64::MAX),
+        );
+    }
+
+    if let Some(max_waiting) = args.pg_pool_max_waiting {
+        config_map.set(
+            "pg_pool_max_waiting",
+            i64::try_from(max_waiting).unwrap_or(i64::MAX),
+        );
+    }
+
+    if let Some(secs) = args.blocking_job_timeout_secs {
+        config_map.set(
+            "blocking_job_timeout_secs",
+            i64::try_from(secs).unwrap_or(i64::MAX),
+        );
+    }
+    if let Some(bytes) = args.max_request_body_bytes {
+        config_map.set(
+            "max_request_body_bytes",
+            i64::try_from(bytes).unwrap_or(i64::MAX),
+        );
+    }
+
+    if let Some(limit) = args.apply_rate_limit {
+        config_map.set("apply_rate_limit", i64::from(limit));
+    }
+    if let Some(limit) = args.execution_rate_limit {
+        config_map.set("execution_rate_limit", i64::from(limit));
+    }
+    if let Some(failure_threshold) = args.veritech_circuit_breaker_failure_threshold {
+        config_map.set(
+            "veritech_circuit_breaker.failure_threshold",
+            i64::from(failure_threshold),
+        );
+        if let Some(cool_down_secs) = args.veritech_circuit_breaker_cool_down_secs {
+            config_map.set("veritech_circuit_breaker.cool_down", cool_down_secs);
+        }
+    }
+
     config_map.set("nats.connection_name", NAME);
     config_map.set("pg.application_name", NAME);
     config_map.set("layer_db_config.pg_pool_config.application_name", NAME);
@@ -1,6 +1,7 @@
 #![recursion_limit = "256"]
 
 use std::{
+    future::IntoFuture,
     path::PathBuf,
     time::Duration,
 };
@@ -9,6 +10,7 @@ use innit_client::InnitClient;
 use sdf_server::{
     BackfillConfig,
     Config,
+    ConfigLoader,
     FuncRunsBackfiller,
     LayerCacheBackfiller,
     Migrator,
@@ -20,7 +22,10 @@ use si_service::{
     color_eyre,
     prelude::*,
     rt,
-    shutdown,
+    shutdown::{
+        self,
+        GracefulShutdown,
+    },
     startup,
     telemetry_application::{
         self,
@@ -40,19 +45,19 @@ const LIB_NAME: &str = concat!(env!("CARGO_BIN_NAME"), "_server");
 
 const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60 * 10);
 
+// Each group is shut down in turn and given its own share of the overall timeout, so a group which
+// is slow to shut down can not stop the layer db from flushing or telemetry from being sent
+const MAIN_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60 * 6);
+const HELPING_TASKS_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60 * 3);
+const ENDPOINTS_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+const TELEMETRY_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
 fn main() -> Result<()> {
     rt::block_on(NAME, async_main())
 }
 
 async fn async_main() -> Result<()> {
-    let main_tracker = TaskTracker::new();
-    let main_token = CancellationToken::new();
-    let helping_tasks_tracker = TaskTracker::new();
-    let helping_tasks_token = CancellationToken::new();
-    let endpoints_tracker = TaskTracker::new();
-    let endpoints_token = CancellationToken::new();
-    let telemetry_tracker = TaskTracker::new();
-    let telemetry_token = CancellationToken::new();
+    let groups = TaskGroups::new();
 
     color_eyre::install()?;
     let args = args::parse();
@@ -82,7 +87,11 @@ async fn async_main() -> Result<()> {
             ])
             .build()?;
 
-        telemetry_application::init(config, &telemetry_tracker, telemetry_token.clone())?
+        telemetry_application::init(
+            config,
+            &groups.telemetry_tracker,
+            groups.telemetry_token.clone(),
+        )?
     };
 
     startup::startup(NAME).await?;
@@ -95,111 +104,41 @@ async fn async_main() -> Result<()> {
     debug!(arguments =?args, "parsed cli arguments");
 
     if let Some((secret_key_path, public_key_path)) = args.generating_veritech_key_pair() {
-        generate_veritech_key_pair(
-            secret_key_path,
-            public_key_path,
-            main_tracker,
-            main_token,
-            telemetry_tracker,
-            telemetry_token,
-            telemetry_shutdown,
-        )
-        .await
+        generate_veritech_key_pair(secret_key_path, public_key_path, groups, telemetry_shutdown)
+            .await
     } else if let Some(symmetric_key_path) = args.generating_symmetric_key() {
-        generate_symmetric_key(
-            symmetric_key_path,
-            main_tracker,
-            main_token,
-            telemetry_tracker,
-            telemetry_token,
-            telemetry_shutdown,
-        )
-        .await
+        generate_symmetric_key(symmetric_key_path, groups, telemetry_shutdown).await
     } else {
         debug!("creating innit-client...");
         let provider = Some(InnitClient::new_from_environment(NAME.to_string()).await?);
+        // Rebuilds the config from the same arguments and sources, for reloading on `SIGHUP`
+        let config_loader: ConfigLoader = {
+            let (args, provider) = (args.clone(), provider.clone());
+            Box::new(move || Box::pin(load_config_with_provider(args.clone(), provider.clone())))
+        };
         let config = load_config_with_provider(args, provider).await?;
 
         debug!(?config, "computed configuration");
 
         if config.migration_mode().is_run_and_quit() {
-            migrate_and_quit(
-                config,
-                main_tracker,
-                main_token,
-                helping_tasks_tracker,
-                helping_tasks_token,
-                telemetry_tracker,
-                telemetry_token,
-                telemetry_shutdown,
-            )
-            .await
+            migrate_and_quit(config, groups, telemetry_shutdown).await
         } else if config.migration_mode().is_garbage_collect_snapshots() {
-            garbage_collect_snapshots(
-                config,
-                main_tracker,
-                main_token,
-                helping_tasks_tracker,
-                helping_tasks_token,
-                telemetry_tracker,
-                telemetry_token,
-                telemetry_shutdown,
-            )
-            .await
+            garbage_collect_snapshots(config, groups, telemetry_shutdown).await
         } else if config.migration_mode().is_backfill_layer_cache() {
-            backfill_layer_cache(
-                config,
-                main_tracker,
-                main_token,
-                helping_tasks_tracker,
-                helping_tasks_token,
-                telemetry_tracker,
-                telemetry_token,
-                telemetry_shutdown,
-            )
-            .await
+            backfill_layer_cache(config, groups, telemetry_shutdown).await
         } else if config.migration_mode().is_backfill_func_runs() {
-            backfill_func_runs(
-                config,
-                main_tracker,
-                main_token,
-                helping_tasks_tracker,
-                helping_tasks_token,
-                telemetry_tracker,
-                telemetry_token,
-                telemetry_shutdown,
-            )
-            .await
+            backfill_func_runs(config, groups, telemetry_shutdown).await
         } else {
-            run_server(
-                config,
-                main_tracker,
-                main_token,
-                helping_tasks_tracker,
-                helping_tasks_token,
-                endpoints_tracker,
-                endpoints_token,
-                telemetry_tracker,
-                telemetry_token,
-                telemetry_shutdown,
-            )
-            .await
+            run_server(config, config_loader, groups, telemetry_shutdown).await
         }
     }
 }
 
 #[inline]
-#[allow(clippy::too_many_arguments)]
 async fn run_server(
     config: Config,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    helping_tasks_tracker: TaskTracker,
-    helping_tasks_token: CancellationToken,
-    endpoints_tracker: TaskTracker,
-    endpoints_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    config_loader: ConfigLoader,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
     let migration_mode_is_run = config.migration_mode().is_run();
@@ -210,19 +149,20 @@ async fn run_server(
         Some(sdf_server::EndpointsServer::new(
             std::sync::Arc::new(endpoints),
             config.service_endpoints().clone(),
-            endpoints_token.clone(),
+            groups.endpoints_token.clone(),
         ))
     } else {
         None
     };
 
     let server = Server::from_config(
-        config,
-        main_token.clone(),
-        &helping_tasks_tracker,
-        helping_tasks_token.clone(),
+        config.clone(),
+        groups.main_token.clone(),
+        &groups.helping_tasks_tracker,
+        groups.helping_tasks_token.clone(),
     )
     .await?;
+    server.reload_config_on_sighup(config, config_loader, groups.main_token.clone())?;
 
     if migration_mode_is_run {
         // If migrations fail, process will exit with an error.
@@ -232,98 +172,76 @@ async fn run_server(
         server.migrator().run_migrations(is_dev_mode, false).await?;
     }
 
-    main_tracker.spawn(async move {
+    groups.main_tracker.spawn(async move {
         info!("ready to receive requests");
         server.run().await
     });
 
     if let Some(endpoints_server) = endpoints_server {
-        endpoints_tracker.spawn(async move {
+        groups.endpoints_tracker.spawn(async move {
             if let Err(err) = endpoints_server.run().await {
                 error!(error = ?err, "error running sdf endpoints server");
             }
         });
     }
 
-    shutdown::graceful()
-        .group(main_tracker, main_token)
-        .group(helping_tasks_tracker, helping_tasks_token)
-        .group(endpoints_tracker, endpoints_token)
-        .group(telemetry_tracker, telemetry_token)
-        .telemetry_guard(telemetry_shutdown.into_future())
-        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
-        .wait()
-        .await
-        .map_err(Into::into)
+    wait_for_shutdown(shutdown::graceful(), groups, telemetry_shutdown).await
 }
 
 #[inline]
-#[allow(clippy::too_many_arguments)]
 async fn migrate_and_quit(
     config: Config,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    helping_tasks_tracker: TaskTracker,
-    helping_tasks_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
-    let migrator =
-        Migrator::from_config(config, &helping_tasks_tracker, helping_tasks_token.clone()).await?;
+    let migrator = Migrator::from_config(
+        config,
+        &groups.helping_tasks_tracker,
+        groups.helping_tasks_token.clone(),
+    )
+    .await?;
 
-    let handle = main_tracker.spawn(migrator.run_migrations(false, false));
+    let handle = groups
+        .main_tracker
+        .spawn(migrator.run_migrations(false, false));
 
-    shutdown::graceful_with_handle(handle)
-        .group(main_tracker, main_token)
-        .group(helping_tasks_tracker, helping_tasks_token)
-        .group(telemetry_tracker, telemetry_token)
-        .telemetry_guard(telemetry_shutdown.into_future())
-        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
-        .wait()
-        .await
-        .map_err(Into::into)
+    wait_for_shutdown(
+        shutdown::graceful_with_handle(handle),
+        groups,
+        telemetry_shutdown,
+    )
+    .await
 }
 
 #[inline]
-#[allow(clippy::too_many_arguments)]
 async fn garbage_collect_snapshots(
     config: Config,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    helping_tasks_tracker: TaskTracker,
-    helping_tasks_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
-    let garbage_collector =
-        SnapshotGarbageCollector::new(config, &helping_tasks_tracker, helping_tasks_token.clone())
-            .await?;
+    let garbage_collector = SnapshotGarbageCollector::new(
+        config,
+        &groups.helping_tasks_tracker,
+        groups.helping_tasks_token.clone(),
+    )
+    .await?;
 
-    let handle = main_tracker.spawn(garbage_collector.garbage_collect_snapshots());
+    let handle = groups
+        .main_tracker
+        .spawn(garbage_collector.garbage_collect_snapshots());
 
-    shutdown::graceful_with_handle(handle)
-        .group(main_tracker, main_token)
-        .group(helping_tasks_tracker, helping_tasks_token)
-        .group(telemetry_tracker, telemetry_token)
-        .telemetry_guard(telemetry_shutdown.into_future())
-        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
-        .wait()
-        .await
-        .map_err(Into::into)
+    wait_for_shutdown(
+        shutdown::graceful_with_handle(handle),
+        groups,
+        telemetry_shutdown,
+    )
+    .await
 }
 
 #[inline]
-#[allow(clippy::too_many_arguments)]
 async fn backfill_layer_cache(
     config: Config,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    helping_tasks_tracker: TaskTracker,
-    helping_tasks_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
     // Parse backfill config from CLI args
@@ -338,32 +256,28 @@ async fn backfill_layer_cache(
     let backfiller = LayerCacheBackfiller::new(
         config,
         backfill_config,
-        &helping_tasks_tracker,
-        helping_tasks_token.clone(),
+        &groups.helping_tasks_tracker,
+        groups.helping_tasks_token.clone(),
     )
     .await?;
 
-    let handle = main_tracker.spawn(backfiller.backfill_all_caches(main_token.clone()));
+    let handle = groups
+        .main_tracker
+        .spawn(backfiller.backfill_all_caches(groups.main_token.clone()));
 
-    shutdown::graceful_with_handle(handle)
-        .group(main_tracker, main_token)
-        .group(helping_tasks_tracker, helping_tasks_token)
-        .group(telemetry_tracker, telemetry_token)
-        .telemetry_guard(telemetry_shutdown.into_future())
-        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
-        .wait()
-        .await
-        .map_err(Into::into)
+    wait_for_shutdown(
+        shutdown::graceful_with_handle(handle),
+        groups,
+        telemetry_shutdown,
+    )
+    .await
 }
 
 #[inline]
 async fn generate_veritech_key_pair(
     secret_key_path: PathBuf,
     public_key_path: PathBuf,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
     info!(
@@ -372,54 +286,45 @@ async fn generate_veritech_key_pair(
         "generating veritech key pair",
     );
 
-    let handle = main_tracker.spawn(key_generation::generate_veritech_key_pair(
-        secret_key_path,
-        public_key_path,
-    ));
+    let handle = groups
+        .main_tracker
+        .spawn(key_generation::generate_veritech_key_pair(
+            secret_key_path,
+            public_key_path,
+        ));
 
-    shutdown::graceful_with_handle(handle)
-        .group(main_tracker, main_token)
-        .group(telemetry_tracker, telemetry_token)
-        .telemetry_guard(telemetry_shutdown.into_future())
-        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
-        .wait()
-        .await
-        .map_err(Into::into)
+    wait_for_shutdown(
+        shutdown::graceful_with_handle(handle),
+        groups,
+        telemetry_shutdown,
+    )
+    .await
 }
 
 #[inline]
 async fn generate_symmetric_key(
     symmetric_key_path: PathBuf,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
     info!(path = %symmetric_key_path.display(), "enerating symmetric key");
 
-    let handle = main_tracker.spawn(key_generation::generate_symmetric_key(symmetric_key_path));
+    let handle = groups
+        .main_tracker
+        .spawn(key_generation::generate_symmetric_key(symmetric_key_path));
 
-    shutdown::graceful_with_handle(handle)
-        .group(main_tracker, main_token)
-        .group(telemetry_tracker, telemetry_token)
-        .telemetry_guard(telemetry_shutdown.into_future())
-        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
-        .wait()
-        .await
-        .map_err(Into::into)
+    wait_for_shutdown(
+        shutdown::graceful_with_handle(handle),
+        groups,
+        telemetry_shutdown,
+    )
+    .await
 }
 
 #[inline]
-#[allow(clippy::too_many_arguments)]
 async fn backfill_func_runs(
     config: Config,
-    main_tracker: TaskTracker,
-    main_token: CancellationToken,
-    helping_tasks_tracker: TaskTracker,
-    helping_tasks_token: CancellationToken,
-    telemetry_tracker: TaskTracker,
-    telemetry_token: CancellationToken,
+    groups: TaskGroups,
     telemetry_shutdown: TelemetryShutdownGuard,
 ) -> Result<()> {
     // Extract parameters before config is moved
@@ -431,22 +336,88 @@ async fn backfill_func_runs(
         .and_then(|id_str| id_str.parse().ok());
     let batch_size = config.backfill_key_batch_size() as i64;
 
-    let handle = main_tracker.spawn(
+    let handle = groups.main_tracker.spawn(
         FuncRunsBackfiller::upload_all_func_runs_and_logs_concurrently(
             config,
-            helping_tasks_tracker.clone(),
-            helping_tasks_token.clone(),
-            main_token.clone(),
+            groups.helping_tasks_tracker.clone(),
+            groups.helping_tasks_token.clone(),
+            groups.main_token.clone(),
             func_run_cutoff_id,
             func_run_log_cutoff_id,
             batch_size,
         ),
     );
 
-    shutdown::graceful_with_handle(handle)
-        .group(main_tracker, main_token)
-        .group(helping_tasks_tracker, helping_tasks_token)
-        .group(telemetry_tracker, telemetry_token)
+    wait_for_shutdown(
+        shutdown::graceful_with_handle(handle),
+        groups,
+        telemetry_shutdown,
+    )
+    .await
+}
+
+/// The task trackers and cancellation tokens of each group of tasks, which are shut down in turn.
+struct TaskGroups {
+    main_tracker: TaskTracker,
+    main_token: CancellationToken,
+    helping_tasks_tracker: TaskTracker,
+    helping_tasks_token: CancellationToken,
+    endpoints_tracker: TaskTracker,
+    endpoints_token: CancellationToken,
+    telemetry_tracker: TaskTracker,
+    telemetry_token: CancellationToken,
+}
+
+impl TaskGroups {
+    fn new() -> Self {
+        Self {
+            main_tracker: TaskTracker::new(),
+            main_token: CancellationToken::new(),
+            helping_tasks_tracker: TaskTracker::new(),
+            helping_tasks_token: CancellationToken::new(),
+            endpoints_tracker: TaskTracker::new(),
+            endpoints_token: CancellationToken::new(),
+            telemetry_tracker: TaskTracker::new(),
+            telemetry_token: CancellationToken::new(),
+        }
+    }
+}
+
+/// Waits for a graceful shutdown, shutting down each of the task groups in turn with its own
+/// timeout. Groups which no tasks were spawned onto finish straight away.
+async fn wait_for_shutdown<HanErr>(
+    shutdown: GracefulShutdown<<TelemetryShutdownGuard as IntoFuture>::IntoFuture, HanErr>,
+    groups: TaskGroups,
+    telemetry_shutdown: TelemetryShutdownGuard,
+) -> Result<()>
+where
+    HanErr: std::error::Error + Send + Sync + 'static,
+{
+    shutdown
+        .named_group(
+            "main",
+            groups.main_tracker,
+            groups.main_token,
+            MAIN_SHUTDOWN_TIMEOUT,
+        )
+        .named_group(
+            "helping tasks",
+            groups.helping_tasks_tracker,
+            groups.helping_tasks_token,
+            HELPING_TASKS_SHUTDOWN_TIMEOUT,
+        )
+        .named_group(
+            "endpoints",
+            groups.endpoints_tracker,
+            groups.endpoints_token,
+            ENDPOINTS_SHUTDOWN_TIMEOUT,
+        )
+        .named_group(
+            "telemetry",
+            groups.telemetry_tracker,
+            groups.telemetry_token,
+            TELEMETRY_SHUTDOWN_TIMEOUT,
+        )
         .telemetry_guard(telemetry_shutdown.into_future())
         .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
         .wait()
@@ -3,7 +3,8 @@
 set -euo pipefail
 
 SB_ID="${1:-0}" # Default to sb_id=0
-JAIL="/srv/jailer/firecracker/$SB_ID/root"
+JAILER_NS="jailer-$SB_ID"
+JAIL="/srv/jailer/firecracker/$JAILER_NS/root"
 MOUNT=$(mktemp -d)
 LOG_FILE="var/log/cyclone.log"
 
@@ -5,7 +5,7 @@ set -euo pipefail
 SB_ID="${1:-0}" # Default to sb_id=0
 JAILER_BINARY="/usr/bin/jailer"
 JAILER_NS="jailer-$SB_ID"
-JAIL="/srv/jailer/firecracker/$SB_ID/root"
+JAIL="/srv/jailer/firecracker/$JAILER_NS/root"
 
 if ! test -f "$JAIL/firecracker.conf"; then
   echo "Files missing from $JAIL. Has the machine configuration been completed?"
@@ -14,7 +14,7 @@ else
   # TODO(johnrwatson): We don't use proper cgroup isolation, we probably want this in the future
   "${JAILER_BINARY}" \
     --cgroup-version 2 \
-    --id $SB_ID \
+    --id $JAILER_NS \
     --exec-file \
     /usr/bin/firecracker \
     --uid 10000$SB_ID \
@@ -3,15 +3,16 @@
 set -euo pipefail
 
 SB_ID="${1:-0}"
+JAILER_NS="jailer-$SB_ID"
 
 # Kill the firecracker process if it exists
-pkill -f "firecracker --id $SB_ID" || true
+pkill -f "firecracker --id $JAILER_NS" || true
 
 # Remove directories and files
-JAIL="/srv/jailer/firecracker/${SB_ID}/root"
+JAIL="/srv/jailer/firecracker/${JAILER_NS}/root"
 DISK="${JAIL}/rootfs.ext4"
-OVERLAY="rootfs-overlay-${SB_ID}"
-OVERLAY_FILE="${JAIL}/rootfs-overlay-${SB_ID}"
+OVERLAY="rootfs-overlay-${JAILER_NS}"
+OVERLAY_FILE="${JAIL}/rootfs-overlay-${JAILER_NS}"
 
 # Unmount disk if mounted
 while mountpoint -q "$DISK"; do
@@ -28,4 +29,4 @@ done
 if losetup -a | grep "$OVERLAY)" &> /dev/null; then
   losetup -d $(losetup -j "$OVERLAY_FILE" -O NAME | sed -n 2p)
 fi
-rm -rf "/srv/jailer/firecracker/$SB_ID"
+rm -rf "/srv/jailer/firecracker/$JAILER_NS"
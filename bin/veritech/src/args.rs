@@ -126,6 +126,11 @@ pub(crate) struct Args {
     #[arg(long)]
     pub(crate) cyclone_pool_size: Option<u32>,
 
+    /// Number of cyclone instances to keep ready when idle, preparing more up to the pool size
+    /// only on demand [default: the pool size]
+    #[arg(long)]
+    pub(crate) cyclone_pool_min_ready: Option<u32>,
+
     /// Cyclone create firecracker setup scripts
     #[arg(long)]
     pub(crate) cyclone_create_firecracker_setup_scripts: Option<bool>,
@@ -198,6 +203,9 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     if let Some(size) = args.cyclone_pool_size {
         config_map.set("cyclone.pool_size", size);
     }
+    if let Some(min_ready) = args.cyclone_pool_min_ready {
+        config_map.set("pool_min_ready", min_ready);
+    }
     if let Some(cyclone_create_firecracker_setup_scripts) =
         args.cyclone_create_firecracker_setup_scripts
     {
@@ -79,6 +79,8 @@ pub use migrate::{
 pub enum AuditDatabaseError {
     #[error("chrono parse error: {0}")]
     ChronoParse(#[from] chrono::ParseError),
+    #[error("invalid audit log cursor: {0}")]
+    InvalidCursor(String),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("pg pool error: {0}")]
@@ -118,6 +120,71 @@ pub struct AuditLogRow {
     pub authentication_method: AuthenticationMethod,
 }
 
+/// Narrows the rows returned by [`AuditLogRow::list_filtered`]. Every filter which is set must
+/// match for a row to be returned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogFilter {
+    /// Only rows of this [kind](AuditLogKind), as stored in [`AuditLogRow::kind`].
+    pub kind: Option<String>,
+    /// Only rows written in this change set.
+    pub change_set_id: Option<ChangeSetId>,
+    /// Only rows written by this user.
+    pub user_id: Option<UserPk>,
+    /// Only rows with this entity type.
+    pub entity_type: Option<String>,
+    /// Only rows written at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only rows written before this time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Marks where a page of [`AuditLogRow::list_filtered`] ended, so that the next page can carry on
+/// after it. It is written as an opaque string to be handed back by clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AuditLogCursor {
+    timestamp: DateTime<Utc>,
+    pk: i64,
+}
+
+impl std::fmt::Display for AuditLogCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.timestamp.timestamp_micros(), self.pk)
+    }
+}
+
+impl FromStr for AuditLogCursor {
+    type Err = AuditDatabaseError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || AuditDatabaseError::InvalidCursor(s.to_owned());
+        let (micros, pk) = s.split_once('.').ok_or_else(invalid)?;
+        let timestamp = micros
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let pk = pk.parse().map_err(|_| invalid())?;
+
+        Ok(Self { timestamp, pk })
+    }
+}
+
+impl TryFrom<String> for AuditLogCursor {
+    type Error = AuditDatabaseError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<AuditLogCursor> for String {
+    fn from(value: AuditLogCursor) -> Self {
+        value.to_string()
+    }
+}
+
 impl AuditLogRow {
     /// Inserts a new row into the audit logs table of the audit database.
     #[allow(clippy::too_many_arguments)]
@@ -294,6 +361,87 @@ impl AuditLogRow {
 
         Ok((logs_for_component, can_load_more))
     }
+
+    /// Lists rows of the audit logs table in the audit database across every change set in the
+    /// workspace, narrowed by the given [`AuditLogFilter`].
+    ///
+    /// Pages carry on after the given cursor, if any, and return the cursor for the next page if
+    /// there are more rows.
+    #[instrument(
+        name = "audit_log.database.list_filtered",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+        ),
+    )]
+    pub async fn list_filtered(
+        context: &AuditDatabaseContext,
+        workspace_id: WorkspacePk,
+        filter: &AuditLogFilter,
+        cursor: Option<AuditLogCursor>,
+        size: usize,
+        sort_ascending: bool,
+    ) -> Result<(Vec<Self>, Option<AuditLogCursor>)> {
+        let change_set_id = filter.change_set_id.map(|id| id.to_string());
+        let user_id = filter.user_id.map(|id| id.to_string());
+        let cursor_timestamp = cursor.map(|cursor| cursor.timestamp);
+        let cursor_pk = cursor.map(|cursor| cursor.pk);
+        // One more row than the page is fetched to find out whether there is a next page.
+        let limit = size as i64 + 1;
+        let client = context.pg_pool().get().await?;
+
+        // Every filter which is not set matches all rows.
+        const FILTERS: &str = "workspace_id = $1
+            AND ($2::text IS NULL OR kind = $2)
+            AND ($3::text IS NULL OR change_set_id = $3)
+            AND ($4::text IS NULL OR user_id = $4)
+            AND ($5::text IS NULL OR entity_type = $5)
+            AND ($6::timestamptz IS NULL OR timestamp >= $6)
+            AND ($7::timestamptz IS NULL OR timestamp < $7)";
+
+        // Rows are ordered by their pk as well as their timestamp, as timestamps may be shared.
+        let (order, after) = if sort_ascending {
+            ("ASC", ">")
+        } else {
+            ("DESC", "<")
+        };
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT * from audit_logs WHERE {FILTERS}
+                        AND ($8::timestamptz IS NULL OR (timestamp, pk) {after} ($8, $9::bigint))
+                        ORDER BY timestamp {order}, pk {order} LIMIT $10"
+                ),
+                &[
+                    &workspace_id,
+                    &filter.kind,
+                    &change_set_id,
+                    &user_id,
+                    &filter.entity_type,
+                    &filter.since,
+                    &filter.until,
+                    &cursor_timestamp,
+                    &cursor_pk,
+                    &limit,
+                ],
+            )
+            .await?;
+
+        let has_more = rows.len() > size;
+        let mut logs = Vec::new();
+        let mut last = None;
+        for row in rows.into_iter().take(size) {
+            last = Some(AuditLogCursor {
+                timestamp: row.try_get("timestamp")?,
+                pk: row.try_get("pk")?,
+            });
+            logs.push(Self::try_from(row)?);
+        }
+        let next_cursor = if has_more { last } else { None };
+
+        Ok((logs, next_cursor))
+    }
 }
 
 impl TryFrom<PgRow> for AuditLogRow {
@@ -58,7 +58,10 @@ pub mod secret;
 
 pub use change_set::ChangeSetTestHelpers;
 use dal::diagram::view::ViewId;
-pub use property_editor_test_view::PropEditorTestView;
+pub use property_editor_test_view::{
+    PropDiff,
+    PropEditorTestView,
+};
 use serde_json::Value;
 
 /// Generates a fake name.
@@ -9,6 +9,7 @@ use color_eyre::{
 use dal::{
     ChangeSet,
     ChangeSetId,
+    Component,
     ComponentId,
     DalContext,
     Func,
@@ -122,6 +123,41 @@ impl ChangeSetTestHelpers {
         ))
     }
 
+    /// Wait for the dependent values of a [`Component`](dal::Component) to be recomputed, checking
+    /// every 100ms until no attribute values of the component are left to update. Returns an error
+    /// if they are still being updated once `timeout` has passed.
+    ///
+    /// Values computed from other values, such as those read with
+    /// [`get_component_output_socket_value`](crate::helpers::get_component_output_socket_value),
+    /// are only up to date after this returns, so call it before asserting on them.
+    ///
+    /// The snapshot is reloaded on every check, discarding anything not yet committed, so commit
+    /// the changes to wait on first with [`DalContext::commit`], which does not wait for the
+    /// dependent values update it enqueues.
+    pub async fn wait_for_dependent_values(
+        ctx: &mut DalContext,
+        component_id: ComponentId,
+        timeout: Duration,
+    ) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            ctx.update_snapshot_to_visibility().await?;
+            if Component::pending_dependent_value_ids(ctx, component_id)
+                .await?
+                .is_empty()
+            {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(eyre!(
+                    "timeout waiting for dependent values of component {component_id} to update"
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
     /// Apply Changeset To base Approvals
     pub async fn apply_change_set_to_base_approvals(ctx: &mut DalContext) -> Result<()> {
         ChangeSet::prepare_for_apply(ctx).await?;
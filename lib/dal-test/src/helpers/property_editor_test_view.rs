@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{
+    BTreeSet,
+    HashMap,
+};
 
 use async_recursion::async_recursion;
 use color_eyre::{
@@ -31,6 +34,24 @@ use serde::{
 };
 use serde_json::Value;
 
+/// A difference between two [`PropEditorTestViews`](PropEditorTestView), found with
+/// [`PropEditorTestView::diff`]. Paths are the prop names (or array indices and map keys) from
+/// "root", joined with "/".
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropDiff {
+    /// Only the other view has a value at the path.
+    Added { path: String, value: Value },
+    /// The views have different values at the path.
+    Changed {
+        path: String,
+        before: Value,
+        after: Value,
+    },
+    /// Only this view has a value at the path.
+    Removed { path: String, value: Value },
+}
+
 #[allow(missing_docs)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PropEditorTestView {
@@ -63,6 +84,153 @@ impl PropEditorTestView {
         Ok(view.get("value").ok_or(eyre!("value not found"))?.clone())
     }
 
+    /// Walks this view and another, such as the views of a component and its copy, returning the
+    /// paths at which their values differ, were added or were removed. Added and removed values
+    /// hold everything beneath their path.
+    ///
+    /// Array elements are lined up by their contents before being compared, so that inserting or
+    /// removing an element is reported once, rather than as a change to every element after it.
+    /// Paths to an added or changed element use its index in `other`, while paths to a removed
+    /// element use its index in `self`.
+    pub fn diff(&self, other: &Self) -> Vec<PropDiff> {
+        let mut diffs = Vec::new();
+        Self::diff_into(self.prop.name.clone(), self, other, &mut diffs);
+        diffs
+    }
+
+    fn diff_into(path: String, before: &Self, after: &Self, diffs: &mut Vec<PropDiff>) {
+        match before.prop.kind {
+            PropertyEditorPropKind::Array => {
+                Self::diff_array_into(&path, &before.children(), &after.children(), diffs);
+            }
+            PropertyEditorPropKind::Map | PropertyEditorPropKind::Object => {
+                let before_children = before.children.as_ref();
+                let after_children = after.children.as_ref();
+                let keys: BTreeSet<&String> = before_children
+                    .into_iter()
+                    .chain(after_children)
+                    .flat_map(HashMap::keys)
+                    .collect();
+
+                for key in keys {
+                    let child_path = format!("{path}/{key}");
+                    match (
+                        before_children.and_then(|children| children.get(key)),
+                        after_children.and_then(|children| children.get(key)),
+                    ) {
+                        (Some(before), Some(after)) => {
+                            Self::diff_into(child_path, before, after, diffs)
+                        }
+                        (Some(before), None) => diffs.push(PropDiff::Removed {
+                            path: child_path,
+                            value: before.content(),
+                        }),
+                        (None, Some(after)) => diffs.push(PropDiff::Added {
+                            path: child_path,
+                            value: after.content(),
+                        }),
+                        (None, None) => {}
+                    }
+                }
+            }
+            _ => {
+                if before.value.value != after.value.value {
+                    diffs.push(PropDiff::Changed {
+                        path,
+                        before: before.value.value.clone(),
+                        after: after.value.value.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn diff_array_into(path: &str, before: &[&Self], after: &[&Self], diffs: &mut Vec<PropDiff>) {
+        let before_contents: Vec<Value> = before.iter().map(|view| view.content()).collect();
+        let after_contents: Vec<Value> = after.iter().map(|view| view.content()).collect();
+
+        // The length of the longest common subsequence of the elements from each index onwards
+        let mut common = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+        for i in (0..before.len()).rev() {
+            for j in (0..after.len()).rev() {
+                common[i][j] = if before_contents[i] == after_contents[j] {
+                    common[i + 1][j + 1] + 1
+                } else {
+                    common[i + 1][j].max(common[i][j + 1])
+                };
+            }
+        }
+
+        // Elements between two matching ones are compared in pairs, with any left over reported
+        // as removed or added
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        let mut flush = |removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+            for (&i, &j) in removed.iter().zip(added.iter()) {
+                Self::diff_into(format!("{path}/{j}"), before[i], after[j], diffs);
+            }
+            for &i in removed.iter().skip(added.len()) {
+                diffs.push(PropDiff::Removed {
+                    path: format!("{path}/{i}"),
+                    value: before_contents[i].clone(),
+                });
+            }
+            for &j in added.iter().skip(removed.len()) {
+                diffs.push(PropDiff::Added {
+                    path: format!("{path}/{j}"),
+                    value: after_contents[j].clone(),
+                });
+            }
+            removed.clear();
+            added.clear();
+        };
+
+        let (mut i, mut j) = (0, 0);
+        while i < before.len() || j < after.len() {
+            if i < before.len() && j < after.len() && before_contents[i] == after_contents[j] {
+                flush(&mut removed, &mut added);
+                i += 1;
+                j += 1;
+            } else if j == after.len() || (i < before.len() && common[i + 1][j] >= common[i][j + 1])
+            {
+                removed.push(i);
+                i += 1;
+            } else {
+                added.push(j);
+                j += 1;
+            }
+        }
+        flush(&mut removed, &mut added);
+    }
+
+    /// The children of an array, in index order.
+    fn children(&self) -> Vec<&Self> {
+        let Some(children) = &self.children else {
+            return Vec::new();
+        };
+
+        let mut indexed: Vec<(usize, &Self)> = children
+            .iter()
+            .filter_map(|(key, child)| key.parse().ok().map(|index| (index, child)))
+            .collect();
+        indexed.sort_by_key(|(index, _)| *index);
+        indexed.into_iter().map(|(_, child)| child).collect()
+    }
+
+    /// Everything at and beneath this view, with the values of children in place of the value of
+    /// their parent.
+    fn content(&self) -> Value {
+        match &self.children {
+            Some(children) => Value::Object(
+                children
+                    .iter()
+                    .map(|(key, child)| (key.clone(), child.content()))
+                    .collect(),
+            ),
+            None => self.value.value.clone(),
+        }
+    }
+
     /// Generates a [`PropEditorTestView`] for a given [`ComponentId`](Component).
     pub async fn for_component_id(
         ctx: &DalContext,
@@ -533,6 +533,10 @@ impl TestContext {
             cache_config: CacheConfig::default().disk_layer(false),
             object_storage_config: self.config.object_storage_config.clone(),
             persister_mode,
+            cache_updates_concurrency: None,
+            disk_percentages: Default::default(),
+            prewarm_concurrency: None,
+            read_only: false,
         };
 
         let (layer_db, layer_db_graceful_shutdown) = DalLayerDb::from_services(
@@ -809,6 +813,7 @@ pub async fn pinga_server(
         config.instance_id(),
         config.concurrency_limit(),
         config.max_deliver(),
+        config.work_queue_retention(),
         services_context,
         shutdown_token,
     )
@@ -945,6 +950,7 @@ pub async fn forklift_server(
             config.audit().insert_concurrency_limit,
         )),
         None,
+        config.billing_events_delivery().clone(),
         si_db_pool,
         layer_cache_pool,
         layered_event_client,
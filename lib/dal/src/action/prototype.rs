@@ -615,6 +615,7 @@ impl ActionPrototype {
             component_id,
             func_id,
             serde_json::json!({ "properties" : component_view }),
+            true,
         )
         .await?;
 
@@ -434,6 +434,7 @@ async fn update_attributes_inner(
                     if parent_prop_is_map_or_array(ctx, target_av_id).await? {
                         // If the parent is a map or array, remove the value
                         AttributeValue::remove(ctx, target_av_id).await?;
+                        Component::mark_modified(ctx, component_id).await?;
                     } else {
                         // Otherwise, use the default prototype (either by removing an existing
                         // component prototype to revert to the schema variant prototype, or by
@@ -589,6 +590,15 @@ pub enum ValueOrSourceSpec {
     RawValue(serde_json::Value),
 }
 
+impl ValueOrSourceSpec {
+    /// An explicit unset: `{ "$source": null }`.
+    pub fn unset() -> Self {
+        ValueOrSourceSpec::SourceSpec(SourceSpec {
+            source: MaybeSource::Null,
+        })
+    }
+}
+
 /// { $source: <source> }. Separated from ValueOrSourceSpec so we could use deny_unknown_fields
 #[derive(Serialize, Deserialize, Clone, Debug, derive_more::From)]
 #[serde(rename = "camelCase", deny_unknown_fields)]
@@ -613,6 +613,9 @@ impl AttributeValue {
         Self::set_value(ctx, attribute_value_id, value.clone()).await?;
         Self::populate_nested_values(ctx, attribute_value_id, value).await?;
 
+        let component_id = Self::component_id(ctx, attribute_value_id).await?;
+        Component::mark_modified(ctx, component_id).await?;
+
         ctx.add_dependent_values_and_enqueue(vec![attribute_value_id])
             .await?;
 
@@ -3,6 +3,8 @@
 use audit_database::{
     AuditDatabaseContext,
     AuditDatabaseError,
+    AuditLogCursor,
+    AuditLogFilter,
     AuditLogRow,
 };
 use audit_logs_stream::AuditLogsStreamError;
@@ -329,6 +331,36 @@ pub async fn list_for_component(
     .await?)
 }
 
+/// Lists audit logs across every change set in the workspace, narrowed by the given
+/// [`AuditLogFilter`]. Unlike [`list`], this is not limited to the change set of the context.
+///
+/// Returns a cursor for the next page, to be passed back in, if there are more audit logs.
+#[instrument(
+    name = "audit_logging.list_filtered",
+    level = "debug",
+    skip_all,
+    fields(size, sort_ascending)
+)]
+pub async fn list_filtered(
+    ctx: &DalContext,
+    audit_database_context: &AuditDatabaseContext,
+    filter: &AuditLogFilter,
+    cursor: Option<AuditLogCursor>,
+    size: usize,
+    sort_ascending: bool,
+) -> Result<(Vec<AuditLogRow>, Option<AuditLogCursor>)> {
+    let workspace_id = ctx.workspace_pk().map_err(Box::new)?;
+    Ok(AuditLogRow::list_filtered(
+        audit_database_context,
+        workspace_id,
+        filter,
+        cursor,
+        size,
+        sort_ascending,
+    )
+    .await?)
+}
+
 async fn prepare_accessor_query(ctx: &DalContext) -> Result<(WorkspacePk, Vec<ChangeSetId>)> {
     let workspace_id = ctx.workspace_pk().map_err(Box::new)?;
     let change_set_id = ctx.change_set_id();
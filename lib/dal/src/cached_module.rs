@@ -3,6 +3,7 @@ use std::{
         HashMap,
         HashSet,
     },
+    str::FromStr,
     sync::Arc,
     time::Duration,
 };
@@ -12,6 +13,7 @@ use chrono::{
     Utc,
 };
 use edda_client::EddaClient;
+use futures::StreamExt as _;
 use itertools::Itertools;
 use module_index_client::{
     ModuleDetailsResponse,
@@ -28,6 +30,10 @@ use si_data_pg::{
     PgRow,
 };
 use si_db::HistoryActor;
+use si_hash::{
+    Hash,
+    HashParseError,
+};
 pub use si_id::CachedModuleId;
 use si_id::UserPk;
 use si_pkg::{
@@ -38,7 +44,6 @@ use si_pkg::{
 };
 use telemetry::prelude::*;
 use thiserror::Error;
-use tokio::task::JoinSet;
 use ulid::Ulid;
 
 use crate::{
@@ -59,6 +64,8 @@ const PLACEHOLDER_OWNER_USER_ID: &str = "-";
 pub enum CachedModuleError {
     #[error("edda client error: {0}")]
     EddaClient(#[from] edda_client::ClientError),
+    #[error("hash parse error: {0}")]
+    HashParse(#[from] HashParseError),
     #[error("join error: {0}")]
     Join(#[from] tokio::task::JoinError),
     #[error("json error: {0}")]
@@ -193,8 +200,17 @@ const CACHED_MODULE_LIST_FIELDS: &str = "
     package_summary
 ";
 
-const BATCH_SIZE: usize = 10;
-const WAIT_BETWEEN_BATCHES: Duration = Duration::from_millis(100);
+/// The default number of modules fetched from the module index at the same time.
+pub const DEFAULT_FETCH_CONCURRENCY_LIMIT: usize = 10;
+
+/// The default number of attempts made to fetch each module from the module index.
+pub const DEFAULT_FETCH_MAX_ATTEMPTS: u32 = 5;
+
+/// How long to wait before the first retry of a failed fetch. The wait doubles for each retry.
+const FETCH_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How many newly cached modules are inserted between commits.
+const COMMIT_BATCH_SIZE: usize = 10;
 
 impl CachedModule {
     pub async fn si_pkg(&mut self, ctx: &DalContext) -> CachedModuleResult<SiPkg> {
@@ -257,10 +273,17 @@ impl CachedModule {
     }
 
     /// Calls out to the module index server to fetch the latest module set, and
-    /// updates the cache for any new builtin modules
+    /// Updates the cache for any new builtin modules.
+    ///
+    /// At most `concurrency_limit` modules are fetched from the module index at the same time, each
+    /// fetch being attempted up to `max_fetch_attempts` times, and `on_progress` is called with the
+    /// number of modules processed so far and the total as each one completes.
     pub async fn update_cached_modules(
         ctx: &DalContext,
         edda_client: EddaClient,
+        concurrency_limit: usize,
+        max_fetch_attempts: u32,
+        on_progress: impl FnMut(usize, usize) + Send,
     ) -> CachedModuleResult<Vec<CachedModule>> {
         let module_index_client = {
             let services_context = ctx.services_context();
@@ -285,8 +308,16 @@ impl CachedModule {
         let ctx_clone = ctx.clone();
         ctx_clone.commit_no_rebase().await?;
 
-        let new_modules =
-            Self::cache_modules(ctx, &modules, module_index_client, edda_client).await?;
+        let new_modules = Self::cache_modules(
+            ctx,
+            &modules,
+            module_index_client,
+            edda_client,
+            concurrency_limit,
+            max_fetch_attempts,
+            on_progress,
+        )
+        .await?;
 
         // Now check and fix up any missing package summaries
         Self::update_missing_package_summaries(ctx).await?;
@@ -299,47 +330,56 @@ impl CachedModule {
         modules: &HashMap<String, ModuleDetailsResponse>,
         module_index_client: ModuleIndexClient,
         edda_client: EddaClient,
+        concurrency_limit: usize,
+        max_fetch_attempts: u32,
+        mut on_progress: impl FnMut(usize, usize) + Send,
     ) -> CachedModuleResult<Vec<CachedModule>> {
         let hashes = modules.keys().map(ToOwned::to_owned).collect_vec();
         let uncached_hashes = CachedModule::find_missing_entries(ctx, hashes).await?;
 
-        let mut new_modules = vec![];
-        for hash_chunk in uncached_hashes.chunks(BATCH_SIZE) {
-            let ctx = ctx.clone();
-            let mut join_set = JoinSet::new();
-
-            for uncached_hash in hash_chunk {
-                let Some(module) = modules.get(uncached_hash).cloned() else {
-                    continue;
-                };
-
-                let module_index = module_index_client.clone();
-                join_set.spawn(async move {
-                    let module_id = module.id.to_owned();
-                    Ok::<(ModuleDetailsResponse, Arc<Vec<u8>>), CachedModuleError>((
-                        module,
-                        Arc::new(
-                            module_index
-                                .get_builtin(Ulid::from_string(&module_id).unwrap_or_default())
-                                .await?,
-                        ),
-                    ))
-                });
-            }
+        // Fetches run alongside each other, bounded so as not to overwhelm the module index,
+        // while the inserts happen one at a time as each fetch completes.
+        let uncached_modules = uncached_hashes
+            .iter()
+            .filter_map(|uncached_hash| modules.get(uncached_hash).cloned())
+            .collect_vec();
+        let total = uncached_modules.len();
+        let module_index_client = &module_index_client;
+        let mut fetches = futures::stream::iter(uncached_modules)
+            .map(|module| async move {
+                let module_bytes =
+                    Self::fetch_builtin(module_index_client, &module, max_fetch_attempts).await?;
+                Ok::<(ModuleDetailsResponse, Arc<Vec<u8>>), CachedModuleError>((
+                    module,
+                    Arc::new(module_bytes),
+                ))
+            })
+            .buffer_unordered(concurrency_limit.max(1));
 
-            while let Some(res) = join_set.join_next().await {
-                let (module, module_bytes) = res??;
-                if let Some(new_cached_module) =
-                    Self::insert(&ctx, &module, module_bytes, None).await?
-                {
-                    new_modules.push(new_cached_module);
-                }
+        let ctx = ctx.clone();
+        let mut processed = 0;
+        let mut new_modules = vec![];
+        let mut uncommitted = 0;
+        while let Some(res) = fetches.next().await {
+            let (module, module_bytes) = res?;
+            // A module which is already cached is skipped rather than failing the update
+            if let Some(new_cached_module) = Self::insert(&ctx, &module, module_bytes, None).await?
+            {
+                new_modules.push(new_cached_module);
+                uncommitted += 1;
             }
 
-            if !uncached_hashes.is_empty() {
+            if uncommitted >= COMMIT_BATCH_SIZE {
                 ctx.commit_no_rebase().await?;
+                uncommitted = 0;
             }
-            tokio::time::sleep(WAIT_BETWEEN_BATCHES).await;
+
+            processed += 1;
+            on_progress(processed, total);
+        }
+
+        if uncommitted > 0 {
+            ctx.commit_no_rebase().await?;
         }
 
         // Ask edda to rebuild the deployment MVs, which include the cached modules
@@ -348,6 +388,39 @@ impl CachedModule {
         Ok(new_modules)
     }
 
+    /// Fetches a builtin module from the module index, retrying with exponential backoff so that
+    /// a transient error does not fail the whole update. Only connection failures, timeouts and
+    /// server errors are retried, and the error is returned once `max_attempts` fetches have
+    /// failed. Any other error, such as the module not being found, is returned straight away.
+    async fn fetch_builtin(
+        module_index_client: &ModuleIndexClient,
+        module: &ModuleDetailsResponse,
+        max_attempts: u32,
+    ) -> CachedModuleResult<Vec<u8>> {
+        let module_id = Ulid::from_string(&module.id).unwrap_or_default();
+        let max_attempts = max_attempts.max(1);
+        let mut backoff = FETCH_INITIAL_BACKOFF;
+        let mut attempt = 1;
+
+        loop {
+            match module_index_client.get_builtin(module_id).await {
+                Ok(module_bytes) => return Ok(module_bytes),
+                Err(err) if err.is_transient() && attempt < max_attempts => {
+                    warn!(
+                        si.error.message = ?err,
+                        "failed to fetch builtin module {} (attempt {attempt} of {max_attempts}), \
+                        retrying in {backoff:?}",
+                        module.name,
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
     async fn remove_unused(
         ctx: &DalContext,
         module_details_by_hash: &HashMap<String, ModuleDetailsResponse>,
@@ -594,7 +667,12 @@ impl CachedModule {
         };
         let schema_id: SchemaId = schema_id.into();
 
-        let Some(package) = PackageData::load(&module_details.id, &pkg_bytes).await? else {
+        // Skip a package which is not the one the module index advertised, such as one corrupted in
+        // transit, so that it is fetched again by the next update rather than failing this one
+        let expected_hash = Hash::from_str(&module_details.latest_hash)?;
+        let Some(package) =
+            PackageData::load(&module_details.id, &pkg_bytes, Some(expected_hash)).await?
+        else {
             return Ok(None);
         };
 
@@ -706,7 +784,7 @@ impl CachedModule {
             return Ok(());
         };
         // This is more problematic because we'll end up retrying summaries all the time
-        let Some(summary) = PackageData::load(module_id, &Arc::new(pkg_bytes)).await? else {
+        let Some(summary) = PackageData::load(module_id, &Arc::new(pkg_bytes), None).await? else {
             return Ok(());
         };
         let query = "
@@ -755,9 +833,29 @@ impl PackageData {
     async fn load(
         module_id: &str, // just for debug messages so we can find the broken rows
         pkg_bytes: &Arc<Vec<u8>>,
+        expected_hash: Option<Hash>,
     ) -> CachedModuleResult<Option<Self>> {
         let pkg_bytes = pkg_bytes.clone();
-        let pkg = slow_rt::spawn(async move { SiPkg::load_from_bytes(&pkg_bytes) })?.await??;
+        let pkg = match slow_rt::spawn(async move {
+            match expected_hash {
+                Some(expected_hash) => SiPkg::load_from_bytes_verified(&pkg_bytes, expected_hash),
+                None => SiPkg::load_from_bytes(&pkg_bytes),
+            }
+        })?
+        .await?
+        {
+            Ok(pkg) => pkg,
+            Err(SiPkgError::HashMismatch { expected, actual }) => {
+                warn!(
+                    %expected,
+                    %actual,
+                    "builtin module {} does not have the advertised hash, skipping it",
+                    module_id
+                );
+                return Ok(None);
+            }
+            Err(err) => return Err(err.into()),
+        };
 
         let Some(schema) = pkg.schemas()?.into_iter().next() else {
             warn!("builtin module {} has no schema", module_id);
@@ -814,3 +912,125 @@ impl PackageData {
         &self.package_summary
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt,
+        },
+        net::TcpListener,
+        time::Instant,
+    };
+    use url::Url;
+
+    use super::*;
+
+    /// Serves the given statuses in order, repeating the last one, with the module bytes as the
+    /// body of successful responses. Returns the server's URL and the number of requests served.
+    async fn serve_statuses(statuses: Vec<u16>) -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("should bind listener");
+        let addr = listener.local_addr().expect("should have a local address");
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request).await;
+                let index = served.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[index.min(statuses.len() - 1)];
+                let body = if status == 200 { "module" } else { "" };
+                let response = format!(
+                    "HTTP/1.1 {status} \r\ncontent-length: {}\r\nconnection: close\r\n\r\n\
+                    {body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (
+            Url::parse(&format!("http://{addr}/")).expect("should parse url"),
+            requests,
+        )
+    }
+
+    fn module_details() -> ModuleDetailsResponse {
+        ModuleDetailsResponse {
+            id: Ulid::new().to_string(),
+            name: "builtin".to_string(),
+            description: None,
+            owner_user_id: "owner".to_string(),
+            owner_display_name: None,
+            metadata: serde_json::Value::Null,
+            latest_hash: "hash".to_string(),
+            latest_hash_created_at: Utc::now(),
+            created_at: Utc::now(),
+            schema_id: None,
+            past_hashes: None,
+            schema_variant_id: None,
+            schema_variant_version: None,
+            structural_hash: None,
+        }
+    }
+
+    async fn fetch_builtin(
+        statuses: Vec<u16>,
+        max_attempts: u32,
+    ) -> (CachedModuleResult<Vec<u8>>, usize, Duration) {
+        let (url, requests) = serve_statuses(statuses).await;
+        let client = ModuleIndexClient::unauthenticated_client(url).expect("should create client");
+
+        let start = Instant::now();
+        let result = CachedModule::fetch_builtin(&client, &module_details(), max_attempts).await;
+
+        (result, requests.load(Ordering::SeqCst), start.elapsed())
+    }
+
+    #[tokio::test]
+    async fn fetch_builtin_retries_transient_errors_with_backoff() {
+        tokio::time::pause();
+
+        let (result, requests, elapsed) = fetch_builtin(vec![503, 500, 200], 5).await;
+
+        assert_eq!(b"module".to_vec(), result.expect("fetch should succeed"));
+        assert_eq!(3, requests);
+        assert_eq!(FETCH_INITIAL_BACKOFF * 3, elapsed);
+    }
+
+    #[tokio::test]
+    async fn fetch_builtin_does_not_retry_not_found() {
+        tokio::time::pause();
+
+        let (result, requests, elapsed) = fetch_builtin(vec![404, 200], 5).await;
+
+        assert!(matches!(
+            result,
+            Err(CachedModuleError::ModuleIndexClient(ref err)) if !err.is_transient()
+        ));
+        assert_eq!(1, requests);
+        assert_eq!(Duration::ZERO, elapsed);
+    }
+
+    #[tokio::test]
+    async fn fetch_builtin_gives_up_after_max_attempts() {
+        tokio::time::pause();
+
+        let (result, requests, _) = fetch_builtin(vec![500], 3).await;
+
+        assert!(matches!(
+            result,
+            Err(CachedModuleError::ModuleIndexClient(ref err)) if err.is_transient()
+        ));
+        assert_eq!(3, requests);
+    }
+}
@@ -44,7 +44,9 @@ use tokio::time;
 
 use crate::{
     ChangeSetStatus,
+    Component,
     ComponentError,
+    Cursor,
     DalContext,
     Func,
     FuncError,
@@ -78,10 +80,15 @@ use crate::{
 };
 
 pub mod approval;
+pub mod conflict;
 pub mod event;
 pub mod status;
 pub mod view;
 
+/// The most updates sent to the rebaser at once when applying a [`ChangeSet`]. Larger applies are
+/// sent as several rebase batches, one after another, with progress reported as each completes.
+pub const APPLY_REBASE_BATCH_SIZE: usize = 1_000;
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ChangeSetError {
@@ -93,6 +100,8 @@ pub enum ChangeSetError {
     ChangeSetNotApprovedForApply(ChangeSetStatus),
     #[error("change set with id {0} not found")]
     ChangeSetNotFound(ChangeSetId),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
     #[error("default change set {0} has no workspace snapshot pointer")]
     DefaultChangeSetNoWorkspaceSnapshotPointer(ChangeSetId),
     #[error("dependent value root error: {0}")]
@@ -155,6 +164,12 @@ pub enum ChangeSetError {
     WsEvent(#[from] Box<WsEventError>),
 }
 
+impl From<ComponentError> for ChangeSetError {
+    fn from(value: ComponentError) -> Self {
+        Box::new(value).into()
+    }
+}
+
 impl From<LayerDbError> for ChangeSetError {
     fn from(value: LayerDbError) -> Self {
         Box::new(value).into()
@@ -250,6 +265,8 @@ pub enum ChangeSetApplyError {
     InvalidUserSystemInit,
     #[error("change set ({0}) does not have a base change set")]
     NoBaseChangeSet(ChangeSetId),
+    #[error("nothing to apply: change set {0} has no changes relative to its base")]
+    NothingToApply(ChangeSetId),
     #[error("si db error: {0}")]
     SiDb(#[from] Box<si_db::SiDbError>),
     #[error("transactions error: {0}")]
@@ -781,6 +798,23 @@ impl ChangeSet {
         Ok(result)
     }
 
+    /// Lists a page of at most `limit` active change sets, ordered by ID and beginning at
+    /// `cursor`. Returns the [`Cursor`] for the next page, if there is one.
+    pub async fn list_active_paginated(
+        ctx: &DalContext,
+        limit: usize,
+        cursor: Option<Cursor>,
+    ) -> ChangeSetResult<(Vec<Self>, Option<Cursor>)> {
+        let change_sets = Self::list_active(ctx).await?;
+
+        Ok(si_db::cursor::paginate(
+            change_sets,
+            limit,
+            cursor,
+            |change_set| change_set.id.into_raw_id(),
+        ))
+    }
+
     /// List all change sets that are applied.
     pub async fn list_all_applied(
         ctx: &DalContext,
@@ -890,6 +924,24 @@ impl ChangeSet {
     /// Also sends the relevant WSEvent
     #[instrument(level = "info", skip_all)]
     pub async fn apply_to_base_change_set(ctx: &mut DalContext) -> ChangeSetApplyResult<ChangeSet> {
+        Self::apply_to_base_change_set_checked(ctx, false).await
+    }
+
+    /// This is a copy of [Self::apply_to_base_change_set], but fails with
+    /// [`ChangeSetApplyError::NothingToApply`] rather than "applying" a [`ChangeSet`] with no net
+    /// changes relative to its base. The updates are only calculated once, for both the check and
+    /// the apply.
+    #[instrument(level = "info", skip_all)]
+    pub async fn apply_to_base_change_set_requiring_updates(
+        ctx: &mut DalContext,
+    ) -> ChangeSetApplyResult<ChangeSet> {
+        Self::apply_to_base_change_set_checked(ctx, true).await
+    }
+
+    async fn apply_to_base_change_set_checked(
+        ctx: &mut DalContext,
+        require_updates: bool,
+    ) -> ChangeSetApplyResult<ChangeSet> {
         let base_change_set_id = ctx.get_workspace_default_change_set_id().await?;
 
         if ctx.change_set_id() == base_change_set_id {
@@ -902,7 +954,7 @@ impl ChangeSet {
         ctx.update_visibility_and_snapshot_to_visibility(ctx.change_set_id())
             .await?;
         change_set_to_be_applied
-            .apply_to_base_change_set_inner(ctx)
+            .apply_to_base_change_set_inner(ctx, require_updates)
             .await?;
 
         // This is just to send the ws events
@@ -977,7 +1029,14 @@ impl ChangeSet {
     ///
     /// This function neither changes the visibility nor the snapshot after performing the
     /// aforementioned actions.
-    async fn apply_to_base_change_set_inner(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+    ///
+    /// If `require_updates` is set, a [`ChangeSet`] with nothing to apply is rejected rather than
+    /// applied.
+    async fn apply_to_base_change_set_inner(
+        &mut self,
+        ctx: &DalContext,
+        require_updates: bool,
+    ) -> ChangeSetApplyResult<()> {
         let workspace_id = self
             .workspace_id
             .ok_or(ChangeSetError::NoWorkspacePkSet(self.id))?;
@@ -985,35 +1044,115 @@ impl ChangeSet {
             .base_change_set_id
             .ok_or(ChangeSetError::NoBaseChangeSet(self.id))?;
 
+        let rebase_batches = self.write_rebase_batches(ctx).await?;
+        if rebase_batches.is_empty() && require_updates {
+            return Err(ChangeSetApplyError::NothingToApply(self.id));
+        }
+        let total_updates = rebase_batches
+            .iter()
+            .map(|(_, update_count)| update_count)
+            .sum();
+
+        let mut processed = 0;
+        if let Err(err) = self
+            .perform_apply(
+                ctx,
+                workspace_id,
+                base_change_set_id,
+                rebase_batches,
+                &mut processed,
+                total_updates,
+            )
+            .await
+        {
+            // The status change is not committed when the apply fails, so the final progress
+            // event is sent straight away for clients waiting on the apply to finish. It reports
+            // the updates of any batches the base had already performed.
+            let error = err.to_string();
+            let publish_result = async {
+                WsEvent::change_set_apply_failed(ctx, self.id, processed, total_updates, error)
+                    .await?
+                    .publish_immediately(ctx)
+                    .await
+            }
+            .await;
+            if let Err(publish_err) = publish_result {
+                warn!(
+                    si.error.message = ?publish_err,
+                    si.change_set.id = %self.id,
+                    "failed to publish change set apply failure",
+                );
+            }
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    /// Writes the updates which applying the [`ChangeSet`] would make to its base as rebase
+    /// batches of at most [`APPLY_REBASE_BATCH_SIZE`] updates, in the order they must be
+    /// performed, returning the address of each along with how many updates it holds.
+    async fn write_rebase_batches(
+        &self,
+        ctx: &DalContext,
+    ) -> ChangeSetResult<Vec<(RebaseBatchAddressKind, usize)>> {
         let snapshot_kind: WorkspaceSnapshotSelectorDiscriminants =
             ctx.workspace_snapshot().map_err(Box::new)?.into();
 
-        let maybe_rebase_batch_address = match snapshot_kind {
+        // Each rebase batch is written along with how many updates it holds, which is what apply
+        // progress is reported against
+        let mut rebase_batches = Vec::new();
+        match snapshot_kind {
             WorkspaceSnapshotSelectorDiscriminants::LegacySnapshot => {
                 if let Some(rebase_batch) =
                     self.detect_updates_that_will_be_applied_legacy(ctx).await?
                 {
-                    Some(RebaseBatchAddressKind::Legacy(
-                        ctx.write_legacy_rebase_batch(rebase_batch).await?,
-                    ))
-                } else {
-                    None
+                    for updates in rebase_batch.updates().chunks(APPLY_REBASE_BATCH_SIZE) {
+                        let address = ctx
+                            .write_legacy_rebase_batch(RebaseBatch::new(updates.to_vec()))
+                            .await?;
+                        rebase_batches
+                            .push((RebaseBatchAddressKind::Legacy(address), updates.len()));
+                    }
                 }
             }
             WorkspaceSnapshotSelectorDiscriminants::SplitSnapshot => {
                 if let Some(rebase_batch) =
                     self.detect_updates_that_will_be_applied_split(ctx).await?
                 {
-                    Some(RebaseBatchAddressKind::Split(
-                        ctx.write_split_snapshot_rebase_batch(rebase_batch).await?,
-                    ))
-                } else {
-                    None
+                    for updates in rebase_batch.chunks(APPLY_REBASE_BATCH_SIZE) {
+                        let address = ctx
+                            .write_split_snapshot_rebase_batch(updates.to_vec())
+                            .await?;
+                        rebase_batches
+                            .push((RebaseBatchAddressKind::Split(address), updates.len()));
+                    }
                 }
             }
-        };
+        }
+
+        Ok(rebase_batches)
+    }
+
+    async fn perform_apply(
+        &mut self,
+        ctx: &DalContext,
+        workspace_id: WorkspacePk,
+        base_change_set_id: ChangeSetId,
+        rebase_batches: Vec<(RebaseBatchAddressKind, usize)>,
+        processed: &mut usize,
+        total_updates: usize,
+    ) -> ChangeSetResult<()> {
+        if !rebase_batches.is_empty() {
+            WsEvent::change_set_apply_progress(ctx, self.id, 0, total_updates)
+                .await?
+                .publish_immediately(ctx)
+                .await?;
+        }
 
-        if let Some(rebase_batch_address) = maybe_rebase_batch_address {
+        // Each batch is performed on the base before the next is sent, so that they are applied in
+        // order
+        for (rebase_batch_address, update_count) in rebase_batches {
             let (request_id, reply_fut) = ctx
                 .run_rebase_from_change_set_with_reply(
                     workspace_id,
@@ -1036,10 +1175,22 @@ impl ChangeSet {
                 .map_err(|_elapsed| {
                     TransactionsError::RebaserReplyDeadlineElasped(timeout, request_id)
                 })??;
+
+            *processed += update_count;
+            WsEvent::change_set_apply_progress(ctx, self.id, *processed, total_updates)
+                .await?
+                .publish_immediately(ctx)
+                .await?;
         }
 
         self.update_status(ctx, ChangeSetStatus::Applied).await?;
         let user = Self::extract_userid_from_context(ctx).await;
+        // The final progress event goes out with the status change, so that it is only seen once
+        // the apply has really finished
+        WsEvent::change_set_apply_completed(ctx, self.id, total_updates)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
         WsEvent::change_set_applied(ctx, self.id, base_change_set_id, user)
             .await?
             .publish_on_commit(ctx)
@@ -1129,6 +1280,33 @@ impl ChangeSet {
         Ok(result)
     }
 
+    /// Returns up to `limit` [`Components`](Component) modified in the current change set, most
+    /// recently modified first.
+    ///
+    /// A component counts as modified if it was created, or had one of its attribute values
+    /// updated, after the change set was created.
+    pub async fn recently_modified_components(
+        ctx: &DalContext,
+        limit: usize,
+    ) -> ChangeSetResult<Vec<Component>> {
+        let change_set = Self::get_by_id(ctx, ctx.change_set_id()).await?;
+
+        let mut components: Vec<Component> = Component::list(ctx)
+            .await?
+            .into_iter()
+            .filter(|component| component.timestamp().updated_at >= change_set.created_at)
+            .collect();
+        components.sort_by(|a, b| {
+            b.timestamp()
+                .updated_at
+                .cmp(&a.timestamp().updated_at)
+                .then_with(|| b.id().cmp(&a.id()))
+        });
+        components.truncate(limit);
+
+        Ok(components)
+    }
+
     pub async fn rename_change_set(
         ctx: &DalContext,
         change_set_id: ChangeSetId,
@@ -0,0 +1,191 @@
+//! This module contains the ability to find other open [`ChangeSets`](ChangeSet) which touch the
+//! same nodes as a given [`ChangeSet`], so that future merge conflicts can be surfaced before the
+//! change set is applied.
+
+use std::collections::HashSet;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_events::workspace_snapshot::EntityKind;
+use si_id::EntityId;
+
+use crate::{
+    DalContext,
+    WorkspaceSnapshot,
+    change_set::{
+        ChangeSet,
+        ChangeSetId,
+        ChangeSetResult,
+        ChangeSetStatus,
+    },
+    workspace_snapshot::{
+        selector::WorkspaceSnapshotSelectorDiscriminants,
+        split_snapshot::SplitSnapshot,
+    },
+};
+
+/// The most other change sets [`ChangeSet::conflicts_with`] compares against, since each
+/// comparison loads and diffs a snapshot.
+pub const MAX_CONFLICT_CANDIDATES: i64 = 50;
+
+/// Another open [`ChangeSet`] which modifies some of the same nodes as the target change set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetConflict {
+    pub change_set_id: ChangeSetId,
+    pub name: String,
+    /// The nodes modified by both change sets.
+    pub entity_ids: Vec<EntityId>,
+}
+
+impl ChangeSet {
+    /// Lists the other open [`ChangeSets`](ChangeSet) with the same base as the given change set
+    /// which modify any of the same nodes, relative to that base.
+    ///
+    /// Only the [`MAX_CONFLICT_CANDIDATES`] most recently updated of those change sets are
+    /// compared against.
+    ///
+    /// Modified nodes are found by comparing merkle tree hashes against the base change set, so a
+    /// node counts as modified if it or anything beneath it changed. Structural nodes, such as the
+    /// root and category nodes, sit above everything and are ignored.
+    pub async fn conflicts_with(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> ChangeSetResult<Vec<ChangeSetConflict>> {
+        let change_set = Self::get_by_id(ctx, change_set_id).await?;
+        let Some(base_change_set_id) = change_set.base_change_set_id else {
+            return Ok(vec![]);
+        };
+
+        let modified = Self::modified_entity_ids(ctx, base_change_set_id, change_set_id).await?;
+        if modified.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut conflicts = Vec::new();
+        for other in Self::conflict_candidates(ctx, base_change_set_id, change_set_id).await? {
+            let other_modified =
+                Self::modified_entity_ids(ctx, base_change_set_id, other.id).await?;
+            let mut entity_ids: Vec<EntityId> =
+                modified.intersection(&other_modified).copied().collect();
+            if entity_ids.is_empty() {
+                continue;
+            }
+            entity_ids.sort();
+
+            conflicts.push(ChangeSetConflict {
+                change_set_id: other.id,
+                name: other.name,
+                entity_ids,
+            });
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Lists the most recently updated active change sets, other than the given one, with the
+    /// given base.
+    async fn conflict_candidates(
+        ctx: &DalContext,
+        base_change_set_id: ChangeSetId,
+        change_set_id: ChangeSetId,
+    ) -> ChangeSetResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM change_set_pointers
+                 WHERE workspace_id = $1
+                   AND base_change_set_id = $2
+                   AND id != $3
+                   AND status IN ($4, $5, $6, $7, $8)
+                 ORDER BY updated_at DESC
+                 LIMIT $9",
+                &[
+                    &ctx.tenancy().workspace_pk_opt(),
+                    &base_change_set_id,
+                    &change_set_id,
+                    &ChangeSetStatus::Open.to_string(),
+                    &ChangeSetStatus::NeedsApproval.to_string(),
+                    &ChangeSetStatus::NeedsAbandonApproval.to_string(),
+                    &ChangeSetStatus::Approved.to_string(),
+                    &ChangeSetStatus::Rejected.to_string(),
+                    &MAX_CONFLICT_CANDIDATES,
+                ],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    async fn modified_entity_ids(
+        ctx: &DalContext,
+        base_change_set_id: ChangeSetId,
+        change_set_id: ChangeSetId,
+    ) -> ChangeSetResult<HashSet<EntityId>> {
+        let snapshot_kind: WorkspaceSnapshotSelectorDiscriminants =
+            ctx.workspace_snapshot().map_err(Box::new)?.into();
+
+        let changes = match snapshot_kind {
+            WorkspaceSnapshotSelectorDiscriminants::LegacySnapshot => {
+                let base_snapshot = WorkspaceSnapshot::find_for_change_set(ctx, base_change_set_id)
+                    .await
+                    .map_err(Box::new)?;
+                let snapshot = WorkspaceSnapshot::find_for_change_set(ctx, change_set_id)
+                    .await
+                    .map_err(Box::new)?;
+                base_snapshot
+                    .detect_changes(&snapshot)
+                    .await
+                    .map_err(Box::new)?
+            }
+            WorkspaceSnapshotSelectorDiscriminants::SplitSnapshot => {
+                let base_snapshot = SplitSnapshot::find_for_change_set(ctx, base_change_set_id)
+                    .await
+                    .map_err(Box::new)?;
+                let snapshot = SplitSnapshot::find_for_change_set(ctx, change_set_id)
+                    .await
+                    .map_err(Box::new)?;
+                base_snapshot
+                    .detect_changes(&snapshot)
+                    .await
+                    .map_err(Box::new)?
+            }
+        };
+
+        Ok(changes
+            .into_iter()
+            .filter(|change| !is_structural(change.entity_kind))
+            .map(|change| change.entity_id)
+            .collect())
+    }
+}
+
+/// Whether nodes of this kind are ancestors of so much of the graph that nearly every change
+/// touches them.
+fn is_structural(entity_kind: EntityKind) -> bool {
+    matches!(
+        entity_kind,
+        EntityKind::CategoryAction
+            | EntityKind::CategoryComponent
+            | EntityKind::CategoryDefaultSubscriptionSources
+            | EntityKind::CategoryDependentValueRoots
+            | EntityKind::CategoryDeprecatedActionBatch
+            | EntityKind::CategoryDiagramObject
+            | EntityKind::CategoryFunc
+            | EntityKind::CategoryModule
+            | EntityKind::CategoryOverlay
+            | EntityKind::CategorySchema
+            | EntityKind::CategorySecret
+            | EntityKind::CategoryView
+            | EntityKind::DependentValueRoot
+            | EntityKind::FinishedDependentValueRoot
+            | EntityKind::OutOfGraph
+            | EntityKind::Root
+            | EntityKind::SubGraphRoot
+            | EntityKind::View
+    )
+}
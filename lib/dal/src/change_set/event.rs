@@ -95,6 +95,64 @@ impl WsEvent {
         .await
     }
 
+    /// Reports how many of the updates being applied to the base change set have been applied.
+    pub async fn change_set_apply_progress(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+        processed: usize,
+        total: usize,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ChangeSetApplyProgress(ChangeSetApplyProgressPayload {
+                change_set_id,
+                processed,
+                total,
+                completed: false,
+                error: None,
+            }),
+        )
+        .await
+    }
+
+    pub async fn change_set_apply_completed(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+        total: usize,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ChangeSetApplyProgress(ChangeSetApplyProgressPayload {
+                change_set_id,
+                processed: total,
+                total,
+                completed: true,
+                error: None,
+            }),
+        )
+        .await
+    }
+
+    pub async fn change_set_apply_failed(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+        processed: usize,
+        total: usize,
+        error: String,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ChangeSetApplyProgress(ChangeSetApplyProgressPayload {
+                change_set_id,
+                processed,
+                total,
+                completed: true,
+                error: Some(error),
+            }),
+        )
+        .await
+    }
+
     pub async fn change_set_canceled(
         ctx: &DalContext,
         change_set_id: ChangeSetId,
@@ -139,6 +197,19 @@ pub struct ChangeSetAppliedPayload {
     user_pk: Option<UserPk>,
 }
 
+/// How far along applying a change set is, counted in updates to its base change set. The last
+/// event for an apply has `completed` set, after which no more are sent, along with the `error` if
+/// the apply failed.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetApplyProgressPayload {
+    change_set_id: ChangeSetId,
+    processed: usize,
+    total: usize,
+    completed: bool,
+    error: Option<String>,
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ChangeSetRenamePayload {
@@ -3,6 +3,8 @@
 
 use std::{
     collections::{
+        BTreeMap,
+        BTreeSet,
         HashMap,
         HashSet,
         VecDeque,
@@ -137,7 +139,7 @@ use crate::{
     implement_add_edge_to,
     layer_db_types::{
         ComponentContent,
-        ComponentContentV2,
+        ComponentContentV3,
     },
     module::{
         Module,
@@ -151,9 +153,12 @@ use crate::{
         QualificationError,
         QualificationSummaryError,
     },
-    schema::variant::{
-        SchemaVariantError,
-        root_prop::component_type::ComponentType,
+    schema::{
+        leaf::LeafPrototypeError,
+        variant::{
+            SchemaVariantError,
+            root_prop::component_type::ComponentType,
+        },
     },
     socket::{
         input::InputSocketError,
@@ -180,16 +185,23 @@ use crate::{
 };
 
 pub mod code;
+pub mod connection;
 pub mod debug;
 pub mod delete;
 pub mod diff;
+pub mod history;
 pub mod new;
+pub mod pending_dependent_values;
+pub mod pin;
 pub mod properties;
 pub mod qualification;
 pub mod resource;
 pub mod socket;
 pub mod subscription_graph;
 pub mod suggestion;
+pub mod validation;
+pub mod values_diff;
+pub mod values_snapshot;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -212,6 +224,12 @@ pub enum ComponentError {
     AttributeValueView(WorkspacePk, ChangeSetId, ComponentId),
     #[error("cannot clone attributes from a component with a different schema variant id")]
     CannotCloneFromDifferentVariants,
+    #[error("cannot pin component {0} to unlocked schema variant {1}")]
+    CannotPinToUnlockedVariant(ComponentId, SchemaVariantId),
+    #[error(
+        "cannot restore values captured on schema variant {1} to component {0} on schema variant {2}"
+    )]
+    CannotRestoreValuesFromDifferentVariant(ComponentId, SchemaVariantId, SchemaVariantId),
     #[error("change set error: {0}")]
     ChangeSet(#[from] ChangeSetError),
     #[error("code view error: {0}")]
@@ -258,6 +276,8 @@ pub enum ComponentError {
     InputSocketTooManyAttributeValues(InputSocketId),
     #[error("layer db error: {0}")]
     LayerDb(#[from] si_layer_cache::LayerDbError),
+    #[error("leaf prototype error: {0}")]
+    LeafPrototype(#[from] Box<LeafPrototypeError>),
     #[error("component {0} missing attribute value for code")]
     MissingCodeValue(ComponentId),
     #[error("missing controlling func data for parent attribute value id: {0}")]
@@ -296,6 +316,8 @@ pub enum ComponentError {
     ParseFloat(#[from] ParseFloatError),
     #[error("parse int error: {0}")]
     ParseInt(#[from] ParseIntError),
+    #[error("cannot pin component {0} to schema variant {1} of a different schema")]
+    PinToVariantOfDifferentSchema(ComponentId, SchemaVariantId),
     #[error("prop error: {0}")]
     Prop(#[from] Box<PropError>),
     #[error("found prop id ({0}) that is not a prop")]
@@ -312,6 +334,8 @@ pub enum ComponentError {
     ResourceAttributeValueNotFound(ComponentId),
     #[error("root attribute value not found for component: {0}")]
     RootAttributeValueNotFound(ComponentId),
+    #[error("schema \"{0}\" is not in the schema allowlist for workspace {1}")]
+    SchemaNotAllowed(String, WorkspacePk),
     #[error("schema variant error: {0}")]
     SchemaVariant(#[from] Box<SchemaVariantError>),
     #[error("schema variant not found for component: {0}")]
@@ -418,6 +442,12 @@ impl From<InputSocketError> for ComponentError {
     }
 }
 
+impl From<LeafPrototypeError> for ComponentError {
+    fn from(value: LeafPrototypeError) -> Self {
+        Box::new(value).into()
+    }
+}
+
 impl From<ModuleError> for ComponentError {
     fn from(value: ModuleError) -> Self {
         Box::new(value).into()
@@ -482,12 +512,14 @@ pub struct Component {
     #[serde(flatten)]
     timestamp: Timestamp,
     to_delete: bool,
+    pinned_schema_variant_id: Option<SchemaVariantId>,
 }
 
-impl From<Component> for ComponentContentV2 {
+impl From<Component> for ComponentContentV3 {
     fn from(value: Component) -> Self {
         Self {
             timestamp: value.timestamp,
+            pinned_schema_variant_id: value.pinned_schema_variant_id,
         }
     }
 }
@@ -500,11 +532,12 @@ pub struct ControllingFuncData {
 }
 
 impl Component {
-    pub fn assemble(node_weight: &ComponentNodeWeight, content: ComponentContentV2) -> Self {
+    pub fn assemble(node_weight: &ComponentNodeWeight, content: ComponentContentV3) -> Self {
         Self {
             id: node_weight.id().into(),
             timestamp: content.timestamp,
             to_delete: node_weight.to_delete(),
+            pinned_schema_variant_id: content.pinned_schema_variant_id,
         }
     }
 
@@ -520,6 +553,12 @@ impl Component {
         self.to_delete
     }
 
+    /// The [`SchemaVariantId`] this [`Component`] is pinned to, if any. See
+    /// [`Component::pin_to_variant`].
+    pub fn pinned_schema_variant_id(&self) -> Option<SchemaVariantId> {
+        self.pinned_schema_variant_id
+    }
+
     pub async fn change_status(&self, ctx: &DalContext) -> ComponentResult<ChangeStatus> {
         let status = if self.exists_on_head(ctx).await? {
             if self.to_delete() {
@@ -1186,7 +1225,7 @@ impl Component {
     async fn try_get_node_weight_and_content(
         ctx: &DalContext,
         component_id: ComponentId,
-    ) -> ComponentResult<Option<(ComponentNodeWeight, ComponentContentV2)>> {
+    ) -> ComponentResult<Option<(ComponentNodeWeight, ComponentContentV3)>> {
         if let Some((component_node_weight, content_hash)) =
             Self::try_get_node_weight_and_content_hash(ctx, component_id).await?
         {
@@ -1208,7 +1247,7 @@ impl Component {
     async fn get_node_weight_and_content(
         ctx: &DalContext,
         component_id: ComponentId,
-    ) -> ComponentResult<(ComponentNodeWeight, ComponentContentV2)> {
+    ) -> ComponentResult<(ComponentNodeWeight, ComponentContentV3)> {
         Self::try_get_node_weight_and_content(ctx, component_id)
             .await?
             .ok_or(ComponentError::NotFound(component_id))
@@ -1311,6 +1350,23 @@ impl Component {
         Ok(components)
     }
 
+    /// List all [`Components`](Component) using the provided [`SchemaVariantId`], as seen from
+    /// the change set of the provided [`DalContext`].
+    pub async fn list_for_schema_variant(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> ComponentResult<Vec<Self>> {
+        let mut component_ids = SchemaVariant::list_component_ids(ctx, schema_variant_id).await?;
+        component_ids.sort();
+
+        let mut components = Vec::with_capacity(component_ids.len());
+        for component_id in component_ids {
+            components.push(Self::get_by_id(ctx, component_id).await?);
+        }
+
+        Ok(components)
+    }
+
     pub async fn list_to_be_deleted(ctx: &DalContext) -> ComponentResult<Vec<ComponentId>> {
         let mut to_be_deleted = vec![];
         let components = Self::list(ctx).await?;
@@ -1958,6 +2014,74 @@ impl Component {
         }
     }
 
+    /// Records that the [`Component`] was modified just now by bumping its `updated_at`
+    /// timestamp.
+    pub(crate) async fn mark_modified(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<()> {
+        Self::get_by_id(ctx, component_id)
+            .await?
+            .modify(ctx, |component| {
+                component.timestamp.updated_at = chrono::Utc::now();
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Lists the [`AttributeValues`](AttributeValue) of the [`Component`] which are waiting to be
+    /// recomputed by the change set's pending dependent values update.
+    ///
+    /// This walks the dependency graph from every pending dependent value root, so it includes
+    /// values which only depend, directly or through other components, on a value that changed.
+    /// The walk covers every component at once, and its result is cached until the change set's
+    /// snapshot or roots change.
+    pub async fn pending_dependent_value_ids(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<AttributeValueId>> {
+        let cache = ctx.pending_dependent_values();
+        let change_set_id = ctx.change_set_id();
+
+        let roots = DependentValueRoot::get_dependent_value_roots(ctx).await?;
+        if roots.is_empty() {
+            cache.remove(change_set_id);
+            return Ok(Vec::new());
+        }
+
+        // The status is polled, so the graph is only built once for every component of the
+        // change set, until its snapshot or roots change
+        let snapshot_address = ctx.workspace_snapshot()?.id().await;
+        let pending = match cache.get(change_set_id, snapshot_address, &roots) {
+            Some(pending) => pending,
+            None => {
+                let graph = DependentValueGraph::new(ctx, roots.clone()).await?;
+                let mut pending: BTreeMap<ComponentId, BTreeSet<AttributeValueId>> =
+                    BTreeMap::new();
+                for value in graph.all_value_ids() {
+                    if let Some(value_component_id) = graph.cached_component_id_for_value(value) {
+                        pending
+                            .entry(value_component_id)
+                            .or_default()
+                            .insert(value.attribute_value_id());
+                    }
+                }
+                cache.insert(
+                    change_set_id,
+                    snapshot_address,
+                    roots,
+                    pending
+                        .into_iter()
+                        .map(|(component_id, values)| (component_id, values.into_iter().collect()))
+                        .collect(),
+                )
+            }
+        };
+
+        Ok(pending.get(&component_id).cloned().unwrap_or_default())
+    }
+
     async fn modify<L>(self, ctx: &DalContext, lambda: L) -> ComponentResult<Self>
     where
         L: FnOnce(&mut Self) -> ComponentResult<()>,
@@ -1965,7 +2089,7 @@ impl Component {
         let original_component = self.clone();
         let mut component = self;
 
-        let before = ComponentContentV2::from(component.clone());
+        let before = ComponentContentV3::from(component.clone());
         lambda(&mut component)?;
 
         // The `to_delete` lives on the node itself, not in the content, so we need to be a little
@@ -1983,10 +2107,10 @@ impl Component {
                 .await?;
         }
 
-        let updated = ComponentContentV2::from(component.clone());
+        let updated = ComponentContentV3::from(component.clone());
         if updated != before {
             let (hash, _) = ctx.layer_db().cas().write(
-                Arc::new(ComponentContent::V2(updated.clone()).into()),
+                Arc::new(ComponentContent::from(updated.clone()).into()),
                 None,
                 ctx.events_tenancy(),
                 ctx.events_actor(),
@@ -2186,6 +2310,36 @@ impl Component {
         Ok(None)
     }
 
+    /// Returns the [`ActionKinds`](ActionKind) that can currently be run for the given
+    /// [`Component`]. A kind is available if the [`SchemaVariant`] (or its [`Schema`]) has a
+    /// prototype for it and its precondition is met: [`ActionKind::Create`] requires that there
+    /// be no resource yet, while [`ActionKind::Destroy`], [`ActionKind::Refresh`] and
+    /// [`ActionKind::Update`] require an existing resource. [`ActionKind::Manual`] is always
+    /// available.
+    pub async fn available_actions(
+        ctx: &DalContext,
+        id: ComponentId,
+    ) -> ComponentResult<Vec<ActionKind>> {
+        let schema_variant_id = Self::schema_variant_id(ctx, id).await?;
+        let has_resource = Self::resource_by_id(ctx, id).await?.is_some();
+
+        let mut available = Vec::new();
+        for prototype in
+            ActionPrototype::list_for_schema_and_variant_id(ctx, schema_variant_id).await?
+        {
+            let preconditions_met = match prototype.kind {
+                ActionKind::Create => !has_resource,
+                ActionKind::Destroy | ActionKind::Refresh | ActionKind::Update => has_resource,
+                ActionKind::Manual => true,
+            };
+            if preconditions_met && !available.contains(&prototype.kind) {
+                available.push(prototype.kind);
+            }
+        }
+
+        Ok(available)
+    }
+
     pub async fn duplicate_without_connections(
         &self,
         ctx: &DalContext,
@@ -2693,6 +2847,14 @@ impl Component {
         ctx: &DalContext,
         component_id: ComponentId,
     ) -> ComponentResult<bool> {
+        if Self::get_by_id(ctx, component_id)
+            .await?
+            .pinned_schema_variant_id
+            .is_some()
+        {
+            return Ok(false);
+        }
+
         let schema_variant = Component::schema_variant_for_component_id(ctx, component_id).await?;
 
         let schema_id = Component::schema_id_for_component_id(ctx, component_id).await?;
@@ -2725,7 +2887,14 @@ impl Component {
     }
 
     /// Is there a newer version of the schema variant that this component is using?
+    ///
+    /// Components [pinned](Component::pin_to_variant) to their schema variant can never be
+    /// upgraded.
     pub async fn can_be_upgraded(&self, ctx: &DalContext) -> ComponentResult<bool> {
+        if self.pinned_schema_variant_id.is_some() {
+            return Ok(false);
+        }
+
         let schema_variant = self.schema_variant(ctx).await?;
         let schema = self.schema(ctx).await?;
         let default_schema_variant_id =
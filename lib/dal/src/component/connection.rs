@@ -0,0 +1,147 @@
+//! This module contains the ability to find broken connections into a [`Component`], so they can
+//! be flagged before they surface as failures at execution time.
+//!
+//! Connections between components are subscriptions from an attribute value on one component to a
+//! path on another. A connection is broken if its path no longer matches the schema of the
+//! component it points at, or if the value at that path cannot be assigned to the subscriber.
+
+use std::collections::VecDeque;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::ComponentResult;
+use crate::{
+    AttributeValue,
+    AttributeValueId,
+    Component,
+    ComponentId,
+    DalContext,
+    Prop,
+    PropKind,
+    attribute::value::subscription::ValueSubscription,
+};
+
+/// A single broken connection into a [`Component`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ConnectionIssue {
+    /// The subscribed-to path does not exist in the schema of the source component.
+    #[serde(rename_all = "camelCase")]
+    InvalidPath {
+        attribute_value_id: AttributeValueId,
+        path: Option<String>,
+        source_component_id: ComponentId,
+        source_path: String,
+        message: String,
+    },
+    /// The subscribed-to value is of a type which cannot be assigned to the subscriber.
+    #[serde(rename_all = "camelCase")]
+    TypeMismatch {
+        attribute_value_id: AttributeValueId,
+        path: Option<String>,
+        prop_kind: PropKind,
+        source_component_id: ComponentId,
+        source_path: String,
+        source_prop_kind: PropKind,
+    },
+}
+
+impl ConnectionIssue {
+    /// The subscriber [`AttributeValue`] the broken connection flows into.
+    pub fn attribute_value_id(&self) -> AttributeValueId {
+        match self {
+            Self::InvalidPath {
+                attribute_value_id, ..
+            }
+            | Self::TypeMismatch {
+                attribute_value_id, ..
+            } => *attribute_value_id,
+        }
+    }
+
+    /// The [`Component`] the broken connection comes from.
+    pub fn source_component_id(&self) -> ComponentId {
+        match self {
+            Self::InvalidPath {
+                source_component_id,
+                ..
+            }
+            | Self::TypeMismatch {
+                source_component_id,
+                ..
+            } => *source_component_id,
+        }
+    }
+}
+
+impl Component {
+    /// Lists every incoming connection to the [`Component`] which is incompatible with the
+    /// attribute it is connected to.
+    ///
+    /// Connections are checked when they are made, so these only arise when a connection was made
+    /// without checks or the schema on either side has changed since.
+    pub async fn connection_issues(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<ConnectionIssue>> {
+        let mut issues = Vec::new();
+
+        let root_av_id = Self::root_attribute_value_id(ctx, component_id).await?;
+        let mut work_queue = VecDeque::from([root_av_id]);
+        while let Some(av_id) = work_queue.pop_front() {
+            work_queue.extend(AttributeValue::get_child_av_ids_in_order(ctx, av_id).await?);
+
+            let Some(subscriptions) = AttributeValue::subscriptions(ctx, av_id).await? else {
+                continue;
+            };
+            for subscription in subscriptions {
+                if let Some(issue) = Self::connection_issue(ctx, av_id, &subscription).await? {
+                    issues.push(issue);
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+
+    async fn connection_issue(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        subscription: &ValueSubscription,
+    ) -> ComponentResult<Option<ConnectionIssue>> {
+        let source_component_id =
+            AttributeValue::component_id(ctx, subscription.attribute_value_id).await?;
+        let source_path = subscription.path.to_string();
+
+        let source_prop_id = match subscription.validate(ctx).await {
+            Ok(source_prop_id) => source_prop_id,
+            Err(err) => {
+                return Ok(Some(ConnectionIssue::InvalidPath {
+                    attribute_value_id,
+                    path: AttributeValue::get_path_for_id(ctx, attribute_value_id).await?,
+                    source_component_id,
+                    source_path,
+                    message: err.to_string(),
+                }));
+            }
+        };
+
+        let source_prop_kind = Prop::kind(ctx, source_prop_id).await?;
+        let prop_kind = AttributeValue::prop_kind(ctx, attribute_value_id).await?;
+        if source_prop_kind.js_compatible_with(prop_kind) {
+            return Ok(None);
+        }
+
+        Ok(Some(ConnectionIssue::TypeMismatch {
+            attribute_value_id,
+            path: AttributeValue::get_path_for_id(ctx, attribute_value_id).await?,
+            prop_kind,
+            source_component_id,
+            source_path,
+            source_prop_kind,
+        }))
+    }
+}
@@ -0,0 +1,36 @@
+//! This module contains the ability to read a [`Component`] as it is in another
+//! [`ChangeSet`](crate::ChangeSet), such as one which has already been applied, without changing
+//! the [`DalContext`] being used.
+
+use super::ComponentResult;
+use crate::{
+    ChangeSet,
+    ChangeSetId,
+    Component,
+    ComponentId,
+    DalContext,
+};
+
+impl Component {
+    /// Returns the view of the [`Component`], as [`Component::view_by_id`] does, from the snapshot
+    /// of the given [`ChangeSet`] in the current workspace, rather than from the current one.
+    ///
+    /// Change sets keep their snapshot after they are applied or abandoned, so this can be used to
+    /// see what a component looked like at an earlier point. Returns `None` if the component does
+    /// not exist in that change set.
+    pub async fn get_at_change_set(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        change_set_id: ChangeSetId,
+    ) -> ComponentResult<Option<serde_json::Value>> {
+        // Ensures the change set belongs to the current workspace before reading its snapshot
+        let change_set = ChangeSet::get_by_id(ctx, change_set_id).await?;
+
+        let mut change_set_ctx = ctx.clone();
+        change_set_ctx
+            .update_visibility_and_snapshot_to_visibility(change_set.id)
+            .await?;
+
+        Self::view_by_id(&change_set_ctx, component_id).await
+    }
+}
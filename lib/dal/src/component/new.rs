@@ -31,6 +31,7 @@ use crate::{
     Prop,
     PropKind,
     SchemaVariant,
+    Workspace,
     action::{
         Action,
         prototype::{
@@ -47,7 +48,7 @@ use crate::{
     diagram::geometry::Geometry,
     layer_db_types::{
         ComponentContent,
-        ComponentContentV2,
+        ComponentContentV3,
     },
     validation::ValidationOutput,
     workspace_snapshot::{
@@ -113,6 +114,8 @@ impl Component {
                 .await?
                 .name()
                 .to_owned();
+        // Every way of creating a component, such as upgrading or pasting one, comes through here
+        Self::ensure_schema_allowed(ctx, &component_schema_name).await?;
 
         let mut component_av_graph = DependencyGraph::new();
 
@@ -383,12 +386,13 @@ impl Component {
         schema_variant_id: SchemaVariantId,
         view_id: ViewId,
     ) -> ComponentResult<Self> {
-        let content = ComponentContentV2 {
+        let content = ComponentContentV3 {
             timestamp: Timestamp::now(),
+            pinned_schema_variant_id: None,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(ComponentContent::V2(content.clone()).into()),
+            Arc::new(ComponentContent::from(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -413,4 +417,24 @@ impl Component {
 
         Ok(component)
     }
+
+    /// Ensures the named schema is allowed to be instantiated in the current workspace, according
+    /// to the workspace's schema allowlist.
+    async fn ensure_schema_allowed(ctx: &DalContext, schema_name: &str) -> ComponentResult<()> {
+        let Some(workspace_pk) = ctx.tenancy().workspace_pk_opt() else {
+            return Ok(());
+        };
+        let Some(workspace) = Workspace::get_by_pk_opt(ctx, workspace_pk).await? else {
+            return Ok(());
+        };
+
+        if !workspace.is_schema_allowed(schema_name) {
+            return Err(ComponentError::SchemaNotAllowed(
+                schema_name.to_owned(),
+                workspace_pk,
+            ));
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,82 @@
+//! A cache of the values of each component which are waiting on a change set's pending dependent
+//! values update, so that polling for them does not build a [`DependentValueGraph`] every time.
+//!
+//! [`DependentValueGraph`]: crate::attribute::value::dependent_value_graph::DependentValueGraph
+
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
+
+use dashmap::DashMap;
+use si_events::WorkspaceSnapshotAddress;
+use si_id::{
+    AttributeValueId,
+    ChangeSetId,
+    ComponentId,
+};
+
+use crate::workspace_snapshot::dependent_value_root::DependentValueRoot;
+
+/// The pending values of each component, by component.
+pub type PendingDependentValues = BTreeMap<ComponentId, Vec<AttributeValueId>>;
+
+#[derive(Debug)]
+struct CachedPendingDependentValues {
+    snapshot_address: WorkspaceSnapshotAddress,
+    roots: Vec<DependentValueRoot>,
+    pending: Arc<PendingDependentValues>,
+}
+
+/// Holds the pending values last computed for each change set, along with the snapshot and
+/// dependent value roots they were computed from.
+///
+/// Every change which affects which values are pending also changes the roots, so the values are
+/// only reused while both the snapshot and its roots are unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct PendingDependentValuesCache {
+    by_change_set: Arc<DashMap<ChangeSetId, CachedPendingDependentValues>>,
+}
+
+impl PendingDependentValuesCache {
+    /// Returns the pending values computed for the change set, if they were computed from the
+    /// same snapshot and roots.
+    pub fn get(
+        &self,
+        change_set_id: ChangeSetId,
+        snapshot_address: WorkspaceSnapshotAddress,
+        roots: &[DependentValueRoot],
+    ) -> Option<Arc<PendingDependentValues>> {
+        self.by_change_set
+            .get(&change_set_id)
+            .filter(|cached| {
+                cached.snapshot_address == snapshot_address && cached.roots.as_slice() == roots
+            })
+            .map(|cached| cached.pending.clone())
+    }
+
+    /// Records the pending values computed for the change set, replacing any computed before.
+    pub fn insert(
+        &self,
+        change_set_id: ChangeSetId,
+        snapshot_address: WorkspaceSnapshotAddress,
+        roots: Vec<DependentValueRoot>,
+        pending: PendingDependentValues,
+    ) -> Arc<PendingDependentValues> {
+        let pending = Arc::new(pending);
+        self.by_change_set.insert(
+            change_set_id,
+            CachedPendingDependentValues {
+                snapshot_address,
+                roots,
+                pending: pending.clone(),
+            },
+        );
+        pending
+    }
+
+    /// Forgets the pending values of the change set, such as once none are pending.
+    pub fn remove(&self, change_set_id: ChangeSetId) {
+        self.by_change_set.remove(&change_set_id);
+    }
+}
@@ -0,0 +1,144 @@
+//! This module contains the ability to pin a [`Component`] to a specific
+//! [`SchemaVariant`](crate::SchemaVariant), excluding it from upgrades until it is unpinned.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    ComponentError,
+    ComponentResult,
+};
+use crate::{
+    Component,
+    ComponentId,
+    DalContext,
+    SchemaVariant,
+    SchemaVariantId,
+};
+
+/// Whether or not a [`Component`] would be upgraded, and to which
+/// [`SchemaVariant`](crate::SchemaVariant).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ComponentUpgradeStatus {
+    /// The component is pinned to its schema variant and will not be upgraded.
+    #[serde(rename_all = "camelCase")]
+    Pinned { schema_variant_id: SchemaVariantId },
+    /// The component can be upgraded to a newer schema variant.
+    #[serde(rename_all = "camelCase")]
+    Upgradable {
+        from_schema_variant_id: SchemaVariantId,
+        to_schema_variant_id: SchemaVariantId,
+    },
+    /// The component is already on the newest schema variant.
+    UpToDate,
+}
+
+impl Component {
+    /// Pins the [`Component`] to the given locked [`SchemaVariant`](crate::SchemaVariant),
+    /// upgrading (or downgrading) it to that variant first if needed.
+    ///
+    /// Pinned components report that they cannot be upgraded and are skipped by
+    /// [`Component::upgrade_many`].
+    ///
+    /// Pinned components are stored as
+    /// [`ComponentContentV3`](crate::layer_db_types::ComponentContentV3), which older binaries
+    /// cannot read, so nothing should be pinned until a rolling deploy of this version has
+    /// finished.
+    pub async fn pin_to_variant(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        schema_variant_id: SchemaVariantId,
+    ) -> ComponentResult<Self> {
+        let schema_variant = SchemaVariant::get_by_id(ctx, schema_variant_id).await?;
+        if !schema_variant.is_locked() {
+            return Err(ComponentError::CannotPinToUnlockedVariant(
+                component_id,
+                schema_variant_id,
+            ));
+        }
+        if SchemaVariant::schema_id(ctx, schema_variant_id).await?
+            != Self::schema_id_for_component_id(ctx, component_id).await?
+        {
+            return Err(ComponentError::PinToVariantOfDifferentSchema(
+                component_id,
+                schema_variant_id,
+            ));
+        }
+
+        let component = if Self::schema_variant_id(ctx, component_id).await? != schema_variant_id {
+            Self::upgrade_to_new_variant(ctx, component_id, schema_variant_id).await?
+        } else {
+            Self::get_by_id(ctx, component_id).await?
+        };
+
+        component
+            .modify(ctx, |component| {
+                component.pinned_schema_variant_id = Some(schema_variant_id);
+                Ok(())
+            })
+            .await
+    }
+
+    /// Unpins the [`Component`], making it eligible for upgrades again.
+    pub async fn unpin(ctx: &DalContext, component_id: ComponentId) -> ComponentResult<Self> {
+        Self::get_by_id(ctx, component_id)
+            .await?
+            .modify(ctx, |component| {
+                component.pinned_schema_variant_id = None;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Determines whether the [`Component`] would be upgraded and, if so, to which
+    /// [`SchemaVariant`](crate::SchemaVariant).
+    pub async fn upgrade_status(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<ComponentUpgradeStatus> {
+        let component = Self::get_by_id(ctx, component_id).await?;
+        if let Some(schema_variant_id) = component.pinned_schema_variant_id {
+            return Ok(ComponentUpgradeStatus::Pinned { schema_variant_id });
+        }
+        if !component.can_be_upgraded(ctx).await? {
+            return Ok(ComponentUpgradeStatus::UpToDate);
+        }
+
+        let schema_id = Self::schema_id_for_component_id(ctx, component_id).await?;
+        let to_schema_variant_id =
+            match SchemaVariant::get_unlocked_for_schema(ctx, schema_id).await? {
+                Some(unlocked_schema_variant) => unlocked_schema_variant.id(),
+                None => SchemaVariant::default_id_for_schema(ctx, schema_id).await?,
+            };
+
+        Ok(ComponentUpgradeStatus::Upgradable {
+            from_schema_variant_id: Self::schema_variant_id(ctx, component_id).await?,
+            to_schema_variant_id,
+        })
+    }
+
+    /// Upgrades every given [`Component`] which can be upgraded, returning the status each one
+    /// had beforehand. Pinned components are reported as such and left untouched.
+    pub async fn upgrade_many(
+        ctx: &DalContext,
+        component_ids: &[ComponentId],
+    ) -> ComponentResult<Vec<(ComponentId, ComponentUpgradeStatus)>> {
+        let mut statuses = Vec::with_capacity(component_ids.len());
+        for &component_id in component_ids {
+            let status = Self::upgrade_status(ctx, component_id).await?;
+            if let ComponentUpgradeStatus::Upgradable {
+                to_schema_variant_id,
+                ..
+            } = status
+            {
+                Self::upgrade_to_new_variant(ctx, component_id, to_schema_variant_id).await?;
+            }
+            statuses.push((component_id, status));
+        }
+
+        Ok(statuses)
+    }
+}
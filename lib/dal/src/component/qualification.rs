@@ -1,5 +1,19 @@
-use serde::Deserialize;
+use std::{
+    collections::{
+        BTreeMap,
+        HashSet,
+    },
+    sync::Arc,
+};
+
+use futures::StreamExt as _;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_id::LeafPrototypeId;
 use telemetry::prelude::*;
+use tokio::sync::RwLock;
 
 use crate::{
     AttributeValue,
@@ -8,15 +22,25 @@ use crate::{
     ComponentError,
     ComponentId,
     DalContext,
+    Func,
+    attribute::value::PrototypeExecution,
     component::ComponentResult,
+    func::leaf::LeafKind,
     qualification::{
         QualificationSubCheckStatus,
         QualificationView,
     },
-    schema::variant::root_prop::RootPropChild,
+    schema::{
+        leaf::LeafPrototype,
+        variant::root_prop::RootPropChild,
+    },
     ws_event::WsEvent,
 };
 
+/// The default number of qualification functions [`Component::run_all_qualifications`] executes
+/// at once.
+pub const DEFAULT_QUALIFICATION_RUN_CONCURRENCY: usize = 10;
+
 // FIXME(nick): use the formal types from the new version of function authoring instead of this
 // struct. This struct is a temporary stopgap until that's implemented.
 #[derive(Deserialize, Debug)]
@@ -25,7 +49,137 @@ pub struct QualificationEntry {
     pub message: Option<String>,
 }
 
+/// The outcome of a single qualification run by [`Component::run_all_qualifications`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualificationRunResult {
+    pub name: String,
+    pub status: QualificationSubCheckStatus,
+    pub message: Option<String>,
+}
+
+/// The outcome of every qualification run for a single [`Component`] by
+/// [`Component::run_all_qualifications`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentQualificationRun {
+    /// `true` if none of the qualifications failed.
+    pub passed: bool,
+    pub results: Vec<QualificationRunResult>,
+}
+
+/// A qualification function to execute for a [`Component`].
+enum QualificationRun {
+    /// A qualification bound to the schema, written into the qualification map.
+    Leaf {
+        name: String,
+        leaf_prototype_id: LeafPrototypeId,
+        qualification_map_id: AttributeValueId,
+        root_attribute_value_id: AttributeValueId,
+    },
+    /// A qualification set directly on an entry in the qualification map.
+    Value {
+        name: String,
+        attribute_value_id: AttributeValueId,
+    },
+}
+
 impl Component {
+    /// Executes every qualification for every [`Component`] in the change set, running at most
+    /// `concurrency` qualification functions at once, and returns the results for each
+    /// component.
+    ///
+    /// This is a check of the change set as it stands: the results are returned rather than
+    /// written to the graph, where qualification results continue to be kept up to date as
+    /// values change. A qualification which cannot be executed is reported as a failure.
+    #[instrument(level = "info", skip(ctx))]
+    pub async fn run_all_qualifications(
+        ctx: &DalContext,
+        concurrency: usize,
+    ) -> ComponentResult<BTreeMap<ComponentId, ComponentQualificationRun>> {
+        let mut runs_by_component = BTreeMap::new();
+        let mut runs = Vec::new();
+        for component_id in Self::list_ids(ctx).await? {
+            runs_by_component.insert(component_id, ComponentQualificationRun::default());
+            for run in Self::qualification_runs(ctx, component_id).await? {
+                runs.push((component_id, run));
+            }
+        }
+
+        // Nothing is written to the graph while qualifications execute, so the lock is only
+        // needed to satisfy the execution functions
+        let read_lock = Arc::new(RwLock::new(()));
+        let mut results = futures::stream::iter(runs)
+            .map(|(component_id, run)| {
+                let read_lock = read_lock.clone();
+                async move { (component_id, run.execute(ctx, read_lock).await) }
+            })
+            .buffer_unordered(concurrency.max(1));
+        while let Some((component_id, result)) = results.next().await {
+            runs_by_component
+                .entry(component_id)
+                .or_default()
+                .results
+                .push(result);
+        }
+
+        for run in runs_by_component.values_mut() {
+            run.results.sort_by(|a, b| a.name.cmp(&b.name));
+            run.passed = !run
+                .results
+                .iter()
+                .any(|result| result.status == QualificationSubCheckStatus::Failure);
+        }
+
+        Ok(runs_by_component)
+    }
+
+    async fn qualification_runs(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<QualificationRun>> {
+        let mut runs = Vec::new();
+
+        let qualification_map_id =
+            Self::find_qualification_map_attribute_value_id(ctx, component_id).await?;
+        let root_attribute_value_id = Self::root_attribute_value_id(ctx, component_id).await?;
+        let schema_id = Self::schema_id_for_component_id(ctx, component_id).await?;
+        let mut leaf_names = HashSet::new();
+        for leaf_prototype in LeafPrototype::for_schema(ctx, schema_id).await? {
+            if leaf_prototype.kind() != LeafKind::Qualification {
+                continue;
+            }
+            let func_id = LeafPrototype::func_id(ctx, leaf_prototype.id()).await?;
+            let name = Func::get_by_id(ctx, func_id).await?.name;
+            leaf_names.insert(name.clone());
+            runs.push(QualificationRun::Leaf {
+                name,
+                leaf_prototype_id: leaf_prototype.id(),
+                qualification_map_id,
+                root_attribute_value_id,
+            });
+        }
+
+        // Entries in the map are keyed by the name of the function that sets them. Those written
+        // by leaf prototypes are already covered above.
+        for qualification_av in Self::list_qualification_avs(ctx, component_id).await? {
+            let Some(name) = qualification_av.key(ctx).await? else {
+                continue;
+            };
+            if leaf_names.contains(&name)
+                || !AttributeValue::is_set_by_dependent_function(ctx, qualification_av.id()).await?
+            {
+                continue;
+            }
+            runs.push(QualificationRun::Value {
+                name,
+                attribute_value_id: qualification_av.id(),
+            });
+        }
+
+        Ok(runs)
+    }
+
     pub async fn list_qualification_avs(
         ctx: &DalContext,
         component_id: ComponentId,
@@ -117,3 +271,60 @@ impl Component {
         }
     }
 }
+
+impl QualificationRun {
+    async fn execute(self, ctx: &DalContext, read_lock: Arc<RwLock<()>>) -> QualificationRunResult {
+        let (name, execution) = match self {
+            Self::Leaf {
+                name,
+                leaf_prototype_id,
+                qualification_map_id,
+                root_attribute_value_id,
+            } => {
+                let execution = LeafPrototype::execute(
+                    ctx,
+                    leaf_prototype_id,
+                    qualification_map_id,
+                    root_attribute_value_id,
+                    read_lock,
+                )
+                .await
+                .map_err(|err| err.to_string());
+                (name, execution)
+            }
+            Self::Value {
+                name,
+                attribute_value_id,
+            } => {
+                let execution =
+                    AttributeValue::execute_prototype_function(ctx, attribute_value_id, read_lock)
+                        .await
+                        .map_err(|err| err.to_string());
+                (name, execution)
+            }
+        };
+
+        let entry = execution.and_then(|PrototypeExecution { func_run_value, .. }| {
+            serde_json::from_value::<QualificationEntry>(
+                func_run_value
+                    .unprocessed_value()
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null),
+            )
+            .map_err(|err| err.to_string())
+        });
+
+        match entry {
+            Ok(entry) => QualificationRunResult {
+                name,
+                status: entry.result.unwrap_or_default(),
+                message: entry.message,
+            },
+            Err(message) => QualificationRunResult {
+                name,
+                status: QualificationSubCheckStatus::Failure,
+                message: Some(message),
+            },
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! This module contains the ability to check whether a [`Component`] is valid as a whole, rather
+//! than one attribute at a time.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    ComponentResult,
+    qualification::QualificationEntry,
+};
+use crate::{
+    AttributeValue,
+    AttributeValueId,
+    Component,
+    ComponentId,
+    DalContext,
+    qualification::QualificationSubCheckStatus,
+    validation::{
+        ValidationOutputNode,
+        ValidationStatus,
+    },
+};
+
+/// The aggregate result of [`Component::validate`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentValidation {
+    pub component_id: ComponentId,
+    /// Every reason the component is invalid. Empty if the component is valid.
+    pub issues: Vec<ComponentValidationIssue>,
+}
+
+impl ComponentValidation {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A single reason a [`Component`] is invalid.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ComponentValidationIssue {
+    /// A qualification reported a failure.
+    #[serde(rename_all = "camelCase")]
+    FailingQualification {
+        qualification_name: String,
+        message: Option<String>,
+    },
+    /// A value is set, but fails its validation.
+    #[serde(rename_all = "camelCase")]
+    InvalidValue {
+        attribute_value_id: AttributeValueId,
+        path: Option<String>,
+        message: Option<String>,
+    },
+    /// A value is required by its validation, but is unset.
+    #[serde(rename_all = "camelCase")]
+    RequiredValueMissing {
+        attribute_value_id: AttributeValueId,
+        path: Option<String>,
+    },
+}
+
+impl Component {
+    /// Checks whether the [`Component`] is valid, aggregating failing attribute validations,
+    /// required values which are unset and failing qualifications into a single result.
+    ///
+    /// Validations and qualifications which have not finished running are not reported.
+    pub async fn validate(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<ComponentValidation> {
+        let mut issues = Vec::new();
+
+        for (attribute_value_id, validation) in
+            ValidationOutputNode::list_for_component(ctx, component_id).await?
+        {
+            match validation.status {
+                ValidationStatus::Failure | ValidationStatus::Error => {}
+                ValidationStatus::Pending | ValidationStatus::Success => continue,
+            }
+
+            let path = AttributeValue::get_path_for_id(ctx, attribute_value_id).await?;
+            // An unset value can only fail validation if the validation requires it to be set
+            let is_unset = matches!(
+                AttributeValue::view(ctx, attribute_value_id).await?,
+                None | Some(serde_json::Value::Null)
+            );
+            issues.push(if is_unset {
+                ComponentValidationIssue::RequiredValueMissing {
+                    attribute_value_id,
+                    path,
+                }
+            } else {
+                ComponentValidationIssue::InvalidValue {
+                    attribute_value_id,
+                    path,
+                    message: validation.message,
+                }
+            });
+        }
+
+        for qualification_av in Self::list_qualification_avs(ctx, component_id).await? {
+            let Some(qualification_value) =
+                AttributeValue::view(ctx, qualification_av.id()).await?
+            else {
+                continue;
+            };
+            let qualification_entry: QualificationEntry =
+                serde_json::from_value(qualification_value)?;
+            if qualification_entry.result != Some(QualificationSubCheckStatus::Failure) {
+                continue;
+            }
+
+            issues.push(ComponentValidationIssue::FailingQualification {
+                qualification_name: qualification_av
+                    .key(ctx)
+                    .await?
+                    .unwrap_or_else(|| qualification_av.id().to_string()),
+                message: qualification_entry.message,
+            });
+        }
+
+        Ok(ComponentValidation {
+            component_id,
+            issues,
+        })
+    }
+}
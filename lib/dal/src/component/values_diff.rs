@@ -0,0 +1,118 @@
+//! This module contains [`Component::diff_values`], a value-level comparison of two
+//! [`Components`](Component).
+
+use std::collections::BTreeMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+use super::{
+    ComponentError,
+    ComponentResult,
+};
+use crate::{
+    Component,
+    ComponentId,
+    DalContext,
+};
+
+/// A single difference between the values of two [`Components`](Component), keyed by the JSON
+/// pointer path of the prop (e.g. `/domain/image`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ComponentValueDifference {
+    /// Both components have a value at the path, but they are not equal.
+    Differing { path: String, a: Value, b: Value },
+    /// Only the first component has a value at the path.
+    OnlyInA { path: String, value: Value },
+    /// Only the second component has a value at the path.
+    OnlyInB { path: String, value: Value },
+}
+
+impl ComponentValueDifference {
+    /// The path of the prop which differs.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Differing { path, .. }
+            | Self::OnlyInA { path, .. }
+            | Self::OnlyInB { path, .. } => path,
+        }
+    }
+}
+
+impl Component {
+    /// Compares the values of two [`Components`](Component), returning every leaf prop path
+    /// whose value is only present on one of them or differs between them, sorted by path.
+    ///
+    /// The components do not need to share a [`SchemaVariant`](crate::SchemaVariant), though
+    /// the diff is most useful when they do.
+    pub async fn diff_values(
+        ctx: &DalContext,
+        a: ComponentId,
+        b: ComponentId,
+    ) -> ComponentResult<Vec<ComponentValueDifference>> {
+        let a_values = Self::leaf_values(ctx, a).await?;
+        let mut b_values = Self::leaf_values(ctx, b).await?;
+
+        let mut differences = Vec::new();
+        for (path, a_value) in a_values {
+            match b_values.remove(&path) {
+                Some(b_value) if b_value == a_value => {}
+                Some(b_value) => differences.push(ComponentValueDifference::Differing {
+                    path,
+                    a: a_value,
+                    b: b_value,
+                }),
+                None => differences.push(ComponentValueDifference::OnlyInA {
+                    path,
+                    value: a_value,
+                }),
+            }
+        }
+        differences.extend(
+            b_values
+                .into_iter()
+                .map(|(path, value)| ComponentValueDifference::OnlyInB { path, value }),
+        );
+        differences.sort_by(|left, right| left.path().cmp(right.path()));
+
+        Ok(differences)
+    }
+
+    async fn leaf_values(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<BTreeMap<String, Value>> {
+        let view = Self::view_by_id(ctx, component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+
+        let mut values = BTreeMap::new();
+        flatten_into(String::new(), view, &mut values);
+        Ok(values)
+    }
+}
+
+/// Flattens a component view into its scalar leaves, keyed by JSON pointer path. Empty objects
+/// and arrays have no leaves, so they are treated the same as unset values.
+fn flatten_into(path: String, value: Value, values: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                flatten_into(format!("{path}/{key}"), child, values);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.into_iter().enumerate() {
+                flatten_into(format!("{path}/{index}"), child, values);
+            }
+        }
+        scalar => {
+            values.insert(path, scalar);
+        }
+    }
+}
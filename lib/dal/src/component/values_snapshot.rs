@@ -0,0 +1,136 @@
+//! This module contains [`ComponentValuesSnapshot`], a lightweight capture of the attribute
+//! values set on a [`Component`] that can later be restored.
+
+use std::collections::HashSet;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    ComponentError,
+    ComponentResult,
+};
+use crate::{
+    Component,
+    ComponentId,
+    DalContext,
+    SchemaVariantId,
+    attribute::attributes::{
+        self,
+        AttributeSources,
+        AttributeValueIdent,
+        Source,
+        ValueOrSourceSpec,
+    },
+};
+
+/// Paths which are never captured or restored, since they reflect the "real world" rather than
+/// values set on the model.
+const IGNORED_PATH_PREFIX: &str = "/resource";
+
+/// The attribute values (and subscriptions) explicitly set on a [`Component`] at a point in time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentValuesSnapshot {
+    schema_variant_id: SchemaVariantId,
+    sources: Vec<(AttributeValueIdent, Source)>,
+}
+
+impl ComponentValuesSnapshot {
+    /// The [`SchemaVariantId`] of the [`Component`] the snapshot was taken from.
+    pub fn schema_variant_id(&self) -> SchemaVariantId {
+        self.schema_variant_id
+    }
+
+    /// The captured `(path, source)` pairs, in the order they appear in the [`Component`].
+    pub fn sources(&self) -> &[(AttributeValueIdent, Source)] {
+        &self.sources
+    }
+}
+
+impl Component {
+    /// Captures every attribute value explicitly set on the [`Component`], so that it can be
+    /// reapplied later with [`Component::restore_values`].
+    ///
+    /// This is much lighter than forking a change set, but only covers the one component. The
+    /// resource is not captured.
+    pub async fn snapshot_values(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<ComponentValuesSnapshot> {
+        let schema_variant_id = Self::schema_variant_id(ctx, component_id).await?;
+        let sources = Self::snapshottable_sources(ctx, component_id).await?;
+
+        Ok(ComponentValuesSnapshot {
+            schema_variant_id,
+            sources,
+        })
+    }
+
+    /// Returns the [`Component`] to the state captured in the [`ComponentValuesSnapshot`].
+    ///
+    /// Values set since the snapshot was taken are unset (or removed, for map and array
+    /// entries), and every captured value is set again. The [`Component`] must be on the same
+    /// [`SchemaVariant`](crate::SchemaVariant) as when the snapshot was taken.
+    pub async fn restore_values(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        snapshot: &ComponentValuesSnapshot,
+    ) -> ComponentResult<()> {
+        let schema_variant_id = Self::schema_variant_id(ctx, component_id).await?;
+        if schema_variant_id != snapshot.schema_variant_id {
+            return Err(ComponentError::CannotRestoreValuesFromDifferentVariant(
+                component_id,
+                snapshot.schema_variant_id,
+                schema_variant_id,
+            ));
+        }
+
+        let captured_paths: HashSet<&str> = snapshot
+            .sources
+            .iter()
+            .map(|(ident, _)| ident.path())
+            .collect();
+
+        // Unset anything that was not set when the snapshot was taken. We go in reverse so that
+        // later array elements are removed before earlier ones, keeping indices stable.
+        let mut updates: Vec<(AttributeValueIdent, ValueOrSourceSpec)> = Vec::new();
+        for (ident, _) in Self::snapshottable_sources(ctx, component_id)
+            .await?
+            .into_iter()
+            .rev()
+        {
+            if !captured_paths.contains(ident.path()) {
+                updates.push((ident, ValueOrSourceSpec::unset()));
+            }
+        }
+
+        updates.extend(
+            snapshot
+                .sources
+                .iter()
+                .cloned()
+                .map(|(ident, source)| (ident, source.into())),
+        );
+
+        attributes::update_attributes_without_validation(
+            ctx,
+            component_id,
+            AttributeSources(updates),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn snapshottable_sources(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<(AttributeValueIdent, Source)>> {
+        let mut sources = Self::sources(ctx, component_id).await?;
+        sources.retain(|(ident, _)| !ident.path().starts_with(IGNORED_PATH_PREFIX));
+        Ok(sources)
+    }
+}
@@ -101,6 +101,7 @@ use crate::{
     AttributeValueId,
     ChangeSetError,
     EncryptedSecret,
+    KeyPairAlgorithm,
     Workspace,
     WorkspaceError,
     WorkspacePk,
@@ -113,11 +114,13 @@ use crate::{
         ChangeSet,
         ChangeSetId,
     },
+    component::pending_dependent_values::PendingDependentValuesCache,
     feature_flags::FeatureFlagService,
     jetstream_streams::JetstreamStreams,
     job::{
         consumer::DalJob,
         processor::{
+            JobPriority,
             JobQueueProcessor,
             JobQueueProcessorError,
         },
@@ -192,6 +195,10 @@ pub struct ServicesContext {
     feature_flag_service: FeatureFlagService,
     /// Dedicated executor for running CPU-intensive tasks
     compute_executor: DedicatedExecutor,
+    /// The algorithm used for key pairs created without choosing one
+    default_key_pair_algorithm: KeyPairAlgorithm,
+    /// The values last found waiting on each change set's dependent values update
+    pending_dependent_values: PendingDependentValuesCache,
 }
 
 impl ServicesContext {
@@ -226,9 +233,18 @@ impl ServicesContext {
             layer_db,
             feature_flag_service,
             compute_executor,
+            default_key_pair_algorithm: KeyPairAlgorithm::default(),
+            pending_dependent_values: PendingDependentValuesCache::default(),
         }
     }
 
+    /// Sets the algorithm used for key pairs created without choosing one, such as the default
+    /// key pair of a new workspace.
+    pub fn with_default_key_pair_algorithm(mut self, algorithm: KeyPairAlgorithm) -> Self {
+        self.default_key_pair_algorithm = algorithm;
+        self
+    }
+
     /// Consumes and returns [`DalContextBuilder`].
     pub fn into_builder(self, blocking: bool) -> DalContextBuilder {
         DalContextBuilder {
@@ -297,6 +313,16 @@ impl ServicesContext {
         &self.compute_executor
     }
 
+    /// Gets the algorithm used for key pairs created without choosing one
+    pub fn default_key_pair_algorithm(&self) -> KeyPairAlgorithm {
+        self.default_key_pair_algorithm
+    }
+
+    /// Gets the values last found waiting on each change set's dependent values update
+    pub fn pending_dependent_values(&self) -> &PendingDependentValuesCache {
+        &self.pending_dependent_values
+    }
+
     /// Builds and returns a new [`Connections`].
     pub async fn connections(&self) -> PgPoolResult<Connections> {
         let pg_conn = self.pg_pool.get().await?;
@@ -465,6 +491,8 @@ pub struct DalContext {
     /// Determines if we should not enqueue dependent value update jobs for attribute updates in
     /// this context. Useful for builtin migrations, since we don't care about attribute values propagation then.
     no_dependent_values: bool,
+    /// The priority lane that jobs enqueued in this context are dispatched on.
+    job_priority: JobPriority,
     /// The workspace snapshot for this context
     workspace_snapshot: Option<WorkspaceSnapshotSelector>,
     /// The change set for this context
@@ -885,6 +913,11 @@ impl DalContext {
         self.services_context().layer_db().clone()
     }
 
+    /// Gets the values last found waiting on each change set's dependent values update.
+    pub fn pending_dependent_values(&self) -> &PendingDependentValuesCache {
+        self.services_context.pending_dependent_values()
+    }
+
     /// Fetch the change set for the current change set visibility
     /// Should only be called by DalContextBuilder or by ourselves if changing visibility or
     /// refetching after a commit
@@ -926,6 +959,17 @@ impl DalContext {
         self.no_dependent_values
     }
 
+    pub fn job_priority(&self) -> JobPriority {
+        self.job_priority
+    }
+
+    /// Sets the priority lane that jobs enqueued in this context from now on are dispatched on.
+    /// Jobs for interactive requests, which a user is waiting on, should use
+    /// [`JobPriority::High`] so that they are not stuck behind background work.
+    pub fn set_job_priority(&mut self, priority: JobPriority) {
+        self.job_priority = priority;
+    }
+
     pub fn services_context(&self) -> ServicesContext {
         self.services_context.clone()
     }
@@ -1090,7 +1134,7 @@ impl DalContext {
         self.txns()
             .await?
             .job_queue
-            .enqueue_action_job(workspace_id, change_set_id, action_id)
+            .enqueue_action_job(workspace_id, change_set_id, action_id, self.job_priority)
             .await;
         Ok(())
     }
@@ -1123,7 +1167,11 @@ impl DalContext {
         self.txns()
             .await?
             .job_queue
-            .enqueue_dependent_values_update_job(self.workspace_pk()?, self.change_set_id())
+            .enqueue_dependent_values_update_job(
+                self.workspace_pk()?,
+                self.change_set_id(),
+                self.job_priority,
+            )
             .await;
 
         Ok(())
@@ -1154,6 +1202,7 @@ impl DalContext {
                 self.workspace_pk()?,
                 self.change_set_id(),
                 attribute_value_id,
+                self.job_priority,
             )
             .await;
 
@@ -1178,6 +1227,7 @@ impl DalContext {
                 view_id,
                 // TODO(nick): make this required.
                 Some(request_ulid),
+                self.job_priority,
             )
             .await;
 
@@ -1191,7 +1241,12 @@ impl DalContext {
         self.txns()
             .await?
             .job_queue
-            .enqueue_debug_func_job(self.workspace_pk()?, self.change_set_id(), job_state_id)
+            .enqueue_debug_func_job(
+                self.workspace_pk()?,
+                self.change_set_id(),
+                job_state_id,
+                self.job_priority,
+            )
             .await;
 
         Ok(())
@@ -1270,6 +1325,11 @@ impl DalContext {
         &self.services_context.compute_executor
     }
 
+    /// Gets the algorithm used for key pairs created without choosing one.
+    pub fn default_key_pair_algorithm(&self) -> KeyPairAlgorithm {
+        self.services_context.default_key_pair_algorithm
+    }
+
     /// Gets a reference to the DAL context's encryption key.
     pub fn encryption_key(&self) -> &VeritechEncryptionKey {
         &self.services_context.encryption_key
@@ -1515,6 +1575,7 @@ impl DalContextBuilder {
             history_actor: HistoryActor::SystemInit,
             request_ulid,
             no_dependent_values: self.no_dependent_values,
+            job_priority: JobPriority::default(),
             workspace_snapshot: None,
             change_set: None,
             event_session_id: EventSessionId::new(),
@@ -1543,6 +1604,7 @@ impl DalContextBuilder {
             history_actor,
             request_ulid,
             no_dependent_values: self.no_dependent_values,
+            job_priority: JobPriority::default(),
             workspace_snapshot: None,
             change_set: None,
             event_session_id: EventSessionId::new(),
@@ -1571,6 +1633,7 @@ impl DalContextBuilder {
             history_actor: HistoryActor::SystemInit,
             request_ulid,
             no_dependent_values: self.no_dependent_values,
+            job_priority: JobPriority::default(),
             workspace_snapshot: None,
             change_set: None,
             event_session_id: EventSessionId::new(),
@@ -1600,6 +1663,7 @@ impl DalContextBuilder {
             request_ulid: access_builder.request_ulid,
             visibility: Visibility::new_head_fake(),
             no_dependent_values: self.no_dependent_values,
+            job_priority: JobPriority::default(),
             workspace_snapshot: None,
             change_set: None,
             event_session_id: EventSessionId::new(),
@@ -1638,6 +1702,7 @@ impl DalContextBuilder {
             history_actor: request_context.history_actor,
             request_ulid: request_context.request_ulid,
             no_dependent_values: self.no_dependent_values,
+            job_priority: JobPriority::default(),
             workspace_snapshot: None,
             change_set: None,
             event_session_id: EventSessionId::new(),
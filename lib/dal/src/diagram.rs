@@ -97,7 +97,7 @@ use crate::{
 };
 
 #[remain::sorted]
-#[derive(Error, Debug)]
+#[derive(Error, Debug, strum::AsRefStr)]
 pub enum DiagramError {
     #[error("approval requirement error: {0}")]
     ApprovalRequirement(#[from] Box<ApprovalRequirementError>),
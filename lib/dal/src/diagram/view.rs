@@ -37,6 +37,7 @@ use crate::{
         geometry::{
             Geometry,
             GeometryId,
+            GeometryRepresents,
         },
     },
     implement_add_edge_to,
@@ -422,6 +423,40 @@ impl View {
         Ok(geometry_pre)
     }
 
+    /// Adds each component which is in no view other than this one to the default view at the
+    /// same position, so that the view can be removed without orphaning any of them. Returns the
+    /// components which were added.
+    pub async fn move_components_to_default(
+        ctx: &DalContext,
+        view_id: ViewId,
+    ) -> DiagramResult<Vec<ComponentId>> {
+        let default_view_id = Self::get_id_for_default(ctx).await?;
+
+        let mut moved_component_ids = Vec::new();
+        for geometry in Geometry::list_by_view_id(ctx, view_id).await? {
+            let Some(GeometryRepresents::Component(component_id)) =
+                Geometry::represented_id(ctx, geometry.id()).await?
+            else {
+                continue;
+            };
+            // Components with a geometry in any other view are not orphaned by the removal
+            if Geometry::list_ids_by_component(ctx, component_id)
+                .await?
+                .len()
+                > 1
+            {
+                continue;
+            }
+
+            let mut default_geometry =
+                Geometry::new_for_component(ctx, component_id, default_view_id).await?;
+            default_geometry.update(ctx, geometry.into_raw()).await?;
+            moved_component_ids.push(component_id);
+        }
+
+        Ok(moved_component_ids)
+    }
+
     pub async fn remove(ctx: &DalContext, view_id: ViewId) -> DiagramResult<()> {
         ctx.workspace_snapshot()?
             .view_remove(view_id)
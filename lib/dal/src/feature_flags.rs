@@ -1,4 +1,14 @@
-use std::collections::HashSet;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    sync::{
+        Arc,
+        PoisonError,
+        RwLock,
+    },
+};
 
 use serde::{
     Deserialize,
@@ -7,6 +17,8 @@ use serde::{
 use si_settings::ValueKind;
 use strum::Display;
 
+use crate::Workspace;
+
 #[derive(Debug, Display, Deserialize, Serialize, Clone, clap::ValueEnum, Hash, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -14,6 +26,8 @@ use strum::Display;
 pub enum FeatureFlag {
     Secrets,
     ActionsV2,
+    /// Allows running a single action immediately, bypassing the action queue.
+    RunActionsImmediately,
 }
 
 impl From<FeatureFlag> for ValueKind {
@@ -22,18 +36,61 @@ impl From<FeatureFlag> for ValueKind {
     }
 }
 
+/// The enabled feature flags, shared by every clone of the service so that replacing them on a
+/// running server is seen everywhere.
+///
+/// A flag can also be overridden for a single workspace, turning it on or off there regardless of
+/// whether it is enabled globally. Overrides are stored on the [`Workspace`] itself so that every
+/// service sees the same ones.
 #[derive(Clone, Debug, Default)]
 pub struct FeatureFlagService {
-    feature_flags: HashSet<FeatureFlag>,
+    feature_flags: Arc<RwLock<HashSet<FeatureFlag>>>,
 }
 
 impl FeatureFlagService {
     pub fn new(boot_features: HashSet<FeatureFlag>) -> Self {
         Self {
-            feature_flags: boot_features,
+            feature_flags: Arc::new(RwLock::new(boot_features)),
         }
     }
 
+    /// Returns whether the feature is enabled for the workspace, using the workspace's override
+    /// if it has one and whether the feature is enabled globally otherwise.
+    pub fn is_enabled_for_workspace(&self, feature: &FeatureFlag, workspace: &Workspace) -> bool {
+        self.is_enabled_with_overrides(feature, workspace.feature_flag_overrides())
+    }
+
+    fn is_enabled_with_overrides(
+        &self,
+        feature: &FeatureFlag,
+        overrides: &HashMap<FeatureFlag, bool>,
+    ) -> bool {
+        overrides
+            .get(feature)
+            .copied()
+            .unwrap_or_else(|| self.feature_is_enabled(feature))
+    }
+
+    /// Returns the currently enabled feature flags.
+    pub fn feature_flags(&self) -> HashSet<FeatureFlag> {
+        self.feature_flags
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces the enabled feature flags, returning the ones which were enabled before.
+    pub fn replace_feature_flags(
+        &self,
+        feature_flags: HashSet<FeatureFlag>,
+    ) -> HashSet<FeatureFlag> {
+        let mut current = self
+            .feature_flags
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *current, feature_flags)
+    }
+
     #[allow(unused)]
     pub fn feature_is_enabled(
         &self,
@@ -41,7 +98,10 @@ impl FeatureFlagService {
         // posthog_client: &PosthogClient,
         feature: &FeatureFlag,
     ) -> bool {
-        self.feature_flags.contains(feature)
+        self.feature_flags
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(feature)
 
         // ctx.services_context().fe
 
@@ -54,3 +114,23 @@ impl FeatureFlagService {
         // }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_overrides_take_precedence() {
+        let service = FeatureFlagService::new(HashSet::from([FeatureFlag::ActionsV2]));
+        let overrides = HashMap::from([
+            (FeatureFlag::Secrets, true),
+            (FeatureFlag::ActionsV2, false),
+        ]);
+        let no_overrides = HashMap::new();
+
+        assert!(service.is_enabled_with_overrides(&FeatureFlag::Secrets, &overrides));
+        assert!(!service.is_enabled_with_overrides(&FeatureFlag::ActionsV2, &overrides));
+        assert!(!service.is_enabled_with_overrides(&FeatureFlag::Secrets, &no_overrides));
+        assert!(service.is_enabled_with_overrides(&FeatureFlag::ActionsV2, &no_overrides));
+    }
+}
@@ -279,6 +279,54 @@ impl FuncArgument {
             ctx.events_actor(),
         )?;
 
+        Self::add_node(ctx, func_id, name, hash, &content).await
+    }
+
+    /// Creates many [`FuncArgument`]s for a [`Func`](crate::Func), writing all of their contents
+    /// to the content store as a single batch.
+    pub async fn new_many(
+        ctx: &DalContext,
+        func_id: FuncId,
+        arguments: Vec<(String, FuncArgumentKind, Option<FuncArgumentKind>)>,
+    ) -> FuncArgumentResult<Vec<Self>> {
+        if arguments.iter().any(|(name, _, _)| name.is_empty()) {
+            return Err(FuncArgumentError::EmptyNameDuringCreation);
+        }
+
+        let timestamp = Timestamp::now();
+        let contents: Vec<_> = arguments
+            .iter()
+            .map(|(_, kind, element_kind)| FuncArgumentContentV1 {
+                kind: *kind,
+                element_kind: *element_kind,
+                timestamp,
+            })
+            .collect();
+
+        let (hashes, _) = ctx.layer_db().cas().write_many(
+            contents
+                .iter()
+                .map(|content| Arc::new(FuncArgumentContent::V1(content.clone()).into()))
+                .collect(),
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let mut func_arguments = Vec::with_capacity(arguments.len());
+        for (((name, _, _), content), hash) in arguments.into_iter().zip(contents).zip(hashes) {
+            func_arguments.push(Self::add_node(ctx, func_id, name, hash, &content).await?);
+        }
+
+        Ok(func_arguments)
+    }
+
+    async fn add_node(
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: String,
+        hash: ContentHash,
+        content: &FuncArgumentContentV1,
+    ) -> FuncArgumentResult<Self> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
         let id = workspace_snapshot.generate_ulid().await?;
         let lineage_id = workspace_snapshot.generate_ulid().await?;
@@ -291,7 +339,7 @@ impl FuncArgument {
 
         let func_argument_node_weight = node_weight.get_func_argument_node_weight()?;
 
-        Ok(FuncArgument::assemble(&func_argument_node_weight, &content))
+        Ok(FuncArgument::assemble(&func_argument_node_weight, content))
     }
 
     pub async fn get_by_id_opt(
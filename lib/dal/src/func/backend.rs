@@ -452,6 +452,11 @@ pub trait FuncDispatch: std::fmt::Debug {
             .handler
             .as_deref()
             .ok_or_else(|| FuncBackendError::DispatchMissingHandler(func.id))?;
+        // Veritech may have a warm pool of instances dedicated to the function
+        let context = FuncDispatchContext {
+            veritech: context.veritech.with_pool_key(&func.name),
+            ..context
+        };
         let value = Self::new(context, code_base64, handler, args, before);
         Ok(value)
     }
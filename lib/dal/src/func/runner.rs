@@ -1,6 +1,7 @@
 use std::{
     collections::VecDeque,
     sync::Arc,
+    time::Duration,
 };
 
 use chrono::Utc;
@@ -26,14 +27,19 @@ use si_events::{
     FuncRunLog,
     FuncRunLogId,
     FuncRunState,
+    FuncRunTimings,
     FuncRunValue,
 };
 use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
 use thiserror::Error;
-use tokio::sync::{
-    mpsc,
-    oneshot,
+use tokio::{
+    sync::{
+        mpsc,
+        oneshot,
+    },
+    task::JoinHandle,
+    time::Instant,
 };
 use ulid::Ulid;
 use veritech_client::{
@@ -164,6 +170,10 @@ pub enum FuncRunnerError {
     FuncBackend(#[from] Box<FuncBackendError>),
     #[error("func run builder error: {0}")]
     FuncRunBuilder(#[from] FuncRunBuilderError),
+    #[error("func run {0} is not in progress (state: {1:?})")]
+    FuncRunNotInProgress(FuncRunId, FuncRunState),
+    #[error("func run {0} not found in workspace")]
+    FuncRunNotInWorkspace(FuncRunId),
     #[error("invalid resolver function type: {0}")]
     InvalidResolverFunctionType(#[from] InvalidResolverFunctionTypeError),
     #[error("kill execution failure: {0:?}")]
@@ -293,6 +303,9 @@ pub struct FuncRunner {
     func: Func,
     args: serde_json::Value,
     before: Vec<BeforeFunction>,
+    prepare_started_at: Instant,
+    /// Whether each line of output is published as it arrives, rather than only the updated log.
+    stream_logs: bool,
 }
 
 impl FuncRunner {
@@ -347,6 +360,8 @@ impl FuncRunner {
             component_id: ComponentId,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let function_args: CasValue = args.clone().into();
             let (function_args_cas_address, _) = ctx.layer_db().cas().write(
                 Arc::new(function_args.into()),
@@ -404,6 +419,8 @@ impl FuncRunner {
                 func,
                 args,
                 before,
+                prepare_started_at,
+                stream_logs: false,
             })
         }
 
@@ -465,6 +482,8 @@ impl FuncRunner {
             func: &Func,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let args = serde_json::Value::Null;
 
             let function_args: CasValue = args.clone().into();
@@ -538,6 +557,8 @@ impl FuncRunner {
                 func: func.clone(),
                 args,
                 before: vec![],
+                prepare_started_at,
+                stream_logs: false,
             })
         }
 
@@ -602,6 +623,8 @@ impl FuncRunner {
             validation_format: String,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let func_id =
                 Func::find_intrinsic(ctx, super::intrinsics::IntrinsicFunc::Validation).await?;
             let func = Func::get_by_id(ctx, func_id).await?;
@@ -700,6 +723,8 @@ impl FuncRunner {
                 func,
                 args,
                 before: vec![],
+                prepare_started_at,
+                stream_logs: false,
             })
         }
 
@@ -762,6 +787,8 @@ impl FuncRunner {
             args: serde_json::Value,
             parent_span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let func = Func::get_by_id(ctx, func_id).await?;
 
             let function_args: CasValue = args.clone().into();
@@ -858,6 +885,8 @@ impl FuncRunner {
                 func,
                 args,
                 before,
+                prepare_started_at,
+                stream_logs: false,
             })
         }
 
@@ -925,6 +954,8 @@ impl FuncRunner {
             args: serde_json::Value,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let func = Func::get_by_id(ctx, management_func_id).await?;
 
             let function_args: CasValue = args.clone().into();
@@ -1023,6 +1054,8 @@ impl FuncRunner {
                 func,
                 args,
                 before,
+                prepare_started_at,
+                stream_logs: false,
             })
         }
 
@@ -1096,6 +1129,8 @@ impl FuncRunner {
             args: serde_json::Value,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let function_args: CasValue = args.clone().into();
             let (function_args_cas_address, _) = ctx.layer_db().cas().write(
                 Arc::new(function_args.into()),
@@ -1192,6 +1227,8 @@ impl FuncRunner {
                 func,
                 args,
                 before,
+                prepare_started_at,
+                stream_logs: false,
             })
         }
 
@@ -1205,6 +1242,8 @@ impl FuncRunner {
         Ok((func_run_id, result_channel))
     }
 
+    /// Runs an action function. With `stream_logs`, each line of output is published as it
+    /// arrives, followed by an event marking the end of the run's output.
     #[instrument(
         name = "func_runner.run_action",
         level = "info",
@@ -1238,6 +1277,7 @@ impl FuncRunner {
         component_id: ComponentId,
         func_id: FuncId,
         args: serde_json::Value,
+        stream_logs: bool,
     ) -> FuncRunnerResult<FuncRunnerValueChannel> {
         let span = current_span_for_instrument_at!("debug");
 
@@ -1258,8 +1298,11 @@ impl FuncRunner {
             component_id: ComponentId,
             func_id: FuncId,
             args: serde_json::Value,
+            stream_logs: bool,
             span: &Span,
         ) -> FuncRunnerResult<FuncRunner> {
+            let prepare_started_at = Instant::now();
+
             let func = Func::get_by_id(ctx, func_id).await?;
             let prototype = ActionPrototype::get_by_id(ctx, action_prototype_id)
                 .await
@@ -1390,12 +1433,22 @@ impl FuncRunner {
                 func,
                 args,
                 before,
+                prepare_started_at,
+                stream_logs,
             })
         }
 
-        let runner = prepare(ctx, action_prototype_id, component_id, func_id, args, &span)
-            .await
-            .map_err(|err| span.record_err(err))?;
+        let runner = prepare(
+            ctx,
+            action_prototype_id,
+            component_id,
+            func_id,
+            args,
+            stream_logs,
+            &span,
+        )
+        .await
+        .map_err(|err| span.record_err(err))?;
 
         let result_channel = runner.execute(ctx.clone(), span).await;
 
@@ -1423,6 +1476,46 @@ impl FuncRunner {
             return Err(FuncRunnerError::DoNotHavePermissionToKillExecution);
         }
 
+        Self::terminate_execution(ctx, func_run_id).await
+    }
+
+    /// Cancels an in-progress execution belonging to the current workspace, terminating the
+    /// function's instance and marking its [`FuncRun`] as [`Killed`](FuncRunState::Killed).
+    ///
+    /// Unlike [`FuncRunner::kill_execution`], this is available to any user of the workspace.
+    #[instrument(
+        name = "func_runner.cancel_execution",
+        level = "info",
+        skip(ctx),
+        fields(job.id = Empty, si.func_run.id = Empty)
+    )]
+    pub async fn cancel_execution(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+    ) -> FuncRunnerResult<()> {
+        let span = current_span_for_instrument_at!("info");
+
+        if !span.is_disabled() {
+            let mut id_buf = FuncRunId::array_to_str_buf();
+
+            let id = func_run_id.array_to_str(&mut id_buf);
+            span.record("job.id", &id);
+            span.record("si.func_run.id", &id);
+        }
+
+        let func_run = FuncRunDb::try_read(ctx, func_run_id).await?;
+        if func_run.workspace_pk() != ctx.workspace_pk()? {
+            return Err(FuncRunnerError::FuncRunNotInWorkspace(func_run_id));
+        }
+        match func_run.state() {
+            FuncRunState::Created | FuncRunState::Dispatched | FuncRunState::Running => {}
+            state => return Err(FuncRunnerError::FuncRunNotInProgress(func_run_id, state)),
+        }
+
+        Self::terminate_execution(ctx, func_run_id).await
+    }
+
+    async fn terminate_execution(ctx: &DalContext, func_run_id: FuncRunId) -> FuncRunnerResult<()> {
         let result = ctx
             .veritech()
             .kill_execution(&KillExecutionRequest {
@@ -1512,8 +1605,12 @@ impl FuncRunner {
             func_run_id,
             output_stream_rx,
             action_id,
+            stream_logs: self.stream_logs,
         };
 
+        // This probably needs a tracker, if we're being honest - but one thing at a time.
+        let logs_task_handle = tokio::spawn(logs_task.run());
+
         let execution_task = FuncRunnerExecutionTask {
             result_tx,
             ctx,
@@ -1523,10 +1620,10 @@ impl FuncRunner {
             args: self.args,
             before: self.before,
             parent_span: execution_parent_span,
+            prepare_started_at: self.prepare_started_at,
+            logs_task_handle,
         };
 
-        // This probably needs a tracker, if we're being honest - but one thing at a time.
-        tokio::spawn(logs_task.run());
         tokio::spawn(execution_task.run());
 
         result_rx
@@ -1780,6 +1877,7 @@ struct FuncRunnerLogsTask {
     func_run_id: FuncRunId,
     output_stream_rx: mpsc::Receiver<OutputStream>,
     action_id: Option<ActionId>,
+    stream_logs: bool,
 }
 
 impl FuncRunnerLogsTask {
@@ -1797,15 +1895,32 @@ impl FuncRunnerLogsTask {
 
     async fn try_run(mut self) -> FuncRunnerResult<()> {
         let mut func_run_log = FuncRunLog::new(self.func_run_id, self.ctx.events_tenancy());
+        let mut sequence = 0;
         while let Some(item) = self.output_stream_rx.recv().await {
-            func_run_log.push_log(si_events::OutputLine {
+            let line = si_events::OutputLine {
                 stream: item.stream,
                 execution_id: item.execution_id,
                 level: item.level,
                 group: item.group,
                 message: item.message,
                 timestamp: item.timestamp,
-            });
+            };
+
+            if self.stream_logs {
+                WsEvent::func_run_log_streamed(
+                    &self.ctx,
+                    self.func_run_id,
+                    self.action_id,
+                    sequence,
+                    Some(line.clone()),
+                )
+                .await?
+                .publish_immediately(&self.ctx)
+                .await?;
+                sequence += 1;
+            }
+
+            func_run_log.push_log(line);
 
             FuncRunLogDb::upsert(&self.ctx, func_run_log.clone()).await?;
 
@@ -1830,6 +1945,20 @@ impl FuncRunnerLogsTask {
         func_run_log.set_finalized();
         FuncRunLogDb::upsert(&self.ctx, func_run_log.clone()).await?;
 
+        if self.stream_logs {
+            // Without a line, this marks the end of the stream
+            WsEvent::func_run_log_streamed(
+                &self.ctx,
+                self.func_run_id,
+                self.action_id,
+                sequence,
+                None,
+            )
+            .await?
+            .publish_immediately(&self.ctx)
+            .await?;
+        }
+
         WsEvent::func_run_log_updated(
             &self.ctx,
             func_run_log.func_run_id(),
@@ -1853,6 +1982,8 @@ struct FuncRunnerExecutionTask {
     args: serde_json::Value,
     before: Vec<BeforeFunction>,
     parent_span: Span,
+    prepare_started_at: Instant,
+    logs_task_handle: JoinHandle<()>,
 }
 
 impl FuncRunnerExecutionTask {
@@ -1890,7 +2021,7 @@ impl FuncRunnerExecutionTask {
         }
     }
 
-    async fn try_run(self) -> FuncRunnerResult<()> {
+    async fn try_run(mut self) -> FuncRunnerResult<()> {
         if !self.func.is_intrinsic() {
             FuncRunner::update_run(&self.ctx, self.func_run.id(), |func_run| {
                 func_run.set_state(FuncRunState::Running);
@@ -1898,6 +2029,9 @@ impl FuncRunnerExecutionTask {
             .await?;
         }
 
+        let prepare = self.prepare_started_at.elapsed();
+        let critical_section_started_at = Instant::now();
+
         let execution_result = match self.func_run.backend_kind().into() {
             FuncBackendKind::JsAction => {
                 FuncBackendJsAction::create_and_execute(
@@ -2026,6 +2160,25 @@ impl FuncRunnerExecutionTask {
             }
         };
 
+        let critical_section = critical_section_started_at.elapsed();
+
+        // Backends which stream output consume the dispatch context, closing the output stream once
+        // they have finished with it. Replacing it closes the stream for those which don't.
+        let output_drain_started_at = Instant::now();
+        self.func_dispatch_context = FuncDispatchContext::new(
+            self.ctx.veritech().clone(),
+            self.func_run.id(),
+            WorkspaceId::from(Ulid::from(self.func_run.workspace_pk())),
+            self.func_run.change_set_id(),
+        )
+        .0;
+        if let Err(err) = self.logs_task_handle.await {
+            warn!(si.error.message = ?err, "function logs task did not complete");
+        }
+        let output_drain = output_drain_started_at.elapsed();
+
+        let postprocess_started_at = Instant::now();
+
         match execution_result {
             Ok((mut unprocessed_value, mut value)) => {
                 // We so sorry - this is the way that the old code
@@ -2073,8 +2226,15 @@ impl FuncRunnerExecutionTask {
                 backend,
             }) => {
                 if !self.func.is_intrinsic() {
+                    // A killed execution has already been (or is about to be) marked as such by
+                    // whoever killed it, so don't clobber that with a generic failure
+                    let state = if kind == FunctionResultFailureErrorKind::KilledExecution {
+                        FuncRunState::Killed
+                    } else {
+                        FuncRunState::Failure
+                    };
                     FuncRunner::update_run(&self.ctx, self.func_run.id(), |func_run| {
-                        func_run.set_state(FuncRunState::Failure);
+                        func_run.set_state(state);
                     })
                     .await?;
                 }
@@ -2101,10 +2261,34 @@ impl FuncRunnerExecutionTask {
             }
         }
 
+        let postprocess = postprocess_started_at.elapsed();
+
+        if !self.func.is_intrinsic() {
+            let timings = FuncRunTimings {
+                prepare_us: duration_as_micros(prepare),
+                critical_section_us: duration_as_micros(critical_section),
+                output_drain_us: duration_as_micros(output_drain),
+                postprocess_us: duration_as_micros(postprocess),
+            };
+            debug!(
+                si.func_run.id = %self.func_run.id(),
+                si.func_run.timings.prepare_us = timings.prepare_us,
+                si.func_run.timings.critical_section_us = timings.critical_section_us,
+                si.func_run.timings.output_drain_us = timings.output_drain_us,
+                si.func_run.timings.postprocess_us = timings.postprocess_us,
+                "recording func run timings"
+            );
+            FuncRunDb::set_timings(&self.ctx, self.func_run.id(), timings).await?;
+        }
+
         Ok(())
     }
 }
 
+fn duration_as_micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct FuncRunLogUpdatedPayload {
@@ -2113,6 +2297,19 @@ pub struct FuncRunLogUpdatedPayload {
     action_id: Option<ActionId>,
 }
 
+/// One line of output from a running function, sent as soon as it arrives. The last event for a
+/// run has no line and is marked `done`.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunLogStreamedPayload {
+    func_run_id: FuncRunId,
+    action_id: Option<ActionId>,
+    /// The position of this event among the run's events, so that they can be put in order.
+    sequence: u64,
+    line: Option<si_events::OutputLine>,
+    done: bool,
+}
+
 impl WsEvent {
     pub async fn func_run_log_updated(
         ctx: &DalContext,
@@ -2130,4 +2327,24 @@ impl WsEvent {
         )
         .await
     }
+
+    pub async fn func_run_log_streamed(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+        action_id: Option<ActionId>,
+        sequence: u64,
+        line: Option<si_events::OutputLine>,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::FuncRunLogStreamed(FuncRunLogStreamedPayload {
+                func_run_id,
+                action_id,
+                sequence,
+                done: line.is_none(),
+                line,
+            }),
+        )
+        .await
+    }
 }
@@ -29,6 +29,7 @@ use crate::{
     WsEventError,
     action::{
         ActionError,
+        ActionId,
         prototype::ActionPrototypeError,
     },
     attribute::value::AttributeValueError,
@@ -43,6 +44,7 @@ use crate::{
             ManagementFuncJobError,
             dependent_values_update::DependentValueUpdateError,
         },
+        processor::JobPriority,
         producer::BlockingJobError,
     },
     prop::PropError,
@@ -55,6 +57,8 @@ use crate::{
 pub enum JobConsumerError {
     #[error("action error: {0}")]
     Action(#[from] Box<ActionError>),
+    #[error("action {0} is already dispatched or running")]
+    ActionAlreadyInFlight(ActionId),
     #[error("action prototype error: {0}")]
     ActionPrototype(#[from] Box<ActionPrototypeError>),
     #[error("ActionProtoype {0} not found")]
@@ -97,6 +101,8 @@ pub enum JobConsumerError {
     Prop(#[from] Box<PropError>),
     #[error("execution of job {0} failed after {1} retry attempts")]
     RetriesFailed(JobArgsVCurrent, u32),
+    #[error("actions can only be run immediately on HEAD, not in change set {0}")]
+    RunImmediatelyOutsideHead(ChangeSetId),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("tokio task error: {0}")]
@@ -241,6 +247,8 @@ pub trait DalJob: std::fmt::Debug + Sync + Send {
     fn args(&self) -> JobArgsVCurrent;
     fn workspace_id(&self) -> WorkspacePk;
     fn change_set_id(&self) -> ChangeSetId;
+    /// The lane the job is dispatched on, whether it is queued or blocked on.
+    fn priority(&self) -> JobPriority;
 }
 
 #[async_trait]
@@ -45,11 +45,15 @@ use crate::{
     billing_publish,
     change_status::ChangeStatus,
     func::runner::FuncRunner,
-    job::consumer::{
-        DalJob,
-        JobCompletionState,
-        JobConsumer,
-        JobConsumerResult,
+    job::{
+        consumer::{
+            DalJob,
+            JobCompletionState,
+            JobConsumer,
+            JobConsumerError,
+            JobConsumerResult,
+        },
+        processor::JobPriority,
     },
 };
 
@@ -71,6 +75,7 @@ pub struct ActionJob {
     workspace_id: WorkspacePk,
     change_set_id: ChangeSetId,
     action_id: ActionId,
+    priority: JobPriority,
 }
 
 impl ActionJob {
@@ -83,8 +88,68 @@ impl ActionJob {
             workspace_id,
             change_set_id,
             action_id,
+            priority: JobPriority::default(),
         })
     }
+
+    /// Sets the lane the job is dispatched on.
+    pub fn with_priority(mut self: Box<Self>, priority: JobPriority) -> Box<Self> {
+        self.priority = priority;
+        self
+    }
+}
+
+impl ActionJob {
+    /// Runs the given action in the calling task rather than enqueueing it for pinga, returning
+    /// the result of the run directly. The run is still recorded as a
+    /// [`FuncRun`](si_events::FuncRun) and its result is processed exactly as it would be for a
+    /// queued action.
+    ///
+    /// This bypasses the ordering of the action queue and is intended for debugging individual
+    /// actions, so callers are expected to gate access to it. Like queued actions, it only runs on
+    /// HEAD, since that is where resources are tracked.
+    #[instrument(
+        name = "action_job.run_immediately",
+        skip_all,
+        level = "info",
+        fields(
+            si.action.id = ?action_id,
+        )
+    )]
+    pub async fn run_immediately(
+        ctx: &mut DalContext,
+        action_id: ActionId,
+    ) -> JobConsumerResult<(Option<ActionRunResultSuccess>, FuncRunId)> {
+        if !ctx.is_head().await? {
+            return Err(JobConsumerError::RunImmediatelyOutsideHead(
+                ctx.change_set_id(),
+            ));
+        }
+
+        let action = Action::get_by_id(ctx, action_id).await?;
+        if matches!(
+            action.state(),
+            ActionState::Dispatched | ActionState::Running
+        ) {
+            return Err(JobConsumerError::ActionAlreadyInFlight(action_id));
+        }
+
+        // Mark the action as dispatched so that it is not also picked up by the queue while we
+        // are running it.
+        Action::set_state(ctx, action_id, ActionState::Dispatched).await?;
+        ctx.commit().await?;
+        ctx.update_snapshot_to_visibility().await?;
+
+        match inner_run(ctx, action_id).await {
+            Ok(run) => Ok(run),
+            Err(err) => {
+                if let Err(err) = process_failed_action(ctx, action_id).await {
+                    error!(si.error.message = ?err, "failed to process action failure");
+                }
+                Err(err)
+            }
+        }
+    }
 }
 
 impl DalJob for ActionJob {
@@ -101,6 +166,10 @@ impl DalJob for ActionJob {
     fn change_set_id(&self) -> ChangeSetId {
         self.change_set_id
     }
+
+    fn priority(&self) -> JobPriority {
+        self.priority
+    }
 }
 
 #[async_trait]
@@ -144,7 +213,7 @@ impl JobConsumer for ActionJob {
 async fn inner_run(
     ctx: &mut DalContext,
     action_id: ActionId,
-) -> JobConsumerResult<Option<ActionRunResultSuccess>> {
+) -> JobConsumerResult<(Option<ActionRunResultSuccess>, FuncRunId)> {
     let (prototype_id, component_id) = prepare_for_execution(ctx, action_id).await?;
 
     // Execute the action function
@@ -157,7 +226,7 @@ async fn inner_run(
     // conditionally perform any graph cleanups
     perform_graph_cleanups(ctx, prototype_id).await?;
 
-    Ok(maybe_resource)
+    Ok((maybe_resource, func_run_id))
 }
 
 // Compute inputs for the action prototype run and backfill span attributes.
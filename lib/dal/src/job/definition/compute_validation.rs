@@ -16,11 +16,14 @@ use crate::{
     ChangeSet,
     ChangeSetStatus,
     DalContext,
-    job::consumer::{
-        DalJob,
-        JobCompletionState,
-        JobConsumer,
-        JobConsumerResult,
+    job::{
+        consumer::{
+            DalJob,
+            JobCompletionState,
+            JobConsumer,
+            JobConsumerResult,
+        },
+        processor::JobPriority,
     },
     validation::{
         ValidationOutput,
@@ -48,6 +51,7 @@ pub struct ComputeValidation {
     workspace_id: WorkspacePk,
     change_set_id: ChangeSetId,
     attribute_value_ids: Vec<AttributeValueId>,
+    priority: JobPriority,
 }
 
 impl ComputeValidation {
@@ -60,8 +64,15 @@ impl ComputeValidation {
             workspace_id,
             change_set_id,
             attribute_value_ids,
+            priority: JobPriority::default(),
         })
     }
+
+    /// Sets the lane the job is dispatched on.
+    pub fn with_priority(mut self: Box<Self>, priority: JobPriority) -> Box<Self> {
+        self.priority = priority;
+        self
+    }
 }
 
 impl DalJob for ComputeValidation {
@@ -78,6 +89,10 @@ impl DalJob for ComputeValidation {
     fn change_set_id(&self) -> ChangeSetId {
         self.change_set_id
     }
+
+    fn priority(&self) -> JobPriority {
+        self.priority
+    }
 }
 
 impl ComputeValidation {
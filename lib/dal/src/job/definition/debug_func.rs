@@ -26,11 +26,14 @@ use crate::{
             FuncRunnerValueChannel,
         },
     },
-    job::consumer::{
-        DalJob,
-        JobCompletionState,
-        JobConsumer,
-        JobConsumerResult,
+    job::{
+        consumer::{
+            DalJob,
+            JobCompletionState,
+            JobConsumer,
+            JobConsumerResult,
+        },
+        processor::JobPriority,
     },
     workspace_snapshot::DependentValueRoot,
 };
@@ -43,6 +46,7 @@ pub struct DebugFuncJob {
     workspace_id: WorkspacePk,
     change_set_id: ChangeSetId,
     job_state_id: DebugFuncJobStateId,
+    priority: JobPriority,
 }
 
 impl DebugFuncJob {
@@ -55,10 +59,17 @@ impl DebugFuncJob {
             workspace_id,
             change_set_id,
             job_state_id,
+            priority: JobPriority::default(),
         }
         .into()
     }
 
+    /// Sets the lane the job is dispatched on.
+    pub fn with_priority(mut self: Box<Self>, priority: JobPriority) -> Box<Self> {
+        self.priority = priority;
+        self
+    }
+
     async fn attempt_dispatch(
         &self,
         ctx: &DalContext,
@@ -207,6 +218,10 @@ impl DalJob for DebugFuncJob {
     fn change_set_id(&self) -> ChangeSetId {
         self.change_set_id
     }
+
+    fn priority(&self) -> JobPriority {
+        self.priority
+    }
 }
 
 #[async_trait]
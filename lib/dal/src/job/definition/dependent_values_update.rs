@@ -58,11 +58,14 @@ use crate::{
             },
         },
     },
-    job::consumer::{
-        DalJob,
-        JobCompletionState,
-        JobConsumer,
-        JobConsumerResult,
+    job::{
+        consumer::{
+            DalJob,
+            JobCompletionState,
+            JobConsumer,
+            JobConsumerResult,
+        },
+        processor::JobPriority,
     },
     prop::PropError,
     schema::leaf::{
@@ -218,6 +221,7 @@ pub struct DependentValuesUpdate {
     change_set_id: ChangeSetId,
     #[serde(skip)]
     set_value_lock: Arc<RwLock<()>>,
+    priority: JobPriority,
 }
 
 impl DependentValuesUpdate {
@@ -226,8 +230,15 @@ impl DependentValuesUpdate {
             workspace_id,
             change_set_id,
             set_value_lock: Arc::new(RwLock::new(())),
+            priority: JobPriority::default(),
         })
     }
+
+    /// Sets the lane the job is dispatched on.
+    pub fn with_priority(mut self: Box<Self>, priority: JobPriority) -> Box<Self> {
+        self.priority = priority;
+        self
+    }
 }
 
 impl DalJob for DependentValuesUpdate {
@@ -242,6 +253,10 @@ impl DalJob for DependentValuesUpdate {
     fn change_set_id(&self) -> ChangeSetId {
         self.change_set_id
     }
+
+    fn priority(&self) -> JobPriority {
+        self.priority
+    }
 }
 
 #[async_trait]
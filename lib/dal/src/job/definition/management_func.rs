@@ -38,11 +38,14 @@ use crate::{
         Action,
         ActionError,
     },
-    job::consumer::{
-        DalJob,
-        JobCompletionState,
-        JobConsumer,
-        JobConsumerResult,
+    job::{
+        consumer::{
+            DalJob,
+            JobCompletionState,
+            JobConsumer,
+            JobConsumerResult,
+        },
+        processor::JobPriority,
     },
     management::{
         ManagementError,
@@ -163,6 +166,7 @@ pub struct ManagementFuncJob {
     prototype_id: ManagementPrototypeId,
     view_id: ViewId,
     request_ulid: ulid::Ulid,
+    priority: JobPriority,
 }
 
 impl ManagementFuncJob {
@@ -186,10 +190,17 @@ impl ManagementFuncJob {
             prototype_id,
             view_id,
             request_ulid,
+            priority: JobPriority::default(),
         }
         .into()
     }
 
+    /// Sets the lane the job is dispatched on.
+    pub fn with_priority(mut self: Box<Self>, priority: JobPriority) -> Box<Self> {
+        self.priority = priority;
+        self
+    }
+
     async fn spin_until_ready(
         &self,
         ctx: &mut DalContext,
@@ -536,6 +547,10 @@ impl DalJob for ManagementFuncJob {
     fn change_set_id(&self) -> ChangeSetId {
         self.change_set_id
     }
+
+    fn priority(&self) -> JobPriority {
+        self.priority
+    }
 }
 
 #[async_trait]
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use dyn_clone::DynClone;
+pub use pinga_client::JobPriority;
 use si_data_nats::async_nats;
 use thiserror::Error;
 
@@ -13,7 +14,10 @@ use crate::job::{
 };
 
 mod nats_processor;
-pub use nats_processor::NatsProcessor;
+pub use nats_processor::{
+    DEFAULT_BLOCKING_JOB_TIMEOUT,
+    NatsProcessor,
+};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -45,6 +49,13 @@ pub trait JobQueueProcessor: std::fmt::Debug + DynClone {
     async fn block_on_job(&self, job: Box<dyn DalJob>) -> BlockingJobResult;
     async fn block_on_jobs(&self, jobs: Vec<Box<dyn DalJob>>) -> BlockingJobResult;
     async fn process_queue(&self, queue: JobQueue) -> JobQueueProcessorResult<()>;
+    /// Like [`process_queue`](Self::process_queue), but dispatches jobs queued at a lower
+    /// priority on the given priority lane.
+    async fn process_queue_with_priority(
+        &self,
+        queue: JobQueue,
+        priority: JobPriority,
+    ) -> JobQueueProcessorResult<()>;
     async fn blocking_process_queue(&self, queue: JobQueue) -> JobQueueProcessorResult<()>;
 }
 
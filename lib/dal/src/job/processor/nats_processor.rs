@@ -1,16 +1,18 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use pinga_client::PingaClient;
-use pinga_core::api_types::{
-    job_execution_request::JobArgsVCurrent,
-    job_execution_response::JobExecutionResultVCurrent,
-};
+use pinga_core::api_types::job_execution_response::JobExecutionResultVCurrent;
 use si_data_nats::NatsClient;
 use telemetry::prelude::*;
+use telemetry_utils::metric;
 use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
 use crate::job::{
     consumer::DalJob,
     processor::{
+        JobPriority,
         JobQueueProcessor,
         JobQueueProcessorError,
         JobQueueProcessorResult,
@@ -22,9 +24,14 @@ use crate::job::{
     queue::JobQueue,
 };
 
+/// The default time to wait for a blocking job to finish before giving up on it.
+pub const DEFAULT_BLOCKING_JOB_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
 #[derive(Clone, Debug)]
 pub struct NatsProcessor {
     pinga: PingaClient,
+    blocking_job_timeout: Duration,
+    shutdown_token: CancellationToken,
 }
 
 impl NatsProcessor {
@@ -33,7 +40,26 @@ impl NatsProcessor {
             .await
             .map_err(|err| JobQueueProcessorError::Transport(Box::new(err)))?;
 
-        Ok(Self { pinga })
+        Ok(Self {
+            pinga,
+            blocking_job_timeout: DEFAULT_BLOCKING_JOB_TIMEOUT,
+            shutdown_token: CancellationToken::new(),
+        })
+    }
+
+    /// Sets how long to wait for a blocking job to finish, so that a job whose worker goes away
+    /// does not block the caller forever. Defaults to [`DEFAULT_BLOCKING_JOB_TIMEOUT`].
+    pub fn with_blocking_job_timeout(mut self, blocking_job_timeout: Duration) -> Self {
+        self.blocking_job_timeout = blocking_job_timeout;
+        self
+    }
+
+    /// Sets a token which, when cancelled, stops waiting on blocking jobs so that they do not
+    /// hold up shutdown. Jobs still waited on at that point fail with
+    /// [`BlockingJobError::Cancelled`].
+    pub fn with_shutdown_token(mut self, shutdown_token: CancellationToken) -> Self {
+        self.shutdown_token = shutdown_token;
+        self
     }
 
     #[instrument(
@@ -42,70 +68,41 @@ impl NatsProcessor {
         skip_all,
         fields()
     )]
-    async fn push_all_jobs(&self, queue: JobQueue) -> JobQueueProcessorResult<()> {
+    async fn push_all_jobs(
+        &self,
+        queue: JobQueue,
+        min_priority: JobPriority,
+    ) -> JobQueueProcessorResult<()> {
         while let Some(job) = queue.pop_job().await {
-            match job.args() {
-                JobArgsVCurrent::Action { action_id } => {
-                    self.pinga
-                        .dispatch_action_job(
-                            job.workspace_id(),
-                            job.change_set_id(),
-                            action_id,
-                            false,
-                        )
-                        .await?;
-                }
-                JobArgsVCurrent::DependentValuesUpdate => {
-                    self.pinga
-                        .dispatch_dependent_values_update_job(
-                            job.workspace_id(),
-                            job.change_set_id(),
-                            false,
-                        )
-                        .await?;
-                }
-                JobArgsVCurrent::Validation {
-                    attribute_value_ids,
-                } => {
-                    self.pinga
-                        .dispatch_validation_job(
-                            job.workspace_id(),
-                            job.change_set_id(),
-                            attribute_value_ids,
-                            false,
-                        )
-                        .await?;
-                }
-                JobArgsVCurrent::ManagementFunc {
-                    component_id,
-                    prototype_id,
-                    view_id,
-                    request_ulid,
-                } => {
-                    self.pinga
-                        .dispatch_management_job(
-                            job.workspace_id(),
-                            job.change_set_id(),
-                            component_id,
-                            prototype_id,
-                            view_id,
-                            request_ulid,
-                            false,
-                        )
-                        .await?;
-                }
-                JobArgsVCurrent::DebugFunc {
-                    debug_func_job_state_id,
-                } => {
-                    self.pinga
-                        .dispatch_debug_job(
-                            job.workspace_id(),
-                            job.change_set_id(),
-                            debug_func_job_state_id,
-                            false,
-                        )
-                        .await?;
+            match self
+                .pinga
+                .dispatch_job_with_priority(
+                    job.workspace_id(),
+                    job.change_set_id(),
+                    job.args(),
+                    false,
+                    job.priority().max(min_priority),
+                )
+                .await
+            {
+                Ok(_) => {}
+                // A job which can not be dispatched never will be, so rather than failing the rest
+                // of the queue it is set aside in the dead letter stream
+                Err(err) if err.is_undeliverable() => {
+                    error!(
+                        si.error.message = ?err,
+                        "dead lettering job which can not be dispatched",
+                    );
+                    metric!(monotonic_counter.pinga.dead_lettered_jobs = 1);
+                    if let Err(dead_letter_err) = self
+                        .pinga
+                        .dead_letter_job(job.workspace_id(), job.change_set_id(), &job.args(), &err)
+                        .await
+                    {
+                        error!(si.error.message = ?dead_letter_err, "failed to dead letter job");
+                    }
                 }
+                Err(err) => return Err(err.into()),
             }
         }
 
@@ -116,67 +113,25 @@ impl NatsProcessor {
 #[async_trait]
 impl JobQueueProcessor for NatsProcessor {
     async fn block_on_job(&self, job: Box<dyn DalJob>) -> BlockingJobResult {
-        let (_request_id, response_fut) = match job.args() {
-            JobArgsVCurrent::Action { action_id } => {
-                self.pinga
-                    .await_action_job(job.workspace_id(), job.change_set_id(), action_id, false)
-                    .await?
-            }
-            JobArgsVCurrent::DependentValuesUpdate => {
-                self.pinga
-                    .await_dependent_values_update_job(
-                        job.workspace_id(),
-                        job.change_set_id(),
-                        false,
-                    )
-                    .await?
-            }
-            JobArgsVCurrent::Validation {
-                attribute_value_ids,
-            } => {
-                self.pinga
-                    .await_validation_job(
-                        job.workspace_id(),
-                        job.change_set_id(),
-                        attribute_value_ids,
-                        false,
-                    )
-                    .await?
-            }
-            JobArgsVCurrent::ManagementFunc {
-                component_id,
-                prototype_id,
-                view_id,
-                request_ulid,
-            } => {
-                self.pinga
-                    .await_management_job(
-                        job.workspace_id(),
-                        job.change_set_id(),
-                        component_id,
-                        prototype_id,
-                        view_id,
-                        request_ulid,
-                        false,
-                    )
-                    .await?
-            }
-            JobArgsVCurrent::DebugFunc {
-                debug_func_job_state_id,
-            } => {
-                self.pinga
-                    .await_debug_job(
-                        job.workspace_id(),
-                        job.change_set_id(),
-                        debug_func_job_state_id,
-                        false,
-                    )
-                    .await?
-            }
-        };
+        let (_request_id, response_fut) = self
+            .pinga
+            .await_job_with_priority(
+                job.workspace_id(),
+                job.change_set_id(),
+                job.args(),
+                false,
+                job.priority(),
+            )
+            .await?;
 
-        // TODO(fnichol): hrm, no timeout, so we wait forever? That's probably not expected?
-        let job_response = response_fut.await?;
+        // Dropping the response future on shutdown also drops its reply subscription, which
+        // unsubscribes it
+        let job_response = tokio::select! {
+            biased;
+            _ = self.shutdown_token.cancelled() => return Err(BlockingJobError::Cancelled),
+            response = tokio::time::timeout(self.blocking_job_timeout, response_fut) => response
+                .map_err(|_elapsed| BlockingJobError::Timeout(self.blocking_job_timeout))??,
+        };
 
         // TODO(fnichol): I don't think we want to return a `Result::Err` if the job ran to
         // completion but encountered an error. However, currently a nontrivial amount of code may
@@ -211,22 +166,41 @@ impl JobQueueProcessor for NatsProcessor {
         }
 
         let mut job_errors = Vec::new();
-        // Wait for all queued jobs to finish (regardless of success), before exiting.
+        let mut cancelled = false;
+        // Wait for all queued jobs to finish (regardless of success), before exiting. On
+        // shutdown, the jobs still in flight are aborted, which drops their reply subscriptions.
         loop {
-            match dispatched_jobs.join_next().await {
+            let next = tokio::select! {
+                biased;
+                _ = self.shutdown_token.cancelled(), if !cancelled => {
+                    cancelled = true;
+                    dispatched_jobs.abort_all();
+                    continue;
+                }
+                next = dispatched_jobs.join_next() => next,
+            };
+            match next {
                 // All jobs done.
                 None => break,
                 Some(Ok(Ok(_))) => { /* Nothing to do. Job succeeded. */ }
                 Some(Ok(Err(job_error))) => {
                     job_errors.push(job_error);
                 }
+                Some(Err(join_err)) if join_err.is_cancelled() => {
+                    job_errors.push(BlockingJobError::Cancelled);
+                }
                 Some(Err(join_err)) => {
                     job_errors.push(BlockingJobError::JobExecution(join_err.to_string()));
                 }
             }
         }
 
-        if !job_errors.is_empty() {
+        if job_errors
+            .iter()
+            .any(|job_error| matches!(job_error, BlockingJobError::Cancelled))
+        {
+            Err(BlockingJobError::Cancelled)
+        } else if !job_errors.is_empty() {
             Err(BlockingJobError::JobExecution(
                 job_errors
                     .iter()
@@ -239,20 +213,30 @@ impl JobQueueProcessor for NatsProcessor {
         }
     }
 
+    async fn process_queue(&self, queue: JobQueue) -> JobQueueProcessorResult<()> {
+        self.process_queue_with_priority(queue, JobPriority::Normal)
+            .await
+    }
+
     #[instrument(
-        name = "nats_processor.process_queue",
+        name = "nats_processor.process_queue_with_priority",
         level = "info",
         skip_all,
         fields(
+            queue.priority = ?priority,
             queue.size = Empty,
         )
     )]
-    async fn process_queue(&self, queue: JobQueue) -> JobQueueProcessorResult<()> {
+    async fn process_queue_with_priority(
+        &self,
+        queue: JobQueue,
+        priority: JobPriority,
+    ) -> JobQueueProcessorResult<()> {
         let span = current_span_for_instrument_at!("info");
 
         span.record("queue.size", queue.size().await);
 
-        self.push_all_jobs(queue).await?;
+        self.push_all_jobs(queue, priority).await?;
 
         Ok(())
     }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
 pub type BlockingJobResult = Result<(), BlockingJobError>;
@@ -5,6 +7,8 @@ pub type BlockingJobResult = Result<(), BlockingJobError>;
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum BlockingJobError {
+    #[error("blocking job cancelled by shutdown")]
+    Cancelled,
     #[error("error during job execution: {0}")]
     JobExecution(String),
     #[error("job queue processor error: {0}")]
@@ -21,6 +25,8 @@ pub enum BlockingJobError {
     PingaClient(#[from] Box<pinga_client::ClientError>),
     #[error("serde error: {0}")]
     Serde(String),
+    #[error("timed out after {0:?} waiting for a blocking job to finish")]
+    Timeout(Duration),
     #[error("A transactions error occurred: {0}")]
     Transactions(String),
 }
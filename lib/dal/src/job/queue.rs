@@ -1,9 +1,6 @@
 use std::sync::Arc;
 
-use ringmap::{
-    RingMap,
-    RingSet,
-};
+use ringmap::RingMap;
 use si_id::{
     ActionId,
     AttributeValueId,
@@ -27,24 +24,31 @@ use crate::job::{
         DependentValuesUpdate,
         compute_validation::ComputeValidation,
     },
+    processor::JobPriority,
 };
 
-type ActionChangeSets = Arc<Mutex<RingSet<(WorkspacePk, ChangeSetId, ActionId)>>>;
-type DependentValuesUpdateChangeSets = Arc<Mutex<RingSet<(WorkspacePk, ChangeSetId)>>>;
-type ValidationChangeSets = Arc<Mutex<RingMap<(WorkspacePk, ChangeSetId), Vec<AttributeValueId>>>>;
+// Each queued job is kept with the priority it is dispatched on
+type ActionChangeSets = Arc<Mutex<RingMap<(WorkspacePk, ChangeSetId, ActionId), JobPriority>>>;
+type DependentValuesUpdateChangeSets = Arc<Mutex<RingMap<(WorkspacePk, ChangeSetId), JobPriority>>>;
+type ValidationChangeSets =
+    Arc<Mutex<RingMap<(WorkspacePk, ChangeSetId), (JobPriority, Vec<AttributeValueId>)>>>;
 type ManagementChangeSets = Arc<
     Mutex<
-        RingSet<(
-            WorkspacePk,
-            ChangeSetId,
-            ManagementPrototypeId,
-            ComponentId,
-            ViewId,
-            Option<ulid::Ulid>,
-        )>,
+        RingMap<
+            (
+                WorkspacePk,
+                ChangeSetId,
+                ManagementPrototypeId,
+                ComponentId,
+                ViewId,
+                Option<ulid::Ulid>,
+            ),
+            JobPriority,
+        >,
     >,
 >;
-type DebugChangeSets = Arc<Mutex<RingSet<(WorkspacePk, ChangeSetId, DebugFuncJobStateId)>>>;
+type DebugChangeSets =
+    Arc<Mutex<RingMap<(WorkspacePk, ChangeSetId, DebugFuncJobStateId), JobPriority>>>;
 
 #[derive(Debug, Clone, Default)]
 pub struct JobQueue {
@@ -55,28 +59,43 @@ pub struct JobQueue {
     debug_change_sets: DebugChangeSets,
 }
 
+/// Raises the priority of an already queued job if it is enqueued again at a higher one.
+fn raise_priority(queued: &mut JobPriority, priority: JobPriority) {
+    *queued = queued.max(priority);
+}
+
 impl JobQueue {
     pub async fn enqueue_action_job(
         &self,
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
         action_id: ActionId,
+        priority: JobPriority,
     ) {
-        self.action_change_sets
-            .lock()
-            .await
-            .insert((workspace_id, change_set_id, action_id));
+        raise_priority(
+            self.action_change_sets
+                .lock()
+                .await
+                .entry((workspace_id, change_set_id, action_id))
+                .or_insert(priority),
+            priority,
+        );
     }
 
     pub async fn enqueue_dependent_values_update_job(
         &self,
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
+        priority: JobPriority,
     ) {
-        self.dependent_value_update_change_sets
-            .lock()
-            .await
-            .insert((workspace_id, change_set_id));
+        raise_priority(
+            self.dependent_value_update_change_sets
+                .lock()
+                .await
+                .entry((workspace_id, change_set_id))
+                .or_insert(priority),
+            priority,
+        );
     }
 
     pub async fn enqueue_validation_job(
@@ -84,13 +103,14 @@ impl JobQueue {
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
         attribute_value_id: AttributeValueId,
+        priority: JobPriority,
     ) {
-        self.validation_change_sets
-            .lock()
-            .await
+        let mut validation_change_sets = self.validation_change_sets.lock().await;
+        let (queued_priority, attribute_value_ids) = validation_change_sets
             .entry((workspace_id, change_set_id))
-            .or_default()
-            .push(attribute_value_id);
+            .or_insert_with(|| (priority, Vec::new()));
+        raise_priority(queued_priority, priority);
+        attribute_value_ids.push(attribute_value_id);
     }
 
     pub async fn enqueue_management_func_job(
@@ -101,15 +121,23 @@ impl JobQueue {
         component_id: ComponentId,
         view_id: ViewId,
         reqeust_ulid: Option<ulid::Ulid>,
+        priority: JobPriority,
     ) {
-        self.management_change_sets.lock().await.insert((
-            workspace_id,
-            change_set_id,
-            prototype_id,
-            component_id,
-            view_id,
-            reqeust_ulid,
-        ));
+        raise_priority(
+            self.management_change_sets
+                .lock()
+                .await
+                .entry((
+                    workspace_id,
+                    change_set_id,
+                    prototype_id,
+                    component_id,
+                    view_id,
+                    reqeust_ulid,
+                ))
+                .or_insert(priority),
+            priority,
+        );
     }
 
     pub async fn enqueue_debug_func_job(
@@ -117,56 +145,61 @@ impl JobQueue {
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
         debug_func_job_state_id: DebugFuncJobStateId,
+        priority: JobPriority,
     ) {
-        self.debug_change_sets.lock().await.insert((
-            workspace_id,
-            change_set_id,
-            debug_func_job_state_id,
-        ));
+        raise_priority(
+            self.debug_change_sets
+                .lock()
+                .await
+                .entry((workspace_id, change_set_id, debug_func_job_state_id))
+                .or_insert(priority),
+            priority,
+        );
     }
 
     /// Pop jobs off queue in a prioritized, FIFO manner.
     pub async fn pop_job(&self) -> Option<Box<dyn DalJob>> {
-        if let Some((workspace_id, change_set_id)) = self
+        if let Some(((workspace_id, change_set_id), priority)) = self
             .dependent_value_update_change_sets
             .lock()
             .await
             .pop_front()
         {
-            Some(DependentValuesUpdate::new(workspace_id, change_set_id))
-        } else if let Some(((workspace_id, change_set_id), attribute_value_ids)) =
+            Some(DependentValuesUpdate::new(workspace_id, change_set_id).with_priority(priority))
+        } else if let Some(((workspace_id, change_set_id), (priority, attribute_value_ids))) =
             self.validation_change_sets.lock().await.pop_front()
         {
-            Some(ComputeValidation::new(
-                workspace_id,
-                change_set_id,
-                attribute_value_ids,
-            ))
-        } else if let Some((workspace_id, change_set_id, action_id)) =
+            Some(
+                ComputeValidation::new(workspace_id, change_set_id, attribute_value_ids)
+                    .with_priority(priority),
+            )
+        } else if let Some(((workspace_id, change_set_id, action_id), priority)) =
             self.action_change_sets.lock().await.pop_front()
         {
-            Some(ActionJob::new(workspace_id, change_set_id, action_id))
+            Some(ActionJob::new(workspace_id, change_set_id, action_id).with_priority(priority))
         } else if let Some((
-            workspace_id,
-            change_set_id,
-            prototype_id,
-            component_id,
-            view_id,
-            request_ulid,
+            (workspace_id, change_set_id, prototype_id, component_id, view_id, request_ulid),
+            priority,
         )) = self.management_change_sets.lock().await.pop_front()
         {
-            Some(ManagementFuncJob::new(
-                workspace_id,
-                change_set_id,
-                prototype_id,
-                component_id,
-                view_id,
-                request_ulid,
-            ))
-        } else if let Some((workspace_id, change_set_id, job_state_id)) =
+            Some(
+                ManagementFuncJob::new(
+                    workspace_id,
+                    change_set_id,
+                    prototype_id,
+                    component_id,
+                    view_id,
+                    request_ulid,
+                )
+                .with_priority(priority),
+            )
+        } else if let Some(((workspace_id, change_set_id, job_state_id), priority)) =
             self.debug_change_sets.lock().await.pop_front()
         {
-            Some(DebugFuncJob::new(workspace_id, change_set_id, job_state_id))
+            Some(
+                DebugFuncJob::new(workspace_id, change_set_id, job_state_id)
+                    .with_priority(priority),
+            )
         } else {
             None
         }
@@ -27,6 +27,12 @@ use sodiumoxide::crypto::box_::{
     PublicKey as BoxPublicKey,
     SecretKey as BoxSecretKey,
 };
+use strum::{
+    AsRefStr,
+    Display,
+    EnumString,
+    VariantNames,
+};
 use telemetry::prelude::*;
 use thiserror::Error;
 
@@ -77,6 +83,38 @@ pub type KeyPairResult<T> = Result<T, KeyPairError>;
 
 pub use si_id::KeyPairPk;
 
+/// The algorithm used to generate a [`KeyPair`], which determines how secrets sealed with its
+/// public key are opened.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+    VariantNames,
+)]
+pub enum KeyPairAlgorithm {
+    /// A libsodium box key pair (X25519 key exchange, XSalsa20 stream cipher and Poly1305 MAC)
+    #[default]
+    #[serde(rename = "curve25519xsalsa20poly1305")]
+    #[strum(serialize = "curve25519xsalsa20poly1305")]
+    Curve25519XSalsa20Poly1305,
+}
+
+impl KeyPairAlgorithm {
+    #[must_use]
+    pub const fn variants() -> &'static [&'static str] {
+        <KeyPairAlgorithm as strum::VariantNames>::VARIANTS
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct KeyPair {
     pk: KeyPairPk,
@@ -84,6 +122,8 @@ pub struct KeyPair {
     workspace_pk: WorkspacePk,
     public_key: BoxPublicKey,
     secret_key: BoxSecretKey,
+    #[serde(default)]
+    algorithm: KeyPairAlgorithm,
     created_lamport_clock: u64,
     #[serde(flatten)]
     timestamp: Timestamp,
@@ -94,17 +134,29 @@ impl KeyPair {
         self.pk
     }
 
+    /// Creates a new [`KeyPair`] using the [`KeyPairAlgorithm`] configured as the default for
+    /// the services backing the context.
     pub async fn new(ctx: &DalContext, name: impl AsRef<str>) -> KeyPairResult<Self> {
+        Self::new_with_algorithm(ctx, name, ctx.default_key_pair_algorithm()).await
+    }
+
+    /// Creates a new [`KeyPair`] using the given [`KeyPairAlgorithm`], which is stored alongside
+    /// the keys.
+    pub async fn new_with_algorithm(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        algorithm: KeyPairAlgorithm,
+    ) -> KeyPairResult<Self> {
         let name = name.as_ref();
         let (public_key, secret_key_crypted, secret_key_nonce, secret_key_key_hash) =
-            Self::gen_keys(ctx.symmetric_crypto_service());
+            Self::gen_keys(ctx.symmetric_crypto_service(), algorithm);
 
         let row = ctx
             .txns()
             .await?
             .pg()
             .query_one(
-                "SELECT object FROM key_pair_create_v1($1, $2, $3, $4, $5, $6)",
+                "SELECT object FROM key_pair_create_v2($1, $2, $3, $4, $5, $6, $7)",
                 &[
                     &name,
                     &ctx.tenancy().workspace_pk_opt(),
@@ -112,6 +164,7 @@ impl KeyPair {
                     &base64_encode_bytes(secret_key_crypted.as_slice()),
                     &base64_encode_bytes(secret_key_nonce.as_ref()),
                     &secret_key_key_hash.to_string(),
+                    &algorithm.as_ref(),
                 ],
             )
             .await?;
@@ -153,6 +206,7 @@ impl KeyPair {
     getter!(workspace_pk, WorkspacePk);
     getter!(public_key, BoxPublicKey);
     getter!(secret_key, BoxSecretKey);
+    getter!(algorithm, KeyPairAlgorithm);
     getter!(created_lamport_clock, u64);
 
     pub async fn workspace(&self, ctx: &DalContext) -> KeyPairResult<Workspace> {
@@ -164,8 +218,11 @@ impl KeyPair {
 
     fn gen_keys(
         symmetric_crypto_service: &SymmetricCryptoService,
+        algorithm: KeyPairAlgorithm,
     ) -> (BoxPublicKey, Vec<u8>, SymmetricNonce, &Hash) {
-        let (public_key, secret_key) = box_::gen_keypair();
+        let (public_key, secret_key) = match algorithm {
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305 => box_::gen_keypair(),
+        };
 
         let (secret_key_crypted, secret_key_nonce, secret_key_key_hash) =
             symmetric_crypto_service.encrypt(secret_key.as_ref());
@@ -198,6 +255,8 @@ pub struct PublicKey {
     /// This field is base64 encoded into a string. Consumers will have to base64 decode it.
     #[serde(with = "key_pair_box_public_key_serde")]
     public_key: BoxPublicKey,
+    #[serde(default)]
+    algorithm: KeyPairAlgorithm,
     created_lamport_clock: u64,
     #[serde(flatten)]
     timestamp: Timestamp,
@@ -222,6 +281,11 @@ impl PublicKey {
     pub fn public_key(&self) -> &BoxPublicKey {
         &self.public_key
     }
+
+    /// The algorithm of the key pair, which determines how to seal secrets with this key.
+    pub fn algorithm(&self) -> &KeyPairAlgorithm {
+        &self.algorithm
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
@@ -236,6 +300,8 @@ struct KeyPairRow {
     secret_key_key_hash: Hash,
     #[serde(with = "base64_bytes_serde")]
     secret_key_crypted: Vec<u8>,
+    #[serde(default)]
+    algorithm: KeyPairAlgorithm,
     created_lamport_clock: u64,
     #[serde(flatten)]
     timestamp: Timestamp,
@@ -251,8 +317,12 @@ impl KeyPairRow {
             &self.secret_key_nonce,
             &self.secret_key_key_hash,
         )?;
-        let secret_key = BoxSecretKey::from_slice(secret_key_bytes.as_slice())
-            .ok_or(KeyPairError::InvalidSecretKeyBytes)?;
+        let secret_key = match self.algorithm {
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305 => {
+                BoxSecretKey::from_slice(secret_key_bytes.as_slice())
+                    .ok_or(KeyPairError::InvalidSecretKeyBytes)?
+            }
+        };
 
         Ok(KeyPair {
             pk: self.pk,
@@ -260,6 +330,7 @@ impl KeyPairRow {
             workspace_pk: self.workspace_pk,
             public_key: self.public_key,
             secret_key,
+            algorithm: self.algorithm,
             created_lamport_clock: self.created_lamport_clock,
             timestamp: self.timestamp,
         })
@@ -275,10 +346,11 @@ mod tests {
     fn key_pair_row(
         name: impl Into<String>,
         symmetric_crypto_service: &SymmetricCryptoService,
+        algorithm: KeyPairAlgorithm,
     ) -> KeyPairRow {
         let name = name.into();
         let (public_key, secret_key_crypted, secret_key_nonce, secret_key_key_hash) =
-            KeyPair::gen_keys(symmetric_crypto_service);
+            KeyPair::gen_keys(symmetric_crypto_service, algorithm);
 
         KeyPairRow {
             pk: KeyPairPk::NONE,
@@ -288,6 +360,7 @@ mod tests {
             secret_key_nonce,
             secret_key_key_hash: *secret_key_key_hash,
             secret_key_crypted,
+            algorithm,
             created_lamport_clock: 0,
             timestamp: Timestamp::now(),
         }
@@ -302,7 +375,11 @@ mod tests {
         sodiumoxide::init().expect("crypto failed to init");
         let symmetric_crypto_service = symmetric_crypto_service();
 
-        let key_pair_row = key_pair_row("the-temperance-movement", &symmetric_crypto_service);
+        let key_pair_row = key_pair_row(
+            "the-temperance-movement",
+            &symmetric_crypto_service,
+            KeyPairAlgorithm::default(),
+        );
 
         let key_pair = key_pair_row
             .decrypt_into(&symmetric_crypto_service)
@@ -316,4 +393,69 @@ mod tests {
             .expect("failed to decrypt test message with keys");
         assert_eq!("it's a secret".as_bytes(), message.as_slice());
     }
+
+    #[test]
+    fn key_pair_with_selected_algorithm_seals_and_opens() {
+        sodiumoxide::init().expect("crypto failed to init");
+        let symmetric_crypto_service = symmetric_crypto_service();
+
+        let key_pair = key_pair_row(
+            "tool",
+            &symmetric_crypto_service,
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305,
+        )
+        .decrypt_into(&symmetric_crypto_service)
+        .expect("failed to decrypt into key_pair");
+
+        assert_eq!(
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305,
+            *key_pair.algorithm()
+        );
+
+        let crypted = sealedbox::seal(b"forty six and two", key_pair.public_key());
+        let message = sealedbox::open(&crypted, key_pair.public_key(), key_pair.secret_key())
+            .expect("failed to decrypt test message with keys");
+        assert_eq!("forty six and two".as_bytes(), message.as_slice());
+    }
+
+    #[test]
+    fn key_pair_row_without_algorithm_uses_default() {
+        let symmetric_crypto_service = symmetric_crypto_service();
+        let mut json = serde_json::to_value(key_pair_row(
+            "lateralus",
+            &symmetric_crypto_service,
+            KeyPairAlgorithm::default(),
+        ))
+        .expect("failed to serialize key pair row");
+        json.as_object_mut()
+            .expect("key pair row should serialize to an object")
+            .remove("algorithm");
+
+        let key_pair_row: KeyPairRow =
+            serde_json::from_value(json).expect("failed to deserialize key pair row");
+
+        assert_eq!(KeyPairAlgorithm::default(), key_pair_row.algorithm);
+    }
+
+    #[test]
+    fn public_key_exposes_the_key_pair_algorithm() {
+        let symmetric_crypto_service = symmetric_crypto_service();
+        let key_pair_row = key_pair_row(
+            "schism",
+            &symmetric_crypto_service,
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305,
+        );
+
+        // The current public key is read from the same row as the key pair
+        let public_key: PublicKey = serde_json::from_value(
+            serde_json::to_value(&key_pair_row).expect("failed to serialize key pair row"),
+        )
+        .expect("failed to deserialize public key");
+
+        assert_eq!(
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305,
+            *public_key.algorithm()
+        );
+        assert_eq!(&key_pair_row.public_key, public_key.public_key());
+    }
 }
@@ -256,15 +256,38 @@ pub struct AttributePrototypeContentV1 {
 pub enum ComponentContent {
     V1(ComponentContentV1),
     V2(ComponentContentV2),
+    V3(ComponentContentV3),
 }
 
 impl ComponentContent {
-    pub fn extract(self) -> ComponentContentV2 {
+    pub fn extract(self) -> ComponentContentV3 {
         match self {
-            ComponentContent::V1(v1) => ComponentContentV2 {
+            ComponentContent::V1(v1) => ComponentContentV3 {
                 timestamp: v1.timestamp,
+                pinned_schema_variant_id: None,
             },
-            ComponentContent::V2(v2) => v2,
+            ComponentContent::V2(v2) => ComponentContentV3 {
+                timestamp: v2.timestamp,
+                pinned_schema_variant_id: None,
+            },
+            ComponentContent::V3(v3) => v3,
+        }
+    }
+}
+
+/// Content is written as [`ComponentContent::V3`] only for pinned components, and as
+/// [`ComponentContent::V2`] otherwise.
+///
+/// Binaries which predate [`ComponentContentV3`] cannot read it, and replicas of those may still be
+/// serving during a rolling deploy. Writing V2 while nothing is pinned keeps every existing
+/// component readable by them, so only pinning a component must wait until the deploy finishes.
+impl From<ComponentContentV3> for ComponentContent {
+    fn from(value: ComponentContentV3) -> Self {
+        match value.pinned_schema_variant_id {
+            Some(_) => Self::V3(value),
+            None => Self::V2(ComponentContentV2 {
+                timestamp: value.timestamp,
+            }),
         }
     }
 }
@@ -283,6 +306,14 @@ pub struct ComponentContentV2 {
     pub timestamp: Timestamp,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentContentV3 {
+    pub timestamp: Timestamp,
+    /// The [`SchemaVariantId`] the component is pinned to, if any. Pinned components are never
+    /// upgraded.
+    pub pinned_schema_variant_id: Option<SchemaVariantId>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum ViewContent {
     V1(ViewContentV1),
@@ -848,3 +879,30 @@ impl From<Vec<AttributePath>> for AttributePathsContent {
         AttributePathsContent::V1(AttributePathsContentV1(paths))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_pinned_components_are_written_as_v3() {
+        let unpinned = ComponentContentV3 {
+            timestamp: Timestamp::now(),
+            pinned_schema_variant_id: None,
+        };
+        let pinned = ComponentContentV3 {
+            pinned_schema_variant_id: Some(SchemaVariantId::generate()),
+            ..unpinned.clone()
+        };
+
+        assert!(matches!(
+            ComponentContent::from(unpinned.clone()),
+            ComponentContent::V2(_)
+        ));
+        assert_eq!(unpinned, ComponentContent::from(unpinned.clone()).extract());
+        assert_eq!(
+            ComponentContent::V3(pinned.clone()),
+            ComponentContent::from(pinned)
+        );
+    }
+}
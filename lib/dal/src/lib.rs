@@ -130,6 +130,7 @@ pub use job::processor::{
 };
 pub use key_pair::{
     KeyPair,
+    KeyPairAlgorithm,
     KeyPairError,
     KeyPairResult,
     PublicKey,
@@ -170,6 +171,10 @@ pub use secret::{
     SecretView,
     SecretViewError,
 };
+pub use si_db::{
+    Cursor,
+    CursorError,
+};
 pub use si_events::{
     WorkspaceSnapshotAddress,
     content_hash::ContentHash,
@@ -17,6 +17,7 @@ pub use import::{
     import_only_new_funcs,
     import_pkg,
     import_pkg_from_pkg,
+    import_pkgs,
     import_schema_variant,
 };
 use serde::{
@@ -125,6 +126,8 @@ pub enum PkgError {
     FuncArgumentNotFoundByName(FuncId, String),
     #[error("action prototype error: {0}")]
     FuncBinding(#[from] Box<FuncBindingError>),
+    #[error("failed to import package \"{0}\", no packages were imported: {1}")]
+    ImportPkgsFailed(String, Box<PkgError>),
     #[error("input socket error: {0}")]
     InputSocket(#[from] Box<InputSocketError>),
     #[error("found multiple intrinsic func specs for name: {0}")]
@@ -59,6 +59,7 @@ use crate::{
     SchemaVariant,
     SchemaVariantId,
     SocketKind,
+    WorkspaceSnapshot,
     action::prototype::ActionPrototype,
     attribute::prototype::argument::{
         AttributePrototypeArgument,
@@ -87,6 +88,10 @@ use crate::{
     prop::PropPath,
     schema::variant::SchemaVariantJson,
     socket::connection_annotation::ConnectionAnnotation,
+    workspace_snapshot::{
+        WorkspaceSnapshotSelector,
+        split_snapshot::SplitSnapshot,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -394,6 +399,56 @@ pub async fn import_pkg_from_pkg(
     }
 }
 
+/// Imports all of the given packages as a single unit.
+///
+/// The packages are imported in order. If any of them fails to import, the workspace snapshot is
+/// restored to its state from before the first package was imported, so that a partial failure
+/// never leaves only some of the packages installed. The error from the failed package is
+/// returned along with the name of that package.
+pub async fn import_pkgs(
+    ctx: &mut DalContext,
+    pkgs: &[SiPkg],
+    options: Option<ImportOptions>,
+) -> PkgResult<
+    Vec<(
+        Option<ModuleId>,
+        Vec<SchemaVariantId>,
+        Option<Vec<bool /*ImportSkips*/>>,
+    )>,
+> {
+    // Persist the working copy so we have a restore point to return to if any import fails
+    let snapshot = ctx.workspace_snapshot()?;
+    let restore_address = snapshot.write(ctx).await?;
+
+    let mut results = Vec::with_capacity(pkgs.len());
+    for pkg in pkgs {
+        match import_pkg_from_pkg(ctx, pkg, options.clone()).await {
+            Ok(result) => results.push(result),
+            Err(err) => {
+                let pkg_name = pkg
+                    .metadata()
+                    .map(|metadata| metadata.name().to_owned())
+                    .unwrap_or_default();
+
+                match snapshot {
+                    WorkspaceSnapshotSelector::LegacySnapshot(_) => {
+                        let restored = WorkspaceSnapshot::find(ctx, restore_address).await?;
+                        ctx.set_workspace_snapshot(restored);
+                    }
+                    WorkspaceSnapshotSelector::SplitSnapshot(_) => {
+                        let restored = SplitSnapshot::find(ctx, restore_address).await?;
+                        ctx.set_workspace_split_snapshot(restored);
+                    }
+                }
+
+                return Err(PkgError::ImportPkgsFailed(pkg_name, Box::new(err)));
+            }
+        }
+    }
+
+    Ok(results)
+}
+
 pub async fn import_pkg(ctx: &DalContext, pkg_file_path: impl AsRef<Path>) -> PkgResult<SiPkg> {
     println!("Importing package from {:?}", pkg_file_path.as_ref());
     let pkg = SiPkg::load_from_file(&pkg_file_path).await?;
@@ -630,9 +685,21 @@ async fn import_func_arguments(
     func_id: FuncId,
     func_arguments: &[SiPkgFuncArgument<'_>],
 ) -> PkgResult<()> {
-    for arg in func_arguments {
-        create_func_argument(ctx, func_id, arg).await?;
-    }
+    FuncArgument::new_many(
+        ctx,
+        func_id,
+        func_arguments
+            .iter()
+            .map(|arg| {
+                (
+                    arg.name().to_owned(),
+                    arg.kind().into(),
+                    arg.element_kind().to_owned().map(|&kind| kind.into()),
+                )
+            })
+            .collect(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -33,6 +33,7 @@ pub use variant::{
 };
 
 use crate::{
+    Cursor,
     DalContext,
     FuncError,
     FuncId,
@@ -556,6 +557,20 @@ impl Schema {
         Ok(schemas)
     }
 
+    /// Lists a page of at most `limit` [`Schemas`](Schema) in the workspace, ordered by ID and
+    /// beginning at `cursor`. Returns the [`Cursor`] for the next page, if there is one.
+    pub async fn list_paginated(
+        ctx: &DalContext,
+        limit: usize,
+        cursor: Option<Cursor>,
+    ) -> SchemaResult<(Vec<Self>, Option<Cursor>)> {
+        let schemas = Self::list(ctx).await?;
+
+        Ok(si_db::cursor::paginate(schemas, limit, cursor, |schema| {
+            schema.id().into_raw_id()
+        }))
+    }
+
     /// Lists all [`Schemas`](Schema) by ID in the workspace.
     pub async fn list_ids(ctx: &DalContext) -> SchemaResult<Vec<SchemaId>> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
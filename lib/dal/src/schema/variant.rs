@@ -10,6 +10,7 @@ use std::{
 };
 
 use authoring::VariantAuthoringError;
+pub use bound_funcs::SchemaVariantBoundFuncs;
 use chrono::Utc;
 pub use json::{
     SchemaVariantJson,
@@ -122,7 +123,10 @@ use crate::{
         PropError,
         PropPath,
     },
-    schema::variant::root_prop::RootProp,
+    schema::{
+        leaf::LeafPrototypeError,
+        variant::root_prop::RootProp,
+    },
     socket::{
         input::InputSocketError,
         output::OutputSocketError,
@@ -152,6 +156,7 @@ use crate::{
 };
 
 pub mod authoring;
+mod bound_funcs;
 mod json;
 pub mod leaves;
 mod metadata_view;
@@ -215,6 +220,8 @@ pub enum SchemaVariantError {
     LeafFunctionMustBeJsAttribute(FuncId),
     #[error("Leaf map prop not found for item prop {0}")]
     LeafMapPropNotFound(PropId),
+    #[error("leaf prototype error: {0}")]
+    LeafPrototype(#[from] Box<LeafPrototypeError>),
     #[error("management prototype error: {0}")]
     ManagementPrototype(#[from] Box<ManagementPrototypeError>),
     #[error("schema variant missing asset func id; schema_variant_id={0}")]
@@ -2513,6 +2520,12 @@ impl From<InputSocketError> for SchemaVariantError {
     }
 }
 
+impl From<LeafPrototypeError> for SchemaVariantError {
+    fn from(value: LeafPrototypeError) -> Self {
+        Box::new(value).into()
+    }
+}
+
 impl From<OutputSocketError> for SchemaVariantError {
     fn from(value: OutputSocketError) -> Self {
         Box::new(value).into()
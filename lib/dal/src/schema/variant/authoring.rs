@@ -251,6 +251,16 @@ struct SchemaVariantJsonWrapper {
     error: Option<String>,
 }
 
+/// The definition of a [`SchemaVariant`] in the form it is authored in: its metadata alongside
+/// the code for its asset func. Importing it via [`VariantAuthoringClient::import_definition`]
+/// produces an equivalent [`SchemaVariant`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariantDefinition {
+    pub metadata: SchemaVariantMetadataJson,
+    pub code: String,
+}
+
 #[derive(Debug)]
 pub struct VariantAuthoringClient;
 
@@ -268,19 +278,85 @@ impl VariantAuthoringClient {
         code: impl AsRef<str>,
     ) -> VariantAuthoringResult<SchemaVariant> {
         let name = name.into();
+        let metadata = SchemaVariantMetadataJson {
+            schema_name: name.clone(),
+            version: SchemaVariant::generate_version_string(),
+            display_name: name,
+            category: category.into(),
+            color: color.into(),
+            component_type: ComponentType::Component,
+            link,
+            description,
+        };
+
+        Self::import_definition(
+            ctx,
+            SchemaVariantDefinition {
+                metadata,
+                code: code.as_ref().to_owned(),
+            },
+        )
+        .await
+    }
+
+    /// Exports the [`SchemaVariant`] as a [`SchemaVariantDefinition`], suitable for keeping
+    /// under version control and importing again via [`Self::import_definition`].
+    #[instrument(name = "variant.authoring.export_definition", level = "info", skip_all)]
+    pub async fn export_definition(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> VariantAuthoringResult<SchemaVariantDefinition> {
+        let variant = SchemaVariant::get_by_id(ctx, schema_variant_id).await?;
+        let schema = variant.schema(ctx).await?;
+
+        let asset_func_id =
+            variant
+                .asset_func_id()
+                .ok_or(VariantAuthoringError::SchemaVariantAssetNotFound(
+                    schema_variant_id,
+                ))?;
+        let code = Func::get_by_id(ctx, asset_func_id)
+            .await?
+            .code_plaintext()?
+            .ok_or(VariantAuthoringError::SchemaVariantAssetNotFound(
+                schema_variant_id,
+            ))?;
+
+        Ok(SchemaVariantDefinition {
+            metadata: SchemaVariantMetadataJson {
+                schema_name: schema.name,
+                version: variant.version().to_owned(),
+                display_name: variant.display_name().to_owned(),
+                category: variant.category().to_owned(),
+                color: variant.color().to_owned(),
+                component_type: variant.component_type(),
+                link: variant.link(),
+                description: variant.description(),
+            },
+            code,
+        })
+    }
+
+    /// Creates a new [`Schema`] and unlocked [`SchemaVariant`] from a
+    /// [`SchemaVariantDefinition`], executing its code to build the variant.
+    #[instrument(name = "variant.authoring.import_definition", level = "info", skip_all)]
+    pub async fn import_definition(
+        ctx: &DalContext,
+        definition: SchemaVariantDefinition,
+    ) -> VariantAuthoringResult<SchemaVariant> {
+        let SchemaVariantDefinition { metadata, code } = definition;
+        let name = metadata.schema_name.clone();
         if Schema::is_name_taken(ctx, &name).await? {
             return Err(VariantAuthoringError::DuplicatedSchemaName(name));
         };
 
-        let variant_version = SchemaVariant::generate_version_string();
-
-        let code_base64 = general_purpose::STANDARD_NO_PAD.encode(code.as_ref());
+        let code_base64 = general_purpose::STANDARD_NO_PAD.encode(code);
         let asset_func = Func::new(
             ctx,
             generate_scaffold_func_name(&name),
-            Some(name.clone()),
-            description.clone(),
-            link.clone(),
+            Some(metadata.display_name.clone()),
+            metadata.description.clone(),
+            metadata.link.clone(),
             false,
             false,
             FuncBackendKind::JsSchemaVariantDefinition,
@@ -294,16 +370,6 @@ impl VariantAuthoringClient {
         let asset_func_spec = build_asset_func_spec(&asset_func)?;
         let definition = Self::execute_asset_func(ctx, &asset_func).await?;
 
-        let metadata = SchemaVariantMetadataJson {
-            schema_name: name.clone(),
-            version: variant_version.clone(),
-            display_name: name.clone(),
-            category: category.into(),
-            color: color.into(),
-            component_type: ComponentType::Component,
-            link: link.clone(),
-            description: description.clone(),
-        };
         let email = ctx.history_actor().email(ctx).await?;
 
         let pkg_spec =
@@ -0,0 +1,119 @@
+//! This module contains the ability to list every [`Func`] bound to a [`SchemaVariant`], grouped by
+//! the role it plays for the variant.
+
+use std::collections::BTreeSet;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::SchemaVariantResult;
+use crate::{
+    DalContext,
+    Func,
+    FuncId,
+    SchemaVariant,
+    SchemaVariantId,
+    func::{
+        FuncKind,
+        leaf::LeafKind,
+    },
+    schema::leaf::LeafPrototype,
+};
+
+/// The [`Funcs`](Func) bound to a [`SchemaVariant`], grouped by their binding role.
+///
+/// Validations are not funcs bound to the variant: they are declared as validation formats on
+/// [`Props`](crate::Prop) and are all run by a single intrinsic, so they have no group here.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariantBoundFuncs {
+    pub actions: Vec<Func>,
+    pub attributes: Vec<Func>,
+    pub authentication: Vec<Func>,
+    pub code_generation: Vec<Func>,
+    pub management: Vec<Func>,
+    pub qualifications: Vec<Func>,
+}
+
+impl SchemaVariant {
+    /// Lists every [`Func`] bound to the [`SchemaVariant`], grouped by binding role. This includes
+    /// the code generation and qualification funcs bound to the variant's [`Schema`](crate::Schema)
+    /// by [`LeafPrototypes`](LeafPrototype), which apply to all of its variants.
+    ///
+    /// [Intrinsic](crate::func::intrinsics::IntrinsicFunc) funcs are not included. Each group is
+    /// sorted by func name.
+    pub async fn bound_funcs(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> SchemaVariantResult<SchemaVariantBoundFuncs> {
+        let mut bound_funcs = SchemaVariantBoundFuncs::default();
+        let mut seen_func_ids = BTreeSet::new();
+
+        for func in Self::all_funcs_without_intrinsics(ctx, schema_variant_id).await? {
+            seen_func_ids.insert(func.id);
+            bound_funcs.push(func);
+        }
+
+        let schema_id = Self::schema_id(ctx, schema_variant_id).await?;
+        for leaf_prototype in LeafPrototype::for_schema(ctx, schema_id).await? {
+            let func_id = LeafPrototype::func_id(ctx, leaf_prototype.id()).await?;
+            if !seen_func_ids.insert(func_id) {
+                continue;
+            }
+
+            let func = Func::get_by_id(ctx, func_id).await?;
+            match leaf_prototype.kind() {
+                LeafKind::CodeGeneration => bound_funcs.code_generation.push(func),
+                LeafKind::Qualification => bound_funcs.qualifications.push(func),
+            }
+        }
+
+        bound_funcs.sort();
+
+        Ok(bound_funcs)
+    }
+}
+
+impl SchemaVariantBoundFuncs {
+    /// The ids of every bound [`Func`], across all groups.
+    pub fn func_ids(&self) -> impl Iterator<Item = FuncId> + '_ {
+        self.actions
+            .iter()
+            .chain(&self.attributes)
+            .chain(&self.authentication)
+            .chain(&self.code_generation)
+            .chain(&self.management)
+            .chain(&self.qualifications)
+            .map(|func| func.id)
+    }
+
+    fn push(&mut self, func: Func) {
+        match func.kind {
+            FuncKind::Action => self.actions.push(func),
+            FuncKind::Attribute => self.attributes.push(func),
+            FuncKind::Authentication => self.authentication.push(func),
+            FuncKind::CodeGeneration => self.code_generation.push(func),
+            FuncKind::Management => self.management.push(func),
+            FuncKind::Qualification => self.qualifications.push(func),
+            FuncKind::Debug
+            | FuncKind::Intrinsic
+            | FuncKind::SchemaVariantDefinition
+            | FuncKind::Unknown => {}
+        }
+    }
+
+    fn sort(&mut self) {
+        for funcs in [
+            &mut self.actions,
+            &mut self.attributes,
+            &mut self.authentication,
+            &mut self.code_generation,
+            &mut self.management,
+            &mut self.qualifications,
+        ] {
+            funcs.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+    }
+}
@@ -29,9 +29,14 @@ use std::{
     fmt,
     str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 
-use chrono::Utc;
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -80,6 +85,7 @@ use crate::{
     FuncId,
     HelperError,
     KeyPair,
+    KeyPairAlgorithm,
     KeyPairError,
     Prop,
     SchemaVariant,
@@ -370,6 +376,11 @@ impl Secret {
         self.id
     }
 
+    /// Returns the [`Timestamp`] for when the [`Secret`] was created and last updated.
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+
     /// Returns a reference to the name.
     pub fn name(&self) -> &str {
         self.name.as_ref()
@@ -714,13 +725,53 @@ impl Secret {
         // into dependent values update.
         ctx.add_dependent_values_and_enqueue(vec![self.id]).await?;
 
+        // The key is not part of the content, so bump the timestamp ourselves to record that the
+        // secret has been rotated.
         self.modify(ctx, |s| {
             s.encrypted_secret_key = new_key;
+            s.timestamp.updated_at = Utc::now();
             Ok(())
         })
         .await
     }
 
+    /// Lists all [`Secrets`](Secret) which have not been updated for longer than `older_than`,
+    /// oldest first, so that they can be rotated.
+    ///
+    /// Updating either the encrypted contents or the metadata of a [`Secret`] counts as an
+    /// update.
+    pub async fn list_needing_rotation(
+        ctx: &DalContext,
+        older_than: Duration,
+    ) -> SecretResult<Vec<Self>> {
+        Self::list_needing_rotation_as_of(ctx, older_than, Utc::now()).await
+    }
+
+    /// Lists all [`Secrets`](Secret) which had not been updated for longer than `older_than` as
+    /// of `now`, oldest first. See [`list_needing_rotation`](Self::list_needing_rotation).
+    pub async fn list_needing_rotation_as_of(
+        ctx: &DalContext,
+        older_than: Duration,
+        now: DateTime<Utc>,
+    ) -> SecretResult<Vec<Self>> {
+        // A cutoff too far in the past to represent cannot have any secrets before it
+        let Some(cutoff) = TimeDelta::from_std(older_than)
+            .ok()
+            .and_then(|older_than| now.checked_sub_signed(older_than))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut secrets: Vec<Self> = Self::list(ctx)
+            .await?
+            .into_iter()
+            .filter(|secret| secret.timestamp.updated_at <= cutoff)
+            .collect();
+        secrets.sort_by_key(|secret| secret.timestamp.updated_at);
+
+        Ok(secrets)
+    }
+
     /// Finds all secret prop ids for all schema variants
     #[instrument(name = "find_secret_prop_ids", level = "debug", skip_all)]
     pub async fn list_all_secret_prop_ids(ctx: &DalContext) -> SecretResult<Vec<PropId>> {
@@ -945,11 +996,15 @@ impl EncryptedSecret {
     pub async fn decrypt(self, ctx: &DalContext) -> SecretResult<DecryptedSecret> {
         let key_pair = self.key_pair(ctx).await?;
 
-        self.into_decrypted(
-            key_pair.public_key(),
-            key_pair.secret_key(),
-            ctx.symmetric_crypto_service(),
-        )
+        // Explicitly match on the key pair's algorithm to ensure that any new algorithms will
+        // trigger a compilation failure
+        match key_pair.algorithm() {
+            KeyPairAlgorithm::Curve25519XSalsa20Poly1305 => self.into_decrypted(
+                key_pair.public_key(),
+                key_pair.secret_key(),
+                ctx.symmetric_crypto_service(),
+            ),
+        }
     }
 
     fn into_decrypted(
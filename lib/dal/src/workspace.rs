@@ -60,6 +60,8 @@ use ulid::Ulid;
 
 use crate::{
     BuiltinsError,
+    Component,
+    ComponentError,
     DalContext,
     KeyPairError,
     TransactionsError,
@@ -106,6 +108,8 @@ pub enum WorkspaceError {
     BuiltinWorkspaceNotFound,
     #[error("change set error: {0}")]
     ChangeSet(#[from] ChangeSetError),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
     #[error("could not find default change set {1} for workspace {0}")]
     DefaultChangeSetNotFound(WorkspacePk, ChangeSetId),
     #[error("Trying to export from system actor. This can only be done by a user actor")]
@@ -122,6 +126,8 @@ pub enum WorkspaceError {
     NoUserInContext,
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("schema {0} is not allowed in workspace {1}")]
+    SchemaNotAllowed(String, WorkspacePk),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("si db error: {0}")]
@@ -171,6 +177,35 @@ pub struct Workspace {
     snapshot_kind: WorkspaceSnapshotSelectorDiscriminants,
     subgraph_version: Option<SubGraphVersionDiscriminants>,
     approvals_enabled: bool,
+    #[serde(default)]
+    schema_allowlist: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_feature_flag_overrides")]
+    feature_flag_overrides: HashMap<FeatureFlag, bool>,
+}
+
+/// Reads feature flag overrides, ignoring any for flags this version does not know about, such
+/// as those set by a newer version, rather than failing to load the workspace.
+fn feature_flag_overrides_from_value(value: serde_json::Value) -> HashMap<FeatureFlag, bool> {
+    let serde_json::Value::Object(overrides) = value else {
+        return HashMap::new();
+    };
+
+    overrides
+        .into_iter()
+        .filter_map(|(key, enabled)| {
+            let feature = serde_json::from_value(serde_json::Value::String(key)).ok()?;
+            Some((feature, enabled.as_bool()?))
+        })
+        .collect()
+}
+
+fn deserialize_feature_flag_overrides<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<FeatureFlag, bool>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde_json::Value::deserialize(deserializer).map(feature_flag_overrides_from_value)
 }
 
 impl TryFrom<PgRow> for Workspace {
@@ -201,6 +236,8 @@ impl TryFrom<PgRow> for Workspace {
             None => None,
         };
 
+        let feature_flag_overrides: serde_json::Value = row.try_get("feature_flag_overrides")?;
+
         let pk = row.try_get("pk")?;
         Ok(Self {
             pk,
@@ -217,6 +254,8 @@ impl TryFrom<PgRow> for Workspace {
             snapshot_kind,
             subgraph_version,
             approvals_enabled: row.try_get("approvals_enabled")?,
+            schema_allowlist: row.try_get("schema_allowlist")?,
+            feature_flag_overrides: feature_flag_overrides_from_value(feature_flag_overrides),
         })
     }
 }
@@ -878,6 +917,25 @@ impl Workspace {
             metadata,
         } = workspace_data.into_latest();
 
+        let cas_values: HashMap<ContentHash, (Arc<ContentTypes>, String)> =
+            serialize::from_bytes(&content_store_values)?;
+
+        let layer_db = ctx.layer_db();
+
+        // TODO use the serialization format to ensure we're hashing the data correctly, if we change the format
+        for (_, (content, _serialization_format)) in cas_values {
+            layer_db
+                .cas()
+                .write(content, None, ctx.events_tenancy(), ctx.events_actor())?;
+        }
+
+        // The content is written first so that the imported components can be read, but nothing
+        // else is changed until they have all been checked against the allowlist
+        if self.schema_allowlist.is_some() {
+            self.ensure_imported_schemas_allowed(ctx, &change_sets)
+                .await?;
+        }
+
         // ABANDON PREVIOUS CHANGESETS
         for mut change_set in ChangeSet::list_active(ctx).await? {
             change_set.abandon(ctx).await?;
@@ -939,16 +997,33 @@ impl Workspace {
             }
         }
 
-        let cas_values: HashMap<ContentHash, (Arc<ContentTypes>, String)> =
-            serialize::from_bytes(&content_store_values)?;
-
-        let layer_db = ctx.layer_db();
+        Ok(())
+    }
 
-        // TODO use the serialization format to ensure we're hashing the data correctly, if we change the format
-        for (_, (content, _serialization_format)) in cas_values {
-            layer_db
-                .cas()
-                .write(content, None, ctx.events_tenancy(), ctx.events_actor())?;
+    /// Ensures every component in the imported change sets is of a schema allowed in this
+    /// workspace.
+    async fn ensure_imported_schemas_allowed(
+        &self,
+        ctx: &DalContext,
+        change_sets: &HashMap<Ulid, Vec<WorkspaceExportChangeSetV0>>,
+    ) -> WorkspaceResult<()> {
+        for change_set_data in change_sets.values().flatten() {
+            let mut snapshot_ctx = ctx.clone();
+            snapshot_ctx.set_workspace_snapshot(WorkspaceSnapshot::from_bytes(
+                &change_set_data.workspace_snapshot_serialized_data,
+            )?);
+
+            for component_id in Component::list_ids(&snapshot_ctx).await.map_err(Box::new)? {
+                let schema = Component::schema_for_component_id(&snapshot_ctx, component_id)
+                    .await
+                    .map_err(Box::new)?;
+                if !self.is_schema_allowed(schema.name()) {
+                    return Err(WorkspaceError::SchemaNotAllowed(
+                        schema.name().to_owned(),
+                        self.pk,
+                    ));
+                }
+            }
         }
 
         Ok(())
@@ -1015,6 +1090,98 @@ impl Workspace {
         Ok(())
     }
 
+    /// Returns the names of the schemas which may be instantiated in this workspace, or `None` if
+    /// any schema may be instantiated.
+    pub fn schema_allowlist(&self) -> Option<&[String]> {
+        self.schema_allowlist.as_deref()
+    }
+
+    /// Returns whether or not components of the named schema may be created in this workspace.
+    pub fn is_schema_allowed(&self, schema_name: &str) -> bool {
+        match &self.schema_allowlist {
+            Some(allowlist) => allowlist.iter().any(|allowed| allowed == schema_name),
+            None => true,
+        }
+    }
+
+    /// Restricts the schemas which may be instantiated in this workspace to those named in the
+    /// allowlist. Setting the allowlist to `None` allows any schema to be instantiated.
+    pub async fn set_schema_allowlist(
+        &mut self,
+        ctx: &DalContext,
+        allowlist: Option<Vec<String>>,
+    ) -> WorkspaceResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE workspaces SET schema_allowlist = $2 WHERE pk = $1",
+                &[&self.pk, &allowlist],
+            )
+            .await?;
+
+        self.schema_allowlist = allowlist;
+
+        Ok(())
+    }
+
+    /// Returns the feature flags which are turned on or off for this workspace regardless of
+    /// whether they are enabled globally.
+    pub fn feature_flag_overrides(&self) -> &HashMap<FeatureFlag, bool> {
+        &self.feature_flag_overrides
+    }
+
+    /// Overrides whether the feature is enabled for this workspace, or removes its override when
+    /// `enabled` is `None`. Returns the override which was set before.
+    ///
+    /// Only the given feature's override is written, so that overrides set meanwhile, including
+    /// any for features this version does not know about, are left alone.
+    pub async fn set_feature_flag_override(
+        &mut self,
+        ctx: &DalContext,
+        feature: FeatureFlag,
+        enabled: Option<bool>,
+    ) -> WorkspaceResult<Option<bool>> {
+        let key = feature.to_string();
+        let txns = ctx.txns().await?;
+        let row = match enabled {
+            Some(enabled) => {
+                txns.pg()
+                    .query_one(
+                        "UPDATE workspaces AS w
+                         SET feature_flag_overrides = jsonb_set(
+                             w.feature_flag_overrides, ARRAY[$2::text], to_jsonb($3::bool)
+                         )
+                         FROM (SELECT feature_flag_overrides -> $2::text AS previous
+                               FROM workspaces WHERE pk = $1) AS old
+                         WHERE w.pk = $1
+                         RETURNING old.previous, w.feature_flag_overrides",
+                        &[&self.pk, &key, &enabled],
+                    )
+                    .await?
+            }
+            None => {
+                txns.pg()
+                    .query_one(
+                        "UPDATE workspaces AS w
+                         SET feature_flag_overrides = w.feature_flag_overrides - $2::text
+                         FROM (SELECT feature_flag_overrides -> $2::text AS previous
+                               FROM workspaces WHERE pk = $1) AS old
+                         WHERE w.pk = $1
+                         RETURNING old.previous, w.feature_flag_overrides",
+                        &[&self.pk, &key],
+                    )
+                    .await?
+            }
+        };
+
+        let previous: Option<serde_json::Value> = row.try_get("previous")?;
+        self.feature_flag_overrides =
+            feature_flag_overrides_from_value(row.try_get("feature_flag_overrides")?);
+
+        Ok(previous.and_then(|previous| previous.as_bool()))
+    }
+
     pub async fn set_snapshot_versions(
         &mut self,
         ctx: &DalContext,
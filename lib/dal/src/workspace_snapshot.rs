@@ -108,6 +108,7 @@ pub mod content_address;
 pub mod dependent_value_root;
 pub mod edge_weight;
 pub mod graph;
+pub mod graph_export;
 pub mod migrator;
 pub mod node_weight;
 pub mod selector;
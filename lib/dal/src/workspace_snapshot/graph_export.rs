@@ -0,0 +1,83 @@
+//! This module contains the ability to export the graph of a [`WorkspaceSnapshotSelector`] in a
+//! stable, serializable form, for inspecting and debugging a change set outside of the graph
+//! types themselves.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    NodeId,
+    NodeInformation,
+    WorkspaceSnapshotResult,
+    WorkspaceSnapshotSelector,
+    edge_weight::EdgeWeightKindDiscriminants,
+    node_weight::NodeWeightDiscriminants,
+};
+
+/// Every node and edge in a workspace snapshot graph.
+///
+/// Nodes are sorted by id and edges by source, target and kind, so that exporting the same graph
+/// twice produces the same output.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphExport {
+    pub nodes: Vec<GraphExportNode>,
+    pub edges: Vec<GraphExportEdge>,
+}
+
+/// A node in a [`GraphExport`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphExportNode {
+    pub node_weight_kind: NodeWeightDiscriminants,
+    pub id: NodeId,
+}
+
+impl From<NodeInformation> for GraphExportNode {
+    fn from(value: NodeInformation) -> Self {
+        Self {
+            node_weight_kind: value.node_weight_kind,
+            id: value.id,
+        }
+    }
+}
+
+/// An edge in a [`GraphExport`], from the `source` node to the `target` node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphExportEdge {
+    pub source: NodeId,
+    pub target: NodeId,
+    pub kind: EdgeWeightKindDiscriminants,
+}
+
+impl WorkspaceSnapshotSelector {
+    /// Exports every node and edge in the graph. See [`GraphExport`].
+    pub async fn export_graph(&self) -> WorkspaceSnapshotResult<GraphExport> {
+        let mut nodes: Vec<GraphExportNode> = self
+            .nodes()
+            .await?
+            .iter()
+            .map(|node| NodeInformation::from(node).into())
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+
+        let mut edges: Vec<GraphExportEdge> = self
+            .edges()
+            .await?
+            .into_iter()
+            .map(|(edge_weight, source, target)| GraphExportEdge {
+                source: source.into(),
+                target: target.into(),
+                kind: edge_weight.kind().into(),
+            })
+            .collect();
+        edges.sort_by(|a, b| {
+            (a.source, a.target, a.kind.to_string()).cmp(&(b.source, b.target, b.kind.to_string()))
+        });
+
+        Ok(GraphExport { nodes, edges })
+    }
+}
@@ -1,5 +1,12 @@
-use std::collections::HashMap;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    future::Future,
+};
 
+use futures::StreamExt as _;
 use si_db::Visibility;
 use si_events::WorkspaceSnapshotAddress;
 use si_layer_cache::LayerDbError;
@@ -74,11 +81,36 @@ pub enum SnapshotGraphMigratorError {
 
 pub type SnapshotGraphMigratorResult<T> = Result<T, SnapshotGraphMigratorError>;
 
-pub struct SnapshotGraphMigrator;
+/// The default number of snapshots migrated at the same time.
+///
+/// Migrating a snapshot holds both its old and new graphs in memory, and the graphs of large
+/// workspaces are big, so peak memory grows with each snapshot in flight. Most of the time spent
+/// on a migration is waiting on the layer db to read and write snapshots, which a handful of
+/// migrations running side by side is enough to overlap. Four keeps peak memory within a few
+/// snapshots while doing that.
+pub const DEFAULT_CONCURRENCY_LIMIT: usize = 4;
+
+pub struct SnapshotGraphMigrator {
+    concurrency_limit: usize,
+}
 
 impl SnapshotGraphMigrator {
     pub fn new() -> Self {
-        Self
+        Self {
+            concurrency_limit: DEFAULT_CONCURRENCY_LIMIT,
+        }
+    }
+
+    /// Sets the maximum number of snapshots to migrate at the same time. A limit of `0` is
+    /// treated as `1`.
+    pub fn with_concurrency_limit(mut self, concurrency_limit: usize) -> Self {
+        self.concurrency_limit = concurrency_limit.max(1);
+        self
+    }
+
+    /// Returns the maximum number of snapshots migrated at the same time.
+    pub fn concurrency_limit(&self) -> usize {
+        self.concurrency_limit
     }
 
     #[instrument(skip(self, ctx))]
@@ -102,9 +134,9 @@ impl SnapshotGraphMigrator {
                 workspace.pk()
             );
 
+            let mut to_migrate = Vec::with_capacity(open_change_sets.len());
             for change_set in open_change_sets {
-                let mut change_set =
-                    ChangeSet::get_by_id_across_workspaces(ctx, change_set.id).await?;
+                let change_set = ChangeSet::get_by_id_across_workspaces(ctx, change_set.id).await?;
 
                 if change_set.workspace_id.is_none() || change_set.status == ChangeSetStatus::Failed
                 {
@@ -125,34 +157,76 @@ impl SnapshotGraphMigrator {
                 // (or there's no clone anymore and we always change it via following method)
                 ctx_after_migration.set_change_set(change_set.clone())?;
 
+                to_migrate.push((change_set, ctx_after_migration));
+            }
+
+            // Migrate each distinct snapshot once, several at a time. A snapshot failing to
+            // migrate does not stop the others, so that every change set which can be migrated
+            // is.
+            let mut seen = HashSet::new();
+            let unmigrated_snapshots: Vec<_> = to_migrate
+                .iter()
+                .filter(|(change_set, _)| {
+                    !migration_map.contains_key(&change_set.workspace_snapshot_address)
+                        && seen.insert(change_set.workspace_snapshot_address)
+                })
+                .map(|(change_set, ctx)| (change_set.workspace_snapshot_address, ctx))
+                .collect();
+
+            let this = &*self;
+            let mut failed_snapshots = HashMap::new();
+            for (snapshot_address, result) in run_with_concurrency_limit(
+                unmigrated_snapshots,
+                self.concurrency_limit,
+                |(snapshot_address, ctx)| async move {
+                    (
+                        snapshot_address,
+                        this.migrate_snapshot(ctx, snapshot_address).await,
+                    )
+                },
+            )
+            .await
+            {
+                match result {
+                    Ok(new_snapshot_address) => {
+                        migration_map.insert(snapshot_address, new_snapshot_address);
+                    }
+                    Err(err) => {
+                        failed_snapshots.insert(snapshot_address, err);
+                    }
+                }
+            }
+
+            for (mut change_set, mut ctx_after_migration) in to_migrate {
                 let snapshot_address = change_set.workspace_snapshot_address;
 
                 let new_snapshot_address = match migration_map.get(&snapshot_address) {
-                    Some(cached_addr) => *cached_addr,
-                    None => match self
-                        .migrate_snapshot(&ctx_after_migration, snapshot_address)
-                        .await
-                    {
-                        Ok(addr) => addr,
-                        Err(err) => {
-                            let err_string = err.to_string();
-                            if err_string.contains("missing from store for node")
-                                || err_string
-                                    .contains("workspace snapshot graph missing at address")
-                            {
-                                error!(
-                                    error = ?err,
-                                    "Migration error: {err_string}, marking change set {} for workspace {:?} as failed",
-                                    change_set.id, change_set.workspace_id
-                                );
-
-                                change_set
-                                    .update_status(ctx, ChangeSetStatus::Failed)
-                                    .await?;
-                                continue;
-                            } else {
-                                return Err(err)?;
-                            }
+                    Some(migrated_addr) => *migrated_addr,
+                    None => match failed_snapshots.get(&snapshot_address) {
+                        Some(err) if is_missing_data_error(err) => {
+                            error!(
+                                error = ?err,
+                                "Migration error: {err}, marking change set {} for workspace {:?} as failed",
+                                change_set.id, change_set.workspace_id
+                            );
+
+                            change_set
+                                .update_status(ctx, ChangeSetStatus::Failed)
+                                .await?;
+                            continue;
+                        }
+                        Some(err) => {
+                            error!(
+                                error = ?err,
+                                "Migration error: {err}, skipping change set {} for workspace {:?}",
+                                change_set.id, change_set.workspace_id
+                            );
+                            continue;
+                        }
+                        None => {
+                            return Err(SnapshotGraphMigratorError::UnexpectedMigrationFailure(
+                                snapshot_address,
+                            ));
                         }
                     },
                 };
@@ -165,10 +239,18 @@ impl SnapshotGraphMigrator {
                     .update_pointer(&ctx_after_migration, new_snapshot_address)
                     .await?;
 
-                migration_map.insert(snapshot_address, new_snapshot_address);
                 change_set_count += 1;
             }
 
+            // Leave the workspace at its old version so that the snapshots which could not be
+            // migrated are attempted again next time.
+            if let Some(err) = failed_snapshots
+                .into_values()
+                .find(|err| !is_missing_data_error(err))
+            {
+                return Err(err);
+            }
+
             workspace
                 .set_snapshot_versions(
                     ctx,
@@ -186,7 +268,7 @@ impl SnapshotGraphMigrator {
 
     #[instrument(skip(self, ctx))]
     pub async fn migrate_snapshot(
-        &mut self,
+        &self,
         ctx: &DalContext,
         workspace_snapshot_address: WorkspaceSnapshotAddress,
     ) -> SnapshotGraphMigratorResult<WorkspaceSnapshotAddress> {
@@ -271,3 +353,88 @@ impl Default for SnapshotGraphMigrator {
         Self::new()
     }
 }
+
+/// Whether the error is due to data missing from the store, in which case the change set can
+/// never be migrated.
+fn is_missing_data_error(err: &SnapshotGraphMigratorError) -> bool {
+    let err_string = err.to_string();
+    err_string.contains("missing from store for node")
+        || err_string.contains("workspace snapshot graph missing at address")
+}
+
+/// Runs `f` over every item, with at most `limit` running at the same time, and returns all of
+/// the outputs (in completion order).
+async fn run_with_concurrency_limit<I, F, Fut>(items: I, limit: usize, f: F) -> Vec<Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    futures::stream::iter(items)
+        .map(f)
+        .buffer_unordered(limit.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{
+                AtomicUsize,
+                Ordering,
+            },
+        },
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn run_with_concurrency_limit_is_bounded_and_isolates_failures() {
+        const LIMIT: usize = 3;
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let mut results = run_with_concurrency_limit(0..10, LIMIT, |i| {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                if i % 4 == 0 { Err(i) } else { Ok(i) }
+            }
+        })
+        .await;
+        results.sort();
+
+        assert_eq!(LIMIT, max_in_flight.load(Ordering::SeqCst));
+        assert_eq!(
+            vec![
+                Ok(1),
+                Ok(2),
+                Ok(3),
+                Ok(5),
+                Ok(6),
+                Ok(7),
+                Ok(9),
+                Err(0),
+                Err(4),
+                Err(8),
+            ],
+            results
+        );
+    }
+
+    #[test]
+    fn concurrency_limit_is_at_least_one() {
+        let migrator = SnapshotGraphMigrator::new().with_concurrency_limit(0);
+        assert_eq!(1, migrator.concurrency_limit());
+    }
+}
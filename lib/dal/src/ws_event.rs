@@ -30,6 +30,7 @@ use crate::{
     change_set::event::{
         ChangeSetActorPayload,
         ChangeSetAppliedPayload,
+        ChangeSetApplyProgressPayload,
         ChangeSetCreatedPayload,
         ChangeSetRenamePayload,
         ChangeSetStateChangePayload,
@@ -55,7 +56,10 @@ use crate::{
         FuncWsEventFuncSummary,
         FuncWsEventGenerating,
         FuncWsEventPayload,
-        runner::FuncRunLogUpdatedPayload,
+        runner::{
+            FuncRunLogStreamedPayload,
+            FuncRunLogUpdatedPayload,
+        },
     },
     management::prototype::{
         ManagementFuncExecutedPayload,
@@ -136,6 +140,7 @@ pub enum WsPayload {
     AuditLogsPublished(AuditLogsPublishedPayload),
     ChangeSetAbandoned(ChangeSetActorPayload),
     ChangeSetApplied(ChangeSetAppliedPayload),
+    ChangeSetApplyProgress(ChangeSetApplyProgressPayload),
     ChangeSetApprovalStatusChanged(ChangeSetId),
     ChangeSetCanceled(ChangeSetId),
     ChangeSetCreated(ChangeSetCreatedPayload),
@@ -155,6 +160,7 @@ pub enum WsPayload {
     FuncCreated(FuncWsEventFuncSummary),
     FuncDeleted(FuncWsEventPayload),
     FuncGenerating(FuncWsEventGenerating),
+    FuncRunLogStreamed(FuncRunLogStreamedPayload),
     FuncRunLogUpdated(FuncRunLogUpdatedPayload),
     FuncSaved(FuncWsEventPayload),
     FuncUpdated(FuncWsEventFuncSummary),
@@ -15,7 +15,9 @@ use dal::{
             ActionPrototype,
         },
     },
+    component::resource::ResourceData,
     func::authoring::FuncAuthoringClient,
+    job::definition::ActionJob,
     schema::variant::authoring::VariantAuthoringClient,
 };
 use dal_test::{
@@ -29,12 +31,16 @@ use dal_test::{
     },
     test,
 };
+use futures::StreamExt as _;
 use pretty_assertions_sorted::{
     assert_eq,
     assert_ne,
 };
 use serde_json::json;
+use si_db::FuncRunDb;
+use si_events::ActionResultState;
 use si_id::ActionId;
+use veritech_client::ResourceStatus;
 
 mod schema_level;
 
@@ -154,6 +160,198 @@ async fn run(ctx: &mut DalContext) -> Result<()> {
     Ok(())
 }
 
+#[test(enable_veritech)]
+async fn run_streams_logs_in_order(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let variant_id = Component::schema_variant_id(ctx, component.id()).await?;
+    let proto = ActionPrototype::for_variant(ctx, variant_id)
+        .await?
+        .pop()
+        .expect("unable to find prototype for variant");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let workspace_pk = ctx.workspace_pk()?;
+    let mut events = ctx
+        .nats_conn()
+        .subscribe(format!("si.workspace_pk.{workspace_pk}.event"))
+        .await?;
+
+    let (_maybe_resource, func_run_id) =
+        ActionPrototype::run(ctx, proto.id(), component.id()).await?;
+
+    let mut streamed = Vec::new();
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(10), events.next())
+            .await?
+            .expect("workspace events ended");
+        let event: serde_json::Value = serde_json::from_slice(message.payload())?;
+        if event["payload"]["kind"] != "FuncRunLogStreamed"
+            || event["payload"]["data"]["funcRunId"] != json!(func_run_id)
+        {
+            continue;
+        }
+        let data = event["payload"]["data"].clone();
+        let done = data["done"] == true;
+        streamed.push(data);
+        if done {
+            break;
+        }
+    }
+
+    // Every line comes in order, and only the last event marks the end, without a line
+    let (done, lines) = streamed.split_last().expect("no log events were streamed");
+    assert!(!lines.is_empty(), "no log lines were streamed");
+    for (sequence, line) in lines.iter().enumerate() {
+        assert_eq!(json!(sequence), line["sequence"]);
+        assert_eq!(json!(false), line["done"]);
+        assert!(line["line"]["message"].is_string());
+    }
+    assert_eq!(json!(lines.len()), done["sequence"]);
+    assert_eq!(serde_json::Value::Null, done["line"]);
+
+    Ok(())
+}
+
+#[test(enable_veritech)]
+async fn run_immediately(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let mut create_action_id = None;
+    for action_id in Action::find_for_component_id(ctx, component.id()).await? {
+        if Action::prototype(ctx, action_id).await?.kind == ActionKind::Create {
+            create_action_id = Some(action_id);
+        }
+    }
+    let create_action_id = create_action_id.expect("create action should be enqueued");
+
+    // Hold the action so that it is not dispatched by the queue once it reaches HEAD
+    Action::set_state(ctx, create_action_id, ActionState::OnHold).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+
+    // The result comes back inline rather than through the queue
+    let (maybe_result, func_run_id) = ActionJob::run_immediately(ctx, create_action_id).await?;
+    let result = maybe_result.expect("action should have returned a result");
+    assert_eq!(ResourceStatus::Ok, result.status);
+
+    // Commit as the route does, then read everything back through a fresh context
+    ctx.blocking_commit().await?;
+    let fresh_ctx = ctx
+        .services_context()
+        .into_builder(false)
+        .build(ctx.access_builder().build(ctx.change_set_id().into()))
+        .await?;
+
+    // The run is still recorded
+    let func_run = FuncRunDb::try_read(&fresh_ctx, func_run_id).await?;
+    assert_eq!(Some(create_action_id), func_run.action_id());
+    assert_eq!(
+        Some(ActionResultState::Success),
+        func_run.action_result_state()
+    );
+
+    // And its result is processed as it would be for a queued action
+    assert!(
+        !Action::find_for_component_id(&fresh_ctx, component.id())
+            .await?
+            .contains(&create_action_id)
+    );
+    assert!(
+        Component::get_by_id(&fresh_ctx, component.id())
+            .await?
+            .resource(&fresh_ctx)
+            .await?
+            .is_some()
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn run_immediately_rejects_in_flight_action(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let action_id = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("an action should be enqueued");
+    Action::set_state(ctx, action_id, ActionState::OnHold).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+
+    Action::set_state(ctx, action_id, ActionState::Dispatched).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert!(matches!(
+        ActionJob::run_immediately(ctx, action_id).await,
+        Err(dal::job::consumer::JobConsumerError::ActionAlreadyInFlight(id)) if id == action_id
+    ));
+
+    Ok(())
+}
+
+#[test]
+async fn run_immediately_rejects_outside_head(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+            .await?;
+    let action_id = Action::find_for_component_id(ctx, component.id())
+        .await?
+        .pop()
+        .expect("an action should be enqueued");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // Resources only exist on HEAD, so running the action in a change set is refused
+    assert!(matches!(
+        ActionJob::run_immediately(ctx, action_id).await,
+        Err(dal::job::consumer::JobConsumerError::RunImmediatelyOutsideHead(id))
+            if id == ctx.change_set_id()
+    ));
+    assert!(
+        Action::find_for_component_id(ctx, component.id())
+            .await?
+            .contains(&action_id)
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn available_actions(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "fearless").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // Without a resource, only create is available
+    assert_eq!(
+        vec![ActionKind::Create],
+        Component::available_actions(ctx, component.id()).await?
+    );
+
+    component
+        .set_resource(
+            ctx,
+            ResourceData::new(ResourceStatus::Ok, Some(json!({"resource": "fearless"}))),
+        )
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // Once there is a resource, create is no longer available, but destroy is
+    let available = Component::available_actions(ctx, component.id()).await?;
+    assert!(!available.contains(&ActionKind::Create));
+    assert!(available.contains(&ActionKind::Destroy));
+    assert!(available.contains(&ActionKind::Refresh));
+    assert!(available.contains(&ActionKind::Update));
+
+    Ok(())
+}
+
 #[test]
 async fn auto_queue_creation(ctx: &mut DalContext) -> Result<()> {
     // ======================================================
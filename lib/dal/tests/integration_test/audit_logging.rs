@@ -1,4 +1,12 @@
-use audit_database::AuditDatabaseContext;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use audit_database::{
+    AuditDatabaseContext,
+    AuditLogFilter,
+};
 use audit_logs_stream::AuditLogsStream;
 use dal::{
     AttributeValue,
@@ -6,6 +14,7 @@ use dal::{
     Prop,
     Schema,
     SchemaVariant,
+    attribute::attributes,
     audit_logging,
     prop::PropPath,
 };
@@ -13,6 +22,7 @@ use dal_test::{
     helpers::{
         ChangeSetTestHelpers,
         confirm_jetstream_stream_has_no_messages,
+        create_component_for_default_schema_name_in_default_view,
         create_named_component_for_schema_variant_on_default_view,
         list_audit_logs_until_expected_number_of_rows,
     },
@@ -267,3 +277,123 @@ async fn round_trip(ctx: &mut DalContext, audit_database_context: AuditDatabaseC
             .expect("could not list component-specific audit logs");
     }
 }
+
+#[test(enable_veritech, enable_forklift)]
+async fn list_filtered(ctx: &mut DalContext, audit_database_context: AuditDatabaseContext) {
+    let context = audit_database_context;
+
+    // Create a component, then set one of its attributes a few times, which audit logs each.
+    let component = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "swifty",
+        "chamber of reflection",
+    )
+    .await
+    .expect("could not create component");
+    for name in ["one", "two", "three"] {
+        attributes::update_attributes(
+            ctx,
+            component.id(),
+            serde_json::from_value(serde_json::json!({ "/domain/name": name }))
+                .expect("could not build attribute sources"),
+        )
+        .await
+        .expect("could not update attributes");
+    }
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // Wait for the audit logs to land in the database.
+    let filter = AuditLogFilter {
+        kind: Some("SetAttribute".to_string()),
+        change_set_id: Some(ctx.change_set_id()),
+        ..Default::default()
+    };
+    let timeout = Duration::from_secs(DATABASE_RETRY_TIMEOUT_SECONDS);
+    let interval = Duration::from_millis(DATABASE_RETRY_INTERVAL_MILLISECONDS);
+    let start = Instant::now();
+    let mut audit_logs = Vec::new();
+    while start.elapsed() < timeout {
+        (audit_logs, _) = audit_logging::list_filtered(ctx, &context, &filter, None, SIZE, true)
+            .await
+            .expect("could not list filtered audit logs");
+        if audit_logs.len() == 3 {
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
+
+    assert_eq!(3, audit_logs.len());
+    for audit_log in &audit_logs {
+        assert_eq!("SetAttribute", audit_log.kind);
+        assert_eq!(Some(ctx.change_set_id()), audit_log.change_set_id);
+        assert_eq!(Some("/domain/name".to_string()), audit_log.entity_name);
+    }
+
+    // Pages carry on from the cursor of the page before them.
+    let (first_page, cursor) = audit_logging::list_filtered(ctx, &context, &filter, None, 2, true)
+        .await
+        .expect("could not list filtered audit logs");
+    assert!(cursor.is_some());
+    let (second_page, cursor) =
+        audit_logging::list_filtered(ctx, &context, &filter, cursor, 2, true)
+            .await
+            .expect("could not list filtered audit logs");
+    assert_eq!(None, cursor);
+    assert_eq!(
+        audit_logs,
+        first_page
+            .into_iter()
+            .chain(second_page)
+            .collect::<Vec<_>>()
+    );
+
+    // Filters which do not match the entries exclude them.
+    let (no_audit_logs, cursor) = audit_logging::list_filtered(
+        ctx,
+        &context,
+        &AuditLogFilter {
+            kind: Some("DeleteComponent".to_string()),
+            ..filter.clone()
+        },
+        None,
+        SIZE,
+        false,
+    )
+    .await
+    .expect("could not list filtered audit logs");
+    assert!(no_audit_logs.is_empty());
+    assert_eq!(None, cursor);
+
+    let last_timestamp = audit_logs.last().expect("no audit logs found").timestamp;
+    let (no_audit_logs, _) = audit_logging::list_filtered(
+        ctx,
+        &context,
+        &AuditLogFilter {
+            since: Some(last_timestamp + chrono::Duration::seconds(1)),
+            ..filter.clone()
+        },
+        None,
+        SIZE,
+        false,
+    )
+    .await
+    .expect("could not list filtered audit logs");
+    assert!(no_audit_logs.is_empty());
+
+    let (until_audit_logs, _) = audit_logging::list_filtered(
+        ctx,
+        &context,
+        &AuditLogFilter {
+            until: Some(last_timestamp + chrono::Duration::seconds(1)),
+            ..filter
+        },
+        None,
+        SIZE,
+        false,
+    )
+    .await
+    .expect("could not list filtered audit logs");
+    assert_eq!(3, until_audit_logs.len());
+}
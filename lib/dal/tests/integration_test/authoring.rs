@@ -1,6 +1,7 @@
 mod clone_variant;
 mod create_variant;
 mod delete_unlocked_variant;
+mod export_definition;
 mod regenerate;
 mod save_variant;
 mod unlock_and_edit_variant;
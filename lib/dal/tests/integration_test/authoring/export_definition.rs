@@ -0,0 +1,100 @@
+use dal::{
+    DalContext,
+    Func,
+    schema::variant::authoring::VariantAuthoringClient,
+};
+use dal_test::test;
+
+#[test(enable_veritech)]
+async fn export_and_import_round_trip(ctx: &mut DalContext) {
+    let code = r#"
+        function main() {
+            const nameProp = new PropBuilder()
+                .setName("name")
+                .setKind("string")
+                .setWidget(new PropWidgetDefinitionBuilder().setKind("text").build())
+                .build();
+            const portsProp = new PropBuilder()
+                .setName("ports")
+                .setKind("array")
+                .setEntry(
+                    new PropBuilder()
+                        .setName("port")
+                        .setKind("integer")
+                        .build()
+                )
+                .build();
+            const outputSocket = new SocketDefinitionBuilder()
+                .setName("name")
+                .setArity("one")
+                .build();
+
+            return new AssetBuilder()
+                .addProp(nameProp)
+                .addProp(portsProp)
+                .addOutputSocket(outputSocket)
+                .build();
+        }
+    "#;
+
+    let original = VariantAuthoringClient::create_schema_and_variant_from_code(
+        ctx,
+        "roundTripOriginal",
+        Some("exported and re-imported".to_string()),
+        None,
+        "Integration Tests",
+        "#00b0b0",
+        code,
+    )
+    .await
+    .expect("unable to create variant from code");
+
+    let exported = VariantAuthoringClient::export_definition(ctx, original.id())
+        .await
+        .expect("unable to export definition");
+    assert_eq!(code, exported.code);
+    assert_eq!("roundTripOriginal", exported.metadata.schema_name);
+    assert_eq!(
+        Some("exported and re-imported".to_string()),
+        exported.metadata.description
+    );
+
+    // Schema names are unique, so import the definition under a new name
+    let mut definition = exported.clone();
+    definition.metadata.schema_name = "roundTripImported".to_string();
+    let imported = VariantAuthoringClient::import_definition(ctx, definition.clone())
+        .await
+        .expect("unable to import definition");
+    assert_ne!(original.id(), imported.id());
+
+    let reexported = VariantAuthoringClient::export_definition(ctx, imported.id())
+        .await
+        .expect("unable to export imported definition");
+    assert_eq!(definition, reexported);
+
+    // Both asset funcs should produce the same prop and socket tree
+    let original_func = Func::get_by_id(
+        ctx,
+        original
+            .asset_func_id()
+            .expect("original has no asset func"),
+    )
+    .await
+    .expect("unable to get original asset func");
+    let imported_func = Func::get_by_id(
+        ctx,
+        imported
+            .asset_func_id()
+            .expect("imported has no asset func"),
+    )
+    .await
+    .expect("unable to get imported asset func");
+    assert_eq!(
+        VariantAuthoringClient::execute_asset_func(ctx, &original_func)
+            .await
+            .expect("unable to execute original asset func"),
+        VariantAuthoringClient::execute_asset_func(ctx, &imported_func)
+            .await
+            .expect("unable to execute imported asset func"),
+    );
+}
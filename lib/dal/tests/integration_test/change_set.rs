@@ -10,7 +10,11 @@ use dal::{
     RequestContext,
     Workspace,
     WorkspacePk,
-    change_set::view::OpenChangeSetsView,
+    attribute::attributes,
+    change_set::{
+        APPLY_REBASE_BATCH_SIZE,
+        view::OpenChangeSetsView,
+    },
     context::TransactionsErrorDiscriminants,
 };
 use dal_test::{
@@ -18,9 +22,11 @@ use dal_test::{
         ChangeSetTestHelpers,
         create_component_for_default_schema_name_in_default_view,
         create_user,
+        update_attribute_value_for_component,
     },
     test,
 };
+use futures::StreamExt as _;
 use itertools::Itertools;
 use pretty_assertions_sorted::assert_eq;
 use si_db::HistoryActor;
@@ -632,3 +638,221 @@ async fn update_pointer_tracks_last_used(ctx: &mut DalContext) {
         .expect("could not get snapshot_id");
     assert_eq!(snapshot_id, old_snapshot.to_string());
 }
+
+#[test]
+async fn recently_modified_components(ctx: &mut DalContext) {
+    let first = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "first")
+        .await
+        .expect("could not create component");
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let second = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "second")
+        .await
+        .expect("could not create component");
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let third = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "third")
+        .await
+        .expect("could not create component");
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    // Modifying the first component should move it to the front
+    update_attribute_value_for_component(
+        ctx,
+        first.id(),
+        &["root", "si", "name"],
+        serde_json::json!("first but renamed"),
+    )
+    .await
+    .expect("could not update attribute value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let recent = ChangeSet::recently_modified_components(ctx, 10)
+        .await
+        .expect("could not list recently modified components");
+    assert_eq!(
+        vec![first.id(), third.id(), second.id()],
+        recent.iter().map(|component| component.id()).collect_vec()
+    );
+
+    // Unsetting a value counts as modifying the component too
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    attributes::update_attributes(
+        ctx,
+        second.id(),
+        serde_json::from_value(serde_json::json!({ "/domain/name": { "$source": null } }))
+            .expect("could not build attribute sources"),
+    )
+    .await
+    .expect("could not unset attribute value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let recent = ChangeSet::recently_modified_components(ctx, 2)
+        .await
+        .expect("could not list recently modified components");
+    assert_eq!(
+        vec![second.id(), first.id()],
+        recent.iter().map(|component| component.id()).collect_vec()
+    );
+}
+
+#[test]
+async fn apply_reports_progress_per_rebase_batch(ctx: &mut DalContext) {
+    let workspace_pk = ctx.workspace_pk().expect("could not get workspace pk");
+    let mut events = ctx
+        .nats_conn()
+        .subscribe(format!("si.workspace_pk.{workspace_pk}.event"))
+        .await
+        .expect("could not subscribe to workspace events");
+
+    // Enough components that applying them takes more than one rebase batch
+    let change_set_id = ctx.change_set_id();
+    for index in 0..40 {
+        create_component_for_default_schema_name_in_default_view(
+            ctx,
+            "swifty",
+            format!("applied {index}"),
+        )
+        .await
+        .expect("could not create component");
+    }
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+
+    let mut progress = Vec::new();
+    loop {
+        let message = tokio::time::timeout(std::time::Duration::from_secs(10), events.next())
+            .await
+            .expect("timed out waiting for the apply to complete")
+            .expect("workspace events ended");
+        let event: serde_json::Value =
+            serde_json::from_slice(message.payload()).expect("could not parse event");
+        if event["payload"]["kind"] != "ChangeSetApplyProgress" {
+            continue;
+        }
+        let data = event["payload"]["data"].clone();
+        assert_eq!(serde_json::json!(change_set_id), data["changeSetId"]);
+        let completed = data["completed"] == true;
+        progress.push(data);
+        if completed {
+            break;
+        }
+    }
+
+    // Progress is counted in the updates applied, from none through each batch to all of them
+    let total = progress[0]["total"].as_u64().expect("total is a number");
+    let batch_size = APPLY_REBASE_BATCH_SIZE as u64;
+    assert!(
+        total > batch_size,
+        "the apply should take more than one rebase batch"
+    );
+    let mut expected = vec![(0, false)];
+    expected.extend((1..=total.div_ceil(batch_size)).map(|batch| {
+        let processed = (batch * batch_size).min(total);
+        (processed, false)
+    }));
+    expected.push((total, true));
+    assert_eq!(
+        expected,
+        progress
+            .iter()
+            .map(|data| {
+                assert_eq!(total, data["total"]);
+                assert_eq!(serde_json::Value::Null, data["error"]);
+                (
+                    data["processed"].as_u64().expect("processed is a number"),
+                    data["completed"] == true,
+                )
+            })
+            .collect_vec()
+    );
+}
+
+#[test]
+async fn conflicts_with(ctx: &mut DalContext) {
+    let shared = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shared")
+        .await
+        .expect("could not create component");
+    let other = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "other")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+
+    // Two change sets editing the same component
+    let first = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    update_attribute_value_for_component(
+        ctx,
+        shared.id(),
+        &["root", "si", "name"],
+        serde_json::json!("renamed in first"),
+    )
+    .await
+    .expect("could not update attribute value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let second = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    update_attribute_value_for_component(
+        ctx,
+        shared.id(),
+        &["root", "si", "name"],
+        serde_json::json!("renamed in second"),
+    )
+    .await
+    .expect("could not update attribute value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    // And one editing a different component
+    let third = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    update_attribute_value_for_component(
+        ctx,
+        other.id(),
+        &["root", "si", "name"],
+        serde_json::json!("renamed in third"),
+    )
+    .await
+    .expect("could not update attribute value");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let conflicts = ChangeSet::conflicts_with(ctx, first.id)
+        .await
+        .expect("could not find conflicts");
+    assert_eq!(
+        vec![second.id],
+        conflicts
+            .iter()
+            .map(|conflict| conflict.change_set_id)
+            .collect_vec()
+    );
+    assert!(
+        conflicts[0]
+            .entity_ids
+            .iter()
+            .any(|entity_id| entity_id.into_inner() == shared.id().into_inner()),
+        "expected the shared component to be reported as conflicting"
+    );
+
+    let conflicts = ChangeSet::conflicts_with(ctx, third.id)
+        .await
+        .expect("could not find conflicts");
+    assert!(conflicts.is_empty(), "unexpected conflicts: {conflicts:?}");
+}
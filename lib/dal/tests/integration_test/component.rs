@@ -36,15 +36,20 @@ use pretty_assertions_sorted::assert_eq;
 
 mod autosubscribe;
 mod connectable_test;
+mod connection_issues;
 mod debug;
 mod delete;
 mod duplicate;
 mod get_code;
 mod get_diff;
+mod list_for_schema_variant;
 mod paste;
 mod property_order;
 mod set_type;
 mod upgrade;
+mod validate;
+mod values_diff;
+mod values_snapshot;
 
 #[test(enable_veritech)]
 async fn update_and_insert_and_update(ctx: &mut DalContext) -> Result<()> {
@@ -729,3 +734,49 @@ async fn default_values_appear_in_views(ctx: &mut DalContext) -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+async fn get_at_change_set(ctx: &mut DalContext) -> Result<()> {
+    let component_id = component::create(ctx, "swifty", "earlier").await?;
+    let earlier_change_set_id = ctx.change_set_id();
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+
+    // Rename the component and add another one in a later change set
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    let later_change_set_id = ctx.change_set_id();
+    Component::get_by_id(ctx, component_id)
+        .await?
+        .set_name(ctx, "later")
+        .await?;
+    let other_component_id = component::create(ctx, "swifty", "other").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let name = |view: Option<serde_json::Value>| {
+        view.expect("has a view")["si"]["name"]
+            .as_str()
+            .expect("has a name")
+            .to_owned()
+    };
+
+    assert_eq!(
+        "earlier",
+        name(Component::get_at_change_set(ctx, component_id, earlier_change_set_id).await?)
+    );
+    assert_eq!(
+        "later",
+        name(Component::get_at_change_set(ctx, component_id, later_change_set_id).await?)
+    );
+    assert_eq!(
+        None,
+        Component::get_at_change_set(ctx, other_component_id, earlier_change_set_id).await?
+    );
+
+    // Reading another change set leaves the context where it was
+    assert_eq!(later_change_set_id, ctx.change_set_id());
+    assert_eq!(
+        "later",
+        name(Component::view_by_id(ctx, component_id).await?)
+    );
+
+    Ok(())
+}
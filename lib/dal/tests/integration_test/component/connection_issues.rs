@@ -0,0 +1,94 @@
+use dal::{
+    AttributeValue,
+    Component,
+    DalContext,
+    PropKind,
+    component::connection::ConnectionIssue,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        attribute::value,
+        change_set,
+        component,
+        schema::variant,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+
+#[test(enable_veritech)]
+async fn connection_issues_reports_type_mismatch(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "testy",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Value", kind: "string" },
+                        { name: "Count", kind: "integer" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+
+    let subscriber_id = component::create(ctx, "testy", "subscriber").await?;
+    let source_id = component::create(ctx, "testy", "source").await?;
+
+    // A compatible connection is not an issue
+    value::subscribe(
+        ctx,
+        ("subscriber", "/domain/Value"),
+        ("source", "/domain/Value"),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+    assert!(
+        Component::connection_issues(ctx, subscriber_id)
+            .await?
+            .is_empty()
+    );
+
+    // Connecting a string to an integer is rejected, so skip the checks to create it anyway
+    assert!(
+        value::subscribe(
+            ctx,
+            ("subscriber", "/domain/Count"),
+            ("source", "/domain/Value"),
+        )
+        .await
+        .is_err()
+    );
+    value::subscribe_unchecked(
+        ctx,
+        ("subscriber", "/domain/Count"),
+        ("source", "/domain/Value"),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    let count_av_id = value::id(ctx, ("subscriber", "/domain/Count")).await?;
+    assert_eq!(
+        vec![ConnectionIssue::TypeMismatch {
+            attribute_value_id: count_av_id,
+            path: AttributeValue::get_path_for_id(ctx, count_av_id).await?,
+            prop_kind: PropKind::Integer,
+            source_component_id: source_id,
+            source_path: "/domain/Value".to_string(),
+            source_prop_kind: PropKind::String,
+        }],
+        Component::connection_issues(ctx, subscriber_id).await?
+    );
+
+    // The issue belongs to the subscriber, not the source
+    assert!(
+        Component::connection_issues(ctx, source_id)
+            .await?
+            .is_empty()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use dal::{
+    Component,
+    DalContext,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        change_set,
+        component,
+        schema::variant,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn list_for_schema_variant(ctx: &mut DalContext) -> Result<()> {
+    let nginx = component::create(ctx, "Docker Image", "nginx").await?;
+    let redis = component::create(ctx, "Docker Image", "redis").await?;
+    component::create(ctx, "swifty", "swifty").await?;
+    change_set::commit(ctx).await?;
+
+    let docker_image_variant_id = variant::id(ctx, "Docker Image").await?;
+    let mut expected = vec![nginx, redis];
+    expected.sort();
+
+    let component_ids: Vec<_> = Component::list_for_schema_variant(ctx, docker_image_variant_id)
+        .await?
+        .iter()
+        .map(|component| component.id())
+        .collect();
+    assert_eq!(expected, component_ids);
+
+    // Removing a component removes it from the list in this change set
+    Component::remove(ctx, redis).await?;
+    change_set::commit(ctx).await?;
+
+    let component_ids: Vec<_> = Component::list_for_schema_variant(ctx, docker_image_variant_id)
+        .await?
+        .iter()
+        .map(|component| component.id())
+        .collect();
+    assert_eq!(vec![nginx], component_ids);
+
+    Ok(())
+}
@@ -15,7 +15,10 @@ use dal::{
             ActionPrototype,
         },
     },
-    component::resource::ResourceData,
+    component::{
+        pin::ComponentUpgradeStatus,
+        resource::ResourceData,
+    },
     diagram::Diagram,
     func::authoring::FuncAuthoringClient,
     prop::PropPath,
@@ -1051,3 +1054,69 @@ async fn upgrade_without_value_changes_does_not_enqueue_update_action(
 
     Ok(())
 }
+
+#[test(enable_veritech)]
+async fn pinned_component_is_excluded_from_upgrade(ctx: &mut DalContext) -> Result<()> {
+    let pinned = component::create(ctx, "swifty", "pinned").await?;
+    let unpinned = component::create(ctx, "swifty", "unpinned").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let schema_id = schema::id(ctx, "swifty").await?;
+    let default_variant_id = Schema::default_variant_id(ctx, schema_id).await?;
+
+    // Pin one of the components before an upgrade becomes available
+    let pinned_component = Component::pin_to_variant(ctx, pinned, default_variant_id).await?;
+    assert_eq!(
+        Some(default_variant_id),
+        pinned_component.pinned_schema_variant_id()
+    );
+
+    let unlocked =
+        VariantAuthoringClient::create_unlocked_variant_copy(ctx, default_variant_id).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert!(!Component::can_be_upgraded_by_id(ctx, pinned).await?);
+    assert!(Component::can_be_upgraded_by_id(ctx, unpinned).await?);
+
+    let statuses = Component::upgrade_many(ctx, &[pinned, unpinned]).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(
+        vec![
+            (
+                pinned,
+                ComponentUpgradeStatus::Pinned {
+                    schema_variant_id: default_variant_id,
+                },
+            ),
+            (
+                unpinned,
+                ComponentUpgradeStatus::Upgradable {
+                    from_schema_variant_id: default_variant_id,
+                    to_schema_variant_id: unlocked.id(),
+                },
+            ),
+        ],
+        statuses
+    );
+
+    assert_eq!(
+        default_variant_id,
+        Component::schema_variant_id(ctx, pinned).await?
+    );
+    assert_eq!(
+        unlocked.id(),
+        Component::schema_variant_id(ctx, unpinned).await?
+    );
+
+    // Once unpinned, the component can be upgraded again
+    Component::unpin(ctx, pinned).await?;
+    assert_eq!(
+        ComponentUpgradeStatus::Upgradable {
+            from_schema_variant_id: default_variant_id,
+            to_schema_variant_id: unlocked.id(),
+        },
+        Component::upgrade_status(ctx, pinned).await?
+    );
+
+    Ok(())
+}
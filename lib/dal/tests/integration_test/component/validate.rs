@@ -0,0 +1,65 @@
+use dal::{
+    AttributeValue,
+    Component,
+    DalContext,
+    component::validation::ComponentValidationIssue,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        ChangeSetTestHelpers,
+        create_component_for_default_schema_name_in_default_view,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn validate_reports_missing_required_value(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Long John Silver")
+            .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let working_eyes_av_id = component
+        .attribute_values_for_prop(ctx, &["root", "domain", "working_eyes"])
+        .await?
+        .pop()
+        .expect("there should only be one value id");
+    let working_eyes_path = AttributeValue::get_path_for_id(ctx, working_eyes_av_id).await?;
+
+    // "working_eyes" is required, but has no value
+    let validation = Component::validate(ctx, component.id()).await?;
+    assert!(!validation.is_valid());
+    assert_eq!(
+        vec![ComponentValidationIssue::RequiredValueMissing {
+            attribute_value_id: working_eyes_av_id,
+            path: working_eyes_path.clone(),
+        }],
+        validation.issues
+    );
+
+    // A value which is set, but fails validation, is reported differently
+    AttributeValue::update(ctx, working_eyes_av_id, Some(json!(3))).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let validation = Component::validate(ctx, component.id()).await?;
+    assert_eq!(
+        vec![ComponentValidationIssue::InvalidValue {
+            attribute_value_id: working_eyes_av_id,
+            path: working_eyes_path,
+            message: Some("\"value\" must be less than or equal to 2".to_string()),
+        }],
+        validation.issues
+    );
+
+    // Once the value is valid, so is the component
+    AttributeValue::update(ctx, working_eyes_av_id, Some(json!(1))).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let validation = Component::validate(ctx, component.id()).await?;
+    assert!(validation.is_valid());
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use dal::{
+    Component,
+    DalContext,
+    component::values_diff::ComponentValueDifference,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        attribute::value,
+        change_set,
+        component,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn diff_values(ctx: &mut DalContext) -> Result<()> {
+    let nginx = component::create(ctx, "Docker Image", "nginx").await?;
+    let redis = component::create(ctx, "Docker Image", "redis").await?;
+    value::set(ctx, (nginx, "/domain/ExposedPorts/-"), "80/tcp").await?;
+    value::set(ctx, (redis, "/domain/image"), "redis:latest").await?;
+    change_set::commit(ctx).await?;
+
+    let differences = Component::diff_values(ctx, nginx, redis).await?;
+    let difference_at = |path: &str| {
+        differences
+            .iter()
+            .find(|difference| difference.path() == path)
+            .cloned()
+    };
+
+    assert_eq!(
+        Some(ComponentValueDifference::Differing {
+            path: "/si/name".to_string(),
+            a: json!("nginx"),
+            b: json!("redis"),
+        }),
+        difference_at("/si/name")
+    );
+    assert_eq!(
+        Some(ComponentValueDifference::Differing {
+            path: "/domain/image".to_string(),
+            a: json!("nginx"),
+            b: json!("redis:latest"),
+        }),
+        difference_at("/domain/image")
+    );
+    assert_eq!(
+        Some(ComponentValueDifference::OnlyInA {
+            path: "/domain/ExposedPorts/0".to_string(),
+            value: json!("80/tcp"),
+        }),
+        difference_at("/domain/ExposedPorts/0")
+    );
+
+    // Values the components share are not part of the diff
+    assert_eq!(None, difference_at("/si/type"));
+
+    // Swapping the components swaps the sides of the diff
+    let swapped = Component::diff_values(ctx, redis, nginx).await?;
+    assert_eq!(
+        Some(ComponentValueDifference::OnlyInB {
+            path: "/domain/ExposedPorts/0".to_string(),
+            value: json!("80/tcp"),
+        }),
+        swapped
+            .iter()
+            .find(|difference| difference.path() == "/domain/ExposedPorts/0")
+            .cloned()
+    );
+    assert_eq!(differences.len(), swapped.len());
+
+    // A component has no differences with itself
+    assert!(Component::diff_values(ctx, nginx, nginx).await?.is_empty());
+
+    Ok(())
+}
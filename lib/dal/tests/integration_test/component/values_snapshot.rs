@@ -0,0 +1,89 @@
+use dal::{
+    Component,
+    ComponentError,
+    DalContext,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        attribute::value,
+        change_set,
+        component,
+    },
+    test,
+};
+use pretty_assertions_sorted::{
+    assert_eq,
+    assert_ne,
+};
+use serde_json::json;
+
+#[test]
+async fn snapshot_and_restore_values(ctx: &mut DalContext) -> Result<()> {
+    let docker_image = component::create(ctx, "Docker Image", "nginx").await?;
+    change_set::commit(ctx).await?;
+
+    let original_domain = value::get(ctx, (docker_image, "/domain")).await?;
+    let snapshot = Component::snapshot_values(ctx, docker_image).await?;
+
+    // Rename the component, override a derived value and add an array entry
+    value::set(ctx, (docker_image, "/si/name"), "redis").await?;
+    value::set(ctx, (docker_image, "/domain/image"), "redis:latest").await?;
+    value::set(ctx, (docker_image, "/domain/ExposedPorts/-"), "80/tcp").await?;
+    change_set::commit(ctx).await?;
+
+    assert_ne!(
+        original_domain,
+        value::get(ctx, (docker_image, "/domain")).await?
+    );
+
+    Component::restore_values(ctx, docker_image, &snapshot).await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(
+        "nginx",
+        Component::name_by_id(ctx, docker_image).await?.as_str()
+    );
+    assert_eq!(
+        json!("nginx"),
+        value::get(ctx, (docker_image, "/domain/image")).await?
+    );
+    assert!(!value::has_value(ctx, (docker_image, "/domain/ExposedPorts/0")).await?);
+    assert_eq!(
+        original_domain,
+        value::get(ctx, (docker_image, "/domain")).await?
+    );
+    assert_eq!(
+        snapshot,
+        Component::snapshot_values(ctx, docker_image).await?
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn restore_values_rejects_a_different_schema_variant(ctx: &mut DalContext) -> Result<()> {
+    let docker_image = component::create(ctx, "Docker Image", "nginx").await?;
+    let swifty = component::create(ctx, "swifty", "taylor").await?;
+    change_set::commit(ctx).await?;
+
+    let snapshot = Component::snapshot_values(ctx, docker_image).await?;
+    let docker_image_variant = Component::schema_variant_id(ctx, docker_image).await?;
+    let swifty_variant = Component::schema_variant_id(ctx, swifty).await?;
+
+    match Component::restore_values(ctx, swifty, &snapshot).await {
+        Err(ComponentError::CannotRestoreValuesFromDifferentVariant(
+            component_id,
+            captured,
+            current,
+        )) => {
+            assert_eq!(swifty, component_id);
+            assert_eq!(docker_image_variant, captured);
+            assert_eq!(swifty_variant, current);
+        }
+        other => panic!("expected a schema variant mismatch, got: {other:?}"),
+    }
+    assert_eq!("taylor", Component::name_by_id(ctx, swifty).await?.as_str());
+
+    Ok(())
+}
@@ -165,3 +165,54 @@ async fn component_concurrency_limit(ctx: &mut DalContext) -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+async fn pending_values_include_downstream_components(ctx: &mut DalContext) -> Result<()> {
+    let source = component::create(ctx, "Docker Image", "source").await?;
+    let downstream = component::create(ctx, "Docker Image", "downstream").await?;
+    let unrelated = component::create(ctx, "Docker Image", "unrelated").await?;
+    value::subscribe(
+        ctx,
+        ("downstream", "/domain/image"),
+        ("source", "/domain/image"),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+    assert!(
+        Component::pending_dependent_value_ids(ctx, downstream)
+            .await?
+            .is_empty()
+    );
+
+    // The only root is on the source, but the downstream value depends on it
+    value::set(ctx, ("source", "/domain/image"), "nginx").await?;
+
+    assert!(
+        !Component::pending_dependent_value_ids(ctx, source)
+            .await?
+            .is_empty()
+    );
+    assert!(
+        Component::pending_dependent_value_ids(ctx, downstream)
+            .await?
+            .contains(&value::id(ctx, ("downstream", "/domain/image")).await?)
+    );
+    assert!(
+        Component::pending_dependent_value_ids(ctx, unrelated)
+            .await?
+            .is_empty()
+    );
+
+    change_set::commit(ctx).await?;
+    assert!(
+        Component::pending_dependent_value_ids(ctx, downstream)
+            .await?
+            .is_empty()
+    );
+    assert_eq!(
+        "nginx",
+        value::get(ctx, ("downstream", "/domain/image")).await?
+    );
+
+    Ok(())
+}
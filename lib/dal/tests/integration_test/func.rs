@@ -14,6 +14,7 @@ use pretty_assertions_sorted::assert_eq;
 
 mod argument;
 mod authoring;
+mod cancel;
 mod debug;
 
 #[test]
@@ -1,4 +1,7 @@
-use std::time::Duration;
+use std::time::{
+    Duration,
+    Instant,
+};
 
 use dal::{
     DalContext,
@@ -17,6 +20,7 @@ use si_events::{
     FuncRun,
     FuncRunId,
     FuncRunState,
+    FuncRunTimings,
 };
 
 #[test(enable_veritech)]
@@ -230,6 +234,78 @@ async fn test_execute_with_modified_code(ctx: &mut DalContext) {
     );
 }
 
+#[test(enable_veritech)]
+async fn test_execute_records_timings(ctx: &mut DalContext) {
+    let component_name = "Timed";
+    let func_name = "test:falloutEntriesToGalaxies";
+    let func_args = serde_json::Value::Array(Vec::new());
+    let schema_name = "starfield";
+
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, schema_name, component_name)
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let func_id = Func::find_id_by_name(ctx, func_name)
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+
+    // Perform the test execution.
+    let started_at = Instant::now();
+    let func_run_id =
+        FuncAuthoringClient::test_execute_func(ctx, func_id, func_args, None, component.id())
+            .await
+            .expect("could not perform test execution for func");
+    wait_for_func_run_with_success_state(ctx, func_run_id).await;
+    let timings = wait_for_func_run_timings(ctx, func_run_id).await;
+    let elapsed_us = u64::try_from(started_at.elapsed().as_micros()).expect("elapsed fits in u64");
+
+    // Check the results.
+    assert!(timings.prepare_us > 0, "prepare should be timed");
+    assert!(
+        timings.critical_section_us > 0,
+        "critical section should be timed"
+    );
+    assert!(timings.postprocess_us > 0, "postprocess should be timed");
+    assert_eq!(
+        timings.prepare_us
+            + timings.critical_section_us
+            + timings.output_drain_us
+            + timings.postprocess_us,
+        timings.total_us()
+    );
+    assert!(
+        timings.total_us() <= elapsed_us,
+        "phases ({}us) should not take longer than the whole execution ({elapsed_us}us)",
+        timings.total_us()
+    );
+    assert_eq!(
+        timings.total_us() - timings.critical_section_us,
+        timings.overhead_us()
+    );
+}
+
+async fn wait_for_func_run_timings(ctx: &DalContext, func_run_id: FuncRunId) -> FuncRunTimings {
+    let seconds = 15;
+
+    for _ in 0..(seconds * 10) {
+        if let Some(timings) = FuncRunDb::timings(ctx, func_run_id)
+            .await
+            .expect("could not read func run timings")
+        {
+            return timings;
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    panic!("timed out waiting for func run timings");
+}
+
 async fn wait_for_func_run_with_success_state(ctx: &DalContext, func_run_id: FuncRunId) -> FuncRun {
     let seconds = 15;
 
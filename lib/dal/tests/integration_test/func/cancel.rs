@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use dal::{
+    Component,
+    DalContext,
+    Func,
+    func::runner::{
+        FuncRunner,
+        FuncRunnerError,
+    },
+};
+use dal_test::{
+    helpers::{
+        ChangeSetTestHelpers,
+        create_component_for_default_schema_name_in_default_view,
+    },
+    test,
+};
+use si_db::FuncRunDb;
+use si_events::FuncRunState;
+use veritech_client::ComponentKind;
+
+async fn debug_args(ctx: &DalContext, component: &Component) -> serde_json::Value {
+    let properties = component.view(ctx).await.expect("get component view");
+    serde_json::json!({ "debug_input": null, "component": {
+        "kind": ComponentKind::Standard,
+        "properties": properties,
+        "id": component.id(),
+    }})
+}
+
+#[test(enable_veritech)]
+async fn cancel_long_running_execution(ctx: &mut DalContext) {
+    let long_running_func = Func::new_debug(
+        "long_running",
+        r#"async function debug() {
+            await new Promise((resolve) => setTimeout(resolve, 600000));
+            return { finished: true };
+        }"#,
+        "debug",
+    );
+    let quick_func = Func::new_debug(
+        "quick",
+        r#"function debug() {
+            return { finished: true };
+        }"#,
+        "debug",
+    );
+
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "runaway")
+            .await
+            .expect("create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot");
+    let args = debug_args(ctx, &component).await;
+
+    let (func_run_id, result_rx) =
+        FuncRunner::run_debug(ctx, long_running_func, component.id(), args.clone())
+            .await
+            .expect("run long running func");
+
+    // Wait for the execution to be picked up by veritech
+    let mut attempts = 0;
+    loop {
+        let state = FuncRunDb::try_read(ctx, func_run_id)
+            .await
+            .expect("read func run")
+            .state();
+        if state == FuncRunState::Running {
+            break;
+        }
+
+        attempts += 1;
+        if attempts > 100 {
+            panic!("execution never started running, last state: {state:?}");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    FuncRunner::cancel_execution(ctx, func_run_id)
+        .await
+        .expect("cancel execution");
+
+    // The execution ends well before the function would have finished on its own
+    let result = tokio::time::timeout(Duration::from_secs(30), result_rx)
+        .await
+        .expect("execution should end once cancelled")
+        .expect("should receive execution result");
+    assert!(result.is_err(), "cancelled execution should not succeed");
+    assert_eq!(
+        FuncRunState::Killed,
+        FuncRunDb::try_read(ctx, func_run_id)
+            .await
+            .expect("read func run")
+            .state()
+    );
+
+    // Only in-progress executions can be cancelled
+    assert!(matches!(
+        FuncRunner::cancel_execution(ctx, func_run_id).await,
+        Err(FuncRunnerError::FuncRunNotInProgress(id, FuncRunState::Killed)) if id == func_run_id
+    ));
+
+    // The instance was freed, so further executions still run
+    let (_, result_rx) = FuncRunner::run_debug(ctx, quick_func, component.id(), args)
+        .await
+        .expect("run quick func");
+    let mut value = tokio::time::timeout(Duration::from_secs(30), result_rx)
+        .await
+        .expect("quick execution should end")
+        .expect("should receive execution result")
+        .expect("quick execution should succeed");
+    assert_eq!(
+        serde_json::json!(true),
+        value.take_value().expect("should have a value")["output"]["finished"]
+    );
+}
@@ -0,0 +1,67 @@
+use dal::{
+    Component,
+    DalContext,
+    Ulid,
+    workspace_snapshot::{
+        NodeId,
+        NodeInformation,
+        edge_weight::EdgeWeightKindDiscriminants,
+        graph_export::GraphExportEdge,
+        node_weight::NodeWeightDiscriminants,
+    },
+};
+use dal_test::{
+    Result,
+    helpers::{
+        ChangeSetTestHelpers,
+        component,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+
+fn node_id(id: impl Into<Ulid>) -> NodeId {
+    id.into().into()
+}
+
+#[test]
+async fn export_graph_contains_component(ctx: &mut DalContext) -> Result<()> {
+    let component_id = component::create(ctx, "swifty", "export me").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+    let root_attribute_value_id = Component::root_attribute_value_id(ctx, component_id).await?;
+
+    let graph_export = ctx.workspace_snapshot()?.export_graph().await?;
+
+    assert!(graph_export.nodes.contains(&NodeInformation {
+        node_weight_kind: NodeWeightDiscriminants::Component,
+        id: node_id(component_id),
+    }));
+    assert!(graph_export.nodes.contains(&NodeInformation {
+        node_weight_kind: NodeWeightDiscriminants::AttributeValue,
+        id: node_id(root_attribute_value_id),
+    }));
+    assert!(graph_export.edges.contains(&GraphExportEdge {
+        source: node_id(component_id),
+        target: node_id(schema_variant_id),
+        kind: EdgeWeightKindDiscriminants::Use,
+    }));
+    assert!(graph_export.edges.contains(&GraphExportEdge {
+        source: node_id(component_id),
+        target: node_id(root_attribute_value_id),
+        kind: EdgeWeightKindDiscriminants::Root,
+    }));
+
+    // Exporting is stable, and every edge is between exported nodes
+    assert_eq!(
+        graph_export,
+        ctx.workspace_snapshot()?.export_graph().await?
+    );
+    for edge in &graph_export.edges {
+        assert!(graph_export.nodes.iter().any(|node| node.id == edge.source));
+        assert!(graph_export.nodes.iter().any(|node| node.id == edge.target));
+    }
+
+    Ok(())
+}
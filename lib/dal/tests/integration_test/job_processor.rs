@@ -0,0 +1,127 @@
+use dal::{
+    AttributeValueId,
+    DalContext,
+    job::{
+        consumer::DalJob,
+        definition::DependentValuesUpdate,
+        processor::{
+            JobPriority,
+            JobQueueProcessor,
+            NatsProcessor,
+        },
+        producer::BlockingJobError,
+        queue::JobQueue,
+    },
+};
+use dal_test::test;
+use pinga_client::dead_letter_header;
+use pinga_core::nats::{
+    pinga_dead_letter_stream,
+    subject,
+};
+use si_data_nats::jetstream;
+use tokio_util::sync::CancellationToken;
+
+#[test]
+async fn blocking_jobs_are_not_waited_on_after_shutdown(ctx: &DalContext) {
+    let shutdown_token = CancellationToken::new();
+    let processor = NatsProcessor::new(ctx.nats_conn().clone())
+        .await
+        .expect("could not create processor")
+        .with_shutdown_token(shutdown_token.clone());
+
+    shutdown_token.cancel();
+    let result = processor
+        .block_on_job(DependentValuesUpdate::new(
+            ctx.workspace_pk().expect("could not get workspace pk"),
+            ctx.change_set_id(),
+        ))
+        .await;
+
+    assert!(
+        matches!(result, Err(BlockingJobError::Cancelled)),
+        "expected the blocking job to be cancelled, got {result:?}"
+    );
+}
+
+#[test]
+async fn jobs_too_large_to_dispatch_are_dead_lettered(ctx: &DalContext) {
+    let nats = ctx.nats_conn().clone();
+    let processor = NatsProcessor::new(nats.clone())
+        .await
+        .expect("could not create processor");
+    let workspace_id = ctx.workspace_pk().expect("could not get workspace pk");
+
+    // Every id takes more than 20 bytes to serialize, so the validation job can not fit in one
+    // message, while the job after it can
+    let queue = JobQueue::default();
+    for _ in 0..=nats.server_info().max_payload / 20 {
+        queue
+            .enqueue_validation_job(
+                workspace_id,
+                ctx.change_set_id(),
+                AttributeValueId::new(),
+                JobPriority::Normal,
+            )
+            .await;
+    }
+    queue
+        .enqueue_dependent_values_update_job(workspace_id, ctx.change_set_id(), JobPriority::Normal)
+        .await;
+
+    processor
+        .process_queue(queue)
+        .await
+        .expect("the rest of the queue should be dispatched");
+
+    let context = jetstream::new(nats.clone());
+    let dead_letter_subject = subject::dead_letter(context.metadata().subject_prefix());
+    let dead_letter = pinga_dead_letter_stream(&context)
+        .await
+        .expect("could not get dead letter stream")
+        .get_last_raw_message_by_subject(dead_letter_subject.as_str())
+        .await
+        .expect("job was not dead lettered");
+    let header = |name| {
+        dead_letter
+            .headers
+            .get(name)
+            .map(|value| value.as_str().to_owned())
+    };
+    assert_eq!(
+        Some("Validation".to_owned()),
+        header(dead_letter_header::JOB_KIND)
+    );
+    assert_eq!(
+        Some(workspace_id.to_string()),
+        header(dead_letter_header::WORKSPACE_ID)
+    );
+    assert_eq!(
+        Some(ctx.change_set_id().to_string()),
+        header(dead_letter_header::CHANGE_SET_ID)
+    );
+    assert!(
+        header(dead_letter_header::ERROR)
+            .is_some_and(|error| error.contains("exceeds the nats max payload"))
+    );
+}
+
+#[test]
+async fn queued_jobs_keep_the_highest_priority_they_were_enqueued_with(ctx: &DalContext) {
+    let workspace_id = ctx.workspace_pk().expect("could not get workspace pk");
+
+    let queue = JobQueue::default();
+    queue
+        .enqueue_dependent_values_update_job(workspace_id, ctx.change_set_id(), JobPriority::Normal)
+        .await;
+    queue
+        .enqueue_dependent_values_update_job(workspace_id, ctx.change_set_id(), JobPriority::High)
+        .await;
+    queue
+        .enqueue_dependent_values_update_job(workspace_id, ctx.change_set_id(), JobPriority::Normal)
+        .await;
+
+    let job = queue.pop_job().await.expect("job was not queued");
+    assert_eq!(JobPriority::High, job.priority());
+    assert!(queue.pop_job().await.is_none());
+}
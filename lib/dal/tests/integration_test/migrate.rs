@@ -37,7 +37,7 @@ async fn migrate_to_split_graph(ctx: &mut DalContext) -> Result<()> {
     ctx.update_snapshot_to_visibility().await?;
 
     let snapshot_address = ctx.workspace_snapshot()?.id().await;
-    let mut migrator = dal::workspace_snapshot::migrator::SnapshotGraphMigrator::new();
+    let migrator = dal::workspace_snapshot::migrator::SnapshotGraphMigrator::new();
 
     let new_address = migrator.migrate_snapshot(ctx, snapshot_address).await?;
 
@@ -12,7 +12,9 @@ mod dependent_values_update;
 mod deserialize;
 mod diagram;
 mod func;
+mod graph_export;
 mod input_sources;
+mod job_processor;
 mod management;
 mod materialized_views;
 mod migrate;
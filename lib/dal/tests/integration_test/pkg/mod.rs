@@ -17,6 +17,7 @@ use dal::{
     module::Module,
     pkg::{
         ImportOptions,
+        PkgError,
         Thing,
         ThingMap,
         UpdateExisting,
@@ -24,6 +25,7 @@ use dal::{
         import_func,
         import_funcs_for_module_update,
         import_pkg_from_pkg,
+        import_pkgs,
     },
     prop::PropPath,
     schema::variant::authoring::VariantAuthoringClient,
@@ -42,6 +44,7 @@ use si_pkg::{
     SchemaSpec,
     SchemaSpecData,
     SiPkg,
+    SiPkgKind,
 };
 
 #[test(enable_veritech)]
@@ -497,3 +500,46 @@ async fn import_func_with_update_existing_updates_code(ctx: &mut DalContext) ->
 
     Ok(())
 }
+
+#[test]
+async fn import_pkgs_rolls_back_on_failure(ctx: &mut DalContext) -> Result<()> {
+    let good_pkg = SiPkg::load_from_spec(
+        PkgSpec::builder()
+            .name("good package")
+            .created_by("sally@systeminit.com")
+            .version("0")
+            .build()?,
+    )?;
+    let bad_pkg = SiPkg::load_from_spec(
+        PkgSpec::builder()
+            .kind(SiPkgKind::WorkspaceBackup)
+            .name("bad package")
+            .created_by("sally@systeminit.com")
+            .version("0")
+            .build()?,
+    )?;
+    let good_pkg_hash = good_pkg.hash()?.to_string();
+
+    let result = import_pkgs(ctx, &[good_pkg.clone(), bad_pkg], None).await;
+    assert!(matches!(
+        result,
+        Err(PkgError::ImportPkgsFailed(ref pkg_name, _)) if pkg_name == "bad package"
+    ));
+
+    // The good package was imported before the failure, but must have been rolled back
+    assert!(
+        Module::find_by_root_hash(ctx, &good_pkg_hash)
+            .await?
+            .is_none()
+    );
+
+    // On its own, the good package imports successfully
+    import_pkgs(ctx, &[good_pkg], None).await?;
+    assert!(
+        Module::find_by_root_hash(ctx, &good_pkg_hash)
+            .await?
+            .is_some()
+    );
+
+    Ok(())
+}
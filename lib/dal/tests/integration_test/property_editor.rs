@@ -1,5 +1,6 @@
 use dal::{
     AttributeValue,
+    ComponentId,
     DalContext,
     Schema,
     SchemaVariant,
@@ -9,14 +10,20 @@ use dal::{
     },
 };
 use dal_test::{
+    Result,
     helpers::{
         ChangeSetTestHelpers,
+        PropDiff,
         PropEditorTestView,
+        attribute::value,
+        change_set,
+        component,
         create_component_for_default_schema_name_in_default_view,
         create_component_for_schema_variant_on_default_view,
     },
     test,
 };
+use pretty_assertions_sorted::assert_eq;
 use serde_json::json;
 
 #[test]
@@ -302,6 +309,128 @@ async fn array_map_manipulation(ctx: &DalContext) {
     assert_eq!(treasure_second_item_key, Some("nyc".to_string()));
 }
 
+/// The differences between the views of two components beneath the given path.
+async fn diff_beneath(
+    ctx: &DalContext,
+    before: ComponentId,
+    after: ComponentId,
+    path: &str,
+) -> Result<Vec<PropDiff>> {
+    let before = PropEditorTestView::for_component_id(ctx, before).await?;
+    let after = PropEditorTestView::for_component_id(ctx, after).await?;
+
+    Ok(before
+        .diff(&after)
+        .into_iter()
+        .filter(|diff| match diff {
+            PropDiff::Added {
+                path: diff_path, ..
+            }
+            | PropDiff::Changed {
+                path: diff_path, ..
+            }
+            | PropDiff::Removed {
+                path: diff_path, ..
+            } => diff_path.starts_with(path),
+        })
+        .collect())
+}
+
+#[test]
+async fn diff_reports_an_inserted_array_element_once(ctx: &mut DalContext) -> Result<()> {
+    let before = component::create(ctx, "pirate", "before").await?;
+    let after = component::create(ctx, "pirate", "after").await?;
+    value::set(
+        ctx,
+        ("before", "/domain/parrot_names"),
+        json!(["tabitha", "jessica", "amanda"]),
+    )
+    .await?;
+    value::set(
+        ctx,
+        ("after", "/domain/parrot_names"),
+        json!(["tabitha", "samantha", "jessica", "amanda"]),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(
+        vec![PropDiff::Added {
+            path: "root/domain/parrot_names/1".to_string(),
+            value: json!("samantha"),
+        }],
+        diff_beneath(ctx, before, after, "root/domain/parrot_names").await?
+    );
+    Ok(())
+}
+
+#[test]
+async fn diff_reports_a_removed_array_element_once(ctx: &mut DalContext) -> Result<()> {
+    let before = component::create(ctx, "pirate", "before").await?;
+    let after = component::create(ctx, "pirate", "after").await?;
+    value::set(
+        ctx,
+        ("before", "/domain/parrot_names"),
+        json!(["tabitha", "samantha", "jessica", "amanda"]),
+    )
+    .await?;
+    value::set(
+        ctx,
+        ("after", "/domain/parrot_names"),
+        json!(["tabitha", "jessica", "amanda"]),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(
+        vec![PropDiff::Removed {
+            path: "root/domain/parrot_names/1".to_string(),
+            value: json!("samantha"),
+        }],
+        diff_beneath(ctx, before, after, "root/domain/parrot_names").await?
+    );
+    Ok(())
+}
+
+#[test]
+async fn diff_compares_map_entries_by_key(ctx: &mut DalContext) -> Result<()> {
+    let before = component::create(ctx, "pirate", "before").await?;
+    let after = component::create(ctx, "pirate", "after").await?;
+    value::set(
+        ctx,
+        ("before", "/domain/treasure"),
+        json!({ "ohio": "cheese", "rio": "coxinha", "tokyo": "sushi" }),
+    )
+    .await?;
+    value::set(
+        ctx,
+        ("after", "/domain/treasure"),
+        json!({ "nyc": "pizza", "rio": "feijoada", "tokyo": "sushi" }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(
+        vec![
+            PropDiff::Added {
+                path: "root/domain/treasure/nyc".to_string(),
+                value: json!("pizza"),
+            },
+            PropDiff::Removed {
+                path: "root/domain/treasure/ohio".to_string(),
+                value: json!("cheese"),
+            },
+            PropDiff::Changed {
+                path: "root/domain/treasure/rio".to_string(),
+                before: json!("coxinha"),
+                after: json!("feijoada"),
+            },
+        ],
+        diff_beneath(ctx, before, after, "root/domain/treasure").await?
+    );
+    Ok(())
+}
+
 #[test]
 async fn override_value_then_reset(ctx: &mut DalContext) {
     let original_pirate_name = "Thomas Cavendish";
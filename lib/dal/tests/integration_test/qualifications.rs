@@ -115,3 +115,65 @@ fn replace_output_stream_view_line_contents(view: QualificationView) -> Qualific
     }
     view
 }
+
+#[test(enable_veritech)]
+async fn run_all_qualifications(ctx: &mut DalContext) {
+    let failing_component = create_component_for_default_schema_name_in_default_view(
+        ctx,
+        "dummy-secret",
+        "no secret, no qualification",
+    )
+    .await
+    .expect("could not create component");
+    let passing_component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "fearless")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let runs = Component::run_all_qualifications(ctx, 2)
+        .await
+        .expect("could not run qualifications");
+
+    // Every component in the change set has a result
+    let mut component_ids = Component::list_ids(ctx)
+        .await
+        .expect("could not list components");
+    component_ids.sort();
+    assert_eq!(component_ids, runs.keys().copied().collect::<Vec<_>>());
+
+    // The dummy secret qualification fails without a secret
+    let failing_run = runs
+        .get(&failing_component.id())
+        .expect("failing component should have a result");
+    assert!(!failing_run.passed);
+    assert_eq!(
+        vec![(
+            "test:qualificationDummySecretStringIsTodd".to_string(),
+            QualificationSubCheckStatus::Failure
+        )],
+        failing_run
+            .results
+            .iter()
+            .map(|result| (result.name.clone(), result.status))
+            .collect::<Vec<_>>()
+    );
+
+    let passing_run = runs
+        .get(&passing_component.id())
+        .expect("passing component should have a result");
+    assert!(passing_run.passed);
+    assert_eq!(
+        vec![(
+            "test:swiftyQualification".to_string(),
+            QualificationSubCheckStatus::Success
+        )],
+        passing_run
+            .results
+            .iter()
+            .map(|result| (result.name.clone(), result.status))
+            .collect::<Vec<_>>()
+    );
+}
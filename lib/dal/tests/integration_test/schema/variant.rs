@@ -246,6 +246,59 @@ async fn all_funcs(ctx: &DalContext) {
     assert_eq!(expected, actual);
 }
 
+#[test]
+async fn bound_funcs(ctx: &DalContext) {
+    fn names(funcs: &[Func]) -> Vec<&str> {
+        funcs.iter().map(|func| func.name.as_str()).collect()
+    }
+
+    let schema_variant_id = SchemaVariant::default_id_for_schema_name(ctx, "swifty")
+        .await
+        .expect("unable to get schema variant");
+    let bound_funcs = SchemaVariant::bound_funcs(ctx, schema_variant_id)
+        .await
+        .expect("unable to get bound funcs");
+
+    assert_eq!(
+        vec![
+            "test:createActionSwifty",
+            "test:deleteActionSwifty",
+            "test:refreshActionSwifty",
+            "test:updateActionSwifty",
+        ],
+        names(&bound_funcs.actions)
+    );
+    assert!(bound_funcs.attributes.is_empty());
+    assert!(bound_funcs.authentication.is_empty());
+    assert_eq!(
+        vec!["test:generateCode"],
+        names(&bound_funcs.code_generation)
+    );
+    assert!(bound_funcs.management.is_empty());
+    assert_eq!(
+        vec!["test:swiftyQualification"],
+        names(&bound_funcs.qualifications)
+    );
+
+    let schema_variant_id = SchemaVariant::default_id_for_schema_name(ctx, "starfield")
+        .await
+        .expect("unable to get schema variant");
+    let bound_funcs = SchemaVariant::bound_funcs(ctx, schema_variant_id)
+        .await
+        .expect("unable to get bound funcs");
+
+    assert_eq!(
+        vec!["test:createActionStarfield", "test:refreshActionStarfield"],
+        names(&bound_funcs.actions)
+    );
+    assert_eq!(
+        vec!["hesperus_is_phosphorus", "test:falloutEntriesToGalaxies"],
+        names(&bound_funcs.attributes)
+    );
+    assert!(bound_funcs.code_generation.is_empty());
+    assert!(bound_funcs.qualifications.is_empty());
+}
+
 #[test]
 async fn list_user_facing_works(ctx: &DalContext) {
     SchemaVariant::list_user_facing(ctx)
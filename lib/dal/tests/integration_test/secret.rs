@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use dal::{
     Component,
     DalContext,
@@ -975,3 +977,71 @@ async fn secret_definition_daisy_chain_subscriptions(ctx: &mut DalContext, nw: &
         );
     }
 }
+
+#[test]
+async fn list_needing_rotation(ctx: &DalContext, nw: &WorkspaceSignup) -> Result<()> {
+    let older = Secret::new(
+        ctx,
+        generate_fake_name()?,
+        "Mock".to_owned(),
+        None,
+        "older-crypted-bytes".as_bytes(),
+        nw.key_pair.pk(),
+        SecretVersion::V1,
+        SecretAlgorithm::Sealedbox,
+    )
+    .await?;
+    let newer = Secret::new(
+        ctx,
+        generate_fake_name()?,
+        "Mock".to_owned(),
+        None,
+        "newer-crypted-bytes".as_bytes(),
+        nw.key_pair.pk(),
+        SecretVersion::V1,
+        SecretAlgorithm::Sealedbox,
+    )
+    .await?;
+
+    let ids = |secrets: Vec<Secret>| secrets.iter().map(Secret::id).collect::<Vec<_>>();
+
+    // Check as of when the newer secret was created, rather than waiting for time to pass
+    let now = newer.timestamp().updated_at;
+    let age_of_older = (now - older.timestamp().updated_at).to_std()?;
+    assert!(age_of_older > Duration::ZERO);
+
+    // Only the older secret is past the cutoff
+    assert_eq!(
+        vec![older.id()],
+        ids(Secret::list_needing_rotation_as_of(ctx, age_of_older, now).await?)
+    );
+
+    // Everything is past a cutoff of zero, oldest first, and nothing is past a very long one
+    assert_eq!(
+        vec![older.id(), newer.id()],
+        ids(Secret::list_needing_rotation_as_of(ctx, Duration::ZERO, now).await?)
+    );
+    assert!(
+        Secret::list_needing_rotation(ctx, Duration::from_secs(60 * 60 * 24 * 365))
+            .await?
+            .is_empty()
+    );
+
+    // Rotating the older secret's encrypted contents makes it the newest
+    let rotated = older
+        .update_encrypted_contents(
+            ctx,
+            "rotated-crypted-bytes".as_bytes(),
+            nw.key_pair.pk(),
+            SecretVersion::V1,
+            SecretAlgorithm::Sealedbox,
+        )
+        .await?;
+    assert!(rotated.timestamp().updated_at > newer.timestamp().updated_at);
+    assert_eq!(
+        vec![newer.id(), rotated.id()],
+        ids(Secret::list_needing_rotation(ctx, Duration::ZERO).await?)
+    );
+
+    Ok(())
+}
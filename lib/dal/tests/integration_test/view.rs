@@ -299,6 +299,72 @@ async fn remove_view_with_removal_of_exclusive_components(ctx: &mut DalContext)
     );
 }
 
+#[test(skip_rebaser, skip_pinga, skip_veritech)]
+async fn remove_view_after_moving_exclusive_components_to_default(ctx: &mut DalContext) {
+    let new_view = ExpectView::create(ctx).await;
+
+    let component = create_component_for_default_schema_name(
+        ctx,
+        "swifty",
+        generate_fake_name(),
+        new_view.id(),
+    )
+    .await
+    .expect("could not create component");
+    let geometry = Geometry::get_by_component_and_view(ctx, component.id(), new_view.id())
+        .await
+        .expect("Unable to get Geometry for Component in new View");
+
+    // A Component which is also in another View is left where it is
+    let other_view = ExpectView::create(ctx).await;
+    let shared_component = create_component_for_default_schema_name(
+        ctx,
+        "swifty",
+        generate_fake_name(),
+        new_view.id(),
+    )
+    .await
+    .expect("could not create component");
+    Geometry::new_for_component(ctx, shared_component.id(), other_view.id())
+        .await
+        .expect("Unable to add Component to other View");
+
+    let moved_component_ids = View::move_components_to_default(ctx, new_view.id())
+        .await
+        .expect("Unable to move Components to default View");
+    assert_eq!(vec![component.id()], moved_component_ids);
+
+    // Moving again is a no-op, since the Component is already in the default View
+    let moved_component_ids = View::move_components_to_default(ctx, new_view.id())
+        .await
+        .expect("Unable to move Components to default View");
+    assert!(moved_component_ids.is_empty());
+
+    View::remove(ctx, new_view.id())
+        .await
+        .expect("Unable to remove View");
+
+    assert_eq!(
+        2,
+        View::list(ctx).await.expect("Unable to list views").len()
+    );
+
+    let default_view_id = View::get_id_for_default(ctx)
+        .await
+        .expect("Unable to get default View");
+    let default_geometry =
+        Geometry::get_by_component_and_view(ctx, component.id(), default_view_id)
+            .await
+            .expect("Unable to get Geometry for Component in default View");
+    assert_eq!(geometry.into_raw(), default_geometry.into_raw());
+    assert!(
+        Geometry::try_get_by_component_and_view(ctx, shared_component.id(), default_view_id)
+            .await
+            .expect("Unable to look up Geometry for Component in default View")
+            .is_none()
+    );
+}
+
 #[test]
 async fn remove_view_that_previously_contained_another_view_that_has_been_removed(
     ctx: &mut DalContext,
@@ -1,18 +1,31 @@
+use std::collections::HashMap;
+
 use dal::{
+    Component,
+    ComponentError,
     DalContext,
     Workspace,
+    WorkspaceError,
     change_set::view::OpenChangeSetsView,
-    diagram::Diagram,
+    diagram::{
+        Diagram,
+        view::View,
+    },
+    feature_flags::FeatureFlag,
+    schema::variant::authoring::VariantAuthoringClient,
 };
 use dal_test::{
+    Result,
     helpers::{
         ChangeSetTestHelpers,
         PropEditorTestView,
         create_component_for_default_schema_name_in_default_view,
+        schema::variant,
     },
     test,
 };
 use pretty_assertions_sorted::assert_eq;
+use si_frontend_types::RawGeometry;
 
 #[test]
 async fn export_import_loop(ctx: &mut DalContext) {
@@ -116,3 +129,162 @@ async fn export_import_loop(ctx: &mut DalContext) {
             .expect("get value for domain/name")
     );
 }
+
+#[test]
+async fn schema_allowlist(ctx: &mut DalContext) -> Result<()> {
+    let mut workspace = ctx.get_workspace().await?;
+    workspace
+        .set_schema_allowlist(ctx, Some(vec!["Docker Image".to_string()]))
+        .await?;
+    ctx.commit_no_rebase().await?;
+
+    let view_id = View::get_id_for_default(ctx).await?;
+    let docker_image_variant_id = variant::id(ctx, "Docker Image").await?;
+    let swifty_variant_id = variant::id(ctx, "swifty").await?;
+
+    // An allowlisted schema can be instantiated
+    Component::new(ctx, "nginx", docker_image_variant_id, view_id).await?;
+
+    // A schema which is not allowlisted is rejected
+    let result = Component::new(ctx, "swifty", swifty_variant_id, view_id).await;
+    assert!(matches!(
+        result,
+        Err(ComponentError::SchemaNotAllowed(schema_name, _)) if schema_name == "swifty"
+    ));
+
+    // Removing the allowlist allows any schema again
+    workspace.set_schema_allowlist(ctx, None).await?;
+    ctx.commit_no_rebase().await?;
+    Component::new(ctx, "swifty", swifty_variant_id, view_id).await?;
+
+    Ok(())
+}
+
+#[test]
+async fn schema_allowlist_covers_upgrade_paste_and_import(ctx: &mut DalContext) -> Result<()> {
+    let view_id = View::get_id_for_default(ctx).await?;
+    let swifty_variant_id = variant::id(ctx, "swifty").await?;
+    let swifty = Component::new(ctx, "swifty", swifty_variant_id, view_id).await?;
+    let unlocked =
+        VariantAuthoringClient::create_unlocked_variant_copy(ctx, swifty_variant_id).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let mut workspace = ctx.get_workspace().await?;
+    let workspace_export = workspace.generate_export_data(ctx, "0.0").await?;
+    workspace
+        .set_schema_allowlist(ctx, Some(vec!["Docker Image".to_string()]))
+        .await?;
+    ctx.commit_no_rebase().await?;
+
+    // Upgrading an existing component is rejected
+    let result = Component::upgrade_to_new_variant(ctx, swifty.id(), unlocked.id()).await;
+    assert!(matches!(
+        result,
+        Err(ComponentError::SchemaNotAllowed(schema_name, _)) if schema_name == "swifty"
+    ));
+
+    // Pasting an existing component is rejected
+    let result = swifty
+        .duplicate_without_connections(ctx, view_id, RawGeometry::default(), None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ComponentError::SchemaNotAllowed(schema_name, _)) if schema_name == "swifty"
+    ));
+
+    // Importing a workspace with a component of the schema is rejected, without abandoning any of
+    // the open change sets
+    let open_change_set_count = OpenChangeSetsView::assemble(ctx).await?.change_sets.len();
+    let result = workspace.import(ctx, workspace_export).await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::SchemaNotAllowed(schema_name, _)) if schema_name == "swifty"
+    ));
+    assert_eq!(
+        open_change_set_count,
+        OpenChangeSetsView::assemble(ctx).await?.change_sets.len()
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn feature_flag_overrides_are_persisted(ctx: &mut DalContext) -> Result<()> {
+    let mut workspace = ctx.get_workspace().await?;
+    let feature_flags_service = ctx.services_context().feature_flags_service().clone();
+    assert!(!feature_flags_service.is_enabled_for_workspace(&FeatureFlag::Secrets, &workspace));
+
+    assert_eq!(
+        None,
+        workspace
+            .set_feature_flag_override(ctx, FeatureFlag::Secrets, Some(true))
+            .await?
+    );
+    ctx.commit_no_rebase().await?;
+
+    // The override is read back from the workspace rather than from this process
+    let reloaded = ctx.get_workspace().await?;
+    assert_eq!(
+        &HashMap::from([(FeatureFlag::Secrets, true)]),
+        reloaded.feature_flag_overrides()
+    );
+    assert!(feature_flags_service.is_enabled_for_workspace(&FeatureFlag::Secrets, &reloaded));
+
+    // Removing the override falls back to the global setting
+    assert_eq!(
+        Some(true),
+        workspace
+            .set_feature_flag_override(ctx, FeatureFlag::Secrets, None)
+            .await?
+    );
+    ctx.commit_no_rebase().await?;
+    let reloaded = ctx.get_workspace().await?;
+    assert!(reloaded.feature_flag_overrides().is_empty());
+    assert!(!feature_flags_service.is_enabled_for_workspace(&FeatureFlag::Secrets, &reloaded));
+
+    Ok(())
+}
+
+#[test]
+async fn unknown_feature_flag_overrides_are_ignored_and_kept(ctx: &mut DalContext) -> Result<()> {
+    let mut workspace = ctx.get_workspace().await?;
+
+    // An override for a feature only a newer version knows about
+    ctx.txns()
+        .await?
+        .pg()
+        .query_none(
+            "UPDATE workspaces SET feature_flag_overrides = '{\"from_the_future\": true}'::jsonb
+             WHERE pk = $1",
+            &[workspace.pk()],
+        )
+        .await?;
+    let reloaded = ctx.get_workspace().await?;
+    assert!(reloaded.feature_flag_overrides().is_empty());
+
+    // Setting and removing known overrides leaves it in place
+    workspace
+        .set_feature_flag_override(ctx, FeatureFlag::Secrets, Some(true))
+        .await?;
+    assert_eq!(
+        &HashMap::from([(FeatureFlag::Secrets, true)]),
+        workspace.feature_flag_overrides()
+    );
+    workspace
+        .set_feature_flag_override(ctx, FeatureFlag::Secrets, None)
+        .await?;
+
+    let row = ctx
+        .txns()
+        .await?
+        .pg()
+        .query_one(
+            "SELECT feature_flag_overrides FROM workspaces WHERE pk = $1",
+            &[workspace.pk()],
+        )
+        .await?;
+    let stored: serde_json::Value = row.try_get("feature_flag_overrides")?;
+    assert_eq!(serde_json::json!({ "from_the_future": true }), stored);
+
+    Ok(())
+}
@@ -26,6 +26,7 @@ use si_tls::CertificateSource;
 use telemetry::prelude::*;
 use thiserror::Error;
 use ulid::Ulid;
+use veritech_client::CircuitBreakerConfig;
 
 const DEFAULT_CONCURRENCY_LIMIT: Option<usize> = None;
 const DEFAULT_PARALLEL_BUILD_LIMIT: usize = 50;
@@ -91,6 +92,9 @@ pub struct Config {
 
     #[builder(default = "default_service_endpoints_config()")]
     service_endpoints: ServiceEndpointsConfig,
+
+    #[builder(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl StandardConfig for Config {
@@ -162,6 +166,11 @@ impl Config {
     pub fn service_endpoints(&self) -> &ServiceEndpointsConfig {
         &self.service_endpoints
     }
+
+    /// Gets the config's veritech client circuit breaker settings, if the breaker is enabled.
+    pub fn veritech_circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.veritech_circuit_breaker
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -191,6 +200,8 @@ pub struct ConfigFile {
     quiescent_period_secs: u64,
     #[serde(default = "default_service_endpoints_config")]
     service_endpoints: ServiceEndpointsConfig,
+    #[serde(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl Default for ConfigFile {
@@ -207,6 +218,7 @@ impl Default for ConfigFile {
             layer_db_config: default_layer_db_config(),
             quiescent_period_secs: default_quiescent_period_secs(),
             service_endpoints: default_service_endpoints_config(),
+            veritech_circuit_breaker: None,
         }
     }
 }
@@ -233,6 +245,7 @@ impl TryFrom<ConfigFile> for Config {
         config.instance_id(value.instance_id);
         config.quiescent_period(Duration::from_secs(value.quiescent_period_secs));
         config.service_endpoints(value.service_endpoints);
+        config.veritech_circuit_breaker(value.veritech_circuit_breaker);
         config.build().map_err(Into::into)
     }
 }
@@ -22,6 +22,10 @@ use thiserror::Error;
 use ulid::Ulid;
 
 const DEFAULT_CONCURRENCY_LIMIT: usize = 1000;
+// Kept below the concurrency limit so that a slow delivery target fills the queue, and the
+// backpressure policy applies, before every handler slot is taken
+const DEFAULT_BILLING_EVENTS_QUEUE_CAPACITY: usize = 100;
+const DEFAULT_BILLING_EVENTS_DELIVERY_CONCURRENCY: usize = 8;
 
 #[allow(missing_docs)]
 #[remain::sorted]
@@ -60,6 +64,9 @@ pub struct Config {
     #[builder(default = "default_data_warehouse_stream_name()")]
     data_warehouse_stream_name: Option<String>,
 
+    #[builder(default)]
+    billing_events_delivery: BillingEventsDeliveryConfig,
+
     #[builder(default = "default_enable_audit_logs_app()")]
     enable_audit_logs_app: bool,
 
@@ -104,6 +111,11 @@ impl Config {
         self.data_warehouse_stream_name.as_deref()
     }
 
+    /// Gets a reference to the billing events delivery config.
+    pub fn billing_events_delivery(&self) -> &BillingEventsDeliveryConfig {
+        &self.billing_events_delivery
+    }
+
     /// Indicates whether or not the audit logs app will be enabled.
     pub fn enable_audit_logs_app(&self) -> bool {
         self.enable_audit_logs_app
@@ -126,6 +138,42 @@ impl Config {
     }
 }
 
+/// What to do with a billing event when the delivery queue is full.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackpressurePolicy {
+    /// Wait for space in the queue before accepting the event.
+    #[default]
+    Block,
+    /// Drop the oldest queued event to make room for the new one. The dropped event is nacked with
+    /// a backoff so that it is redelivered later, once the burst has hopefully passed.
+    DropOldest,
+}
+
+/// The config for delivering billing events to the data warehouse stream.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BillingEventsDeliveryConfig {
+    /// The maximum number of billing events buffered while waiting to be delivered.
+    #[serde(default = "default_billing_events_queue_capacity")]
+    pub queue_capacity: usize,
+    /// What to do with a billing event when the queue is full.
+    #[serde(default)]
+    pub backpressure_policy: BackpressurePolicy,
+    /// The maximum number of billing events being delivered at once.
+    #[serde(default = "default_billing_events_delivery_concurrency")]
+    pub delivery_concurrency: usize,
+}
+
+impl Default for BillingEventsDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: default_billing_events_queue_capacity(),
+            backpressure_policy: Default::default(),
+            delivery_concurrency: default_billing_events_delivery_concurrency(),
+        }
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConfigFile {
@@ -137,6 +185,8 @@ pub struct ConfigFile {
     pub nats: NatsConfig,
     #[serde(default = "default_data_warehouse_stream_name")]
     pub data_warehouse_stream_name: Option<String>,
+    #[serde(default)]
+    pub billing_events_delivery: BillingEventsDeliveryConfig,
     #[serde(default = "default_enable_audit_logs_app")]
     pub enable_audit_logs_app: bool,
     #[serde(default)]
@@ -154,6 +204,7 @@ impl Default for ConfigFile {
             concurrency_limit: default_concurrency_limit(),
             nats: Default::default(),
             data_warehouse_stream_name: default_data_warehouse_stream_name(),
+            billing_events_delivery: Default::default(),
             enable_audit_logs_app: default_enable_audit_logs_app(),
             audit: Default::default(),
             snapshot_eviction: Default::default(),
@@ -177,6 +228,7 @@ impl TryFrom<ConfigFile> for Config {
             concurrency_limit: value.concurrency_limit,
             nats: value.nats,
             data_warehouse_stream_name: value.data_warehouse_stream_name,
+            billing_events_delivery: value.billing_events_delivery,
             enable_audit_logs_app: value.enable_audit_logs_app,
             audit: value.audit,
             snapshot_eviction: value.snapshot_eviction,
@@ -197,6 +249,14 @@ fn default_data_warehouse_stream_name() -> Option<String> {
     None
 }
 
+fn default_billing_events_queue_capacity() -> usize {
+    DEFAULT_BILLING_EVENTS_QUEUE_CAPACITY
+}
+
+fn default_billing_events_delivery_concurrency() -> usize {
+    DEFAULT_BILLING_EVENTS_DELIVERY_CONCURRENCY
+}
+
 fn default_enable_audit_logs_app() -> bool {
     false
 }
@@ -29,6 +29,8 @@ mod config;
 mod middleware;
 mod server;
 pub use config::{
+    BackpressurePolicy,
+    BillingEventsDeliveryConfig,
     Config,
     ConfigError,
     ConfigFile,
@@ -25,7 +25,10 @@ use thiserror::Error;
 use tokio::task::JoinError;
 use tokio_util::sync::CancellationToken;
 
-use crate::config::Config;
+use crate::config::{
+    BillingEventsDeliveryConfig,
+    Config,
+};
 
 mod app;
 
@@ -133,6 +136,7 @@ impl Server {
             config.concurrency_limit(),
             audit_bag,
             config.data_warehouse_stream_name(),
+            config.billing_events_delivery().clone(),
             si_db_pool,
             layer_cache_pool,
             layered_event_client,
@@ -152,6 +156,7 @@ impl Server {
         concurrency_limit: usize,
         audit_bag: Option<(AuditDatabaseContext, usize)>,
         data_warehouse_stream_name: Option<&str>,
+        billing_events_delivery_config: BillingEventsDeliveryConfig,
         si_db_pool: PgPool,
         layer_cache_pool: PgPool,
         layered_event_client: LayeredEventClient,
@@ -185,6 +190,7 @@ impl Server {
             connection_metadata,
             concurrency_limit,
             data_warehouse_stream_name,
+            billing_events_delivery_config,
             token.clone(),
         )
         .await?;
@@ -13,6 +13,8 @@ use telemetry::prelude::*;
 use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 
+use crate::BillingEventsDeliveryConfig;
+
 mod audit_logs;
 mod billing_events;
 
@@ -66,6 +68,7 @@ pub(crate) async fn billing_events(
     connection_metadata: Arc<ConnectionMetadata>,
     concurrency_limit: usize,
     data_warehouse_stream_name: Option<&str>,
+    delivery_config: BillingEventsDeliveryConfig,
     token: CancellationToken,
 ) -> Result<Box<dyn Future<Output = io::Result<()>> + Unpin + Send>> {
     Ok(billing_events::build_and_run(
@@ -74,6 +77,7 @@ pub(crate) async fn billing_events(
         connection_metadata,
         concurrency_limit,
         data_warehouse_stream_name,
+        delivery_config,
         token,
     )
     .await?)
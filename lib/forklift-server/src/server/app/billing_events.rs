@@ -5,6 +5,7 @@ use std::{
     },
     io,
     sync::Arc,
+    time::Duration,
 };
 
 use app_state::{
@@ -19,6 +20,8 @@ use data_warehouse_stream_client::{
     DataWarehouseStreamClient,
     DataWarehouseStreamClientError,
 };
+use delivery_queue::DeliveryQueue;
+use futures::FutureExt as _;
 use naxum::{
     MessageHead,
     ServiceBuilder,
@@ -27,7 +30,10 @@ use naxum::{
     extract::MatchedSubject,
     handler::Handler as _,
     middleware::{
-        ack::AckLayer,
+        ack::{
+            AckLayer,
+            BackoffOnFailure,
+        },
         matched_subject::{
             ForSubject,
             MatchedSubjectLayer,
@@ -58,9 +64,18 @@ use telemetry::prelude::*;
 use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 
+use crate::BillingEventsDeliveryConfig;
+
 mod app_state;
+mod delivery_queue;
 mod handlers;
 
+// Events which could not be delivered, or were dropped from a full queue, are nacked with a
+// backoff rather than redelivered straight away, so that a burst does not turn into a redelivery
+// loop. The consumer has no max deliver, so they are never terminated.
+const NACK_BASE_DELAY: Duration = Duration::from_secs(1);
+const NACK_MAX_DELAY: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Error)]
 pub enum BillingEventsAppSetupError {
     #[error("async nats consumer error: {0}")]
@@ -86,6 +101,7 @@ pub(crate) async fn build_and_run(
     connection_metadata: Arc<ConnectionMetadata>,
     concurrency_limit: usize,
     data_warehouse_stream_name: Option<&str>,
+    delivery_config: BillingEventsDeliveryConfig,
     token: CancellationToken,
 ) -> Result<Box<dyn Future<Output = io::Result<()>> + Unpin + Send>> {
     let incoming = {
@@ -104,31 +120,68 @@ pub(crate) async fn build_and_run(
             .await?
     };
 
-    let inner = match data_warehouse_stream_name {
-        Some(stream_name) => {
-            info!(%stream_name, "creating billing events app in data warehouse stream delivery mode...");
-            let client = DataWarehouseStreamClient::new(stream_name).await?;
-            let state = AppState::new(client);
-            build_app(
-                state,
-                connection_metadata,
-                incoming,
-                concurrency_limit,
-                token.clone(),
-            )?
-        }
-        None => {
-            info!("creating billing events app in no-op mode...");
-            let state = NoopAppState::new();
-            build_noop_app(
-                state,
-                connection_metadata,
-                incoming,
-                concurrency_limit,
-                token.clone(),
-            )?
-        }
-    };
+    let inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send> =
+        match data_warehouse_stream_name {
+            Some(stream_name) => {
+                info!(%stream_name, "creating billing events app in data warehouse stream delivery mode...");
+                let client = DataWarehouseStreamClient::new(stream_name).await?;
+                if delivery_config.queue_capacity >= concurrency_limit {
+                    warn!(
+                        queue_capacity = delivery_config.queue_capacity,
+                        concurrency_limit,
+                        "billing events delivery queue can never fill, so its backpressure \
+                        policy will not apply; configure a capacity below the concurrency limit",
+                    );
+                }
+                let delivery_queue = DeliveryQueue::new(&delivery_config);
+                // The workers outlive the app so that in-flight handlers can see their events
+                // delivered before they ack
+                let delivery_token = CancellationToken::new();
+                let deliveries = delivery_queue::spawn_delivery_workers(
+                    delivery_config.delivery_concurrency,
+                    delivery_queue.clone(),
+                    client,
+                    delivery_token.clone(),
+                );
+                let state = AppState::new(delivery_queue);
+                let app = build_app(
+                    state,
+                    connection_metadata,
+                    incoming,
+                    concurrency_limit,
+                    token.clone(),
+                )?;
+
+                // Once the app has shut down, stop the delivery workers and wait for them to finish
+                Box::new(
+                    async move {
+                        let result = app.await;
+                        delivery_token.cancel();
+                        for delivery in futures::future::join_all(deliveries).await {
+                            if let Err(err) = delivery {
+                                error!(
+                                    si.error.message = ?err,
+                                    "billing events delivery task failed",
+                                );
+                            }
+                        }
+                        result
+                    }
+                    .boxed(),
+                )
+            }
+            None => {
+                info!("creating billing events app in no-op mode...");
+                let state = NoopAppState::new();
+                build_noop_app(
+                    state,
+                    connection_metadata,
+                    incoming,
+                    concurrency_limit,
+                    token.clone(),
+                )?
+            }
+        };
 
     Ok(inner)
 }
@@ -154,7 +207,11 @@ fn build_app(
                 .make_span_with(telemetry_nats::NatsMakeSpan::builder(connection_metadata).build())
                 .on_response(telemetry_nats::NatsOnResponse::new()),
         )
-        .layer(AckLayer::new())
+        .layer(AckLayer::new().on_failure(BackoffOnFailure::with_params(
+            NACK_BASE_DELAY,
+            NACK_MAX_DELAY,
+            i64::MAX,
+        )))
         .service(handlers::process_request.with_state(state))
         .map_response(Response::into_response);
 
@@ -1,15 +1,13 @@
-use data_warehouse_stream_client::DataWarehouseStreamClient;
+use super::delivery_queue::DeliveryQueue;
 
 #[derive(Debug, Clone)]
 pub(crate) struct AppState {
-    pub(crate) data_warehouse_stream_client: DataWarehouseStreamClient,
+    pub(crate) delivery_queue: DeliveryQueue,
 }
 
 impl AppState {
-    pub(crate) fn new(data_warehouse_stream_client: DataWarehouseStreamClient) -> Self {
-        Self {
-            data_warehouse_stream_client,
-        }
+    pub(crate) fn new(delivery_queue: DeliveryQueue) -> Self {
+        Self { delivery_queue }
     }
 }
 
@@ -0,0 +1,615 @@
+//! A bounded queue sitting between the billing events handler and the delivery target.
+//!
+//! Delivering to the data warehouse can be much slower than pulling billing events off of the
+//! stream. Rather than buffering an unbounded number of in-flight events, the handler pushes onto
+//! a [`DeliveryQueue`] of fixed capacity and a configurable number of workers drain it, so events
+//! are not necessarily delivered in the order they were pushed. What happens when the queue is
+//! full is decided by the configured [`BackpressurePolicy`].
+//!
+//! Every push hands back a [`DeliveryReceipt`] which resolves once the event has been delivered,
+//! has failed to deliver, or was dropped from the queue. The handler only acks the message after a
+//! successful delivery so that anything else is redelivered by the stream.
+
+use std::{
+    collections::VecDeque,
+    error::Error,
+    future::Future,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+use data_warehouse_stream_client::{
+    DataWarehouseStreamClient,
+    DataWarehouseStreamClientError,
+};
+use telemetry::prelude::*;
+use telemetry_utils::monotonic;
+use thiserror::Error;
+use tokio::{
+    sync::{
+        Notify,
+        oneshot,
+    },
+    task::JoinHandle,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{
+    BackpressurePolicy,
+    BillingEventsDeliveryConfig,
+};
+
+/// Somewhere serialized billing events can be delivered to.
+pub(crate) trait DeliveryTarget: Send + Sync + 'static {
+    type Error: Error + Send + Sync + 'static;
+
+    fn deliver(&self, payload: Vec<u8>) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+impl DeliveryTarget for DataWarehouseStreamClient {
+    type Error = DataWarehouseStreamClientError;
+
+    async fn deliver(&self, payload: Vec<u8>) -> Result<(), Self::Error> {
+        self.publish(payload).await
+    }
+}
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub(crate) enum DeliveryError {
+    #[error("billing event dropped from a full delivery queue")]
+    Dropped,
+    #[error("billing event delivery failed: {0}")]
+    Failed(String),
+    #[error("billing events delivery worker stopped before delivering the event")]
+    WorkerStopped,
+}
+
+/// Resolves once a pushed billing event has been delivered, or has not been.
+#[derive(Debug)]
+pub(crate) struct DeliveryReceipt(oneshot::Receiver<Result<(), DeliveryError>>);
+
+impl DeliveryReceipt {
+    pub(crate) async fn delivered(self) -> Result<(), DeliveryError> {
+        self.0.await.unwrap_or(Err(DeliveryError::WorkerStopped))
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    payload: Vec<u8>,
+    completion: oneshot::Sender<Result<(), DeliveryError>>,
+}
+
+impl Entry {
+    fn complete(self, result: Result<(), DeliveryError>) {
+        // The handler may have given up on the receipt, in which case nobody is left to tell
+        let _ = self.completion.send(result);
+    }
+}
+
+/// A bounded, multi-producer, multi-consumer queue of serialized billing events.
+#[derive(Clone, Debug)]
+pub(crate) struct DeliveryQueue {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    capacity: usize,
+    policy: BackpressurePolicy,
+    buffer: Mutex<VecDeque<Entry>>,
+    items_available: Notify,
+    space_available: Notify,
+}
+
+impl DeliveryQueue {
+    pub(crate) fn new(config: &BillingEventsDeliveryConfig) -> Self {
+        // A queue which can hold nothing would either drop or block on every event
+        let capacity = config.queue_capacity.max(1);
+
+        Self {
+            inner: Arc::new(Inner {
+                capacity,
+                policy: config.backpressure_policy,
+                buffer: Mutex::new(VecDeque::with_capacity(capacity)),
+                items_available: Notify::new(),
+                space_available: Notify::new(),
+            }),
+        }
+    }
+
+    /// Pushes a payload onto the queue, applying the [`BackpressurePolicy`] if it is full.
+    ///
+    /// The returned receipt must be awaited before acking the message the payload came from.
+    pub(crate) async fn push(&self, payload: Vec<u8>) -> DeliveryReceipt {
+        let (completion, receipt) = oneshot::channel();
+        let mut entry = Some(Entry {
+            payload,
+            completion,
+        });
+        let mut blocked = false;
+
+        loop {
+            {
+                let mut buffer = self.lock_buffer();
+                if buffer.len() >= self.inner.capacity {
+                    match self.inner.policy {
+                        BackpressurePolicy::DropOldest => {
+                            // The dropped event was never acked, so it will be redelivered
+                            if let Some(dropped) = buffer.pop_front() {
+                                dropped.complete(Err(DeliveryError::Dropped));
+                            }
+                            warn!(
+                                capacity = self.inner.capacity,
+                                "billing events delivery queue full, dropped oldest event",
+                            );
+                            monotonic!(
+                                billing_events_delivery_queue.backpressure = 1,
+                                policy = "drop_oldest"
+                            );
+                        }
+                        BackpressurePolicy::Block => {
+                            if !blocked {
+                                blocked = true;
+                                debug!(
+                                    capacity = self.inner.capacity,
+                                    "billing events delivery queue full, waiting for space",
+                                );
+                                monotonic!(
+                                    billing_events_delivery_queue.backpressure = 1,
+                                    policy = "block"
+                                );
+                            }
+                        }
+                    }
+                }
+
+                if buffer.len() < self.inner.capacity {
+                    if let Some(entry) = entry.take() {
+                        buffer.push_back(entry);
+                    }
+                    drop(buffer);
+                    self.inner.items_available.notify_one();
+                    return DeliveryReceipt(receipt);
+                }
+            }
+
+            self.inner.space_available.notified().await;
+        }
+    }
+
+    /// Waits for and removes the oldest entry on the queue.
+    async fn pop(&self) -> Entry {
+        loop {
+            if let Some(entry) = self.try_pop() {
+                return entry;
+            }
+            self.inner.items_available.notified().await;
+        }
+    }
+
+    fn try_pop(&self) -> Option<Entry> {
+        let entry = self.lock_buffer().pop_front();
+        if entry.is_some() {
+            self.inner.space_available.notify_one();
+        }
+        entry
+    }
+
+    fn lock_buffer(&self) -> std::sync::MutexGuard<'_, VecDeque<Entry>> {
+        self.inner
+            .buffer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Spawns `concurrency` workers delivering queued payloads to the target, as with
+/// [`run_delivery`].
+pub(crate) fn spawn_delivery_workers<T: DeliveryTarget + Clone>(
+    concurrency: usize,
+    queue: DeliveryQueue,
+    target: T,
+    token: CancellationToken,
+) -> Vec<JoinHandle<()>> {
+    (0..concurrency.max(1))
+        .map(|_| tokio::spawn(run_delivery(queue.clone(), target.clone(), token.clone())))
+        .collect()
+}
+
+/// Delivers queued payloads to the target until cancelled, then delivers whatever remains.
+///
+/// The token should only be cancelled once nothing else will be pushed, otherwise a late push
+/// would wait on its receipt forever.
+pub(crate) async fn run_delivery<T: DeliveryTarget>(
+    queue: DeliveryQueue,
+    target: T,
+    token: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            biased;
+            entry = queue.pop() => deliver(&target, entry).await,
+            _ = token.cancelled() => break,
+        }
+    }
+
+    while let Some(entry) = queue.try_pop() {
+        deliver(&target, entry).await;
+    }
+    debug!("billing events delivery queue drained");
+}
+
+async fn deliver<T: DeliveryTarget>(target: &T, entry: Entry) {
+    let Entry {
+        payload,
+        completion,
+    } = entry;
+
+    let result = target.deliver(payload).await.map_err(|err| {
+        error!(si.error.message = ?err, "failed to deliver billing event");
+        monotonic!(billing_events_delivery_queue.delivery_failed = 1);
+        DeliveryError::Failed(err.to_string())
+    });
+    let _ = completion.send(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        fmt,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        time::Duration,
+    };
+
+    use tokio::{
+        sync::Semaphore,
+        time::timeout,
+    };
+
+    use super::*;
+
+    #[derive(Clone, Debug, Default)]
+    struct SlowTarget {
+        delivered: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl DeliveryTarget for SlowTarget {
+        type Error = Infallible;
+
+        async fn deliver(&self, payload: Vec<u8>) -> Result<(), Self::Error> {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.delivered
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(payload);
+            Ok(())
+        }
+    }
+
+    /// A target which holds every delivery until its gate is opened.
+    #[derive(Clone, Debug)]
+    struct GatedTarget {
+        gate: Arc<Semaphore>,
+        in_flight: Arc<AtomicUsize>,
+        delivered: Arc<AtomicUsize>,
+    }
+
+    impl GatedTarget {
+        fn closed() -> Self {
+            Self {
+                gate: Arc::new(Semaphore::new(0)),
+                in_flight: Arc::new(AtomicUsize::new(0)),
+                delivered: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        fn open(&self) {
+            self.gate.add_permits(1_000);
+        }
+    }
+
+    impl DeliveryTarget for GatedTarget {
+        type Error = Infallible;
+
+        async fn deliver(&self, _payload: Vec<u8>) -> Result<(), Self::Error> {
+            self.in_flight.fetch_add(1, Ordering::SeqCst);
+            let _permit = self.gate.acquire().await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.delivered.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Unavailable;
+
+    impl fmt::Display for Unavailable {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("unavailable")
+        }
+    }
+
+    impl Error for Unavailable {}
+
+    #[derive(Clone, Debug)]
+    struct FailingTarget;
+
+    impl DeliveryTarget for FailingTarget {
+        type Error = Unavailable;
+
+        async fn deliver(&self, _payload: Vec<u8>) -> Result<(), Self::Error> {
+            Err(Unavailable)
+        }
+    }
+
+    fn queue(queue_capacity: usize, backpressure_policy: BackpressurePolicy) -> DeliveryQueue {
+        DeliveryQueue::new(&BillingEventsDeliveryConfig {
+            queue_capacity,
+            backpressure_policy,
+            delivery_concurrency: 1,
+        })
+    }
+
+    async fn eventually(description: &str, condition: impl Fn() -> bool) {
+        let waited = timeout(Duration::from_secs(1), async {
+            while !condition() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await;
+        assert!(waited.is_ok(), "timed out waiting until {description}");
+    }
+
+    /// Spawns a task for each event standing in for a handler, which pushes the event and then
+    /// waits for its receipt, returning how many of them have finished pushing.
+    fn spawn_handlers(
+        queue: &DeliveryQueue,
+        events: u8,
+    ) -> (Arc<AtomicUsize>, Vec<JoinHandle<Result<(), DeliveryError>>>) {
+        let pushed = Arc::new(AtomicUsize::new(0));
+        let handlers = (1..=events)
+            .map(|n| {
+                let queue = queue.clone();
+                let pushed = pushed.clone();
+                tokio::spawn(async move {
+                    let receipt = queue.push(vec![n]).await;
+                    pushed.fetch_add(1, Ordering::SeqCst);
+                    receipt.delivered().await
+                })
+            })
+            .collect();
+        (pushed, handlers)
+    }
+
+    #[tokio::test]
+    async fn block_holds_back_handlers_once_a_slow_target_fills_the_queue() {
+        let queue = queue(2, BackpressurePolicy::Block);
+        let target = GatedTarget::closed();
+        let token = CancellationToken::new();
+        let workers = spawn_delivery_workers(1, queue.clone(), target.clone(), token.clone());
+
+        let (pushed, handlers) = spawn_handlers(&queue, 5);
+
+        // One event is being delivered and two fill the queue, so the other two handlers wait
+        eventually("the queue is full", || {
+            target.in_flight.load(Ordering::SeqCst) == 1 && queue.lock_buffer().len() == 2
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(3, pushed.load(Ordering::SeqCst));
+        assert_eq!(2, queue.lock_buffer().len());
+
+        target.open();
+        for handler in handlers {
+            handler
+                .await
+                .expect("handler task should not panic")
+                .expect("event should have been delivered");
+        }
+        assert_eq!(5, target.delivered.load(Ordering::SeqCst));
+
+        token.cancel();
+        for worker in workers {
+            worker.await.expect("delivery worker should not panic");
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_drops_events_once_a_slow_target_fills_the_queue() {
+        let queue = queue(2, BackpressurePolicy::DropOldest);
+        let target = GatedTarget::closed();
+        let token = CancellationToken::new();
+        let workers = spawn_delivery_workers(1, queue.clone(), target.clone(), token.clone());
+
+        // Let the worker take the first event before the rest arrive
+        let (_, mut handlers) = spawn_handlers(&queue, 1);
+        eventually("the first event is being delivered", || {
+            target.in_flight.load(Ordering::SeqCst) == 1
+        })
+        .await;
+        let (pushed, rest) = spawn_handlers(&queue, 4);
+        handlers.extend(rest);
+
+        // Nobody waits for space, the two oldest queued events are dropped instead
+        eventually("every event was pushed", || {
+            pushed.load(Ordering::SeqCst) == 4
+        })
+        .await;
+        assert_eq!(2, queue.lock_buffer().len());
+
+        target.open();
+        let mut dropped = 0;
+        for handler in handlers {
+            if let Err(err) = handler.await.expect("handler task should not panic") {
+                assert!(
+                    matches!(err, DeliveryError::Dropped),
+                    "unexpected delivery error: {err}"
+                );
+                dropped += 1;
+            }
+        }
+        assert_eq!(2, dropped);
+        assert_eq!(3, target.delivered.load(Ordering::SeqCst));
+
+        token.cancel();
+        for worker in workers {
+            worker.await.expect("delivery worker should not panic");
+        }
+    }
+
+    #[tokio::test]
+    async fn workers_deliver_concurrently() {
+        let queue = queue(4, BackpressurePolicy::Block);
+        let target = GatedTarget::closed();
+        let token = CancellationToken::new();
+        let workers = spawn_delivery_workers(3, queue.clone(), target.clone(), token.clone());
+
+        let (_, handlers) = spawn_handlers(&queue, 4);
+
+        eventually("three events are being delivered at once", || {
+            target.in_flight.load(Ordering::SeqCst) == 3
+        })
+        .await;
+
+        target.open();
+        for handler in handlers {
+            handler
+                .await
+                .expect("handler task should not panic")
+                .expect("event should have been delivered");
+        }
+
+        token.cancel();
+        for worker in workers {
+            worker.await.expect("delivery worker should not panic");
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_discards_oldest_events_when_full() {
+        let queue = queue(2, BackpressurePolicy::DropOldest);
+
+        let mut receipts = Vec::new();
+        for n in 1..=4u8 {
+            receipts.push(
+                timeout(Duration::from_secs(1), queue.push(vec![n]))
+                    .await
+                    .expect("push should never wait with the drop oldest policy"),
+            );
+        }
+
+        assert_eq!(vec![3], queue.pop().await.payload);
+        assert_eq!(vec![4], queue.pop().await.payload);
+        assert!(queue.try_pop().is_none());
+
+        // The dropped events must be reported so that they are not acked
+        for receipt in receipts.drain(..2) {
+            assert!(matches!(
+                receipt.delivered().await,
+                Err(DeliveryError::Dropped)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn block_waits_for_space_when_full() {
+        let queue = queue(1, BackpressurePolicy::Block);
+
+        let _first = queue.push(vec![1]).await;
+        assert!(
+            timeout(Duration::from_millis(50), queue.push(vec![2]))
+                .await
+                .is_err(),
+            "push should wait while the queue is full"
+        );
+
+        let pusher = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.push(vec![3]).await }
+        });
+        assert_eq!(vec![1], queue.pop().await.payload);
+        timeout(Duration::from_secs(1), pusher)
+            .await
+            .expect("push should complete once there is space")
+            .expect("pusher task should not panic");
+
+        assert_eq!(vec![3], queue.pop().await.payload);
+    }
+
+    #[tokio::test]
+    async fn receipts_resolve_only_after_delivery() {
+        let queue = queue(2, BackpressurePolicy::Block);
+        let target = SlowTarget::default();
+        let token = CancellationToken::new();
+        let worker = tokio::spawn(run_delivery(queue.clone(), target.clone(), token.clone()));
+
+        let receipt = queue.push(vec![1]).await;
+        receipt
+            .delivered()
+            .await
+            .expect("event should have been delivered");
+        assert_eq!(
+            vec![vec![1]],
+            *target
+                .delivered
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        );
+
+        token.cancel();
+        worker.await.expect("delivery worker should not panic");
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_are_reported_to_the_receipt() {
+        let queue = queue(2, BackpressurePolicy::Block);
+        let token = CancellationToken::new();
+        let worker = tokio::spawn(run_delivery(queue.clone(), FailingTarget, token.clone()));
+
+        let receipt = queue.push(vec![1]).await;
+        assert!(matches!(
+            receipt.delivered().await,
+            Err(DeliveryError::Failed(_))
+        ));
+
+        token.cancel();
+        worker.await.expect("delivery worker should not panic");
+    }
+
+    #[tokio::test]
+    async fn slow_target_receives_every_event_when_blocking() {
+        let queue = queue(2, BackpressurePolicy::Block);
+        let target = SlowTarget::default();
+        let token = CancellationToken::new();
+        let worker = tokio::spawn(run_delivery(queue.clone(), target.clone(), token.clone()));
+
+        let mut receipts = Vec::new();
+        for n in 1..=5u8 {
+            receipts.push(queue.push(vec![n]).await);
+        }
+        token.cancel();
+        worker.await.expect("delivery worker should not panic");
+
+        for receipt in receipts {
+            receipt
+                .delivered()
+                .await
+                .expect("event should have been delivered");
+        }
+        assert_eq!(
+            vec![vec![1], vec![2], vec![3], vec![4], vec![5]],
+            *target
+                .delivered
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        );
+    }
+}
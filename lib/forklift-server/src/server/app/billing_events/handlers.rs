@@ -1,5 +1,4 @@
 use billing_events::BillingEvent;
-use data_warehouse_stream_client::DataWarehouseStreamClientError;
 use naxum::{
     Json,
     extract::State,
@@ -12,16 +11,19 @@ use si_data_nats::Subject;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use super::app_state::{
-    AppState,
-    NoopAppState,
+use super::{
+    app_state::{
+        AppState,
+        NoopAppState,
+    },
+    delivery_queue::DeliveryError,
 };
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub(crate) enum HandlerError {
-    #[error("data warehouse stream client error: {0}")]
-    DataWarehouseStreamClient(#[from] DataWarehouseStreamClientError),
+    #[error("delivery error: {0}")]
+    Delivery(#[from] DeliveryError),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
 }
@@ -46,12 +48,12 @@ pub(crate) async fn process_request(
     span.record("si.change_set.id", request.change_set_id.to_string());
 
     let serialized_request = serde_json::to_vec(&request)?;
-    state
-        .data_warehouse_stream_client
-        .publish(serialized_request)
-        .await?;
+    let receipt = state.delivery_queue.push(serialized_request).await;
+
+    // Only return success (and therefore ack) once delivered, so JetStream redelivers otherwise
+    receipt.delivered().await?;
 
-    info!(kind = ?request.kind, ?request, "processed billing event");
+    info!(kind = ?request.kind, ?request, "delivered billing event");
     Ok(())
 }
 
@@ -191,13 +191,17 @@ pub struct FuncRunLogViewV1 {
 
 impl From<&OutputLine> for OutputLineViewV1 {
     fn from(output_line: &OutputLine) -> Self {
+        let structured = output_line.structured_message();
+        let level = output_line.level_with(structured.as_ref());
+
         Self {
             stream: output_line.stream.clone(),
             execution_id: output_line.execution_id.clone(),
-            level: output_line.level.clone(),
+            level,
             group: output_line.group.clone(),
             message: output_line.message.clone(),
             timestamp: output_line.timestamp,
+            structured,
         }
     }
 }
@@ -217,6 +221,8 @@ pub struct OutputLineViewV1 {
     pub message: String,
     #[schema(example = 1743104518)]
     pub timestamp: u64,
+    #[schema(value_type = Option<Object>, example = json!({"level": "info", "msg": "bucket created"}))]
+    pub structured: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl From<FuncRunLog> for FuncRunLogViewV1 {
@@ -38,7 +38,7 @@ impl AxumApp {
         crdt_multiplexer_client: MultiplexerClient,
         edda_updates_multiplexer_client: EddaUpdatesMultiplexerClient,
         create_workspace_permissions: WorkspacePermissionsMode,
-        create_workspace_allowlist: Vec<WorkspacePermissions>,
+        create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
         application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
         shutdown_token: CancellationToken,
         spicedb_client: Option<SpiceDbClient>,
@@ -81,7 +81,7 @@ impl AxumApp {
         crdt_multiplexer_client: MultiplexerClient,
         edda_updates_multiplexer_client: EddaUpdatesMultiplexerClient,
         create_workspace_permissions: WorkspacePermissionsMode,
-        create_workspace_allowlist: Vec<WorkspacePermissions>,
+        create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
         application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
         shutdown_token: CancellationToken,
         spicedb_client: Option<SpiceDbClient>,
@@ -14,7 +14,10 @@ use std::{
 use audit_database::AuditDatabaseConfig;
 use buck2_resources::Buck2Resources;
 pub use dal::MigrationMode;
-use dal::feature_flags::FeatureFlag;
+use dal::{
+    KeyPairAlgorithm,
+    feature_flags::FeatureFlag,
+};
 use derive_builder::Builder;
 pub use sdf_core::workspace_permissions::{
     WorkspacePermissions,
@@ -54,6 +57,7 @@ use si_tls::CertificateSource;
 use telemetry::prelude::*;
 use thiserror::Error;
 use ulid::Ulid;
+use veritech_client::CircuitBreakerConfig;
 
 const DEFAULT_MODULE_INDEX_URL: &str = "https://module-index.systeminit.com";
 const DEFAULT_AUTH_API_URL: &str = "https://auth-api.systeminit.com";
@@ -137,6 +141,9 @@ pub struct Config {
 
     create_workspace_allowlist: Vec<WorkspacePermissions>,
 
+    #[builder(default)]
+    default_key_pair_algorithm: KeyPairAlgorithm,
+
     #[builder(default)]
     audit: AuditDatabaseConfig,
 
@@ -145,6 +152,9 @@ pub struct Config {
 
     #[builder(default = "default_service_endpoints_config()")]
     service_endpoints: ServiceEndpointsConfig,
+
+    #[builder(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl StandardConfig for Config {
@@ -246,6 +256,12 @@ impl Config {
         &self.create_workspace_allowlist
     }
 
+    /// Gets the algorithm used for key pairs created without choosing one, such as the default
+    /// key pair of a new workspace
+    pub fn default_key_pair_algorithm(&self) -> KeyPairAlgorithm {
+        self.default_key_pair_algorithm
+    }
+
     /// Gets a referece to the config's spicedb config
     #[must_use]
     pub fn spicedb(&self) -> &SpiceDbConfig {
@@ -267,6 +283,11 @@ impl Config {
     pub fn service_endpoints(&self) -> &ServiceEndpointsConfig {
         &self.service_endpoints
     }
+
+    /// Gets the config's veritech client circuit breaker settings, if the breaker is enabled.
+    pub fn veritech_circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.veritech_circuit_breaker
+    }
 }
 
 impl ConfigBuilder {
@@ -316,11 +337,15 @@ pub struct ConfigFile {
     #[serde(default)]
     create_workspace_allowlist: Vec<WorkspacePermissions>,
     #[serde(default)]
+    default_key_pair_algorithm: KeyPairAlgorithm,
+    #[serde(default)]
     spicedb: SpiceDbConfig,
     #[serde(default)]
     audit: AuditDatabaseConfig,
     #[serde(default = "default_service_endpoints_config")]
     service_endpoints: ServiceEndpointsConfig,
+    #[serde(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl Default for ConfigFile {
@@ -342,10 +367,12 @@ impl Default for ConfigFile {
             boot_feature_flags: Default::default(),
             create_workspace_permissions: Default::default(),
             create_workspace_allowlist: Default::default(),
+            default_key_pair_algorithm: Default::default(),
             spicedb: Default::default(),
             audit: Default::default(),
             dev_mode: false,
             service_endpoints: default_service_endpoints_config(),
+            veritech_circuit_breaker: None,
         }
     }
 }
@@ -378,10 +405,12 @@ impl TryFrom<ConfigFile> for Config {
             boot_feature_flags: value.boot_feature_flags.into_iter().collect::<HashSet<_>>(),
             create_workspace_permissions: value.create_workspace_permissions,
             create_workspace_allowlist: value.create_workspace_allowlist,
+            default_key_pair_algorithm: value.default_key_pair_algorithm,
             spicedb: value.spicedb,
             audit: value.audit,
             dev_mode: value.dev_mode,
             service_endpoints: value.service_endpoints,
+            veritech_circuit_breaker: value.veritech_circuit_breaker,
         })
     }
 }
@@ -97,7 +97,7 @@ pub(crate) async fn services_context_from_config(
     let jetstream_streams = get_or_create_jetstream_streams(nats.clone()).await?;
     let pg_pool = create_pg_pool(config.pg_pool()).await?;
     let rebaser = create_rebaser_client(nats.clone()).await?;
-    let veritech = create_veritech_client(nats.clone());
+    let veritech = create_veritech_client(nats.clone(), config.veritech_circuit_breaker());
     let job_processor = create_job_processor(nats.clone()).await?;
     let symmetric_crypto_service =
         create_symmetric_crypto_service(config.symmetric_crypto_service()).await?;
@@ -129,7 +129,8 @@ pub(crate) async fn services_context_from_config(
         layer_db,
         feature_flags_service,
         compute_executor,
-    );
+    )
+    .with_default_key_pair_algorithm(config.default_key_pair_algorithm());
 
     Ok((services_context, layer_db_graceful_shutdown))
 }
@@ -181,8 +182,11 @@ async fn create_rebaser_client(nats: NatsClient) -> InitResult<RebaserClient> {
     Ok(client)
 }
 
-pub(crate) fn create_veritech_client(nats: NatsClient) -> veritech_client::Client {
-    veritech_client::Client::new(nats)
+pub(crate) fn create_veritech_client(
+    nats: NatsClient,
+    circuit_breaker: Option<veritech_client::CircuitBreakerConfig>,
+) -> veritech_client::Client {
+    veritech_client::Client::new_with_circuit_breaker(nats, circuit_breaker)
 }
 
 #[instrument(
@@ -37,6 +37,7 @@ mod uds;
 
 pub use dal::{
     JobQueueProcessor,
+    KeyPairAlgorithm,
     NatsProcessor,
     ServicesContext,
     feature_flags::{
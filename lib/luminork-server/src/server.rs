@@ -223,7 +223,7 @@ impl Server {
             crdt_multiplexer_client,
             edda_updates_multiplexer_client,
             create_workspace_permissions,
-            create_workspace_allowlist,
+            Arc::new(RwLock::new(create_workspace_allowlist)),
             application_runtime_mode,
             token.clone(),
             spicedb_client,
@@ -178,7 +178,10 @@ pub use schemas::{
         FindSchemaV1Response,
     },
     install_from_file::InstallFromFileV1Response,
-    list_schemas::ListSchemaV1Response,
+    list_schemas::{
+        ListSchemaV1Response,
+        list_schemas_page,
+    },
     search_schemas::{
         SearchSchemasV1Request,
         SearchSchemasV1Response,
@@ -1,4 +1,7 @@
-use axum::response::Json;
+use axum::{
+    extract::Query,
+    response::Json,
+};
 use dal::change_set::ChangeSet;
 use serde::Serialize;
 use serde_json::json;
@@ -11,13 +14,19 @@ use crate::{
         PosthogEventTracker,
         workspace::WorkspaceDalContext,
     },
+    service::v1::common::{
+        QueryStringPaginationParams,
+        paginate,
+    },
 };
 
 #[utoipa::path(
     get,
     path = "/v1/w/{workspace_id}/change-sets",
     params(
-        ("workspace_id" = String, Path, description = "Workspace identifier")
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("limit" = Option<String>, Query, description = "Maximum number of results to return (default: all, max: 300)"),
+        ("cursor" = Option<String>, Query, description = "Cursor for pagination (ChangeSetId of the last item from previous page)"),
     ),
     tag = "change_sets",
     summary = "List all active Change Sets",
@@ -29,14 +38,22 @@ use crate::{
 )]
 pub async fn list_change_sets(
     WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+    Query(params): Query<QueryStringPaginationParams>,
     tracker: PosthogEventTracker,
 ) -> ChangeSetResult<Json<ListChangeSetV1Response>> {
     tracker.track(ctx, "api_list_change_set", json!({}));
 
-    let change_sets = ChangeSet::list_active(ctx).await?;
+    // Without a limit every change set is returned in its existing order, as before pagination
+    // was supported
+    let page = paginate(
+        ChangeSet::list_active(ctx).await?,
+        |change_set| change_set.id,
+        params.limit(300),
+        params.cursor.as_deref(),
+    );
 
     let mut views: Vec<ChangeSetViewV1> = vec![];
-    for change_set in change_sets {
+    for change_set in page.items {
         views.push(ChangeSetViewV1 {
             id: change_set.id,
             name: change_set.clone().name,
@@ -45,7 +62,10 @@ pub async fn list_change_sets(
         });
     }
 
-    Ok(Json(ListChangeSetV1Response { change_sets: views }))
+    Ok(Json(ListChangeSetV1Response {
+        change_sets: views,
+        next_cursor: page.next_cursor,
+    }))
 }
 
 #[derive(Serialize, ToSchema)]
@@ -53,4 +73,6 @@ pub async fn list_change_sets(
 pub struct ListChangeSetV1Response {
     #[schema(value_type = Vec<Object>, example = "[{\"id\":\"01H9ZQD35JPMBGHH69BT0Q79VY\",\"name\":\"Add new feature\",\"status\":\"Open\",\"isHead\": \"false\"},{\"id\":\"01H9ZQE356JPMBGHH69BT0Q70UO\",\"name\":\"HEAD\",\"status\":\"Open\", \"isHead\": \"true\"}]")]
     pub change_sets: Vec<ChangeSetViewV1>,
+    #[schema(nullable = true)]
+    pub next_cursor: Option<String>,
 }
@@ -138,3 +138,120 @@ pub struct QueryStringPaginationParams {
     #[schema(value_type = Option<bool>)]
     pub include_codegen: Option<bool>,
 }
+
+impl QueryStringPaginationParams {
+    /// The requested page size, if any, capped at `max`. A limit which is not a number is ignored.
+    pub fn limit(&self, max: usize) -> Option<usize> {
+        self.limit
+            .as_deref()
+            .and_then(|limit| limit.parse::<usize>().ok())
+            .map(|limit| limit.min(max))
+    }
+}
+
+/// One page of items, and the cursor to request the next page with, if there is one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Sorts `items` by `key` and returns up to `limit` of them following the item whose key is
+/// `cursor`, or all of them if there is no `limit`. A cursor which no longer matches an item starts
+/// from the beginning.
+///
+/// Without a `limit` or `cursor` the items are returned in their original order, as they were
+/// before pagination was supported.
+pub fn paginate<T, K>(
+    mut items: Vec<T>,
+    key: impl Fn(&T) -> K,
+    limit: Option<usize>,
+    cursor: Option<&str>,
+) -> Page<T>
+where
+    K: Ord + ToString,
+{
+    if limit.is_none() && cursor.is_none() {
+        return Page {
+            items,
+            next_cursor: None,
+        };
+    }
+
+    items.sort_by_key(|item| key(item));
+
+    let start_index = cursor
+        .and_then(|cursor| {
+            items
+                .iter()
+                .position(|item| key(item).to_string() == cursor)
+        })
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let end_index = limit
+        .map(|limit| (start_index + limit).min(items.len()))
+        .unwrap_or(items.len());
+
+    let next_cursor = if end_index < items.len() && end_index > start_index {
+        Some(key(&items[end_index - 1]).to_string())
+    } else {
+        None
+    };
+
+    items.truncate(end_index);
+    items.drain(..start_index);
+
+    Page { items, next_cursor }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginate_walks_cursor_to_completion() {
+        let items: Vec<u32> = (0..23).rev().collect();
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        let mut pages = 0;
+        loop {
+            let page = paginate(items.clone(), |item| *item, Some(10), cursor.as_deref());
+            pages += 1;
+            seen.extend(page.items);
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(3, pages);
+        assert_eq!((0..23).collect::<Vec<u32>>(), seen);
+    }
+
+    #[test]
+    fn paginate_without_limit_returns_everything_in_order() {
+        let page = paginate(vec![3, 1, 2], |item| *item, None, None);
+
+        assert_eq!(
+            Page {
+                items: vec![3, 1, 2],
+                next_cursor: None
+            },
+            page
+        );
+    }
+
+    #[test]
+    fn paginate_restarts_on_unknown_cursor() {
+        let page = paginate(vec![3, 1, 2], |item| *item, Some(2), Some("42"));
+
+        assert_eq!(
+            Page {
+                items: vec![1, 2],
+                next_cursor: Some("2".to_string())
+            },
+            page
+        );
+    }
+}
@@ -12,6 +12,7 @@ use dal::{
     Func,
     WsEvent,
     diagram::view::View,
+    job::processor::JobPriority,
     management::prototype::ManagementPrototype,
 };
 use serde::{
@@ -69,7 +70,7 @@ const MAX_PENDING_DURATION: Duration = Duration::minutes(7);
 )]
 #[allow(deprecated)]
 pub async fn execute_management_function(
-    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
     _tracker: PosthogEventTracker,
     Path(ComponentV1RequestPath { component_id }): Path<ComponentV1RequestPath>,
     payload: Result<
@@ -129,6 +130,8 @@ pub async fn execute_management_function(
     // is so that the activity tracker has something to work with for tracking the lifecycle of a
     // management function in flight.
     let request_ulid = ulid::Ulid::new();
+    // The user is waiting on the results of the management func
+    ctx.set_job_priority(JobPriority::High);
     ctx.enqueue_management_func(prototype_id, component_id, view_id, request_ulid)
         .await?;
     WsEvent::management_operations_in_progress(ctx, request_ulid)
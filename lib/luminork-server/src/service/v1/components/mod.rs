@@ -189,6 +189,8 @@ pub enum ComponentsError {
     UlidDecode(#[from] ulid::DecodeError),
     #[error("component upgrade skipped due to running or dispatched actions")]
     UpgradeSkippedDueToActions,
+    #[error("component upgrade skipped since the component is pinned to its schema variant")]
+    UpgradeSkippedDueToPin,
     #[error("validation error: {0}")]
     Validation(String),
     #[error("view not found: {0}")]
@@ -13,6 +13,7 @@ use dal::{
     SchemaVariantId,
     WsEvent,
     attribute::attributes::AttributeSources,
+    job::processor::JobPriority,
     prop::{
         PROP_PATH_SEPARATOR,
         PropPath,
@@ -67,7 +68,7 @@ use crate::{
     )
 )]
 pub async fn update_component(
-    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
     Path(ComponentV1RequestPath { component_id }): Path<ComponentV1RequestPath>,
     payload: Result<Json<UpdateComponentV1Request>, axum::extract::rejection::JsonRejection>,
@@ -111,6 +112,8 @@ pub async fn update_component(
     let is_secret_defining = SchemaVariant::is_secret_defining(ctx, variant_id).await?;
 
     if !payload.attributes.is_empty() {
+        // The user is waiting on the results of this edit
+        ctx.set_job_priority(JobPriority::High);
         dal::update_attributes(ctx, component_id, payload.attributes.clone()).await?;
     }
 
@@ -61,6 +61,9 @@ pub async fn upgrade_component(
     }
 
     let current_component = Component::get_by_id(ctx, component_id).await?;
+    if current_component.pinned_schema_variant_id().is_some() {
+        return Err(ComponentsError::UpgradeSkippedDueToPin);
+    }
     let current_schema_variant = current_component.schema_variant(ctx).await?;
     let schema = current_schema_variant.schema(ctx).await?;
 
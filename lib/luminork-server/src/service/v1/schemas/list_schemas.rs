@@ -2,6 +2,7 @@ use axum::{
     extract::Query,
     response::Json,
 };
+use dal::DalContext;
 use serde::{
     Deserialize,
     Serialize,
@@ -15,6 +16,7 @@ use utoipa::{
 use super::{
     SchemaError,
     SchemaResponse,
+    SchemaResult,
     get_full_schema_list,
 };
 use crate::{
@@ -22,7 +24,10 @@ use crate::{
         PosthogEventTracker,
         change_set::ChangeSetDalContext,
     },
-    service::v1::common::QueryStringPaginationParams,
+    service::v1::common::{
+        QueryStringPaginationParams,
+        paginate,
+    },
 };
 
 #[utoipa::path(
@@ -48,48 +53,30 @@ pub async fn list_schemas(
     tracker: PosthogEventTracker,
 ) -> Result<Json<ListSchemaV1Response>, SchemaError> {
     // Set default limit and enforce a max limit
-    let limit = params
-        .limit
-        .and_then(|s| s.parse::<u32>().ok())
-        .unwrap_or(50)
-        .min(300) as usize;
-    let cursor = params.cursor;
+    let limit = params.limit(300).unwrap_or(50);
 
-    let mut all_schemas = get_full_schema_list(ctx).await?;
+    let response = list_schemas_page(ctx, limit, params.cursor.as_deref()).await?;
 
-    // Sort schemas by schema_id for consistent pagination
-    all_schemas.sort_by(|a, b| a.schema_id.cmp(&b.schema_id));
-    let start_index = if let Some(ref cursor_str) = cursor {
-        // Find the index of the cursor schema based on string comparison
-        match all_schemas
-            .iter()
-            .position(|schema| schema.schema_id.to_string() == *cursor_str)
-        {
-            Some(index) => index + 1, // Start after the cursor
-            None => 0,                // cursor is gone, start from beginning
-        }
-    } else {
-        0 // Start from the beginning
-    };
+    tracker.track(ctx, "api_list_schemas", json!({}));
 
-    // Get paginated schemas
-    let end_index = (start_index + limit).min(all_schemas.len());
-    let paginated_schemas: Vec<SchemaResponse> = all_schemas[start_index..end_index].to_vec();
+    Ok(Json(response))
+}
 
-    let next_cursor = if end_index < all_schemas.len() && !paginated_schemas.is_empty() {
-        paginated_schemas
-            .last()
-            .map(|schema| schema.schema_id.to_string())
-    } else {
-        None
-    };
+/// Lists up to `limit` schemas following the schema whose id is `cursor`, ordered by schema id.
+pub async fn list_schemas_page(
+    ctx: &DalContext,
+    limit: usize,
+    cursor: Option<&str>,
+) -> SchemaResult<ListSchemaV1Response> {
+    let all_schemas = get_full_schema_list(ctx).await?;
 
-    tracker.track(ctx, "api_list_schemas", json!({}));
+    // Sort schemas by schema_id for consistent pagination
+    let page = paginate(all_schemas, |schema| schema.schema_id, Some(limit), cursor);
 
-    Ok(Json(ListSchemaV1Response {
-        schemas: paginated_schemas,
-        next_cursor,
-    }))
+    Ok(ListSchemaV1Response {
+        schemas: page.items,
+        next_cursor: page.next_cursor,
+    })
 }
 
 // For runtime
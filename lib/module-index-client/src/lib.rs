@@ -30,6 +30,21 @@ pub enum ModuleIndexClientError {
     UrlParse(#[from] url::ParseError),
 }
 
+impl ModuleIndexClientError {
+    /// Returns `true` if the request failed in a way that may succeed when retried: the module
+    /// index could not be reached, did not respond in time, or responded with a server error.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Request(err) => {
+                err.is_connect()
+                    || err.is_timeout()
+                    || err.status().is_some_and(|status| status.is_server_error())
+            }
+            _ => false,
+        }
+    }
+}
+
 pub type ModuleIndexClientResult<T> = Result<T, ModuleIndexClientError>;
 
 #[derive(Debug, Clone)]
@@ -267,7 +282,16 @@ impl ModuleIndexClient {
     }
 
     pub async fn list_builtins(&self) -> ModuleIndexClientResult<BuiltinsDetailsResponse> {
-        let url = self.base_url.join("builtins")?;
+        self.list_builtins_filtered(ModuleFilter::default()).await
+    }
+
+    /// Lists the builtin modules which match the filter. The filter is applied by the module
+    /// index, so only the matching modules are downloaded.
+    pub async fn list_builtins_filtered(
+        &self,
+        filter: ModuleFilter,
+    ) -> ModuleIndexClientResult<BuiltinsDetailsResponse> {
+        let url = builtins_url(&self.base_url, &filter)?;
         let resp = self.inner.get(url).send().await?.error_for_status()?;
 
         let mut builtins = resp.json::<BuiltinsDetailsResponse>().await?;
@@ -276,7 +300,7 @@ impl ModuleIndexClient {
             && self.base_url.clone().as_str().contains("http://localhost")
         {
             // We want to fall back to the production module index to pull builtins from there instead
-            let url = Url::parse("https://module-index.systeminit.com")?.join("builtins")?;
+            let url = builtins_url(&Url::parse("https://module-index.systeminit.com")?, &filter)?;
 
             let resp = self.inner.get(url).send().await?.error_for_status()?;
 
@@ -411,3 +435,105 @@ impl ModuleIndexClient {
             .await?)
     }
 }
+
+fn builtins_url(base_url: &Url, filter: &ModuleFilter) -> ModuleIndexClientResult<Url> {
+    let mut url = base_url.join("builtins")?;
+
+    if let Some(name) = &filter.name {
+        url.query_pairs_mut().append_pair("name", name);
+    }
+    if let Some(category) = &filter.category {
+        url.query_pairs_mut().append_pair("category", category);
+    }
+    if !filter.schema_ids.is_empty() {
+        let schema_ids = filter
+            .schema_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        url.query_pairs_mut().append_pair("schemaIds", &schema_ids);
+    }
+
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{
+            AsyncReadExt,
+            AsyncWriteExt,
+        },
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Serves every request with the given status and an empty body, returning the server's URL.
+    async fn serve_status(status: StatusCode) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("should bind listener");
+        let addr = listener.local_addr().expect("should have a local address");
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request).await;
+                let response =
+                    format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        Url::parse(&format!("http://{addr}/")).expect("should parse url")
+    }
+
+    async fn get_builtin_error(base_url: Url) -> ModuleIndexClientError {
+        ModuleIndexClient::unauthenticated_client(base_url)
+            .expect("should create client")
+            .get_builtin(Ulid::new())
+            .await
+            .expect_err("fetching the builtin should fail")
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_transient() {
+        for status in [
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_GATEWAY,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            let err = get_builtin_error(serve_status(status).await).await;
+            assert!(err.is_transient(), "{status} should be transient");
+        }
+    }
+
+    #[tokio::test]
+    async fn connection_failures_are_transient() {
+        // Nothing listens on a port once its listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("should bind listener");
+        let addr = listener.local_addr().expect("should have a local address");
+        drop(listener);
+
+        let err =
+            get_builtin_error(Url::parse(&format!("http://{addr}/")).expect("should parse url"))
+                .await;
+        assert!(err.is_transient());
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_transient() {
+        for status in [
+            StatusCode::NOT_FOUND,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::BAD_REQUEST,
+        ] {
+            let err = get_builtin_error(serve_status(status).await).await;
+            assert!(!err.is_transient(), "{status} should not be transient");
+        }
+        assert!(!ModuleIndexClientError::ModuleNotFound(Ulid::new().to_string()).is_transient());
+    }
+}
@@ -1,7 +1,4 @@
-use std::{
-    collections::HashMap,
-    sync::Arc,
-};
+use std::sync::Arc;
 
 use axum::extract::FromRef;
 use s3::creds::Credentials as AwsCredentials;
@@ -13,7 +10,11 @@ use tokio::sync::{
     mpsc,
 };
 
-use crate::s3::S3Config;
+use crate::{
+    config::TokenEmailsConfig,
+    s3::S3Config,
+    token_emails::TokenEmails,
+};
 
 #[remain::sorted]
 #[derive(Debug, Eq, PartialEq)]
@@ -28,7 +29,7 @@ pub struct AppState {
     posthog_client: PosthogClient,
     aws_creds: AwsCredentials,
     s3_config: S3Config,
-    token_emails: Arc<Mutex<HashMap<String, String>>>,
+    token_emails: Arc<Mutex<TokenEmails>>,
 
     // see notes in sdf AppState
     #[from_ref(skip)]
@@ -45,6 +46,7 @@ impl AppState {
         posthog_client: PosthogClient,
         aws_creds: AwsCredentials,
         s3_config: S3Config,
+        token_emails_config: TokenEmailsConfig,
         tmp_shutdown_tx: mpsc::Sender<ShutdownSource>,
     ) -> Self {
         Self {
@@ -54,7 +56,7 @@ impl AppState {
             posthog_client,
             aws_creds,
             s3_config,
-            token_emails: Arc::new(Mutex::new(HashMap::new())),
+            token_emails: Arc::new(Mutex::new(TokenEmails::new(token_emails_config))),
             _tmp_shutdown_tx: Arc::new(tmp_shutdown_tx),
         }
     }
@@ -88,8 +90,8 @@ impl AppState {
         &self.s3_config
     }
 
-    /// Clones the ArcMutex that holds a hashmap between auth tokens and emails
-    pub fn token_emails(&self) -> Arc<Mutex<HashMap<String, String>>> {
+    /// Clones the ArcMutex that holds the cache of emails for auth tokens
+    pub fn token_emails(&self) -> Arc<Mutex<TokenEmails>> {
         self.token_emails.clone()
     }
 }
@@ -0,0 +1,101 @@
+//! Backfills of module columns which can only be filled in from the packages stored in S3.
+
+use s3::{
+    Bucket as S3Bucket,
+    error::S3Error,
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    QueryFilter,
+    Set,
+};
+use si_pkg::{
+    SiPkg,
+    SiPkgError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    models::si_module::{
+        self,
+        ModuleKind,
+    },
+    routes::upsert_module_route::schema_category,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+enum BackfillError {
+    #[error("s3 error: {0}")]
+    S3(#[from] S3Error),
+    #[error("module parsing error: {0}")]
+    SiPkg(#[from] SiPkgError),
+}
+
+/// Sets the schema category of modules uploaded before it was recorded, so that builtins can be
+/// filtered by it, by reading it from each module's stored package.
+///
+/// Modules whose package can not be read, or has no category, are logged and left as they are to
+/// be tried again on the next start.
+pub(crate) async fn backfill_schema_categories(db: DatabaseConnection, s3_bucket: S3Bucket) {
+    let modules = match si_module::Entity::find()
+        .filter(si_module::Column::Kind.eq(ModuleKind::Module))
+        .filter(si_module::Column::SchemaCategory.is_null())
+        .all(&db)
+        .await
+    {
+        Ok(modules) => modules,
+        Err(err) => {
+            warn!(si.error.message = ?err, "failed to find modules without a schema category");
+            return;
+        }
+    };
+    if modules.is_empty() {
+        return;
+    }
+
+    info!("backfilling schema category for {} modules", modules.len());
+    let mut backfilled = 0;
+    for module in modules {
+        let module_id = module.id;
+        match category_from_stored_package(&s3_bucket, &module.latest_hash).await {
+            Ok(Some(category)) => {
+                let mut active: si_module::ActiveModel = module.into();
+                active.schema_category = Set(Some(category));
+                match active.update(&db).await {
+                    Ok(_) => backfilled += 1,
+                    Err(err) => {
+                        warn!(
+                            si.error.message = ?err,
+                            %module_id,
+                            "failed to backfill schema category"
+                        );
+                    }
+                }
+            }
+            Ok(None) => debug!(%module_id, "module package has no schema category"),
+            Err(err) => {
+                warn!(
+                    si.error.message = %err,
+                    %module_id,
+                    "failed to read schema category from module package"
+                );
+            }
+        }
+    }
+    info!("backfilled schema category for {} modules", backfilled);
+}
+
+async fn category_from_stored_package(
+    s3_bucket: &S3Bucket,
+    module_hash: &str,
+) -> Result<Option<String>, BackfillError> {
+    let response = s3_bucket.get_object(format!("{module_hash}.sipkg")).await?;
+    let pkg = SiPkg::load_from_bytes(response.bytes())?;
+
+    Ok(schema_category(&pkg.schemas()?))
+}
@@ -87,6 +87,9 @@ pub struct Config {
     #[builder(default)]
     rate_limit: RateLimitConfig,
 
+    #[builder(default)]
+    token_emails: TokenEmailsConfig,
+
     s3: S3Config,
 }
 
@@ -151,6 +154,12 @@ impl Config {
         &self.rate_limit
     }
 
+    /// Gets a reference to the config's token email cache config.
+    #[must_use]
+    pub fn token_emails(&self) -> &TokenEmailsConfig {
+        &self.token_emails
+    }
+
     /// Gets a config's s3 details
     #[must_use]
     pub fn s3(&self) -> &S3Config {
@@ -181,6 +190,8 @@ pub struct ConfigFile {
     #[serde(default)]
     pub rate_limit: RateLimitConfig,
     #[serde(default)]
+    pub token_emails: TokenEmailsConfig,
+    #[serde(default)]
     pub s3: S3Config,
 }
 
@@ -203,6 +214,7 @@ impl Default for ConfigFile {
             jwt_secondary_signing_public_key_algo: None,
             posthog: Default::default(),
             rate_limit: Default::default(),
+            token_emails: Default::default(),
             s3: Default::default(),
         }
     }
@@ -226,6 +238,7 @@ impl TryFrom<ConfigFile> for Config {
         config.jwt_signing_public_key_path(value.jwt_signing_public_key_path.try_into()?);
         config.jwt_signing_public_key_algo(value.jwt_signing_public_key_algo);
         config.posthog(value.posthog);
+        config.token_emails(value.token_emails);
         config.s3(value.s3);
         config.build().map_err(Into::into)
     }
@@ -263,6 +276,24 @@ impl From<RateLimitConfig> for RateLimitLayer {
     }
 }
 
+/// Bounds for the cache of which email each auth token belongs to.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TokenEmailsConfig {
+    /// The most tokens to remember, after which the least recently used are evicted.
+    pub capacity: usize,
+    /// How long to remember a token's email before asking the auth api again.
+    pub ttl: Duration,
+}
+
+impl Default for TokenEmailsConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            ttl: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
 #[allow(clippy::disallowed_methods)] // Used to determine if running in development
 pub fn detect_and_configure_development(config: &mut ConfigFile) -> Result<()> {
     if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
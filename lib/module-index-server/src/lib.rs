@@ -1,10 +1,13 @@
 mod app_state;
+mod backfill;
 mod config;
 mod extract;
 mod models;
 mod routes;
 mod s3;
 pub mod server;
+mod token_emails;
+mod upload;
 mod whoami;
 
 pub use crate::{
@@ -15,6 +18,7 @@ pub use crate::{
         ConfigFile,
         StandardConfig,
         StandardConfigFile,
+        TokenEmailsConfig,
         detect_and_configure_development,
     },
     server::{
@@ -41,6 +41,8 @@ pub struct Model {
     pub kind: ModuleKind,
     pub is_builtin_at: Option<DateTimeWithTimeZone>,
     pub is_builtin_at_by_display_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub schema_category: Option<String>,
     #[sea_orm(column_type = r##"custom("ident")"##, nullable)]
     pub schema_id: Option<SchemaId>,
     #[sea_orm(column_type = r##"custom("ident")"##, nullable)]
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use axum::{
     Json,
     extract::{
@@ -30,6 +32,7 @@ use crate::{
         si_module,
         si_module::{
             ModuleKind,
+            SchemaId,
             SchemaIdReferenceLink,
             make_module_details_response,
         },
@@ -42,6 +45,8 @@ use crate::{
 pub enum ListBuiltinsError {
     #[error("db error: {0}")]
     DbErr(#[from] DbErr),
+    #[error("invalid schema id: {0}")]
+    InvalidSchemaId(String),
     #[error("whoami error: {0}")]
     Whoami(#[from] WhoamiError),
 }
@@ -49,7 +54,11 @@ pub enum ListBuiltinsError {
 // TODO: figure out how to not keep this serialization logic here
 impl IntoResponse for ListBuiltinsError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let status = match self {
+            ListBuiltinsError::InvalidSchemaId(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let error_message = self.to_string();
 
         let body = Json(
             serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
@@ -62,8 +71,12 @@ impl IntoResponse for ListBuiltinsError {
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListBuiltinsRequest {
+    /// A glob matched against the module name.
     pub name: Option<String>,
     pub su: Option<bool>,
+    pub category: Option<String>,
+    /// Comma separated schema ids.
+    pub schema_ids: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -74,7 +87,7 @@ pub struct ListBuiltinsResponse {
 
 pub async fn list_builtins_route(
     DbConnection(txn): DbConnection,
-    Query(_request): Query<ListBuiltinsRequest>,
+    Query(request): Query<ListBuiltinsRequest>,
     State(_state): State<AppState>,
 ) -> Result<Json<ListBuiltinsResponse>, ListBuiltinsError> {
     let query = si_module::Entity::find();
@@ -85,6 +98,7 @@ pub async fn list_builtins_route(
         .filter(si_module::Column::IsBuiltinAt.is_not_null())
         .filter(si_module::Column::RejectedAt.is_null())
         .filter(si_module::Column::Kind.eq(ModuleKind::Module));
+    let query = filter_by_request(query, &request)?;
 
     // This should give us a list of builtin modules that are not rejected
     let modules = query
@@ -96,3 +110,146 @@ pub async fn list_builtins_route(
 
     Ok(Json(ListBuiltinsResponse { modules }))
 }
+
+/// Narrows a query for modules to those matching the name glob, category and schema ids of the
+/// request, where they are given.
+fn filter_by_request<Q: QueryFilter>(
+    query: Q,
+    request: &ListBuiltinsRequest,
+) -> Result<Q, ListBuiltinsError> {
+    let query = match request.name.as_deref() {
+        Some(name) => query.filter(si_module::Column::Name.like(glob_to_like_pattern(name))),
+        None => query,
+    };
+    let query = match request.category.as_deref() {
+        Some(category) => query.filter(si_module::Column::SchemaCategory.eq(category)),
+        None => query,
+    };
+    let query = match request.schema_ids.as_deref() {
+        Some(schema_ids) => {
+            let schema_ids = schema_ids
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(|id| {
+                    SchemaId::from_str(id)
+                        .map_err(|_| ListBuiltinsError::InvalidSchemaId(id.to_owned()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            query.filter(si_module::Column::SchemaId.is_in(schema_ids))
+        }
+        None => query,
+    };
+
+    Ok(query)
+}
+
+/// Turns a glob, where `*` matches any run of characters and `?` matches any single character,
+/// into a SQL `LIKE` pattern.
+fn glob_to_like_pattern(glob: &str) -> String {
+    let mut pattern = String::with_capacity(glob.len());
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push('%'),
+            '?' => pattern.push('_'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            c => pattern.push(c),
+        }
+    }
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{
+        DbBackend,
+        QueryTrait,
+    };
+
+    use super::*;
+
+    fn request() -> ListBuiltinsRequest {
+        ListBuiltinsRequest {
+            name: None,
+            su: None,
+            category: None,
+            schema_ids: None,
+        }
+    }
+
+    fn filtered_sql(request: &ListBuiltinsRequest) -> String {
+        filter_by_request(si_module::Entity::find(), request)
+            .expect("failed to filter query")
+            .build(DbBackend::Postgres)
+            .to_string()
+    }
+
+    #[test]
+    fn no_filters_leave_the_query_alone() {
+        assert_eq!(
+            si_module::Entity::find()
+                .build(DbBackend::Postgres)
+                .to_string(),
+            filtered_sql(&request())
+        );
+    }
+
+    #[test]
+    fn filters_by_name_glob_and_category() {
+        let sql = filtered_sql(&ListBuiltinsRequest {
+            name: Some("AWS *".to_owned()),
+            category: Some("AWS EC2".to_owned()),
+            ..request()
+        });
+
+        assert!(sql.contains(r#""modules"."name" LIKE 'AWS %'"#), "{sql}");
+        assert!(
+            sql.contains(r#""modules"."schema_category" = 'AWS EC2'"#),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn filters_by_schema_ids() {
+        let schema_ids = [SchemaId::new(), SchemaId::new()];
+        let sql = filtered_sql(&ListBuiltinsRequest {
+            schema_ids: Some(format!("{}, {},", schema_ids[0], schema_ids[1])),
+            ..request()
+        });
+
+        assert!(
+            sql.contains(&format!(
+                r#""modules"."schema_id" IN ('{}', '{}')"#,
+                schema_ids[0], schema_ids[1]
+            )),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn invalid_schema_ids_are_rejected() {
+        let result = filter_by_request(
+            si_module::Entity::find(),
+            &ListBuiltinsRequest {
+                schema_ids: Some("not-a-schema-id".to_owned()),
+                ..request()
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(ListBuiltinsError::InvalidSchemaId(id)) if id == "not-a-schema-id"
+        ));
+    }
+
+    #[test]
+    fn glob_becomes_like_pattern() {
+        assert_eq!("AWS %", glob_to_like_pattern("AWS *"));
+        assert_eq!("Docker Imag_", glob_to_like_pattern("Docker Imag?"));
+        assert_eq!("100\\%\\_done", glob_to_like_pattern("100%_done"));
+        assert_eq!("exact", glob_to_like_pattern("exact"));
+    }
+}
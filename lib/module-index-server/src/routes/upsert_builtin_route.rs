@@ -93,10 +93,19 @@ pub async fn upsert_builtin_route(
         return Ok(Json(false));
     }
 
-    let multiparts = extract_multiparts(&mut multipart).await?;
+    let multiparts = extract_multiparts(&mut multipart, &s3_bucket).await?;
     if let Some(schema_id) = multiparts.schema_id.clone() {
-        let existing_schema_id = SchemaId::from_str(&schema_id)?;
-        reject_other_modules_of_a_schema_id("Clover".to_string(), existing_schema_id, &txn).await?;
+        let rejected = async {
+            let existing_schema_id = SchemaId::from_str(&schema_id)?;
+            reject_other_modules_of_a_schema_id("Clover".to_string(), existing_schema_id, &txn)
+                .await?;
+            Ok::<_, UpsertBuiltinError>(())
+        }
+        .await;
+        if let Err(err) = rejected {
+            multiparts.discard(&s3_bucket).await;
+            return Err(err);
+        }
     }
 
     // Upload the new module
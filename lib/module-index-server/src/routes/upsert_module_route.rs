@@ -2,7 +2,6 @@ use std::str::FromStr;
 
 use axum::{
     Json,
-    body::Bytes,
     extract::{
         Multipart,
         multipart::MultipartError,
@@ -48,6 +47,7 @@ use si_pkg::{
     SiPkg,
     SiPkgError,
     SiPkgKind,
+    SiPkgSchema,
 };
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -66,6 +66,10 @@ use crate::{
         SchemaVariantId,
         make_module_details_response,
     },
+    upload::{
+        StagedUpload,
+        UploadError,
+    },
 };
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -93,6 +97,8 @@ pub enum UpsertModuleError {
     SiPkgError(#[from] SiPkgError),
     #[error("Ulid decode error: {0}")]
     UlidDecode(#[from] ulid::DecodeError),
+    #[error("upload error: {0}")]
+    Upload(#[from] UploadError),
     #[error("upload is required")]
     UploadRequiredError,
 }
@@ -119,7 +125,7 @@ pub async fn upsert_module_route(
     DbConnection(txn): DbConnection,
     mut multipart: Multipart,
 ) -> Result<Json<ModuleDetailsResponse>, UpsertModuleError> {
-    let multiparts = extract_multiparts(&mut multipart).await?;
+    let multiparts = extract_multiparts(&mut multipart, &s3_bucket).await?;
     let new_module = upsert_module(multiparts, &txn, user_claim, s3_bucket).await?;
 
     let (module, linked_modules) = si_module::Entity::find_by_id(new_module.id)
@@ -140,64 +146,118 @@ pub struct SiMultipartData {
     pub schema_variant_id: Option<String>,
     pub schema_variant_version: Option<String>,
     pub module_based_on_hash: Option<String>,
-    pub module_data: Option<Bytes>,
+    pub module_data: Option<StagedUpload>,
     pub module_is_private_scoped: Option<bool>,
 }
 
+impl SiMultipartData {
+    /// Deletes the staged module bundle, if there is one, for an upload which will not be
+    /// upserted.
+    pub async fn discard(self, s3_bucket: &s3::Bucket) {
+        if let Some(staged_upload) = self.module_data {
+            staged_upload.discard(s3_bucket).await;
+        }
+    }
+}
+
+/// Extracts the fields of a module upload, streaming the module bundle itself to S3 as it arrives.
+///
+/// If extraction fails after the bundle has been staged, the staged bundle is deleted.
 pub async fn extract_multiparts(
     multipart: &mut Multipart,
+    s3_bucket: &s3::Bucket,
 ) -> Result<SiMultipartData, UpsertModuleError> {
-    let mut module_data = None;
-    let mut module_based_on_hash = None;
-    let mut module_schema_id = None;
-    let mut module_schema_variant_id = None;
-    let mut module_schema_variant_version = None;
-    let mut module_is_private_scoped = None;
+    let mut multi_part_data = SiMultipartData {
+        schema_id: None,
+        schema_variant_id: None,
+        schema_variant_version: None,
+        module_based_on_hash: None,
+        module_data: None,
+        module_is_private_scoped: None,
+    };
+    match extract_fields(multipart, s3_bucket, &mut multi_part_data).await {
+        Ok(()) => Ok(multi_part_data),
+        Err(err) => {
+            multi_part_data.discard(s3_bucket).await;
+            Err(err)
+        }
+    }
+}
+
+async fn extract_fields(
+    multipart: &mut Multipart,
+    s3_bucket: &s3::Bucket,
+    multi_part_data: &mut SiMultipartData,
+) -> Result<(), UpsertModuleError> {
     while let Some(field) = multipart.next_field().await? {
         match field.name() {
             Some(MODULE_BUNDLE_FIELD_NAME) => {
-                module_data = Some(field.bytes().await?);
+                let staged_upload = StagedUpload::stage(s3_bucket, field).await?;
+                // Only the last bundle sent is kept
+                if let Some(replaced) = multi_part_data.module_data.replace(staged_upload) {
+                    replaced.discard(s3_bucket).await;
+                }
             }
             Some(MODULE_BASED_ON_HASH_FIELD_NAME) => {
-                module_based_on_hash = Some(field.text().await?);
+                multi_part_data.module_based_on_hash = Some(field.text().await?);
             }
             Some(MODULE_SCHEMA_ID_FIELD_NAME) => {
-                module_schema_id = Some(field.text().await?);
+                multi_part_data.schema_id = Some(field.text().await?);
             }
             Some(MODULE_SCHEMA_VARIANT_ID_FIELD_NAME) => {
-                module_schema_variant_id = Some(field.text().await?);
+                multi_part_data.schema_variant_id = Some(field.text().await?);
             }
             Some(MODULE_SCHEMA_VARIANT_VERSION_FIELD_NAME) => {
-                module_schema_variant_version = Some(field.text().await?);
+                multi_part_data.schema_variant_version = Some(field.text().await?);
             }
             Some(MODULE_IS_PRIVATE_SCOPED_FIELD_NAME) => {
-                module_is_private_scoped =
+                multi_part_data.module_is_private_scoped =
                     Some(field.text().await?.parse::<bool>().unwrap_or_default());
             }
             _ => debug!("Unknown multipart form field on module upload, skipping..."),
         }
     }
 
-    Ok(SiMultipartData {
-        schema_id: module_schema_id,
-        schema_variant_id: module_schema_variant_id,
-        schema_variant_version: module_schema_variant_version,
-        module_based_on_hash,
-        module_data,
-        module_is_private_scoped,
-    })
+    Ok(())
 }
 
+/// Upserts an uploaded module, moving its staged bundle to its final key in S3. The staged bundle
+/// is deleted if the module can not be upserted.
 pub async fn upsert_module(
-    multi_part_data: SiMultipartData,
+    mut multi_part_data: SiMultipartData,
     txn: &sea_orm::DatabaseTransaction,
     user_claim: si_jwt_public_key::SiJwtClaims,
     s3_bucket: s3::Bucket,
 ) -> Result<si_module::Model, UpsertModuleError> {
-    let data = multi_part_data
+    let staged_upload = multi_part_data
         .module_data
+        .take()
         .ok_or(UpsertModuleError::UploadRequiredError)?;
-    let loaded_module = SiPkg::load_from_bytes(&data)?;
+
+    let (new_module, module_hash) =
+        match new_module_from_upload(&staged_upload, multi_part_data, txn, user_claim).await {
+            Ok(prepared) => prepared,
+            Err(err) => {
+                staged_upload.discard(&s3_bucket).await;
+                return Err(err);
+            }
+        };
+    staged_upload
+        .promote(&s3_bucket, format!("{module_hash}.sipkg"))
+        .await?;
+    let new_module: si_module::Model = new_module.insert(txn).await?;
+
+    Ok(new_module)
+}
+
+/// Reads a staged module bundle, returning the module to insert for it and its hash.
+async fn new_module_from_upload(
+    staged_upload: &StagedUpload,
+    multi_part_data: SiMultipartData,
+    txn: &sea_orm::DatabaseTransaction,
+    user_claim: si_jwt_public_key::SiJwtClaims,
+) -> Result<(si_module::ActiveModel, String), UpsertModuleError> {
+    let loaded_module = staged_upload.load_pkg().await?;
     let module_metadata = loaded_module.metadata()?;
     info!(
         "upserting module: {:?} based on hash: {:?} with provided schema id of {:?}",
@@ -244,11 +304,9 @@ pub async fn upsert_module(
     if let Some(schema_id) = schema_id {
         info!("module gets schema id: {}", schema_id.as_raw_id());
     }
-    let schemas: Vec<String> = loaded_module
-        .schemas()?
-        .iter()
-        .map(|s| s.name().to_owned())
-        .collect();
+    let loaded_schemas = loaded_module.schemas()?;
+    let schemas: Vec<String> = loaded_schemas.iter().map(|s| s.name().to_owned()).collect();
+    let schema_category = schema_category(&loaded_schemas);
     let funcs: Vec<FuncMetadata> = loaded_module
         .funcs()?
         .iter()
@@ -291,16 +349,89 @@ pub async fn upsert_module(
             funcs,
         })?),
         kind: Set(module_kind),
+        schema_category: Set(schema_category),
         schema_id: Set(schema_id),
         schema_variant_id: Set(schema_variant_id),
         schema_variant_version: Set(multi_part_data.schema_variant_version),
         is_private_scoped: Set(multi_part_data.module_is_private_scoped.unwrap_or_default()),
         ..Default::default() // all other attributes are `NotSet`
     };
-    s3_bucket
-        .put_object(format!("{}.sipkg", module_metadata.hash()), &data)
-        .await?;
-    let new_module: si_module::Model = new_module.insert(txn).await?;
 
-    Ok(new_module)
+    Ok((new_module, module_metadata.hash().to_string()))
+}
+
+/// Gets the category of the module's schema, which the module index filters builtins by. A module
+/// holds at most one schema.
+pub(crate) fn schema_category(schemas: &[SiPkgSchema<'_>]) -> Option<String> {
+    schemas
+        .first()
+        .and_then(|schema| schema.data())
+        .map(|data| data.category().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use si_pkg::{
+        PkgSpec,
+        SchemaSpec,
+        SchemaSpecData,
+    };
+
+    use super::*;
+
+    fn pkg(schema: Option<SchemaSpec>) -> SiPkg {
+        let mut builder = PkgSpec::builder();
+        builder
+            .name("hotel california")
+            .version("1")
+            .created_by("eagles@example.com");
+        if let Some(schema) = schema {
+            builder.schema(schema);
+        }
+
+        SiPkg::load_from_spec(builder.build().expect("failed to build spec"))
+            .expect("failed to load spec")
+    }
+
+    #[test]
+    fn schema_category_is_taken_from_the_schema() {
+        let pkg = pkg(Some(
+            SchemaSpec::builder()
+                .name("hotel")
+                .data(
+                    SchemaSpecData::builder()
+                        .name("hotel")
+                        .category("California")
+                        .build()
+                        .expect("failed to build schema data"),
+                )
+                .build()
+                .expect("failed to build schema"),
+        ));
+
+        assert_eq!(
+            Some("California".to_owned()),
+            schema_category(&pkg.schemas().expect("failed to get schemas"))
+        );
+    }
+
+    #[test]
+    fn schema_category_is_none_without_schema_data() {
+        let without_data = pkg(Some(
+            SchemaSpec::builder()
+                .name("hotel")
+                .build()
+                .expect("failed to build schema"),
+        ));
+        let without_schema = pkg(None);
+
+        assert_eq!(
+            None,
+            schema_category(&without_data.schemas().expect("failed to get schemas"))
+        );
+        assert_eq!(
+            None,
+            schema_category(&without_schema.schemas().expect("failed to get schemas"))
+        );
+    }
 }
@@ -20,9 +20,14 @@ use hyper::{
         conn::AddrIncoming,
     },
 };
-use s3::creds::{
-    Credentials as AwsCredentials,
-    error::CredentialsError,
+use s3::{
+    Bucket as S3Bucket,
+    Region as AwsRegion,
+    creds::{
+        Credentials as AwsCredentials,
+        error::CredentialsError,
+    },
+    error::S3Error,
 };
 use sea_orm::{
     ConnectOptions,
@@ -77,7 +82,11 @@ use crate::{
         AppState,
         ShutdownSource,
     },
-    config::RateLimitConfig,
+    backfill,
+    config::{
+        RateLimitConfig,
+        TokenEmailsConfig,
+    },
     s3::S3Config,
 };
 
@@ -104,6 +113,8 @@ pub enum ServerError {
     PgPool(#[from] Box<PgPoolError>),
     #[error("posthog error: {0}")]
     Posthog(#[from] si_posthog::PosthogError),
+    #[error("s3 error: {0}")]
+    S3(#[from] S3Error),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("failed to setup signal handler")]
@@ -168,6 +179,19 @@ impl Server<(), ()> {
             }
         };
 
+        // Modules uploaded before their schema category was recorded are filled in from their
+        // stored packages in the background, so as not to hold up startup
+        let region = config
+            .s3()
+            .region
+            .parse::<AwsRegion>()
+            .map_err(|_| ServerError::AwsConfigError)?;
+        let s3_bucket = S3Bucket::new(&config.s3().bucket, region, aws_creds.clone())?;
+        drop(tokio::spawn(backfill::backfill_schema_categories(
+            pg_pool.clone(),
+            s3_bucket,
+        )));
+
         let (service, shutdown_rx, shutdown_broadcast_rx) = build_service(
             pg_pool,
             config.auth_api_url().to_owned(),
@@ -176,6 +200,7 @@ impl Server<(), ()> {
             aws_creds,
             config.rate_limit().clone(),
             config.s3().clone(),
+            config.token_emails().clone(),
         )?;
 
         info!(
@@ -312,6 +337,7 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_service(
     pg_pool: DatabaseConnection,
     auth_api_url: String,
@@ -320,6 +346,7 @@ pub fn build_service(
     aws_creds: AwsCredentials,
     rate_limit_config: RateLimitConfig,
     s3_config: S3Config,
+    token_emails_config: TokenEmailsConfig,
 ) -> ServerResult<(Router, oneshot::Receiver<()>, broadcast::Receiver<()>)> {
     let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
     let (shutdown_broadcast_tx, shutdown_broadcast_rx) = broadcast::channel(1);
@@ -331,6 +358,7 @@ pub fn build_service(
         posthog_client,
         aws_creds,
         s3_config,
+        token_emails_config,
         shutdown_tx,
     );
 
@@ -0,0 +1,204 @@
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    time::Instant,
+};
+
+use crate::config::TokenEmailsConfig;
+
+/// A cache of the emails that auth tokens belong to, so that each request does not need to ask
+/// the auth api.
+///
+/// Entries expire after the configured TTL, and once the cache is at capacity the least recently
+/// used entry is evicted to make room for a new one.
+#[derive(Debug)]
+pub struct TokenEmails {
+    config: TokenEmailsConfig,
+    entries: HashMap<String, TokenEmail>,
+    /// Tokens by when they were last used, oldest first.
+    recency: BTreeMap<u64, String>,
+    /// Tokens by when they were inserted, oldest first, so that expired entries can be removed
+    /// without visiting the rest.
+    expiry: BTreeMap<(Instant, u64), String>,
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct TokenEmail {
+    email: String,
+    inserted_at: Instant,
+    inserted_seq: u64,
+    used_at: u64,
+}
+
+impl TokenEmails {
+    pub fn new(config: TokenEmailsConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            expiry: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Gets the email for a token, if it is cached and has not expired.
+    pub fn get(&mut self, token: &str) -> Option<String> {
+        self.get_at(token, Instant::now())
+    }
+
+    /// Caches the email for a token, evicting the least recently used entry if at capacity.
+    pub fn insert(&mut self, token: String, email: String) {
+        self.insert_at(token, email, Instant::now())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn get_at(&mut self, token: &str, now: Instant) -> Option<String> {
+        let entry = self.entries.get(token)?;
+        if now.duration_since(entry.inserted_at) >= self.config.ttl {
+            self.remove(token);
+            return None;
+        }
+
+        let used_at = self.tick();
+        let entry = self.entries.get_mut(token)?;
+        self.recency.remove(&entry.used_at);
+        self.recency.insert(used_at, token.to_owned());
+        entry.used_at = used_at;
+
+        Some(entry.email.clone())
+    }
+
+    fn insert_at(&mut self, token: String, email: String, now: Instant) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        self.remove(&token);
+        self.remove_expired(now);
+        while self.entries.len() >= self.config.capacity {
+            let Some((_, oldest_token)) = self.recency.first_key_value() else {
+                break;
+            };
+            let oldest_token = oldest_token.clone();
+            self.remove(&oldest_token);
+        }
+
+        let used_at = self.tick();
+        self.recency.insert(used_at, token.clone());
+        self.expiry.insert((now, used_at), token.clone());
+        self.entries.insert(
+            token,
+            TokenEmail {
+                email,
+                inserted_at: now,
+                inserted_seq: used_at,
+                used_at,
+            },
+        );
+    }
+
+    fn remove(&mut self, token: &str) {
+        if let Some(entry) = self.entries.remove(token) {
+            self.recency.remove(&entry.used_at);
+            self.expiry.remove(&(entry.inserted_at, entry.inserted_seq));
+        }
+    }
+
+    /// Removes expired entries, oldest first, stopping at the first which has not expired.
+    fn remove_expired(&mut self, now: Instant) {
+        while let Some((&(inserted_at, _), _)) = self.expiry.first_key_value() {
+            if now.duration_since(inserted_at) < self.config.ttl {
+                break;
+            }
+            if let Some((_, token)) = self.expiry.pop_first() {
+                if let Some(entry) = self.entries.remove(&token) {
+                    self.recency.remove(&entry.used_at);
+                }
+            }
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn token_emails(capacity: usize) -> TokenEmails {
+        TokenEmails::new(TokenEmailsConfig {
+            capacity,
+            ttl: Duration::from_secs(60),
+        })
+    }
+
+    #[test]
+    fn exceeding_capacity_evicts_least_recently_used() {
+        let mut token_emails = token_emails(2);
+        let now = Instant::now();
+
+        token_emails.insert_at("a".into(), "a@systeminit.com".into(), now);
+        token_emails.insert_at("b".into(), "b@systeminit.com".into(), now);
+        // Using "a" makes "b" the least recently used
+        assert_eq!(
+            Some("a@systeminit.com".to_string()),
+            token_emails.get_at("a", now)
+        );
+        token_emails.insert_at("c".into(), "c@systeminit.com".into(), now);
+
+        assert_eq!(2, token_emails.len());
+        assert_eq!(2, token_emails.expiry.len());
+        assert_eq!(None, token_emails.get_at("b", now));
+        assert_eq!(
+            Some("a@systeminit.com".to_string()),
+            token_emails.get_at("a", now)
+        );
+        assert_eq!(
+            Some("c@systeminit.com".to_string()),
+            token_emails.get_at("c", now)
+        );
+    }
+
+    #[test]
+    fn expired_entries_are_dropped() {
+        let mut token_emails = token_emails(10);
+        let now = Instant::now();
+
+        token_emails.insert_at("a".into(), "a@systeminit.com".into(), now);
+        token_emails.insert_at(
+            "b".into(),
+            "b@systeminit.com".into(),
+            now + Duration::from_secs(30),
+        );
+
+        let later = now + Duration::from_secs(60);
+        assert_eq!(None, token_emails.get_at("a", later));
+        assert_eq!(
+            Some("b@systeminit.com".to_string()),
+            token_emails.get_at("b", later)
+        );
+        assert_eq!(1, token_emails.len());
+
+        // Inserting also sweeps out anything which has expired
+        let much_later = now + Duration::from_secs(120);
+        token_emails.insert_at("c".into(), "c@systeminit.com".into(), much_later);
+        assert_eq!(1, token_emails.len());
+        assert_eq!(1, token_emails.recency.len());
+        assert_eq!(1, token_emails.expiry.len());
+    }
+}
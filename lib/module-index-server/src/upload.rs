@@ -0,0 +1,417 @@
+//! Streams uploaded packages to S3 as they arrive, so that a large package is never held in memory
+//! while it is being uploaded.
+//!
+//! Uploads are written to a staging key with an S3 multipart upload, part by part, while being
+//! spooled to a temporary file and hashed. Once the package has been parsed from the spool, as it
+//! is read, to learn its metadata, the staged object is moved to its final key.
+
+use std::{
+    io::{
+        self,
+        Read,
+    },
+    pin::pin,
+};
+
+use axum::{
+    body::Bytes,
+    extract::multipart::{
+        Field,
+        MultipartError,
+    },
+};
+use futures::{
+    Stream,
+    StreamExt,
+};
+use s3::{
+    Bucket,
+    error::S3Error,
+    serde_types::Part,
+};
+use si_hash::Hash;
+use si_pkg::{
+    SiPkg,
+    SiPkgError,
+};
+use telemetry::prelude::*;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tokio::io::{
+    AsyncWrite,
+    AsyncWriteExt,
+};
+use ulid::Ulid;
+
+/// The size of each part uploaded to S3, and so the most of an upload held in memory at once.
+///
+/// S3 requires every part but the last to be at least 5MiB.
+pub const UPLOAD_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Uploads are staged under this prefix until they are moved to their final key, and deleted from
+/// it when the request fails.
+const STAGING_PREFIX: &str = "uploads";
+
+const CONTENT_TYPE: &str = "application/octet-stream";
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum UploadError {
+    #[error("spooled upload has hash {actual}, but {expected} was uploaded")]
+    ContentHashMismatch { expected: Hash, actual: Hash },
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("multipart decode error: {0}")]
+    Multipart(#[from] MultipartError),
+    #[error("module parsing error: {0}")]
+    Pkg(#[from] SiPkgError),
+    #[error("s3 error: {0}")]
+    S3(#[from] S3Error),
+    #[error("tokio task join error: {0}")]
+    TaskJoin(#[from] tokio::task::JoinError),
+}
+
+pub type UploadResult<T> = Result<T, UploadError>;
+
+/// Where the parts of a streamed upload are sent.
+pub(crate) trait PartSink {
+    async fn put_part(&mut self, part_number: u32, part: Vec<u8>) -> Result<(), S3Error>;
+}
+
+/// An in progress S3 multipart upload.
+struct S3MultipartUpload<'a> {
+    bucket: &'a Bucket,
+    key: String,
+    upload_id: String,
+    parts: Vec<Part>,
+}
+
+impl<'a> S3MultipartUpload<'a> {
+    async fn initiate(bucket: &'a Bucket, key: String) -> Result<Self, S3Error> {
+        let response = bucket.initiate_multipart_upload(&key, CONTENT_TYPE).await?;
+
+        Ok(Self {
+            bucket,
+            key,
+            upload_id: response.upload_id,
+            parts: Vec::new(),
+        })
+    }
+
+    async fn complete(&mut self) -> Result<(), S3Error> {
+        self.bucket
+            .complete_multipart_upload(&self.key, &self.upload_id, std::mem::take(&mut self.parts))
+            .await?;
+        Ok(())
+    }
+
+    async fn abort(self) -> Result<(), S3Error> {
+        self.bucket.abort_upload(&self.key, &self.upload_id).await
+    }
+}
+
+impl PartSink for S3MultipartUpload<'_> {
+    async fn put_part(&mut self, part_number: u32, part: Vec<u8>) -> Result<(), S3Error> {
+        let part = self
+            .bucket
+            .put_multipart_chunk(part, &self.key, part_number, &self.upload_id, CONTENT_TYPE)
+            .await?;
+        self.parts.push(part);
+        Ok(())
+    }
+}
+
+/// The result of streaming an upload through [`stream_parts`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct StreamedUpload {
+    pub content_hash: Hash,
+    pub size: u64,
+}
+
+/// Splits a stream of chunks into parts of `part_size` bytes for `sink`, copying every chunk to
+/// `spool` and hashing it along the way. At most one part is buffered at a time.
+///
+/// At least one part is always sent, even for an empty stream, since a multipart upload can not
+/// be completed without one.
+pub(crate) async fn stream_parts<S, E, W>(
+    chunks: S,
+    part_size: usize,
+    sink: &mut impl PartSink,
+    spool: &mut W,
+) -> UploadResult<StreamedUpload>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Into<UploadError>,
+    W: AsyncWrite + Unpin,
+{
+    let mut chunks = pin!(chunks);
+    let mut hasher = blake3::Hasher::new();
+    let mut size = 0;
+    let mut part = Vec::with_capacity(part_size);
+    let mut part_number = 0;
+
+    while let Some(chunk) = chunks.next().await {
+        let mut chunk = chunk.map_err(Into::into)?;
+        hasher.update(&chunk);
+        spool.write_all(&chunk).await?;
+        size += chunk.len() as u64;
+
+        while !chunk.is_empty() {
+            let take = (part_size - part.len()).min(chunk.len());
+            part.extend_from_slice(&chunk.split_to(take));
+            if part.len() == part_size {
+                part_number += 1;
+                let full_part = std::mem::replace(&mut part, Vec::with_capacity(part_size));
+                sink.put_part(part_number, full_part).await?;
+            }
+        }
+    }
+
+    if !part.is_empty() || part_number == 0 {
+        part_number += 1;
+        sink.put_part(part_number, part).await?;
+    }
+    spool.flush().await?;
+
+    Ok(StreamedUpload {
+        content_hash: hasher.finalize().into(),
+        size,
+    })
+}
+
+/// Parses a package from `spool` as it is read, hashing it along the way to check that it is
+/// the upload which was hashed as it was streamed.
+pub(crate) fn load_spooled_pkg(spool: impl Read, expected: Hash) -> UploadResult<SiPkg> {
+    let mut reader = HashingReader {
+        inner: spool,
+        hasher: blake3::Hasher::new(),
+    };
+    let pkg = SiPkg::load_from_reader(&mut reader)?;
+    // The package may end before the spool does, so the rest is read just to be hashed
+    io::copy(&mut reader, &mut io::sink())?;
+
+    let actual: Hash = reader.hasher.finalize().into();
+    if actual != expected {
+        return Err(UploadError::ContentHashMismatch { expected, actual });
+    }
+
+    Ok(pkg)
+}
+
+/// Hashes everything read through it.
+struct HashingReader<R> {
+    inner: R,
+    hasher: blake3::Hasher,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// A package which has been streamed to a staging key in S3 and spooled to a temporary file, but
+/// not yet moved to its final key.
+#[derive(Debug)]
+pub struct StagedUpload {
+    key: String,
+    streamed: StreamedUpload,
+    spool: NamedTempFile,
+}
+
+impl StagedUpload {
+    /// Streams a multipart field to a staging key in S3.
+    pub async fn stage(bucket: &Bucket, field: Field<'_>) -> UploadResult<Self> {
+        let key = format!("{STAGING_PREFIX}/{}", Ulid::new());
+        let spool = NamedTempFile::new()?;
+        let mut spool_writer = tokio::fs::File::from_std(spool.reopen()?);
+
+        let mut upload = S3MultipartUpload::initiate(bucket, key.clone()).await?;
+        let streamed = match Self::stream_and_complete(field, &mut upload, &mut spool_writer).await
+        {
+            Ok(streamed) => streamed,
+            Err(err) => {
+                if let Err(abort_err) = upload.abort().await {
+                    warn!("failed to abort upload {}: {}", key, abort_err);
+                }
+                return Err(err);
+            }
+        };
+
+        debug!(
+            "staged upload {} of {} bytes with hash {}",
+            key, streamed.size, streamed.content_hash
+        );
+
+        Ok(Self {
+            key,
+            streamed,
+            spool,
+        })
+    }
+
+    async fn stream_and_complete(
+        field: Field<'_>,
+        upload: &mut S3MultipartUpload<'_>,
+        spool_writer: &mut tokio::fs::File,
+    ) -> UploadResult<StreamedUpload> {
+        let streamed = stream_parts(field, UPLOAD_PART_SIZE, upload, spool_writer).await?;
+        upload.complete().await?;
+        Ok(streamed)
+    }
+
+    /// Parses the package from the spool as it is read, checking it matches what was sent to S3.
+    pub async fn load_pkg(&self) -> UploadResult<SiPkg> {
+        let spool = self.spool.reopen()?;
+        let expected = self.streamed.content_hash;
+
+        tokio::task::spawn_blocking(move || load_spooled_pkg(spool, expected)).await?
+    }
+
+    /// Moves the staged object to its final key. The staged object is deleted whether or not it
+    /// could be copied.
+    pub async fn promote(self, bucket: &Bucket, key: impl AsRef<str>) -> UploadResult<()> {
+        let copied = bucket.copy_object_internal(&self.key, key.as_ref()).await;
+        self.discard(bucket).await;
+        copied?;
+        Ok(())
+    }
+
+    /// Deletes the staged object, for an upload which will not be promoted. Failing to delete it
+    /// is logged rather than returned, since the request is already failing.
+    pub async fn discard(self, bucket: &Bucket) {
+        if let Err(err) = bucket.delete_object(&self.key).await {
+            warn!("failed to delete staged upload {}: {}", self.key, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use si_pkg::PkgSpec;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        parts: Vec<(u32, Vec<u8>)>,
+    }
+
+    impl PartSink for RecordingSink {
+        async fn put_part(&mut self, part_number: u32, part: Vec<u8>) -> Result<(), S3Error> {
+            self.parts.push((part_number, part));
+            Ok(())
+        }
+    }
+
+    fn chunked(payload: &[u8], chunk_size: usize) -> impl Stream<Item = Result<Bytes, io::Error>> {
+        let chunks: Vec<_> = payload
+            .chunks(chunk_size)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        stream::iter(chunks)
+    }
+
+    #[tokio::test]
+    async fn large_upload_is_streamed_in_bounded_parts() {
+        // Enough for a few full parts and a partial one, sent in chunks which do not line up
+        // with part boundaries
+        let payload: Vec<u8> = (0..UPLOAD_PART_SIZE * 3 + 12_345)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let mut sink = RecordingSink::default();
+        let mut spool = Vec::new();
+
+        let streamed = stream_parts(
+            chunked(&payload, 64 * 1024 + 7),
+            UPLOAD_PART_SIZE,
+            &mut sink,
+            &mut spool,
+        )
+        .await
+        .expect("failed to stream upload");
+
+        assert_eq!(Hash::new(&payload), streamed.content_hash);
+        assert_eq!(payload.len() as u64, streamed.size);
+        assert_eq!(payload, spool);
+
+        let part_numbers: Vec<u32> = sink.parts.iter().map(|(number, _)| *number).collect();
+        assert_eq!(vec![1, 2, 3, 4], part_numbers);
+        for (_, part) in &sink.parts[..3] {
+            assert_eq!(UPLOAD_PART_SIZE, part.len());
+        }
+        assert_eq!(12_345, sink.parts[3].1.len());
+
+        let uploaded: Vec<u8> = sink.parts.into_iter().flat_map(|(_, part)| part).collect();
+        assert_eq!(payload, uploaded);
+    }
+
+    #[tokio::test]
+    async fn empty_upload_sends_one_part() {
+        let mut sink = RecordingSink::default();
+        let mut spool = Vec::new();
+
+        let streamed = stream_parts(chunked(&[], 1), UPLOAD_PART_SIZE, &mut sink, &mut spool)
+            .await
+            .expect("failed to stream upload");
+
+        assert_eq!(Hash::new(&[]), streamed.content_hash);
+        assert_eq!(1, sink.parts.len());
+        assert!(sink.parts[0].1.is_empty());
+    }
+
+    fn pkg_bytes() -> Vec<u8> {
+        let spec = PkgSpec::builder()
+            .name("hotel california")
+            .version("1")
+            .created_by("eagles@example.com")
+            .build()
+            .expect("failed to build spec");
+        SiPkg::load_from_spec(spec)
+            .expect("failed to load spec")
+            .write_to_bytes()
+            .expect("failed to write pkg")
+    }
+
+    #[tokio::test]
+    async fn spooled_upload_is_parsed_as_it_is_read() {
+        let payload = pkg_bytes();
+        let mut sink = RecordingSink::default();
+        let mut spool = Vec::new();
+        let streamed = stream_parts(
+            chunked(&payload, 100),
+            UPLOAD_PART_SIZE,
+            &mut sink,
+            &mut spool,
+        )
+        .await
+        .expect("failed to stream upload");
+
+        let pkg = load_spooled_pkg(spool.as_slice(), streamed.content_hash)
+            .expect("failed to load spooled pkg");
+
+        assert_eq!(
+            SiPkg::load_from_bytes(&payload)
+                .expect("failed to load pkg")
+                .hash()
+                .expect("failed to hash pkg"),
+            pkg.hash().expect("failed to hash spooled pkg")
+        );
+    }
+
+    #[test]
+    fn spool_differing_from_the_upload_is_rejected() {
+        let payload = pkg_bytes();
+        let uploaded_hash = Hash::new(&payload);
+        let mut spool = payload;
+        spool.extend_from_slice(b"trailing bytes which were never uploaded");
+
+        assert!(matches!(
+            load_spooled_pkg(spool.as_slice(), uploaded_hash),
+            Err(UploadError::ContentHashMismatch { expected, .. }) if expected == uploaded_hash
+        ));
+    }
+}
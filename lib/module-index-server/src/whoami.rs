@@ -1,7 +1,4 @@
-use std::{
-    collections::HashMap,
-    sync::Arc,
-};
+use std::sync::Arc;
 
 use auth_api_client::{
     client::AuthApiClient,
@@ -11,6 +8,8 @@ use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
+use crate::token_emails::TokenEmails;
+
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum WhoamiError {
@@ -25,12 +24,12 @@ type WhoamiResult<T> = Result<T, WhoamiError>;
 pub async fn get_email_for_auth_token(
     auth_api_url: &str,
     token: &str,
-    token_map: Arc<Mutex<HashMap<String, String>>>,
+    token_map: Arc<Mutex<TokenEmails>>,
 ) -> WhoamiResult<String> {
     let mut token_map = token_map.lock().await;
 
     match token_map.get(token) {
-        Some(email) => Ok(email.into()),
+        Some(email) => Ok(email),
         None => {
             let auth_api_client = match AuthApiClient::from_bearer_token(auth_api_url, token) {
                 Ok(client) => client,
@@ -72,7 +71,7 @@ pub fn is_systeminit_email(email: &str) -> bool {
 pub async fn is_systeminit_auth_token(
     auth_api_url: &str,
     token: &str,
-    token_map: Arc<Mutex<HashMap<String, String>>>,
+    token_map: Arc<Mutex<TokenEmails>>,
 ) -> WhoamiResult<bool> {
     Ok(is_systeminit_email(
         &get_email_for_auth_token(auth_api_url, token, token_map).await?,
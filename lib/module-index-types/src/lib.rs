@@ -29,6 +29,36 @@ pub struct BuiltinsDetailsResponse {
     pub modules: Vec<ModuleDetailsResponse>,
 }
 
+/// Narrows the builtin modules listed by the module index. A module is only listed if it matches
+/// every part of the filter which is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleFilter {
+    /// A glob matched against the module name, where `*` matches any run of characters and `?`
+    /// matches any single character.
+    pub name: Option<String>,
+    /// The category of the schema in the module.
+    pub category: Option<String>,
+    /// The schemas the module may be for. Empty means any schema.
+    pub schema_ids: Vec<Ulid>,
+}
+
+impl ModuleFilter {
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn with_schema_ids(mut self, schema_ids: impl IntoIterator<Item = Ulid>) -> Self {
+        self.schema_ids = schema_ids.into_iter().collect();
+        self
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListModulesResponse {
@@ -1,3 +1,4 @@
 pub mod header;
 pub mod jetstream;
+pub mod prefix_check;
 pub mod subject;
@@ -0,0 +1,180 @@
+//! A startup handshake checking that services agree on their NATS subject prefix.
+//!
+//! Each service derives its subject prefix from its own configuration. If, for example, sdf and
+//! pinga disagree, jobs are published under one prefix and consumed from another, and they
+//! silently never run.
+//!
+//! Services which consume work run a [`PrefixCheckResponder`], which listens for checks under its
+//! own subject prefix. Services which produce work call [`verify`] at startup, which asks for a
+//! responder for a given service under *our* subject prefix and warns if none reply.
+//!
+//! Since several deployments may share a NATS server, each with its own prefix, the check never
+//! looks outside of our own subjects, and a missing responder is only a warning: the service may
+//! simply not have started yet.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_data_nats::{
+    NatsClient,
+    Subject,
+    Subscriber,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// The default amount of time to wait for a reply when verifying a service's prefix.
+pub const DEFAULT_VERIFY_TIMEOUT: Duration = Duration::from_secs(2);
+
+const SUBJECT_SUFFIX: &str = "si.subject_prefix_check";
+
+/// The names of services which respond to prefix checks.
+pub mod service {
+    pub const PINGA: &str = "pinga";
+    pub const VERITECH: &str = "veritech";
+}
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum PrefixCheckError {
+    #[error("nats error: {0}")]
+    Nats(#[from] si_data_nats::NatsError),
+    #[error("serde json error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+type PrefixCheckResult<T> = Result<T, PrefixCheckError>;
+
+/// The reply sent by a [`PrefixCheckResponder`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefixCheckReply {
+    pub service: String,
+    pub subject_prefix: Option<String>,
+}
+
+/// The result of a [`verify`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefixCheckOutcome {
+    /// An instance of the service is using our prefix.
+    Matched,
+    /// No instance of the service replied under our prefix, either because it has not started
+    /// yet or because it is using another prefix.
+    NoResponders,
+}
+
+/// Returns the subject, under the given prefix, on which responders for the given service listen.
+pub fn subject(prefix: Option<&str>, service: &str) -> Subject {
+    crate::subject::prefixed(prefix, format!("{SUBJECT_SUFFIX}.{service}"))
+}
+
+/// Replies to prefix checks for a service with the subject prefix of its NATS client.
+#[derive(Debug)]
+pub struct PrefixCheckResponder {
+    nats: NatsClient,
+    subscriber: Subscriber,
+    reply: Vec<u8>,
+    token: CancellationToken,
+}
+
+impl PrefixCheckResponder {
+    /// Creates a new [`PrefixCheckResponder`] for the given service.
+    pub async fn new(
+        nats: NatsClient,
+        service: &str,
+        token: CancellationToken,
+    ) -> PrefixCheckResult<Self> {
+        let subject_prefix = nats.metadata().subject_prefix().map(ToOwned::to_owned);
+        let subscriber = nats
+            .subscribe(subject(subject_prefix.as_deref(), service))
+            .await?;
+        let reply = serde_json::to_vec(&PrefixCheckReply {
+            service: service.to_owned(),
+            subject_prefix,
+        })?;
+
+        Ok(Self {
+            nats,
+            subscriber,
+            reply,
+            token,
+        })
+    }
+
+    /// Runs the [`PrefixCheckResponder`] until cancelled.
+    pub async fn run(mut self) {
+        loop {
+            tokio::select! {
+                maybe_message = self.subscriber.next() => {
+                    let Some(message) = maybe_message else {
+                        break;
+                    };
+                    let Some(reply) = message.reply() else {
+                        continue;
+                    };
+                    if let Err(err) = self
+                        .nats
+                        .publish(reply.to_owned(), self.reply.clone().into())
+                        .await
+                    {
+                        warn!(si.error.message = ?err, "failed to reply to subject prefix check");
+                    }
+                }
+                _ = self.token.cancelled() => break,
+            }
+        }
+        debug!("subject prefix check responder shut down");
+    }
+}
+
+/// Asks for a responder for the given service under the prefix of our NATS client, warning if
+/// none replies within `timeout`.
+pub async fn verify(
+    nats: &NatsClient,
+    service: &str,
+    timeout: Duration,
+) -> PrefixCheckResult<PrefixCheckOutcome> {
+    let expected = nats.metadata().subject_prefix();
+    let inbox = nats.new_inbox();
+    let mut subscriber = nats.subscribe(inbox.clone()).await?;
+    nats.publish_with_reply(subject(expected, service), inbox, Vec::new().into())
+        .await?;
+    nats.flush().await?;
+
+    let mut outcome = PrefixCheckOutcome::NoResponders;
+    let deadline = tokio::time::Instant::now() + timeout;
+    while let Ok(Some(message)) = tokio::time::timeout_at(deadline, subscriber.next()).await {
+        match serde_json::from_slice::<PrefixCheckReply>(message.payload()) {
+            // No need to wait out the timeout once one has replied
+            Ok(_) => {
+                outcome = PrefixCheckOutcome::Matched;
+                break;
+            }
+            Err(err) => {
+                warn!(si.error.message = ?err, "ignoring malformed subject prefix check reply")
+            }
+        }
+    }
+    subscriber.unsubscribe().await?;
+
+    match outcome {
+        PrefixCheckOutcome::Matched => {
+            info!(service, "verified subject prefix matches");
+        }
+        PrefixCheckOutcome::NoResponders => {
+            warn!(
+                service,
+                subject_prefix = ?expected,
+                "no replies to subject prefix check under our subject prefix; if the service has \
+                 started, check the nats subject prefix configuration of each service"
+            );
+        }
+    }
+
+    Ok(outcome)
+}
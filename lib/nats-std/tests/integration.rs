@@ -0,0 +1,99 @@
+use std::{
+    env,
+    error,
+    time::Duration,
+};
+
+use nats_std::prefix_check::{
+    self,
+    PrefixCheckOutcome,
+    PrefixCheckResponder,
+};
+use si_data_nats::{
+    NatsClient,
+    NatsConfig,
+};
+use tokio_util::sync::CancellationToken;
+use ulid::Ulid;
+
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+async fn nats(subject_prefix: &str) -> std::result::Result<NatsClient, Box<dyn error::Error>> {
+    let mut config = NatsConfig::default();
+
+    #[allow(clippy::disallowed_methods)]
+    if let Ok(url) = env::var("NATS_URL") {
+        config.url = url;
+    } else if let Ok(url) = env::var("SI_TEST_NATS_URL") {
+        config.url = url;
+    } else {
+        config.url = "nats://localhost:4222".to_owned();
+    }
+    config.subject_prefix = Some(subject_prefix.to_owned());
+
+    Ok(NatsClient::new(&config).await?)
+}
+
+async fn run_responder(
+    subject_prefix: &str,
+    token: CancellationToken,
+) -> std::result::Result<(), Box<dyn error::Error>> {
+    let responder = PrefixCheckResponder::new(
+        nats(subject_prefix).await?,
+        prefix_check::service::PINGA,
+        token,
+    )
+    .await?;
+    tokio::spawn(responder.run());
+    Ok(())
+}
+
+#[tokio::test]
+async fn matching_prefix() -> std::result::Result<(), Box<dyn error::Error>> {
+    let subject_prefix = Ulid::new().to_string();
+    let token = CancellationToken::new();
+    run_responder(&subject_prefix, token.clone()).await?;
+
+    let outcome = prefix_check::verify(
+        &nats(&subject_prefix).await?,
+        prefix_check::service::PINGA,
+        TIMEOUT,
+    )
+    .await?;
+    token.cancel();
+
+    assert_eq!(PrefixCheckOutcome::Matched, outcome);
+    Ok(())
+}
+
+#[tokio::test]
+async fn mismatching_prefix_is_not_an_error() -> std::result::Result<(), Box<dyn error::Error>> {
+    // Another deployment sharing the NATS server, or a misconfigured service, is only heard under
+    // its own prefix
+    let token = CancellationToken::new();
+    run_responder(&Ulid::new().to_string(), token.clone()).await?;
+
+    let outcome = prefix_check::verify(
+        &nats(&Ulid::new().to_string()).await?,
+        prefix_check::service::PINGA,
+        TIMEOUT,
+    )
+    .await?;
+    token.cancel();
+
+    assert_eq!(PrefixCheckOutcome::NoResponders, outcome);
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_responders() -> std::result::Result<(), Box<dyn error::Error>> {
+    let outcome = prefix_check::verify(
+        &nats(&Ulid::new().to_string()).await?,
+        prefix_check::service::VERITECH,
+        TIMEOUT,
+    )
+    .await?;
+
+    assert_eq!(PrefixCheckOutcome::NoResponders, outcome);
+    Ok(())
+}
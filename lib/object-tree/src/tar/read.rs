@@ -66,13 +66,26 @@ impl<T> ObjectTree<T> {
     /// - A node file fails to be correctly parsed
     /// - The resulting tree structure has no root node or multiple root nodes
     pub fn read_from_tar<N>(tar_data: &[u8]) -> Result<ObjectTree<N>, TarReadError>
+    where
+        N: ReadBytes,
+    {
+        Self::read_from_tar_reader(tar_data)
+    }
+
+    /// Reads and returns an [`ObjectTree`] from a `tar` read incrementally from `reader`, such as
+    /// a file, rather than from a tar already held in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` for the same reasons as [`ObjectTree::read_from_tar`].
+    pub fn read_from_tar_reader<N>(reader: impl Read) -> Result<ObjectTree<N>, TarReadError>
     where
         N: ReadBytes,
     {
         let mut graph = Graph::new();
         let mut root_idx: Option<NodeIndex> = None;
 
-        let mut unpacked_tar = ::tar::Archive::new(tar_data);
+        let mut unpacked_tar = ::tar::Archive::new(reader);
         let mut tar_data = HashMap::new();
         for maybe_tar_entry in unpacked_tar.entries()? {
             let mut tar_entry = maybe_tar_entry?;
@@ -1,4 +1,7 @@
-use std::result;
+use std::{
+    error,
+    result,
+};
 
 use futures::{
     StreamExt as _,
@@ -8,6 +11,7 @@ use nats_std::header;
 pub use pinga_core::{
     api_types,
     api_types::RequestId,
+    nats::JobPriority,
 };
 use pinga_core::{
     api_types::{
@@ -32,9 +36,9 @@ use si_data_nats::{
     Message,
     NatsClient,
     Subject,
-    async_nats::{
-        self,
-        jetstream::context::PublishError,
+    async_nats::jetstream::context::{
+        CreateStreamError,
+        PublishError,
     },
     jetstream::{
         self,
@@ -56,11 +60,28 @@ use telemetry::prelude::*;
 use telemetry_nats::propagation;
 use thiserror::Error;
 
+/// The most of a dead lettered job's debug representation which is kept as its payload.
+const DEAD_LETTER_MAX_PAYLOAD_LEN: usize = 64 * 1024;
+
+/// Headers on a dead lettered job, holding why it could not be dispatched and where it was for.
+pub mod dead_letter_header {
+    pub const CHANGE_SET_ID: &str = "X-Dead-Letter-Change-Set-Id";
+    pub const ERROR: &str = "X-Dead-Letter-Error";
+    pub const JOB_KIND: &str = "X-Dead-Letter-Job-Kind";
+    pub const WORKSPACE_ID: &str = "X-Dead-Letter-Workspace-Id";
+}
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ClientError {
+    #[error("error creating dead letter stream: {0}")]
+    CreateDeadLetterStream(#[source] CreateStreamError),
     #[error("error creating jetstream stream: {0}")]
-    CreateStream(#[source] async_nats::jetstream::context::CreateStreamError),
+    CreateStream(#[source] nats::WorkQueueStreamError),
+    #[error("dead letter publish error: {0}")]
+    DeadLetterPublish(#[source] PublishError),
+    #[error("request of {size} bytes exceeds the nats max payload of {max_payload} bytes")]
+    PayloadTooLarge { size: usize, max_payload: usize },
     #[error("request publish error: {0}")]
     Publish(#[from] PublishError),
     #[error("error parsing reply headers: {0}")]
@@ -79,6 +100,14 @@ pub enum ClientError {
 
 type Error = ClientError;
 
+impl ClientError {
+    /// Returns `true` if the request could never be dispatched, however many times it is retried,
+    /// so it should be dead lettered instead.
+    pub fn is_undeliverable(&self) -> bool {
+        matches!(self, Self::PayloadTooLarge { .. } | Self::Serialize(_))
+    }
+}
+
 type Result<T> = result::Result<T, ClientError>;
 
 pub type PingaClient = Client;
@@ -95,10 +124,13 @@ impl Client {
     pub async fn new(nats: NatsClient) -> Result<Self> {
         let context = jetstream::new(nats.clone());
 
-        // Ensure that the stream is already created
+        // Ensure that the streams are already created
         let _ = nats::pinga_work_queue(&context)
             .await
             .map_err(Error::CreateStream)?;
+        let _ = nats::pinga_dead_letter_stream(&context)
+            .await
+            .map_err(Error::CreateDeadLetterStream)?;
 
         Ok(Self { nats, context })
     }
@@ -116,6 +148,7 @@ impl Client {
             change_set_id,
             JobArgsVCurrent::Action { action_id },
             is_job_blocking,
+            JobPriority::Normal,
         )
         .await
     }
@@ -132,6 +165,7 @@ impl Client {
             change_set_id,
             JobArgsVCurrent::DependentValuesUpdate,
             is_job_blocking,
+            JobPriority::Normal,
         )
         .await
     }
@@ -151,6 +185,7 @@ impl Client {
                 attribute_value_ids,
             },
             is_job_blocking,
+            JobPriority::Normal,
         )
         .await
     }
@@ -177,6 +212,7 @@ impl Client {
                 request_ulid,
             },
             is_job_blocking,
+            JobPriority::Normal,
         )
         .await
     }
@@ -196,10 +232,25 @@ impl Client {
                 debug_func_job_state_id,
             },
             is_job_blocking,
+            JobPriority::Normal,
         )
         .await
     }
 
+    /// Requests a job execution on the given priority lane and returns an awaitable response
+    /// future.
+    pub async fn await_job_with_priority(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        args: JobArgsVCurrent,
+        is_job_blocking: bool,
+        priority: JobPriority,
+    ) -> Result<(RequestId, BoxFuture<'static, Result<JobExecutionResponse>>)> {
+        self.call_with_reply(workspace_id, change_set_id, args, is_job_blocking, priority)
+            .await
+    }
+
     /// Requests an action job execution and doesnt't wait for a response.
     pub async fn dispatch_action_job(
         &self,
@@ -213,6 +264,7 @@ impl Client {
             change_set_id,
             JobArgsVCurrent::Action { action_id },
             is_job_blocking,
+            JobPriority::Normal,
             None,
         )
         .await
@@ -230,6 +282,7 @@ impl Client {
             change_set_id,
             JobArgsVCurrent::DependentValuesUpdate,
             is_job_blocking,
+            JobPriority::Normal,
             None,
         )
         .await
@@ -250,6 +303,7 @@ impl Client {
                 attribute_value_ids,
             },
             is_job_blocking,
+            JobPriority::Normal,
             None,
         )
         .await
@@ -277,6 +331,7 @@ impl Client {
                 request_ulid,
             },
             is_job_blocking,
+            JobPriority::Normal,
             None,
         )
         .await
@@ -297,17 +352,67 @@ impl Client {
                 debug_func_job_state_id,
             },
             is_job_blocking,
+            JobPriority::Normal,
             None,
         )
         .await
     }
 
+    /// Requests a job execution on the given priority lane and doesn't wait for a response.
+    pub async fn dispatch_job_with_priority(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        args: JobArgsVCurrent,
+        is_job_blocking: bool,
+        priority: JobPriority,
+    ) -> Result<RequestId> {
+        self.call_async(
+            workspace_id,
+            change_set_id,
+            args,
+            is_job_blocking,
+            priority,
+            None,
+        )
+        .await
+    }
+
+    /// Publishes a job which could not be dispatched to the dead letter stream, along with why,
+    /// so that it can be inspected later.
+    ///
+    /// Since the job may not be serializable, the payload is its debug representation, cut down
+    /// to [`DEAD_LETTER_MAX_PAYLOAD_LEN`] bytes.
+    pub async fn dead_letter_job(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        args: &JobArgsVCurrent,
+        error: &dyn error::Error,
+    ) -> Result<()> {
+        let (headers, payload) = dead_letter_message(workspace_id, change_set_id, args, error);
+
+        self.context
+            .publish_with_headers(
+                nats::subject::dead_letter(self.context.metadata().subject_prefix()),
+                headers,
+                payload.into(),
+            )
+            .await
+            .map_err(Error::DeadLetterPublish)?
+            .await
+            .map_err(Error::DeadLetterPublish)?;
+
+        Ok(())
+    }
+
     async fn call_async(
         &self,
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
         args: JobArgsVCurrent,
         is_job_blocking: bool,
+        priority: JobPriority,
         maybe_reply_inbox: Option<&Subject>,
     ) -> Result<RequestId> {
         let id = RequestId::new();
@@ -326,17 +431,27 @@ impl Client {
         let mut wid_buf = [0; WorkspacePk::ID_LEN];
         let mut csid_buf = [0; ChangeSetId::ID_LEN];
 
-        let requests_subject = nats::subject::pinga_job(
+        let requests_subject = nats::subject::pinga_job_with_priority(
             self.context.metadata().subject_prefix(),
             workspace_id.array_to_str(&mut wid_buf),
             change_set_id.array_to_str(&mut csid_buf),
             kind,
+            priority,
         );
 
         let mut info = ContentInfo::from(&request);
         let (content_type, payload) = request.to_vec()?;
         info.content_type = content_type.into();
 
+        // The server would reject the request, and would every time it was retried
+        let max_payload = self.nats.server_info().max_payload;
+        if payload.len() > max_payload {
+            return Err(Error::PayloadTooLarge {
+                size: payload.len(),
+                max_payload,
+            });
+        }
+
         let mut headers = HeaderMap::new();
         propagation::inject_headers(&mut headers);
         info.inject_into_headers(&mut headers);
@@ -357,6 +472,7 @@ impl Client {
         change_set_id: ChangeSetId,
         args: JobArgsVCurrent,
         is_job_blocking: bool,
+        priority: JobPriority,
     ) -> Result<(RequestId, BoxFuture<'static, Result<JobExecutionResponse>>)> {
         let reply_inbox: Subject = self.nats.new_inbox().into();
 
@@ -380,6 +496,7 @@ impl Client {
                 change_set_id,
                 args,
                 is_job_blocking,
+                priority,
                 Some(&reply_inbox),
             )
             .await?;
@@ -399,6 +516,27 @@ impl Client {
     }
 }
 
+fn dead_letter_message(
+    workspace_id: WorkspacePk,
+    change_set_id: ChangeSetId,
+    args: &JobArgsVCurrent,
+    error: &dyn error::Error,
+) -> (HeaderMap, Vec<u8>) {
+    let kind: &'static str = args.into();
+
+    let mut headers = HeaderMap::new();
+    propagation::inject_headers(&mut headers);
+    headers.insert(dead_letter_header::ERROR, error.to_string());
+    headers.insert(dead_letter_header::JOB_KIND, kind);
+    headers.insert(dead_letter_header::WORKSPACE_ID, workspace_id.to_string());
+    headers.insert(dead_letter_header::CHANGE_SET_ID, change_set_id.to_string());
+
+    let mut payload = format!("{args:?}").into_bytes();
+    payload.truncate(DEAD_LETTER_MAX_PAYLOAD_LEN);
+
+    (headers, payload)
+}
+
 fn response_from_reply<T>(message: Message) -> Result<T>
 where
     T: Negotiate,
@@ -408,3 +546,46 @@ where
 
     T::negotiate(&content_info, message.payload()).map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_letter_message_carries_job_and_error() {
+        let workspace_id = WorkspacePk::new();
+        let change_set_id = ChangeSetId::new();
+        let args = JobArgsVCurrent::DependentValuesUpdate;
+
+        let (headers, payload) = dead_letter_message(
+            workspace_id,
+            change_set_id,
+            &args,
+            &ClientError::ReplyMissingHeaders,
+        );
+
+        assert_eq!(
+            Some("reply message is missing headers"),
+            headers.get(dead_letter_header::ERROR).map(|v| v.as_str())
+        );
+        assert_eq!(
+            Some("DependentValuesUpdate"),
+            headers
+                .get(dead_letter_header::JOB_KIND)
+                .map(|v| v.as_str())
+        );
+        assert_eq!(
+            Some(workspace_id.to_string().as_str()),
+            headers
+                .get(dead_letter_header::WORKSPACE_ID)
+                .map(|v| v.as_str())
+        );
+        assert_eq!(
+            Some(change_set_id.to_string().as_str()),
+            headers
+                .get(dead_letter_header::CHANGE_SET_ID)
+                .map(|v| v.as_str())
+        );
+        assert_eq!(b"DependentValuesUpdate".to_vec(), payload);
+    }
+}
@@ -1,48 +1,211 @@
+use std::time::Duration;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use si_data_nats::{
-    async_nats,
+    async_nats::{
+        self,
+        jetstream::{
+            context::{
+                CreateStreamError,
+                UpdateStreamError,
+            },
+            stream::StorageType,
+        },
+    },
     jetstream,
 };
+use thiserror::Error;
 
 const NATS_WORK_QUEUE_STREAM_NAME: &str = "PINGA_JOBS";
 const NATS_WORK_QUEUE_STREAM_SUBJECTS: &[&str] = &["pinga.jobs.>"];
+const NATS_DEAD_LETTER_STREAM_NAME: &str = "PINGA_DEAD_LETTERS";
+
+/// How long dead lettered jobs are kept for inspection.
+const DEAD_LETTER_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Retention settings for the Pinga work queue stream.
+///
+/// Unset limits are left to the Jetstream server's defaults (i.e. unbounded), which matches the
+/// behavior of the stream before these settings existed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WorkQueueRetentionConfig {
+    /// Maximum age of any message in the stream, in seconds.
+    pub max_age_secs: Option<u64>,
+    /// Maximum number of messages held by the stream before new messages are discarded.
+    pub max_messages: Option<i64>,
+    /// The storage backend for the stream.
+    pub storage: StorageType,
+}
 
+/// The lane a job is published on. Pinga takes high priority jobs ahead of normal ones, so that
+/// jobs for interactive requests are not stuck behind a flood of background work.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum JobPriority {
+    High,
+    #[default]
+    Normal,
+}
+
+impl JobPriority {
+    /// Returns the higher of the two priorities.
+    pub fn max(self, other: Self) -> Self {
+        if self == Self::High || other == Self::High {
+            Self::High
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WorkQueueStreamError {
+    #[error("failed to get or create the pinga work queue stream: {0}")]
+    Create(#[source] CreateStreamError),
+    #[error("failed to update the pinga work queue stream: {0}")]
+    Update(#[source] UpdateStreamError),
+}
+
+/// Gets the Pinga work queue stream, creating it with the default retention if it does not exist.
+///
+/// The retention of an existing stream is left alone, since it is owned by the Pinga server (see
+/// [`pinga_work_queue_with_retention`]).
 pub async fn pinga_work_queue(
     context: &jetstream::Context,
-) -> Result<async_nats::jetstream::stream::Stream, async_nats::jetstream::context::CreateStreamError>
-{
+) -> Result<async_nats::jetstream::stream::Stream, WorkQueueStreamError> {
+    get_or_create_work_queue(context, &WorkQueueRetentionConfig::default()).await
+}
+
+/// Gets the Pinga work queue stream, creating it if it does not exist, and makes sure its limits
+/// match the given retention.
+///
+/// The stream may already exist with other limits, for example if it was created by a client
+/// before the server started or the retention config has changed since, so it is updated when its
+/// limits differ. The storage type of an existing stream can not be changed and is kept as is.
+pub async fn pinga_work_queue_with_retention(
+    context: &jetstream::Context,
+    retention: &WorkQueueRetentionConfig,
+) -> Result<async_nats::jetstream::stream::Stream, WorkQueueStreamError> {
+    let mut stream = get_or_create_work_queue(context, retention).await?;
+
+    let desired = work_queue_stream_config(context.metadata().subject_prefix(), retention);
+    let current = &stream.cached_info().config;
+    if current.max_age != desired.max_age
+        || message_limit(current.max_messages) != message_limit(desired.max_messages)
+    {
+        let updated = async_nats::jetstream::stream::Config {
+            storage: current.storage,
+            ..desired
+        };
+        context
+            .update_stream(&updated)
+            .await
+            .map_err(WorkQueueStreamError::Update)?;
+        stream = get_or_create_work_queue(context, retention).await?;
+    }
+
+    Ok(stream)
+}
+
+async fn get_or_create_work_queue(
+    context: &jetstream::Context,
+    retention: &WorkQueueRetentionConfig,
+) -> Result<async_nats::jetstream::stream::Stream, WorkQueueStreamError> {
     let prefix = context.metadata().subject_prefix();
 
+    context
+        .get_or_create_stream(work_queue_stream_config(prefix, retention))
+        .await
+        .map_err(WorkQueueStreamError::Create)
+}
+
+/// Gets the stream holding jobs which could not be dispatched, creating it if it does not exist.
+pub async fn pinga_dead_letter_stream(
+    context: &jetstream::Context,
+) -> Result<async_nats::jetstream::stream::Stream, CreateStreamError> {
+    context
+        .get_or_create_stream(dead_letter_stream_config(
+            context.metadata().subject_prefix(),
+        ))
+        .await
+}
+
+fn dead_letter_stream_config(prefix: Option<&str>) -> async_nats::jetstream::stream::Config {
+    async_nats::jetstream::stream::Config {
+        name: nats_std::jetstream::prefixed(prefix, NATS_DEAD_LETTER_STREAM_NAME),
+        description: Some("Pinga jobs which could not be dispatched".to_owned()),
+        subjects: vec![subject::dead_letter(prefix).to_string()],
+        max_age: DEAD_LETTER_MAX_AGE,
+        ..Default::default()
+    }
+}
+
+// Jetstream reports an unlimited message count as `-1`, while our config leaves it at `0`
+fn message_limit(max_messages: i64) -> Option<i64> {
+    (max_messages > 0).then_some(max_messages)
+}
+
+fn work_queue_stream_config(
+    prefix: Option<&str>,
+    retention: &WorkQueueRetentionConfig,
+) -> async_nats::jetstream::stream::Config {
     let subjects: Vec<_> = NATS_WORK_QUEUE_STREAM_SUBJECTS
         .iter()
         .map(|suffix| nats_std::subject::prefixed(prefix, suffix).to_string())
         .collect();
 
-    let stream = context
-        .get_or_create_stream(async_nats::jetstream::stream::Config {
-            name: nats_std::jetstream::prefixed(prefix, NATS_WORK_QUEUE_STREAM_NAME),
-            description: Some("Pinga work queue of jobs".to_owned()),
-            retention: async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
-            discard: async_nats::jetstream::stream::DiscardPolicy::New,
-            allow_direct: true,
-            subjects,
-            ..Default::default()
-        })
-        .await?;
+    let mut config = async_nats::jetstream::stream::Config {
+        name: nats_std::jetstream::prefixed(prefix, NATS_WORK_QUEUE_STREAM_NAME),
+        description: Some("Pinga work queue of jobs".to_owned()),
+        retention: async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
+        discard: async_nats::jetstream::stream::DiscardPolicy::New,
+        allow_direct: true,
+        storage: retention.storage,
+        subjects,
+        ..Default::default()
+    };
+    if let Some(max_age_secs) = retention.max_age_secs {
+        config.max_age = Duration::from_secs(max_age_secs);
+    }
+    if let Some(max_messages) = retention.max_messages {
+        config.max_messages = max_messages;
+    }
 
-    Ok(stream)
+    config
 }
 
 pub mod subject {
     use si_data_nats::Subject;
 
+    use super::JobPriority;
+
     const INCOMING_SUBJECT: &str = "pinga.jobs.*.*.*";
+    const INCOMING_HIGH_PRIORITY_SUBJECT: &str = "pinga.jobs.*.*.*.high";
+    const DEAD_LETTER_SUBJECT: &str = "pinga.deadletter";
     const SUBJECT_PREFIX: &str = "pinga.jobs";
+    const HIGH_PRIORITY_SUFFIX: &str = "high";
 
     #[inline]
     pub fn incoming(prefix: Option<&str>) -> Subject {
         nats_std::subject::prefixed(prefix, INCOMING_SUBJECT)
     }
 
+    #[inline]
+    pub fn incoming_high_priority(prefix: Option<&str>) -> Subject {
+        nats_std::subject::prefixed(prefix, INCOMING_HIGH_PRIORITY_SUBJECT)
+    }
+
+    /// The subject for jobs which could not be dispatched, which is captured by the dead letter
+    /// stream (see [`pinga_dead_letter_stream`](super::pinga_dead_letter_stream)).
+    #[inline]
+    pub fn dead_letter(prefix: Option<&str>) -> Subject {
+        nats_std::subject::prefixed(prefix, DEAD_LETTER_SUBJECT)
+    }
+
     #[inline]
     pub fn pinga_job(
         prefix: Option<&str>,
@@ -55,4 +218,116 @@ pub mod subject {
             format!("{SUBJECT_PREFIX}.{workspace_id}.{change_set_id}.{args}",),
         )
     }
+
+    /// The subject for a job on the given priority lane. Normal priority jobs use the same
+    /// subject as [`pinga_job`], while high priority jobs have a suffix so that they can be
+    /// consumed separately.
+    #[inline]
+    pub fn pinga_job_with_priority(
+        prefix: Option<&str>,
+        workspace_id: &str,
+        change_set_id: &str,
+        args: &str,
+        priority: JobPriority,
+    ) -> Subject {
+        match priority {
+            JobPriority::High => nats_std::subject::prefixed(
+                prefix,
+                format!(
+                    "{SUBJECT_PREFIX}.{workspace_id}.{change_set_id}.{args}.{HIGH_PRIORITY_SUFFIX}"
+                ),
+            ),
+            JobPriority::Normal => pinga_job(prefix, workspace_id, change_set_id, args),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn work_queue_stream_config_defaults() {
+        let config = work_queue_stream_config(None, &WorkQueueRetentionConfig::default());
+
+        assert_eq!(
+            async_nats::jetstream::stream::Config::default().max_age,
+            config.max_age
+        );
+        assert_eq!(
+            async_nats::jetstream::stream::Config::default().max_messages,
+            config.max_messages
+        );
+        assert_eq!(StorageType::File, config.storage);
+        assert_eq!("PINGA_JOBS", config.name);
+    }
+
+    #[test]
+    fn work_queue_stream_config_with_retention() {
+        let retention = WorkQueueRetentionConfig {
+            max_age_secs: Some(3600),
+            max_messages: Some(10_000),
+            storage: StorageType::Memory,
+        };
+
+        let config = work_queue_stream_config(Some("test"), &retention);
+
+        assert_eq!(Duration::from_secs(3600), config.max_age);
+        assert_eq!(10_000, config.max_messages);
+        assert_eq!(StorageType::Memory, config.storage);
+        assert_eq!(
+            async_nats::jetstream::stream::RetentionPolicy::WorkQueue,
+            config.retention
+        );
+        assert_eq!(vec!["test.pinga.jobs.>".to_owned()], config.subjects);
+    }
+
+    #[test]
+    fn dead_letter_stream_config_is_outside_the_work_queue() {
+        let config = dead_letter_stream_config(Some("test"));
+
+        assert_eq!("test_PINGA_DEAD_LETTERS", config.name);
+        assert_eq!(vec!["test.pinga.deadletter".to_owned()], config.subjects);
+        assert_eq!(
+            async_nats::jetstream::stream::RetentionPolicy::Limits,
+            config.retention
+        );
+        assert_eq!(DEAD_LETTER_MAX_AGE, config.max_age);
+    }
+
+    #[test]
+    fn pinga_job_subject_differs_by_priority() {
+        let normal = subject::pinga_job_with_priority(
+            Some("test"),
+            "workspace",
+            "change_set",
+            "DependentValuesUpdate",
+            JobPriority::Normal,
+        );
+        let high = subject::pinga_job_with_priority(
+            Some("test"),
+            "workspace",
+            "change_set",
+            "DependentValuesUpdate",
+            JobPriority::High,
+        );
+
+        assert_eq!(
+            "test.pinga.jobs.workspace.change_set.DependentValuesUpdate",
+            normal.as_str()
+        );
+        assert_eq!(
+            "test.pinga.jobs.workspace.change_set.DependentValuesUpdate.high",
+            high.as_str()
+        );
+        assert_eq!(
+            subject::pinga_job(
+                Some("test"),
+                "workspace",
+                "change_set",
+                "DependentValuesUpdate"
+            ),
+            normal
+        );
+    }
 }
@@ -0,0 +1,68 @@
+use std::{
+    env,
+    error,
+    time::Duration,
+};
+
+use pinga_core::nats::{
+    WorkQueueRetentionConfig,
+    pinga_work_queue,
+    pinga_work_queue_with_retention,
+};
+use si_data_nats::{
+    NatsClient,
+    NatsConfig,
+    jetstream,
+    jetstream::Context,
+};
+use ulid::Ulid;
+
+async fn setup_nats() -> std::result::Result<Context, Box<dyn error::Error>> {
+    let mut config = NatsConfig::default();
+
+    #[allow(clippy::disallowed_methods)]
+    if let Ok(url) = env::var("NATS_URL") {
+        config.url = url;
+    } else if let Ok(url) = env::var("SI_TEST_NATS_URL") {
+        config.url = url;
+    } else {
+        config.url = "nats://localhost:4222".to_owned();
+    }
+    // Get a new stream for every test execution.
+    config.subject_prefix = Some(Ulid::new().to_string());
+
+    let client = NatsClient::new(&config).await?;
+
+    Ok(jetstream::new(client))
+}
+
+#[tokio::test]
+async fn retention_is_applied_to_an_existing_stream()
+-> std::result::Result<(), Box<dyn error::Error>> {
+    let context = setup_nats().await?;
+
+    // A client creates the stream first, with the default retention
+    let mut stream = pinga_work_queue(&context).await?;
+    let created = stream.info().await?.config.clone();
+    assert_eq!(Duration::ZERO, created.max_age);
+
+    let retention = WorkQueueRetentionConfig {
+        max_age_secs: Some(3600),
+        max_messages: Some(1000),
+        ..Default::default()
+    };
+    let mut stream = pinga_work_queue_with_retention(&context, &retention).await?;
+    let updated = stream.info().await?.config.clone();
+    assert_eq!(Duration::from_secs(3600), updated.max_age);
+    assert_eq!(1000, updated.max_messages);
+
+    // Clients getting the stream afterwards leave the server's retention alone
+    let mut stream = pinga_work_queue(&context).await?;
+    let unchanged = stream.info().await?.config.clone();
+    assert_eq!(Duration::from_secs(3600), unchanged.max_age);
+    assert_eq!(1000, unchanged.max_messages);
+
+    context.delete_stream(&created.name).await?;
+
+    Ok(())
+}
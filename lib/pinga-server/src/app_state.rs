@@ -3,7 +3,10 @@ use std::sync::Arc;
 use dal::DalContextBuilder;
 use si_data_nats::NatsClient;
 
-use crate::server::ServerMetadata;
+use crate::{
+    job_results::JobResults,
+    server::ServerMetadata,
+};
 
 /// Application state.
 #[derive(Clone, Debug)]
@@ -14,6 +17,8 @@ pub struct AppState {
     pub(crate) nats: NatsClient,
     /// DAL context builder for each processing request
     pub ctx_builder: DalContextBuilder,
+    /// Claims on running jobs and results of finished jobs, shared with other instances
+    pub(crate) job_results: JobResults,
 }
 
 impl AppState {
@@ -23,12 +28,14 @@ impl AppState {
         concurrency_limit: usize,
         nats: NatsClient,
         ctx_builder: DalContextBuilder,
+        job_results: JobResults,
     ) -> Self {
         Self {
             metadata,
             concurrency_limit,
             nats,
             ctx_builder,
+            job_results,
         }
     }
 }
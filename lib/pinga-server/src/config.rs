@@ -5,6 +5,7 @@ use std::{
 
 use buck2_resources::Buck2Resources;
 use derive_builder::Builder;
+use pinga_core::nats::WorkQueueRetentionConfig;
 use serde::{
     Deserialize,
     Serialize,
@@ -30,6 +31,7 @@ use si_tls::CertificateSource;
 use telemetry::prelude::*;
 use thiserror::Error;
 use ulid::Ulid;
+use veritech_client::CircuitBreakerConfig;
 
 const DEFAULT_CONCURRENCY_LIMIT: usize = 64;
 
@@ -73,6 +75,9 @@ pub struct Config {
     #[builder(default = "default_max_deliver()")]
     max_deliver: i64,
 
+    #[builder(default)]
+    work_queue_retention: WorkQueueRetentionConfig,
+
     #[builder(default = "random_instance_id()")]
     instance_id: String,
 
@@ -84,6 +89,9 @@ pub struct Config {
 
     #[builder(default = "default_service_endpoints_config()")]
     service_endpoints: ServiceEndpointsConfig,
+
+    #[builder(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl StandardConfig for Config {
@@ -128,6 +136,11 @@ impl Config {
         self.max_deliver
     }
 
+    /// Gets the config's retention settings for the NATS JetStream work queue stream.
+    pub fn work_queue_retention(&self) -> WorkQueueRetentionConfig {
+        self.work_queue_retention
+    }
+
     /// Gets the config's instance ID.
     pub fn instance_id(&self) -> &str {
         self.instance_id.as_ref()
@@ -143,6 +156,11 @@ impl Config {
     pub fn service_endpoints(&self) -> &ServiceEndpointsConfig {
         &self.service_endpoints
     }
+
+    /// Gets the config's veritech client circuit breaker settings, if the breaker is enabled.
+    pub fn veritech_circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.veritech_circuit_breaker
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -157,6 +175,8 @@ pub struct ConfigFile {
     concurrency_limit: usize,
     #[serde(default = "default_max_deliver")]
     max_deliver: i64,
+    #[serde(default)]
+    work_queue_retention: WorkQueueRetentionConfig,
     #[serde(default = "random_instance_id")]
     instance_id: String,
     #[serde(default = "default_layer_db_config")]
@@ -165,6 +185,8 @@ pub struct ConfigFile {
     symmetric_crypto_service: SymmetricCryptoServiceConfigFile,
     #[serde(default = "default_service_endpoints_config")]
     service_endpoints: ServiceEndpointsConfig,
+    #[serde(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl Default for ConfigFile {
@@ -174,11 +196,13 @@ impl Default for ConfigFile {
             nats: Default::default(),
             concurrency_limit: default_concurrency_limit(),
             max_deliver: default_max_deliver(),
+            work_queue_retention: Default::default(),
             crypto: Default::default(),
             instance_id: random_instance_id(),
             layer_db_config: default_layer_db_config(),
             symmetric_crypto_service: default_symmetric_crypto_config(),
             service_endpoints: default_service_endpoints_config(),
+            veritech_circuit_breaker: None,
         }
     }
 }
@@ -199,10 +223,12 @@ impl TryFrom<ConfigFile> for Config {
         config.crypto(value.crypto);
         config.concurrency_limit(value.concurrency_limit);
         config.max_deliver(value.max_deliver);
+        config.work_queue_retention(value.work_queue_retention);
         config.instance_id(value.instance_id);
         config.symmetric_crypto_service(value.symmetric_crypto_service.try_into()?);
         config.layer_db_config(value.layer_db_config);
         config.service_endpoints(value.service_endpoints);
+        config.veritech_circuit_breaker(value.veritech_circuit_breaker);
         config.build().map_err(Into::into)
     }
 }
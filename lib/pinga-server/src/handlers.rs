@@ -57,6 +57,12 @@ use thiserror::Error;
 
 use crate::{
     app_state::AppState,
+    job_results::{
+        ClaimedJob,
+        JobClaim,
+        JobResult,
+        JobResults,
+    },
     server::ServerMetadata,
 };
 
@@ -89,6 +95,7 @@ pub async fn process_request(
         concurrency_limit,
         nats,
         ctx_builder,
+        job_results,
     } = state;
 
     let workspace_id = request.workspace_id;
@@ -98,11 +105,50 @@ pub async fn process_request(
     span.record("si.workspace.id", workspace_id.to_string());
     span.record("si.change_set.id", change_set_id.to_string());
 
+    // Jetstream may redeliver a request, possibly to another instance, so skip (and ack) one
+    // whose job is still running, and answer one whose job finished with the recorded result,
+    // rather than executing the job a second time
+    let maybe_claim = match job_results.claim(request.id).await {
+        Ok(JobClaim::Claimed(claim)) => Some(claim),
+        Ok(JobClaim::InProgress) => {
+            warn!(
+                job.id = %request.id,
+                si.workspace.id = %workspace_id,
+                si.change_set.id = %change_set_id,
+                "skipping redelivered job request which is still running",
+            );
+            return Ok(());
+        }
+        Ok(JobClaim::Finished(result)) => {
+            warn!(
+                job.id = %request.id,
+                si.workspace.id = %workspace_id,
+                si.change_set.id = %change_set_id,
+                "skipping job request which has already finished",
+            );
+            if let Some(reply) = maybe_reply {
+                reply_with_result(&nats, reply, &request, result).await;
+            }
+            return Ok(());
+        }
+        // Not being able to check should not stop jobs from running
+        Err(err) => {
+            error!(
+                si.error.message = ?err,
+                job.id = %request.id,
+                "failed to claim job request",
+            );
+            None
+        }
+    };
+
     execute_job(
         metadata,
         concurrency_limit,
         nats,
         ctx_builder,
+        job_results,
+        maybe_claim,
         workspace_id,
         subject,
         maybe_reply,
@@ -146,6 +192,8 @@ async fn execute_job(
     concurrency_limit: usize,
     nats: NatsClient,
     ctx_builder: DalContextBuilder,
+    job_results: JobResults,
+    maybe_claim: Option<ClaimedJob>,
     workspace_id: WorkspacePk,
     subject: Subject,
     maybe_reply: Option<Subject>,
@@ -177,7 +225,12 @@ async fn execute_job(
 
     metric!(counter.pinga_jobs_in_progress = 1, label = job_kind);
 
-    let execution_result = match try_execute_job(ctx_builder, request.clone()).await {
+    let job = try_execute_job(ctx_builder, request.clone());
+    let job_outcome = match maybe_claim {
+        Some(claim) => claim.hold_while(job).await,
+        None => job.await,
+    };
+    let execution_result = match job_outcome {
         Ok(_) => {
             span.record_ok();
             Ok(())
@@ -193,46 +246,62 @@ async fn execute_job(
         }
     };
 
+    let job_result: JobResult = execution_result.map_err(|err| err.to_string());
+    if let Err(err) = job_results.record(id, &job_result).await {
+        error!(
+            si.error.message = ?err,
+            job.invocation_id = %id,
+            "failed to record the result of job",
+        );
+    }
+
     // If a reply was requested, send it
     if let Some(reply) = maybe_reply {
-        let response = JobExecutionResponse::new(JobExecutionResponseVCurrent {
-            id: request.id,
-            workspace_id: request.workspace_id,
-            change_set_id: request.change_set_id,
-            result: match execution_result {
-                Ok(_) => JobExecutionResultVCurrent::Ok,
-                Err(err) => JobExecutionResultVCurrent::Err {
-                    message: err.to_string(),
-                },
-            },
-        });
+        reply_with_result(&nats, reply, &request, job_result).await;
+    }
 
-        let mut info = ContentInfo::from(&response);
-        let (content_type, payload) = match response.to_vec() {
-            Ok(p) => p,
-            Err(err) => {
-                error!(si.error.message = ?err, "failed to serialize response body");
-                return;
-            }
-        };
-        info.content_type = content_type.into();
+    metric!(counter.pinga_jobs_in_progress = -1, label = job_kind);
+}
 
-        let mut headers = HeaderMap::new();
-        propagation::inject_headers(&mut headers);
-        info.inject_into_headers(&mut headers);
+async fn reply_with_result(
+    nats: &NatsClient,
+    reply: Subject,
+    request: &JobExecutionRequest,
+    result: JobResult,
+) {
+    let response = JobExecutionResponse::new(JobExecutionResponseVCurrent {
+        id: request.id,
+        workspace_id: request.workspace_id,
+        change_set_id: request.change_set_id,
+        result: match result {
+            Ok(_) => JobExecutionResultVCurrent::Ok,
+            Err(message) => JobExecutionResultVCurrent::Err { message },
+        },
+    });
 
-        if let Err(err) = nats
-            .publish_with_headers(reply, headers, payload.into())
-            .await
-        {
-            error!(
-                si.error.message = ?err,
-                "unable to publish response of blocking job completion",
-            );
-        };
-    }
+    let mut info = ContentInfo::from(&response);
+    let (content_type, payload) = match response.to_vec() {
+        Ok(p) => p,
+        Err(err) => {
+            error!(si.error.message = ?err, "failed to serialize response body");
+            return;
+        }
+    };
+    info.content_type = content_type.into();
 
-    metric!(counter.pinga_jobs_in_progress = -1, label = job_kind);
+    let mut headers = HeaderMap::new();
+    propagation::inject_headers(&mut headers);
+    info.inject_into_headers(&mut headers);
+
+    if let Err(err) = nats
+        .publish_with_headers(reply, headers, payload.into())
+        .await
+    {
+        error!(
+            si.error.message = ?err,
+            "unable to publish response of blocking job completion",
+        );
+    };
 }
 
 async fn try_execute_job(
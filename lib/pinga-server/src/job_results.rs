@@ -0,0 +1,341 @@
+//! A record of the job requests being executed and the results of those which have finished,
+//! shared by every Pinga instance through a NATS KV bucket keyed by request id.
+//!
+//! Jetstream may redeliver a job request, to this or another instance, whether the job is still
+//! running or has already finished. Before executing a job, an instance claims its request in the
+//! bucket. A redelivered request is skipped while its claim is held, and answered with the
+//! recorded result once the job has finished, rather than executing the job a second time. That
+//! matters for destructive jobs, such as an action deleting a resource.
+//!
+//! A claim is a lease which the instance executing the job renews while it runs. If the instance
+//! dies, its claim lapses and a later redelivery executes the job again.
+
+use std::{
+    future::Future,
+    pin::pin,
+    result,
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use pinga_core::api_types::RequestId;
+use si_data_nats::{
+    async_nats::jetstream::{
+        context::{
+            CreateKeyValueError,
+            KeyValueError,
+            KeyValueErrorKind,
+        },
+        kv,
+    },
+    jetstream,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+const NATS_KV_BUCKET_NAME: &str = "PINGA_JOB_RESULTS";
+
+/// How long the result of a finished job is kept, which bounds how late a redelivery can be
+/// while still being recognized.
+pub const JOB_RESULTS_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long a claim lasts without being renewed.
+const CLAIM_LEASE: Duration = Duration::from_secs(60);
+/// How often the instance executing a job renews its claim.
+const CLAIM_RENEW_INTERVAL: Duration = Duration::from_secs(15);
+
+const STARTED_PREFIX: &str = "started:";
+const FINISHED_OK: &str = "ok";
+const FINISHED_ERR_PREFIX: &str = "error:";
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum JobResultsError {
+    #[error("kv create error: {0}")]
+    Create(#[from] kv::CreateError),
+    #[error("error creating kv store: {0}")]
+    CreateKeyValue(#[from] CreateKeyValueError),
+    #[error("kv entry error: {0}")]
+    Entry(#[from] kv::EntryError),
+    #[error("error getting kv store: {0}")]
+    GetKeyValue(#[from] KeyValueError),
+    #[error("invalid job record: {0:?}")]
+    InvalidRecord(String),
+    #[error("kv put error: {0}")]
+    Put(#[from] kv::PutError),
+    #[error("kv update error: {0}")]
+    Update(#[from] kv::UpdateError),
+}
+
+type Result<T> = result::Result<T, JobResultsError>;
+
+/// The result of a finished job, holding the error message if it failed.
+pub type JobResult = result::Result<(), String>;
+
+/// What is recorded in the bucket for a job request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum JobRecord {
+    /// The job is being executed, and its claim was last renewed at this many milliseconds
+    /// since the Unix epoch.
+    Started {
+        renewed_at_ms: u64,
+    },
+    Finished(JobResult),
+}
+
+impl JobRecord {
+    fn started_now() -> Self {
+        Self::Started {
+            renewed_at_ms: now_ms(),
+        }
+    }
+
+    fn encode(&self) -> String {
+        match self {
+            Self::Started { renewed_at_ms } => format!("{STARTED_PREFIX}{renewed_at_ms}"),
+            Self::Finished(Ok(())) => FINISHED_OK.to_owned(),
+            Self::Finished(Err(message)) => format!("{FINISHED_ERR_PREFIX}{message}"),
+        }
+    }
+
+    fn decode(value: &[u8]) -> Result<Self> {
+        let value = String::from_utf8_lossy(value);
+        if value == FINISHED_OK {
+            Ok(Self::Finished(Ok(())))
+        } else if let Some(message) = value.strip_prefix(FINISHED_ERR_PREFIX) {
+            Ok(Self::Finished(Err(message.to_owned())))
+        } else if let Some(renewed_at_ms) = value
+            .strip_prefix(STARTED_PREFIX)
+            .and_then(|ms| ms.parse().ok())
+        {
+            Ok(Self::Started { renewed_at_ms })
+        } else {
+            Err(JobResultsError::InvalidRecord(value.into_owned()))
+        }
+    }
+
+    /// Returns `true` if this is a claim which has not been renewed within its lease.
+    fn is_lapsed_at(&self, now_ms: u64) -> bool {
+        match self {
+            Self::Started { renewed_at_ms } => {
+                now_ms.saturating_sub(*renewed_at_ms) > CLAIM_LEASE.as_millis() as u64
+            }
+            Self::Finished(_) => false,
+        }
+    }
+}
+
+/// The outcome of claiming a job request.
+#[derive(Debug)]
+pub enum JobClaim {
+    /// The job should be executed by this instance while holding the claim.
+    Claimed(ClaimedJob),
+    /// The job is being executed under another live claim.
+    InProgress,
+    /// The job has already finished, with this result.
+    Finished(JobResult),
+}
+
+#[derive(Clone, Debug)]
+pub struct JobResults {
+    store: kv::Store,
+}
+
+impl JobResults {
+    pub fn new(store: kv::Store) -> Self {
+        Self { store }
+    }
+
+    /// Claims the job for the given request, unless it is already claimed or has finished.
+    pub async fn claim(&self, id: RequestId) -> Result<JobClaim> {
+        let key = id.to_string();
+
+        match self
+            .store
+            .create(&key, JobRecord::started_now().encode().into())
+            .await
+        {
+            Ok(revision) => return Ok(JobClaim::Claimed(self.claimed(key, revision))),
+            Err(err) if matches!(err.kind(), kv::CreateErrorKind::AlreadyExists) => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let maybe_entry = self
+            .store
+            .entry(&key)
+            .await?
+            .filter(|entry| matches!(entry.operation, kv::Operation::Put));
+        let Some(entry) = maybe_entry else {
+            // The record expired in the meantime, so whoever recreates it first claims the job
+            let revision = self
+                .store
+                .create(&key, JobRecord::started_now().encode().into())
+                .await?;
+            return Ok(JobClaim::Claimed(self.claimed(key, revision)));
+        };
+
+        match JobRecord::decode(&entry.value)? {
+            JobRecord::Finished(result) => Ok(JobClaim::Finished(result)),
+            record if record.is_lapsed_at(now_ms()) => {
+                // Only one instance can take over a lapsed claim, since the update fails if
+                // another instance changed the record first
+                match self
+                    .store
+                    .update(
+                        &key,
+                        JobRecord::started_now().encode().into(),
+                        entry.revision,
+                    )
+                    .await
+                {
+                    Ok(revision) => Ok(JobClaim::Claimed(self.claimed(key, revision))),
+                    Err(err) if matches!(err.kind(), kv::UpdateErrorKind::WrongLastRevision) => {
+                        Ok(JobClaim::InProgress)
+                    }
+                    Err(err) => Err(err.into()),
+                }
+            }
+            JobRecord::Started { .. } => Ok(JobClaim::InProgress),
+        }
+    }
+
+    /// Records the result of the job for the given request, releasing its claim.
+    pub async fn record(&self, id: RequestId, result: &JobResult) -> Result<()> {
+        self.store
+            .put(
+                id.to_string(),
+                JobRecord::Finished(result.clone()).encode().into(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn claimed(&self, key: String, revision: u64) -> ClaimedJob {
+        ClaimedJob {
+            store: self.store.clone(),
+            key,
+            revision,
+        }
+    }
+}
+
+/// A claim on a job request held by this instance.
+#[derive(Debug)]
+pub struct ClaimedJob {
+    store: kv::Store,
+    key: String,
+    revision: u64,
+}
+
+impl ClaimedJob {
+    /// Runs the job's future to completion, renewing the claim until it finishes.
+    pub async fn hold_while<F: Future>(mut self, job: F) -> F::Output {
+        let mut job = pin!(job);
+        let mut renew = tokio::time::interval_at(
+            tokio::time::Instant::now() + CLAIM_RENEW_INTERVAL,
+            CLAIM_RENEW_INTERVAL,
+        );
+
+        loop {
+            tokio::select! {
+                output = &mut job => return output,
+                _ = renew.tick() => self.renew().await,
+            }
+        }
+    }
+
+    async fn renew(&mut self) {
+        match self
+            .store
+            .update(
+                &self.key,
+                JobRecord::started_now().encode().into(),
+                self.revision,
+            )
+            .await
+        {
+            Ok(revision) => self.revision = revision,
+            // The job keeps running, but another instance may now execute it too
+            Err(err) => warn!(
+                si.error.message = ?err,
+                job.id = %self.key,
+                "failed to renew the claim on job request",
+            ),
+        }
+    }
+}
+
+/// Gets or creates the bucket recording job requests.
+pub async fn job_results_kv(
+    context: &jetstream::Context,
+    prefix: Option<&str>,
+) -> Result<kv::Store> {
+    let bucket = nats_std::jetstream::prefixed(prefix, NATS_KV_BUCKET_NAME);
+
+    match context.get_key_value(bucket.clone()).await {
+        Ok(kv) => Ok(kv),
+        Err(err) => match err.kind() {
+            KeyValueErrorKind::GetBucket | KeyValueErrorKind::JetStream => Ok(context
+                .create_key_value(kv::Config {
+                    bucket,
+                    description: "Pinga job requests and results".to_owned(),
+                    max_age: JOB_RESULTS_RETENTION,
+                    ..Default::default()
+                })
+                .await?),
+            _ => Err(err.into()),
+        },
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_round_trip() {
+        for record in [
+            JobRecord::Started {
+                renewed_at_ms: 1_700_000_000_000,
+            },
+            JobRecord::Finished(Ok(())),
+            JobRecord::Finished(Err("action failed: error: oh no".to_owned())),
+        ] {
+            assert_eq!(
+                record,
+                JobRecord::decode(record.encode().as_bytes()).expect("failed to decode record")
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_record_is_rejected() {
+        assert!(matches!(
+            JobRecord::decode(b"started:soon"),
+            Err(JobResultsError::InvalidRecord(_))
+        ));
+    }
+
+    #[test]
+    fn claims_lapse_when_not_renewed() {
+        let claim = JobRecord::Started {
+            renewed_at_ms: 1_000,
+        };
+        let lease_ms = CLAIM_LEASE.as_millis() as u64;
+
+        assert!(!claim.is_lapsed_at(1_000 + lease_ms));
+        assert!(claim.is_lapsed_at(1_000 + lease_ms + 1));
+        assert!(!JobRecord::Finished(Ok(())).is_lapsed_at(u64::MAX));
+    }
+}
@@ -15,6 +15,7 @@ use thiserror::Error;
 mod app_state;
 mod config;
 mod handlers;
+mod job_results;
 pub mod server;
 
 pub use si_service_endpoints::{
@@ -54,12 +55,12 @@ pub enum ServerError {
     DedicatedExecutorInitialize(#[from] DedicatedExecutorInitializeError),
     #[error("initialization error: {0}")]
     Initialization(#[from] InitializationError),
+    #[error("job results error: {0}")]
+    JobResults(#[from] job_results::JobResultsError),
     #[error("stream consumer error: {0}")]
     JsConsumer(#[from] async_nats::jetstream::stream::ConsumerError),
     #[error("consumer stream error: {0}")]
     JsConsumerStream(#[from] async_nats::jetstream::consumer::StreamError),
-    #[error("stream create error: {0}")]
-    JsCreateStreamError(#[from] async_nats::jetstream::context::CreateStreamError),
     #[error("layer cache error: {0}")]
     LayerCache(#[from] si_layer_cache::LayerDbError),
     #[error("failed to initialize a nats client: {0}")]
@@ -68,6 +69,8 @@ pub enum ServerError {
     Naxum(#[source] io::Error),
     #[error("pg pool error: {0}")]
     PgPool(#[from] Box<PgPoolError>),
+    #[error("subject prefix check error: {0}")]
+    PrefixCheck(#[from] nats_std::prefix_check::PrefixCheckError),
     #[error("rebaser client error: {0}")]
     Rebaser(#[from] rebaser_client::ClientError),
     #[error("symmetric crypto error: {0}")]
@@ -76,6 +79,8 @@ pub enum ServerError {
     Transactions(#[from] TransactionsError),
     #[error("error when loading cyclone encryption key: {0}")]
     VeritechEncryptionKey(#[from] si_crypto::VeritechEncryptionKeyError),
+    #[error("work queue stream error: {0}")]
+    WorkQueueStream(#[from] pinga_core::nats::WorkQueueStreamError),
 }
 
 impl From<PgPoolError> for ServerError {
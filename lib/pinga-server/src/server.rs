@@ -17,6 +17,14 @@ use dal::{
     ServicesContext,
     feature_flags::FeatureFlagService,
 };
+use futures::stream::{
+    self,
+    PollNext,
+};
+use nats_std::prefix_check::{
+    self,
+    PrefixCheckResponder,
+};
 use naxum::{
     MessageHead,
     ServiceBuilder,
@@ -38,7 +46,8 @@ use naxum::{
     },
 };
 use pinga_core::nats::{
-    pinga_work_queue,
+    WorkQueueRetentionConfig,
+    pinga_work_queue_with_retention,
     subject,
 };
 use rebaser_client::RebaserClient;
@@ -51,6 +60,7 @@ use si_crypto::{
 use si_data_nats::{
     NatsClient,
     NatsConfig,
+    Subject,
     async_nats,
     jetstream,
 };
@@ -65,7 +75,10 @@ use tokio_util::{
     sync::CancellationToken,
     task::TaskTracker,
 };
-use veritech_client::Client as VeritechClient;
+use veritech_client::{
+    CircuitBreakerConfig,
+    Client as VeritechClient,
+};
 
 use crate::{
     Config,
@@ -73,9 +86,14 @@ use crate::{
     ServerResult,
     app_state::AppState,
     handlers,
+    job_results::{
+        JobResults,
+        job_results_kv,
+    },
 };
 
 const CONSUMER_NAME: &str = "pinga-server";
+const HIGH_PRIORITY_CONSUMER_NAME: &str = "pinga-server-high-priority";
 
 /// Server metadata, used with telemetry.
 #[derive(Clone, Debug)]
@@ -99,6 +117,7 @@ impl ServerMetadata {
 pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
+    prefix_check_responder: PrefixCheckResponder,
     shutdown_token: CancellationToken,
 }
 
@@ -123,10 +142,12 @@ impl Server {
 
         let encryption_key = Self::load_encryption_key(config.crypto().clone()).await?;
         let nats = Self::connect_to_nats(config.nats()).await?;
+        Self::verify_subject_prefixes(nats.clone());
         let nats_streams = JetstreamStreams::new(nats.clone()).await?;
         let pg_pool = Self::create_pg_pool(config.pg_pool()).await?;
         let rebaser = Self::create_rebaser_client(nats.clone()).await?;
-        let veritech = Self::create_veritech_client(nats.clone());
+        let veritech =
+            Self::create_veritech_client(nats.clone(), config.veritech_circuit_breaker());
         let job_processor = Self::create_job_processor(nats.clone()).await?;
         let symmetric_crypto_service =
             Self::create_symmetric_crypto_service(config.symmetric_crypto_service()).await?;
@@ -160,6 +181,7 @@ impl Server {
             config.instance_id().to_string(),
             config.concurrency_limit(),
             config.max_deliver(),
+            config.work_queue_retention(),
             services_context,
             token,
         )
@@ -171,6 +193,7 @@ impl Server {
         instance_id: impl Into<String>,
         concurrency_limit: usize,
         max_deliver: i64,
+        work_queue_retention: WorkQueueRetentionConfig,
         services_context: ServicesContext,
         shutdown_token: CancellationToken,
     ) -> ServerResult<Self> {
@@ -191,19 +214,56 @@ impl Server {
         let nats = services_context.nats_conn().clone();
         let context = jetstream::new(nats.clone());
 
-        let incoming = pinga_work_queue(&context)
+        let work_queue = pinga_work_queue_with_retention(&context, &work_queue_retention).await?;
+        let incoming_high_priority = work_queue
+            .create_consumer(Self::incoming_consumer_config(
+                HIGH_PRIORITY_CONSUMER_NAME,
+                subject::incoming_high_priority(prefix.as_deref()),
+                max_deliver,
+            ))
             .await?
+            .stream()
+            .max_messages_per_batch(concurrency_limit)
+            .messages()
+            .await?;
+        let incoming_normal = work_queue
             .create_consumer(Self::incoming_consumer_config(
-                prefix.as_deref(),
+                CONSUMER_NAME,
+                subject::incoming(prefix.as_deref()),
                 max_deliver,
             ))
             .await?
+            .stream()
+            .max_messages_per_batch(concurrency_limit)
             .messages()
             .await?;
+        // Whenever both lanes have a job waiting, the high priority job is taken first. Each lane
+        // prefetches at most `concurrency_limit` jobs, so that jobs this instance cannot start yet
+        // are left for other instances rather than sitting in a local buffer until their acks time
+        // out and they are redelivered.
+        let incoming = stream::select_with_strategy(
+            incoming_high_priority,
+            incoming_normal,
+            prefer_high_priority,
+        );
+
+        let prefix_check_responder = PrefixCheckResponder::new(
+            nats.clone(),
+            prefix_check::service::PINGA,
+            shutdown_token.clone(),
+        )
+        .await?;
 
+        let job_results = JobResults::new(job_results_kv(&context, prefix.as_deref()).await?);
         let ctx_builder = DalContext::builder(services_context, false);
 
-        let state = AppState::new(metadata.clone(), concurrency_limit, nats, ctx_builder);
+        let state = AppState::new(
+            metadata.clone(),
+            concurrency_limit,
+            nats,
+            ctx_builder,
+            job_results,
+        );
 
         let app = ServiceBuilder::new()
             .layer(
@@ -229,6 +289,7 @@ impl Server {
         Ok(Self {
             metadata,
             inner: Box::new(inner.into_future()),
+            prefix_check_responder,
             shutdown_token,
         })
     }
@@ -241,6 +302,7 @@ impl Server {
     }
 
     pub async fn try_run(self) -> ServerResult<()> {
+        tokio::spawn(self.prefix_check_responder.run());
         self.inner.await.map_err(ServerError::Naxum)?;
         info!("pinga main loop shutdown complete");
         Ok(())
@@ -264,6 +326,22 @@ impl Server {
         Ok(client)
     }
 
+    /// Checks, in the background so as not to hold up startup, that veritech is using the same
+    /// NATS subject prefix, warning if it does not appear to be.
+    fn verify_subject_prefixes(nats: NatsClient) {
+        tokio::spawn(async move {
+            if let Err(err) = prefix_check::verify(
+                &nats,
+                prefix_check::service::VERITECH,
+                prefix_check::DEFAULT_VERIFY_TIMEOUT,
+            )
+            .await
+            {
+                warn!(si.error.message = ?err, "failed to check veritech subject prefix");
+            }
+        });
+    }
+
     #[instrument(name = "pinga.init.create_pg_pool", level = "info", skip_all)]
     async fn create_pg_pool(pg_pool_config: &PgPoolConfig) -> ServerResult<PgPool> {
         let pool = PgPool::new(pg_pool_config).await?;
@@ -279,8 +357,11 @@ impl Server {
     }
 
     #[instrument(name = "pinga.init.create_veritech_client", level = "info", skip_all)]
-    fn create_veritech_client(nats: NatsClient) -> VeritechClient {
-        VeritechClient::new(nats)
+    fn create_veritech_client(
+        nats: NatsClient,
+        circuit_breaker: Option<CircuitBreakerConfig>,
+    ) -> VeritechClient {
+        VeritechClient::new_with_circuit_breaker(nats, circuit_breaker)
     }
 
     #[instrument(name = "pinga.init.create_job_processor", level = "info", skip_all)]
@@ -310,12 +391,13 @@ impl Server {
 
     #[inline]
     fn incoming_consumer_config(
-        subject_prefix: Option<&str>,
+        durable_name: &str,
+        filter_subject: Subject,
         max_deliver: i64,
     ) -> async_nats::jetstream::consumer::pull::Config {
         async_nats::jetstream::consumer::pull::Config {
-            durable_name: Some(CONSUMER_NAME.to_owned()),
-            filter_subject: subject::incoming(subject_prefix).to_string(),
+            durable_name: Some(durable_name.to_owned()),
+            filter_subject: filter_subject.to_string(),
             // TODO(nick,fletcher): this should eventually be "1" and not be configurable.
             max_deliver,
             ..Default::default()
@@ -323,6 +405,10 @@ impl Server {
     }
 }
 
+fn prefer_high_priority(_: &mut ()) -> PollNext {
+    PollNext::Left
+}
+
 #[derive(Clone, Debug)]
 struct PingaForSubject {
     prefix: Option<()>,
@@ -352,6 +438,7 @@ where
                     Some(_workspace_id),
                     Some(_change_set_id),
                     Some(kind),
+                    maybe_priority,
                     None,
                 ) = (
                     parts.next(),
@@ -361,8 +448,14 @@ where
                     parts.next(),
                     parts.next(),
                     parts.next(),
+                    parts.next(),
                 ) {
-                    let matched = format!("{prefix}.{p1}.{p2}.:workspace_id.:change_set_id.{kind}");
+                    let matched = match maybe_priority {
+                        Some(priority) => format!(
+                            "{prefix}.{p1}.{p2}.:workspace_id.:change_set_id.{kind}.{priority}"
+                        ),
+                        None => format!("{prefix}.{p1}.{p2}.:workspace_id.:change_set_id.{kind}"),
+                    };
                     req.extensions_mut().insert(MatchedSubject::from(matched));
                 };
             }
@@ -373,6 +466,7 @@ where
                     Some(_workspace_id),
                     Some(_change_set_id),
                     Some(kind),
+                    maybe_priority,
                     None,
                 ) = (
                     parts.next(),
@@ -381,8 +475,14 @@ where
                     parts.next(),
                     parts.next(),
                     parts.next(),
+                    parts.next(),
                 ) {
-                    let matched = format!("{p1}.{p2}.:workspace_id.:change_set_id.{kind}");
+                    let matched = match maybe_priority {
+                        Some(priority) => {
+                            format!("{p1}.{p2}.:workspace_id.:change_set_id.{kind}.{priority}")
+                        }
+                        None => format!("{p1}.{p2}.:workspace_id.:change_set_id.{kind}"),
+                    };
                     req.extensions_mut().insert(MatchedSubject::from(matched));
                 };
             }
@@ -27,6 +27,7 @@ use si_tls::CertificateSource;
 use telemetry::prelude::*;
 use thiserror::Error;
 use ulid::Ulid;
+use veritech_client::CircuitBreakerConfig;
 
 use crate::{
     StandardConfig,
@@ -94,6 +95,9 @@ pub struct Config {
 
     #[builder(default = "default_service_endpoints_config()")]
     service_endpoints: ServiceEndpointsConfig,
+
+    #[builder(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl StandardConfig for Config {
@@ -160,6 +164,11 @@ impl Config {
     pub fn service_endpoints(&self) -> &ServiceEndpointsConfig {
         &self.service_endpoints
     }
+
+    /// Gets the config's veritech client circuit breaker settings, if the breaker is enabled.
+    pub fn veritech_circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.veritech_circuit_breaker
+    }
 }
 
 /// Static feature flags for Rebaser.
@@ -199,6 +208,8 @@ pub struct ConfigFile {
     features: Features,
     #[serde(default = "default_service_endpoints_config")]
     service_endpoints: ServiceEndpointsConfig,
+    #[serde(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl Default for ConfigFile {
@@ -214,6 +225,7 @@ impl Default for ConfigFile {
             quiescent_period_secs: default_quiescent_period_secs(),
             features: Default::default(),
             service_endpoints: default_service_endpoints_config(),
+            veritech_circuit_breaker: None,
         }
     }
 }
@@ -239,6 +251,7 @@ impl TryFrom<ConfigFile> for Config {
         config.quiescent_period(Duration::from_secs(value.quiescent_period_secs));
         config.features(value.features);
         config.service_endpoints(value.service_endpoints);
+        config.veritech_circuit_breaker(value.veritech_circuit_breaker);
         config.build().map_err(Into::into)
     }
 }
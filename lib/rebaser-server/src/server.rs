@@ -70,7 +70,10 @@ use tokio_util::{
     sync::CancellationToken,
     task::TaskTracker,
 };
-use veritech_client::Client as VeritechClient;
+use veritech_client::{
+    CircuitBreakerConfig,
+    Client as VeritechClient,
+};
 
 use crate::{
     Config,
@@ -140,7 +143,8 @@ impl Server {
         let jetstream_streams = JetstreamStreams::new(nats.clone()).await?;
         let pg_pool = Self::create_pg_pool(config.pg_pool()).await?;
         let rebaser = Self::create_rebaser_client(nats.clone()).await?;
-        let veritech = Self::create_veritech_client(nats.clone());
+        let veritech =
+            Self::create_veritech_client(nats.clone(), config.veritech_circuit_breaker());
         let job_processor = Self::create_job_processor(nats.clone()).await?;
         let symmetric_crypto_service =
             Self::create_symmetric_crypto_service(config.symmetric_crypto_service()).await?;
@@ -337,8 +341,11 @@ impl Server {
     }
 
     #[instrument(name = "rebaser.init.create_veritech_client", level = "info", skip_all)]
-    fn create_veritech_client(nats: NatsClient) -> VeritechClient {
-        VeritechClient::new(nats)
+    fn create_veritech_client(
+        nats: NatsClient,
+        circuit_breaker: Option<CircuitBreakerConfig>,
+    ) -> VeritechClient {
+        VeritechClient::new_with_circuit_breaker(nats, circuit_breaker)
     }
 
     #[instrument(name = "rebaser.init.create_job_processor", level = "info", skip_all)]
@@ -21,6 +21,13 @@ struct ApiErrorError {
     message: String,
     #[serde(serialize_with = "status_code_to_u16")]
     status_code: StatusCode,
+    /// The service the error came from, such as `component`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    /// The specific error within its [`kind`](Self::kind), so that clients can match on it rather
+    /// than on the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
 }
 
 fn status_code_to_u16<S>(status_code: &StatusCode, serializer: S) -> Result<S::Ok, S::Error>
@@ -45,11 +52,21 @@ impl ApiError {
             error: ApiErrorError {
                 message: err.to_string(),
                 status_code,
+                kind: None,
+                code: None,
             },
             level: None,
         }
     }
 
+    /// Identifies the error in a machine readable way, by the service it came from and the
+    /// error's name within that service.
+    pub fn with_code(mut self, kind: impl Into<String>, code: impl Into<String>) -> Self {
+        self.error.kind = Some(kind.into());
+        self.error.code = Some(code.into());
+        self
+    }
+
     // keeping this here to allow for future use
     #[allow(dead_code)]
     fn with_level(mut self, level: TracingLevel) -> Self {
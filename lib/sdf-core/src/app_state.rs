@@ -9,6 +9,10 @@ use axum::extract::FromRef;
 use edda_client::EddaClient;
 use frigg::FriggStore;
 use nats_multiplexer_client::MultiplexerClient;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use si_data_spicedb::SpiceDbClient;
 use si_jwt_public_key::JwtPublicSigningKeyChain;
 use tokio::sync::{
@@ -30,7 +34,8 @@ use crate::{
 };
 
 #[remain::sorted]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub enum ApplicationRuntimeMode {
     Maintenance,
     Running,
@@ -46,7 +51,7 @@ pub struct AppState {
     for_tests: bool,
     nats_multiplexer_clients: NatsMultiplexerClients,
     create_workspace_permissions: WorkspacePermissionsMode,
-    create_workspace_allowlist: Vec<WorkspacePermissions>,
+    create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
     pub application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
     shutdown_token: CancellationToken,
     spicedb_client: Option<SpiceDbClient>,
@@ -67,7 +72,7 @@ impl AppState {
         crdt_multiplexer_client: MultiplexerClient,
         edda_updates_multiplexer_client: EddaUpdatesMultiplexerClient,
         create_workspace_permissions: WorkspacePermissionsMode,
-        create_workspace_allowlist: Vec<WorkspacePermissions>,
+        create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
         application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
         shutdown_token: CancellationToken,
         spicedb_client: Option<SpiceDbClient>,
@@ -124,8 +129,9 @@ impl AppState {
         self.create_workspace_permissions
     }
 
-    pub fn create_workspace_allowlist(&self) -> &[String] {
-        &self.create_workspace_allowlist
+    /// Returns the current allowlist, which may be reloaded while the server is running.
+    pub async fn create_workspace_allowlist(&self) -> Vec<WorkspacePermissions> {
+        self.create_workspace_allowlist.read().await.clone()
     }
 
     pub fn shutdown_token(&self) -> &CancellationToken {
@@ -53,6 +53,8 @@ pub enum DalWrapperError {
     InvalidWorkspaceForPermissionLookup(dal::WorkspacePk, dal::WorkspacePk),
     #[error("missing applicable approval id: {0}")]
     MissingApplicableApproval(si_id::ChangeSetApprovalId),
+    #[error("nothing to apply: change set {0} has no changes relative to its base")]
+    NothingToApply(dal::ChangeSetId),
     #[error("no users in workspace: {0}")]
     NoUsersInWorkspace(si_id::WorkspacePk),
     #[error("permissions error: {0}")]
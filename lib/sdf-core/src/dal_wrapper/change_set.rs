@@ -7,6 +7,7 @@ use std::{
 
 use dal::{
     ChangeSet,
+    ChangeSetApplyError,
     DalContext,
     UserPk,
     Workspace,
@@ -64,8 +65,8 @@ pub async fn approval_requirements_are_satisfied_or_error(
 }
 
 /// Applies the current change set to the base change set, but with protections in place, such as
-/// ensuring that the workspace is opt-ed into approvals, requirements are met and that we have
-/// committed preparations.
+/// ensuring that the change set has changes to apply, the workspace is opt-ed into approvals,
+/// requirements are met and that we have committed preparations.
 pub async fn protected_apply_to_base_change_set(
     ctx: &mut DalContext,
     spicedb_client: &mut si_data_spicedb::Client,
@@ -104,7 +105,16 @@ pub async fn protected_apply_to_base_change_set(
     ctx.commit().await?;
 
     // With the requirement check and preparations committed, we can finally perform the apply.
-    ChangeSet::apply_to_base_change_set(ctx).await?;
+    // Applying a change set with no net changes would do nothing, so say so rather than silently
+    // "succeeding".
+    ChangeSet::apply_to_base_change_set_requiring_updates(ctx)
+        .await
+        .map_err(|err| match err {
+            ChangeSetApplyError::NothingToApply(change_set_id) => {
+                DalWrapperError::NothingToApply(change_set_id)
+            }
+            err => err.into(),
+        })?;
 
     Ok(())
 }
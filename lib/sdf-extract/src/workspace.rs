@@ -15,7 +15,12 @@ use axum::{
 use dal::{
     DalContext,
     UserPk,
+    Workspace,
     WorkspacePk,
+    feature_flags::{
+        FeatureFlag,
+        FeatureFlagService,
+    },
 };
 use derive_more::{
     Deref,
@@ -136,11 +141,60 @@ impl FromRequestParts<AppState> for WorkspaceAuthorization {
             .find(|m| m.pk() == user_id)
             .ok_or_else(|| unauthorized_error("User not a member of the workspace"))?;
 
-        Ok(Self {
+        // Stash the authorization, so that middleware and handlers share it
+        let result = Self {
             ctx_without_snapshot,
             user,
             workspace_id,
             authorized_role,
+        };
+        parts.extensions.insert(result.clone());
+
+        Ok(result)
+    }
+}
+
+///
+/// Evaluates feature flags for the target workspace, taking its overrides into account.
+///
+/// - Authenticates the user and checks workspace membership (via WorkspaceAuthorization)
+/// - Loads the workspace for its feature flag overrides
+///
+#[derive(Clone, Debug)]
+pub struct WorkspaceFeatureFlags {
+    feature_flags_service: FeatureFlagService,
+    workspace: Workspace,
+}
+
+impl WorkspaceFeatureFlags {
+    /// Returns whether the feature is enabled for the target workspace.
+    pub fn is_enabled(&self, feature: &FeatureFlag) -> bool {
+        self.feature_flags_service
+            .is_enabled_for_workspace(feature, &self.workspace)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for WorkspaceFeatureFlags {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let WorkspaceAuthorization {
+            ctx_without_snapshot,
+            workspace_id,
+            ..
+        } = parts.extract_with_state(state).await?;
+
+        let workspace = Workspace::get_by_pk(&ctx_without_snapshot, workspace_id)
+            .await
+            .map_err(internal_error)?;
+
+        Ok(Self {
+            feature_flags_service: state.services_context().feature_flags_service().clone(),
+            workspace,
         })
     }
 }
@@ -20,6 +20,10 @@ use crate::{
     ApplicationRuntimeMode,
     WorkspacePermissions,
     WorkspacePermissionsMode,
+    middleware::{
+        DEFAULT_MAX_REQUEST_BODY_BYTES,
+        WorkspaceRateLimits,
+    },
     routes::routes,
 };
 
@@ -38,13 +42,15 @@ impl AxumApp {
         crdt_multiplexer_client: MultiplexerClient,
         edda_updates_multiplexer_client: EddaUpdatesMultiplexerClient,
         create_workspace_permissions: WorkspacePermissionsMode,
-        create_workspace_allowlist: Vec<WorkspacePermissions>,
+        create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
         application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
         shutdown_token: CancellationToken,
         spicedb_client: Option<SpiceDbClient>,
         frigg: FriggStore,
         audit_database_context: AuditDatabaseContext,
         edda_client: EddaClient,
+        workspace_rate_limits: WorkspaceRateLimits,
+        max_request_body_bytes: usize,
     ) -> Self {
         Self::inner_from_services(
             services_context,
@@ -63,6 +69,8 @@ impl AxumApp {
             frigg,
             audit_database_context,
             edda_client,
+            workspace_rate_limits,
+            max_request_body_bytes,
         )
     }
 
@@ -82,13 +90,14 @@ impl AxumApp {
         crdt_multiplexer_client: MultiplexerClient,
         edda_updates_multiplexer_client: EddaUpdatesMultiplexerClient,
         create_workspace_permissions: WorkspacePermissionsMode,
-        create_workspace_allowlist: Vec<WorkspacePermissions>,
+        create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
         application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
         shutdown_token: CancellationToken,
         spicedb_client: SpiceDbClient,
         frigg: FriggStore,
         audit_database_context: AuditDatabaseContext,
         edda_client: EddaClient,
+        workspace_rate_limits: WorkspaceRateLimits,
     ) -> Self {
         Self::inner_from_services(
             services_context,
@@ -107,6 +116,8 @@ impl AxumApp {
             frigg,
             audit_database_context,
             edda_client,
+            workspace_rate_limits,
+            DEFAULT_MAX_REQUEST_BODY_BYTES,
         )
     }
 
@@ -125,13 +136,15 @@ impl AxumApp {
         crdt_multiplexer_client: MultiplexerClient,
         edda_updates_multiplexer_client: EddaUpdatesMultiplexerClient,
         create_workspace_permissions: WorkspacePermissionsMode,
-        create_workspace_allowlist: Vec<WorkspacePermissions>,
+        create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
         application_runtime_mode: Arc<RwLock<ApplicationRuntimeMode>>,
         shutdown_token: CancellationToken,
         spicedb_client: Option<SpiceDbClient>,
         frigg: FriggStore,
         audit_database_context: AuditDatabaseContext,
         edda_client: EddaClient,
+        workspace_rate_limits: WorkspaceRateLimits,
+        max_request_body_bytes: usize,
     ) -> Self {
         let state = AppState::new(
             services_context,
@@ -157,7 +170,7 @@ impl AxumApp {
             _ => None,
         });
 
-        let app = routes(state).layer(
+        let app = routes(state, workspace_rate_limits, max_request_body_bytes).layer(
             TraceLayer::new_for_http()
                 .make_span_with(
                     telemetry_http::HttpMakeSpan::builder()
@@ -9,12 +9,16 @@ use std::{
         Path,
         PathBuf,
     },
+    time::Duration,
 };
 
 use audit_database::AuditDatabaseConfig;
 use buck2_resources::Buck2Resources;
 pub use dal::MigrationMode;
-use dal::feature_flags::FeatureFlag;
+use dal::{
+    KeyPairAlgorithm,
+    feature_flags::FeatureFlag,
+};
 use derive_builder::Builder;
 pub use sdf_core::workspace_permissions::{
     WorkspacePermissions,
@@ -54,6 +58,12 @@ use si_tls::CertificateSource;
 use telemetry::prelude::*;
 use thiserror::Error;
 use ulid::Ulid;
+use veritech_client::CircuitBreakerConfig;
+
+use crate::middleware::{
+    DEFAULT_MAX_REQUEST_BODY_BYTES,
+    WorkspaceRateLimits,
+};
 
 const DEFAULT_MODULE_INDEX_URL: &str = "https://module-index.systeminit.com";
 const DEFAULT_AUTH_API_URL: &str = "https://auth-api.systeminit.com";
@@ -71,6 +81,8 @@ pub enum ConfigError {
     LayerCache(#[from] LayerDbError),
     #[error("no socket addrs where resolved")]
     NoSocketAddrResolved,
+    #[error("failed to serialize config for comparison: {0}")]
+    Serialize(#[from] serde_json::Error),
     #[error("settings error: {0}")]
     Settings(#[from] si_settings::SettingsError),
     #[error("failed to resolve socket addrs")]
@@ -85,6 +97,54 @@ impl ConfigError {
 
 type Result<T> = std::result::Result<T, ConfigError>;
 
+/// The config fields which can be changed on a running server by sending it `SIGHUP`. A change to
+/// any other field needs a restart.
+pub const RELOADABLE_CONFIG_FIELDS: &[&str] = &["boot_feature_flags", "create_workspace_allowlist"];
+
+/// The config fields which are compared when reloading, where any change rejects the reload. The
+/// instance id is left out since it is random unless configured.
+const UNRELOADABLE_CONFIG_FIELDS: &[&str] = &[
+    "incoming_stream",
+    "pg_pool",
+    "module_index_url",
+    "auth_api_url",
+    "nats",
+    "posthog",
+    "symmetric_crypto_service",
+    "migration_mode",
+    "crypto",
+    "jwt_signing_public_key",
+    "jwt_secondary_signing_public_key",
+    "layer_db_config",
+    "spicedb",
+    "pkgs_path",
+    "create_workspace_permissions",
+    "default_key_pair_algorithm",
+    "audit",
+    "dev_mode",
+    "service_endpoints",
+    "backfill_cutoff_timestamp",
+    "backfill_cache_types",
+    "backfill_key_batch_size",
+    "backfill_checkpoint_interval_secs",
+    "backfill_max_concurrent_uploads",
+    "backfill_func_runs_cutoff_id",
+    "backfill_func_run_logs_cutoff_id",
+    "snapshot_migration_concurrency_limit",
+    "module_fetch_max_attempts",
+    "compute_executor_max_task_input_size",
+    "ws_idle_timeout_secs",
+    "pg_pool_max_waiting",
+    "blocking_job_timeout_secs",
+    "max_request_body_bytes",
+    "apply_rate_limit",
+    "execution_rate_limit",
+    "veritech_circuit_breaker",
+];
+
+// Unless it is configured, the layer db cache is given a fresh temporary directory on every load
+const GENERATED_CONFIG_POINTERS: &[&str] = &["/layer_db_config/cache_config/disk_path"];
+
 #[derive(Debug, Builder, Serialize, Clone)]
 pub struct Config {
     #[builder(default = "random_instance_id()")]
@@ -137,6 +197,9 @@ pub struct Config {
 
     create_workspace_allowlist: Vec<WorkspacePermissions>,
 
+    #[builder(default)]
+    default_key_pair_algorithm: KeyPairAlgorithm,
+
     #[builder(default)]
     audit: AuditDatabaseConfig,
 
@@ -166,6 +229,36 @@ pub struct Config {
 
     #[builder(default)]
     backfill_func_run_logs_cutoff_id: Option<String>,
+
+    #[builder(default = "default_snapshot_migration_concurrency_limit()")]
+    snapshot_migration_concurrency_limit: usize,
+
+    #[builder(default = "default_module_fetch_max_attempts()")]
+    module_fetch_max_attempts: u32,
+
+    #[builder(default)]
+    compute_executor_max_task_input_size: Option<usize>,
+
+    #[builder(default)]
+    ws_idle_timeout_secs: Option<u64>,
+
+    #[builder(default)]
+    pg_pool_max_waiting: Option<usize>,
+
+    #[builder(default = "default_blocking_job_timeout_secs()")]
+    blocking_job_timeout_secs: u64,
+
+    #[builder(default = "default_max_request_body_bytes()")]
+    max_request_body_bytes: usize,
+
+    #[builder(default)]
+    apply_rate_limit: Option<u32>,
+
+    #[builder(default)]
+    execution_rate_limit: Option<u32>,
+
+    #[builder(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl StandardConfig for Config {
@@ -267,6 +360,12 @@ impl Config {
         &self.create_workspace_allowlist
     }
 
+    /// Gets the algorithm used for key pairs created without choosing one, such as the default
+    /// key pair of a new workspace
+    pub fn default_key_pair_algorithm(&self) -> KeyPairAlgorithm {
+        self.default_key_pair_algorithm
+    }
+
     /// Gets a referece to the config's spicedb config
     #[must_use]
     pub fn spicedb(&self) -> &SpiceDbConfig {
@@ -316,6 +415,91 @@ impl Config {
     pub fn backfill_func_run_logs_cutoff_id(&self) -> Option<&str> {
         self.backfill_func_run_logs_cutoff_id.as_deref()
     }
+
+    /// Gets the maximum number of workspace snapshots migrated at the same time.
+    ///
+    /// Set with `snapshot_migration_concurrency_limit`, which defaults to
+    /// [`DEFAULT_CONCURRENCY_LIMIT`](dal::workspace_snapshot::migrator::DEFAULT_CONCURRENCY_LIMIT).
+    /// Raising it speeds up migrations at the cost of holding more snapshots in memory at once,
+    /// while a limit of `0` is treated as `1`.
+    pub fn snapshot_migration_concurrency_limit(&self) -> usize {
+        self.snapshot_migration_concurrency_limit
+    }
+
+    /// Gets the number of attempts made to fetch each builtin module from the module index.
+    pub fn module_fetch_max_attempts(&self) -> u32 {
+        self.module_fetch_max_attempts
+    }
+
+    /// Gets the maximum input size, in bytes, of size-checked compute executor tasks, if any.
+    pub fn compute_executor_max_task_input_size(&self) -> Option<usize> {
+        self.compute_executor_max_task_input_size
+    }
+
+    /// Gets the duration after which inactive WebSocket sessions are closed, if any.
+    pub fn ws_idle_timeout(&self) -> Option<Duration> {
+        self.ws_idle_timeout_secs.map(Duration::from_secs)
+    }
+
+    /// Gets how many requests may wait for a database connection before further requests are
+    /// rejected, if any limit is set.
+    pub fn pg_pool_max_waiting(&self) -> Option<usize> {
+        self.pg_pool_max_waiting
+    }
+
+    /// Gets how long to wait for a blocking job to finish before giving up on it.
+    pub fn blocking_job_timeout(&self) -> Duration {
+        Duration::from_secs(self.blocking_job_timeout_secs)
+    }
+
+    /// Gets the largest request body, in bytes, accepted by routes without their own limit.
+    pub fn max_request_body_bytes(&self) -> usize {
+        self.max_request_body_bytes
+    }
+
+    /// Gets the per-workspace limits on change set applies and func executions, each of which is
+    /// off unless set.
+    ///
+    /// These are counted by each sdf replica on its own, so the limit a workspace sees across a
+    /// deployment grows with the number of replicas serving it.
+    pub fn workspace_rate_limits(&self) -> WorkspaceRateLimits {
+        WorkspaceRateLimits {
+            apply: self.apply_rate_limit,
+            execution: self.execution_rate_limit,
+        }
+    }
+
+    /// Gets the config's veritech client circuit breaker settings, if the breaker is enabled.
+    pub fn veritech_circuit_breaker(&self) -> Option<CircuitBreakerConfig> {
+        self.veritech_circuit_breaker
+    }
+
+    /// Returns the names of the fields in [`UNRELOADABLE_CONFIG_FIELDS`] which differ in the
+    /// `reloaded` config.
+    pub fn unreloadable_changes(&self, reloaded: &Config) -> Result<Vec<String>> {
+        Ok(unreloadable_changes(
+            &comparable_config_value(self)?,
+            &comparable_config_value(reloaded)?,
+        ))
+    }
+}
+
+fn comparable_config_value(config: &Config) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(config)?;
+    for pointer in GENERATED_CONFIG_POINTERS {
+        if let Some(generated) = value.pointer_mut(pointer) {
+            *generated = serde_json::Value::Null;
+        }
+    }
+    Ok(value)
+}
+
+fn unreloadable_changes(current: &serde_json::Value, reloaded: &serde_json::Value) -> Vec<String> {
+    UNRELOADABLE_CONFIG_FIELDS
+        .iter()
+        .filter(|field| current.get(field) != reloaded.get(field))
+        .map(|field| field.to_string())
+        .collect()
 }
 
 impl ConfigBuilder {
@@ -365,6 +549,8 @@ pub struct ConfigFile {
     #[serde(default)]
     create_workspace_allowlist: Vec<WorkspacePermissions>,
     #[serde(default)]
+    default_key_pair_algorithm: KeyPairAlgorithm,
+    #[serde(default)]
     spicedb: SpiceDbConfig,
     #[serde(default)]
     audit: AuditDatabaseConfig,
@@ -384,6 +570,26 @@ pub struct ConfigFile {
     backfill_func_runs_cutoff_id: Option<String>,
     #[serde(default)]
     backfill_func_run_logs_cutoff_id: Option<String>,
+    #[serde(default = "default_snapshot_migration_concurrency_limit")]
+    snapshot_migration_concurrency_limit: usize,
+    #[serde(default = "default_module_fetch_max_attempts")]
+    module_fetch_max_attempts: u32,
+    #[serde(default)]
+    compute_executor_max_task_input_size: Option<usize>,
+    #[serde(default)]
+    ws_idle_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pg_pool_max_waiting: Option<usize>,
+    #[serde(default = "default_blocking_job_timeout_secs")]
+    blocking_job_timeout_secs: u64,
+    #[serde(default = "default_max_request_body_bytes")]
+    max_request_body_bytes: usize,
+    #[serde(default)]
+    apply_rate_limit: Option<u32>,
+    #[serde(default)]
+    execution_rate_limit: Option<u32>,
+    #[serde(default)]
+    veritech_circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl Default for ConfigFile {
@@ -405,6 +611,7 @@ impl Default for ConfigFile {
             boot_feature_flags: Default::default(),
             create_workspace_permissions: Default::default(),
             create_workspace_allowlist: Default::default(),
+            default_key_pair_algorithm: Default::default(),
             spicedb: Default::default(),
             audit: Default::default(),
             dev_mode: false,
@@ -416,6 +623,16 @@ impl Default for ConfigFile {
             backfill_max_concurrent_uploads: default_backfill_max_concurrent_uploads(),
             backfill_func_runs_cutoff_id: None,
             backfill_func_run_logs_cutoff_id: None,
+            snapshot_migration_concurrency_limit: default_snapshot_migration_concurrency_limit(),
+            module_fetch_max_attempts: default_module_fetch_max_attempts(),
+            compute_executor_max_task_input_size: None,
+            ws_idle_timeout_secs: None,
+            pg_pool_max_waiting: None,
+            blocking_job_timeout_secs: default_blocking_job_timeout_secs(),
+            max_request_body_bytes: default_max_request_body_bytes(),
+            apply_rate_limit: None,
+            execution_rate_limit: None,
+            veritech_circuit_breaker: None,
         }
     }
 }
@@ -448,6 +665,7 @@ impl TryFrom<ConfigFile> for Config {
             boot_feature_flags: value.boot_feature_flags.into_iter().collect::<HashSet<_>>(),
             create_workspace_permissions: value.create_workspace_permissions,
             create_workspace_allowlist: value.create_workspace_allowlist,
+            default_key_pair_algorithm: value.default_key_pair_algorithm,
             spicedb: value.spicedb,
             audit: value.audit,
             dev_mode: value.dev_mode,
@@ -459,6 +677,16 @@ impl TryFrom<ConfigFile> for Config {
             backfill_max_concurrent_uploads: value.backfill_max_concurrent_uploads,
             backfill_func_runs_cutoff_id: value.backfill_func_runs_cutoff_id,
             backfill_func_run_logs_cutoff_id: value.backfill_func_run_logs_cutoff_id,
+            snapshot_migration_concurrency_limit: value.snapshot_migration_concurrency_limit,
+            module_fetch_max_attempts: value.module_fetch_max_attempts,
+            compute_executor_max_task_input_size: value.compute_executor_max_task_input_size,
+            ws_idle_timeout_secs: value.ws_idle_timeout_secs,
+            pg_pool_max_waiting: value.pg_pool_max_waiting,
+            blocking_job_timeout_secs: value.blocking_job_timeout_secs,
+            max_request_body_bytes: value.max_request_body_bytes,
+            apply_rate_limit: value.apply_rate_limit,
+            execution_rate_limit: value.execution_rate_limit,
+            veritech_circuit_breaker: value.veritech_circuit_breaker,
         })
     }
 }
@@ -536,6 +764,22 @@ fn default_backfill_max_concurrent_uploads() -> usize {
     5
 }
 
+fn default_snapshot_migration_concurrency_limit() -> usize {
+    dal::workspace_snapshot::migrator::DEFAULT_CONCURRENCY_LIMIT
+}
+
+fn default_module_fetch_max_attempts() -> u32 {
+    dal::cached_module::DEFAULT_FETCH_MAX_ATTEMPTS
+}
+
+fn default_blocking_job_timeout_secs() -> u64 {
+    dal::job::processor::DEFAULT_BLOCKING_JOB_TIMEOUT.as_secs()
+}
+
+fn default_max_request_body_bytes() -> usize {
+    DEFAULT_MAX_REQUEST_BODY_BYTES
+}
+
 #[allow(clippy::disallowed_methods)] // Used to determine if running in development
 fn detect_and_configure_development(config: &mut ConfigFile) -> Result<()> {
     if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
@@ -682,3 +926,69 @@ fn cargo_development(dir: String, config: &mut ConfigFile) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(module_index_url: &str) -> Config {
+        Config::builder()
+            .pkgs_path(
+                CanonicalFile::try_from(env!("CARGO_MANIFEST_DIR"))
+                    .expect("manifest dir should exist"),
+            )
+            .module_index_url(module_index_url.to_owned())
+            .boot_feature_flags(HashSet::new())
+            .create_workspace_permissions(WorkspacePermissionsMode::default())
+            .create_workspace_allowlist(Vec::new())
+            .build()
+            .expect("config should build")
+    }
+
+    #[test]
+    fn loading_the_same_sources_twice_reports_no_changes() {
+        let current = load("http://localhost:5157");
+        let reloaded = load("http://localhost:5157");
+
+        // Each load picks its own default cache directory, which must not count as a change
+        assert_ne!(
+            current.layer_db_config().cache_config.disk_path(),
+            reloaded.layer_db_config().cache_config.disk_path()
+        );
+        assert!(
+            current
+                .unreloadable_changes(&reloaded)
+                .expect("configs should serialize")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn changed_unreloadable_fields_are_reported() {
+        let current = load("http://localhost:5157");
+        let reloaded = load("http://localhost:5158");
+
+        assert_eq!(
+            vec!["module_index_url".to_owned()],
+            current
+                .unreloadable_changes(&reloaded)
+                .expect("configs should serialize")
+        );
+    }
+
+    #[test]
+    fn every_field_is_either_compared_or_reloadable() {
+        let value =
+            serde_json::to_value(load("http://localhost:5157")).expect("config should serialize");
+        let fields = value.as_object().expect("config should be an object");
+
+        for field in fields.keys() {
+            assert!(
+                field == "instance_id"
+                    || RELOADABLE_CONFIG_FIELDS.contains(&field.as_str())
+                    || UNRELOADABLE_CONFIG_FIELDS.contains(&field.as_str()),
+                "config field {field} is neither compared nor reloadable"
+            );
+        }
+    }
+}
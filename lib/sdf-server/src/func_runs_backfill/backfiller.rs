@@ -34,7 +34,7 @@ impl FuncRunsBackfiller {
         task_token: CancellationToken,
     ) -> FuncRunsBackfillResult<Self> {
         let (services_context, layer_db_graceful_shutdown) =
-            init::services_context_from_config(&config, task_token).await?;
+            init::services_context_from_config(&config, task_token.clone(), task_token).await?;
 
         task_tracker.spawn(layer_db_graceful_shutdown.into_future());
 
@@ -58,7 +58,7 @@ impl SnapshotGarbageCollector {
         task_token: CancellationToken,
     ) -> Result<Self> {
         let (services_context, layer_db_graceful_shutdown) =
-            init::services_context_from_config(&config, task_token).await?;
+            init::services_context_from_config(&config, task_token.clone(), task_token).await?;
 
         task_tracker.spawn(layer_db_graceful_shutdown.into_future());
 
@@ -1,6 +1,7 @@
 use std::{
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use dal::{
@@ -12,6 +13,7 @@ use dal::{
     ServicesContext,
     feature_flags::FeatureFlagService,
 };
+use nats_std::prefix_check;
 use rebaser_client::RebaserClient;
 use si_crypto::{
     SymmetricCryptoService,
@@ -87,8 +89,14 @@ impl From<si_data_pg::PgPoolError> for InitError {
 
 type InitResult<T> = std::result::Result<T, InitError>;
 
+/// Builds the [`ServicesContext`] from config.
+///
+/// Blocking jobs stop being waited on once `job_processor_token` is cancelled. For a server, this
+/// must be cancelled when its main loop begins shutting down, since requests waiting on blocking
+/// jobs otherwise hold up the graceful shutdown until the jobs finish.
 pub(crate) async fn services_context_from_config(
     config: &Config,
+    job_processor_token: CancellationToken,
     helping_tasks_token: CancellationToken,
 ) -> InitResult<(ServicesContext, LayerDbGracefulShutdown)> {
     dal::init()?;
@@ -98,8 +106,13 @@ pub(crate) async fn services_context_from_config(
     let jetstream_streams = get_or_create_jetstream_streams(nats.clone()).await?;
     let pg_pool = create_pg_pool(config.pg_pool()).await?;
     let rebaser = create_rebaser_client(nats.clone()).await?;
-    let veritech = create_veritech_client(nats.clone());
-    let job_processor = create_job_processor(nats.clone()).await?;
+    let veritech = create_veritech_client(nats.clone(), config.veritech_circuit_breaker());
+    let job_processor = create_job_processor(
+        nats.clone(),
+        config.blocking_job_timeout(),
+        job_processor_token,
+    )
+    .await?;
     let symmetric_crypto_service =
         create_symmetric_crypto_service(config.symmetric_crypto_service()).await?;
 
@@ -107,7 +120,7 @@ pub(crate) async fn services_context_from_config(
     let module_index_url = Some(config.module_index_url().to_string());
     let feature_flags_service = FeatureFlagService::new(config.boot_feature_flags().clone());
 
-    let compute_executor = create_compute_executor()?;
+    let compute_executor = create_compute_executor(config.compute_executor_max_task_input_size())?;
 
     let (layer_db, layer_db_graceful_shutdown) = initialize_layer_db(
         config.layer_db_config().clone(),
@@ -130,7 +143,8 @@ pub(crate) async fn services_context_from_config(
         layer_db,
         feature_flags_service,
         compute_executor,
-    );
+    )
+    .with_default_key_pair_algorithm(config.default_key_pair_algorithm());
 
     Ok((services_context, layer_db_graceful_shutdown))
 }
@@ -168,6 +182,23 @@ pub(crate) async fn get_or_create_jetstream_streams(
     Ok(streams)
 }
 
+/// Checks, in the background so as not to hold up startup, that the services sdf sends work to
+/// are using the same NATS subject prefix, warning if they do not appear to be.
+pub(crate) fn verify_subject_prefixes(nats: NatsClient) {
+    tokio::spawn(async move {
+        for service in [
+            prefix_check::service::PINGA,
+            prefix_check::service::VERITECH,
+        ] {
+            if let Err(err) =
+                prefix_check::verify(&nats, service, prefix_check::DEFAULT_VERIFY_TIMEOUT).await
+            {
+                warn!(si.error.message = ?err, service, "failed to check subject prefix");
+            }
+        }
+    });
+}
+
 #[instrument(name = "sdf.init.create_pg_pool", level = "info", skip_all)]
 pub(crate) async fn create_pg_pool(pg_pool_config: &PgPoolConfig) -> InitResult<PgPool> {
     let pool = PgPool::new(pg_pool_config).await?;
@@ -182,20 +213,32 @@ async fn create_rebaser_client(nats: NatsClient) -> InitResult<RebaserClient> {
     Ok(client)
 }
 
-pub(crate) fn create_veritech_client(nats: NatsClient) -> veritech_client::Client {
-    veritech_client::Client::new(nats)
+pub(crate) fn create_veritech_client(
+    nats: NatsClient,
+    circuit_breaker: Option<veritech_client::CircuitBreakerConfig>,
+) -> veritech_client::Client {
+    veritech_client::Client::new_with_circuit_breaker(nats, circuit_breaker)
 }
 
 #[instrument(name = "sdf.init.create_compute_executor", level = "info", skip_all)]
-pub(crate) fn create_compute_executor() -> InitResult<DedicatedExecutor> {
-    dal::compute_executor("sdf").map_err(Into::into)
+pub(crate) fn create_compute_executor(
+    max_task_input_size: Option<usize>,
+) -> InitResult<DedicatedExecutor> {
+    Ok(dal::compute_executor("sdf")?.with_max_task_input_size(max_task_input_size))
 }
 
 #[instrument(name = "sdf.init.create_job_processor", level = "info", skip_all)]
 pub(crate) async fn create_job_processor(
     nats: NatsClient,
+    blocking_job_timeout: Duration,
+    shutdown_token: CancellationToken,
 ) -> InitResult<Box<dyn JobQueueProcessor + Send + Sync>> {
-    Ok(Box::new(NatsProcessor::new(nats).await?) as Box<dyn JobQueueProcessor + Send + Sync>)
+    Ok(Box::new(
+        NatsProcessor::new(nats)
+            .await?
+            .with_blocking_job_timeout(blocking_job_timeout)
+            .with_shutdown_token(shutdown_token),
+    ) as Box<dyn JobQueueProcessor + Send + Sync>)
 }
 
 #[instrument(
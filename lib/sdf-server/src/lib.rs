@@ -40,6 +40,7 @@ mod uds;
 
 pub use dal::{
     JobQueueProcessor,
+    KeyPairAlgorithm,
     NatsProcessor,
     ServicesContext,
     feature_flags::{
@@ -67,6 +68,7 @@ pub use self::{
         ConfigFile,
         IncomingStream,
         MigrationMode,
+        RELOADABLE_CONFIG_FIELDS,
         StandardConfig,
         StandardConfigFile,
         WorkspacePermissions,
@@ -87,6 +89,7 @@ pub use self::{
     migrations::Migrator,
     nats_multiplexer::CRDT_MULTIPLEXER_SUBJECT,
     server::{
+        ConfigLoader,
         Server,
         ServerMetadata,
         ServerSocket,
@@ -1,6 +1,29 @@
+mod pg_pool_backpressure;
+mod request_body_limit;
 mod workspace_permission;
+mod workspace_rate_limit;
 
-pub use self::workspace_permission::{
-    WorkspacePermission,
-    WorkspacePermissionLayer,
+pub use self::{
+    pg_pool_backpressure::{
+        PG_POOL_BACKPRESSURE_RETRY_AFTER_SECS,
+        PgPoolBackpressure,
+        PgPoolBackpressureLayer,
+        pg_pool_is_saturated,
+    },
+    request_body_limit::{
+        DEFAULT_MAX_REQUEST_BODY_BYTES,
+        limit_request_bodies,
+    },
+    workspace_permission::{
+        WorkspacePermission,
+        WorkspacePermissionLayer,
+    },
+    workspace_rate_limit::{
+        RATE_LIMIT_WINDOW,
+        RateLimitDecision,
+        WorkspaceRateLimit,
+        WorkspaceRateLimitLayer,
+        WorkspaceRateLimiter,
+        WorkspaceRateLimits,
+    },
 };
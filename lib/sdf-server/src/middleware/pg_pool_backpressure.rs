@@ -0,0 +1,159 @@
+//! Backpressure for when the database connection pool is exhausted, rejecting requests with a
+//! `429 Too Many Requests` and a `Retry-After` header rather than letting them queue unbounded for
+//! a connection.
+
+use std::task::{
+    Context,
+    Poll,
+};
+
+use axum::{
+    body::Body,
+    http::{
+        HeaderValue,
+        Request,
+        StatusCode,
+        header::RETRY_AFTER,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use futures::future::BoxFuture;
+use sdf_core::api_error::ApiError;
+use si_data_pg::{
+    PgPool,
+    PgPoolStatus,
+};
+use telemetry::prelude::*;
+use tower::{
+    Layer,
+    Service,
+};
+
+/// How long clients are asked to wait before retrying a request rejected for backpressure.
+pub const PG_POOL_BACKPRESSURE_RETRY_AFTER_SECS: u64 = 1;
+
+// Health checks are always served, so that a busy node is not mistaken for a dead one
+const EXEMPT_PATH_PREFIX: &str = "/api/health";
+
+/// Returns `true` if the pool has no idle connections and more than `max_waiting` tasks are
+/// already waiting for one.
+pub fn pg_pool_is_saturated(status: &PgPoolStatus, max_waiting: usize) -> bool {
+    status.available == 0 && status.waiting > max_waiting
+}
+
+#[derive(Clone, Debug)]
+pub struct PgPoolBackpressureLayer {
+    pg_pool: PgPool,
+    max_waiting: usize,
+}
+
+impl PgPoolBackpressureLayer {
+    /// Rejects requests while more than `max_waiting` tasks are waiting for a connection.
+    pub fn new(pg_pool: PgPool, max_waiting: usize) -> Self {
+        Self {
+            pg_pool,
+            max_waiting,
+        }
+    }
+}
+
+impl<S> Layer<S> for PgPoolBackpressureLayer {
+    type Service = PgPoolBackpressure<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PgPoolBackpressure {
+            inner,
+            pg_pool: self.pg_pool.clone(),
+            max_waiting: self.max_waiting,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PgPoolBackpressure<S> {
+    inner: S,
+    pg_pool: PgPool,
+    max_waiting: usize,
+}
+
+impl<S> Service<Request<Body>> for PgPoolBackpressure<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let status = self.pg_pool.status();
+        if !req.uri().path().starts_with(EXEMPT_PATH_PREFIX)
+            && pg_pool_is_saturated(&status, self.max_waiting)
+        {
+            warn!(
+                db.pool.max_size = status.max_size,
+                db.pool.waiting = status.waiting,
+                max_waiting = self.max_waiting,
+                "rejecting request while the pg pool is exhausted",
+            );
+            return Box::pin(async { Ok(rejection()) });
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
+
+fn rejection() -> Response {
+    let mut response = ApiError::new(
+        StatusCode::TOO_MANY_REQUESTS,
+        "server is busy, please try again later",
+    )
+    .into_response();
+    response.headers_mut().insert(
+        RETRY_AFTER,
+        HeaderValue::from(PG_POOL_BACKPRESSURE_RETRY_AFTER_SECS),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(available: usize, waiting: usize) -> PgPoolStatus {
+        PgPoolStatus {
+            max_size: 8,
+            size: 8,
+            available,
+            waiting,
+        }
+    }
+
+    #[test]
+    fn saturated_only_when_too_many_are_waiting() {
+        assert!(!pg_pool_is_saturated(&status(2, 0), 4));
+        assert!(!pg_pool_is_saturated(&status(0, 4), 4));
+        assert!(pg_pool_is_saturated(&status(0, 5), 4));
+    }
+
+    #[test]
+    fn rejection_asks_clients_to_retry() {
+        let response = rejection();
+
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+        assert_eq!(
+            Some("1"),
+            response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Limits on the size of request bodies, so that a huge upload or payload cannot exhaust the
+//! process's memory.
+
+use axum::{
+    BoxError,
+    Router,
+    body::{
+        Body,
+        Bytes,
+    },
+    extract::DefaultBodyLimit,
+};
+use hyper::body::HttpBody;
+use tower::ServiceBuilder;
+use tower_http::limit::RequestBodyLimitLayer;
+
+/// The largest request body, in bytes, accepted by routes without their own limit. This matches
+/// the limit axum's extractors apply by default.
+pub const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Limits request bodies to `max_request_body_bytes` for every route added to `router` so far,
+/// however the route reads its body.
+///
+/// Bodies declaring a larger `Content-Length` are rejected with a `413 Payload Too Large` before
+/// reaching the route, and other bodies fail to be read once they grow past the limit.
+///
+/// Routes which take larger bodies, such as package uploads, are given their own limit with this
+/// and added to the app only after its default limit has been applied, so that it does not cover
+/// them.
+pub fn limit_request_bodies<S>(router: Router<S>, max_request_body_bytes: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        // Extractors would otherwise hold bodies to their own default limit as well
+        .layer(DefaultBodyLimit::disable())
+        .layer(
+            ServiceBuilder::new()
+                .layer(RequestBodyLimitLayer::new(max_request_body_bytes))
+                .map_request_body(into_body),
+        )
+}
+
+/// Turns a limited body back into the body type which routes are written against.
+fn into_body<B>(body: B) -> Body
+where
+    B: HttpBody<Data = Bytes> + Send + Unpin + 'static,
+    B::Error: Into<BoxError>,
+{
+    Body::wrap_stream(futures::stream::unfold(body, |mut body| async move {
+        body.data().await.map(|chunk| (chunk, body))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json,
+        http::{
+            Request,
+            StatusCode,
+            header::{
+                CONTENT_LENGTH,
+                CONTENT_TYPE,
+            },
+        },
+        routing::post,
+    };
+    use tower::ServiceExt as _;
+
+    use super::*;
+
+    const MAX_REQUEST_BODY_BYTES: usize = 1024;
+
+    async fn echo(Json(value): Json<serde_json::Value>) -> Json<serde_json::Value> {
+        Json(value)
+    }
+
+    // Reads the raw body, which no extractor limit applies to
+    async fn count(body: Body) -> Result<String, StatusCode> {
+        hyper::body::to_bytes(body)
+            .await
+            .map(|bytes| bytes.len().to_string())
+            .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)
+    }
+
+    fn json_request(uri: &str, body_len: usize) -> Request<Body> {
+        let body = serde_json::json!({ "data": "x".repeat(body_len) }).to_string();
+        Request::post(uri)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .expect("failed to build request")
+    }
+
+    // A body which does not declare its length up front, so it can only be limited as it is read
+    fn streamed_request(uri: &str, body_len: usize) -> Request<Body> {
+        let chunks = (0..2).map(move |_| Ok::<_, BoxError>(Bytes::from(vec![b'x'; body_len / 2])));
+        Request::post(uri)
+            .body(Body::wrap_stream(futures::stream::iter(chunks)))
+            .expect("failed to build request")
+    }
+
+    fn app() -> Router {
+        let app = Router::new()
+            .route("/echo", post(echo))
+            .route("/count", post(count));
+        let upload = Router::new().route("/upload", post(echo));
+
+        limit_request_bodies(app, MAX_REQUEST_BODY_BYTES)
+            .merge(limit_request_bodies(upload, 4 * MAX_REQUEST_BODY_BYTES))
+    }
+
+    #[tokio::test]
+    async fn bodies_within_the_limit_are_accepted() {
+        let response = app()
+            .oneshot(json_request("/echo", MAX_REQUEST_BODY_BYTES / 2))
+            .await
+            .expect("failed to call app");
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    #[tokio::test]
+    async fn oversized_json_bodies_are_rejected() {
+        let response = app()
+            .oneshot(json_request("/echo", MAX_REQUEST_BODY_BYTES))
+            .await
+            .expect("failed to call app");
+
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
+
+    #[tokio::test]
+    async fn oversized_raw_bodies_are_rejected() {
+        let response = app()
+            .oneshot(streamed_request("/count", MAX_REQUEST_BODY_BYTES / 2))
+            .await
+            .expect("failed to call app");
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app()
+            .oneshot(streamed_request("/count", 2 * MAX_REQUEST_BODY_BYTES))
+            .await
+            .expect("failed to call app");
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
+
+    #[tokio::test]
+    async fn route_limits_take_precedence() {
+        let response = app()
+            .oneshot(json_request("/upload", 2 * MAX_REQUEST_BODY_BYTES))
+            .await
+            .expect("failed to call app");
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
@@ -0,0 +1,314 @@
+//! Optional per-workspace rate limiting for expensive endpoints, reporting the limit to clients
+//! with `X-RateLimit-Limit` and, once it has been reached, `Retry-After` headers.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError,
+    },
+    task::{
+        Context,
+        Poll,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use axum::{
+    RequestPartsExt as _,
+    body::Body,
+    http::{
+        HeaderMap,
+        HeaderName,
+        HeaderValue,
+        Request,
+        StatusCode,
+        header::RETRY_AFTER,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use dal::WorkspacePk;
+use futures::future::BoxFuture;
+use sdf_core::api_error::ApiError;
+use tower::{
+    Layer,
+    Service,
+};
+
+use crate::{
+    AppState,
+    extract::workspace::WorkspaceAuthorization,
+};
+
+/// The window over which rate limits are counted.
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// The per-workspace limits applied to expensive endpoints, counted per [`RATE_LIMIT_WINDOW`].
+/// Endpoints without a limit are not rate limited, which is the default.
+///
+/// Counts are held in memory by each sdf replica, so a workspace whose requests are spread across
+/// `n` replicas may make up to `n` times these limits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkspaceRateLimits {
+    /// The number of change set applies a workspace may make, if limited.
+    pub apply: Option<u32>,
+    /// The number of func executions a workspace may request, if limited.
+    pub execution: Option<u32>,
+}
+
+impl WorkspaceRateLimits {
+    /// Returns a limiter for change set applies, if they are limited.
+    pub fn apply_limiter(&self) -> Option<WorkspaceRateLimiter> {
+        self.apply
+            .map(|limit| WorkspaceRateLimiter::new(limit, RATE_LIMIT_WINDOW))
+    }
+
+    /// Returns a limiter for func executions, if they are limited.
+    pub fn execution_limiter(&self) -> Option<WorkspaceRateLimiter> {
+        self.execution
+            .map(|limit| WorkspaceRateLimiter::new(limit, RATE_LIMIT_WINDOW))
+    }
+}
+
+const X_RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
+
+/// Counts requests per workspace over a fixed window, allowing up to `limit` in each window.
+///
+/// Clones share their counts, so a single limiter can be layered onto several routes to give them
+/// a shared budget.
+#[derive(Clone, Debug)]
+pub struct WorkspaceRateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Arc<Mutex<HashMap<WorkspacePk, RateLimitWindow>>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RateLimitWindow {
+    started_at: Instant,
+    count: u32,
+}
+
+/// The outcome of counting a single request against a [`WorkspaceRateLimiter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub limit: u32,
+    pub remaining: u32,
+    /// How long until the request may be retried, if it was rejected.
+    pub retry_after: Option<Duration>,
+}
+
+impl WorkspaceRateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Default::default(),
+        }
+    }
+
+    /// Counts a request from the workspace, deciding whether it may proceed.
+    pub fn check(&self, workspace_id: WorkspacePk) -> RateLimitDecision {
+        self.check_at(workspace_id, Instant::now())
+    }
+
+    fn check_at(&self, workspace_id: WorkspacePk, now: Instant) -> RateLimitDecision {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !windows.contains_key(&workspace_id) {
+            // Forget workspaces whose windows have lapsed, so the map does not grow unbounded
+            windows.retain(|_, window| now.duration_since(window.started_at) < self.window);
+        }
+        let window = windows.entry(workspace_id).or_insert(RateLimitWindow {
+            started_at: now,
+            count: 0,
+        });
+        if now.duration_since(window.started_at) >= self.window {
+            *window = RateLimitWindow {
+                started_at: now,
+                count: 0,
+            };
+        }
+
+        if window.count >= self.limit {
+            return RateLimitDecision {
+                limit: self.limit,
+                remaining: 0,
+                retry_after: Some(self.window - now.duration_since(window.started_at)),
+            };
+        }
+
+        window.count += 1;
+        RateLimitDecision {
+            limit: self.limit,
+            remaining: self.limit - window.count,
+            retry_after: None,
+        }
+    }
+}
+
+impl RateLimitDecision {
+    pub fn is_allowed(&self) -> bool {
+        self.retry_after.is_none()
+    }
+
+    /// Adds the rate limit headers describing this decision to a response.
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(
+            HeaderName::from_static(X_RATELIMIT_LIMIT),
+            HeaderValue::from(self.limit),
+        );
+        if let Some(retry_after) = self.retry_after {
+            // Round up, so clients never retry before the window has reset
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            headers.insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+    }
+
+    /// Builds the response for a request which was rejected by this decision.
+    pub fn rejection(&self) -> Response {
+        let mut response = ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate limit exceeded for workspace, please try again later",
+        )
+        .into_response();
+        self.apply_headers(response.headers_mut());
+        response
+    }
+}
+
+/// Counts requests against a [`WorkspaceRateLimiter`], or passes them straight through if there is
+/// none.
+#[derive(Clone)]
+pub struct WorkspaceRateLimitLayer {
+    state: AppState,
+    limiter: Option<WorkspaceRateLimiter>,
+}
+
+impl WorkspaceRateLimitLayer {
+    pub fn new(state: AppState, limiter: Option<WorkspaceRateLimiter>) -> Self {
+        Self { state, limiter }
+    }
+}
+
+impl<S> Layer<S> for WorkspaceRateLimitLayer {
+    type Service = WorkspaceRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        WorkspaceRateLimit {
+            inner,
+            state: self.state.clone(),
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WorkspaceRateLimit<S> {
+    inner: S,
+    state: AppState,
+    limiter: Option<WorkspaceRateLimiter>,
+}
+
+impl<S> Service<Request<Body>> for WorkspaceRateLimit<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut me = self.clone();
+
+        Box::pin(async move {
+            let Some(limiter) = &me.limiter else {
+                return me.inner.call(req).await;
+            };
+            let (mut parts, body) = req.into_parts();
+
+            // Only count requests which are authorized for the workspace, so one workspace can
+            // not use up another's budget. The authorization is stashed in the request, so the
+            // handler does not authorize it again.
+            let workspace_id = match parts.extract_with_state(&me.state).await {
+                Ok(WorkspaceAuthorization { workspace_id, .. }) => workspace_id,
+                Err(err) => return Ok(err.into_response()),
+            };
+
+            let decision = limiter.check(workspace_id);
+            if !decision.is_allowed() {
+                return Ok(decision.rejection());
+            }
+
+            let req = Request::from_parts(parts, body);
+
+            let mut response = me.inner.call(req).await?;
+            decision.apply_headers(response.headers_mut());
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(response: &Response, name: &str) -> Option<String> {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().expect("header is ascii").to_owned())
+    }
+
+    #[test]
+    fn counts_requests_per_workspace() {
+        let limiter = WorkspaceRateLimiter::new(2, Duration::from_secs(60));
+        let workspace_id = WorkspacePk::new();
+        let other_workspace_id = WorkspacePk::new();
+        let now = Instant::now();
+
+        assert_eq!(1, limiter.check_at(workspace_id, now).remaining);
+        assert_eq!(0, limiter.check_at(workspace_id, now).remaining);
+        assert!(!limiter.check_at(workspace_id, now).is_allowed());
+
+        // Other workspaces have their own budget
+        assert_eq!(1, limiter.check_at(other_workspace_id, now).remaining);
+
+        // The budget is restored once the window has passed
+        let later = now + Duration::from_secs(60);
+        assert_eq!(1, limiter.check_at(workspace_id, later).remaining);
+    }
+
+    #[test]
+    fn limited_request_reports_headers() {
+        let limiter = WorkspaceRateLimiter::new(1, Duration::from_secs(60));
+        let workspace_id = WorkspacePk::new();
+        let now = Instant::now();
+
+        let mut allowed = StatusCode::OK.into_response();
+        limiter
+            .check_at(workspace_id, now)
+            .apply_headers(allowed.headers_mut());
+        assert_eq!(Some("1".to_string()), header(&allowed, "x-ratelimit-limit"));
+        assert_eq!(None, header(&allowed, "retry-after"));
+
+        let limited = limiter
+            .check_at(workspace_id, now + Duration::from_millis(20_500))
+            .rejection();
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, limited.status());
+        assert_eq!(Some("1".to_string()), header(&limited, "x-ratelimit-limit"));
+        assert_eq!(Some("40".to_string()), header(&limited, "retry-after"));
+    }
+}
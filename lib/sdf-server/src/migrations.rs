@@ -8,7 +8,11 @@ use audit_database::{
 use dal::{
     DalContext,
     ServicesContext,
-    cached_module::CachedModule,
+    cached_module::{
+        CachedModule,
+        DEFAULT_FETCH_CONCURRENCY_LIMIT,
+        DEFAULT_FETCH_MAX_ATTEMPTS,
+    },
     slow_rt::SlowRuntimeError,
     workspace_snapshot::migrator::SnapshotGraphMigrator,
 };
@@ -29,6 +33,9 @@ use crate::{
     init,
 };
 
+/// How many modules are processed between module cache progress logs.
+const MODULE_CACHE_PROGRESS_INTERVAL: usize = 25;
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum MigratorError {
@@ -78,6 +85,8 @@ type MigratorResult<T> = std::result::Result<T, MigratorError>;
 pub struct Migrator {
     services_context: ServicesContext,
     audit_database_context: AuditDatabaseContext,
+    snapshot_migration_concurrency_limit: usize,
+    module_fetch_max_attempts: u32,
 }
 
 impl Migrator {
@@ -87,18 +96,25 @@ impl Migrator {
         helping_tasks_tracker: &TaskTracker,
         helping_tasks_token: CancellationToken,
     ) -> MigratorResult<Self> {
-        let (services_context, layer_db_graceful_shutdown) =
-            init::services_context_from_config(&config, helping_tasks_token).await?;
+        let (services_context, layer_db_graceful_shutdown) = init::services_context_from_config(
+            &config,
+            helping_tasks_token.clone(),
+            helping_tasks_token,
+        )
+        .await?;
 
         // Spawn helping tasks and track them for graceful shutdown
         helping_tasks_tracker.spawn(layer_db_graceful_shutdown.into_future());
 
         let audit_database_context = AuditDatabaseContext::from_config(config.audit()).await?;
 
-        Ok(Self::from_services(
-            services_context,
-            audit_database_context,
-        ))
+        Ok(
+            Self::from_services(services_context, audit_database_context)
+                .with_snapshot_migration_concurrency_limit(
+                    config.snapshot_migration_concurrency_limit(),
+                )
+                .with_module_fetch_max_attempts(config.module_fetch_max_attempts()),
+        )
     }
 
     #[instrument(name = "sdf.migrator.init.from_services", level = "info", skip_all)]
@@ -109,9 +125,25 @@ impl Migrator {
         Self {
             services_context,
             audit_database_context,
+            snapshot_migration_concurrency_limit:
+                dal::workspace_snapshot::migrator::DEFAULT_CONCURRENCY_LIMIT,
+            module_fetch_max_attempts: DEFAULT_FETCH_MAX_ATTEMPTS,
         }
     }
 
+    /// Sets the maximum number of workspace snapshots migrated at the same time.
+    pub fn with_snapshot_migration_concurrency_limit(mut self, limit: usize) -> Self {
+        self.snapshot_migration_concurrency_limit = limit;
+        self
+    }
+
+    /// Sets the number of attempts made to fetch each builtin module when updating the module
+    /// cache.
+    pub fn with_module_fetch_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.module_fetch_max_attempts = max_attempts;
+        self
+    }
+
     #[instrument(
         name = "sdf.migrator.run_migrations",
         level = "info",
@@ -194,7 +226,8 @@ impl Migrator {
             .await
             .map_err(MigratorError::migrate_snapshots)?;
 
-        let mut migrator = SnapshotGraphMigrator::new();
+        let mut migrator = SnapshotGraphMigrator::new()
+            .with_concurrency_limit(self.snapshot_migration_concurrency_limit);
         migrator
             .migrate_all(&ctx)
             .await
@@ -210,10 +243,21 @@ impl Migrator {
         async fn update_cached_modules(
             ctx: DalContext,
             edda_client: EddaClient,
+            max_fetch_attempts: u32,
         ) -> MigratorResult<()> {
-            let new_modules = CachedModule::update_cached_modules(&ctx, edda_client)
-                .await
-                .map_err(MigratorError::migrate_cached_modules)?;
+            let new_modules = CachedModule::update_cached_modules(
+                &ctx,
+                edda_client,
+                DEFAULT_FETCH_CONCURRENCY_LIMIT,
+                max_fetch_attempts,
+                |processed, total| {
+                    if processed % MODULE_CACHE_PROGRESS_INTERVAL == 0 || processed == total {
+                        info!("migrating module cache: {processed}/{total} modules processed");
+                    }
+                },
+            )
+            .await
+            .map_err(MigratorError::migrate_cached_modules)?;
             info!(
                 "{} new builtin assets found in module index",
                 new_modules.len()
@@ -229,8 +273,9 @@ impl Migrator {
 
         info!("Updating local module cache");
 
+        let max_fetch_attempts = self.module_fetch_max_attempts;
         tokio::spawn(async move {
-            match update_cached_modules(ctx, edda_client).await {
+            match update_cached_modules(ctx, edda_client, max_fetch_attempts).await {
                 Ok(()) => {
                     info!("Module cache updated successfully");
                 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use axum::{
     Router,
     extract::State,
@@ -25,6 +27,14 @@ use serde_json::{
     Value,
     json,
 };
+use si_layer_cache::db::health::{
+    self,
+    ComponentHealth,
+    HEALTH_PROBE_TIMEOUT,
+    LayerDbHealth,
+};
+use telemetry::prelude::*;
+use tokio::sync::RwLock;
 use tower_http::{
     compression::CompressionLayer,
     cors::{
@@ -33,9 +43,15 @@ use tower_http::{
     },
 };
 
-use crate::app_state::{
-    AppState,
-    ApplicationRuntimeMode,
+use crate::{
+    app_state::{
+        AppState,
+        ApplicationRuntimeMode,
+    },
+    middleware::{
+        WorkspaceRateLimits,
+        limit_request_bodies,
+    },
 };
 
 const MAINTENANCE_MODE_MESSAGE: &str = concat!(
@@ -45,26 +61,48 @@ const MAINTENANCE_MODE_MESSAGE: &str = concat!(
     "or in the SI Discord for more information if this problem persists",
 );
 
+/// How long clients are asked to wait before retrying while in maintenance mode.
+const MAINTENANCE_MODE_RETRY_AFTER_SECS: u64 = 60;
+
 async fn app_state_middeware<B>(
-    State(state): State<AppState>,
+    State(application_runtime_mode): State<Arc<RwLock<ApplicationRuntimeMode>>>,
     request: Request<B>,
     next: Next<B>,
 ) -> Response {
-    match *state.application_runtime_mode.read().await {
+    match *application_runtime_mode.read().await {
         ApplicationRuntimeMode::Maintenance => {
             // Return a 503 when the server is in maintenance/offline
-            (StatusCode::SERVICE_UNAVAILABLE, MAINTENANCE_MODE_MESSAGE).into_response()
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(
+                    header::RETRY_AFTER,
+                    MAINTENANCE_MODE_RETRY_AFTER_SECS.to_string(),
+                )],
+                MAINTENANCE_MODE_MESSAGE,
+            )
+                .into_response()
         }
         ApplicationRuntimeMode::Running => next.run(request).await,
     }
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn routes(state: AppState) -> Router {
-    Router::new()
+pub fn routes(
+    state: AppState,
+    workspace_rate_limits: WorkspaceRateLimits,
+    max_request_body_bytes: usize,
+) -> Router {
+    let app = Router::new()
         .nest("/api", v1_routes())
-        .nest("/api/v2", crate::service::v2::routes(state.clone()))
-        .nest("/api/whoami", crate::service::whoami::routes())
+        .nest(
+            "/api/v2",
+            crate::service::v2::routes(state.clone(), workspace_rate_limits),
+        )
+        .nest("/api/whoami", crate::service::whoami::routes());
+
+    limit_request_bodies(app, max_request_body_bytes)
+        // uploads are added after the default body limit, as they apply their own larger one
+        .nest("/api/v2", crate::service::v2::upload_routes(state.clone()))
         .layer(CompressionLayer::new())
         // allows us to be permissive about cors from our owned subdomains
         .layer(
@@ -102,6 +140,20 @@ pub fn routes(state: AppState) -> Router {
             "/api/",
             Router::new().route("/", get(system_status_route).layer(CorsLayer::permissive())),
         )
+        // switching the runtime mode must be serviced during maintenance mode to leave it
+        .nest(
+            "/api/admin",
+            crate::service::v2::admin::runtime_mode_routes(state.clone()),
+        )
+        // liveness of the process and readiness of our backing services, also serviced during
+        // maintenance mode
+        .nest(
+            "/api/health",
+            Router::new()
+                .route("/live", get(system_status_route))
+                .route("/ready", get(readiness_route))
+                .route("/layer_db", get(layer_db_health_route)),
+        )
         // Load dev routes if we are in dev mode (decided by "opt-level" at the moment).
         .nest("/api/dev", dev_routes())
         // Consider turning app state into an Arc so that all of the middleware
@@ -122,6 +174,64 @@ async fn system_status_route() -> Json<Value> {
     Json(json!({ "ok": true }))
 }
 
+async fn layer_db_health_route(State(state): State<AppState>) -> Response {
+    let health = state.services_context().layer_db().health().await;
+    if health.is_healthy() {
+        return Json(json!({ "ok": true })).into_response();
+    }
+
+    // Anyone can reach this route, so what failed and why is only logged
+    warn!(layer_db.health = ?health, "layer db is unhealthy");
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "ok": false })),
+    )
+        .into_response()
+}
+
+/// The health of each service sdf needs to reach in order to serve requests.
+#[derive(Debug)]
+struct ReadinessReport {
+    postgres: ComponentHealth,
+    nats: ComponentHealth,
+    layer_db: LayerDbHealth,
+}
+
+impl ReadinessReport {
+    fn is_ready(&self) -> bool {
+        self.postgres.is_healthy() && self.nats.is_healthy() && self.layer_db.is_healthy()
+    }
+}
+
+async fn readiness_route(State(state): State<AppState>) -> Response {
+    let services_context = state.services_context();
+    let (postgres, nats, layer_db) = tokio::join!(
+        health::probe(
+            HEALTH_PROBE_TIMEOUT,
+            services_context.pg_pool().test_connection()
+        ),
+        health::probe(HEALTH_PROBE_TIMEOUT, services_context.nats_conn().flush()),
+        services_context.layer_db().health(),
+    );
+
+    let report = ReadinessReport {
+        postgres,
+        nats,
+        layer_db,
+    };
+    if report.is_ready() {
+        return Json(json!({ "ok": true })).into_response();
+    }
+
+    // Anyone can reach this route, so what failed and why is only logged
+    warn!(readiness = ?report, "sdf is not ready");
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "ok": false })),
+    )
+        .into_response()
+}
+
 #[cfg(debug_assertions)]
 pub fn dev_routes() -> Router<AppState> {
     crate::service::dev::routes()
@@ -132,3 +242,27 @@ pub fn dev_routes() -> Router<AppState> {
     telemetry::prelude::debug!("skipping dev routes...");
     Router::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readiness_requires_every_service() {
+        let healthy = ComponentHealth::Healthy { latency_ms: 1 };
+        let unhealthy = ComponentHealth::Unhealthy {
+            error: "connection refused".to_string(),
+        };
+        let report = |layer_db_postgres: ComponentHealth| ReadinessReport {
+            postgres: healthy.clone(),
+            nats: healthy.clone(),
+            layer_db: LayerDbHealth {
+                postgres: layer_db_postgres,
+                nats: healthy.clone(),
+            },
+        };
+
+        assert!(report(healthy.clone()).is_ready());
+        assert!(!report(unhealthy).is_ready());
+    }
+}
@@ -4,24 +4,31 @@ use std::{
     net::SocketAddr,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use audit_database::AuditDatabaseContext;
 use axum::{
+    Extension,
     Router,
     async_trait,
     routing::IntoMakeService,
 };
-use dal::ServicesContext;
+use dal::{
+    ServicesContext,
+    feature_flags::FeatureFlagService,
+};
 use edda_client::EddaClient;
 use frigg::{
     FriggStore,
     frigg_kv,
 };
+use futures::future::BoxFuture;
 use hyper::server::accept::Accept;
 use nats_multiplexer::Multiplexer;
 use nats_multiplexer_client::MultiplexerClient;
 use sdf_core::nats_multiplexer::EddaUpdatesMultiplexerClient;
+use sdf_v1_routes_ws::WsIdleTimeout;
 use si_data_nats::jetstream;
 use si_data_spicedb::SpiceDbClient;
 use si_jwt_public_key::JwtPublicSigningKeyChain;
@@ -44,13 +51,19 @@ use crate::{
     ApplicationRuntimeMode,
     AxumApp,
     Config,
+    ConfigError,
     IncomingStream,
     Migrator,
+    RELOADABLE_CONFIG_FIELDS,
     ServerError,
     ServerResult,
     WorkspacePermissions,
     WorkspacePermissionsMode,
     init,
+    middleware::{
+        PgPoolBackpressureLayer,
+        WorkspaceRateLimits,
+    },
     nats_multiplexer::{
         CRDT_MULTIPLEXER_SUBJECT,
         WS_MULTIPLEXER_SUBJECT,
@@ -72,12 +85,18 @@ impl ServerMetadata {
     }
 }
 
+/// Loads a fresh [`Config`] when the server is asked to reload its config.
+pub type ConfigLoader = Box<dyn Fn() -> BoxFuture<'static, Result<Config, ConfigError>> + Send>;
+
 pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Runnable + Send>,
     // Only used to build a [`Migrator`] for migrations
     migrator_toolkit: MigratorToolkit,
     socket: ServerSocket,
+    // The state which is updated when the config is reloaded
+    feature_flags_service: FeatureFlagService,
+    create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
 }
 
 struct MigratorToolkit {
@@ -113,8 +132,13 @@ impl Server {
         helping_tasks_tracker: &TaskTracker,
         helping_tasks_token: CancellationToken,
     ) -> ServerResult<Self> {
-        let (services_context, layer_db_graceful_shutdown) =
-            init::services_context_from_config(&config, helping_tasks_token.clone()).await?;
+        let (services_context, layer_db_graceful_shutdown) = init::services_context_from_config(
+            &config,
+            token.child_token(),
+            helping_tasks_token.clone(),
+        )
+        .await?;
+        init::verify_subject_prefixes(services_context.nats_conn().clone());
 
         let jwt_public_signing_key = init::load_jwt_public_signing_key(
             config.jwt_signing_public_key().clone(),
@@ -199,6 +223,10 @@ impl Server {
             frigg,
             audit_database_context,
             edda_client,
+            config.ws_idle_timeout(),
+            config.max_request_body_bytes(),
+            config.pg_pool_max_waiting(),
+            config.workspace_rate_limits(),
         )
         .await
     }
@@ -223,8 +251,15 @@ impl Server {
         frigg: FriggStore,
         audit_database_context: AuditDatabaseContext,
         edda_client: EddaClient,
+        ws_idle_timeout: Option<Duration>,
+        max_request_body_bytes: usize,
+        pg_pool_max_waiting: Option<usize>,
+        workspace_rate_limits: WorkspaceRateLimits,
     ) -> ServerResult<Self> {
-        let app = AxumApp::from_services(
+        let feature_flags_service = services_context.feature_flags_service().clone();
+        let create_workspace_allowlist = Arc::new(RwLock::new(create_workspace_allowlist));
+
+        let mut app = AxumApp::from_services(
             services_context.clone(),
             jwt_public_signing_key_chain,
             posthog_client,
@@ -233,15 +268,26 @@ impl Server {
             crdt_multiplexer_client,
             edda_updates_multiplexer_client,
             create_workspace_permissions,
-            create_workspace_allowlist,
+            create_workspace_allowlist.clone(),
             application_runtime_mode,
             token.clone(),
             spicedb_client,
             frigg,
             audit_database_context.clone(),
             edda_client,
+            workspace_rate_limits,
+            max_request_body_bytes,
         )
         .into_inner();
+        if let Some(ws_idle_timeout) = ws_idle_timeout {
+            app = app.layer(Extension(WsIdleTimeout(ws_idle_timeout)));
+        }
+        if let Some(max_waiting) = pg_pool_max_waiting {
+            app = app.layer(PgPoolBackpressureLayer::new(
+                services_context.pg_pool().clone(),
+                max_waiting,
+            ));
+        }
 
         let (inner, socket): (Box<dyn Runnable + Send>, _) = match incoming_stream {
             IncomingStream::TcpSocket(socket_addr) => {
@@ -282,6 +328,8 @@ impl Server {
                 audit_database_context,
             },
             socket,
+            feature_flags_service,
+            create_workspace_allowlist,
         })
     }
 
@@ -292,6 +340,26 @@ impl Server {
         }
     }
 
+    /// Reloads the config with `loader` each time the server receives `SIGHUP`, starting from the
+    /// `config` the server was built with.
+    ///
+    /// Only the fields in [`RELOADABLE_CONFIG_FIELDS`] are applied. A reload which changes any
+    /// other field is rejected as a whole and the running config is kept.
+    pub fn reload_config_on_sighup(
+        &self,
+        config: Config,
+        loader: ConfigLoader,
+        token: CancellationToken,
+    ) -> ServerResult<()> {
+        prepare_config_reload_watcher(
+            config,
+            loader,
+            self.feature_flags_service.clone(),
+            self.create_workspace_allowlist.clone(),
+            token,
+        )
+    }
+
     /// Builds and returns a [`Migrator`] for running migrations.
     pub fn migrator(&self) -> Migrator {
         Migrator::from_services(
@@ -366,3 +434,81 @@ fn prepare_maintenance_mode_watcher(
 
     Ok(())
 }
+
+fn prepare_config_reload_watcher(
+    mut config: Config,
+    loader: ConfigLoader,
+    feature_flags_service: FeatureFlagService,
+    create_workspace_allowlist: Arc<RwLock<Vec<WorkspacePermissions>>>,
+    cancellation_token: CancellationToken,
+) -> ServerResult<()> {
+    let mut sighup_watcher =
+        signal::unix::signal(signal::unix::SignalKind::hangup()).map_err(ServerError::Signal)?;
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = sighup_watcher.recv() => {
+                    info!("received SIGHUP signal, reloading config");
+                    let reloaded = match loader().await {
+                        Ok(reloaded) => reloaded,
+                        Err(err) => {
+                            error!(
+                                si.error.message = ?err,
+                                "failed to load config, keeping the running config",
+                            );
+                            continue;
+                        }
+                    };
+                    match config.unreloadable_changes(&reloaded) {
+                        Ok(changed) if changed.is_empty() => {}
+                        Ok(changed) => {
+                            warn!(
+                                ?changed,
+                                reloadable = ?RELOADABLE_CONFIG_FIELDS,
+                                "rejecting config reload which changes fields that need a restart",
+                            );
+                            continue;
+                        }
+                        Err(err) => {
+                            error!(
+                                si.error.message = ?err,
+                                "failed to compare reloaded config, keeping the running config",
+                            );
+                            continue;
+                        }
+                    }
+
+                    let before = feature_flags_service
+                        .replace_feature_flags(reloaded.boot_feature_flags().clone());
+                    info!(
+                        ?before,
+                        after = ?reloaded.boot_feature_flags(),
+                        "reloaded feature flags",
+                    );
+
+                    let mut allowlist = create_workspace_allowlist.write().await;
+                    info!(
+                        before = ?*allowlist,
+                        after = ?reloaded.create_workspace_allowlist(),
+                        "reloaded create workspace allowlist",
+                    );
+                    *allowlist = reloaded.create_workspace_allowlist().clone();
+                    drop(allowlist);
+
+                    config = reloaded;
+                }
+                _ = cancellation_token.cancelled() => {
+                    break
+                }
+                else => {
+                    // All other arms are closed, nothing left to do but return
+                    trace!("returning from config reload watcher with all select arms closed");
+                    break
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
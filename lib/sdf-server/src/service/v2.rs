@@ -17,6 +17,7 @@ use crate::{
             WorkspaceAuthorization,
         },
     },
+    middleware::WorkspaceRateLimits,
 };
 
 pub mod action;
@@ -35,23 +36,43 @@ pub mod variant;
 pub mod view;
 pub mod workspace;
 
-pub fn routes(state: AppState) -> Router<AppState> {
-    Router::new()
-        .nest("/admin", admin::v2_routes(state.clone()))
-        .nest("/workspaces/:workspace_id", workspace_routes(state))
+pub fn routes(state: AppState, rate_limits: WorkspaceRateLimits) -> Router<AppState> {
+    Router::new().nest(
+        "/workspaces/:workspace_id",
+        workspace_routes(state, rate_limits),
+    )
+}
+
+/// Routes which take larger request bodies than the default limit allows, such as package and
+/// snapshot uploads. Each applies its own limit instead.
+pub fn upload_routes(state: AppState) -> Router<AppState> {
+    Router::new().nest("/admin", admin::v2_routes(state)).nest(
+        "/workspaces/:workspace_id/change-sets/:change_set_id/modules",
+        module::v2_routes()
+            .route_layer(middleware::from_extractor::<TargetChangeSetIdentFromPath>())
+            .route_layer(middleware::from_extractor::<TargetWorkspaceIdFromPath>()),
+    )
 }
 
-fn workspace_routes(state: AppState) -> Router<AppState> {
+fn workspace_routes(state: AppState, rate_limits: WorkspaceRateLimits) -> Router<AppState> {
+    // Every route which applies a change set shares one budget per workspace
+    let apply_limiter = rate_limits.apply_limiter();
+
     Router::new()
         .nest("/", workspace::v2_routes())
-        .nest("/change-sets", change_set::change_sets_routes())
+        .nest(
+            "/change-sets",
+            change_set::change_sets_routes(state.clone(), apply_limiter.clone()),
+        )
         .nest(
             "/change-sets/:change_set_id",
-            change_set::change_set_routes(state.clone())
+            change_set::change_set_routes(state.clone(), apply_limiter)
                 .nest("/audit-logs", audit_log::v2_routes())
                 .nest("/components", component::v2_routes())
-                .nest("/funcs", func::v2_routes())
-                .nest("/modules", module::v2_routes())
+                .nest(
+                    "/funcs",
+                    func::v2_routes(state, rate_limits.execution_limiter()),
+                )
                 .nest("/schema-variants", variant::v2_routes())
                 .nest("/management", management::v2_routes())
                 .nest("/views", view::v2_routes())
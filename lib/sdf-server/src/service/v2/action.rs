@@ -37,6 +37,11 @@ use dal::{
             ActionPrototypeError,
         },
     },
+    feature_flags::FeatureFlag,
+    job::{
+        consumer::JobConsumerError,
+        definition::ActionJob,
+    },
     slow_rt::SlowRuntimeError,
 };
 use sdf_core::{
@@ -46,6 +51,7 @@ use sdf_core::{
 use sdf_extract::{
     PosthogEventTracker,
     change_set::ChangeSetDalContext,
+    workspace::WorkspaceFeatureFlags,
 };
 use serde::{
     Deserialize,
@@ -62,6 +68,7 @@ use si_id::{
 };
 use si_layer_cache::LayerDbError;
 use thiserror::Error;
+use veritech_client::ActionRunResultSuccess;
 
 use crate::{
     app_state::AppState,
@@ -98,12 +105,18 @@ pub enum ActionRequestError {
     InvalidUser(UserPk),
     #[error("invalid user system init")]
     InvalidUserSystemInit,
+    #[error("job consumer error: {0}")]
+    JobConsumer(#[from] JobConsumerError),
     #[error("layer db error: {0}")]
     LayerDb(#[from] LayerDbError),
     #[error("no schema found for component {0}")]
     NoSchemaForComponent(ComponentId),
     #[error("no schema variant found for component {0}")]
     NoSchemaVariantForComponent(ComponentId),
+    #[error("running actions immediately is not enabled")]
+    RunImmediatelyNotEnabled,
+    #[error("actions can only be run immediately on HEAD, not in change set {0}")]
+    RunImmediatelyOutsideHead(ChangeSetId),
     #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
     #[error("schema variant error: {0}")]
@@ -132,9 +145,17 @@ impl IntoResponse for ActionRequestError {
                 (StatusCode::GONE, err.to_string())
             }
             ActionRequestError::ActionAlreadyEnqueued(_)
-            | ActionRequestError::Action(dal::action::ActionError::ActionAlreadyEnqueued(_)) => {
+            | ActionRequestError::Action(dal::action::ActionError::ActionAlreadyEnqueued(_))
+            | ActionRequestError::JobConsumer(JobConsumerError::ActionAlreadyInFlight(_)) => {
                 (StatusCode::CONFLICT, self.to_string())
             }
+            ActionRequestError::RunImmediatelyNotEnabled => {
+                (StatusCode::FORBIDDEN, self.to_string())
+            }
+            ActionRequestError::RunImmediatelyOutsideHead(_)
+            | ActionRequestError::JobConsumer(JobConsumerError::RunImmediatelyOutsideHead(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -149,6 +170,7 @@ pub fn v2_routes() -> Router<AppState> {
         .route("/:action_id/cancel", put(cancel))
         .route("/:action_id/put_on_hold", put(hold))
         .route("/:action_id/retry", put(retry))
+        .route("/:action_id/run_immediately", post(run_immediately))
         .route("/:action_id/func_run_id", get(get_func_run_id))
         .route("/:action_id/queued_details", get(queued_details))
 }
@@ -344,6 +366,55 @@ pub async fn retry(
     Ok(())
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunImmediatelyResponse {
+    pub func_run_id: FuncRunId,
+    pub result: Option<ActionRunResultSuccess>,
+}
+
+/// Runs the action right away, bypassing the action queue, and returns its result inline. Only
+/// available when the corresponding feature flag is enabled for the workspace since it skips queue
+/// ordering, and only on HEAD.
+pub async fn run_immediately(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    feature_flags: WorkspaceFeatureFlags,
+    tracker: PosthogEventTracker,
+    Path((_workspace_pk, change_set_id, action_id)): Path<(WorkspacePk, ChangeSetId, ActionId)>,
+) -> ActionResult<Json<RunImmediatelyResponse>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    if !feature_flags.is_enabled(&FeatureFlag::RunActionsImmediately) {
+        return Err(ActionRequestError::RunImmediatelyNotEnabled);
+    }
+    if !ctx.is_head().await? {
+        return Err(ActionRequestError::RunImmediatelyOutsideHead(change_set_id));
+    }
+
+    let (result, func_run_id) = ActionJob::run_immediately(&mut ctx, action_id).await?;
+
+    // The run's changes must be visible to the next request by the time the result is returned
+    ctx.blocking_commit().await?;
+
+    tracker.track(
+        &ctx,
+        "run_action_immediately",
+        json!({
+            "action_id": action_id,
+            "func_run_id": func_run_id,
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    Ok(Json(RunImmediatelyResponse {
+        func_run_id,
+        result,
+    }))
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatestFuncRunId {
@@ -1,10 +1,7 @@
 use axum::{
     Router,
     async_trait,
-    extract::{
-        DefaultBodyLimit,
-        FromRequestParts,
-    },
+    extract::FromRequestParts,
     http::{
         Request,
         StatusCode,
@@ -61,15 +58,20 @@ use crate::{
         },
         unauthorized_error,
     },
+    middleware::limit_request_bodies,
 };
 
 mod get_cas_data;
 mod get_snapshot;
 mod innit;
 mod kill_execution;
+mod list_audit_logs;
 mod list_change_sets;
 mod search_workspaces;
 mod set_concurrency_limit;
+mod set_feature_flag_override;
+mod set_runtime_mode;
+mod set_schema_allowlist;
 mod set_snapshot;
 mod update_module_cache;
 mod upload_cas_data;
@@ -89,6 +91,8 @@ pub enum AdminAPIError {
     ),
     #[error("attribute value error: {0}")]
     AttributeValue(#[from] dal::attribute::value::AttributeValueError),
+    #[error("audit logging error: {0}")]
+    AuditLogging(#[from] dal::audit_logging::AuditLoggingError),
     #[error("axum http error: {0}")]
     AxumHttp(#[from] axum::http::Error),
     #[error("cached module error: {0}")]
@@ -137,6 +141,7 @@ pub struct AdminWorkspace {
     pub timestamp: si_events::Timestamp,
     pub snapshot_version: SnapshotVersion,
     pub component_concurrency_limit: Option<i32>,
+    pub schema_allowlist: Option<Vec<String>>,
 }
 
 impl From<Workspace> for AdminWorkspace {
@@ -148,6 +153,7 @@ impl From<Workspace> for AdminWorkspace {
             timestamp: value.timestamp().to_owned(),
             snapshot_version: value.snapshot_version(),
             component_concurrency_limit: value.raw_component_concurrency_limit(),
+            schema_allowlist: value.schema_allowlist().map(<[String]>::to_vec),
         }
     }
 }
@@ -202,7 +208,7 @@ impl IntoResponse for AdminAPIError {
 pub type AdminAPIResult<T> = Result<T, AdminAPIError>;
 
 pub fn v2_routes(state: AppState) -> Router<AppState> {
-    Router::new()
+    let routes = Router::new()
         .route("/innit/cache/clear", post(innit::clear_parameter_cache))
         .route(
             "/func/runs/:func_run_id/kill_execution",
@@ -217,6 +223,18 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/workspaces/:workspace_id/set_concurrency_limit",
             post(set_concurrency_limit::set_concurrency_limit),
         )
+        .route(
+            "/workspaces/:workspace_id/set_feature_flag_override",
+            post(set_feature_flag_override::set_feature_flag_override),
+        )
+        .route(
+            "/workspaces/:workspace_id/set_schema_allowlist",
+            post(set_schema_allowlist::set_schema_allowlist),
+        )
+        .route(
+            "/workspaces/:workspace_id/audit_logs",
+            get(list_audit_logs::list_audit_logs),
+        )
         .route(
             "/workspaces/:workspace_id/change_sets",
             get(list_change_sets::list_change_sets),
@@ -244,8 +262,18 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
         .route(
             "/workspaces/:workspace_id/change_sets/:change_set_id/validate_snapshot",
             post(validate_snapshot::validate_and_fix_snapshot),
-        )
-        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        );
+
+    limit_request_bodies(routes, MAX_UPLOAD_BYTES).route_layer(
+        axum::middleware::from_fn_with_state(state, require_systeminit_user),
+    )
+}
+
+/// Admin routes which are served even in maintenance mode, so that a node can be brought back
+/// out of it.
+pub fn runtime_mode_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/runtime_mode", post(set_runtime_mode::set_runtime_mode))
         .route_layer(axum::middleware::from_fn_with_state(
             state,
             require_systeminit_user,
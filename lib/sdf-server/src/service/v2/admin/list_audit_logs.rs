@@ -0,0 +1,115 @@
+use audit_database::{
+    AuditLogCursor,
+    AuditLogFilter,
+    AuditLogRow,
+};
+use axum::{
+    Json,
+    extract::{
+        Path,
+        Query,
+        State,
+    },
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use dal::{
+    ChangeSetId,
+    UserPk,
+    WorkspacePk,
+    audit_logging,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::Tenancy;
+use telemetry::prelude::*;
+
+use crate::{
+    AppState,
+    service::v2::admin::{
+        AdminAPIResult,
+        AdminUserContext,
+    },
+};
+
+const DEFAULT_SIZE: usize = 200;
+/// Larger requested sizes are clamped to this, so that one request cannot load a whole workspace's
+/// audit logs.
+const MAX_SIZE: usize = 1000;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogsRequest {
+    /// The `nextCursor` of the previous page, to list the page after it.
+    cursor: Option<AuditLogCursor>,
+    size: Option<usize>,
+    sort_ascending: Option<bool>,
+    kind: Option<String>,
+    change_set_id: Option<ChangeSetId>,
+    user_id: Option<UserPk>,
+    entity_type: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogsResponse {
+    logs: Vec<AuditLogRow>,
+    can_load_more: bool,
+    next_cursor: Option<AuditLogCursor>,
+}
+
+#[instrument(name = "admin.list_audit_logs", skip_all)]
+pub async fn list_audit_logs(
+    AdminUserContext(mut ctx): AdminUserContext,
+    State(state): State<AppState>,
+    Path(workspace_id): Path<WorkspacePk>,
+    Query(request): Query<ListAuditLogsRequest>,
+) -> AdminAPIResult<Json<ListAuditLogsResponse>> {
+    ctx.update_tenancy(Tenancy::new(workspace_id));
+
+    let filter = AuditLogFilter {
+        kind: request.kind,
+        change_set_id: request.change_set_id,
+        user_id: request.user_id,
+        entity_type: request.entity_type,
+        since: request.since,
+        until: request.until,
+    };
+    let (logs, next_cursor) = audit_logging::list_filtered(
+        &ctx,
+        state.audit_database_context(),
+        &filter,
+        request.cursor,
+        page_size(request.size),
+        request.sort_ascending.unwrap_or(false),
+    )
+    .await?;
+
+    Ok(Json(ListAuditLogsResponse {
+        logs,
+        can_load_more: next_cursor.is_some(),
+        next_cursor,
+    }))
+}
+
+fn page_size(requested: Option<usize>) -> usize {
+    requested.unwrap_or(DEFAULT_SIZE).min(MAX_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_defaults_and_is_clamped() {
+        assert_eq!(DEFAULT_SIZE, page_size(None));
+        assert_eq!(10, page_size(Some(10)));
+        assert_eq!(MAX_SIZE, page_size(Some(usize::MAX)));
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use dal::{
+    Workspace,
+    WorkspacePk,
+    feature_flags::FeatureFlag,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::Tenancy;
+use telemetry::prelude::*;
+
+use crate::service::v2::admin::{
+    AdminAPIResult,
+    AdminUserContext,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeatureFlagOverrideRequest {
+    pub feature_flag: FeatureFlag,
+    /// Whether the feature flag is enabled for the workspace, or `None` to remove the override so
+    /// that the workspace follows the global setting again.
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeatureFlagOverrideResponse {
+    pub overrides: HashMap<FeatureFlag, bool>,
+}
+
+/// Overrides whether a feature flag is enabled for a workspace, for staged rollouts. Overrides
+/// are stored on the workspace so that every service sees them.
+#[instrument(
+    name = "admin.set_feature_flag_override",
+    level = "info",
+    skip_all,
+    fields(
+        si.workspace.id = %workspace_id,
+        si.feature_flag = %request.feature_flag,
+        si.feature_flag.enabled = ?request.enabled,
+    ),
+)]
+pub async fn set_feature_flag_override(
+    AdminUserContext(mut ctx): AdminUserContext,
+    Path(workspace_id): Path<WorkspacePk>,
+    Json(request): Json<SetFeatureFlagOverrideRequest>,
+) -> AdminAPIResult<Json<SetFeatureFlagOverrideResponse>> {
+    ctx.update_tenancy(Tenancy::new(workspace_id));
+
+    let mut workspace = Workspace::get_by_pk(&ctx, workspace_id).await?;
+    let previous = workspace
+        .set_feature_flag_override(&ctx, request.feature_flag.clone(), request.enabled)
+        .await?;
+
+    ctx.commit_no_rebase().await?;
+
+    info!(
+        user = ?ctx.history_actor(),
+        feature_flag = %request.feature_flag,
+        ?previous,
+        new = ?request.enabled,
+        "changed workspace feature flag override",
+    );
+
+    Ok(Json(SetFeatureFlagOverrideResponse {
+        overrides: workspace.feature_flag_overrides().clone(),
+    }))
+}
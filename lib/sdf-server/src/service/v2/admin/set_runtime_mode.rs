@@ -0,0 +1,55 @@
+use axum::{
+    extract::State,
+    response::Json,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use telemetry::prelude::*;
+
+use crate::{
+    AppState,
+    app_state::ApplicationRuntimeMode,
+    service::v2::admin::{
+        AdminAPIResult,
+        AdminUserContext,
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRuntimeModeRequest {
+    pub mode: ApplicationRuntimeMode,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRuntimeModeResponse {
+    pub mode: ApplicationRuntimeMode,
+}
+
+/// Sets the application runtime mode, as sending SIGUSR2 to the process toggles it, for when the
+/// process can not be signalled.
+#[instrument(
+    name = "admin.set_runtime_mode",
+    level = "info",
+    skip_all,
+    fields(si.sdf.runtime_mode = ?request.mode),
+)]
+pub async fn set_runtime_mode(
+    AdminUserContext(ctx): AdminUserContext,
+    State(state): State<AppState>,
+    Json(request): Json<SetRuntimeModeRequest>,
+) -> AdminAPIResult<Json<SetRuntimeModeResponse>> {
+    let mut mode = state.application_runtime_mode.write().await;
+    info!(
+        user = ?ctx.history_actor(),
+        previous = ?*mode,
+        new = ?request.mode,
+        "changing application runtime mode",
+    );
+    *mode = request.mode;
+
+    Ok(Json(SetRuntimeModeResponse { mode: *mode }))
+}
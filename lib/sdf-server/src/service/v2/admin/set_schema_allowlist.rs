@@ -0,0 +1,94 @@
+use axum::{
+    extract::{
+        Host,
+        OriginalUri,
+        Path,
+    },
+    response::Json,
+};
+use dal::{
+    Workspace,
+    WorkspacePk,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::Tenancy;
+use telemetry::prelude::*;
+
+use crate::{
+    extract::PosthogClient,
+    service::v2::admin::{
+        AdminAPIResult,
+        AdminUserContext,
+    },
+    track_no_ctx_workspace,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSchemaAllowlistRequest {
+    pub schema_allowlist: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSchemaAllowlistResponse {
+    pub schema_allowlist: Option<Vec<String>>,
+}
+
+#[instrument(
+    name = "admin.set_schema_allowlist",
+    level = "info",
+    skip_all,
+    fields(
+        si.workspace.id = %workspace_id,
+        si.workspace.schema_allowlist = Empty,
+    ),
+)]
+pub async fn set_schema_allowlist(
+    AdminUserContext(mut ctx): AdminUserContext,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path(workspace_id): Path<WorkspacePk>,
+    Json(request): Json<SetSchemaAllowlistRequest>,
+) -> AdminAPIResult<Json<SetSchemaAllowlistResponse>> {
+    ctx.update_tenancy(Tenancy::new(workspace_id));
+
+    let span = current_span_for_instrument_at!("info");
+
+    span.record(
+        "si.workspace.schema_allowlist",
+        request
+            .schema_allowlist
+            .as_ref()
+            .map(|allowlist| allowlist.join(","))
+            .unwrap_or("any".to_string()),
+    );
+
+    let mut workspace = Workspace::get_by_pk(&ctx, workspace_id).await?;
+
+    workspace
+        .set_schema_allowlist(&ctx, request.schema_allowlist)
+        .await?;
+
+    ctx.commit_no_rebase().await?;
+
+    track_no_ctx_workspace(
+        &posthog_client,
+        &original_uri,
+        &host_name,
+        ctx.history_actor().distinct_id(),
+        workspace_id,
+        "admin.set_schema_allowlist",
+        serde_json::json!({
+            "schema_allowlist": workspace.schema_allowlist(),
+        }),
+    );
+
+    Ok(Json(SetSchemaAllowlistResponse {
+        schema_allowlist: workspace.schema_allowlist().map(<[String]>::to_vec),
+    }))
+}
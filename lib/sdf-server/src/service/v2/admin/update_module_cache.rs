@@ -9,7 +9,11 @@ use axum::{
 use dal::{
     DalContext,
     WsEvent,
-    cached_module::CachedModule,
+    cached_module::{
+        CachedModule,
+        DEFAULT_FETCH_CONCURRENCY_LIMIT,
+        DEFAULT_FETCH_MAX_ATTEMPTS,
+    },
 };
 use sdf_core::async_route::handle_error;
 use sdf_extract::EddaClient;
@@ -87,7 +91,14 @@ pub async fn update_cached_modules_inner(
     edda_client: edda_client::EddaClient,
 ) -> AdminAPIResult<()> {
     info!("Starting module cache update");
-    CachedModule::update_cached_modules(ctx, edda_client.clone()).await?;
+    CachedModule::update_cached_modules(
+        ctx,
+        edda_client.clone(),
+        DEFAULT_FETCH_CONCURRENCY_LIMIT,
+        DEFAULT_FETCH_MAX_ATTEMPTS,
+        |_, _| {},
+    )
+    .await?;
     edda_client.rebuild_for_deployment().await?;
 
     track(
@@ -40,7 +40,11 @@ use si_data_spicedb::SpiceDbError;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::middleware::WorkspacePermissionLayer;
+use crate::middleware::{
+    WorkspacePermissionLayer,
+    WorkspaceRateLimitLayer,
+    WorkspaceRateLimiter,
+};
 
 mod abandon;
 mod apply;
@@ -51,10 +55,12 @@ mod components_on_head;
 mod create;
 mod create_initialize_apply;
 mod force_apply;
+mod graph_export;
 mod list;
 mod rename;
 mod reopen;
 mod request_approval;
+mod run_qualifications;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -160,6 +166,10 @@ impl IntoResponse for Error {
             | Error::ChangeSet(dal::ChangeSetError::CantRenameHeadChangeSet) => {
                 (StatusCode::PRECONDITION_FAILED, None)
             }
+            Self::DalWrapper(DalWrapperError::NothingToApply(_)) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Some("Nothing to apply: this change set has no changes.".to_string()),
+            ),
             Self::ChangeSetApply(_) => (StatusCode::CONFLICT, None),
             Self::Transactions(dal::TransactionsError::BadWorkspaceAndChangeSet) => {
                 (StatusCode::FORBIDDEN, None)
@@ -222,7 +232,10 @@ pub async fn post_to_webhook(
     Ok(())
 }
 
-pub fn change_sets_routes() -> Router<AppState> {
+pub fn change_sets_routes(
+    state: AppState,
+    apply_limiter: Option<WorkspaceRateLimiter>,
+) -> Router<AppState> {
     Router::new()
         .route("/", get(list::list_actionable))
         .route(
@@ -232,13 +245,23 @@ pub fn change_sets_routes() -> Router<AppState> {
         .route("/create_change_set", post(create::create_change_set))
         .route(
             "/create_initialize_apply",
-            post(create_initialize_apply::create_initialize_apply),
+            post(create_initialize_apply::create_initialize_apply)
+                .layer(WorkspaceRateLimitLayer::new(state, apply_limiter)),
         )
 }
 
-pub fn change_set_routes(state: AppState) -> Router<AppState> {
+pub fn change_set_routes(
+    state: AppState,
+    apply_limiter: Option<WorkspaceRateLimiter>,
+) -> Router<AppState> {
     Router::new()
-        .route("/apply", post(apply::apply))
+        .route(
+            "/apply",
+            post(apply::apply).layer(WorkspaceRateLimitLayer::new(
+                state.clone(),
+                apply_limiter.clone(),
+            )),
+        )
         .route("/approval_status", get(approval_status::approval_status))
         .route("/approve", post(approve::approve))
         .route("/abandon", post(abandon::abandon))
@@ -248,11 +271,14 @@ pub fn change_set_routes(state: AppState) -> Router<AppState> {
         )
         .route(
             "/force_apply",
-            post(force_apply::force_apply).layer(WorkspacePermissionLayer::new(
-                state.clone(),
-                permissions::Permission::Approve,
-            )),
+            post(force_apply::force_apply)
+                .layer(WorkspaceRateLimitLayer::new(state.clone(), apply_limiter))
+                .layer(WorkspacePermissionLayer::new(
+                    state.clone(),
+                    permissions::Permission::Approve,
+                )),
         )
+        .route("/graph/export.json", get(graph_export::graph_export))
         .route("/rename", post(rename::rename))
         // Consider how we make it editable again after it's been rejected
         .route("/reopen", post(reopen::reopen))
@@ -260,6 +286,10 @@ pub fn change_set_routes(state: AppState) -> Router<AppState> {
             "/request_approval",
             post(request_approval::request_approval),
         )
+        .route(
+            "/run_qualifications",
+            post(run_qualifications::run_qualifications),
+        )
         .nest("/index", super::index::v2_change_set_routes())
 }
 
@@ -0,0 +1,13 @@
+use axum::Json;
+use dal::workspace_snapshot::graph_export::GraphExport;
+use sdf_extract::change_set::ChangeSetDalContext;
+
+use super::Result;
+
+pub async fn graph_export(
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
+) -> Result<Json<GraphExport>> {
+    let graph_export = ctx.workspace_snapshot()?.export_graph().await?;
+
+    Ok(Json(graph_export))
+}
@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use axum::Json;
+use dal::{
+    Component,
+    ComponentId,
+    component::qualification::{
+        ComponentQualificationRun,
+        DEFAULT_QUALIFICATION_RUN_CONCURRENCY,
+    },
+};
+use sdf_extract::change_set::ChangeSetDalContext;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::Result;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunQualificationsResponse {
+    /// `true` if no qualification failed for any component
+    passed: bool,
+    components: BTreeMap<ComponentId, ComponentQualificationRun>,
+}
+
+pub async fn run_qualifications(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+) -> Result<Json<RunQualificationsResponse>> {
+    let components =
+        Component::run_all_qualifications(ctx, DEFAULT_QUALIFICATION_RUN_CONCURRENCY).await?;
+
+    Ok(Json(RunQualificationsResponse {
+        passed: components.values().all(|run| run.passed),
+        components,
+    }))
+}
@@ -29,8 +29,11 @@ use si_id::ComponentId;
 use crate::app_state::AppState;
 
 pub mod attributes;
+pub mod connect_components;
 pub mod debug_component;
 pub mod delete_components;
+pub mod dependent_values_status;
+pub mod get_attribute_value;
 pub mod get_json;
 pub mod manage;
 pub mod name;
@@ -39,10 +42,12 @@ pub mod secrets;
 pub mod upgrade_components;
 
 #[remain::sorted]
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, thiserror::Error, strum::AsRefStr)]
 pub enum Error {
     #[error("action error: {0}")]
     Action(#[from] dal::action::ActionError),
+    #[error("prop path '{0}' has more than one attribute value for component {1}")]
+    AmbiguousPropPath(String, ComponentId),
     #[error("attributes error: {0}")]
     Attributes(#[from] dal::attribute::attributes::AttributesError),
     #[error("attribute value error: {0}")]
@@ -55,6 +60,8 @@ pub enum Error {
     Component(#[from] dal::ComponentError),
     #[error("component debug error: {0}")]
     ComponentDebug(#[from] dal::component::debug::ComponentDebugViewError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
     #[error("dal secret error: {0}")]
     DalSecret(#[from] dal::SecretError),
     #[error("dependent value root error: {0}")]
@@ -69,6 +76,8 @@ pub enum Error {
     KeyPair(#[from] KeyPairError),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
+    #[error("prop path '{0}' not found for component {1}")]
+    PropPathNotFound(String, ComponentId),
     #[error("schema variant error: {0}")]
     SchemaVariant(#[from] dal::SchemaVariantError),
     #[error("schema variant upgrade not required")]
@@ -91,7 +100,10 @@ impl IntoResponse for Error {
             Error::SchemaVariantUpgradeSkipped | Error::UpgradeSkippedDueToActions => {
                 StatusCode::NOT_MODIFIED
             }
-            Error::AttributeValueNotFound(_, _) => StatusCode::NOT_FOUND,
+            Error::AttributeValueNotFound(_, _)
+            | Error::ComponentNotFound(_)
+            | Error::PropPathNotFound(_, _) => StatusCode::NOT_FOUND,
+            Error::AmbiguousPropPath(_, _) => StatusCode::BAD_REQUEST,
             Error::Attributes(AttributesError::AttributeValue(
                 AttributeValueError::SubscriptionWouldCycle { .. },
             ))
@@ -101,20 +113,30 @@ impl IntoResponse for Error {
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
-        let error_message = self.to_string();
-        ApiError::new(status_code, error_message).into_response()
+        ApiError::new(status_code, &self)
+            .with_code("component", self.as_ref())
+            .into_response()
     }
 }
 
 pub fn v2_routes() -> Router<AppState> {
     Router::new()
         .route("/upgrade", post(upgrade_components::upgrade_components))
+        .route("/connect", post(connect_components::connect_components))
         .route("/delete", delete(delete_components::delete_components))
         .route("/restore", put(restore_components::restore_components))
         .nest(
             "/:componentId",
             Router::new()
+                .route(
+                    "/attribute_value",
+                    get(get_attribute_value::get_attribute_value),
+                )
                 .route("/debug", get(debug_component::debug_component))
+                .route(
+                    "/dependent_values_status",
+                    get(dependent_values_status::dependent_values_status),
+                )
                 .route("/json", get(get_json::get_json))
                 .nest("/attributes", attributes::v2_routes())
                 .nest("/name", name::v2_routes())
@@ -25,6 +25,7 @@ use dal::{
         AttributeSources,
         AttributeValueIdent,
     },
+    job::processor::JobPriority,
 };
 use sdf_core::{
     api_error::ApiError,
@@ -171,6 +172,8 @@ async fn update_attributes(
 ) -> Result<ForceChangeSetResponse<()>> {
     let force_change_set_id = ChangeSet::force_new(ctx).await?;
 
+    // The user is waiting on the results of this edit
+    ctx.set_job_priority(JobPriority::High);
     let counts = dal::update_attributes(ctx, component_id, updates).await?;
 
     ctx.commit().await?;
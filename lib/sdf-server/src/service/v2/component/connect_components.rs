@@ -0,0 +1,184 @@
+use axum::Json;
+use dal::{
+    ChangeSet,
+    Component,
+    ComponentId,
+    DalContext,
+    attribute::attributes::{
+        AttributeSources,
+        AttributeValueIdent,
+        ValueOrSourceSpec,
+    },
+};
+use sdf_core::force_change_set_response::ForceChangeSetResponse;
+use sdf_extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+
+use super::Result;
+
+/// Subscribes the attribute at `to_path` on one component to the attribute at `from_path` on
+/// another.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentConnection {
+    pub from_component_id: ComponentId,
+    pub from_path: String,
+    pub to_component_id: ComponentId,
+    pub to_path: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectComponentsRequest {
+    pub connections: Vec<ComponentConnection>,
+}
+
+/// Whether each connection in the request, in the same order, was made.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectComponentsResponse {
+    pub results: Vec<ConnectionResult>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionResult {
+    pub connected: bool,
+    pub error: Option<String>,
+}
+
+/// Makes many connections at once, so that wiring up several components takes one request.
+///
+/// Every connection is checked before any is made. If any can not be made, because a component
+/// or attribute can not be found, none are made, and those which can not be made are reported as
+/// failed. Otherwise the connections to each component are made together.
+pub async fn connect_components(
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Json(request): Json<ConnectComponentsRequest>,
+) -> Result<ForceChangeSetResponse<ConnectComponentsResponse>> {
+    let force_change_set_id = ChangeSet::force_new(ctx).await?;
+
+    let (results, subscription_count) = connect(ctx, request.connections).await?;
+
+    ctx.commit().await?;
+
+    tracker.track(
+        ctx,
+        "components_connected",
+        json!({
+            "how": "/component/connect",
+            "change_set_id": ctx.change_set_id(),
+            "subscription_count": subscription_count,
+        }),
+    );
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        ConnectComponentsResponse { results },
+    ))
+}
+
+/// Makes the connections, returning whether each one was made along with how many subscriptions
+/// were set.
+///
+/// Nothing is made unless every connection is valid, and the connections to each component are
+/// made with a single update of its attributes.
+pub async fn connect(
+    ctx: &DalContext,
+    connections: Vec<ComponentConnection>,
+) -> Result<(Vec<ConnectionResult>, usize)> {
+    let mut invalid_reasons = Vec::with_capacity(connections.len());
+    for connection in &connections {
+        invalid_reasons.push(invalid_connection_reason(ctx, connection).await?);
+    }
+    if invalid_reasons.iter().any(Option::is_some) {
+        let results = invalid_reasons
+            .into_iter()
+            .map(|reason| ConnectionResult {
+                connected: false,
+                error: Some(reason.unwrap_or_else(|| {
+                    "not connected, as other connections in the request are invalid".to_string()
+                })),
+            })
+            .collect();
+        return Ok((results, 0));
+    }
+
+    // Connections are grouped by the component they set, in the order they were requested
+    let mut updates_by_component: Vec<(ComponentId, AttributeSources)> = Vec::new();
+    let connection_count = connections.len();
+    for connection in connections {
+        let source: ValueOrSourceSpec = serde_json::from_value(json!({
+            "$source": {
+                "component": connection.from_component_id.to_string(),
+                "path": connection.from_path,
+            }
+        }))?;
+        let update = (AttributeValueIdent::new(connection.to_path), source);
+        match updates_by_component
+            .iter_mut()
+            .find(|(component_id, _)| *component_id == connection.to_component_id)
+        {
+            Some((_, updates)) => updates.0.push(update),
+            None => updates_by_component
+                .push((connection.to_component_id, AttributeSources(vec![update]))),
+        }
+    }
+
+    let mut subscription_count = 0;
+    for (component_id, updates) in updates_by_component {
+        subscription_count += dal::update_attributes(ctx, component_id, updates)
+            .await?
+            .subscription_count;
+    }
+
+    let results = (0..connection_count)
+        .map(|_| ConnectionResult {
+            connected: true,
+            error: None,
+        })
+        .collect();
+
+    Ok((results, subscription_count))
+}
+
+async fn invalid_connection_reason(
+    ctx: &DalContext,
+    connection: &ComponentConnection,
+) -> Result<Option<String>> {
+    for component_id in [connection.from_component_id, connection.to_component_id] {
+        if Component::try_get_by_id(ctx, component_id).await?.is_none() {
+            return Ok(Some(format!("component {component_id} not found")));
+        }
+    }
+
+    for (path, component_id) in [
+        (&connection.from_path, connection.from_component_id),
+        (&connection.to_path, connection.to_component_id),
+    ] {
+        // A path naming an attribute value of another component is reported like any other
+        // connection which can not be made
+        match AttributeValueIdent::new(path)
+            .resolve(ctx, component_id)
+            .await
+        {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Ok(Some(format!(
+                    "attribute value '{path}' not found for component {component_id}"
+                )));
+            }
+            Err(err) => return Ok(Some(err.to_string())),
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,42 @@
+use axum::{
+    Json,
+    extract::Path,
+};
+use dal::{
+    AttributeValueId,
+    Component,
+};
+use sdf_extract::change_set::ChangeSetDalContext;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    ComponentIdFromPath,
+    Result,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentValuesStatusResponse {
+    /// Whether any of the component's values are still waiting to be recomputed.
+    pub pending: bool,
+    /// The component's values which are waiting to be recomputed.
+    pub in_flight: Vec<AttributeValueId>,
+}
+
+/// Reports whether the values of a component have settled, going by the dependent values update
+/// still waiting to be processed in the change set. Values which depend on a changed value, even
+/// one on another component, count as waiting.
+pub async fn dependent_values_status(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    Path(ComponentIdFromPath { component_id }): Path<ComponentIdFromPath>,
+) -> Result<Json<DependentValuesStatusResponse>> {
+    let in_flight = Component::pending_dependent_value_ids(ctx, component_id).await?;
+
+    Ok(Json(DependentValuesStatusResponse {
+        pending: !in_flight.is_empty(),
+        in_flight,
+    }))
+}
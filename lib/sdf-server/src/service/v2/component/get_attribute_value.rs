@@ -0,0 +1,99 @@
+use axum::{
+    Json,
+    extract::{
+        Path,
+        Query,
+    },
+};
+use dal::{
+    AttributeValue,
+    AttributeValueId,
+    Component,
+    ComponentId,
+    DalContext,
+    Prop,
+    prop::PropPath,
+};
+use sdf_extract::change_set::ChangeSetDalContext;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    ComponentIdFromPath,
+    Error,
+    Result,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAttributeValueRequest {
+    /// The path to the prop, with its parts separated by `/`, such as `root/domain/name`.
+    pub prop_path: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAttributeValueResponse {
+    pub attribute_value_id: AttributeValueId,
+    pub value: Option<serde_json::Value>,
+}
+
+/// Gets the current value of one attribute of a component, without building the whole attribute
+/// tree for it.
+pub async fn get_attribute_value(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    Path(ComponentIdFromPath { component_id }): Path<ComponentIdFromPath>,
+    Query(request): Query<GetAttributeValueRequest>,
+) -> Result<Json<GetAttributeValueResponse>> {
+    Ok(Json(
+        attribute_value(ctx, component_id, &request.prop_path).await?,
+    ))
+}
+
+/// Finds the attribute value for the prop at `prop_path` on the component.
+///
+/// Props beneath an array or a map have one value per element, so a path to one of those is
+/// rejected as ambiguous rather than returning whichever element happens to come first.
+pub async fn attribute_value(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    prop_path: &str,
+) -> Result<GetAttributeValueResponse> {
+    if !Component::exists_by_id(ctx, component_id).await? {
+        return Err(Error::ComponentNotFound(component_id));
+    }
+    let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
+
+    let prop_id = Prop::find_prop_id_by_path_opt(
+        ctx,
+        schema_variant_id,
+        &PropPath::new(prop_path.split('/')),
+    )
+    .await?
+    .ok_or_else(|| Error::PropPathNotFound(prop_path.to_owned(), component_id))?;
+    let attribute_value_id =
+        match Component::attribute_values_for_prop_id(ctx, component_id, prop_id)
+            .await?
+            .as_slice()
+        {
+            [attribute_value_id] => *attribute_value_id,
+            [] => {
+                return Err(Error::AttributeValueNotFound(
+                    prop_path.to_owned(),
+                    component_id,
+                ));
+            }
+            _ => {
+                return Err(Error::AmbiguousPropPath(prop_path.to_owned(), component_id));
+            }
+        };
+
+    let value = AttributeValue::view(ctx, attribute_value_id).await?;
+
+    Ok(GetAttributeValueResponse {
+        attribute_value_id,
+        value,
+    })
+}
@@ -40,6 +40,10 @@ pub async fn upgrade_components(
 
     for component_id in component_ids {
         let current_component = Component::get_by_id(ctx, component_id).await?;
+        // Pinned components are left on their schema variant
+        if current_component.pinned_schema_variant_id().is_some() {
+            continue;
+        }
         let current_schema_variant = current_component.schema_variant(ctx).await?;
         let schema = current_schema_variant.schema(ctx).await?;
 
@@ -35,10 +35,17 @@ use telemetry::prelude::*;
 use thiserror::Error;
 use veritech_client::FunctionResultFailureErrorKind;
 
-use crate::AppState;
+use crate::{
+    AppState,
+    middleware::{
+        WorkspaceRateLimitLayer,
+        WorkspaceRateLimiter,
+    },
+};
 
 pub mod argument;
 pub mod binding;
+pub mod cancel_execution;
 pub mod create_func;
 pub mod create_unlocked_copy;
 pub mod delete_func;
@@ -47,6 +54,7 @@ pub mod get_code;
 pub mod get_func_run;
 pub mod get_func_run_logs;
 pub mod get_func_run_logs_av;
+pub mod get_func_run_logs_txt;
 pub mod get_func_runs_paginated;
 pub mod list_funcs;
 pub mod save_code;
@@ -84,6 +92,10 @@ pub enum FuncAPIError {
     FuncNameReserved(String),
     #[error("The function does not exist")]
     FuncNotFound(FuncId),
+    #[error("no logs found for func run: {0}")]
+    FuncRunLogNotFound(si_events::FuncRunId),
+    #[error("func runner error: {0}")]
+    FuncRunner(#[from] FuncRunnerError),
     #[error("hyper error: {0}")]
     Http(#[from] axum::http::Error),
     #[error("layer db error: {0}")]
@@ -172,12 +184,22 @@ impl IntoResponse for FuncAPIError {
             | Self::MissingPrototypeId
             | Self::MissingSchemaVariantAndFunc
             | Self::Func(FuncError::FuncLocked(_))
-            | Self::SchemaVariant(dal::SchemaVariantError::SchemaVariantLocked(_)) => {
+            | Self::SchemaVariant(dal::SchemaVariantError::SchemaVariantLocked(_))
+            | Self::SiDb(si_db::SiDbError::Cursor(_)) => {
                 (StatusCode::BAD_REQUEST, None)
             }
 
+            // Only in-progress executions can be cancelled
+            Self::FuncRunner(FuncRunnerError::FuncRunNotInProgress(_, _)) => {
+                (StatusCode::CONFLICT, None)
+            }
+
             // Return 404 when the func is not found
             Self::FuncNotFound(_) |
+            // Return 404 when the func run belongs to another workspace
+            Self::FuncRunner(FuncRunnerError::FuncRunNotInWorkspace(_)) |
+            // Return 404 when no logs have been stored for the func run
+            Self::FuncRunLogNotFound(_) |
             // When a graph node cannot be found for a schema variant, it is not found
             Self::SchemaVariant(dal::SchemaVariantError::NotFound(_)) => {
                 (StatusCode::NOT_FOUND, None)
@@ -210,7 +232,10 @@ impl IntoResponse for FuncAPIError {
     }
 }
 
-pub fn v2_routes() -> Router<AppState> {
+pub fn v2_routes(
+    state: AppState,
+    execution_limiter: Option<WorkspaceRateLimiter>,
+) -> Router<AppState> {
     Router::new()
         // Func Stuff
         .route("/", get(list_funcs::list_funcs))
@@ -220,6 +245,14 @@ pub fn v2_routes() -> Router<AppState> {
             "/runs/:func_run_id/logs",
             get(get_func_run_logs::get_func_run_logs),
         )
+        .route(
+            "/runs/:func_run_id/logs.txt",
+            get(get_func_run_logs_txt::get_func_run_logs_txt),
+        )
+        .route(
+            "/runs/:func_run_id/cancel",
+            post(cancel_execution::cancel_execution),
+        )
         .route(
             "/runs/paginated",
             get(get_func_runs_paginated::get_func_runs_paginated),
@@ -231,8 +264,18 @@ pub fn v2_routes() -> Router<AppState> {
         .route("/", post(create_func::create_func))
         .route("/:func_id", put(update_func::update_func)) // only save the func's metadata
         .route("/:func_id/code", put(save_code::save_code)) // only saves func code
-        .route("/:func_id/test_execute", post(test_execute::test_execute))
-        .route("/:func_id/execute", post(execute_func::execute_func))
+        .route(
+            "/:func_id/test_execute",
+            post(test_execute::test_execute).layer(WorkspaceRateLimitLayer::new(
+                state.clone(),
+                execution_limiter.clone(),
+            )),
+        )
+        .route(
+            "/:func_id/execute",
+            post(execute_func::execute_func)
+                .layer(WorkspaceRateLimitLayer::new(state, execution_limiter)),
+        )
         .route(
             "/:func_id",
             post(create_unlocked_copy::create_unlocked_copy),
@@ -0,0 +1,30 @@
+use axum::extract::Path;
+use dal::{
+    ChangeSetId,
+    WorkspacePk,
+    func::runner::FuncRunner,
+};
+use si_events::FuncRunId;
+
+use super::FuncAPIResult;
+use crate::{
+    extract::HandlerContext,
+    service::v2::AccessBuilder,
+};
+
+pub async fn cancel_execution(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, func_run_id)): Path<(WorkspacePk, ChangeSetId, FuncRunId)>,
+) -> FuncAPIResult<()> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    FuncRunner::cancel_execution(&ctx, func_run_id).await?;
+
+    // We commit without a rebase here because we need to commit our func run table changes.
+    ctx.commit_no_rebase().await?;
+
+    Ok(())
+}
@@ -37,6 +37,7 @@ use si_events::{
     FuncRunLog,
     FuncRunLogId,
     FuncRunState,
+    FuncRunTimings,
     OutputLine,
 };
 
@@ -58,17 +59,23 @@ pub struct OutputLineView {
     pub group: Option<String>,
     pub message: String,
     pub timestamp: u64,
+    /// The fields of the message if it was a JSON object, otherwise the message is plain text.
+    pub structured: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl From<&OutputLine> for OutputLineView {
     fn from(output_line: &OutputLine) -> Self {
+        let structured = output_line.structured_message();
+        let level = output_line.level_with(structured.as_ref());
+
         Self {
             stream: output_line.stream.clone(),
             execution_id: output_line.execution_id.clone(),
-            level: output_line.level.clone(),
+            level,
             group: output_line.group.clone(),
             message: output_line.message.clone(),
             timestamp: output_line.timestamp,
+            structured,
         }
     }
 }
@@ -132,6 +139,7 @@ pub struct FuncRunView {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     unprocessed_result_value: Option<serde_json::Value>,
+    timings: Option<FuncRunTimings>,
 }
 
 impl FuncRunView {
@@ -142,6 +150,7 @@ impl FuncRunView {
         result_value: Option<serde_json::Value>,
         logs: Option<FuncRunLogView>,
         unprocessed_result_value: Option<serde_json::Value>,
+        timings: Option<FuncRunTimings>,
     ) -> Self {
         FuncRunView {
             id: func_run.id(),
@@ -178,6 +187,7 @@ impl FuncRunView {
             created_at: func_run.created_at(),
             updated_at: func_run.updated_at(),
             unprocessed_result_value,
+            timings,
         }
     }
 }
@@ -247,6 +257,7 @@ pub async fn get_func_run_view(ctx: &DalContext, func_run: &FuncRun) -> FuncAPIR
     let logs = FuncRunLogDb::get_for_func_run_id(ctx, func_run.id())
         .await?
         .map(|v| v.into());
+    let timings = FuncRunDb::timings(ctx, func_run.id()).await?;
 
     Ok(FuncRunView::new(
         func_run,
@@ -255,6 +266,7 @@ pub async fn get_func_run_view(ctx: &DalContext, func_run: &FuncRun) -> FuncAPIR
         result_value,
         logs,
         unprocessed_result_value,
+        timings,
     ))
 }
 
@@ -0,0 +1,77 @@
+use std::convert::Infallible;
+
+use axum::{
+    body::StreamBody,
+    extract::Path,
+    http::header,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use dal::WorkspacePk;
+use si_db::FuncRunLogDb;
+use si_events::{
+    FuncRunId,
+    FuncRunLog,
+};
+
+use crate::{
+    extract::HandlerContext,
+    service::v2::{
+        AccessBuilder,
+        func::{
+            FuncAPIError,
+            FuncAPIResult,
+        },
+    },
+};
+
+/// Download the logs for a specific function run as a plain text attachment
+///
+/// Each stored output line is written on its own line, in the order it was
+/// emitted by the function.
+pub async fn get_func_run_logs_txt(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id, func_run_id)): Path<(
+        WorkspacePk,
+        dal::ChangeSetId,
+        FuncRunId,
+    )>,
+) -> FuncAPIResult<Response> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let func_run_log = FuncRunLogDb::get_for_func_run_id(&ctx, func_run_id)
+        .await?
+        .ok_or(FuncAPIError::FuncRunLogNotFound(func_run_id))?;
+
+    Ok(logs_attachment_response(func_run_log))
+}
+
+/// Builds a plain text attachment response containing the messages of the given [`FuncRunLog`].
+///
+/// The body is streamed a line at a time, rather than copying every message into one buffer.
+fn logs_attachment_response(func_run_log: FuncRunLog) -> Response {
+    let func_run_id = func_run_log.func_run_id();
+    let lines = futures::stream::iter((0..func_run_log.logs().len()).map(move |index| {
+        Ok::<_, Infallible>(format!("{}\n", func_run_log.logs()[index].message))
+    }));
+
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/plain; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"func-run-{func_run_id}.txt\""),
+            ),
+        ],
+        StreamBody::new(lines),
+    )
+        .into_response()
+}
@@ -5,7 +5,10 @@ use axum::{
         Query,
     },
 };
-use dal::DalContext;
+use dal::{
+    Cursor,
+    DalContext,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -14,7 +17,6 @@ use si_db::FuncRunDb;
 use si_events::{
     CasValue,
     FuncRun,
-    FuncRunId,
     WorkspacePk,
 };
 
@@ -88,8 +90,9 @@ async fn get_func_run_view_without_logs(
         }
     };
 
-    // Skip fetching logs to improve performance
+    // Skip fetching logs and timings to improve performance
     let logs = None;
+    let timings = None;
 
     Ok(FuncRunView::new(
         func_run,
@@ -98,6 +101,7 @@ async fn get_func_run_view_without_logs(
         result_value,
         logs,
         unprocessed_result_value,
+        timings,
     ))
 }
 
@@ -105,7 +109,7 @@ async fn get_func_run_view_without_logs(
 #[serde(rename_all = "camelCase")]
 pub struct PaginationParams {
     limit: Option<u32>,
-    cursor: Option<FuncRunId>,
+    cursor: Option<String>,
     component_id: Option<dal::ComponentId>,
 }
 
@@ -113,14 +117,14 @@ pub struct PaginationParams {
 #[serde(rename_all = "camelCase")]
 pub struct GetFuncRunsPaginatedResponse {
     func_runs: Vec<FuncRunView>,
-    next_cursor: Option<FuncRunId>,
+    next_cursor: Option<String>,
 }
 
 /// Get paginated function runs for a workspace
 ///
 /// This endpoint supports cursor-based pagination:
 /// - `limit` parameter controls how many items to return per page (default: 50, max: 100)
-/// - `cursor` parameter should be the ID of the last item from the previous page
+/// - `cursor` parameter should be the `nextCursor` returned with the previous page
 /// - `component_id` parameter filters results to a specific component (optional)
 ///
 /// Results are ordered by creation time (newest first).
@@ -136,6 +140,11 @@ pub async fn get_func_runs_paginated(
 
     // Set default limit and enforce a max limit
     let limit = params.limit.unwrap_or(50).min(100);
+    let cursor = params
+        .cursor
+        .map(|cursor| Cursor::decode(&cursor, ctx.symmetric_crypto_service()))
+        .transpose()
+        .map_err(si_db::SiDbError::from)?;
 
     // Query the database with pagination parameters
     let func_runs = if let Some(component_id) = params.component_id {
@@ -146,7 +155,7 @@ pub async fn get_func_runs_paginated(
             change_set_id,
             component_id,
             limit as i64,
-            params.cursor,
+            cursor,
         )
         .await?
     } else {
@@ -155,14 +164,16 @@ pub async fn get_func_runs_paginated(
             workspace_pk,
             change_set_id,
             limit as i64,
-            params.cursor,
+            cursor,
         )
         .await?
     };
 
     // Determine the next cursor (if we have at least `limit` results)
     let next_cursor = if func_runs.len() == limit as usize {
-        func_runs.last().map(|run| run.id())
+        func_runs
+            .last()
+            .map(|run| Cursor::Id(run.id().into_raw_id()).encode(ctx.symmetric_crypto_service()))
     } else {
         None
     };
@@ -38,6 +38,7 @@ use dal::{
         },
     },
     func::authoring::FuncAuthoringError,
+    job::processor::JobPriority,
     management::{
         ManagementError,
         prototype::{
@@ -208,6 +209,8 @@ pub async fn run_prototype_inner(
     // event payload. This is so that the activity tracker has something to work with for tracking
     // the lifecycle of a management function in flight.
     let request_ulid = request.request_ulid.unwrap_or_default();
+    // The user is waiting on the results of the management func
+    ctx.set_job_priority(JobPriority::High);
     ctx.enqueue_management_func(prototype_id, component_id, view_id, request_ulid)
         .await?;
     WsEvent::management_operations_in_progress(ctx, request_ulid)
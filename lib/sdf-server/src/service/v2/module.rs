@@ -1,9 +1,6 @@
 use axum::{
     Router,
-    extract::{
-        DefaultBodyLimit,
-        multipart::MultipartError,
-    },
+    extract::multipart::MultipartError,
     http::StatusCode,
     response::{
         IntoResponse,
@@ -27,7 +24,10 @@ use si_pkg::SiPkgError;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::AppState;
+use crate::{
+    AppState,
+    middleware::limit_request_bodies,
+};
 
 mod builtins;
 mod contribute;
@@ -110,7 +110,7 @@ impl IntoResponse for ModulesAPIError {
 }
 
 pub fn v2_routes() -> Router<AppState> {
-    Router::new()
+    let routes = Router::new()
         .route("/contribute", post(contribute::contribute))
         .route("/sync", get(sync::sync))
         .route("/", get(list::list))
@@ -121,6 +121,7 @@ pub fn v2_routes() -> Router<AppState> {
         .route(
             "/install_from_file",
             post(install_from_file::install_module_from_file),
-        )
-        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        );
+
+    limit_request_bodies(routes, MAX_UPLOAD_BYTES)
 }
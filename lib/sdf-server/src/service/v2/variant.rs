@@ -28,6 +28,7 @@ use crate::AppState;
 pub mod create_unlocked_copy;
 mod delete_unlocked_variant;
 mod get_variant;
+mod get_variant_definition;
 mod list_variants;
 
 #[remain::sorted]
@@ -72,6 +73,11 @@ impl IntoResponse for SchemaVariantsAPIError {
             }
             // When a graph node cannot be found for a schema variant, it is not found
             Self::SchemaVariant(dal::SchemaVariantError::NotFound(_)) => StatusCode::NOT_FOUND,
+            Self::VariantAuthoring(
+                dal::schema::variant::authoring::VariantAuthoringError::SchemaVariantAssetNotFound(
+                    _,
+                ),
+            ) => StatusCode::NOT_FOUND,
             _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
         };
 
@@ -83,6 +89,10 @@ pub fn v2_routes() -> Router<AppState> {
     Router::new()
         .route("/", get(list_variants::list_variants))
         .route("/:schema_variant_id", get(get_variant::get_variant))
+        .route(
+            "/:schema_variant_id/definition",
+            get(get_variant_definition::get_variant_definition),
+        )
         .route(
             "/:schema_variant_id",
             post(create_unlocked_copy::create_unlocked_copy),
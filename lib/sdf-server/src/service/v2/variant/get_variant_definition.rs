@@ -0,0 +1,60 @@
+use axum::{
+    Json,
+    extract::{
+        Host,
+        OriginalUri,
+        Path,
+    },
+};
+use dal::{
+    ChangeSetId,
+    SchemaVariantId,
+    WorkspacePk,
+    schema::variant::authoring::{
+        SchemaVariantDefinition,
+        VariantAuthoringClient,
+    },
+};
+
+use super::SchemaVariantsAPIResult;
+use crate::{
+    extract::{
+        HandlerContext,
+        PosthogClient,
+    },
+    service::v2::AccessBuilder,
+    track,
+};
+
+pub async fn get_variant_definition(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, schema_variant_id)): Path<(
+        WorkspacePk,
+        ChangeSetId,
+        SchemaVariantId,
+    )>,
+) -> SchemaVariantsAPIResult<Json<SchemaVariantDefinition>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let definition = VariantAuthoringClient::export_definition(&ctx, schema_variant_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "export_variant_definition",
+        serde_json::json!({
+            "variant_id": schema_variant_id,
+            "schema_name": &definition.metadata.schema_name,
+        }),
+    );
+
+    Ok(Json(definition))
+}
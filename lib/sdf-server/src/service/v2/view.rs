@@ -55,10 +55,12 @@ mod set_geometry;
 pub mod update_view;
 
 #[remain::sorted]
-#[derive(Debug, Error)]
+#[derive(Debug, Error, strum::AsRefStr)]
 pub enum ViewError {
     #[error("cached module error: {0}")]
     CachedModule(#[from] CachedModuleError),
+    #[error("the default view can not be deleted")]
+    CantDeleteDefaultView(),
     #[error("workspace must have at least one view")]
     CantDeleteOnlyView(),
     #[error("changeset error: {0}")]
@@ -107,7 +109,9 @@ impl IntoResponse for ViewError {
     fn into_response(self) -> Response {
         let (status_code, error_message) = match self {
             ViewError::NameAlreadyInUse(_) => (StatusCode::CONFLICT, self.to_string()),
-            ViewError::CantDeleteOnlyView() => (StatusCode::PRECONDITION_FAILED, self.to_string()),
+            ViewError::CantDeleteDefaultView() | ViewError::CantDeleteOnlyView() => {
+                (StatusCode::PRECONDITION_FAILED, self.to_string())
+            }
             ViewError::DalDiagram(
                 dal::diagram::DiagramError::DeletingLastGeometryForComponent(_, _),
             )
@@ -128,7 +132,16 @@ impl IntoResponse for ViewError {
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
-        ApiError::new(status_code, error_message).into_response()
+        // Diagram errors are reported under their own kind, so that clients see which diagram
+        // error occurred rather than only that the view service hit one
+        let (kind, code) = match &self {
+            ViewError::DalDiagram(err) => ("diagram", err.as_ref()),
+            _ => ("view", self.as_ref()),
+        };
+
+        ApiError::new(status_code, error_message)
+            .with_code(kind, code)
+            .into_response()
     }
 }
 
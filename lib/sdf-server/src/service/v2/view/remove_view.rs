@@ -2,6 +2,7 @@ use axum::extract::{
     Host,
     OriginalUri,
     Path,
+    Query,
 };
 use dal::{
     ChangeSet,
@@ -13,6 +14,10 @@ use dal::{
         ViewId,
     },
 };
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use si_events::audit_log::AuditLogKind;
 
 use super::{
@@ -31,6 +36,19 @@ use crate::{
     track,
 };
 
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Moves the components which are only in this view to the default view, rather than refusing
+    /// to remove the view.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Removes the view. The default view, and the only view in a workspace, can not be removed.
+///
+/// A view containing components which are in no other view is only removed when `force` is set,
+/// moving those components to the default view first.
 pub async fn remove_view(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(access_builder): AccessBuilder,
@@ -38,6 +56,7 @@ pub async fn remove_view(
     OriginalUri(original_uri): OriginalUri,
     Host(host_name): Host,
     Path((_workspace_pk, change_set_id, view_id)): Path<(WorkspacePk, ChangeSetId, ViewId)>,
+    Query(Request { force }): Query<Request>,
 ) -> ViewResult<ForceChangeSetResponse<()>> {
     let mut ctx = builder
         .build(access_builder.build(change_set_id.into()))
@@ -51,6 +70,15 @@ pub async fn remove_view(
     }
 
     let view = View::get_by_id(&ctx, view_id).await?;
+    if view.is_default(&ctx).await? {
+        return Err(ViewError::CantDeleteDefaultView());
+    }
+
+    let moved_component_ids = if force {
+        View::move_components_to_default(&ctx, view_id).await?
+    } else {
+        Vec::new()
+    };
     View::remove(&ctx, view_id).await?;
 
     WsEvent::view_deleted(&ctx, view_id)
@@ -74,6 +102,8 @@ pub async fn remove_view(
             "how": "/view",
             "view_id": view.id(),
             "view_name": view.name(),
+            "force": force,
+            "moved_component_ids": moved_component_ids,
             "change_set_id": ctx.change_set_id(),
         }),
     );
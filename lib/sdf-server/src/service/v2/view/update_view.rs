@@ -47,6 +47,7 @@ pub struct Request {
     pub name: String,
 }
 
+/// Renames the view, rejecting a name already used by another view, and returns the updated view.
 pub async fn update_view(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(access_builder): AccessBuilder,
@@ -55,7 +56,7 @@ pub async fn update_view(
     Host(host_name): Host,
     Path((_workspace_pk, change_set_id, view_id)): Path<(WorkspacePk, ChangeSetId, ViewId)>,
     Json(Request { name }): Json<Request>,
-) -> ViewResult<ForceChangeSetResponse<()>> {
+) -> ViewResult<ForceChangeSetResponse<ViewView>> {
     let mut ctx = builder
         .build(access_builder.build(change_set_id.into()))
         .await?;
@@ -74,8 +75,8 @@ pub async fn update_view(
 
     let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
 
+    let mut view = View::get_by_id(&ctx, view_id).await?;
     if should_update {
-        let mut view = View::get_by_id(&ctx, view_id).await?;
         let old_view_name = view.name().to_owned();
         view.set_name(&ctx, name).await?;
 
@@ -101,8 +102,10 @@ pub async fn update_view(
             view.name().to_owned(),
         )
         .await?;
-        let view_view = ViewView::from_view(&ctx, view).await?;
+    }
+    let view_view = ViewView::from_view(&ctx, view).await?;
 
+    if should_update {
         WsEvent::view_updated(&ctx, view_view.clone())
             .await?
             .publish_on_commit(&ctx)
@@ -111,5 +114,5 @@ pub async fn update_view(
 
     ctx.commit().await?;
 
-    Ok(ForceChangeSetResponse::new(force_change_set_id, ()))
+    Ok(ForceChangeSetResponse::new(force_change_set_id, view_view))
 }
@@ -206,3 +206,28 @@ async fn protected_apply(ctx: &mut DalContext, spicedb_client: SpiceDbClient) ->
 
     Ok(())
 }
+
+#[sdf_test]
+async fn protected_apply_with_nothing_to_apply(
+    ctx: &mut DalContext,
+    spicedb_client: SpiceDbClient,
+) -> Result<()> {
+    let mut spicedb_client = spicedb_client;
+
+    // FIXME(nick,jacob): see the comment attached to this function.
+    write_schema(&mut spicedb_client).await?;
+
+    // A freshly forked change set has no changes relative to HEAD.
+    let change_set_id = ctx.change_set_id();
+    match dal_wrapper::change_set::protected_apply_to_base_change_set(ctx, &mut spicedb_client)
+        .await
+    {
+        Err(DalWrapperError::NothingToApply(id)) => assert_eq!(
+            change_set_id, // expected
+            id             // actual
+        ),
+        other => return Err(eyre!("unexpected result: {other:?}")),
+    }
+
+    Ok(())
+}
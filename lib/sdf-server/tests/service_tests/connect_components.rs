@@ -0,0 +1,120 @@
+use dal::{
+    ComponentId,
+    DalContext,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        attribute::value,
+        change_set,
+        component,
+    },
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use sdf_server::service::v2::component::connect_components::{
+    ComponentConnection,
+    ConnectionResult,
+    connect,
+};
+
+fn connection(
+    from_component_id: ComponentId,
+    from_path: &str,
+    to_component_id: ComponentId,
+    to_path: &str,
+) -> ComponentConnection {
+    ComponentConnection {
+        from_component_id,
+        from_path: from_path.to_string(),
+        to_component_id,
+        to_path: to_path.to_string(),
+    }
+}
+
+fn failed(error: String) -> ConnectionResult {
+    ConnectionResult {
+        connected: false,
+        error: Some(error),
+    }
+}
+
+#[sdf_test]
+async fn invalid_connections_stop_every_connection(ctx: &mut DalContext) -> Result<()> {
+    let source = component::create(ctx, "Docker Image", "source").await?;
+    let target = component::create(ctx, "Docker Image", "target").await?;
+    value::set(ctx, ("source", "/domain/image"), "nginx").await?;
+    change_set::commit(ctx).await?;
+    let missing = ComponentId::new();
+
+    let (results, subscription_count) = connect(
+        ctx,
+        vec![
+            connection(source, "/domain/nope", target, "/domain/image"),
+            connection(source, "/domain/image", target, "/domain/nope"),
+            connection(missing, "/domain/image", target, "/domain/image"),
+            connection(source, "/domain/image", target, "/domain/image"),
+        ],
+    )
+    .await?;
+
+    assert_eq!(
+        vec![
+            failed(format!(
+                "attribute value '/domain/nope' not found for component {source}"
+            )),
+            failed(format!(
+                "attribute value '/domain/nope' not found for component {target}"
+            )),
+            failed(format!("component {missing} not found")),
+            failed("not connected, as other connections in the request are invalid".to_string()),
+        ],
+        results
+    );
+    assert_eq!(0, subscription_count);
+
+    // Not even the valid connection was made
+    change_set::commit(ctx).await?;
+    assert!(!value::has_subscription(ctx, ("target", "/domain/image")).await?);
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn connections_are_made_together(ctx: &mut DalContext) -> Result<()> {
+    let source = component::create(ctx, "Docker Image", "source").await?;
+    let target = component::create(ctx, "Docker Image", "target").await?;
+    let other_target = component::create(ctx, "Docker Image", "other target").await?;
+    value::set(ctx, ("source", "/domain/image"), "nginx").await?;
+    change_set::commit(ctx).await?;
+
+    let (results, subscription_count) = connect(
+        ctx,
+        vec![
+            connection(source, "/domain/image", target, "/domain/image"),
+            connection(source, "/domain/image", other_target, "/domain/image"),
+            connection(source, "/domain/image", target, "/si/name"),
+        ],
+    )
+    .await?;
+
+    let connected = ConnectionResult {
+        connected: true,
+        error: None,
+    };
+    assert_eq!(
+        vec![connected.clone(), connected.clone(), connected],
+        results
+    );
+    assert_eq!(3, subscription_count);
+
+    change_set::commit(ctx).await?;
+    assert_eq!("nginx", value::get(ctx, ("target", "/domain/image")).await?);
+    assert_eq!(
+        "nginx",
+        value::get(ctx, ("other target", "/domain/image")).await?
+    );
+    assert!(value::has_subscription(ctx, ("target", "/si/name")).await?);
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use axum::{
+    http::StatusCode,
+    response::IntoResponse,
+};
+use dal::job::consumer::JobConsumerError;
+use pretty_assertions_sorted::assert_eq;
+use sdf_server::service::v2::{
+    action::ActionRequestError,
+    component,
+    view::ViewError,
+};
+use serde_json::json;
+use si_events::{
+    ChangeSetId,
+    ComponentId,
+};
+use si_id::ViewId;
+
+#[tokio::test]
+async fn component_error_has_structured_body() {
+    let component_id = ComponentId::new();
+
+    let response =
+        component::Error::AttributeValueNotFound("/domain/name".to_string(), component_id)
+            .into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .expect("failed to read response body");
+    let body: serde_json::Value = serde_json::from_slice(&body).expect("response body is not json");
+    assert_eq!(
+        json!({
+            "error": {
+                "message": format!(
+                    "attribute value '/domain/name' not found for component {component_id}"
+                ),
+                "statusCode": 404,
+                "kind": "component",
+                "code": "AttributeValueNotFound",
+            },
+            "level": null,
+        }),
+        body
+    );
+}
+
+#[tokio::test]
+async fn view_error_has_structured_body() {
+    let response = ViewError::NameAlreadyInUse("Mustaine".to_string()).into_response();
+
+    assert_eq!(StatusCode::CONFLICT, response.status());
+
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .expect("failed to read response body");
+    let body: serde_json::Value = serde_json::from_slice(&body).expect("response body is not json");
+    assert_eq!(
+        json!({
+            "error": {
+                "message": "there is already a view called Mustaine",
+                "statusCode": 409,
+                "kind": "view",
+                "code": "NameAlreadyInUse",
+            },
+            "level": null,
+        }),
+        body
+    );
+}
+
+#[tokio::test]
+async fn diagram_error_is_reported_under_its_own_kind() {
+    let view_id = ViewId::new();
+
+    let response =
+        ViewError::DalDiagram(dal::diagram::DiagramError::ViewNotFound(view_id)).into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .expect("failed to read response body");
+    let body: serde_json::Value = serde_json::from_slice(&body).expect("response body is not json");
+    assert_eq!(Some(&json!("diagram")), body.pointer("/error/kind"));
+    assert_eq!(Some(&json!("ViewNotFound")), body.pointer("/error/code"));
+    assert_eq!(Some(&json!(404)), body.pointer("/error/statusCode"));
+}
+
+#[tokio::test]
+async fn running_actions_immediately_outside_head_is_a_bad_request() {
+    let change_set_id = ChangeSetId::new();
+
+    for error in [
+        ActionRequestError::RunImmediatelyOutsideHead(change_set_id),
+        ActionRequestError::JobConsumer(JobConsumerError::RunImmediatelyOutsideHead(change_set_id)),
+    ] {
+        assert_eq!(StatusCode::BAD_REQUEST, error.into_response().status());
+    }
+}
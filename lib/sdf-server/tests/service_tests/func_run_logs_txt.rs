@@ -0,0 +1,100 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request,
+        StatusCode,
+        header,
+    },
+    response::Response,
+};
+use dal::DalContext;
+use dal_test::{
+    AuthToken,
+    Result,
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use si_db::FuncRunLogDb;
+use si_events::{
+    FuncRunId,
+    FuncRunLog,
+    OutputLine,
+    Tenancy,
+};
+use tower::ServiceExt;
+
+fn output_line(message: &str) -> OutputLine {
+    OutputLine {
+        stream: "stdout".to_string(),
+        execution_id: "execution".to_string(),
+        level: "info".to_string(),
+        group: None,
+        message: message.to_string(),
+        timestamp: 0,
+    }
+}
+
+async fn get_logs_txt(
+    ctx: &DalContext,
+    router: &Router,
+    auth_token: &AuthToken,
+    func_run_id: FuncRunId,
+) -> Result<Response> {
+    let request = Request::get(format!(
+        "/api/v2/workspaces/{}/change-sets/{}/funcs/runs/{func_run_id}/logs.txt",
+        ctx.workspace_pk()?,
+        ctx.change_set_id(),
+    ))
+    .header(header::AUTHORIZATION, format!("Bearer {}", auth_token.0))
+    .body(Body::empty())?;
+
+    Ok(router.clone().oneshot(request).await?)
+}
+
+#[sdf_test]
+async fn logs_are_returned_as_text_attachment(
+    ctx: &mut DalContext,
+    router: Router,
+    auth_token: AuthToken,
+) -> Result<()> {
+    let func_run_id = FuncRunId::new();
+    let mut func_run_log = FuncRunLog::new(
+        func_run_id,
+        Tenancy::new(ctx.workspace_pk()?, ctx.change_set_id()),
+    );
+    func_run_log.push_log(output_line("Running CLI command"));
+    func_run_log.push_log(output_line("Output: {\"status\":\"ok\"}"));
+    FuncRunLogDb::upsert(ctx, func_run_log).await?;
+    ctx.commit_no_rebase().await?;
+
+    let response = get_logs_txt(ctx, &router, &auth_token, func_run_id).await?;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        Some("text/plain; charset=utf-8"),
+        response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+    );
+    assert_eq!(
+        Some(format!("attachment; filename=\"func-run-{func_run_id}.txt\"").as_str()),
+        response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .and_then(|value| value.to_str().ok())
+    );
+
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    assert_eq!(
+        "Running CLI command\nOutput: {\"status\":\"ok\"}\n",
+        String::from_utf8_lossy(&body)
+    );
+
+    // A func run without stored logs is not found
+    let response = get_logs_txt(ctx, &router, &auth_token, FuncRunId::new()).await?;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    Ok(())
+}
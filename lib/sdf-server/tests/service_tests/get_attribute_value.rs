@@ -0,0 +1,78 @@
+use axum::{
+    http::StatusCode,
+    response::IntoResponse,
+};
+use dal::{
+    ComponentId,
+    DalContext,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        attribute::value,
+        change_set,
+        component,
+    },
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use sdf_server::service::v2::component::{
+    Error,
+    get_attribute_value::attribute_value,
+};
+use serde_json::json;
+
+#[sdf_test]
+async fn gets_the_value_at_a_prop_path(ctx: &mut DalContext) -> Result<()> {
+    let component_id = component::create(ctx, "Docker Image", "docker").await?;
+    value::set(ctx, ("docker", "/domain/image"), "nginx").await?;
+    change_set::commit(ctx).await?;
+
+    let response = attribute_value(ctx, component_id, "root/domain/image").await?;
+
+    assert_eq!(
+        value::id(ctx, ("docker", "/domain/image")).await?,
+        response.attribute_value_id
+    );
+    assert_eq!(Some(json!("nginx")), response.value);
+    Ok(())
+}
+
+#[sdf_test]
+async fn paths_beneath_an_array_are_ambiguous(ctx: &mut DalContext) -> Result<()> {
+    let component_id = component::create(ctx, "Docker Image", "docker").await?;
+    value::set(
+        ctx,
+        ("docker", "/domain/ExposedPorts"),
+        json!(["80", "443"]),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    let error = attribute_value(ctx, component_id, "root/domain/ExposedPorts/ExposedPort")
+        .await
+        .expect_err("a path with one value per array element should be rejected");
+
+    assert!(matches!(error, Error::AmbiguousPropPath(_, _)));
+    assert_eq!(StatusCode::BAD_REQUEST, error.into_response().status());
+    Ok(())
+}
+
+#[sdf_test]
+async fn missing_props_and_components_are_not_found(ctx: &mut DalContext) -> Result<()> {
+    let component_id = component::create(ctx, "Docker Image", "docker").await?;
+    change_set::commit(ctx).await?;
+
+    let error = attribute_value(ctx, component_id, "root/domain/nope")
+        .await
+        .expect_err("a path to a missing prop should be rejected");
+    assert!(matches!(error, Error::PropPathNotFound(_, _)));
+    assert_eq!(StatusCode::NOT_FOUND, error.into_response().status());
+
+    let error = attribute_value(ctx, ComponentId::new(), "root/domain/image")
+        .await
+        .expect_err("a missing component should be rejected");
+    assert!(matches!(error, Error::ComponentNotFound(_)));
+    assert_eq!(StatusCode::NOT_FOUND, error.into_response().status());
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request,
+        StatusCode,
+        header,
+    },
+};
+use dal::{
+    DalContext,
+    workspace_snapshot::graph_export::GraphExport,
+};
+use dal_test::{
+    AuthToken,
+    Result,
+    helpers::{
+        change_set,
+        component,
+    },
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use tower::ServiceExt;
+
+#[sdf_test]
+async fn exports_the_change_set_graph(
+    ctx: &mut DalContext,
+    router: Router,
+    auth_token: AuthToken,
+) -> Result<()> {
+    let component_id = component::create(ctx, "swifty", "export me").await?;
+    change_set::commit(ctx).await?;
+
+    let request = Request::get(format!(
+        "/api/v2/workspaces/{}/change-sets/{}/graph/export.json",
+        ctx.workspace_pk()?,
+        ctx.change_set_id(),
+    ))
+    .header(header::AUTHORIZATION, format!("Bearer {}", auth_token.0))
+    .body(Body::empty())?;
+    let response = router.oneshot(request).await?;
+
+    assert_eq!(StatusCode::OK, response.status());
+
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    let body: serde_json::Value = serde_json::from_slice(&body)?;
+    let nodes = body["nodes"].as_array().expect("nodes should be an array");
+    assert!(nodes.contains(&json!({
+        "nodeWeightKind": "Component",
+        "id": component_id.to_string(),
+    })));
+
+    // The response is the export of the change set's graph
+    assert_eq!(
+        ctx.workspace_snapshot()?.export_graph().await?,
+        serde_json::from_value::<GraphExport>(body)?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request,
+        StatusCode,
+    },
+};
+use dal_test::{
+    Result,
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use tower::ServiceExt;
+
+#[sdf_test]
+async fn layer_db_health_reports_only_a_status(router: Router) -> Result<()> {
+    // The route is unauthenticated
+    let request = Request::get("/api/health/layer_db").body(Body::empty())?;
+    let response = router.oneshot(request).await?;
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    assert_eq!(
+        json!({ "ok": true }),
+        serde_json::from_slice::<serde_json::Value>(&body)?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request,
+        StatusCode,
+        header,
+    },
+    response::Response,
+};
+use dal::DalContext;
+use dal_test::{
+    AuthToken,
+    Result,
+    WorkspaceSignup,
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use tower::ServiceExt;
+
+async fn get(router: &Router, uri: impl AsRef<str>, auth_token: &AuthToken) -> Result<Response> {
+    let request = Request::get(uri.as_ref())
+        .header(header::AUTHORIZATION, format!("Bearer {}", auth_token.0))
+        .body(Body::empty())?;
+
+    Ok(router.clone().oneshot(request).await?)
+}
+
+async fn set_runtime_mode(router: &Router, mode: &str, auth_token: &AuthToken) -> Result<()> {
+    let request = Request::post("/api/admin/runtime_mode")
+        .header(header::AUTHORIZATION, format!("Bearer {}", auth_token.0))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "mode": mode }).to_string()))?;
+    let response = router.clone().oneshot(request).await?;
+    assert_eq!(StatusCode::OK, response.status());
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn maintenance_mode_rejects_all_but_health_and_runtime_mode(
+    ctx: &DalContext,
+    nw: &WorkspaceSignup,
+    router: Router,
+    auth_token: AuthToken,
+) -> Result<()> {
+    // Only System Initiative users may switch the runtime mode
+    ctx.txns()
+        .await?
+        .pg()
+        .execute(
+            "UPDATE users SET email = $1 WHERE pk = $2",
+            &[&"maintainer@systeminit.com", &nw.user.pk()],
+        )
+        .await?;
+    ctx.commit_no_rebase().await?;
+
+    let graph_export = format!(
+        "/api/v2/workspaces/{}/change-sets/{}/graph/export.json",
+        ctx.workspace_pk()?,
+        ctx.change_set_id(),
+    );
+    let response = get(&router, &graph_export, &auth_token).await?;
+    assert_eq!(StatusCode::OK, response.status());
+
+    set_runtime_mode(&router, "maintenance", &auth_token).await?;
+
+    let response = get(&router, &graph_export, &auth_token).await?;
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    assert_eq!(
+        Some("60"),
+        response
+            .headers()
+            .get(header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+    );
+    let response = get(&router, "/api/whoami", &auth_token).await?;
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+
+    // Health routes are still serviced
+    for uri in ["/api/", "/api/health/live"] {
+        let response = get(&router, uri, &auth_token).await?;
+        assert_eq!(StatusCode::OK, response.status(), "{uri}");
+    }
+
+    // And so is the runtime mode, so that maintenance mode can be left
+    set_runtime_mode(&router, "running", &auth_token).await?;
+
+    let response = get(&router, &graph_export, &auth_token).await?;
+    assert_eq!(StatusCode::OK, response.status());
+
+    Ok(())
+}
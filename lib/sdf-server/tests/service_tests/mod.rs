@@ -1,2 +1,11 @@
 mod change_set_apply;
 mod change_set_approval;
+mod connect_components;
+mod error_responses;
+mod func_run_logs_txt;
+mod get_attribute_value;
+mod graph_export;
+mod health;
+mod maintenance_mode;
+mod schema;
+mod workspace_rate_limit;
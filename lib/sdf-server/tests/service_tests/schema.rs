@@ -0,0 +1,63 @@
+use dal::{
+    DalContext,
+    SchemaVariant,
+    schema::variant::authoring::VariantAuthoringClient,
+};
+use dal_test::{
+    Result,
+    helpers::change_set,
+    sdf_test,
+};
+use luminork_server::service::v1::list_schemas_page;
+use pretty_assertions_sorted::assert_eq;
+
+#[sdf_test]
+async fn list_schemas_walks_the_cursor_to_completion(ctx: &mut DalContext) -> Result<()> {
+    let mut created_schema_ids = Vec::new();
+    for index in 0..5 {
+        let variant = VariantAuthoringClient::create_schema_and_variant(
+            ctx,
+            format!("paginated schema {index}"),
+            None,
+            None,
+            "test",
+            "FFFFFF",
+        )
+        .await?;
+        created_schema_ids.push(SchemaVariant::schema_id(ctx, variant.id()).await?);
+    }
+    change_set::commit(ctx).await?;
+
+    let mut seen_schema_ids = Vec::new();
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let page = list_schemas_page(ctx, 2, cursor.as_deref()).await?;
+        assert!(page.schemas.len() <= 2);
+        pages += 1;
+        seen_schema_ids.extend(page.schemas.iter().map(|schema| schema.schema_id));
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+
+    // Every schema is seen exactly once, in the same order as when listed in one page
+    let all = list_schemas_page(ctx, usize::MAX, None).await?;
+    assert_eq!(None, all.next_cursor);
+    assert_eq!(
+        all.schemas
+            .iter()
+            .map(|schema| schema.schema_id)
+            .collect::<Vec<_>>(),
+        seen_schema_ids
+    );
+    // The five schemas created here alone take three pages
+    assert!(pages >= 3);
+    assert!(
+        created_schema_ids
+            .iter()
+            .all(|schema_id| seen_schema_ids.contains(schema_id))
+    );
+    Ok(())
+}
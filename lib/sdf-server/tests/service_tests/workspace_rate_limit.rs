@@ -0,0 +1,87 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request,
+        StatusCode,
+        header,
+    },
+    response::Response,
+};
+use dal::{
+    DalContext,
+    FuncId,
+};
+use dal_test::{
+    AuthToken,
+    Result,
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use tower::ServiceExt;
+
+// The request is rejected by the handler, which still counts against the workspace's budget
+async fn execute_func(
+    ctx: &DalContext,
+    router: Router,
+    auth_token: &AuthToken,
+) -> Result<Response> {
+    let request = Request::post(format!(
+        "/api/v2/workspaces/{}/change-sets/{}/funcs/{}/execute",
+        ctx.workspace_pk()?,
+        ctx.change_set_id(),
+        FuncId::new(),
+    ))
+    .header(header::AUTHORIZATION, format!("Bearer {}", auth_token.0))
+    .header(header::CONTENT_TYPE, "application/json")
+    .body(Body::from("{}"))?;
+
+    Ok(router.oneshot(request).await?)
+}
+
+fn header_value(response: &Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .map(|value| value.to_str().expect("header is ascii").to_owned())
+}
+
+#[sdf_test(rate_limit_workspaces)]
+async fn requests_over_the_limit_are_rejected(
+    ctx: &DalContext,
+    router: Router,
+    auth_token: AuthToken,
+) -> Result<()> {
+    let response = execute_func(ctx, router.clone(), &auth_token).await?;
+    assert_ne!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!(
+        Some("1".to_string()),
+        header_value(&response, "x-ratelimit-limit")
+    );
+    assert_eq!(None, header_value(&response, "x-ratelimit-remaining"));
+
+    let response = execute_func(ctx, router, &auth_token).await?;
+    assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    assert_eq!(
+        Some("1".to_string()),
+        header_value(&response, "x-ratelimit-limit")
+    );
+    assert!(header_value(&response, "retry-after").is_some());
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn requests_are_not_limited_by_default(
+    ctx: &DalContext,
+    router: Router,
+    auth_token: AuthToken,
+) -> Result<()> {
+    for _ in 0..3 {
+        let response = execute_func(ctx, router.clone(), &auth_token).await?;
+        assert_ne!(StatusCode::TOO_MANY_REQUESTS, response.status());
+        assert_eq!(None, header_value(&response, "x-ratelimit-limit"));
+    }
+
+    Ok(())
+}
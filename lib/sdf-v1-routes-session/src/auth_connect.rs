@@ -291,7 +291,7 @@ pub async fn auth_connect(
         res_body.user,
         res_body.workspace,
         state.create_workspace_permissions(),
-        state.create_workspace_allowlist(),
+        &state.create_workspace_allowlist().await,
         state.spicedb_client_clone().as_mut(),
     )
     .await?;
@@ -344,7 +344,7 @@ pub async fn auth_reconnect(
         auth_response_body.user,
         auth_response_body.workspace,
         state.create_workspace_permissions(),
-        state.create_workspace_allowlist(),
+        &state.create_workspace_allowlist().await,
         state.spicedb_client_clone().as_mut(),
     )
     .await?;
@@ -1,6 +1,10 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
 use axum::{
+    Extension,
     extract::{
         State,
         WebSocketUpgrade,
@@ -34,7 +38,10 @@ use telemetry_utils::{
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::WsError;
+use crate::{
+    WsError,
+    WsIdleTimeout,
+};
 
 pub mod proto;
 
@@ -49,7 +56,10 @@ pub async fn bifrost_handler(
     ComputeExecutor(compute_executor): ComputeExecutor,
     State(shutdown_token): State<CancellationToken>,
     State(channel_multiplexer_clients): State<NatsMultiplexerClients>,
+    idle_timeout: Option<Extension<WsIdleTimeout>>,
 ) -> Result<impl IntoResponse, WsError> {
+    let idle_timeout = idle_timeout.map(|Extension(WsIdleTimeout(duration))| duration);
+
     Ok(wsu.on_upgrade(move |socket| {
         run_bifrost_proto(
             socket,
@@ -59,6 +69,7 @@ pub async fn bifrost_handler(
             channel_multiplexer_clients.edda_updates,
             compute_executor,
             shutdown_token,
+            idle_timeout,
         )
     }))
 }
@@ -71,6 +82,7 @@ async fn run_bifrost_proto(
     bifrost_multiplexer_client: EddaUpdatesMultiplexerClient,
     compute_executor: DedicatedExecutor,
     shutdown_token: CancellationToken,
+    idle_timeout: Option<Duration>,
 ) {
     monotonic!(sdf_bifrost_connections_opened = 1);
     counter!(sdf_bifrost_active_connections = 1);
@@ -81,6 +93,7 @@ async fn run_bifrost_proto(
         compute_executor,
         workspace_pk,
         shutdown_token,
+        idle_timeout,
     )
     .start(bifrost_multiplexer_client)
     .await
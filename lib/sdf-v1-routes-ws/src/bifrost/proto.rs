@@ -9,7 +9,10 @@ use std::{
     },
     string::FromUtf8Error,
     sync::Arc,
-    time::Instant,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use axum::extract::ws::{
@@ -71,6 +74,8 @@ use tokio_stream::{
 use tokio_tungstenite::tungstenite;
 use tokio_util::sync::CancellationToken;
 
+use crate::idle_timeout::IdleTimeout;
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum BifrostError {
@@ -82,6 +87,8 @@ pub enum BifrostError {
     ComputeExecutor(#[from] DedicatedExecutorError),
     #[error("message decompress error: {0}")]
     Decompress(String),
+    #[error("decompressed message is larger than the limit of {limit} bytes")]
+    DecompressedTooLarge { limit: usize },
     #[error("edda updates multiplexer client error: {0}")]
     EddaUpdatesMultiplexerClient(#[source] Box<dyn error::Error>),
     #[error("frigg reads task recv error: channel is empty and closed")]
@@ -104,6 +111,17 @@ pub enum BifrostError {
     WsSendIo(#[source] axum::Error),
 }
 
+impl BifrostError {
+    /// Returns `true` if the error is a message too large to be forwarded.
+    fn is_message_too_large(&self) -> bool {
+        matches!(
+            self,
+            Self::ComputeExecutor(DedicatedExecutorError::TaskInputTooLarge { .. })
+                | Self::DecompressedTooLarge { .. }
+        )
+    }
+}
+
 type Result<T> = std::result::Result<T, BifrostError>;
 
 type Error = BifrostError;
@@ -144,6 +162,7 @@ pub fn run(
     compute_executor: DedicatedExecutor,
     workspace_id: WorkspacePk,
     token: CancellationToken,
+    idle_timeout: Option<Duration>,
 ) -> Bifrost {
     Bifrost {
         metadata,
@@ -151,6 +170,7 @@ pub fn run(
         compute_executor,
         workspace_id,
         token,
+        idle_timeout,
     }
 }
 
@@ -161,6 +181,7 @@ pub struct Bifrost {
     compute_executor: DedicatedExecutor,
     workspace_id: WorkspacePk,
     token: CancellationToken,
+    idle_timeout: Option<Duration>,
 }
 
 impl Bifrost {
@@ -212,6 +233,7 @@ impl Bifrost {
             responses_rx,
             handle,
             token: self.token,
+            idle: IdleTimeout::new(self.idle_timeout),
         })
     }
 }
@@ -226,6 +248,7 @@ pub struct BifrostStarted {
     responses_rx: mpsc::Receiver<WsFrontEndObjectResponse>,
     handle: BifrostFriggReadsTaskHandle,
     token: CancellationToken,
+    idle: IdleTimeout,
 }
 
 impl Debug for BifrostStarted {
@@ -236,6 +259,7 @@ impl Debug for BifrostStarted {
             .field("responses_rx", &self.responses_rx)
             .field("handle", &self.handle)
             .field("token", &self.token)
+            .field("idle", &self.idle)
             .finish_non_exhaustive()
     }
 }
@@ -277,9 +301,38 @@ impl BifrostStarted {
                         handle: self.handle,
                     });
                 }
+                // No message from the web socket client within the idle timeout
+                _ = self.idle.expired() => {
+                    monotonic!(sdf_bifrost_select_branch = 1, branch = "idle_timeout");
+                    debug!("web socket has been idle past its timeout, closing");
+
+                    let close_frame = ws::CloseFrame {
+                        // Indicates a normal closure, leaving the client free to reconnect the
+                        // next time it needs updates
+                        code: ws::close_code::NORMAL,
+                        reason: "idle timeout".into(),
+                    };
+                    if let Err(err) = ws_client.send(ws::Message::Close(Some(close_frame))).await {
+                        warn!(
+                            error = ?err,
+                            "error while closing idle websocket connection",
+                        );
+                    }
+
+                    monotonic!(sdf_bifrost_connections_closed = 1, reason = "idle_timeout");
+                    counter!(sdf_bifrost_active_connections = -1);
+
+                    return Ok(BifrostClosing {
+                        ws_is_closed: true,
+                        handle: self.handle,
+                    });
+                }
                 // Maybe a message from web socket client
                 maybe_ws_client_message = ws_client.recv() => {
                     monotonic!(sdf_bifrost_select_branch = 1, branch = "ws_client_message");
+                    if let Some(Ok(_)) = maybe_ws_client_message {
+                        self.idle.reset();
+                    }
 
                     match maybe_ws_client_message {
                         // Received web socket text message
@@ -416,6 +469,39 @@ impl BifrostStarted {
                             let MultiplexerRequestPayload { nats_message, otel_ctx } = payload_with_nats_message;
                             let ws_message = match self.build_ws_message(nats_message).await {
                                 Ok(ws_message) => ws_message,
+                                // Skipping the message would leave the client out of date, so
+                                // close the connection and let the client reload instead
+                                Err(err) if err.is_message_too_large() => {
+                                    warn!(
+                                        si.error.message = ?err,
+                                        "nats message too large to forward to web socket; closing",
+                                    );
+
+                                    let close_frame = ws::CloseFrame {
+                                        code: ws::close_code::SIZE,
+                                        reason: "message too large".into(),
+                                    };
+                                    if let Err(err) = ws_client
+                                        .send(ws::Message::Close(Some(close_frame)))
+                                        .await
+                                    {
+                                        warn!(
+                                            error = ?err,
+                                            "error while closing websocket after a message too large",
+                                        );
+                                    }
+
+                                    monotonic!(
+                                        sdf_bifrost_connections_closed = 1,
+                                        reason = "message_too_large"
+                                    );
+                                    counter!(sdf_bifrost_active_connections = -1);
+
+                                    return Ok(BifrostClosing {
+                                        ws_is_closed: true,
+                                        handle: self.handle,
+                                    });
+                                }
                                 Err(err) => {
                                     warn!(
                                         si.error.message = ?err,
@@ -510,14 +596,14 @@ impl BifrostStarted {
                 span.record("bytes.size.compressed", nats_message.payload().len());
 
                 let decompress_start = Instant::now();
+                let limit = self.compute_executor.max_task_input_size();
                 let result = self
                     .compute_executor
-                    .spawn(async move {
+                    .spawn_with_input_size(nats_message.payload().len(), async move {
                         let compressed = nats_message.into_inner().payload;
-                        inflate::decompress_to_vec(&compressed)
+                        decompress_with_limit(&compressed, false, limit)
                     })
-                    .await?
-                    .map_err(|decompress_err| Error::Decompress(decompress_err.to_string()))?;
+                    .await??;
 
                 let decompress_duration = decompress_start.elapsed();
                 histogram!(
@@ -531,14 +617,14 @@ impl BifrostStarted {
                 span.record("bytes.size.compressed", nats_message.payload().len());
 
                 let decompress_start = std::time::Instant::now();
+                let limit = self.compute_executor.max_task_input_size();
                 let result = self
                     .compute_executor
-                    .spawn(async move {
+                    .spawn_with_input_size(nats_message.payload().len(), async move {
                         let compressed = nats_message.into_inner().payload;
-                        inflate::decompress_to_vec_zlib(&compressed)
+                        decompress_with_limit(&compressed, true, limit)
                     })
-                    .await?
-                    .map_err(|decompress_err| Error::Decompress(decompress_err.to_string()))?;
+                    .await??;
 
                 let decompress_duration = decompress_start.elapsed();
                 histogram!(
@@ -825,6 +911,22 @@ mod task {
     }
 }
 
+/// Decompresses a raw DEFLATE (or, if `zlib` is set, ZLIB) payload, giving up rather than growing
+/// the output past `limit` bytes so that a small compressed message cannot inflate into a huge one.
+fn decompress_with_limit(compressed: &[u8], zlib: bool, limit: Option<usize>) -> Result<Vec<u8>> {
+    let max_size = limit.unwrap_or(usize::MAX);
+    let result = if zlib {
+        inflate::decompress_to_vec_zlib_with_limit(compressed, max_size)
+    } else {
+        inflate::decompress_to_vec_with_limit(compressed, max_size)
+    };
+
+    result.map_err(|err| match (err.status, limit) {
+        (inflate::TINFLStatus::HasMoreOutput, Some(limit)) => Error::DecompressedTooLarge { limit },
+        _ => Error::Decompress(err.to_string()),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -907,4 +1009,58 @@ mod tests {
             // panic!("let's see the serialized!");
         }
     }
+
+    mod decompress {
+        use miniz_oxide::deflate;
+
+        use super::*;
+
+        fn payload() -> Vec<u8> {
+            "bifrost".repeat(1024).into_bytes()
+        }
+
+        #[test]
+        fn decompresses_within_limit() {
+            let payload = payload();
+
+            let deflated = deflate::compress_to_vec(&payload, 6);
+            let inflated = decompress_with_limit(&deflated, false, Some(payload.len()))
+                .expect("failed to decompress");
+            assert_eq!(payload, inflated);
+
+            let zlibbed = deflate::compress_to_vec_zlib(&payload, 6);
+            let inflated =
+                decompress_with_limit(&zlibbed, true, None).expect("failed to decompress");
+            assert_eq!(payload, inflated);
+        }
+
+        #[test]
+        fn rejects_output_over_limit() {
+            let payload = payload();
+            let limit = payload.len() - 1;
+
+            // The compressed payloads are well under the limit, only their output is over it
+            let deflated = deflate::compress_to_vec(&payload, 6);
+            assert!(deflated.len() < limit);
+            assert!(matches!(
+                decompress_with_limit(&deflated, false, Some(limit)),
+                Err(BifrostError::DecompressedTooLarge { limit: l }) if l == limit
+            ));
+
+            let zlibbed = deflate::compress_to_vec_zlib(&payload, 6);
+            assert!(zlibbed.len() < limit);
+            assert!(matches!(
+                decompress_with_limit(&zlibbed, true, Some(limit)),
+                Err(BifrostError::DecompressedTooLarge { limit: l }) if l == limit
+            ));
+        }
+
+        #[test]
+        fn rejects_corrupt_payload() {
+            assert!(matches!(
+                decompress_with_limit(b"not compressed", true, Some(1024)),
+                Err(BifrostError::Decompress(_))
+            ));
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use std::{
     collections::hash_map::Entry,
     sync::Arc,
+    time::Duration,
 };
 
 use axum::{
+    Extension,
     extract::{
         Query,
         State,
@@ -67,7 +69,11 @@ use y::{
 };
 use y_sync::net::BroadcastGroup;
 
-use crate::WsError;
+use crate::{
+    WsError,
+    WsIdleTimeout,
+    idle_timeout::IdleTimeout,
+};
 
 pub mod y;
 
@@ -115,7 +121,9 @@ pub async fn crdt(
     State(shutdown_token): State<CancellationToken>,
     State(broadcast_groups): State<BroadcastGroups>,
     State(nats_multiplexer_clients): State<NatsMultiplexerClients>,
+    idle_timeout: Option<Extension<WsIdleTimeout>>,
 ) -> Result<impl IntoResponse, WsError> {
+    let idle_timeout = idle_timeout.map(|Extension(WsIdleTimeout(duration))| duration);
     let workspace_pk = auth.workspace_id;
     let channel_name = Subject::from(format!("crdt.{workspace_pk}.{id}"));
 
@@ -139,6 +147,7 @@ pub async fn crdt(
             workspace_pk,
             id,
             shutdown_token,
+            idle_timeout,
         )
         .await
     }))
@@ -156,6 +165,7 @@ pub async fn crdt_handle<W, R>(
     workspace_pk: WorkspacePk,
     id: String,
     token: CancellationToken,
+    idle_timeout: Option<Duration>,
 ) where
     W: Sink<Message> + Unpin + Send + 'static,
     R: Stream<Item = Result<Message, axum::Error>> + Unpin + Send + 'static,
@@ -163,23 +173,36 @@ pub async fn crdt_handle<W, R>(
 {
     let tracker = TaskTracker::new();
 
+    // Cancelled on shutdown, or when the client has been idle past its timeout
+    let session_token = token.child_token();
+
     let mut ws_receiver_stream = BroadcastStream::new(ws_receiver);
 
     // Spawn "writes-to-client" task which consumes from nats
-    let to_client_token = token.clone();
+    let to_client_token = session_token.clone();
+    let shutdown_token = token.clone();
     tracker.spawn(async move {
         loop {
             tokio::select! {
                 _ = to_client_token.cancelled() => {
                     trace!("web socket writes-to-client has received cancellation");
-                    let close_frame = ws::CloseFrame {
-                        // Indicates that an endpoint is "going away", such as a server going
-                        // down
-                        code: ws::close_code::AWAY,
-                        // NOTE: reason string must be less than *123* bytes
-                        //
-                        // See: https://en.wikipedia.org/wiki/WebSocket
-                        reason: "endpoint received graceful shutdown".into(),
+                    let close_frame = if shutdown_token.is_cancelled() {
+                        ws::CloseFrame {
+                            // Indicates that an endpoint is "going away", such as a server going
+                            // down
+                            code: ws::close_code::AWAY,
+                            // NOTE: reason string must be less than *123* bytes
+                            //
+                            // See: https://en.wikipedia.org/wiki/WebSocket
+                            reason: "endpoint received graceful shutdown".into(),
+                        }
+                    } else {
+                        ws::CloseFrame {
+                            // Indicates a normal closure, leaving the client free to reconnect
+                            // the next time it needs updates
+                            code: ws::close_code::NORMAL,
+                            reason: "idle timeout".into(),
+                        }
                     };
                     // Close connection with specific close frame that indicates the server
                     // is going away
@@ -212,19 +235,26 @@ pub async fn crdt_handle<W, R>(
     });
 
     // Spawn "reads-from-client" task which publishes to nats
-    let from_client_token = token.clone();
+    let from_client_token = session_token.clone();
     let from_client_nats = nats.clone();
     let from_client_subject = subject.clone();
     tracker.spawn(async move {
+        let mut idle = IdleTimeout::new(idle_timeout);
         loop {
             tokio::select! {
                 _ = from_client_token.cancelled() => {
                     trace!("web socket reads-from-client has received cancellation");
                     break;
                 }
+                _ = idle.expired() => {
+                    debug!("web socket has been idle past its timeout, closing");
+                    from_client_token.cancel();
+                    break;
+                }
                 maybe_message_result = stream.next() => {
                     match maybe_message_result {
                         Some(Ok(msg)) => {
+                            idle.reset();
                             if let Message::Binary(vec) = msg {
                                 if let Err(err) = from_client_nats
                                     .publish(from_client_subject.clone(), vec.into())
@@ -265,7 +295,7 @@ pub async fn crdt_handle<W, R>(
 
     let sub = bcast.subscribe(sink, stream);
     tokio::select! {
-        _ = token.cancelled() => {
+        _ = session_token.cancelled() => {
             trace!("web socket has received cancellation");
         }
         result = sub.completed() => {
@@ -0,0 +1,98 @@
+//! Closing WebSocket sessions which have had no client activity for a while.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// The duration after which a WebSocket session with no client activity is closed.
+///
+/// When this is not present as a request extension, sessions are never closed for inactivity.
+#[derive(Clone, Copy, Debug)]
+pub struct WsIdleTimeout(pub Duration);
+
+/// Tracks when a session was last active and when it should be considered idle.
+#[derive(Debug)]
+pub(crate) struct IdleTimeout {
+    duration: Option<Duration>,
+    deadline: Instant,
+}
+
+impl IdleTimeout {
+    pub(crate) fn new(duration: Option<Duration>) -> Self {
+        let mut idle = Self {
+            duration,
+            deadline: Instant::now(),
+        };
+        idle.reset();
+        idle
+    }
+
+    /// Pushes the deadline back by the full timeout, starting now.
+    pub(crate) fn reset(&mut self) {
+        if let Some(duration) = self.duration {
+            self.deadline = Instant::now() + duration;
+        }
+    }
+
+    /// Completes once the deadline passes, or never if no timeout is configured.
+    pub(crate) async fn expired(&self) {
+        match self.duration {
+            Some(_) => tokio::time::sleep_until(self.deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::{
+        Instant,
+        timeout,
+    };
+
+    use super::IdleTimeout;
+
+    #[tokio::test]
+    async fn idle_session_is_closed_after_timeout() {
+        tokio::time::pause();
+        let start = Instant::now();
+        let idle = IdleTimeout::new(Some(Duration::from_secs(60)));
+
+        timeout(Duration::from_secs(120), idle.expired())
+            .await
+            .expect("idle session should have timed out");
+        assert_eq!(Duration::from_secs(60), start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn active_session_is_not_closed() {
+        tokio::time::pause();
+        let mut idle = IdleTimeout::new(Some(Duration::from_secs(60)));
+
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            idle.reset();
+        }
+
+        assert!(
+            timeout(Duration::from_secs(59), idle.expired())
+                .await
+                .is_err(),
+            "active session should not have timed out"
+        );
+    }
+
+    #[tokio::test]
+    async fn no_timeout_never_expires() {
+        tokio::time::pause();
+        let idle = IdleTimeout::new(None);
+
+        assert!(
+            timeout(Duration::from_secs(3600), idle.expired())
+                .await
+                .is_err()
+        );
+    }
+}
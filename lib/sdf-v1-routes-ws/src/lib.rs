@@ -26,8 +26,11 @@ use tokio::sync::TryLockError;
 
 pub mod bifrost;
 pub mod crdt;
+mod idle_timeout;
 pub mod workspace_updates;
 
+pub use idle_timeout::WsIdleTimeout;
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum WsError {
@@ -1,6 +1,10 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
 use axum::{
+    Extension,
     extract::{
         State,
         WebSocketUpgrade,
@@ -24,7 +28,10 @@ use telemetry::prelude::*;
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 
-use crate::WsError;
+use crate::{
+    WsError,
+    WsIdleTimeout,
+};
 
 #[allow(clippy::unused_async)]
 pub async fn workspace_updates(
@@ -35,7 +42,10 @@ pub async fn workspace_updates(
     auth: WorkspaceAuthorization,
     State(shutdown_token): State<CancellationToken>,
     State(channel_multiplexer_clients): State<NatsMultiplexerClients>,
+    idle_timeout: Option<Extension<WsIdleTimeout>>,
 ) -> Result<impl IntoResponse, WsError> {
+    let idle_timeout = idle_timeout.map(|Extension(WsIdleTimeout(duration))| duration);
+
     Ok(wsu.on_upgrade(move |socket| {
         run_workspace_updates_proto(
             socket,
@@ -43,6 +53,7 @@ pub async fn workspace_updates(
             auth.workspace_id,
             channel_multiplexer_clients.ws,
             shutdown_token,
+            idle_timeout,
         )
     }))
 }
@@ -53,8 +64,9 @@ async fn run_workspace_updates_proto(
     workspace_pk: WorkspacePk,
     ws_multiplexer_client: Arc<Mutex<MultiplexerClient>>,
     shutdown_token: CancellationToken,
+    idle_timeout: Option<Duration>,
 ) {
-    let proto = match workspace_updates::run(nats, workspace_pk, shutdown_token)
+    let proto = match workspace_updates::run(nats, workspace_pk, shutdown_token, idle_timeout)
         .start(ws_multiplexer_client)
         .await
     {
@@ -85,6 +97,7 @@ mod workspace_updates {
     use std::{
         error::Error,
         sync::Arc,
+        time::Duration,
     };
 
     use axum::extract::ws::{
@@ -127,6 +140,8 @@ mod workspace_updates {
     use tokio_tungstenite::tungstenite;
     use tokio_util::sync::CancellationToken;
 
+    use crate::idle_timeout::IdleTimeout;
+
     #[remain::sorted]
     #[derive(Serialize, Deserialize, Debug, Clone)]
     #[serde(tag = "kind", content = "data")]
@@ -159,11 +174,13 @@ mod workspace_updates {
         nats: NatsClient,
         workspace_pk: WorkspacePk,
         token: CancellationToken,
+        idle_timeout: Option<Duration>,
     ) -> WorkspaceUpdates {
         WorkspaceUpdates {
             nats,
             workspace_pk,
             token,
+            idle_timeout,
         }
     }
 
@@ -197,6 +214,7 @@ mod workspace_updates {
         nats: NatsClient,
         workspace_pk: WorkspacePk,
         token: CancellationToken,
+        idle_timeout: Option<Duration>,
     }
 
     impl WorkspaceUpdates {
@@ -212,6 +230,7 @@ mod workspace_updates {
                 workspace_pk: self.workspace_pk,
                 receiver,
                 token: self.token,
+                idle: IdleTimeout::new(self.idle_timeout),
             })
         }
     }
@@ -222,6 +241,7 @@ mod workspace_updates {
         nats: NatsClient,
         receiver: broadcast::Receiver<MultiplexerRequestPayload>,
         token: CancellationToken,
+        idle: IdleTimeout,
     }
 
     impl WorkspaceUpdatesStarted {
@@ -253,7 +273,26 @@ mod workspace_updates {
                         }
                         return Ok(WorkspaceUpdatesClosing { ws_is_closed: true });
                     }
+                    _ = self.idle.expired() => {
+                        debug!("web socket has been idle past its timeout, closing");
+                        let close_frame = ws::CloseFrame {
+                            // Indicates a normal closure, leaving the client free to reconnect
+                            // the next time it needs updates
+                            code: ws::close_code::NORMAL,
+                            reason: "idle timeout".into(),
+                        };
+                        if let Err(err) = ws.send(ws::Message::Close(Some(close_frame))).await {
+                            warn!(
+                                error = ?err,
+                                "error while closing idle websocket connection",
+                            );
+                        }
+                        return Ok(WorkspaceUpdatesClosing { ws_is_closed: true });
+                    }
                     msg = ws.recv() => {
+                        if let Some(Ok(_)) = msg {
+                            self.idle.reset();
+                        }
                         match msg {
                             Some(Ok(message)) => if let ws::Message::Text(msg) = message {
                                 let event: WebsocketEventRequest = match serde_json::from_str(&msg) {
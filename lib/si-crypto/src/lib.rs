@@ -22,6 +22,7 @@ mod veritech;
 
 pub use sensitive_strings::SensitiveStrings;
 pub use symmetric::{
+    SYMMETRIC_TAG_LEN,
     SymmetricCryptoError,
     SymmetricCryptoResult,
     SymmetricCryptoService,
@@ -29,6 +30,7 @@ pub use symmetric::{
     SymmetricCryptoServiceConfigFile,
     SymmetricKey,
     SymmetricNonce,
+    SymmetricTag,
 };
 pub use veritech::{
     config::VeritechCryptoConfig,
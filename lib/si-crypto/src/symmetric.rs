@@ -21,8 +21,17 @@ use si_std::{
     CanonicalFile,
     CanonicalFileError,
 };
-use sodiumoxide::crypto::secretbox;
-pub use sodiumoxide::crypto::secretbox::Nonce as SymmetricNonce;
+use sodiumoxide::crypto::{
+    auth::hmacsha256,
+    secretbox,
+};
+pub use sodiumoxide::crypto::{
+    auth::hmacsha256::{
+        TAGBYTES as SYMMETRIC_TAG_LEN,
+        Tag as SymmetricTag,
+    },
+    secretbox::Nonce as SymmetricNonce,
+};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -216,6 +225,27 @@ impl SymmetricCryptoService {
         secretbox::open(ciphertext, nonce, &key.0)
             .map_err(|_| SymmetricCryptoError::DecryptionFailed)
     }
+
+    #[allow(clippy::missing_panics_doc)]
+    /// Returns an HMAC-SHA-256 [`SymmetricTag`] of a message, keyed by the active
+    /// [`SymmetricKey`], so that the message can be handed out and checked for tampering when it
+    /// is handed back.
+    pub fn authenticate(&self, message: &[u8]) -> SymmetricTag {
+        let key = self
+            .keys
+            .get(self.active_key_hash.as_ref())
+            .expect("active_key value not present in keys hashmap; this is bug!");
+
+        hmacsha256::authenticate(message, &key.hmac_key())
+    }
+
+    /// Returns whether a [`SymmetricTag`] was produced by [`Self::authenticate`] for the message
+    /// with any of the loaded [`SymmetricKey`]s, so that tags outlive a key rotation.
+    pub fn verify(&self, tag: &SymmetricTag, message: &[u8]) -> bool {
+        self.keys
+            .values()
+            .any(|key| hmacsha256::verify(tag, message, &key.hmac_key()))
+    }
 }
 
 /// A symmetric encryption key (i.e. a key which can encrypt *and* decrypt data).
@@ -223,6 +253,10 @@ impl SymmetricCryptoService {
 pub struct SymmetricKey(secretbox::Key);
 
 impl SymmetricKey {
+    fn hmac_key(&self) -> hmacsha256::Key {
+        hmacsha256::Key(self.0.0)
+    }
+
     /// Save a simple key to a file on the given path.
     ///
     /// # Errors
@@ -331,6 +365,20 @@ mod tests {
         assert_eq!(message.as_slice(), decrypted);
     }
 
+    #[test]
+    fn authenticate_verify_round_trip() {
+        let service = SymmetricCryptoService::new(SymmetricCryptoService::generate_key(), vec![]);
+        let other_service =
+            SymmetricCryptoService::new(SymmetricCryptoService::generate_key(), vec![]);
+
+        let message = b"I'm gonna make him an offer he can't refuse.";
+        let tag = service.authenticate(message);
+
+        assert!(service.verify(&tag, message));
+        assert!(!service.verify(&tag, b"I'm gonna make him an offer he can refuse."));
+        assert!(!other_service.verify(&tag, message));
+    }
+
     #[test]
     fn key_rotation() {
         let old_key = SymmetricCryptoService::generate_key();
@@ -14,10 +14,14 @@ use std::{
         self,
         Debug,
     },
+    future::Future,
     net::ToSocketAddrs,
     rc::Rc,
     sync::Arc,
-    time::Duration,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use bytes::Buf;
@@ -186,6 +190,9 @@ pub struct PgPoolConfig {
     /// If set to `None`, the eviction task won't be started
     pub pool_lifetime_check_interval_secs: Option<u64>,
     pub recycling_method: Option<RecyclingMethodConfig>,
+    /// Queries in a [`PgSharedTransaction`] taking longer than this are logged as slow. If set to
+    /// `None`, no queries are flagged.
+    pub slow_query_threshold_ms: Option<u64>,
 }
 
 impl Default for PgPoolConfig {
@@ -208,6 +215,7 @@ impl Default for PgPoolConfig {
             pool_idle_connection_lifetime_secs: 6 * 3600,   // 6 hours
             pool_lifetime_check_interval_secs: None,
             recycling_method: None,
+            slow_query_threshold_ms: None,
         }
     }
 }
@@ -239,6 +247,19 @@ pub struct PgPool {
     metadata: Arc<ConnectionMetadata>,
 }
 
+/// A snapshot of how the connections in a [`PgPool`] are being used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PgPoolStatus {
+    /// The most connections the pool will hold.
+    pub max_size: usize,
+    /// The connections currently held by the pool.
+    pub size: usize,
+    /// The connections which are idle and ready to be handed out.
+    pub available: usize,
+    /// The tasks waiting for a connection to become available.
+    pub waiting: usize,
+}
+
 impl std::fmt::Debug for PgPool {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PgPool")
@@ -257,6 +278,41 @@ struct ConnectionMetadata {
     net_peer_ip: String,
     net_peer_port: u16,
     net_transport: &'static str,
+    slow_query_threshold: SlowQueryThreshold,
+}
+
+/// Flags queries which take longer than a configured threshold.
+#[derive(Clone, Copy, Debug, Default)]
+struct SlowQueryThreshold(Option<Duration>);
+
+impl SlowQueryThreshold {
+    /// Runs the query, flagging it if it exceeds the threshold.
+    async fn observe<F: Future>(self, statement: &str, query: F) -> F::Output {
+        if self.0.is_none() {
+            return query.await;
+        }
+
+        let started_at = Instant::now();
+        let output = query.await;
+        self.check(statement, started_at.elapsed());
+        output
+    }
+
+    /// Logs a warning if `elapsed` exceeds the threshold, returning whether it did.
+    fn check(self, statement: &str, elapsed: Duration) -> bool {
+        match self.0 {
+            Some(threshold) if elapsed > threshold => {
+                warn!(
+                    si.query = %statement,
+                    db.query.duration_ms = elapsed.as_millis(),
+                    db.query.slow_threshold_ms = threshold.as_millis(),
+                    "slow query exceeded threshold",
+                );
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl PgPool {
@@ -338,6 +394,9 @@ impl PgPool {
             net_peer_ip,
             net_peer_port: settings.port,
             net_transport: "ip_tcp",
+            slow_query_threshold: SlowQueryThreshold(
+                settings.slow_query_threshold_ms.map(Duration::from_millis),
+            ),
         };
 
         span.record("db.system", metadata.db_system);
@@ -453,6 +512,17 @@ impl PgPool {
         &self.metadata.db_name
     }
 
+    /// Gets the current usage of the connections in the pool.
+    pub fn status(&self) -> PgPoolStatus {
+        let status = self.pool.status();
+        PgPoolStatus {
+            max_size: status.max_size,
+            size: status.size,
+            available: status.available,
+            waiting: status.waiting,
+        }
+    }
+
     /// Retrieve object from pool or wait for one to become available.
     #[instrument(
         name = "pg_pool.get",
@@ -2542,7 +2612,12 @@ impl PgSharedTransaction {
         params: &[&(dyn ToSql + Sync)],
     ) -> Result<Vec<PgRow>, PgError> {
         match self.inner.lock().await.borrow_txn().as_ref() {
-            Some(txn) => txn.query(statement, params).await.inspect_err(
+            Some(txn) => self
+                .metadata
+                .slow_query_threshold
+                .observe(statement, txn.query(statement, params))
+                .await
+                .inspect_err(
                 |err| error!(si.error.message = ?err, error = ?err, si.query=?statement, "error executing query in PG shared txn"),
             ),
             None => {
@@ -2573,7 +2648,12 @@ impl PgSharedTransaction {
         params: &[&(dyn ToSql + Sync)],
     ) -> Result<PgRow, PgError> {
         match self.inner.lock().await.borrow_txn().as_ref() {
-            Some(txn) => txn.query_one(statement, params).await.inspect_err(
+            Some(txn) => self
+                .metadata
+                .slow_query_threshold
+                .observe(statement, txn.query_one(statement, params))
+                .await
+                .inspect_err(
                 |err| error!(si.error.message = ?err, error = ?err, si.query=?statement, "error executing query_one in PG shared txn"),
             ),
             None => {
@@ -2604,7 +2684,12 @@ impl PgSharedTransaction {
         params: &[&(dyn ToSql + Sync)],
     ) -> Result<Option<PgRow>, PgError> {
         match self.inner.lock().await.borrow_txn().as_ref() {
-            Some(txn) => txn.query_opt(statement, params).await.inspect_err(
+            Some(txn) => self
+                .metadata
+                .slow_query_threshold
+                .observe(statement, txn.query_opt(statement, params))
+                .await
+                .inspect_err(
                 |err| error!(si.error.message = ?err, error = ?err, si.query=?statement, "error executing query_opt in PG shared txn"),
             ),
             None => {
@@ -2635,7 +2720,12 @@ impl PgSharedTransaction {
         params: &[&(dyn ToSql + Sync)],
     ) -> Result<(), PgError> {
         match self.inner.lock().await.borrow_txn().as_ref() {
-            Some(txn) => match txn.query_opt(statement, params).await? {
+            Some(txn) => match self
+                .metadata
+                .slow_query_threshold
+                .observe(statement, txn.query_opt(statement, params))
+                .await?
+            {
                 None => Ok(()),
                 Some(row) => Err(PgError::UnexpectedRow(Box::new(row))),
             },
@@ -2703,7 +2793,12 @@ impl PgSharedTransaction {
         params: &[&(dyn ToSql + Sync)],
     ) -> Result<u64, PgError> {
         match self.inner.lock().await.borrow_txn().as_ref() {
-            Some(txn) => txn.execute(statement, params).await.inspect_err(
+            Some(txn) => self
+                .metadata
+                .slow_query_threshold
+                .observe(statement, txn.execute(statement, params))
+                .await
+                .inspect_err(
                 |err| error!(si.error.message = ?err, error = ?err, si.query=?statement, "error executing in PG shared txn"),
             ),
             None => {
@@ -3066,3 +3161,15 @@ async fn evict_old_and_idle_pgpool_connections(
         gauge!(db.pool.connection.max_idle_seconds = oldest_used_duration.as_secs_f64());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_query_does_not_trigger_warning() {
+        let threshold = SlowQueryThreshold(Some(Duration::from_millis(10)));
+
+        assert!(!threshold.check("SELECT 1", Duration::from_millis(1)));
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    env,
+    path::Path,
+};
+
+use buck2_resources::Buck2Resources;
+use si_data_pg::PgPoolConfig;
+use si_tls::CertificateSource;
+
+mod slow_query;
+
+const DEFAULT_TEST_PG_DBNAME: &str = "si_test";
+const DEFAULT_TEST_PG_USER: &str = "si_test";
+const DEFAULT_TEST_PG_PORT_STR: &str = "6432";
+
+const ENV_VAR_PG_HOSTNAME: &str = "SI_TEST_PG_HOSTNAME";
+const ENV_VAR_PG_DBNAME: &str = "SI_TEST_PG_DBNAME";
+const ENV_VAR_PG_USER: &str = "SI_TEST_PG_USER";
+const ENV_VAR_PG_PORT: &str = "SI_TEST_PG_PORT";
+
+#[allow(clippy::disallowed_methods)] // Environment variables are used exclusively in test
+pub fn test_pg_pool_config(slow_query_threshold_ms: Option<u64>) -> PgPoolConfig {
+    let mut pg = PgPoolConfig {
+        application_name: "si-data-pg-tests".into(),
+        certificate: Some(CertificateSource::Path(
+            detect_and_configure_development()
+                .try_into()
+                .expect("should get a certificate"),
+        )),
+        slow_query_threshold_ms,
+        ..Default::default()
+    };
+    if let Ok(value) = env::var(ENV_VAR_PG_HOSTNAME) {
+        pg.hostname = value;
+    }
+    pg.dbname = env::var(ENV_VAR_PG_DBNAME).unwrap_or_else(|_| DEFAULT_TEST_PG_DBNAME.to_string());
+    pg.user = env::var(ENV_VAR_PG_USER).unwrap_or_else(|_| DEFAULT_TEST_PG_USER.to_string());
+    pg.port = env::var(ENV_VAR_PG_PORT)
+        .unwrap_or_else(|_| DEFAULT_TEST_PG_PORT_STR.to_string())
+        .parse()
+        .expect("port should parse as an integer");
+    pg
+}
+
+/// Finds the dev postgres certificate, whether the tests are run by buck2 or cargo.
+#[allow(clippy::disallowed_methods)]
+fn detect_and_configure_development() -> String {
+    if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
+        Buck2Resources::read()
+            .expect("should be able to read buck2 resources")
+            .get_ends_with("dev.postgres.root.crt")
+            .expect("should be able to get cert")
+            .to_string_lossy()
+            .to_string()
+    } else if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
+        Path::new(&dir)
+            .join("../../config/keys/dev.postgres.root.crt")
+            .to_string_lossy()
+            .to_string()
+    } else {
+        "".to_string()
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    io,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+use si_data_pg::{
+    PgPool,
+    PgPoolConfig,
+    PgSharedTransaction,
+};
+use telemetry::tracing::instrument::WithSubscriber;
+
+use super::test_pg_pool_config;
+
+/// Collects everything logged by a test's subscriber.
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().expect("logs lock poisoned")).into_owned()
+    }
+}
+
+impl io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .expect("logs lock poisoned")
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs the statements in a shared transaction, returning everything logged meanwhile.
+async fn logs_from_queries(config: &PgPoolConfig, statements: &[&str]) -> String {
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer({
+            let logs = logs.clone();
+            move || logs.clone()
+        })
+        .finish();
+
+    async {
+        let pool = PgPool::new(config).await.expect("failed to create pg pool");
+        let conn = pool.get().await.expect("failed to get a pg connection");
+        let txn = PgSharedTransaction::create(conn)
+            .await
+            .expect("failed to start a transaction");
+        for statement in statements {
+            txn.execute(statement, &[])
+                .await
+                .expect("failed to execute query");
+        }
+        txn.rollback().await.expect("failed to roll back");
+    }
+    .with_subscriber(subscriber)
+    .await;
+
+    logs.contents()
+}
+
+#[tokio::test]
+async fn slow_query_triggers_warning() {
+    let logs = logs_from_queries(
+        &test_pg_pool_config(Some(100)),
+        &["SELECT pg_sleep(0.2)", "SELECT 1"],
+    )
+    .await;
+
+    assert_eq!(1, logs.matches("slow query exceeded threshold").count());
+    assert!(logs.contains("SELECT pg_sleep(0.2)"));
+}
+
+#[tokio::test]
+async fn disabled_threshold_never_triggers_warning() {
+    let logs = logs_from_queries(&test_pg_pool_config(None), &["SELECT pg_sleep(0.2)"]).await;
+
+    assert!(!logs.contains("slow query exceeded threshold"));
+}
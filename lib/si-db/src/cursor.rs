@@ -0,0 +1,240 @@
+//! This module contains [`Cursor`], the opaque pagination cursor shared by list methods.
+//!
+//! A cursor is either the ID of the last item on the previous page or an offset into the full
+//! list. Either way, it is encoded as URL-safe base64 alongside an HMAC keyed by the
+//! [`SymmetricCryptoService`], so callers can treat it as an opaque string and cursors which were
+//! tampered with, or not handed out by us, are rejected rather than paging from the wrong place.
+
+use base64::{
+    Engine,
+    engine::general_purpose::URL_SAFE_NO_PAD,
+};
+use si_crypto::{
+    SYMMETRIC_TAG_LEN,
+    SymmetricCryptoService,
+    SymmetricTag,
+};
+use thiserror::Error;
+use ulid::Ulid;
+
+const ID_KIND: u8 = 0;
+const OFFSET_KIND: u8 = 1;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum CursorError {
+    #[error("cursor is not valid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("cursor has an invalid length: {0}")]
+    InvalidLength(usize),
+    #[error("cursor is an offset, but an id was expected")]
+    NotAnId,
+    #[error("cursor tag does not match")]
+    TagMismatch,
+    #[error("cursor has an unknown kind: {0}")]
+    UnknownKind(u8),
+}
+
+pub type CursorResult<T> = Result<T, CursorError>;
+
+/// An opaque pagination cursor, pointing at where the next page of a list begins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cursor {
+    /// The next page begins after the item with this ID.
+    Id(Ulid),
+    /// The next page begins this many items into the list.
+    Offset(u64),
+}
+
+impl Cursor {
+    /// Encodes the [`Cursor`] as an opaque, URL-safe string, authenticated with the active key of
+    /// the [`SymmetricCryptoService`].
+    pub fn encode(&self, crypto: &SymmetricCryptoService) -> String {
+        let mut bytes = match self {
+            Self::Id(id) => {
+                let mut bytes = vec![ID_KIND];
+                bytes.extend_from_slice(&id.to_bytes());
+                bytes
+            }
+            Self::Offset(offset) => {
+                let mut bytes = vec![OFFSET_KIND];
+                bytes.extend_from_slice(&offset.to_be_bytes());
+                bytes
+            }
+        };
+        let tag = crypto.authenticate(&bytes);
+        bytes.extend_from_slice(tag.as_ref());
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decodes a [`Cursor`] from a string produced by [`Cursor::encode`], rejecting it if it has
+    /// been modified or was not authenticated by one of the keys of the
+    /// [`SymmetricCryptoService`].
+    pub fn decode(encoded: &str, crypto: &SymmetricCryptoService) -> CursorResult<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(encoded)?;
+        if bytes.len() < 1 + SYMMETRIC_TAG_LEN {
+            return Err(CursorError::InvalidLength(bytes.len()));
+        }
+        let (body, tag) = bytes.split_at(bytes.len() - SYMMETRIC_TAG_LEN);
+        let tag = SymmetricTag::from_slice(tag).ok_or(CursorError::InvalidLength(bytes.len()))?;
+        if !crypto.verify(&tag, body) {
+            return Err(CursorError::TagMismatch);
+        }
+
+        let (kind, payload) = (body[0], &body[1..]);
+        match kind {
+            ID_KIND => payload
+                .try_into()
+                .map(|bytes| Self::Id(Ulid::from_bytes(bytes)))
+                .map_err(|_| CursorError::InvalidLength(bytes.len())),
+            OFFSET_KIND => payload
+                .try_into()
+                .map(|bytes| Self::Offset(u64::from_be_bytes(bytes)))
+                .map_err(|_| CursorError::InvalidLength(bytes.len())),
+            unknown => Err(CursorError::UnknownKind(unknown)),
+        }
+    }
+
+    /// Returns the ID this [`Cursor`] points after, for lists which only page by ID.
+    pub fn id(&self) -> CursorResult<Ulid> {
+        match self {
+            Self::Id(id) => Ok(*id),
+            Self::Offset(_) => Err(CursorError::NotAnId),
+        }
+    }
+}
+
+/// Returns a single page of `items`, ordered by ID, beginning at `cursor` and holding at most
+/// `limit` items. The returned [`Cursor`] points at the next page, if there is one.
+pub fn paginate<T>(
+    mut items: Vec<T>,
+    limit: usize,
+    cursor: Option<Cursor>,
+    id: impl Fn(&T) -> Ulid,
+) -> (Vec<T>, Option<Cursor>) {
+    items.sort_by_key(&id);
+
+    let start = match cursor {
+        Some(Cursor::Id(after)) => items.partition_point(|item| id(item) <= after),
+        Some(Cursor::Offset(offset)) => usize::try_from(offset).unwrap_or(usize::MAX),
+        None => 0,
+    }
+    .min(items.len());
+    let end = start.saturating_add(limit).min(items.len());
+
+    let next_cursor = if end < items.len() {
+        match cursor {
+            Some(Cursor::Offset(_)) => Some(Cursor::Offset(end as u64)),
+            Some(Cursor::Id(_)) | None => items[..end].last().map(|item| Cursor::Id(id(item))),
+        }
+    } else {
+        None
+    };
+
+    let page = items.drain(start..end).collect();
+    (page, next_cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crypto() -> SymmetricCryptoService {
+        SymmetricCryptoService::new(SymmetricCryptoService::generate_key(), vec![])
+    }
+
+    #[test]
+    fn round_trip() {
+        let crypto = crypto();
+        for cursor in [
+            Cursor::Id(Ulid::new()),
+            Cursor::Id(Ulid::nil()),
+            Cursor::Offset(0),
+            Cursor::Offset(u64::MAX),
+        ] {
+            let encoded = cursor.encode(&crypto);
+            assert_eq!(
+                cursor,
+                Cursor::decode(&encoded, &crypto).expect("should decode")
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_cursor() {
+        let crypto = crypto();
+        let encoded = Cursor::Offset(50).encode(&crypto);
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(&encoded).expect("should decode");
+        bytes[8] = bytes[8].wrapping_add(1);
+        let tampered = URL_SAFE_NO_PAD.encode(bytes);
+
+        assert!(matches!(
+            Cursor::decode(&tampered, &crypto),
+            Err(CursorError::TagMismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_cursor_authenticated_with_another_key() {
+        let encoded = Cursor::Offset(50).encode(&crypto());
+
+        assert!(matches!(
+            Cursor::decode(&encoded, &crypto()),
+            Err(CursorError::TagMismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_cursor() {
+        let crypto = crypto();
+        assert!(matches!(
+            Cursor::decode("not a cursor!", &crypto),
+            Err(CursorError::Base64(_))
+        ));
+        assert!(matches!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode([ID_KIND]), &crypto),
+            Err(CursorError::InvalidLength(1))
+        ));
+
+        let mut unknown_kind = vec![7, 1, 2, 3];
+        let tag = crypto.authenticate(&unknown_kind);
+        unknown_kind.extend_from_slice(tag.as_ref());
+        assert!(matches!(
+            Cursor::decode(&URL_SAFE_NO_PAD.encode(unknown_kind), &crypto),
+            Err(CursorError::UnknownKind(7))
+        ));
+    }
+
+    #[test]
+    fn paginates_by_id() {
+        let ids: Vec<Ulid> = (0..5).map(|n| Ulid::from_parts(n, 0)).collect();
+        let mut shuffled = ids.clone();
+        shuffled.reverse();
+
+        let (page, cursor) = paginate(shuffled.clone(), 2, None, |id| *id);
+        assert_eq!(ids[0..2], page);
+        assert_eq!(Some(Cursor::Id(ids[1])), cursor);
+
+        let (page, cursor) = paginate(shuffled.clone(), 2, cursor, |id| *id);
+        assert_eq!(ids[2..4], page);
+
+        let (page, cursor) = paginate(shuffled, 2, cursor, |id| *id);
+        assert_eq!(ids[4..], page);
+        assert_eq!(None, cursor);
+    }
+
+    #[test]
+    fn paginates_by_offset() {
+        let ids: Vec<Ulid> = (0..5).map(|n| Ulid::from_parts(n, 0)).collect();
+
+        let (page, cursor) = paginate(ids.clone(), 3, Some(Cursor::Offset(1)), |id| *id);
+        assert_eq!(ids[1..4], page);
+        assert_eq!(Some(Cursor::Offset(4)), cursor);
+
+        let (page, cursor) = paginate(ids, 3, Some(Cursor::Offset(10)), |id| *id);
+        assert!(page.is_empty());
+        assert_eq!(None, cursor);
+    }
+}
@@ -8,12 +8,14 @@ use si_events::{
     FuncId,
     FuncRun,
     FuncRunId,
+    FuncRunTimings,
     WorkspacePk,
 };
 use telemetry::prelude::*;
 use telemetry_utils::monotonic;
 
 use crate::{
+    Cursor,
     SiDbContext,
     SiDbError,
     SiDbResult,
@@ -502,6 +504,52 @@ impl FuncRunDb {
         }
     }
 
+    /// Records how long each phase of the given func run took.
+    pub async fn set_timings(
+        ctx: &impl SiDbContext,
+        key: FuncRunId,
+        timings: FuncRunTimings,
+    ) -> SiDbResult<()> {
+        let json: serde_json::Value = serde_json::to_value(timings)?;
+
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                &format!("UPDATE {DBNAME} SET timings = $2 WHERE key = $1"),
+                &[&key.to_string(), &json],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the recorded phase timings for the given func run, if any.
+    ///
+    /// Timings are recorded once the func run has finished executing and its output has been
+    /// drained, so they are absent for in-flight runs and for runs predating timing capture.
+    pub async fn timings(
+        ctx: &impl SiDbContext,
+        key: FuncRunId,
+    ) -> SiDbResult<Option<FuncRunTimings>> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                &format!("SELECT timings FROM {DBNAME} WHERE key = $1"),
+                &[&key.to_string()],
+            )
+            .await?;
+
+        let Some(row) = maybe_row else {
+            return Ok(None);
+        };
+        let maybe_json: Option<serde_json::Value> = row.try_get("timings")?;
+
+        Ok(maybe_json.map(serde_json::from_value).transpose()?)
+    }
+
     pub async fn try_read(ctx: &impl SiDbContext, key: FuncRunId) -> SiDbResult<FuncRun> {
         Self::read(ctx, key)
             .await?
@@ -553,7 +601,7 @@ impl FuncRunDb {
     ///
     /// This method uses cursor-based pagination where:
     /// - `limit` controls how many items to return per page
-    /// - `cursor` is the ID of the last item from the previous page
+    /// - `cursor` points after the last item from the previous page
     /// - Results are filtered by workspace_id and change_set_id
     ///
     /// Results are ordered by creation time (newest first).
@@ -562,8 +610,11 @@ impl FuncRunDb {
         workspace_pk: WorkspacePk,
         change_set_id: ChangeSetId,
         limit: i64,
-        cursor: Option<FuncRunId>,
+        cursor: Option<Cursor>,
     ) -> SiDbResult<Vec<FuncRun>> {
+        let cursor = cursor
+            .map(|cursor| cursor.id().map(FuncRunId::from_raw_id))
+            .transpose()?;
         let rows = if let Some(cursor_id) = cursor {
             ctx.txns()
                 .await?
@@ -623,7 +674,7 @@ impl FuncRunDb {
     ///
     /// This method uses cursor-based pagination where:
     /// - `limit` controls how many items to return per page
-    /// - `cursor` is the ID of the last item from the previous page
+    /// - `cursor` points after the last item from the previous page
     /// - Results are filtered by workspace_id, change_set_id, and component_id
     ///
     /// Results are ordered by creation time (newest first).
@@ -633,8 +684,11 @@ impl FuncRunDb {
         change_set_id: ChangeSetId,
         component_id: ComponentId,
         limit: i64,
-        cursor: Option<FuncRunId>,
+        cursor: Option<Cursor>,
     ) -> SiDbResult<Vec<FuncRun>> {
+        let cursor = cursor
+            .map(|cursor| cursor.id().map(FuncRunId::from_raw_id))
+            .transpose()?;
         let rows = if let Some(cursor_id) = cursor {
             ctx.txns()
                 .await?
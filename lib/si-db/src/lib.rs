@@ -5,6 +5,7 @@ use si_id::UserPk;
 mod actor_view;
 pub mod change_set;
 mod context;
+pub mod cursor;
 mod func_run;
 mod func_run_log;
 mod history_event;
@@ -22,6 +23,11 @@ pub mod workspace;
 
 pub use actor_view::ActorView;
 pub use context::SiDbContext;
+pub use cursor::{
+    Cursor,
+    CursorError,
+    CursorResult,
+};
 pub use func_run::FuncRunDb;
 pub use func_run_log::FuncRunLogDb;
 pub use history_event::{
@@ -61,6 +67,8 @@ mod embedded {
 pub enum SiDbError {
     #[error("action id not found: {0}")]
     ActionIdNotFound(si_events::ActionId),
+    #[error("cursor error: {0}")]
+    Cursor(#[from] CursorError),
     #[error("layer db error: {0}")]
     LayerDb(String),
     #[error("missing func run: {0}")]
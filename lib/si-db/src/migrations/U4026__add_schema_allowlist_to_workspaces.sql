@@ -0,0 +1,2 @@
+ALTER TABLE workspaces
+    ADD COLUMN schema_allowlist text[] NULL;
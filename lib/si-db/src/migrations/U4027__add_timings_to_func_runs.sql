@@ -0,0 +1,2 @@
+ALTER TABLE func_runs
+    ADD COLUMN timings jsonb NULL;
@@ -0,0 +1,34 @@
+ALTER TABLE key_pairs
+    ADD COLUMN algorithm text NOT NULL DEFAULT 'curve25519xsalsa20poly1305';
+
+CREATE OR REPLACE FUNCTION key_pair_create_v2(
+    this_name text,
+    this_workspace_pk ident,
+    this_public_key text,
+    this_secret_key_crypted text,
+    this_secret_key_nonce text,
+    this_secret_key_key_hash text,
+    this_algorithm text,
+    OUT object json) AS
+$$
+DECLARE
+    this_new_row           key_pairs%ROWTYPE;
+BEGIN
+    INSERT INTO key_pairs (name,
+                           workspace_pk,
+                           public_key,
+                           secret_key_crypted,
+                           secret_key_nonce,
+                           secret_key_key_hash,
+                           algorithm)
+    VALUES (this_name,
+            this_workspace_pk,
+            this_public_key,
+            this_secret_key_crypted,
+            this_secret_key_nonce,
+            this_secret_key_key_hash,
+            this_algorithm)
+    RETURNING * INTO this_new_row;
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
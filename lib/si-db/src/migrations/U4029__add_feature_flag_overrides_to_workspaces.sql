@@ -0,0 +1,2 @@
+ALTER TABLE workspaces
+    ADD COLUMN feature_flag_overrides jsonb NOT NULL DEFAULT '{}'::jsonb;
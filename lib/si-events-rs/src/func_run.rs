@@ -373,6 +373,40 @@ impl FuncRun {
     }
 }
 
+/// How long each phase of a [`FuncRun`] took, in microseconds.
+///
+/// This is stored alongside the [`FuncRun`] rather than on it since the latter is postcard
+/// serialized and cannot gain new fields without breaking existing records.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunTimings {
+    /// Time spent in the dal preparing the run (looking up the func, gathering before funcs and
+    /// secrets, writing the arguments and code to the CAS) before it was dispatched.
+    pub prepare_us: u64,
+    /// Time spent executing the func itself, either in veritech or inline for intrinsics.
+    pub critical_section_us: u64,
+    /// Time spent after the execution finished waiting for the remaining output to be drained
+    /// into the [`FuncRunLog`](crate::FuncRunLog).
+    pub output_drain_us: u64,
+    /// Time spent recording the result of the execution and handing it back to the caller.
+    pub postprocess_us: u64,
+}
+
+impl FuncRunTimings {
+    /// The sum of every phase.
+    pub fn total_us(&self) -> u64 {
+        self.prepare_us
+            .saturating_add(self.critical_section_us)
+            .saturating_add(self.output_drain_us)
+            .saturating_add(self.postprocess_us)
+    }
+
+    /// The time spent outside of the func itself, i.e. dal overhead.
+    pub fn overhead_us(&self) -> u64 {
+        self.total_us().saturating_sub(self.critical_section_us)
+    }
+}
+
 #[derive(Debug)]
 pub struct FuncRunValue {
     func_run_id: FuncRunId,
@@ -24,6 +24,33 @@ pub struct OutputLine {
     pub timestamp: u64,
 }
 
+impl OutputLine {
+    /// Parses the message as a structured log entry.
+    ///
+    /// Functions often emit JSON-formatted log lines. If the message is a JSON object, its fields
+    /// are returned, otherwise the message is plain text and `None` is returned.
+    pub fn structured_message(&self) -> Option<serde_json::Map<String, serde_json::Value>> {
+        match serde_json::from_str(self.message.trim()) {
+            Ok(serde_json::Value::Object(fields)) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Returns the level of the line, preferring a string `level` field from its structured
+    /// message, as returned by [`structured_message`](Self::structured_message), over the level
+    /// reported by the output stream.
+    pub fn level_with(
+        &self,
+        structured: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> String {
+        structured
+            .and_then(|fields| fields.get("level"))
+            .and_then(|level| level.as_str())
+            .unwrap_or(&self.level)
+            .to_owned()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FuncRunLog {
     id: FuncRunLogId,
@@ -85,3 +112,49 @@ impl FuncRunLog {
         self.finalized = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output_line(message: &str) -> OutputLine {
+        OutputLine {
+            stream: "stdout".to_string(),
+            execution_id: "execution".to_string(),
+            level: "info".to_string(),
+            group: Some("log".to_string()),
+            message: message.to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn json_line_is_structured() {
+        let line = output_line(r#"{"level":"warn","msg":"bucket is public","bucket":"poop"}"#);
+
+        let fields = line
+            .structured_message()
+            .expect("line should be structured");
+        assert_eq!(
+            Some(&serde_json::json!("bucket is public")),
+            fields.get("msg")
+        );
+        assert_eq!(Some(&serde_json::json!("poop")), fields.get("bucket"));
+        assert_eq!("warn", line.level_with(Some(&fields)));
+    }
+
+    #[test]
+    fn plain_line_is_text() {
+        let line = output_line("Output: creating bucket");
+
+        assert_eq!(None, line.structured_message());
+        assert_eq!("info", line.level_with(None));
+    }
+
+    #[test]
+    fn non_object_json_line_is_text() {
+        let line = output_line("42");
+
+        assert_eq!(None, line.structured_message());
+    }
+}
@@ -83,6 +83,7 @@ pub use crate::{
         FuncRunBuilderError,
         FuncRunId,
         FuncRunState,
+        FuncRunTimings,
         FuncRunValue,
         ManagementPrototypeId,
         ViewId,
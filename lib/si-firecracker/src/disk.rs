@@ -34,16 +34,17 @@ const OVERLAY_PREFIX: &str = "rootfs-overlay-";
 pub struct FirecrackerDisk {}
 
 impl FirecrackerDisk {
-    pub fn clean(id: u32) -> Result<()> {
-        Self::unmount(id)?;
-        Self::remove_overlay(id)?;
-        Self::remove_loop(id)?;
-        Self::remove_jail(id)?;
+    /// Removes the disk of the jail kept under the given name, see `JailerIdentity::jail_id`.
+    pub fn clean(jail_id: &str) -> Result<()> {
+        Self::unmount(jail_id)?;
+        Self::remove_overlay(jail_id)?;
+        Self::remove_loop(jail_id)?;
+        Self::remove_jail(jail_id)?;
         Ok(())
     }
 
-    fn unmount(id: u32) -> Result<()> {
-        let device = Self::jail_dir_from_id(id).join(ROOTFS);
+    fn unmount(jail_id: &str) -> Result<()> {
+        let device = Self::jail_dir(jail_id).join(ROOTFS);
         if device.exists() {
             trace!("Unmounting device {}", device.display());
             match umount(&device) {
@@ -64,8 +65,8 @@ impl FirecrackerDisk {
         Ok(())
     }
 
-    fn remove_overlay(id: u32) -> Result<()> {
-        let overlay = Self::overlay_from_id(id);
+    fn remove_overlay(jail_id: &str) -> Result<()> {
+        let overlay = Self::overlay(jail_id);
         let dm = DM::new()?;
         let device = DmName::new(&overlay)?;
         let dev_id = &DevId::Name(device);
@@ -75,8 +76,8 @@ impl FirecrackerDisk {
         Ok(())
     }
 
-    fn remove_loop(id: u32) -> Result<()> {
-        let device = Self::jail_dir_from_id(id).join(Self::overlay_from_id(id));
+    fn remove_loop(jail_id: &str) -> Result<()> {
+        let device = Self::jail_dir(jail_id).join(Self::overlay(jail_id));
         if let Some(loopdev) = Self::find_loop_device_by_backing_file(&device)? {
             trace!("Detaching from loop device {}", device.display());
             let device = LoopDevice::open(loopdev)?;
@@ -85,8 +86,8 @@ impl FirecrackerDisk {
         Ok(())
     }
 
-    fn remove_jail(id: u32) -> Result<()> {
-        let jail = &Self::jail_dir_from_id(id);
+    fn remove_jail(jail_id: &str) -> Result<()> {
+        let jail = &Self::jail_dir(jail_id);
         if jail.exists() {
             trace!("Removing {}", jail.display());
             remove_dir_all(jail)?;
@@ -94,13 +95,13 @@ impl FirecrackerDisk {
         Ok(())
     }
 
-    pub fn jail_dir_from_id(id: u32) -> PathBuf {
+    pub fn jail_dir(jail_id: &str) -> PathBuf {
         let path = PathBuf::from(JAIL_PATH_PREFIX);
-        path.join(id.to_string()).join("root")
+        path.join(jail_id).join("root")
     }
 
-    fn overlay_from_id(id: u32) -> String {
-        format!("{OVERLAY_PREFIX}{id}")
+    fn overlay(jail_id: &str) -> String {
+        format!("{OVERLAY_PREFIX}{jail_id}")
     }
 
     fn find_loop_device_by_backing_file(backing_file: &Path) -> Result<Option<OsString>> {
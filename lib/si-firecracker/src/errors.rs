@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    process::ExitStatus,
+};
 
 use thiserror::Error;
 
@@ -26,6 +29,13 @@ pub enum FirecrackerJailError {
     // Failed to setup firecracker
     #[error("Failed to setup firecracker: {0}")]
     Setup(#[from] tokio::io::Error),
+    // The firecracker-setup script exited unsuccessfully
+    #[error("firecracker-setup failed with {status}; stdout: {stdout:?}; stderr: {stderr:?}")]
+    SetupRun {
+        status: ExitStatus,
+        stdout: String,
+        stderr: String,
+    },
     // The setup script(s) do not exist
     #[error("Setup script(s) do not exist: {0:?}")]
     SetupScriptsDoNotExist(Vec<String>),
@@ -7,6 +7,7 @@ use std::{
         PathBuf,
     },
     result,
+    time::Duration,
 };
 
 use cyclone_core::process;
@@ -16,6 +17,7 @@ use tokio::{
         Child,
         Command,
     },
+    time,
 };
 use tracing::info;
 
@@ -36,6 +38,47 @@ const FIRECRACKER_SCRIPTS: &[(&str, &[u8])] = &[
     (FIRECRACKER_SETUP_PATH, FIRECRACKER_SETUP_BYTES),
 ];
 
+/// The user, group and network namespace each jail runs in, which must match how the host was
+/// provisioned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JailerIdentity {
+    /// The base of each jail's uid, which has the jail's id appended to it, so a base of `500`
+    /// runs jail `12` as uid `50012`.
+    pub uid_base: u32,
+    /// The gid every jail runs as.
+    pub gid: u32,
+    /// The prefix of each jail's network namespace name, which has the jail's id appended to it.
+    pub netns_prefix: String,
+}
+
+impl Default for JailerIdentity {
+    fn default() -> Self {
+        Self {
+            uid_base: 500,
+            gid: 10000,
+            netns_prefix: "jailer-".to_string(),
+        }
+    }
+}
+
+impl JailerIdentity {
+    /// The uid the jail with the given id runs as.
+    pub fn uid(&self, id: u32) -> String {
+        format!("{}{id}", self.uid_base)
+    }
+
+    /// The name of the network namespace the jail with the given id runs in.
+    pub fn netns(&self, id: u32) -> String {
+        format!("{}{id}", self.netns_prefix)
+    }
+
+    /// The name the jail with the given id, and its disk, are kept under. This is its network
+    /// namespace's name, so deployments sharing a host with their own prefixes do not share jails.
+    pub fn jail_id(&self, id: u32) -> String {
+        self.netns(id)
+    }
+}
+
 #[derive(Debug)]
 pub struct FirecrackerJail {
     jailer: Command,
@@ -48,7 +91,9 @@ impl FirecrackerJail {
         self.socket.to_owned()
     }
 
-    pub async fn build(id: u32) -> Result<Self> {
+    /// Builds the jailer command for a jail, which runs as the user, group and network namespace
+    /// that [`Self::prepare`] set up for it.
+    pub async fn build(id: u32, identity: &JailerIdentity) -> Result<Self> {
         let mut cmd = Command::new("/usr/bin/jailer");
         cmd.arg("--cgroup-version")
             .arg("2")
@@ -59,19 +104,19 @@ impl FirecrackerJail {
             .arg("--cgroup")
             .arg("cpu.max=1000000,1000000")
             .arg("--id")
-            .arg(id.to_string())
+            .arg(identity.jail_id(id))
             .arg("--exec-file")
             .arg("/usr/bin/firecracker")
             .arg("--uid")
-            .arg(format!("500{id}"))
+            .arg(identity.uid(id))
             .arg("--gid")
-            .arg("10000")
+            .arg(identity.gid.to_string())
             .arg("--netns")
-            .arg(format!("/var/run/netns/jailer-{id}"))
+            .arg(format!("/var/run/netns/{}", identity.netns(id)))
             .arg("--")
             .arg("--config-file")
             .arg("./firecracker.conf");
-        let socket = PathBuf::from(&format!("/srv/jailer/firecracker/{id}/root/v.sock"));
+        let socket = FirecrackerDisk::jail_dir(&identity.jail_id(id)).join("v.sock");
 
         Ok(Self {
             jailer: cmd,
@@ -80,14 +125,17 @@ impl FirecrackerJail {
         })
     }
 
-    pub async fn clean(id: u32) -> Result<()> {
-        FirecrackerDisk::clean(id)?;
+    pub async fn clean(id: u32, identity: &JailerIdentity) -> Result<()> {
+        FirecrackerDisk::clean(&identity.jail_id(id))?;
         Ok(())
     }
 
-    pub async fn prepare(id: u32) -> Result<()> {
+    pub async fn prepare(id: u32, identity: &JailerIdentity) -> Result<()> {
         let output = Command::new(FIRECRACKER_PREPARE_PATH)
             .arg(id.to_string())
+            .arg(identity.uid_base.to_string())
+            .arg(identity.gid.to_string())
+            .arg(&identity.netns_prefix)
             .output()
             .await
             .map_err(FirecrackerJailError::Prepare)?;
@@ -100,7 +148,7 @@ impl FirecrackerJail {
         }
 
         // TODO(nick,john,fletcher): delete or restore this once verideath investigation is done.
-        // UnixStreamForwarder::new(FirecrackerDisk::jail_dir_from_id(id), id)
+        // UnixStreamForwarder::new(FirecrackerDisk::jail_dir(&identity.jail_id(id)), id, identity)
         //     .await?
         //     .start()
         //     .await?;
@@ -108,7 +156,11 @@ impl FirecrackerJail {
         Ok(())
     }
 
-    pub async fn setup(pool_size: u32, create_scripts: bool) -> Result<()> {
+    pub async fn setup(
+        pool_size: u32,
+        create_scripts: bool,
+        identity: &JailerIdentity,
+    ) -> Result<()> {
         if create_scripts {
             info!("creating scripts...");
             Self::create_scripts().await?;
@@ -135,28 +187,24 @@ impl FirecrackerJail {
 
         // we want to work with a clean slate, but we don't necessarily care about failures here
         for id in 0..pool_size + 1 {
-            Self::clean(id).await?;
+            Self::clean(id, identity).await?;
         }
 
+        // Capture both stdout and stderr, since many script errors end with empty stderr
         let output = Command::new("sudo")
             .arg(FIRECRACKER_SETUP_PATH)
             .arg("-j")
             .arg(pool_size.to_string())
             .arg("-rk")
-            .spawn()?
-            .wait_with_output()
+            .output()
             .await?;
 
         if !output.status.success() {
-            // FIXME(nick): came by and read this... it looks like we wanted this error enum to
-            // encapsulate all kinds of setup errors into one. We should instead create a new
-            // error enum with its own variants and make this a formal variant. Why? We may need
-            // to provide more context and/or capture stdout here. Many script errors end with
-            // empty stderr.
-            return Err(FirecrackerJailError::Setup(Error::other(
-                String::from_utf8(output.stderr)
-                    .unwrap_or_else(|_| "Failed to decode stderr".to_string()),
-            )));
+            return Err(FirecrackerJailError::SetupRun {
+                status: output.status,
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
         }
 
         Ok(())
@@ -177,6 +225,22 @@ impl FirecrackerJail {
         }
     }
 
+    /// Waits up to `grace` for the jailed VM to exit on its own before sending it a `SIGTERM`,
+    /// and then up to `grace` again before killing it.
+    pub async fn terminate_graceful(&mut self, grace: Duration) -> Result<()> {
+        match self.child.as_mut() {
+            Some(c) => {
+                if let Ok(exited) = time::timeout(grace, c.wait()).await {
+                    exited.map_err(process::ShutdownError::ChildWait)?;
+                    return Ok(());
+                }
+                process::child_shutdown(c, Some(process::Signal::SIGTERM), Some(grace)).await?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
     async fn create_scripts() -> Result<()> {
         for (path, bytes) in FIRECRACKER_SCRIPTS {
             Self::create_script(Path::new(*path), bytes).await?;
@@ -22,6 +22,9 @@ function retry() {
 ########## ############################# #########
 
 SB_ID="${1:-0}" # Default to sb_id=0
+JAILER_UID_BASE="${2:-500}" # The jail's uid is its id appended to this base
+JAILER_GID="${3:-10000}" # The jailer-processes group created during provisioning
+JAILER_NS_PREFIX="${4:-jailer-}"
 
 DATA_DIR="/firecracker-data"
 JAILER_DIR="/srv/jailer/firecracker"
@@ -34,9 +37,14 @@ SCRIPTS="scripts"
 RO_DRIVE="$DATA_DIR/$ROOTFS"
 KERNEL_IMG="$DATA_DIR/$KERNEL"
 
-TAP_DEV="fc-${SB_ID}-tap0"
 FC_MAC="$(printf '02:FC:00:00:%02X:%02X' $((SB_ID / 256)) $((SB_ID % 256)))"
-JAILER_NS="jailer-$SB_ID"
+JAILER_NS="${JAILER_NS_PREFIX}${SB_ID}"
+JAILER_UID="${JAILER_UID_BASE}${SB_ID}"
+# Devices are named by the jail's uid, rather than its id, so that deployments sharing a host
+# with their own uid bases do not share them. Interface names are limited to 15 characters.
+TAP_DEV="fc-${JAILER_UID}"
+VETH_MAIN_DEV="vm-${JAILER_UID}"
+VETH_DEV="vj-${JAILER_UID}"
 
 ########## ############################# #########
 ##########           User Prep           #########
@@ -44,16 +52,16 @@ JAILER_NS="jailer-$SB_ID"
 
 # Create a user and group to run the execution via for one micro-vm
 function user_prep() {
-  useradd -M -u 500$SB_ID $JAILER_NS
+  useradd -M -u $JAILER_UID $JAILER_NS
   usermod -L $JAILER_NS
 
   # This group was created earlier on the machine provisioning
-  usermod -a -G jailer-processes $JAILER_NS
+  usermod -a -G $JAILER_GID $JAILER_NS
   usermod -a -G root $JAILER_NS
   usermod -a -G kvm $JAILER_NS
 }
 
-if ! id 500$SB_ID >/dev/null 2>&1; then
+if ! id $JAILER_UID >/dev/null 2>&1; then
   retry user_prep
 fi
 
@@ -61,7 +69,8 @@ fi
 ##########          Disk Prep            #########
 ########## ############################# #########
 
-JAIL="$JAILER_DIR/$SB_ID/root"
+# Jails are kept under their network namespace's name, to match the jailer's --id
+JAIL="$JAILER_DIR/$JAILER_NS/root"
 mkdir -p "$JAIL/"
 rm -rf "$JAIL/{dev,run}"
 
@@ -103,12 +112,12 @@ if ! test -f "$JAIL/$KERNEL"; then
   retry kernel_prep
 fi
 
-OVERLAY="rootfs-overlay-$SB_ID"
+OVERLAY="rootfs-overlay-$JAILER_NS"
 if ! dmsetup info $OVERLAY &> /dev/null; then
   retry rootfs_prep
 fi
 
-chown -R jailer-$SB_ID:jailer-$SB_ID $JAIL/
+chown -R $JAILER_NS:$JAILER_NS $JAIL/
 
 ########## ############################# #########
 ##########          Network Prep         #########
@@ -124,7 +133,6 @@ if ! test -f /run/netns/$JAILER_NS; then
   TAP_IP="10.0.0.2" # more difficult & to simplify rootfs creation/configuration
   NET_LINK_MAIN_IP="$(printf '100.65.%s.%s' $(((4 * SB_ID + 1) / 256)) $(((4 * SB_ID + 1) % 256)))"
   NET_LINK_JAILER_IP="$(printf '100.65.%s.%s' $(((4 * SB_ID + 2) / 256)) $(((4 * SB_ID + 2) % 256)))"
-  VETH_DEV="veth-jailer$SB_ID"
 
   # Setup TAP device that uses proxy ARP
   ip netns exec $JAILER_NS ip link del "$TAP_DEV" 2> /dev/null || true
@@ -139,13 +147,13 @@ if ! test -f /run/netns/$JAILER_NS; then
   ip netns exec $JAILER_NS ip link set dev "$TAP_DEV" up
 
   # Set up IP link into default namespace for external routing
-  ip link add veth-main$SB_ID type veth peer name $VETH_DEV
+  ip link add $VETH_MAIN_DEV type veth peer name $VETH_DEV
   ip link set $VETH_DEV netns $JAILER_NS
-  ip addr add $NET_LINK_MAIN_IP/30 dev veth-main$SB_ID
+  ip addr add $NET_LINK_MAIN_IP/30 dev $VETH_MAIN_DEV
   ip netns exec $JAILER_NS ip addr add $NET_LINK_JAILER_IP/30 dev $VETH_DEV
 
   # Bring the veth link up for external routing
-  ip link set dev veth-main$SB_ID up
+  ip link set dev $VETH_MAIN_DEV up
   ip netns exec $JAILER_NS ip link set dev $VETH_DEV up
   ip netns exec $JAILER_NS ip route replace default via $NET_LINK_MAIN_IP
 
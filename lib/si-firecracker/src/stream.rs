@@ -30,8 +30,8 @@ use tokio_vsock::{
 };
 use tracing::debug;
 
-const UID_BASE: u32 = 5000;
-const GID: u32 = 10000;
+use crate::firecracker::JailerIdentity;
+
 const DEFAULT_OTEL_PORT: u32 = 4317;
 
 #[remain::sorted]
@@ -49,6 +49,8 @@ pub enum StreamForwarderError {
     Read(#[source] std::io::Error),
     #[error("TCP Stream error: {0}")]
     Tcp(#[source] std::io::Error),
+    #[error("invalid jailer uid {1}: {0}")]
+    Uid(#[source] std::num::ParseIntError, String),
     #[error("Vsock Stream error: {0}")]
     Vsock(#[source] std::io::Error),
 }
@@ -62,7 +64,11 @@ pub struct UnixStreamForwarder {
 }
 
 impl UnixStreamForwarder {
-    pub async fn new(in_path: impl AsRef<Path>, id: u32) -> Result<Self> {
+    pub async fn new(
+        in_path: impl AsRef<Path>,
+        id: u32,
+        identity: &JailerIdentity,
+    ) -> Result<Self> {
         let port = DEFAULT_OTEL_PORT;
         let mut path = in_path.as_ref().to_path_buf();
         path.push(format!("v.sock_{port}"));
@@ -72,7 +78,7 @@ impl UnixStreamForwarder {
         let source = UnixListener::bind(&path)
             .map_err(|err| StreamForwarderError::Bind(err, path.clone()))?;
         // the jailer runs as a specific user and that user must own the socket
-        chown_for_id(path, id)?;
+        chown_for_id(path, id, identity)?;
 
         Ok(Self { source, port })
     }
@@ -149,9 +155,13 @@ where
     Ok(())
 }
 
-fn chown_for_id(path: PathBuf, id: u32) -> Result<()> {
-    let uid = Uid::from_raw(UID_BASE + id);
-    let gid = Gid::from_raw(GID);
+fn chown_for_id(path: PathBuf, id: u32, identity: &JailerIdentity) -> Result<()> {
+    let uid = identity.uid(id);
+    let uid = Uid::from_raw(
+        uid.parse()
+            .map_err(|err| StreamForwarderError::Uid(err, uid.clone()))?,
+    );
+    let gid = Gid::from_raw(identity.gid);
     chown(&path, Some(uid), Some(gid)).map_err(StreamForwarderError::Chown)?;
     Ok(())
 }
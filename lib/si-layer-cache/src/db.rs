@@ -19,13 +19,20 @@ use si_data_pg::{
     PgPool,
     PgPoolConfig,
 };
+use si_events::{
+    Actor,
+    Tenancy,
+};
 use si_runtime::DedicatedExecutor;
 use split_snapshot_rebase_batch::SplitSnapshotRebaseBatchDb;
 use split_snapshot_subgraph::SplitSnapshotSubGraphDb;
 use split_snapshot_supergraph::SplitSnapshotSuperGraphDb;
 use telemetry::prelude::*;
 use tokio::{
-    sync::mpsc,
+    sync::{
+        Semaphore,
+        mpsc,
+    },
     try_join,
 };
 use tokio_util::{
@@ -37,6 +44,10 @@ use ulid::Ulid;
 use self::{
     cache_updates::CacheUpdatesTask,
     cas::CasDb,
+    health::{
+        HEALTH_PROBE_TIMEOUT,
+        LayerDbHealth,
+    },
     rebase_batch::RebaseBatchDb,
     workspace_snapshot::WorkspaceSnapshotDb,
 };
@@ -48,9 +59,17 @@ use crate::{
         func_run_log::FuncRunLogLayerDb,
     },
     error::LayerDbResult,
+    event::{
+        LayeredEvent,
+        LayeredEventKind,
+    },
     hybrid_cache::CacheConfig,
-    layer_cache::LayerCache,
+    layer_cache::{
+        LayerCache,
+        LayerCacheMetrics,
+    },
     persister::{
+        PersistStatus,
         PersisterClient,
         PersisterMode,
         PersisterTask,
@@ -64,6 +83,7 @@ pub mod change_batch;
 pub mod encrypted_secret;
 pub mod func_run;
 pub mod func_run_log;
+pub mod health;
 pub mod rebase_batch;
 pub mod serialize;
 pub mod split_snapshot_rebase_batch;
@@ -71,15 +91,80 @@ pub mod split_snapshot_subgraph;
 pub mod split_snapshot_supergraph;
 pub mod workspace_snapshot;
 
+/// The default number of cache updates from other instances to apply concurrently.
+pub const DEFAULT_CACHE_UPDATES_CONCURRENCY: usize = 16;
+
+/// The default number of keys to read concurrently when prewarming a cache.
+pub const DEFAULT_PREWARM_CONCURRENCY: usize = 8;
+
+/// The default share of the usable disk given to each cache, as a percentage. These add up to
+/// 100.
+pub const DEFAULT_DISK_PERCENTAGES: [(&str, u8); 10] = [
+    (cas::CACHE_NAME, 24),
+    (change_batch::CACHE_NAME, 5),
+    (encrypted_secret::CACHE_NAME, 5),
+    (func_run::CACHE_NAME, 4),
+    (func_run_log::CACHE_NAME, 4),
+    (rebase_batch::CACHE_NAME, 5),
+    (workspace_snapshot::CACHE_NAME, 50),
+    (split_snapshot_subgraph::CACHE_NAME, 1),
+    (split_snapshot_supergraph::CACHE_NAME, 1),
+    (split_snapshot_rebase_batch::CACHE_NAME, 1),
+];
+
 fn validate_config(config: &LayerDbConfig) -> LayerDbResult<()> {
     // Validate that S3 is configured when mode requires it
     if config.persister_mode != PersisterMode::PostgresOnly {
         // Config validation happens during S3Layer creation
         // If mode != PostgresOnly, we'll create S3Layers
     }
+
+    for name in config.disk_percentages.keys() {
+        if !DEFAULT_DISK_PERCENTAGES
+            .iter()
+            .any(|(cache_name, _)| *cache_name == name.as_str())
+        {
+            warn!(
+                cache.name = name.as_str(),
+                "ignoring disk percentage for unknown cache"
+            );
+        }
+    }
+    let total_disk_percentage = total_disk_percentage(config);
+    if total_disk_percentage > 100 {
+        warn!(
+            total_disk_percentage,
+            "layer cache disk percentages add up to more than 100% of the usable disk, so the \
+            disk caches may fill it"
+        );
+    }
+
     Ok(())
 }
 
+/// The share of the usable disk given to all of the caches together, as a percentage.
+fn total_disk_percentage(config: &LayerDbConfig) -> u32 {
+    DEFAULT_DISK_PERCENTAGES
+        .iter()
+        .map(|(name, _)| disk_percentage(config, name) as u32)
+        .sum()
+}
+
+/// The share of the usable disk given to the named cache, as a percentage.
+fn disk_percentage(config: &LayerDbConfig, name: &str) -> u8 {
+    config
+        .disk_percentages
+        .get(name)
+        .copied()
+        .or_else(|| {
+            DEFAULT_DISK_PERCENTAGES
+                .iter()
+                .find(|(cache_name, _)| *cache_name == name)
+                .map(|(_, percentage)| *percentage)
+        })
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Clone)]
 pub struct LayerDb<
     CasValue,
@@ -174,7 +259,7 @@ where
         let tracker = TaskTracker::new();
 
         let (tx, rx) = mpsc::unbounded_channel();
-        let persister_client = PersisterClient::new(tx);
+        let persister_client = PersisterClient::new(tx, config.read_only);
 
         // Validate configuration
         validate_config(&config)?;
@@ -290,7 +375,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 24,
-                24,
+                disk_percentage(&config, cas::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -302,7 +387,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 5,
-                5,
+                disk_percentage(&config, change_batch::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -314,7 +399,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 5,
-                5,
+                disk_percentage(&config, encrypted_secret::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -326,7 +411,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 4,
-                4,
+                disk_percentage(&config, func_run::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -338,7 +423,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 4,
-                4,
+                disk_percentage(&config, func_run_log::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -350,7 +435,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 5,
-                5,
+                disk_percentage(&config, rebase_batch::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -362,7 +447,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 50,
-                50,
+                disk_percentage(&config, workspace_snapshot::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -374,7 +459,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 1,
-                1,
+                disk_percentage(&config, split_snapshot_subgraph::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -386,7 +471,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 1,
-                1,
+                disk_percentage(&config, split_snapshot_supergraph::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             ),
@@ -398,7 +483,7 @@ where
                 tracker.clone(),
                 token.clone(),
                 1,
-                1,
+                disk_percentage(&config, split_snapshot_rebase_batch::CACHE_NAME),
                 s3_layers.clone(),
                 config.persister_mode,
             )
@@ -417,37 +502,66 @@ where
             split_snapshot_subgraph_cache.clone(),
             split_snapshot_supergraph_cache.clone(),
             split_snapshot_rebase_batch_cache.clone(),
+            config
+                .cache_updates_concurrency
+                .unwrap_or(DEFAULT_CACHE_UPDATES_CONCURRENCY),
             token.clone(),
         )
         .await?;
         tracker.spawn(cache_updates_task.run());
 
-        let persister_task = PersisterTask::create(
-            rx,
-            pg_pool.clone(),
-            &nats_client,
-            instance_id,
-            cache_config.disk_path().to_path_buf(), // Use cache disk path as base
-            token.clone(),
-            s3_layers.clone(),
-            config.persister_mode,
-        )
-        .await?;
-        tracker.spawn(persister_task.run());
+        // A read only layer db never writes, so has nothing to persist
+        if config.read_only {
+            info!("layer db is read only, not starting the persister");
+        } else {
+            let persister_task = PersisterTask::create(
+                rx,
+                pg_pool.clone(),
+                &nats_client,
+                instance_id,
+                cache_config.disk_path().to_path_buf(), // Use cache disk path as base
+                token.clone(),
+                s3_layers.clone(),
+                config.persister_mode,
+            )
+            .await?;
+            tracker.spawn(persister_task.run());
+        }
 
-        let cas = CasDb::new(cas_cache, persister_client.clone());
+        // Shared by every cache which can be prewarmed, so that prewarming several at once is
+        // still bounded
+        let prewarm_semaphore = Arc::new(Semaphore::new(
+            config
+                .prewarm_concurrency
+                .unwrap_or(DEFAULT_PREWARM_CONCURRENCY)
+                .max(1),
+        ));
+
+        let cas = CasDb::new(
+            cas_cache,
+            persister_client.clone(),
+            prewarm_semaphore.clone(),
+        );
         let change_batch = ChangeBatchDb::new(change_batch_cache, persister_client.clone());
         let encrypted_secret =
             EncryptedSecretDb::new(encrypted_secret_cache, persister_client.clone());
         let func_run = FuncRunLayerDb::new(func_run_cache, persister_client.clone());
         let func_run_log = FuncRunLogLayerDb::new(func_run_log_cache, persister_client.clone());
-        let workspace_snapshot = WorkspaceSnapshotDb::new(snapshot_cache, persister_client.clone());
+        let workspace_snapshot = WorkspaceSnapshotDb::new(
+            snapshot_cache,
+            persister_client.clone(),
+            prewarm_semaphore.clone(),
+        );
         let rebase_batch = RebaseBatchDb::new(rebase_batch_cache, persister_client.clone());
-        let split_snapshot_subgraph =
-            SplitSnapshotSubGraphDb::new(split_snapshot_subgraph_cache, persister_client.clone());
+        let split_snapshot_subgraph = SplitSnapshotSubGraphDb::new(
+            split_snapshot_subgraph_cache,
+            persister_client.clone(),
+            prewarm_semaphore.clone(),
+        );
         let split_snapshot_supergraph = SplitSnapshotSuperGraphDb::new(
             split_snapshot_supergraph_cache,
             persister_client.clone(),
+            prewarm_semaphore,
         );
         let split_snapshot_rebase_batch = SplitSnapshotRebaseBatchDb::new(
             split_snapshot_rebase_batch_cache,
@@ -486,6 +600,60 @@ where
         &self.nats_client
     }
 
+    /// Probes the Postgres and NATS connections backing this [`LayerDb`], reporting the health of
+    /// each.
+    pub async fn health(&self) -> LayerDbHealth {
+        let (postgres, nats) = tokio::join!(
+            health::probe(HEALTH_PROBE_TIMEOUT, self.pg_pool.test_connection()),
+            health::probe(HEALTH_PROBE_TIMEOUT, self.nats_client.flush()),
+        );
+
+        LayerDbHealth { postgres, nats }
+    }
+
+    /// Reports where the reads of each cache have been served from, keyed by cache name.
+    pub fn cache_metrics(&self) -> HashMap<String, LayerCacheMetrics> {
+        [
+            (self.cas.cache.name(), self.cas.cache.metrics()),
+            (
+                self.change_batch.cache.name(),
+                self.change_batch.cache.metrics(),
+            ),
+            (
+                self.encrypted_secret.cache.name(),
+                self.encrypted_secret.cache.metrics(),
+            ),
+            (self.func_run.cache.name(), self.func_run.cache.metrics()),
+            (
+                self.func_run_log.cache.name(),
+                self.func_run_log.cache.metrics(),
+            ),
+            (
+                self.rebase_batch.cache.name(),
+                self.rebase_batch.cache.metrics(),
+            ),
+            (
+                self.workspace_snapshot.cache.name(),
+                self.workspace_snapshot.cache.metrics(),
+            ),
+            (
+                self.split_snapshot_subgraph.cache.name(),
+                self.split_snapshot_subgraph.cache.metrics(),
+            ),
+            (
+                self.split_snapshot_supergraph.cache.name(),
+                self.split_snapshot_supergraph.cache.metrics(),
+            ),
+            (
+                self.split_snapshot_rebase_batch.cache.name(),
+                self.split_snapshot_rebase_batch.cache.metrics(),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, metrics)| (name.to_string(), metrics))
+        .collect()
+    }
+
     pub fn persister_client(&self) -> &PersisterClient {
         &self.persister_client
     }
@@ -534,6 +702,82 @@ where
         &self.split_snapshot_rebase_batch
     }
 
+    /// Drops a key from the memory and disk tiers of every cache, here and on peer instances,
+    /// while keeping it in pg. This lets a value which was cached in a bad state, such as a
+    /// corrupted snapshot, be read afresh from pg without knowing which cache holds it.
+    ///
+    /// Returns once every eviction has been sent to peers.
+    #[instrument(
+        name = "layer_db.evict",
+        level = "info",
+        skip_all,
+        fields(
+            si.layer_cache.key = key,
+        )
+    )]
+    pub async fn evict(&self, key: &str, tenancy: Tenancy, actor: Actor) -> LayerDbResult<()> {
+        self.persister_client.ensure_writable()?;
+
+        self.cas.cache.remove_from_memory(key);
+        self.change_batch.cache.remove_from_memory(key);
+        self.encrypted_secret.cache.remove_from_memory(key);
+        self.func_run.cache.remove_from_memory(key);
+        self.func_run_log.cache.remove_from_memory(key);
+        self.rebase_batch.cache.remove_from_memory(key);
+        self.workspace_snapshot.cache.remove_from_memory(key);
+        self.split_snapshot_subgraph.cache.remove_from_memory(key);
+        self.split_snapshot_supergraph.cache.remove_from_memory(key);
+        self.split_snapshot_rebase_batch
+            .cache
+            .remove_from_memory(key);
+
+        let evictions = [
+            (cas::DBNAME, LayeredEventKind::CasEvict),
+            (change_batch::DBNAME, LayeredEventKind::ChangeBatchEvict),
+            (
+                encrypted_secret::DBNAME,
+                LayeredEventKind::EncryptedSecretEvict,
+            ),
+            (func_run::DBNAME, LayeredEventKind::FuncRunEvict),
+            (func_run_log::DBNAME, LayeredEventKind::FuncRunLogEvict),
+            (rebase_batch::DBNAME, LayeredEventKind::RebaseBatchEvict),
+            (workspace_snapshot::DBNAME, LayeredEventKind::SnapshotEvict),
+            (
+                split_snapshot_subgraph::DBNAME,
+                LayeredEventKind::SplitSnapshotSubGraphEvict,
+            ),
+            (
+                split_snapshot_supergraph::DBNAME,
+                LayeredEventKind::SplitSnapshotSuperGraphEvict,
+            ),
+            (
+                split_snapshot_rebase_batch::DBNAME,
+                LayeredEventKind::SplitRebaseBatchEvict,
+            ),
+        ];
+        let mut readers = Vec::with_capacity(evictions.len());
+        for (db_name, event_kind) in evictions {
+            let event = LayeredEvent::new(
+                event_kind,
+                Arc::new(db_name.to_string()),
+                key.into(),
+                Arc::new(Vec::new()),
+                Arc::new(db_name.to_string()),
+                None,
+                tenancy,
+                actor,
+            );
+            readers.push(self.persister_client.evict_memory_only_event(event)?);
+        }
+        for reader in readers {
+            if let PersistStatus::Error(err) = reader.get_status().await? {
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn instance_id(&self) -> Ulid {
         self.instance_id
     }
@@ -653,4 +897,70 @@ pub struct LayerDbConfig {
     pub cache_config: CacheConfig,
     pub object_storage_config: crate::s3::ObjectStorageConfig,
     pub persister_mode: PersisterMode,
+    /// The number of cache updates from other instances to apply concurrently. Updates for the
+    /// same key are always applied in order. Defaults to
+    /// [`DEFAULT_CACHE_UPDATES_CONCURRENCY`].
+    #[serde(default)]
+    pub cache_updates_concurrency: Option<usize>,
+    /// Overrides the share of the usable disk given to a cache, as a percentage, keyed by cache
+    /// name. Caches which are not listed keep their share from [`DEFAULT_DISK_PERCENTAGES`].
+    #[serde(default)]
+    pub disk_percentages: HashMap<String, u8>,
+    /// The number of keys to read concurrently when prewarming caches, shared across all of
+    /// them. Defaults to [`DEFAULT_PREWARM_CONCURRENCY`].
+    #[serde(default)]
+    pub prewarm_concurrency: Option<usize>,
+    /// When set, reads are served as usual but every write or eviction through the layer db is
+    /// refused with [`LayerDbError::ReadOnly`](crate::LayerDbError::ReadOnly), and nothing is
+    /// persisted or published to other instances. Useful for read replicas.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_disk_percentages(disk_percentages: &[(&str, u8)]) -> LayerDbConfig {
+        LayerDbConfig {
+            disk_percentages: disk_percentages
+                .iter()
+                .map(|(name, percentage)| (name.to_string(), *percentage))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn disk_percentage_defaults_when_not_overridden() {
+        let config = LayerDbConfig::default();
+
+        for (name, percentage) in DEFAULT_DISK_PERCENTAGES {
+            assert_eq!(percentage, disk_percentage(&config, name));
+        }
+        assert_eq!(100, total_disk_percentage(&config));
+        assert_eq!(0, disk_percentage(&config, "not-a-cache"));
+    }
+
+    #[test]
+    fn disk_percentage_overrides_only_the_listed_caches() {
+        let config = config_with_disk_percentages(&[(workspace_snapshot::CACHE_NAME, 20)]);
+
+        assert_eq!(20, disk_percentage(&config, workspace_snapshot::CACHE_NAME));
+        assert_eq!(24, disk_percentage(&config, cas::CACHE_NAME));
+        assert_eq!(70, total_disk_percentage(&config));
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn validation_only_warns_about_overcommitted_and_unknown_caches() {
+        let config = config_with_disk_percentages(&[
+            (workspace_snapshot::CACHE_NAME, 80),
+            ("not-a-cache", 50),
+        ]);
+
+        // Unknown caches are ignored, and going over 100% only warns
+        assert_eq!(130, total_disk_percentage(&config));
+        assert!(validate_config(&config).is_ok());
+    }
 }
@@ -1,4 +1,12 @@
-use std::sync::Arc;
+use std::{
+    collections::hash_map::DefaultHasher,
+    future::Future,
+    hash::{
+        Hash,
+        Hasher,
+    },
+    sync::Arc,
+};
 
 use serde::{
     Serialize,
@@ -11,7 +19,11 @@ use si_events::{
     change_batch::ChangeBatch,
 };
 use telemetry::prelude::*;
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::{
+    self,
+    UnboundedReceiver,
+    UnboundedSender,
+};
 use tokio_util::{
     sync::CancellationToken,
     task::TaskTracker,
@@ -55,6 +67,7 @@ pub struct CacheUpdatesTask<
     split_supergraph_cache: Arc<LayerCache<Arc<SplitSupergraphValue>>>,
     split_rebase_batch_cache: Arc<LayerCache<Arc<SplitRebaseBatchValue>>>,
     event_channel: UnboundedReceiver<LayeredEvent>,
+    concurrency: usize,
     shutdown_token: CancellationToken,
     tracker: TaskTracker,
 }
@@ -102,6 +115,7 @@ where
         split_subgraph_cache: Arc<LayerCache<Arc<SplitSubgraphValue>>>,
         split_supergraph_cache: Arc<LayerCache<Arc<SplitSupergraphValue>>>,
         split_snapshot_rebase_batch_cache: Arc<LayerCache<Arc<SplitRebaseBatchValue>>>,
+        concurrency: usize,
         shutdown_token: CancellationToken,
     ) -> LayerDbResult<Self> {
         let tracker = TaskTracker::new();
@@ -123,6 +137,7 @@ where
             split_supergraph_cache,
             split_rebase_batch_cache: split_snapshot_rebase_batch_cache,
             event_channel,
+            concurrency,
             shutdown_token,
             tracker,
         })
@@ -142,22 +157,77 @@ where
         debug!(task = Self::NAME, "shutdown complete");
     }
 
+    /// Applies updates as they arrive, in order for each key and concurrently across keys.
+    ///
+    /// Updates to a key must be applied in order, or a stale write could land after a newer one
+    /// (or after an eviction).
     pub async fn process_messages(&mut self) {
+        let cache_update_task = Arc::new(CacheUpdateTask::new(
+            self.cas_cache.clone(),
+            self.change_batch_cache.clone(),
+            self.encrypted_secret_cache.clone(),
+            self.func_run_cache.clone(),
+            self.func_run_log_cache.clone(),
+            self.snapshot_cache.clone(),
+            self.rebase_batch_cache.clone(),
+            self.split_subgraph_cache.clone(),
+            self.split_supergraph_cache.clone(),
+            self.split_rebase_batch_cache.clone(),
+        ));
+        // Dropped with this future on shutdown, which lets the lanes drain and exit
+        let lanes = KeyedLanes::spawn(self.concurrency, &self.tracker, move |event| {
+            let cache_update_task = cache_update_task.clone();
+            async move { cache_update_task.run(event).await }
+        });
+
         while let Some(event) = self.event_channel.recv().await {
-            let cache_update_task = CacheUpdateTask::new(
-                self.cas_cache.clone(),
-                self.change_batch_cache.clone(),
-                self.encrypted_secret_cache.clone(),
-                self.func_run_cache.clone(),
-                self.func_run_log_cache.clone(),
-                self.snapshot_cache.clone(),
-                self.rebase_batch_cache.clone(),
-                self.split_subgraph_cache.clone(),
-                self.split_supergraph_cache.clone(),
-                self.split_rebase_batch_cache.clone(),
+            let key = event.key.clone();
+            lanes.send(&key, event);
+        }
+    }
+}
+
+/// Applies items with a fixed number of concurrent lanes, where all items for a key are applied
+/// on the same lane, in the order they were sent.
+struct KeyedLanes<T> {
+    lanes: Vec<UnboundedSender<T>>,
+}
+
+impl<T> KeyedLanes<T>
+where
+    T: Send + 'static,
+{
+    fn spawn<F, Fut>(concurrency: usize, tracker: &TaskTracker, apply: F) -> Self
+    where
+        F: Fn(T) -> Fut + Clone + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let lanes = (0..concurrency.max(1))
+            .map(|_| {
+                let (tx, mut rx) = mpsc::unbounded_channel();
+                let apply = apply.clone();
+                tracker.spawn(async move {
+                    while let Some(item) = rx.recv().await {
+                        apply(item).await;
+                    }
+                });
+                tx
+            })
+            .collect();
+
+        Self { lanes }
+    }
+
+    fn send(&self, key: &str, item: T) {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let lane = (hasher.finish() % self.lanes.len() as u64) as usize;
+
+        if self.lanes[lane].send(item).is_err() {
+            warn!(
+                task = "LayerDB::CacheUpdatesTask",
+                "cache update lane closed; dropping update"
             );
-            self.tracker
-                .spawn(async move { cache_update_task.run(event).await });
         }
     }
 }
@@ -223,6 +293,9 @@ where
 
     async fn process_message(&self, event: LayeredEvent) -> LayerDbResult<()> {
         match event.event_kind {
+            crate::event::LayeredEventKind::CasEvict => {
+                self.cas_cache.evict_from_cache_updates(event.key);
+            }
             crate::event::LayeredEventKind::CasInsertion => {
                 if !self.cas_cache.contains(&event.key) {
                     let serialized_value =
@@ -242,6 +315,10 @@ where
             crate::event::LayeredEventKind::ChangeBatchEvict => {
                 self.change_batch_cache.evict_from_cache_updates(event.key);
             }
+            crate::event::LayeredEventKind::EncryptedSecretEvict => {
+                self.encrypted_secret_cache
+                    .evict_from_cache_updates(event.key);
+            }
             crate::event::LayeredEventKind::EncryptedSecretInsertion => {
                 if !self.encrypted_secret_cache.contains(&event.key) {
                     let serialized_value =
@@ -256,6 +333,12 @@ where
                 self.func_run_cache
                     .insert_or_update_from_cache_updates(event.key, serialized_value);
             }
+            crate::event::LayeredEventKind::FuncRunEvict => {
+                self.func_run_cache.evict_from_cache_updates(event.key);
+            }
+            crate::event::LayeredEventKind::FuncRunLogEvict => {
+                self.func_run_log_cache.evict_from_cache_updates(event.key);
+            }
             crate::event::LayeredEventKind::FuncRunLogWrite => {
                 let serialized_value =
                     Arc::try_unwrap(event.payload.value).unwrap_or_else(|arc| (*arc).clone());
@@ -338,3 +421,77 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{
+            Mutex,
+            atomic::{
+                AtomicUsize,
+                Ordering,
+            },
+        },
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn keyed_lanes_are_concurrent_across_keys_and_ordered_per_key() {
+        let tracker = TaskTracker::new();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let applied: Arc<Mutex<HashMap<String, Vec<usize>>>> = Default::default();
+
+        let lanes = KeyedLanes::spawn(8, &tracker, {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            let applied = applied.clone();
+            move |(key, seq): (String, usize)| {
+                let in_flight = in_flight.clone();
+                let max_in_flight = max_in_flight.clone();
+                let applied = applied.clone();
+                async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    applied
+                        .lock()
+                        .expect("lock poisoned")
+                        .entry(key)
+                        .or_default()
+                        .push(seq);
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                }
+            }
+        });
+
+        let keys: Vec<String> = (0..32).map(|i| format!("key-{i}")).collect();
+        for seq in 0..10 {
+            for key in &keys {
+                lanes.send(key, (key.clone(), seq));
+            }
+        }
+        drop(lanes);
+        tracker.close();
+        tracker.wait().await;
+
+        assert!(
+            max_in_flight.load(Ordering::SeqCst) > 1,
+            "expected updates for different keys to be applied concurrently"
+        );
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 8);
+
+        let applied = applied.lock().expect("lock poisoned");
+        assert_eq!(keys.len(), applied.len());
+        for key in &keys {
+            assert_eq!(
+                (0..10).collect::<Vec<_>>(),
+                applied[key],
+                "updates for {key} were applied out of order"
+            );
+        }
+    }
+}
@@ -15,6 +15,7 @@ use si_events::{
     WebEvent,
 };
 use telemetry::prelude::*;
+use tokio::sync::Semaphore;
 
 use super::serialize;
 use crate::{
@@ -42,16 +43,22 @@ where
 {
     pub cache: Arc<LayerCache<Arc<V>>>,
     persister_client: PersisterClient,
+    prewarm_semaphore: Arc<Semaphore>,
 }
 
 impl<V> CasDb<V>
 where
     V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
 {
-    pub fn new(cache: Arc<LayerCache<Arc<V>>>, persister_client: PersisterClient) -> Self {
+    pub fn new(
+        cache: Arc<LayerCache<Arc<V>>>,
+        persister_client: PersisterClient,
+        prewarm_semaphore: Arc<Semaphore>,
+    ) -> Self {
         CasDb {
             cache,
             persister_client,
+            prewarm_semaphore,
         }
     }
 
@@ -63,6 +70,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<(ContentHash, PersisterStatusReader)> {
+        self.persister_client.ensure_writable()?;
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
         let key = ContentHash::new(&postcard_value);
         let cache_key: Arc<str> = key.to_string().into();
@@ -85,10 +93,88 @@ where
         Ok((key, reader))
     }
 
+    /// Writes many values at once, sending them to the persister as a single batch rather than
+    /// one at a time. The batch is persisted with multi-row inserts and published together. The
+    /// keys are returned in the same order as the values.
+    #[instrument(name = "cas.write_many", level = "debug", skip_all, fields(count = values.len()))]
+    pub fn write_many(
+        &self,
+        values: Vec<Arc<V>>,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<(Vec<ContentHash>, PersisterStatusReader)> {
+        self.persister_client.ensure_writable()?;
+        let mut keys = Vec::with_capacity(values.len());
+        let mut events = Vec::with_capacity(values.len());
+        for value in values {
+            let (postcard_value, size_hint) = serialize::to_vec(&value)?;
+            let key = ContentHash::new(&postcard_value);
+            let cache_key: Arc<str> = key.to_string().into();
+
+            self.cache.insert(cache_key.clone(), value, size_hint);
+
+            events.push(LayeredEvent::new(
+                LayeredEventKind::CasInsertion,
+                Arc::new(DBNAME.to_string()),
+                cache_key,
+                Arc::new(postcard_value),
+                Arc::new("cas".to_string()),
+                None,
+                tenancy,
+                actor,
+            ));
+            keys.push(key);
+        }
+        let reader = self.persister_client.write_events(events)?;
+
+        Ok((keys, reader))
+    }
+
+    /// Drops a value from the memory and disk tiers, here and on peer instances, while keeping it
+    /// in pg. This lets a value which was cached in a bad state be read afresh from pg.
+    #[instrument(
+        name = "cas.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.cas.address = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: &ContentHash,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::CasEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new("cas".to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     pub async fn read(&self, key: &ContentHash) -> LayerDbResult<Option<Arc<V>>> {
         self.cache.get(key.to_string().into()).await
     }
 
+    /// Reads the given keys into the cache ahead of demand, such as when an instance starts.
+    /// Returns, for each key, whether it was found or the error which prevented reading it.
+    pub async fn prewarm(&self, keys: &[String]) -> HashMap<String, LayerDbResult<bool>> {
+        self.cache.prewarm(keys, &self.prewarm_semaphore).await
+    }
+
     /// We often need to extract the value from the arc by cloning it (although
     /// this should be avoided for large values). This will do that, and also
     /// helpfully convert the value to the type we want to deal with
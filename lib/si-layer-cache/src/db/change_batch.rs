@@ -58,6 +58,7 @@ impl ChangeBatchDb {
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<(ChangeBatchAddress, PersisterStatusReader)> {
+        self.persister_client.ensure_writable()?;
         let value_clone = value.clone();
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
 
@@ -151,6 +152,7 @@ impl ChangeBatchDb {
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
         let cache_key = key.to_string();
         self.cache.remove_from_memory(&cache_key);
 
@@ -169,6 +171,39 @@ impl ChangeBatchDb {
         Ok(reader)
     }
 
+    #[instrument(
+        name = "change_batch.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.change_batch.address = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: &ChangeBatchAddress,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::ChangeBatchEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new(SORT_KEY.to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     #[instrument(
         name = "change_batch.read_bytes_from_durable_storage",
         level = "debug",
@@ -14,6 +14,7 @@ use si_events::{
     Tenancy,
     WebEvent,
 };
+use telemetry::prelude::*;
 
 use super::serialize;
 use crate::{
@@ -66,6 +67,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
 
         let cache_key: Arc<str> = key.to_string().into();
@@ -88,6 +90,39 @@ where
         Ok(reader)
     }
 
+    #[instrument(
+        name = "encrypted_secret.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.encrypted_secret.key = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: &EncryptedSecretKey,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::EncryptedSecretEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new(SORT_KEY.to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     pub async fn read(&self, key: &EncryptedSecretKey) -> LayerDbResult<Option<Arc<V>>> {
         self.cache.get(key.to_string().into()).await
     }
@@ -29,7 +29,10 @@ use crate::{
         LayeredEventPayload,
     },
     layer_cache::LayerCache,
-    persister::PersisterClient,
+    persister::{
+        PersisterClient,
+        PersisterStatusReader,
+    },
     pg::PgLayer,
 };
 
@@ -157,6 +160,7 @@ impl FuncRunLayerDb {
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<()> {
+        self.persister_client.ensure_writable()?;
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
         let cache_key: Arc<str> = value.id().to_string().into();
         let sort_key: Arc<str> = value.tenancy().workspace_pk.to_string().into();
@@ -180,6 +184,39 @@ impl FuncRunLayerDb {
         Ok(())
     }
 
+    #[instrument(
+        name = "func_run.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.func_run.id = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: FuncRunId,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::FuncRunEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new(tenancy.workspace_pk.to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     // NOTE(victor): Migrated to si_db::FuncRunDb
     #[instrument(level = "debug", skip_all)]
     pub async fn get_last_run_for_action_id_opt(
@@ -8,6 +8,7 @@ use si_events::{
     Tenancy,
     WebEvent,
 };
+use telemetry::prelude::*;
 
 use super::serialize;
 use crate::{
@@ -17,7 +18,10 @@ use crate::{
         LayeredEventKind,
     },
     layer_cache::LayerCache,
-    persister::PersisterClient,
+    persister::{
+        PersisterClient,
+        PersisterStatusReader,
+    },
 };
 
 pub const DBNAME: &str = "func_run_logs";
@@ -57,6 +61,7 @@ impl FuncRunLogLayerDb {
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<()> {
+        self.persister_client.ensure_writable()?;
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
         let cache_key: Arc<str> = value.id().to_string().into();
         let sort_key: Arc<str> = value.tenancy().workspace_pk.to_string().into();
@@ -83,6 +88,39 @@ impl FuncRunLogLayerDb {
         Ok(())
     }
 
+    #[instrument(
+        name = "func_run_log.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.func_run_log.id = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: FuncRunLogId,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::FuncRunLogEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new(tenancy.workspace_pk.to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     // NOTE(victor): Migrated to si_db::FuncRunLogsDb
     pub async fn get_for_func_run_id(
         &self,
@@ -0,0 +1,122 @@
+//! Health reporting for the backing services of a [`LayerDb`](super::LayerDb).
+
+use std::{
+    fmt,
+    future::Future,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// How long to wait on a single health probe before reporting it as unhealthy.
+pub const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The health of a single service backing a [`LayerDb`](super::LayerDb).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum ComponentHealth {
+    /// The probe succeeded, taking `latency_ms` to do so.
+    #[serde(rename_all = "camelCase")]
+    Healthy { latency_ms: u64 },
+    /// The probe failed or timed out.
+    Unhealthy { error: String },
+}
+
+impl ComponentHealth {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy { .. })
+    }
+}
+
+/// The health of each service backing a [`LayerDb`](super::LayerDb).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerDbHealth {
+    pub postgres: ComponentHealth,
+    pub nats: ComponentHealth,
+}
+
+impl LayerDbHealth {
+    /// Returns `true` if every backing service is healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.postgres.is_healthy() && self.nats.is_healthy()
+    }
+}
+
+/// Runs a single health probe, reporting it as unhealthy if it fails or exceeds `timeout`.
+pub async fn probe<F, E>(timeout: Duration, probe: F) -> ComponentHealth
+where
+    F: Future<Output = Result<(), E>>,
+    E: fmt::Display,
+{
+    let started_at = Instant::now();
+    match tokio::time::timeout(timeout, probe).await {
+        Ok(Ok(())) => ComponentHealth::Healthy {
+            latency_ms: u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+        },
+        Ok(Err(err)) => ComponentHealth::Unhealthy {
+            error: err.to_string(),
+        },
+        Err(_) => ComponentHealth::Unhealthy {
+            error: format!("timed out after {}ms", timeout.as_millis()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn successful_probe_is_healthy() {
+        let health = probe(HEALTH_PROBE_TIMEOUT, async { Ok::<_, String>(()) }).await;
+
+        assert!(health.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn failing_probe_is_unhealthy() {
+        let health = probe(HEALTH_PROBE_TIMEOUT, async {
+            Err::<(), _>("connection refused".to_string())
+        })
+        .await;
+
+        assert_eq!(
+            ComponentHealth::Unhealthy {
+                error: "connection refused".to_string()
+            },
+            health
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_probe_is_unhealthy() {
+        let health = probe(Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, String>(())
+        })
+        .await;
+
+        assert!(!health.is_healthy());
+    }
+
+    #[test]
+    fn report_reflects_failing_component() {
+        let health = LayerDbHealth {
+            postgres: ComponentHealth::Healthy { latency_ms: 1 },
+            nats: ComponentHealth::Unhealthy {
+                error: "disconnected".to_string(),
+            },
+        };
+
+        assert!(!health.is_healthy());
+        assert!(health.postgres.is_healthy());
+        assert!(!health.nats.is_healthy());
+    }
+}
@@ -61,6 +61,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<(RebaseBatchAddress, PersisterStatusReader)> {
+        self.persister_client.ensure_writable()?;
         let value_clone = value.clone();
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
 
@@ -154,6 +155,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
         let cache_key = key.to_string();
         self.cache.remove_from_memory(&cache_key);
 
@@ -172,6 +174,39 @@ where
         Ok(reader)
     }
 
+    #[instrument(
+        name = "rebase_batch.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.rebase_batch.address = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: &RebaseBatchAddress,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::RebaseBatchEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new("rebase_batch".to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     #[instrument(
         name = "rebase_batch.read_bytes_from_durable_storage",
         level = "debug",
@@ -61,6 +61,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<(SplitSnapshotRebaseBatchAddress, PersisterStatusReader)> {
+        self.persister_client.ensure_writable()?;
         let value_clone = value.clone();
         let (postcard_value, size_hint) = serialize::to_vec(&value)?;
 
@@ -153,6 +154,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
         let cache_key = key.to_string();
         self.cache.remove_from_memory(&cache_key);
 
@@ -171,6 +173,39 @@ where
         Ok(reader)
     }
 
+    #[instrument(
+        name = "split_snapshot_rebase_batch.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.split_snapshot_rebase_batch.address = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: &SplitSnapshotRebaseBatchAddress,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::SplitRebaseBatchEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new("split_snapshot_rebase_batch".to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     #[instrument(
         name = "split_snapshot_rebase_batch.read_bytes_from_durable_storage",
         level = "debug",
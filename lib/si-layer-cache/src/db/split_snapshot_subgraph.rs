@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     sync::Arc,
     time::Instant,
 };
@@ -14,6 +15,7 @@ use si_events::{
     WorkspaceSnapshotAddress,
 };
 use telemetry::prelude::*;
+use tokio::sync::Semaphore;
 
 use super::serialize;
 use crate::{
@@ -40,16 +42,22 @@ where
 {
     pub cache: Arc<LayerCache<Arc<V>>>,
     persister_client: PersisterClient,
+    prewarm_semaphore: Arc<Semaphore>,
 }
 
 impl<V> SplitSnapshotSubGraphDb<V>
 where
     V: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
 {
-    pub fn new(cache: Arc<LayerCache<Arc<V>>>, persister_client: PersisterClient) -> Self {
+    pub fn new(
+        cache: Arc<LayerCache<Arc<V>>>,
+        persister_client: PersisterClient,
+        prewarm_semaphore: Arc<Semaphore>,
+    ) -> Self {
         Self {
             cache,
             persister_client,
+            prewarm_semaphore,
         }
     }
 
@@ -63,6 +71,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<(WorkspaceSnapshotAddress, PersisterStatusReader)> {
+        self.persister_client.ensure_writable()?;
         let span = Span::current();
         let write_wait = Instant::now();
         let value_clone = value.clone();
@@ -105,6 +114,12 @@ where
         self.cache.get(key.to_string().into()).await
     }
 
+    /// Reads the given keys into the cache ahead of demand, such as when an instance starts.
+    /// Returns, for each key, whether it was found or the error which prevented reading it.
+    pub async fn prewarm(&self, keys: &[String]) -> HashMap<String, LayerDbResult<bool>> {
+        self.cache.prewarm(keys, &self.prewarm_semaphore).await
+    }
+
     #[instrument(
         name = "split_snapshot_subgraph.read_wait_for_memory",
         level = "debug",
@@ -159,6 +174,7 @@ where
         tenancy: Tenancy,
         actor: Actor,
     ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
         let cache_key = key.to_string();
         self.cache.remove_from_memory(&cache_key);
 
@@ -177,6 +193,39 @@ where
         Ok(reader)
     }
 
+    #[instrument(
+        name = "split_snapshot_subgraph.evict_memory_only",
+        level = "debug",
+        skip_all,
+        fields(
+            si.split_snapshot_subgraph.address = %key,
+        )
+    )]
+    pub fn evict_memory_only(
+        &self,
+        key: &WorkspaceSnapshotAddress,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        self.persister_client.ensure_writable()?;
+        let cache_key = key.to_string();
+        self.cache.remove_from_memory(&cache_key);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::SplitSnapshotSubGraphEvict,
+            Arc::new(DBNAME.to_string()),
+            cache_key.into(),
+            Arc::new(Vec::new()),
+            Arc::new("split_snapshot_subgraph".to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.evict_memory_only_event(event)?;
+
+        Ok(reader)
+    }
+
     /// Used for when we want to get the exact bytes we're storing for this
     /// snapshot, useful when converting an out of date snapshot into a new one
     #[instrument(
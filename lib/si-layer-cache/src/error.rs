@@ -300,6 +300,8 @@ pub enum LayerDbError {
     PgPool(#[from] PgPoolError),
     #[error("postcard error: {0}")]
     Postcard(#[from] postcard::Error),
+    #[error("layer db is read only")]
+    ReadOnly,
     #[error("failed to create retry queue directory: {0}")]
     RetryQueueDirCreate(#[source] std::io::Error),
     #[error("failed to read retry queue directory: {0}")]
@@ -326,6 +328,8 @@ pub enum LayerDbError {
     S3Put(String),
     #[error("S3 queue processor error: {0}")]
     S3QueueProcessor(String),
+    #[error("semaphore acquire error: {0}")]
+    SemaphoreAcquire(#[from] tokio::sync::AcquireError),
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("tokio oneshot recv error: {0}")]
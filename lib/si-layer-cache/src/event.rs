@@ -13,7 +13,10 @@ use bytes::{
     BytesMut,
 };
 use chrono::prelude::*;
-use futures::StreamExt;
+use futures::{
+    StreamExt,
+    TryStreamExt,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -68,6 +71,8 @@ use crate::{
 };
 
 const DEFAULT_CHUNK_SIZE: usize = 128 * 1024;
+// How many events of a batch are published and awaiting acknowledgement at once
+const PUBLISH_MANY_CONCURRENCY: usize = 32;
 const MAX_BYTES: i64 = 1024 * 1024; // mirrors settings in Synadia NATs
 
 const HEADER_EVENT_ID: &str = "X-EVENT-ID";
@@ -100,10 +105,14 @@ pub use si_id::LayeredEventId;
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum LayeredEventKind {
+    CasEvict,
     CasInsertion,
     ChangeBatchEvict,
     ChangeBatchWrite,
+    EncryptedSecretEvict,
     EncryptedSecretInsertion,
+    FuncRunEvict,
+    FuncRunLogEvict,
     FuncRunLogWrite,
     FuncRunWrite,
     Raw,
@@ -241,6 +250,19 @@ impl LayeredEventClient {
         });
         Ok(join_handle)
     }
+
+    /// Publishes a batch of events, with a bounded number in flight at once, and resolves once
+    /// every event has been published.
+    pub async fn publish_many(&self, events: Vec<Arc<LayeredEvent>>) -> LayerDbResult<()> {
+        futures::stream::iter(events)
+            .map(|event| async move {
+                let _ = self.publish(event).await?.await?;
+                Ok::<_, LayerDbError>(())
+            })
+            .buffer_unordered(PUBLISH_MANY_CONCURRENCY)
+            .try_collect()
+            .await
+    }
 }
 
 pub struct LayeredEventServer {
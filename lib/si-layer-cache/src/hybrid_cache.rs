@@ -357,6 +357,15 @@ impl CacheConfig {
         self
     }
 
+    /// Replaces the root directory which the disk path is built from, so that multiple instances
+    /// on one host can each be given their own directory.
+    ///
+    /// This must be set before any calls to [`Self::with_path_join`], which append to it.
+    pub fn with_disk_path(mut self, disk_path: impl Into<PathBuf>) -> Self {
+        self.disk_path = disk_path.into();
+        self
+    }
+
     /// Appends an additional path to the existing disk path
     pub fn with_path_join(mut self, path: impl AsRef<Path>) -> Self {
         self.disk_path = self.disk_path.join(path);
@@ -3,10 +3,17 @@ use std::{
     fmt::Display,
     hash::Hash,
     str::FromStr,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+    },
 };
 
 use serde::{
+    Deserialize,
     Serialize,
     de::DeserializeOwned,
 };
@@ -20,6 +27,7 @@ use si_events::{
 use si_runtime::DedicatedExecutor;
 use telemetry::prelude::*;
 use telemetry_utils::monotonic;
+use tokio::sync::Semaphore;
 use tokio_util::{
     sync::CancellationToken,
     task::TaskTracker,
@@ -43,6 +51,53 @@ use crate::{
     s3::S3Layer,
 };
 
+/// Cumulative counts of where the reads made through [`LayerCache::get`] were served from.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerCacheMetrics {
+    /// Reads served from the in-memory tier.
+    pub memory_hits: u64,
+    /// Reads served from the on-disk tier.
+    pub disk_hits: u64,
+    /// Reads which fell through to durable storage (pg or s3, depending on the persister mode)
+    /// and were found there.
+    pub backend_hits: u64,
+    /// Reads which were not found anywhere.
+    pub misses: u64,
+}
+
+impl LayerCacheMetrics {
+    pub fn total(&self) -> u64 {
+        self.memory_hits + self.disk_hits + self.backend_hits + self.misses
+    }
+
+    /// The fraction of reads served from memory, or `None` if there have been no reads.
+    pub fn memory_hit_ratio(&self) -> Option<f64> {
+        self.ratio(self.memory_hits)
+    }
+
+    /// The fraction of reads served from memory or disk, without falling through to durable
+    /// storage, or `None` if there have been no reads.
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        self.ratio(self.memory_hits + self.disk_hits)
+    }
+
+    fn ratio(&self, count: u64) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some(count as f64 / total as f64),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct LayerCacheCounters {
+    memory_hits: AtomicU64,
+    disk_hits: AtomicU64,
+    backend_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
 #[derive(Debug, Clone)]
 pub struct LayerCache<V>
 where
@@ -56,6 +111,7 @@ where
     // NEW fields
     s3_layers: Option<Arc<HashMap<&'static str, S3Layer>>>,
     mode: PersisterMode,
+    counters: Arc<LayerCacheCounters>,
 }
 
 impl<V> LayerCache<V>
@@ -84,6 +140,7 @@ where
             compute_executor,
             s3_layers,
             mode,
+            counters: Arc::new(LayerCacheCounters::default()),
         }
         .into();
 
@@ -150,9 +207,19 @@ where
             cache_name = self.name.as_str()
         );
 
-        // Try memory/disk cache first
+        // Try memory/disk cache first, checking memory on its own so that we know which tier
+        // served the read
         let foyer_start = Instant::now();
-        if let Some(value) = self.cache.get(key.clone()).await {
+        let foyer_hit = match self.cache.get_from_memory(key.clone()).await {
+            Some(value) => Some((value, &self.counters.memory_hits)),
+            None => self
+                .cache
+                .get(key.clone())
+                .await
+                .map(|value| (value, &self.counters.disk_hits)),
+        };
+        if let Some((value, counter)) = foyer_hit {
+            counter.fetch_add(1, Ordering::Relaxed);
             histogram!(
                 layer_cache.read_latency_ms = foyer_start.elapsed().as_millis() as f64,
                 cache_name = self.name.as_str(),
@@ -336,6 +403,7 @@ where
                 );
 
                 let deserialized: V = serialize::from_bytes(&bytes)?;
+                self.counters.backend_hits.fetch_add(1, Ordering::Relaxed);
 
                 // Insert into cache for future reads
                 self.cache
@@ -357,6 +425,7 @@ where
                     cache.mode = ?self.mode,
                     "not found in any backend, returning None"
                 );
+                self.counters.misses.fetch_add(1, Ordering::Relaxed);
 
                 // Emit end-to-end metric for complete miss
                 histogram!(
@@ -653,10 +722,46 @@ where
         Ok(found_keys)
     }
 
+    /// Reads each of `keys` into the cache ahead of demand, holding a permit from `semaphore`
+    /// for each read so that prewarming can not swamp the backend.
+    ///
+    /// Returns, for each key, whether it was found or the error which prevented reading it.
+    pub async fn prewarm(
+        &self,
+        keys: &[String],
+        semaphore: &Semaphore,
+    ) -> HashMap<String, LayerDbResult<bool>> {
+        let reads = keys.iter().map(|key| async move {
+            let result: LayerDbResult<bool> = async {
+                let _permit = semaphore.acquire().await?;
+                Ok(self.get(key.as_str().into()).await?.is_some())
+            }
+            .await;
+            (key.clone(), result)
+        });
+
+        futures::future::join_all(reads).await.into_iter().collect()
+    }
+
     pub async fn deserialize_memory_value(&self, bytes: Arc<Vec<u8>>) -> LayerDbResult<V> {
         serialize::from_bytes_async(&bytes).await
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reports where the reads made through [`get`](Self::get) have been served from since this
+    /// cache was created.
+    pub fn metrics(&self) -> LayerCacheMetrics {
+        LayerCacheMetrics {
+            memory_hits: self.counters.memory_hits.load(Ordering::Relaxed),
+            disk_hits: self.counters.disk_hits.load(Ordering::Relaxed),
+            backend_hits: self.counters.backend_hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+        }
+    }
+
     pub fn cache(&self) -> Cache<V> {
         self.cache.clone()
     }
@@ -80,6 +80,7 @@ pub enum PersisterMode {
 #[derive(Debug)]
 pub enum PersistMessage {
     Write((LayeredEvent, PersisterStatusWriter)),
+    WriteMany((Vec<LayeredEvent>, PersisterStatusWriter)),
     Evict((LayeredEvent, PersisterStatusWriter)),
     EvictMemoryOnly((LayeredEvent, PersisterStatusWriter)),
 }
@@ -124,11 +125,26 @@ impl PersisterStatusReader {
 #[derive(Debug, Clone)]
 pub struct PersisterClient {
     tx: mpsc::UnboundedSender<PersistMessage>,
+    read_only: bool,
 }
 
 impl PersisterClient {
-    pub fn new(tx: mpsc::UnboundedSender<PersistMessage>) -> PersisterClient {
-        PersisterClient { tx }
+    /// Creates a client which sends persist messages over `tx`. When `read_only` is set, every
+    /// write or eviction is refused with [`LayerDbError::ReadOnly`] instead.
+    pub fn new(tx: mpsc::UnboundedSender<PersistMessage>, read_only: bool) -> PersisterClient {
+        PersisterClient { tx, read_only }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns [`LayerDbError::ReadOnly`] if this client refuses writes and evictions.
+    pub fn ensure_writable(&self) -> LayerDbResult<()> {
+        if self.read_only {
+            return Err(LayerDbError::ReadOnly);
+        }
+        Ok(())
     }
 
     fn get_status_channels(&self) -> (PersisterStatusWriter, PersisterStatusReader) {
@@ -140,6 +156,32 @@ impl PersisterClient {
     }
 
     pub fn write_event(&self, event: LayeredEvent) -> LayerDbResult<PersisterStatusReader> {
+        self.ensure_writable()?;
+        Self::record_insert(&event);
+
+        let (status_write, status_read) = self.get_status_channels();
+        self.tx
+            .send(PersistMessage::Write((event, status_write)))
+            .map_err(Box::new)?;
+        Ok(status_read)
+    }
+
+    /// Writes a batch of events with a single persister task. The returned status is only
+    /// finished once every event has been persisted, and reports the first error otherwise.
+    pub fn write_events(&self, events: Vec<LayeredEvent>) -> LayerDbResult<PersisterStatusReader> {
+        self.ensure_writable()?;
+        for event in &events {
+            Self::record_insert(event);
+        }
+
+        let (status_write, status_read) = self.get_status_channels();
+        self.tx
+            .send(PersistMessage::WriteMany((events, status_write)))
+            .map_err(Box::new)?;
+        Ok(status_read)
+    }
+
+    fn record_insert(event: &LayeredEvent) {
         let byte_size = event.payload.value.len();
         let cache_name = event.payload.db_name.as_str();
 
@@ -152,15 +194,10 @@ impl PersisterClient {
             layer_cache_insert_size_bytes = byte_size as f64,
             cache_name = cache_name
         );
-
-        let (status_write, status_read) = self.get_status_channels();
-        self.tx
-            .send(PersistMessage::Write((event, status_write)))
-            .map_err(Box::new)?;
-        Ok(status_read)
     }
 
     pub fn evict_event(&self, event: LayeredEvent) -> LayerDbResult<PersisterStatusReader> {
+        self.ensure_writable()?;
         let (status_write, status_read) = self.get_status_channels();
         self.tx
             .send(PersistMessage::Evict((event, status_write)))
@@ -172,6 +209,7 @@ impl PersisterClient {
         &self,
         event: LayeredEvent,
     ) -> LayerDbResult<PersisterStatusReader> {
+        self.ensure_writable()?;
         let (status_write, status_read) = self.get_status_channels();
         self.tx
             .send(PersistMessage::EvictMemoryOnly((event, status_write)))
@@ -437,6 +475,127 @@ impl PersisterTask {
         Ok(())
     }
 
+    /// Persists a batch of events like [`Self::do_persist_event`], but writes them to Postgres
+    /// with multi-row inserts and publishes them together.
+    async fn do_persist_events(
+        events: &[LayeredEvent],
+        mode: PersisterMode,
+        pg_pool: &PgPool,
+        s3_layers: &Option<Arc<HashMap<&'static str, S3Layer>>>,
+        layered_event_client: &LayeredEventClient,
+        retry_queue_command_tx: &mpsc::UnboundedSender<crate::retry_queue::RetryQueueMessage>,
+    ) -> LayerDbResult<()> {
+        if mode != PersisterMode::S3Only {
+            match Self::do_write_many_to_postgres(events, pg_pool, s3_layers).await {
+                Ok(_) => {
+                    for event in events {
+                        monotonic!(
+                            layer_cache_persister_write_success = 1,
+                            cache_name = event.payload.db_name.as_str(),
+                            backend = BackendType::Postgres.as_ref()
+                        );
+                    }
+                }
+                Err(e) => {
+                    error!(error = ?e, backend = "postgres", "batch write failed");
+                    if Self::do_is_retryable(&e, BackendType::Postgres) {
+                        for event in events {
+                            retry_queue_command_tx
+                                .send(crate::retry_queue::RetryQueueMessage::Enqueue {
+                                    event: event.clone(),
+                                    backend: BackendType::Postgres,
+                                })
+                                .map_err(|e| LayerDbError::RetryQueueSend(e.to_string()))?;
+                        }
+                    }
+                    // Postgres is only the fallback when S3 is also written
+                    if mode == PersisterMode::PostgresOnly {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        if mode != PersisterMode::PostgresOnly {
+            // S3 writes are queued by the S3 layer, so each one is cheap
+            let mut first_err = None;
+            for event in events {
+                match Self::do_write_to_backend(event, BackendType::S3, pg_pool, s3_layers).await {
+                    Ok(_) => {
+                        monotonic!(
+                            layer_cache_persister_write_success = 1,
+                            cache_name = event.payload.db_name.as_str(),
+                            backend = BackendType::S3.as_ref()
+                        );
+                    }
+                    Err(e) => {
+                        error!(error = ?e, backend = "s3", "batch write failed for an event");
+                        if Self::do_is_retryable(&e, BackendType::S3) {
+                            retry_queue_command_tx
+                                .send(crate::retry_queue::RetryQueueMessage::Enqueue {
+                                    event: event.clone(),
+                                    backend: BackendType::S3,
+                                })
+                                .map_err(|e| LayerDbError::RetryQueueSend(e.to_string()))?;
+                        }
+                        first_err.get_or_insert(e);
+                    }
+                }
+            }
+            if let (PersisterMode::S3Only, Some(e)) = (mode, first_err) {
+                return Err(e);
+            }
+        }
+
+        // Publish to NATS regardless of backend
+        layered_event_client
+            .publish_many(events.iter().cloned().map(Arc::new).collect())
+            .await
+    }
+
+    /// Writes a batch of events to Postgres with one multi-row insert per table. Events which are
+    /// not plain inserts are written one at a time.
+    async fn do_write_many_to_postgres(
+        events: &[LayeredEvent],
+        pg_pool: &PgPool,
+        s3_layers: &Option<Arc<HashMap<&'static str, S3Layer>>>,
+    ) -> LayerDbResult<()> {
+        let mut rows_by_table: HashMap<&str, Vec<(&str, &str, &[u8])>> = HashMap::new();
+        for event in events {
+            match event.event_kind {
+                LayeredEventKind::FuncRunLogWrite | LayeredEventKind::FuncRunWrite => {
+                    Self::do_write_to_backend(event, BackendType::Postgres, pg_pool, s3_layers)
+                        .await?;
+                }
+                _ => rows_by_table
+                    .entry(event.payload.db_name.as_str())
+                    .or_default()
+                    .push((
+                        event.payload.key.as_ref(),
+                        event.payload.sort_key.as_str(),
+                        &event.payload.value[..],
+                    )),
+            }
+        }
+
+        for (table_name, rows) in rows_by_table {
+            let write_start = std::time::Instant::now();
+            let result = PgLayer::new(pg_pool.clone(), table_name)
+                .insert_many(&rows)
+                .await;
+            histogram!(
+                layer_cache_persister.write_many_duration_ms =
+                    write_start.elapsed().as_millis() as f64,
+                cache_name = table_name,
+                status = if result.is_ok() { "success" } else { "error" },
+                backend = BackendType::Postgres.as_ref()
+            );
+            result?;
+        }
+
+        Ok(())
+    }
+
     fn do_is_retryable(error: &LayerDbError, backend: BackendType) -> bool {
         match backend {
             BackendType::Postgres => crate::retry_queue::is_retryable_error(error),
@@ -462,10 +621,14 @@ impl PersisterTask {
                 let pg_layer = PgLayer::new(pg_pool.clone(), event.payload.db_name.as_ref());
 
                 match event.event_kind {
-                    LayeredEventKind::CasInsertion
+                    LayeredEventKind::CasEvict
+                    | LayeredEventKind::CasInsertion
                     | LayeredEventKind::ChangeBatchEvict
                     | LayeredEventKind::ChangeBatchWrite
+                    | LayeredEventKind::EncryptedSecretEvict
                     | LayeredEventKind::EncryptedSecretInsertion
+                    | LayeredEventKind::FuncRunEvict
+                    | LayeredEventKind::FuncRunLogEvict
                     | LayeredEventKind::Raw
                     | LayeredEventKind::RebaseBatchEvict
                     | LayeredEventKind::RebaseBatchWrite
@@ -644,6 +807,25 @@ impl PersisterTask {
         debug!(task = Self::NAME, "shutdown complete");
     }
 
+    /// Emits the end-to-end persistence latency of a written event.
+    fn record_persistence_latency(event: &LayeredEvent, mode: PersisterMode) {
+        let latency = Utc::now()
+            .signed_duration_since(event.metadata.timestamp)
+            .to_std()
+            .unwrap_or_default();
+
+        // Emit for PostgreSQL whenever PG writes occur (all modes except S3Only)
+        if mode != PersisterMode::S3Only {
+            metric!(
+                histogram.layer_cache_persistence_latency_seconds = latency.as_secs_f64(),
+                cache_name = event.payload.db_name.as_str(),
+                backend = "postgres",
+                operation = "write",
+                event_kind = event.event_kind.as_ref()
+            );
+        }
+    }
+
     fn spawn_persist_task(&mut self, msg: PersistMessage) {
         match msg {
             PersistMessage::Write((event, status_tx)) => {
@@ -680,24 +862,51 @@ impl PersisterTask {
 
                     match result {
                         Ok(_) => {
-                            // Emit end-to-end persistence latency
-                            let latency = Utc::now()
-                                .signed_duration_since(event.metadata.timestamp)
-                                .to_std()
-                                .unwrap_or_default();
+                            Self::record_persistence_latency(&event, mode);
+                            status_tx.send(PersistStatus::Finished)
+                        }
+                        Err(err) => status_tx.send(PersistStatus::Error(err)),
+                    }
+                });
+            }
+            PersistMessage::WriteMany((events, status_tx)) => {
+                let layered_event_client = self.layered_event_client.clone();
+                let retry_queue_command_tx = self.retry_queue_command_tx.clone();
+                let mode = self.mode;
+                let pg_pool = self.pg_pool.clone();
+                let s3_layers = self.s3_layers.clone();
 
-                            // Emit for PostgreSQL whenever PG writes occur (all modes except S3Only)
-                            if mode != PersisterMode::S3Only {
-                                metric!(
-                                    histogram.layer_cache_persistence_latency_seconds =
-                                        latency.as_secs_f64(),
-                                    cache_name = &cache_name,
-                                    backend = "postgres",
-                                    operation = "write",
-                                    event_kind = event.event_kind.as_ref()
-                                );
-                            }
+                let backend = match self.mode {
+                    PersisterMode::PostgresOnly => BackendType::Postgres,
+                    PersisterMode::DualWrite => BackendType::Postgres, // Primary is PG
+                    PersisterMode::S3Primary | PersisterMode::S3Only => BackendType::S3,
+                };
 
+                for event in &events {
+                    metric!(
+                        counter.layer_cache_persister_write_attempted = 1,
+                        cache_name = event.payload.db_name.as_str(),
+                        backend = backend.as_ref(),
+                        event_kind = event.event_kind.as_ref()
+                    );
+                }
+
+                self.tracker.spawn(async move {
+                    let result = Self::do_persist_events(
+                        &events,
+                        mode,
+                        &pg_pool,
+                        &s3_layers,
+                        &layered_event_client,
+                        &retry_queue_command_tx,
+                    )
+                    .await;
+
+                    match result {
+                        Ok(_) => {
+                            for event in &events {
+                                Self::record_persistence_latency(event, mode);
+                            }
                             status_tx.send(PersistStatus::Finished)
                         }
                         Err(err) => status_tx.send(PersistStatus::Error(err)),
@@ -1100,10 +1309,14 @@ impl PersistEventTask {
 
         let pg_layer = PgLayer::new(self.pg_pool.clone(), event.payload.db_name.as_ref());
         match event.event_kind {
-            LayeredEventKind::CasInsertion
+            LayeredEventKind::CasEvict
+            | LayeredEventKind::CasInsertion
             | LayeredEventKind::ChangeBatchEvict
             | LayeredEventKind::ChangeBatchWrite
+            | LayeredEventKind::EncryptedSecretEvict
             | LayeredEventKind::EncryptedSecretInsertion
+            | LayeredEventKind::FuncRunEvict
+            | LayeredEventKind::FuncRunLogEvict
             | LayeredEventKind::Raw
             | LayeredEventKind::RebaseBatchEvict
             | LayeredEventKind::RebaseBatchWrite
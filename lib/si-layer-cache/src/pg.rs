@@ -23,6 +23,9 @@ mod embedded {
 pub const DBNAME: &str = "si_layer_db";
 pub const APPLICATION_NAME: &str = "si-layer-db";
 
+// Keeps each multi-row insert well under Postgres' limit of 65535 bind parameters
+const INSERT_MANY_ROWS_PER_STATEMENT: usize = 1000;
+
 pub fn default_pg_pool_config() -> PgPoolConfig {
     PgPoolConfig {
         dbname: DBNAME.into(),
@@ -253,6 +256,34 @@ impl PgLayer {
         Ok(())
     }
 
+    /// Inserts many `(key, sort_key, value)` rows with as few statements as possible, skipping
+    /// keys which already exist.
+    pub async fn insert_many(&self, rows: &[(&str, &str, &[u8])]) -> LayerDbResult<()> {
+        let client = self.pool.get().await?;
+        for chunk in rows.chunks(INSERT_MANY_ROWS_PER_STATEMENT) {
+            let values = (0..chunk.len())
+                .map(|row| format!("(${}, ${}, ${})", 3 * row + 1, 3 * row + 2, 3 * row + 3))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let query = format!(
+                "INSERT INTO {} (key, sort_key, value) VALUES {values} ON CONFLICT DO NOTHING",
+                self.table_name
+            );
+            let params: Vec<&(dyn ToSql + Sync)> = chunk
+                .iter()
+                .flat_map(|(key, sort_key, value)| {
+                    [
+                        key as &(dyn ToSql + Sync),
+                        sort_key as &(dyn ToSql + Sync),
+                        value as &(dyn ToSql + Sync),
+                    ]
+                })
+                .collect();
+            client.query(&query, &params).await?;
+        }
+        Ok(())
+    }
+
     pub async fn insert_raw(
         &self,
         query: &str,
@@ -131,6 +131,117 @@ async fn write_and_read_many() {
     }
 }
 
+#[tokio::test]
+async fn write_many_to_db() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        setup_pg_db("cas_write_many_to_db").await,
+        setup_nats_client(Some("cas_write_many_to_db".to_string())).await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let cas_values: Vec<Arc<CasValue>> = vec![
+        Arc::new(serde_json::json!("stone sour").into()),
+        Arc::new(serde_json::json!("tone flour").into()),
+        Arc::new(serde_json::json!("bologna chowder").into()),
+    ];
+
+    let (keys, status) = ldb
+        .cas()
+        .write_many(
+            cas_values.clone(),
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    // The keys match what writing each value on its own would produce, in order
+    assert_eq!(cas_values.len(), keys.len());
+    for (cas_value, key) in cas_values.iter().zip(&keys) {
+        let (postcard_value, _) = serialize::to_vec(cas_value).expect("should serialize");
+        assert_eq!(ContentHash::new(&postcard_value), *key);
+
+        let key_str: Arc<str> = key.to_string().into();
+        let in_pg_postcard = ldb
+            .cas()
+            .cache
+            .pg()
+            .get(&key_str)
+            .await
+            .expect("error getting data from pg")
+            .expect("no cas object in pg");
+        let in_pg: CasValue =
+            serialize::from_bytes(&in_pg_postcard[..]).expect("cannot deserialize data");
+        assert_eq!(cas_value.as_ref(), &in_pg);
+    }
+}
+
+#[tokio::test]
+async fn prewarm_reads_keys_into_cache() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        setup_pg_db("cas_prewarm_reads_keys_into_cache").await,
+        setup_nats_client(Some("cas_prewarm_reads_keys_into_cache".to_string())).await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate ldb");
+
+    let cas_values: Vec<Arc<CasValue>> = vec![
+        Arc::new(serde_json::json!("stone sour").into()),
+        Arc::new(serde_json::json!("tone flour").into()),
+    ];
+    let (keys, status) = ldb
+        .cas()
+        .write_many(
+            cas_values,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    // Start cold, as a fresh instance would
+    let mut keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+    for key in &keys {
+        ldb.cas().cache.remove_from_memory(key);
+        assert!(!ldb.cas().cache.contains(key));
+    }
+
+    let missing_key = ContentHash::new(b"poop canoe").to_string();
+    keys.push(missing_key.clone());
+
+    let results = ldb.cas().prewarm(&keys).await;
+
+    assert_eq!(keys.len(), results.len());
+    for key in &keys[..2] {
+        assert!(
+            matches!(results.get(key), Some(Ok(true))),
+            "{key} was not warmed"
+        );
+        assert!(ldb.cas().cache.contains(key));
+    }
+    assert!(matches!(results.get(&missing_key), Some(Ok(false))));
+    assert!(!ldb.cas().cache.contains(&missing_key));
+}
+
 #[tokio::test]
 async fn cold_read_from_db() {
     let token = CancellationToken::new();
@@ -284,6 +395,99 @@ async fn writes_are_gossiped() {
     assert_eq!(cas_value.as_ref(), &in_pg);
 }
 
+#[tokio::test]
+async fn memory_only_evictions_are_gossiped() {
+    let token = CancellationToken::new();
+
+    let db = setup_pg_db("cas_memory_only_evictions_are_gossiped").await;
+
+    let (ldb_slash, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db.clone(),
+        setup_nats_client(Some("cas_memory_only_evictions_are_gossiped".to_string())).await,
+        setup_compute_executor(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_slash.pg_migrate().await.expect("migrate layerdb");
+
+    let (ldb_axl, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db,
+        setup_nats_client(Some("cas_memory_only_evictions_are_gossiped".to_string())).await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_axl.pg_migrate().await.expect("migrate layerdb");
+
+    let cas_value: Arc<CasValue> = Arc::new(serde_json::json!("paradise city").into());
+    let (cas_pk, status) = ldb_slash
+        .cas()
+        .write(
+            cas_value.clone(),
+            None,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    let cas_pk_str: Arc<str> = cas_pk.to_string().into();
+
+    // Reading on axl fetches the value from pg into its cache.
+    let read = ldb_axl
+        .cas()
+        .read(&cas_pk)
+        .await
+        .expect("failed to read from layerdb");
+    assert_eq!(Some(cas_value.clone()), read);
+    assert!(ldb_axl.cas().cache.contains(&cas_pk_str));
+
+    // Evict from the caches only!
+    let status = ldb_slash
+        .cas()
+        .evict_memory_only(
+            &cas_pk,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::System,
+        )
+        .expect("cannot evict local data");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Eviction failed; {e}"),
+    }
+    assert!(!ldb_slash.cas().cache.contains(&cas_pk_str));
+
+    let max_check_count = 100;
+
+    let mut memory_check_count = 0;
+    while memory_check_count < max_check_count {
+        if !ldb_axl.cas().cache.contains(&cas_pk_str) {
+            break;
+        }
+        memory_check_count += 1;
+        tokio::time::sleep_until(Instant::now() + Duration::from_millis(1)).await;
+    }
+    assert_ne!(
+        max_check_count, memory_check_count,
+        "value did not evict from the remote cache within 100ms"
+    );
+
+    // The value is still in pg, so it can be read again.
+    let read = ldb_axl
+        .cas()
+        .read(&cas_pk)
+        .await
+        .expect("failed to read from layerdb");
+    assert_eq!(Some(cas_value), read);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn stress_test() {
     let token = CancellationToken::new();
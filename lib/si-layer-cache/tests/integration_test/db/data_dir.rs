@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use si_events::{
+    Actor,
+    CasValue,
+    ChangeSetId,
+    Tenancy,
+    UserPk,
+    WorkspacePk,
+};
+use si_layer_cache::{
+    LayerDb,
+    db::cas,
+    persister::PersistStatus,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::integration_test::{
+    make_test_layerdb_config,
+    setup_compute_executor,
+    setup_nats_client,
+    setup_pg_db,
+};
+
+type TestLayerDb = LayerDb<CasValue, String, String, String, String, String, String>;
+
+#[tokio::test]
+async fn separate_data_dirs_are_isolated() {
+    let token = CancellationToken::new();
+    let pg_pool = setup_pg_db("data_dir_separate_data_dirs_are_isolated").await;
+
+    let data_dir_a = tempfile::TempDir::with_prefix("layer-db-a-").expect("create tmp dir");
+    let data_dir_b = tempfile::TempDir::with_prefix("layer-db-b-").expect("create tmp dir");
+
+    let mut config_a = make_test_layerdb_config();
+    config_a.cache_config = config_a.cache_config.with_disk_path(data_dir_a.path());
+    let (ldb_a, _): (TestLayerDb, _) = LayerDb::from_services(
+        config_a,
+        pg_pool.clone(),
+        setup_nats_client(Some(
+            "data_dir_separate_data_dirs_are_isolated_a".to_string(),
+        ))
+        .await,
+        setup_compute_executor(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_a.pg_migrate().await.expect("migrate layer db");
+
+    let mut config_b = make_test_layerdb_config();
+    config_b.cache_config = config_b.cache_config.with_disk_path(data_dir_b.path());
+    let (ldb_b, _): (TestLayerDb, _) = LayerDb::from_services(
+        config_b,
+        pg_pool,
+        setup_nats_client(Some(
+            "data_dir_separate_data_dirs_are_isolated_b".to_string(),
+        ))
+        .await,
+        setup_compute_executor(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+
+    // Each instance keeps its disk cache under its own data dir
+    assert!(data_dir_a.path().join(cas::CACHE_NAME).is_dir());
+    assert!(data_dir_b.path().join(cas::CACHE_NAME).is_dir());
+
+    let cas_value: Arc<CasValue> = Arc::new(serde_json::json!("no cross contamination").into());
+    let (cas_pk, status) = ldb_a
+        .cas()
+        .write(
+            cas_value.clone(),
+            None,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    let cas_pk_str: Arc<str> = cas_pk.to_string().into();
+    assert_eq!(
+        Some(cas_value),
+        ldb_a.cas().cache.cache().get(cas_pk_str.clone()).await
+    );
+    assert_eq!(None, ldb_b.cas().cache.cache().get(cas_pk_str).await);
+
+    token.cancel();
+}
@@ -198,6 +198,82 @@ async fn update() {
     );
 }
 
+#[tokio::test]
+async fn evictions_drop_the_key_from_every_instance_but_keep_it_in_pg() {
+    let token = CancellationToken::new();
+
+    let db = setup_pg_db("func_run_evictions").await;
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db.clone(),
+        setup_nats_client(Some("func_run_evictions".to_string())).await,
+        setup_compute_executor(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate layer db");
+
+    let (ldb_remote, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db,
+        setup_nats_client(Some("func_run_evictions".to_string())).await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+
+    let (tenancy, actor) = (
+        Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+        Actor::User(UserPk::new()),
+    );
+
+    let func_run = create_func_run(actor, tenancy, "stevie nicks");
+    let key_str: Arc<str> = func_run.id().to_string().into();
+    let value: Arc<FuncRun> = Arc::new(func_run);
+
+    ldb.func_run()
+        .write(value.clone(), None, tenancy, actor)
+        .await
+        .expect("failed to write to layerdb");
+    ldb_remote
+        .func_run()
+        .read(value.id())
+        .await
+        .expect("failed to read from layerdb")
+        .expect("func run not found");
+    assert!(ldb_remote.func_run().cache.contains(&key_str));
+
+    ldb.evict(&key_str, tenancy, Actor::System)
+        .await
+        .expect("failed to evict");
+    assert!(!ldb.func_run().cache.contains(&key_str));
+
+    let max_check_count = 100;
+    let mut memory_check_count = 0;
+    while memory_check_count < max_check_count {
+        if !ldb_remote.func_run().cache.contains(&key_str) {
+            break;
+        }
+        memory_check_count += 1;
+        tokio::time::sleep_until(Instant::now() + Duration::from_millis(1)).await;
+    }
+    assert_ne!(
+        max_check_count, memory_check_count,
+        "value did not evict from the remote cache within 100ms"
+    );
+
+    // Still in pg, so reads fetch it afresh
+    let read = ldb
+        .func_run()
+        .read(value.id())
+        .await
+        .expect("failed to read from layerdb")
+        .expect("func run not in pg");
+    assert_eq!(value.id(), read.id());
+}
+
 #[tokio::test]
 async fn write_and_read_many_for_workspace_id() {
     let token = CancellationToken::new();
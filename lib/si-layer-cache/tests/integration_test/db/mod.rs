@@ -1,4 +1,6 @@
 mod cas;
+mod data_dir;
 mod func_run;
 mod func_run_log;
+mod split_snapshot_subgraph;
 mod workspace_snapshot;
@@ -0,0 +1,137 @@
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use si_events::{
+    Actor,
+    ChangeSetId,
+    Tenancy,
+    UserPk,
+    WorkspacePk,
+};
+use si_layer_cache::{
+    LayerDb,
+    persister::PersistStatus,
+};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::integration_test::{
+    make_test_layerdb_config,
+    setup_compute_executor,
+    setup_nats_client,
+    setup_pg_db,
+};
+
+type TestLayerDb = LayerDb<String, String, String, String, String, String, String>;
+
+#[tokio::test]
+async fn memory_only_evictions_are_gossiped() {
+    let token = CancellationToken::new();
+
+    let db = setup_pg_db("split_snapshot_subgraph_memory_only_evictions_are_gossiped").await;
+
+    let (ldb_slash, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db.clone(),
+        setup_nats_client(Some(
+            "split_snapshot_subgraph_memory_only_evictions_are_gossiped".to_string(),
+        ))
+        .await,
+        setup_compute_executor(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_slash.pg_migrate().await.expect("migrate layerdb");
+
+    let (ldb_axl, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db,
+        setup_nats_client(Some(
+            "split_snapshot_subgraph_memory_only_evictions_are_gossiped".to_string(),
+        ))
+        .await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb_axl.pg_migrate().await.expect("migrate layerdb");
+
+    let value: Arc<String> = Arc::new("slither".into());
+    let (key, status) = ldb_slash
+        .split_snapshot_subgraph()
+        .write(
+            value.clone(),
+            None,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    let key_str: Arc<str> = key.to_string().into();
+
+    // Reading on axl fetches the value from pg into its cache.
+    let read = ldb_axl
+        .split_snapshot_subgraph()
+        .read(&key)
+        .await
+        .expect("failed to read from layerdb");
+    assert_eq!(Some(value.clone()), read);
+    assert!(ldb_axl.split_snapshot_subgraph().cache.contains(&key_str));
+
+    // Evict from the caches only!
+    let status = ldb_slash
+        .split_snapshot_subgraph()
+        .evict_memory_only(
+            &key,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::System,
+        )
+        .expect("cannot evict local data");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Eviction failed; {e}"),
+    }
+    assert!(!ldb_slash.split_snapshot_subgraph().cache.contains(&key_str));
+
+    let max_check_count = 100;
+
+    let mut memory_check_count = 0;
+    while memory_check_count < max_check_count {
+        if !ldb_axl.split_snapshot_subgraph().cache.contains(&key_str) {
+            break;
+        }
+        memory_check_count += 1;
+        tokio::time::sleep_until(Instant::now() + Duration::from_millis(1)).await;
+    }
+    assert_ne!(
+        max_check_count, memory_check_count,
+        "value did not evict from the remote cache within 100ms"
+    );
+
+    // The value is still in pg, so it can be read again.
+    assert!(
+        ldb_axl
+            .split_snapshot_subgraph()
+            .cache
+            .pg()
+            .get(&key_str)
+            .await
+            .expect("error getting data from pg")
+            .is_some(),
+        "item was evicted from the database when it should have been kept"
+    );
+    let read = ldb_axl
+        .split_snapshot_subgraph()
+        .read(&key)
+        .await
+        .expect("failed to read from layerdb");
+    assert_eq!(Some(value), read);
+}
@@ -9,9 +9,11 @@ use si_events::{
     Tenancy,
     UserPk,
     WorkspacePk,
+    WorkspaceSnapshotAddress,
 };
 use si_layer_cache::{
     LayerDb,
+    LayerDbError,
     db::serialize,
     persister::PersistStatus,
 };
@@ -288,3 +290,94 @@ async fn evictions_are_gossiped() {
         "value did not evict from the remote memory cache within 100ms"
     );
 }
+
+#[tokio::test]
+async fn read_only_reads_but_refuses_writes() {
+    let token = CancellationToken::new();
+
+    let db = setup_pg_db("workspace_snapshot_read_only_reads_but_refuses_writes").await;
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        db.clone(),
+        setup_nats_client(Some(
+            "workspace_snapshot_read_only_reads_but_refuses_writes".to_string(),
+        ))
+        .await,
+        setup_compute_executor(),
+        token.clone(),
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate layerdb");
+
+    let mut read_only_config = make_test_layerdb_config();
+    read_only_config.read_only = true;
+    let (ldb_read_only, _): (TestLayerDb, _) = LayerDb::from_services(
+        read_only_config,
+        db,
+        setup_nats_client(Some(
+            "workspace_snapshot_read_only_reads_but_refuses_writes".to_string(),
+        ))
+        .await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+
+    let value: Arc<String> = Arc::new("the trooper".into());
+    let (key, status) = ldb
+        .workspace_snapshot()
+        .write(
+            value.clone(),
+            None,
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    // Reads still work
+    let read = ldb_read_only
+        .workspace_snapshot()
+        .read(&key)
+        .await
+        .expect("failed to read from layerdb");
+    assert_eq!(Some(value), read);
+
+    // Writes are refused, and do not land in the cache
+    let refused: Arc<String> = Arc::new("aces high".into());
+    let result = ldb_read_only.workspace_snapshot().write(
+        refused.clone(),
+        None,
+        Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+        Actor::User(UserPk::new()),
+    );
+    assert!(matches!(result, Err(LayerDbError::ReadOnly)));
+    let (refused_postcard, _) = serialize::to_vec(&refused).expect("should serialize");
+    let refused_key = WorkspaceSnapshotAddress::new(&refused_postcard);
+    assert!(
+        !ldb_read_only
+            .workspace_snapshot()
+            .cache
+            .contains(&refused_key.to_string())
+    );
+
+    // As are evictions
+    let result = ldb_read_only.workspace_snapshot().evict(
+        &key,
+        Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+        Actor::System,
+    );
+    assert!(matches!(result, Err(LayerDbError::ReadOnly)));
+    assert!(
+        ldb_read_only
+            .workspace_snapshot()
+            .cache
+            .contains(&key.to_string())
+    );
+}
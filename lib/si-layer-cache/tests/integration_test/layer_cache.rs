@@ -7,7 +7,10 @@ use rand::{
 use si_layer_cache::{
     db::serialize,
     hybrid_cache::CacheConfig,
-    layer_cache::LayerCache,
+    layer_cache::{
+        LayerCache,
+        LayerCacheMetrics,
+    },
     persister::PersisterMode,
 };
 use tokio_util::{
@@ -174,3 +177,49 @@ async fn get_last_four_from_database() {
 
     assert_eq!(get_values.len(), 4);
 }
+
+#[tokio::test]
+async fn get_records_where_reads_were_served_from() {
+    let layer_cache = make_layer_cache("get_records_where_reads_were_served_from").await;
+    assert_eq!(LayerCacheMetrics::default(), layer_cache.metrics());
+    assert_eq!(None, layer_cache.metrics().memory_hit_ratio());
+
+    // Only in pg, so the first read falls through and the second is served from memory
+    let (postcard_serialized, _) =
+        serialize::to_vec("welcome to the jungle").expect("should serialize");
+    layer_cache
+        .pg()
+        .insert("jungle", "cas", &postcard_serialized)
+        .await
+        .expect("cannot insert into pg");
+    for _ in 0..2 {
+        layer_cache
+            .get("jungle".into())
+            .await
+            .expect("error getting object from cache")
+            .expect("cannot find object");
+    }
+
+    // Not anywhere
+    assert_eq!(
+        None,
+        layer_cache
+            .get("paradise city".into())
+            .await
+            .expect("error getting object from cache")
+    );
+
+    let metrics = layer_cache.metrics();
+    assert_eq!(
+        LayerCacheMetrics {
+            memory_hits: 1,
+            disk_hits: 0,
+            backend_hits: 1,
+            misses: 1,
+        },
+        metrics
+    );
+    assert_eq!(3, metrics.total());
+    assert_eq!(Some(1.0 / 3.0), metrics.memory_hit_ratio());
+    assert_eq!(Some(1.0 / 3.0), metrics.cache_hit_ratio());
+}
@@ -165,5 +165,9 @@ pub fn make_test_layerdb_config() -> si_layer_cache::db::LayerDbConfig {
         cache_config: si_layer_cache::hybrid_cache::CacheConfig::default(),
         object_storage_config: si_layer_cache::ObjectStorageConfig::default(),
         persister_mode,
+        cache_updates_concurrency: None,
+        disk_percentages: Default::default(),
+        prewarm_concurrency: None,
+        read_only: false,
     }
 }
@@ -14,6 +14,7 @@ pub use workspace::{
 
 #[cfg(test)]
 mod tests {
+    use object_tree::Hash;
     use petgraph::dot::Dot;
     use tokio::sync::Mutex;
 
@@ -164,4 +165,26 @@ mod tests {
 
         let _ = dbg!(props.lock().await);
     }
+
+    #[tokio::test]
+    async fn load_from_bytes_verified_checks_hash() {
+        let spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let hash = pkg.hash().expect("failed to get pkg hash");
+        let pkg_data = pkg.write_to_bytes().expect("failed to serialize pkg");
+
+        let read_pkg = SiPkg::load_from_bytes_verified(&pkg_data, hash)
+            .expect("failed to load pkg with its own hash");
+        assert_eq!(hash, read_pkg.hash().expect("failed to get read pkg hash"));
+
+        let other_hash = Hash::new(b"some other pkg");
+        match SiPkg::load_from_bytes_verified(&pkg_data, other_hash) {
+            Err(SiPkgError::HashMismatch { expected, actual }) => {
+                assert_eq!(other_hash, expected);
+                assert_eq!(hash, actual);
+            }
+            Err(err) => panic!("expected a hash mismatch, got: {err}"),
+            Ok(_) => panic!("expected a hash mismatch, but the pkg loaded"),
+        }
+    }
 }
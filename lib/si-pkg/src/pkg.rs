@@ -2,6 +2,7 @@ use core::fmt;
 use std::{
     collections::HashMap,
     convert::Infallible,
+    io::Read,
     path::Path,
     sync::Arc,
 };
@@ -92,6 +93,8 @@ pub enum SiPkgError {
     ComponentMissingPosition(String),
     #[error("graph error: {0}")]
     Graph(#[from] GraphError),
+    #[error("pkg has hash {actual}, but {expected} was expected")]
+    HashMismatch { expected: Hash, actual: Hash },
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
     #[error("memory error: {0}")]
@@ -154,13 +157,34 @@ impl SiPkg {
     }
 
     pub fn load_from_bytes(bytes: &[u8]) -> PkgResult<Self> {
-        let tree: ObjectTree<PkgNode> = ObjectTree::<PkgNode>::read_from_tar(bytes)?;
+        Self::load_from_reader(bytes)
+    }
+
+    /// Loads a pkg from a reader, such as a file, without first reading all of it into memory.
+    pub fn load_from_reader(reader: impl Read) -> PkgResult<Self> {
+        let tree: ObjectTree<PkgNode> = ObjectTree::<PkgNode>::read_from_tar_reader(reader)?;
 
         Ok(Self {
             tree: Arc::new(tree),
         })
     }
 
+    /// Loads a pkg from bytes, checking that its hash is the one expected, such as the hash the
+    /// module index advertised for a downloaded pkg.
+    pub fn load_from_bytes_verified(bytes: &[u8], expected_hash: Hash) -> PkgResult<Self> {
+        let pkg = Self::load_from_bytes(bytes)?;
+
+        let actual = pkg.hash()?;
+        if actual != expected_hash {
+            return Err(SiPkgError::HashMismatch {
+                expected: expected_hash,
+                actual,
+            });
+        }
+
+        Ok(pkg)
+    }
+
     pub fn load_from_spec<I>(spec: I) -> PkgResult<Self>
     where
         I: TryInto<PkgSpec>,
@@ -1,4 +1,7 @@
-use std::result;
+use std::{
+    result,
+    time::Duration,
+};
 
 use async_trait::async_trait;
 
@@ -318,6 +321,24 @@ pub trait Instance {
     /// ```
     async fn terminate(&mut self) -> result::Result<(), Self::Error>;
 
+    /// Terminates the instance, first giving it up to `grace` to finish any work in progress.
+    ///
+    /// By default, this terminates the instance straight away.
+    async fn terminate_graceful(&mut self, _grace: Duration) -> result::Result<(), Self::Error>
+    where
+        Self: Send,
+    {
+        self.terminate().await
+    }
+
+    /// Whether the instance can be handed out again once it is returned to the pool, rather than
+    /// being terminated and replaced. It must still pass [`Self::ensure_healthy`] to be handed out.
+    ///
+    /// By default, instances are never reused.
+    fn is_reusable(&self) -> bool {
+        false
+    }
+
     /// Get the id of the underlying child runtime
     fn id(&self) -> u32;
 }
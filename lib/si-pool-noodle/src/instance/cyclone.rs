@@ -14,6 +14,7 @@ pub use local_uds::{
     LocalUdsInstanceSpecBuilder,
     LocalUdsRuntimeStrategy,
     LocalUdsSocketStrategy,
+    RequestCreditPolicy,
 };
 
 mod local_http;
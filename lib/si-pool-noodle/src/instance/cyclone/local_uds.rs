@@ -6,7 +6,10 @@ use std::{
     },
     result,
     sync::Arc,
-    time::Duration,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use async_trait::async_trait;
@@ -65,8 +68,12 @@ use serde::{
 #[cfg(target_os = "linux")]
 use si_firecracker::{
     errors::FirecrackerJailError,
-    firecracker::FirecrackerJail,
+    firecracker::{
+        FirecrackerJail,
+        JailerIdentity,
+    },
 };
+use telemetry_utils::metric;
 use tempfile::{
     NamedTempFile,
     TempPath,
@@ -123,6 +130,9 @@ pub enum LocalUdsInstanceError {
     /// Docker api not found
     #[error("no docker api")]
     DockerAPINotFound,
+    /// Instance has outlived its maximum age.
+    #[error("instance has outlived its maximum age, cyclone server is considered unhealthy")]
+    Expired,
     #[cfg(target_os = "linux")]
     /// Failed to firecracker jail.
     #[error("firecracker error: {0}")]
@@ -142,6 +152,9 @@ pub enum LocalUdsInstanceError {
     /// Instance has exhausted its predefined request count.
     #[error("no remaining requests, cyclone server is considered unhealthy")]
     NoRemainingRequests,
+    /// Cyclone server failed a readiness probe.
+    #[error("readiness probe failed, cyclone server is considered unhealthy: {0}")]
+    NotReady(#[source] Box<ClientError>),
     /// Failed to setup the host correctly.
     #[error("failed to setup host")]
     SetupFailed,
@@ -184,9 +197,16 @@ pub struct LocalUdsInstance {
     // when `LocalUdsInstance` is dropped, the temp file is marked for deletion.
     _temp_path: Option<TempPath>,
     client: UdsClient,
+    created_at: Instant,
+    credited_requests: u32,
     limit_requests: Option<u32>,
+    max_age: Option<Duration>,
+    request_credit: Option<RequestCreditPolicy>,
+    /// Only counted for reusable instances, as it is used to tune their request limit and credit.
+    requests_served: u32,
     runtime: Box<dyn LocalInstanceRuntime>,
-    watch_shutdown_tx: oneshot::Sender<()>,
+    verify_on_acquire: bool,
+    watch_shutdown_tx: Option<oneshot::Sender<()>>,
 }
 
 // TODO(nick): make this more useful.
@@ -202,14 +222,42 @@ impl Instance for LocalUdsInstance {
     type Error = LocalUdsInstanceError;
 
     async fn terminate(&mut self) -> result::Result<(), Self::Error> {
+        self.record_requests_served();
         self.runtime.terminate().await
     }
 
+    async fn terminate_graceful(&mut self, grace: Duration) -> result::Result<(), Self::Error> {
+        self.record_requests_served();
+        // Closing the watch session tells the server to shut itself down once it has finished
+        // any execution in progress
+        if let Some(watch_shutdown_tx) = self.watch_shutdown_tx.take() {
+            let _ = watch_shutdown_tx.send(());
+        }
+        self.runtime.terminate_graceful(grace).await
+    }
+
     async fn ensure_healthy(&mut self) -> result::Result<(), Self::Error> {
+        if self.request_credit.is_some() {
+            // An instance which has used up its requests may still be probed, since a successful
+            // probe is what earns it more
+            self.ensure_running_client()?;
+            self.verify_readiness().await?;
+            self.credit_requests();
+            return self.ensure_healthy_client().await;
+        }
+
         self.ensure_healthy_client().await?;
+        if self.verify_on_acquire {
+            self.verify_readiness().await?;
+        }
 
         Ok(())
     }
+
+    fn is_reusable(&self) -> bool {
+        self.request_credit.is_some() && self.limit_requests.is_some()
+    }
+
     fn id(&self) -> u32 {
         self.runtime.id()
     }
@@ -271,16 +319,43 @@ impl CycloneClient<UnixStream> for LocalUdsInstance {
 
 impl LocalUdsInstance {
     async fn ensure_healthy_client(&mut self) -> Result<()> {
+        self.ensure_running_client()?;
+        if !self.has_remaining_requests() {
+            return Err(LocalUdsInstanceError::NoRemainingRequests);
+        }
+
+        Ok(())
+    }
+
+    /// Probes the server's readiness, which catches a server that has died before its watch
+    /// session noticed.
+    async fn verify_readiness(&mut self) -> Result<()> {
+        match self
+            .client
+            .readiness()
+            .await
+            .map_err(|err| LocalUdsInstanceError::NotReady(Box::new(err)))?
+        {
+            ReadinessStatus::Ready => Ok(()),
+        }
+    }
+
+    fn ensure_running_client(&self) -> Result<()> {
         if !self.is_watch_shutdown_open() {
             return Err(LocalUdsInstanceError::WatchShutDown);
         }
-        if !self.has_remaining_requests() {
-            return Err(LocalUdsInstanceError::NoRemainingRequests);
+        if self.has_expired() {
+            return Err(LocalUdsInstanceError::Expired);
         }
 
         Ok(())
     }
 
+    fn has_expired(&self) -> bool {
+        self.max_age
+            .is_some_and(|max_age| self.created_at.elapsed() > max_age)
+    }
+
     fn has_remaining_requests(&self) -> bool {
         match self.limit_requests {
             Some(0) => false,
@@ -289,16 +364,66 @@ impl LocalUdsInstance {
     }
 
     fn is_watch_shutdown_open(&self) -> bool {
-        !self.watch_shutdown_tx.is_closed()
+        self.watch_shutdown_tx
+            .as_ref()
+            .is_some_and(|watch_shutdown_tx| !watch_shutdown_tx.is_closed())
+    }
+
+    fn record_requests_served(&self) {
+        if self.is_reusable() {
+            metric!(
+                histogram.pool_noodle.instance.requests_served = u64::from(self.requests_served)
+            );
+        }
     }
 
     fn count_request(&mut self) {
+        let is_reusable = self.is_reusable();
         if let Some(limit_requests) = self.limit_requests.as_mut() {
             *limit_requests = limit_requests.saturating_sub(1);
+            if is_reusable {
+                self.requests_served = self.requests_served.saturating_add(1);
+            }
         }
     }
+
+    /// Credits requests back to the limit after a successful readiness probe, if there is a
+    /// credit policy and it has not already credited its maximum.
+    fn credit_requests(&mut self) {
+        let (Some(policy), Some(limit_requests)) =
+            (self.request_credit, self.limit_requests.as_mut())
+        else {
+            return;
+        };
+
+        let credit = policy
+            .per_probe
+            .min(policy.max_total.saturating_sub(self.credited_requests));
+        *limit_requests = limit_requests.saturating_add(credit);
+        self.credited_requests += credit;
+    }
 }
 
+/// Lets a successful readiness probe credit requests back to the request limit of a spawned
+/// Cyclone server, extending its life.
+///
+/// Instances with a request limit and a credit policy are returned to the pool for reuse rather
+/// than replaced, and are probed each time they are acquired, so only use this for backends where
+/// reusing a server across requests is safe. The server is told to allow its request limit plus
+/// `max_total`, so it stays bounded either way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequestCreditPolicy {
+    /// Requests credited back by each successful readiness probe.
+    pub per_probe: u32,
+    /// Most requests credited back over the life of a server.
+    pub max_total: u32,
+}
+
+/// The default image for a Cyclone server spawned with [`LocalUdsRuntimeStrategy::LocalDocker`].
+const DEFAULT_CONTAINER_IMAGE: &str = "systeminit/cyclone:stable";
+/// The default platform for a Cyclone server spawned with [`LocalUdsRuntimeStrategy::LocalDocker`].
+const DEFAULT_CONTAINER_PLATFORM: &str = "linux/amd64";
+
 /// The [`Spec`] for [`LocalUdsInstance`]
 #[derive(Builder, Clone, Debug, Default)]
 pub struct LocalUdsInstanceSpec {
@@ -326,10 +451,34 @@ pub struct LocalUdsInstanceSpec {
     #[builder(setter(into, strip_option), default)]
     watch_timeout: Option<Duration>,
 
+    /// Sets how many times to retry connecting the initial watch session to a spawned Cyclone
+    /// server while it boots, before considering the spawn failed.
+    #[builder(setter(into), default = "300")]
+    watch_connect_retries: u32,
+
+    /// Sets how long to wait between attempts to connect the initial watch session.
+    #[builder(setter(into), default = "Duration::from_millis(64)")]
+    watch_connect_interval: Duration,
+
     /// Sets the limit requests strategy for a spawned Cyclone server.
     #[builder(setter(into), default = "Some(1)")]
     limit_requests: Option<u32>,
 
+    /// Sets a policy for reusing a spawned Cyclone server, crediting requests back to its request
+    /// limit after the readiness probe each time it is acquired. Off by default.
+    #[builder(setter(into, strip_option), default)]
+    request_credit: Option<RequestCreditPolicy>,
+
+    /// Sets the maximum age of a spawned Cyclone server, after which it is considered unhealthy
+    /// and recycled.
+    #[builder(setter(into, strip_option), default)]
+    max_instance_age: Option<Duration>,
+
+    /// Sets whether to probe a spawned Cyclone server's readiness each time it is acquired from
+    /// the pool, recycling it if the probe fails. Off by default, since it adds a round trip.
+    #[builder(default = "false")]
+    verify_on_acquire: bool,
+
     /// Enables the `ping` execution endpoint for a spawned Cyclone server.
     #[builder(private, setter(name = "_ping"), default = "false")]
     ping: bool,
@@ -342,6 +491,22 @@ pub struct LocalUdsInstanceSpec {
     #[builder(private, setter(name = "_action"), default = "false")]
     action: bool,
 
+    /// Enables the `validation` execution endpoint for a spawned Cyclone server.
+    #[builder(private, setter(name = "_validation"), default = "false")]
+    validation: bool,
+
+    /// Enables the `schema_variant_definition` execution endpoint for a spawned Cyclone server.
+    #[builder(
+        private,
+        setter(name = "_schema_variant_definition"),
+        default = "false"
+    )]
+    schema_variant_definition: bool,
+
+    /// Enables the `management` execution endpoint for a spawned Cyclone server.
+    #[builder(private, setter(name = "_management"), default = "false")]
+    management: bool,
+
     /// Size of the pool to configure for the spec.
     #[builder(setter(into), default = "500")]
     pub pool_size: u32,
@@ -353,6 +518,35 @@ pub struct LocalUdsInstanceSpec {
     /// Sets whether or not the firecracker setup scripts will be created.
     #[builder(default = "true")]
     create_firecracker_setup_scripts: bool,
+
+    /// Sets the base of the uid each firecracker jail runs as, which has the jail's id appended to
+    /// it, so a base of `500` runs jail `12` as uid `50012`.
+    #[builder(setter(into), default = "500")]
+    jailer_uid_base: u32,
+
+    /// Sets the gid every firecracker jail runs as, which is the group created for jailed
+    /// processes when the host was provisioned.
+    #[builder(setter(into), default = "10000")]
+    jailer_gid: u32,
+
+    /// Sets the prefix of the network namespace name for each firecracker jail, which has the
+    /// jail's id appended to it. The jail's user is given the same name.
+    #[builder(setter(into), default = "\"jailer-\".to_string()")]
+    netns_prefix: String,
+
+    /// Docker image for a spawned Cyclone server, when using the Docker runtime strategy.
+    #[builder(
+        setter(into, strip_option),
+        default = "Some(DEFAULT_CONTAINER_IMAGE.to_string())"
+    )]
+    container_image: Option<String>,
+
+    /// Docker platform for a spawned Cyclone server, when using the Docker runtime strategy.
+    #[builder(
+        setter(into, strip_option),
+        default = "Some(DEFAULT_CONTAINER_PLATFORM.to_string())"
+    )]
+    container_platform: Option<String>,
 }
 
 #[async_trait]
@@ -366,7 +560,9 @@ impl Spec for LocalUdsInstanceSpec {
             LocalUdsRuntimeStrategy::LocalDocker => Ok(()),
             LocalUdsRuntimeStrategy::LocalProcess => Ok(()),
             #[cfg(target_os = "linux")]
-            LocalUdsRuntimeStrategy::LocalFirecracker => LocalFirecrackerRuntime::clean(id).await,
+            LocalUdsRuntimeStrategy::LocalFirecracker => {
+                LocalFirecrackerRuntime::clean(id, self).await
+            }
         }
     }
 
@@ -376,7 +572,9 @@ impl Spec for LocalUdsInstanceSpec {
             LocalUdsRuntimeStrategy::LocalDocker => Ok(()),
             LocalUdsRuntimeStrategy::LocalProcess => Ok(()),
             #[cfg(target_os = "linux")]
-            LocalUdsRuntimeStrategy::LocalFirecracker => LocalFirecrackerRuntime::prepare(id).await,
+            LocalUdsRuntimeStrategy::LocalFirecracker => {
+                LocalFirecrackerRuntime::prepare(id, self).await
+            }
         }
     }
 
@@ -419,20 +617,26 @@ impl Spec for LocalUdsInstanceSpec {
         // Establish the client watch session. As the process may be booting, we will retry for a
         // period before giving up and assuming that the server instance has failed.
         let watch = {
-            let mut retries = 300;
+            let mut retries = self.watch_connect_retries;
             loop {
-                match client.watch().await {
+                let err = match client.watch().await {
                     Ok(watch) => {
                         break watch;
                     }
                     Err(err) => err,
                 };
                 if retries < 1 {
+                    debug!(
+                        error = ?err,
+                        watch_connect_retries = self.watch_connect_retries,
+                        watch_connect_interval = ?self.watch_connect_interval,
+                        "timed out retrying to start a client watch session",
+                    );
                     runtime.terminate().await?;
                     return Err(Self::Error::WatchInitTimeout);
                 }
                 retries -= 1;
-                time::sleep(Duration::from_millis(64)).await;
+                time::sleep(self.watch_connect_interval).await;
             }
         };
 
@@ -452,11 +656,71 @@ impl Spec for LocalUdsInstanceSpec {
         Ok(Self::Instance {
             _temp_path: temp_path,
             client,
+            created_at: Instant::now(),
+            credited_requests: 0,
             limit_requests: self.limit_requests,
+            max_age: self.max_instance_age,
+            request_credit: self.request_credit,
+            requests_served: 0,
             runtime,
-            watch_shutdown_tx,
+            verify_on_acquire: self.verify_on_acquire,
+            watch_shutdown_tx: Some(watch_shutdown_tx),
+        })
+    }
+}
+
+impl LocalUdsInstanceSpec {
+    /// The request limit to give a spawned Cyclone server, which allows for any requests a
+    /// [`RequestCreditPolicy`] may credit back.
+    fn server_limit_requests(&self) -> Option<u32> {
+        self.limit_requests.map(|limit_requests| {
+            limit_requests.saturating_add(
+                self.request_credit
+                    .map_or(0, |request_credit| request_credit.max_total),
+            )
         })
     }
+
+    /// The user, group and network namespace each firecracker jail runs in.
+    #[cfg(target_os = "linux")]
+    fn jailer_identity(&self) -> JailerIdentity {
+        JailerIdentity {
+            uid_base: self.jailer_uid_base,
+            gid: self.jailer_gid,
+            netns_prefix: self.netns_prefix.clone(),
+        }
+    }
+
+    /// The endpoint args to give a spawned Cyclone server. Cyclone enables most of its endpoints
+    /// unless told otherwise, so each of those which was not selected is explicitly disabled.
+    fn endpoint_args(&self) -> Vec<&'static str> {
+        let mut args = Vec::new();
+        if self.ping {
+            args.push("--enable-ping");
+        }
+        for (enabled, enable_arg, disable_arg) in [
+            (self.resolver, "--enable-resolver", "--disable-resolver"),
+            (self.action, "--enable-action-run", "--disable-action-run"),
+            (
+                self.validation,
+                "--enable-validation",
+                "--disable-validation",
+            ),
+            (
+                self.schema_variant_definition,
+                "--enable-schema-variant-definition",
+                "--disable-schema-variant-definition",
+            ),
+            (
+                self.management,
+                "--enable-management",
+                "--disable-management",
+            ),
+        ] {
+            args.push(if enabled { enable_arg } else { disable_arg });
+        }
+        args
+    }
 }
 
 impl SpecBuilder for LocalUdsInstanceSpecBuilder {
@@ -489,9 +753,28 @@ impl LocalUdsInstanceSpecBuilder {
         self._action(true)
     }
 
+    /// Enables the `validation` execution endpoint for a spawned Cyclone server.
+    pub fn validation(&mut self) -> &mut Self {
+        self._validation(true)
+    }
+
+    /// Enables the `schema_variant_definition` execution endpoint for a spawned Cyclone server.
+    pub fn schema_variant_definition(&mut self) -> &mut Self {
+        self._schema_variant_definition(true)
+    }
+
+    /// Enables the `management` execution endpoint for a spawned Cyclone server.
+    pub fn management(&mut self) -> &mut Self {
+        self._management(true)
+    }
+
     /// Enables all available endpoints for a spawned Cyclone server
     pub fn all_endpoints(&mut self) -> &mut Self {
-        self.action().resolver()
+        self.action()
+            .resolver()
+            .validation()
+            .schema_variant_definition()
+            .management()
     }
 }
 
@@ -582,6 +865,16 @@ pub trait LocalInstanceRuntime: Send + Sync {
     fn socket(&mut self) -> PathBuf;
     async fn spawn(&mut self) -> result::Result<(), LocalUdsInstanceError>;
     async fn terminate(&mut self) -> result::Result<(), LocalUdsInstanceError>;
+
+    /// Waits up to `grace` for the server to exit on its own before sending it a `SIGTERM`, and
+    /// then up to `grace` again before killing it. By default, this terminates the server straight
+    /// away.
+    async fn terminate_graceful(
+        &mut self,
+        _grace: Duration,
+    ) -> result::Result<(), LocalUdsInstanceError> {
+        self.terminate().await
+    }
 }
 
 #[derive(Debug)]
@@ -605,22 +898,14 @@ impl LocalProcessRuntime {
         if let Some(timeout) = spec.lang_server_function_timeout {
             cmd.arg("--timeout").arg(timeout.to_string());
         }
-        if let Some(limit_requests) = spec.limit_requests {
+        if let Some(limit_requests) = spec.server_limit_requests() {
             cmd.arg("--limit-requests").arg(limit_requests.to_string());
         }
         if let Some(timeout) = spec.watch_timeout {
             cmd.arg("--watch-timeout")
                 .arg(timeout.as_secs().to_string());
         }
-        if spec.ping {
-            cmd.arg("--enable-ping");
-        }
-        if spec.resolver {
-            cmd.arg("--enable-resolver");
-        }
-        if spec.action {
-            cmd.arg("--enable-action-run");
-        }
+        cmd.args(spec.endpoint_args());
 
         Ok(Box::new(LocalProcessRuntime {
             cmd,
@@ -656,6 +941,24 @@ impl LocalInstanceRuntime for LocalProcessRuntime {
             None => Ok(()),
         }
     }
+
+    async fn terminate_graceful(
+        &mut self,
+        grace: Duration,
+    ) -> result::Result<(), LocalUdsInstanceError> {
+        match self.child.as_mut() {
+            Some(c) => {
+                if let Ok(exited) = time::timeout(grace, c.wait()).await {
+                    exited.map_err(ShutdownError::ChildWait)?;
+                    return Ok(());
+                }
+                debug!("server did not exit within {:?}, terminating it", grace);
+                process::child_shutdown(c, Some(process::Signal::SIGTERM), Some(grace)).await?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -677,7 +980,7 @@ impl LocalDockerRuntime {
             String::from("/usr/local/bin/lang-js"),
             String::from("--enable-watch"),
         ];
-        if let Some(limit_requests) = spec.limit_requests {
+        if let Some(limit_requests) = spec.server_limit_requests() {
             cmd.push(String::from("--limit-requests"));
             cmd.push(limit_requests.to_string())
         }
@@ -685,15 +988,7 @@ impl LocalDockerRuntime {
             cmd.push(String::from("--watch-timeout"));
             cmd.push(timeout.as_secs().to_string());
         }
-        if spec.ping {
-            cmd.push(String::from("--enable-ping"));
-        }
-        if spec.resolver {
-            cmd.push(String::from("--enable-resolver"));
-        }
-        if spec.action {
-            cmd.push(String::from("--enable-action-run"));
-        }
+        cmd.extend(spec.endpoint_args().into_iter().map(String::from));
 
         let docker = Docker::connect_with_local_defaults()?;
 
@@ -719,10 +1014,16 @@ impl LocalDockerRuntime {
             .create_container(
                 Some(CreateContainerOptions {
                     name: format!("cyclone-container-{rand_string}"),
-                    platform: Some(String::from("linux/amd64")),
+                    platform: Some(
+                        spec.container_platform
+                            .unwrap_or_else(|| DEFAULT_CONTAINER_PLATFORM.to_string()),
+                    ),
                 }),
                 Config {
-                    image: Some(String::from("systeminit/cyclone:stable")),
+                    image: Some(
+                        spec.container_image
+                            .unwrap_or_else(|| DEFAULT_CONTAINER_IMAGE.to_string()),
+                    ),
                     cmd: Some(cmd),
                     host_config: Some(HostConfig {
                         mounts: Some(mounts),
@@ -784,8 +1085,8 @@ struct LocalFirecrackerRuntime {
 
 #[cfg(target_os = "linux")]
 impl LocalFirecrackerRuntime {
-    async fn build(_spec: LocalUdsInstanceSpec, id: u32) -> Result<Box<dyn LocalInstanceRuntime>> {
-        let jail = FirecrackerJail::build(id).await?;
+    async fn build(spec: LocalUdsInstanceSpec, id: u32) -> Result<Box<dyn LocalInstanceRuntime>> {
+        let jail = FirecrackerJail::build(id, &spec.jailer_identity()).await?;
         Ok(Box::new(LocalFirecrackerRuntime { jail, vm_id: id }))
     }
 }
@@ -807,20 +1108,35 @@ impl LocalInstanceRuntime for LocalFirecrackerRuntime {
     async fn terminate(&mut self) -> Result<()> {
         Ok(self.jail.terminate().await?)
     }
+
+    async fn terminate_graceful(&mut self, grace: Duration) -> Result<()> {
+        Ok(self.jail.terminate_graceful(grace).await?)
+    }
 }
 
 #[cfg(target_os = "linux")]
 impl LocalFirecrackerRuntime {
-    async fn clean(id: u32) -> Result<()> {
-        Ok(FirecrackerJail::clean(id).await?)
+    async fn clean(id: u32, spec: &LocalUdsInstanceSpec) -> Result<()> {
+        Ok(FirecrackerJail::clean(id, &spec.jailer_identity()).await?)
     }
 
-    async fn prepare(id: u32) -> Result<()> {
-        Ok(FirecrackerJail::prepare(id).await?)
+    async fn prepare(id: u32, spec: &LocalUdsInstanceSpec) -> Result<()> {
+        Ok(FirecrackerJail::prepare(id, &spec.jailer_identity()).await?)
     }
 
     async fn setup_firecracker(spec: &LocalUdsInstanceSpec) -> Result<()> {
-        Ok(FirecrackerJail::setup(spec.pool_size, spec.create_firecracker_setup_scripts).await?)
+        FirecrackerJail::setup(
+            spec.pool_size,
+            spec.create_firecracker_setup_scripts,
+            &spec.jailer_identity(),
+        )
+        .await
+        .map_err(|err| match err {
+            FirecrackerJailError::SetupRun { .. } => {
+                LocalUdsInstanceError::FirecrackerSetupRun(err.to_string())
+            }
+            err => err.into(),
+        })
     }
 }
 
@@ -891,3 +1207,303 @@ async fn watch_task<Strm>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+
+    use tokio::io::{
+        AsyncReadExt,
+        AsyncWriteExt,
+    };
+
+    use super::*;
+
+    struct StubRuntime;
+
+    #[async_trait]
+    impl LocalInstanceRuntime for StubRuntime {
+        fn id(&self) -> u32 {
+            1
+        }
+
+        fn socket(&mut self) -> PathBuf {
+            PathBuf::from("/nonexistent/cyclone.sock")
+        }
+
+        async fn spawn(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn terminate(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn unselected_endpoints_are_disabled() {
+        let spec = LocalUdsInstance::spec()
+            .action()
+            .validation()
+            .build()
+            .expect("spec should build");
+
+        assert_eq!(
+            vec![
+                "--disable-resolver",
+                "--enable-action-run",
+                "--enable-validation",
+                "--disable-schema-variant-definition",
+                "--disable-management",
+            ],
+            spec.endpoint_args()
+        );
+    }
+
+    /// Serves a ready readiness probe for every request on a Unix domain socket.
+    fn serve_readiness(socket: &Path) -> tokio::task::JoinHandle<()> {
+        let listener = tokio::net::UnixListener::bind(socket).expect("failed to bind socket");
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0; 1024];
+                    while let Ok(read) = stream.read(&mut buf).await {
+                        if read == 0 {
+                            break;
+                        }
+                        let response = "HTTP/1.1 200 OK\r\ncontent-length: 6\r\n\r\nready\n";
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn request_credit_extends_instance_life_up_to_max() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let socket = dir.path().join("cyclone.sock");
+        let server = serve_readiness(&socket);
+        let client = Client::uds(socket, Arc::new(ClientConfig::default()))
+            .expect("failed to create client");
+        let (watch_shutdown_tx, _watch_shutdown_rx) = oneshot::channel();
+        let mut instance = LocalUdsInstance {
+            _temp_path: None,
+            client,
+            created_at: Instant::now(),
+            credited_requests: 0,
+            limit_requests: Some(1),
+            max_age: None,
+            request_credit: Some(RequestCreditPolicy {
+                per_probe: 1,
+                max_total: 2,
+            }),
+            requests_served: 0,
+            runtime: Box::new(StubRuntime),
+            verify_on_acquire: false,
+            watch_shutdown_tx: Some(watch_shutdown_tx),
+        };
+        assert!(instance.is_reusable());
+
+        // each acquire probes the server and credits a request, so two credits allow two requests
+        // beyond the limit of one
+        for _ in 0..3 {
+            instance
+                .ensure_healthy()
+                .await
+                .expect("should have a remaining request");
+            instance.count_request();
+        }
+
+        assert_eq!(2, instance.credited_requests);
+        assert_eq!(3, instance.requests_served);
+        assert!(matches!(
+            instance.ensure_healthy().await,
+            Err(LocalUdsInstanceError::NoRemainingRequests)
+        ));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn request_credit_requires_a_ready_server() {
+        let mut runtime = StubRuntime;
+        let client = Client::uds(runtime.socket(), Arc::new(ClientConfig::default()))
+            .expect("failed to create client");
+        let (watch_shutdown_tx, _watch_shutdown_rx) = oneshot::channel();
+        let mut instance = LocalUdsInstance {
+            _temp_path: None,
+            client,
+            created_at: Instant::now(),
+            credited_requests: 0,
+            limit_requests: Some(1),
+            max_age: None,
+            request_credit: Some(RequestCreditPolicy {
+                per_probe: 1,
+                max_total: 2,
+            }),
+            requests_served: 0,
+            runtime: Box::new(runtime),
+            verify_on_acquire: false,
+            watch_shutdown_tx: Some(watch_shutdown_tx),
+        };
+
+        assert!(matches!(
+            instance.ensure_healthy().await,
+            Err(LocalUdsInstanceError::NotReady(_))
+        ));
+        assert_eq!(0, instance.credited_requests);
+    }
+
+    #[tokio::test]
+    async fn instance_expires_after_max_age() {
+        let mut runtime = StubRuntime;
+        let client = Client::uds(runtime.socket(), Arc::new(ClientConfig::default()))
+            .expect("failed to create client");
+        let (watch_shutdown_tx, _watch_shutdown_rx) = oneshot::channel();
+        let mut instance = LocalUdsInstance {
+            _temp_path: None,
+            client,
+            created_at: Instant::now(),
+            credited_requests: 0,
+            limit_requests: None,
+            max_age: Some(Duration::from_millis(20)),
+            request_credit: None,
+            requests_served: 0,
+            runtime: Box::new(runtime),
+            verify_on_acquire: false,
+            watch_shutdown_tx: Some(watch_shutdown_tx),
+        };
+
+        instance
+            .ensure_healthy()
+            .await
+            .expect("should be healthy before its max age");
+
+        time::sleep(Duration::from_millis(50)).await;
+        assert!(matches!(
+            instance.ensure_healthy().await,
+            Err(LocalUdsInstanceError::Expired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn graceful_termination_closes_the_watch_session() {
+        let mut runtime = StubRuntime;
+        let client = Client::uds(runtime.socket(), Arc::new(ClientConfig::default()))
+            .expect("failed to create client");
+        let (watch_shutdown_tx, mut watch_shutdown_rx) = oneshot::channel();
+        let mut instance = LocalUdsInstance {
+            _temp_path: None,
+            client,
+            created_at: Instant::now(),
+            credited_requests: 0,
+            limit_requests: None,
+            max_age: None,
+            request_credit: None,
+            requests_served: 0,
+            runtime: Box::new(runtime),
+            verify_on_acquire: false,
+            watch_shutdown_tx: Some(watch_shutdown_tx),
+        };
+
+        instance
+            .terminate_graceful(Duration::from_millis(10))
+            .await
+            .expect("failed to terminate");
+
+        assert_eq!(Ok(()), watch_shutdown_rx.try_recv());
+        assert!(matches!(
+            instance.ensure_healthy().await,
+            Err(LocalUdsInstanceError::WatchShutDown)
+        ));
+    }
+
+    fn process_runtime(script: &str) -> LocalProcessRuntime {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        LocalProcessRuntime {
+            cmd,
+            child: None,
+            socket: PathBuf::from("/nonexistent/cyclone.sock"),
+        }
+    }
+
+    #[tokio::test]
+    async fn graceful_termination_waits_for_the_server_to_exit() {
+        let mut runtime = process_runtime("sleep 0.1");
+        runtime.spawn().await.expect("failed to spawn");
+
+        runtime
+            .terminate_graceful(Duration::from_secs(5))
+            .await
+            .expect("failed to terminate");
+
+        let status = runtime
+            .child
+            .as_mut()
+            .expect("child should have been spawned")
+            .try_wait()
+            .expect("failed to check child");
+        assert!(status.is_some_and(|status| status.success()));
+    }
+
+    #[tokio::test]
+    async fn graceful_termination_kills_a_server_ignoring_sigterm() {
+        let grace = Duration::from_millis(100);
+        let mut runtime = process_runtime("trap '' TERM; exec sleep 60");
+        runtime.spawn().await.expect("failed to spawn");
+
+        let started = Instant::now();
+        runtime
+            .terminate_graceful(grace)
+            .await
+            .expect("failed to terminate");
+
+        let status = runtime
+            .child
+            .as_mut()
+            .expect("child should have been spawned")
+            .try_wait()
+            .expect("failed to check child")
+            .expect("child should have exited");
+        assert_eq!(Some(process::Signal::SIGKILL as i32), status.signal());
+        assert!(started.elapsed() >= grace * 2);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn verify_on_acquire_recycles_an_unreachable_server() {
+        let mut runtime = StubRuntime;
+        let client = Client::uds(runtime.socket(), Arc::new(ClientConfig::default()))
+            .expect("failed to create client");
+        let (watch_shutdown_tx, _watch_shutdown_rx) = oneshot::channel();
+        let mut instance = LocalUdsInstance {
+            _temp_path: None,
+            client,
+            created_at: Instant::now(),
+            credited_requests: 0,
+            limit_requests: None,
+            max_age: None,
+            request_credit: None,
+            requests_served: 0,
+            runtime: Box::new(runtime),
+            verify_on_acquire: true,
+            watch_shutdown_tx: Some(watch_shutdown_tx),
+        };
+
+        // the watch session looks open, but there is no server behind the socket
+        assert!(matches!(
+            instance.ensure_healthy().await,
+            Err(LocalUdsInstanceError::NotReady(_))
+        ));
+
+        instance.verify_on_acquire = false;
+        instance
+            .ensure_healthy()
+            .await
+            .expect("local checks alone should pass");
+    }
+}
@@ -1,4 +1,13 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    sync::{
+        Arc,
+        atomic::{
+            AtomicU32,
+            Ordering,
+        },
+    },
+};
 
 use telemetry_utils::metric;
 use tokio::sync::mpsc::Sender;
@@ -30,6 +39,7 @@ where
     drop_tx: Sender<PoolNoodleTaskType<I, S>>,
     instance: Option<I>,
     spec: S,
+    active: Arc<AtomicU32>,
 }
 
 impl<I, E, S> LifeGuard<I, E, S>
@@ -42,11 +52,14 @@ where
         instance: Option<I>,
         drop_tx: Sender<PoolNoodleTaskType<I, S>>,
         spec: S,
+        active: Arc<AtomicU32>,
     ) -> Self {
+        active.fetch_add(1, Ordering::Relaxed);
         Self {
             drop_tx,
             instance,
             spec,
+            active,
         }
     }
 }
@@ -76,6 +89,7 @@ where
         if futures::executor::block_on(self.drop_tx.send(task)).is_err() {
             warn!("failed to drop instance: {}", id);
         };
+        self.active.fetch_sub(1, Ordering::Relaxed);
         metric!(counter.pool_noodle.active = -1);
         metric!(counter.pool_noodle.task.drop = 1);
         debug!("PoolNoodle: instance pushed to dropped");
@@ -40,8 +40,16 @@
 
 use std::{
     fmt::Display,
+    ops::RangeInclusive,
     result,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            AtomicU32,
+            Ordering,
+        },
+    },
 };
 
 use crossbeam_queue::ArrayQueue;
@@ -58,11 +66,16 @@ use tokio::{
     },
     time::{
         Duration,
+        Instant,
         sleep,
+        sleep_until,
         timeout,
     },
 };
-use tokio_util::sync::CancellationToken;
+use tokio_util::{
+    sync::CancellationToken,
+    task::TaskTracker,
+};
 use tracing::{
     debug,
     info,
@@ -82,21 +95,98 @@ use crate::{
 
 type Result<T, E> = result::Result<T, PoolNoodleError<E>>;
 
+/// How long to wait between attempts to get a ready instance from the pool, unless another
+/// [`BackoffStrategy`] is configured.
+pub const GET_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often [`PoolNoodle::drain`] checks whether outstanding work has finished.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long an instance returned while the pool is draining is given to finish its work before it
+/// is terminated.
+const DRAIN_TERMINATION_GRACE: Duration = Duration::from_secs(30);
+
 #[derive(Clone, Debug)]
 /// Configuration object for setting up pool noodle
 pub struct PoolNoodleConfig<S> {
+    /// How long to wait between attempts to get a ready instance from the pool
+    pub backoff: BackoffStrategy,
     /// Verify instances can be started and stopped before starting the pool management tasks
     pub check_health: bool,
     /// Max number of worker threads to run at once. Defaults to available_parallelism() or 16
     pub max_concurrency: u32,
+    /// Number of instances to keep ready in the general pool when there is no demand. More are
+    /// prepared, up to `pool_size`, only while requests are waiting for an instance. Defaults to
+    /// keeping the whole general pool ready
+    pub min_ready: Option<u32>,
     /// Maximum number of instances to manage at once
     pub pool_size: u32,
-    /// Number of attempts to get from the pool before giving up with 10 ms between attempts
+    /// Number of attempts to get from the pool before giving up, waiting according to `backoff`
+    /// between attempts
     pub retry_limit: u32,
     /// Shuts down the pool management tasks
     pub shutdown_token: CancellationToken,
     /// The spec for the type of instance to manage
     pub spec: S,
+    /// Sub-pools of instances dedicated to frequently-executed work, carved out of `pool_size`
+    pub warm_pools: Vec<WarmPoolConfig>,
+}
+
+/// How long [`PoolNoodle::get`] waits between attempts to get a ready instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// Wait the same amount of time between every attempt
+    Fixed(Duration),
+    /// Wait `base` after the first attempt, doubling the wait after each attempt until it reaches
+    /// `max`
+    Exponential {
+        /// The wait after the first attempt
+        base: Duration,
+        /// The longest wait between attempts
+        max: Duration,
+    },
+}
+
+impl Default for BackoffStrategy {
+    fn default() -> Self {
+        Self::Fixed(GET_RETRY_INTERVAL)
+    }
+}
+
+impl BackoffStrategy {
+    /// How long to wait after the given attempt, counting from zero.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Self::Fixed(delay) => delay,
+            Self::Exponential { base, max } => 2_u32
+                .checked_pow(attempt)
+                .and_then(|factor| base.checked_mul(factor))
+                .map_or(max, |delay| delay.min(max)),
+        }
+    }
+
+    /// How long is spent waiting in total over the given number of attempts.
+    pub fn total(&self, attempts: u32) -> Duration {
+        match *self {
+            Self::Fixed(delay) => delay.saturating_mul(attempts),
+            Self::Exponential { .. } => (0..attempts)
+                .map(|attempt| self.delay(attempt))
+                .fold(Duration::ZERO, Duration::saturating_add),
+        }
+    }
+}
+
+/// Configuration for a sub-pool of instances dedicated to a single key, such as the name of a
+/// frequently executed function.
+///
+/// Work requested for the key is served from its sub-pool first, falling back to the general pool
+/// when the sub-pool is empty. Other work never uses the sub-pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WarmPoolConfig {
+    /// The key which work must be requested with to use this sub-pool
+    pub key: String,
+    /// The number of instances dedicated to this sub-pool
+    pub size: u32,
 }
 
 impl<S> Default for PoolNoodleConfig<S>
@@ -105,16 +195,37 @@ where
 {
     fn default() -> Self {
         Self {
+            backoff: BackoffStrategy::default(),
             check_health: false,
             max_concurrency: 1000,
+            min_ready: None,
             pool_size: 100,
             retry_limit: 120, // * 100ms between tries, we will try for 2 minutes before giving up
             shutdown_token: CancellationToken::new(),
             spec: S::default(),
+            warm_pools: Vec::new(),
         }
     }
 }
 
+/// A point-in-time snapshot of how saturated a [`PoolNoodle`] is.
+///
+/// Each field is read separately while the pool keeps running, so the values may not be exactly
+/// consistent with one another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolNoodleStats {
+    /// Number of instances ready to be handed out, across the general and warm pools
+    pub ready_len: usize,
+    /// Number of clean, prepare and drop tasks waiting to be picked up by the main loop
+    pub work_queue_len: usize,
+    /// Number of instances currently handed out
+    pub active: u32,
+    /// Maximum number of instances managed at once
+    pub pool_size: u32,
+    /// Max number of worker threads run at once
+    pub max_concurrency: u32,
+}
+
 /// Pool Noodle is a tool for ensuring that we maintain a bare minimum number of Firecracker Jails
 /// for function execution. We wrap it in an Arc Mutex so we can update the queues it manages
 /// across threads.
@@ -167,12 +278,16 @@ where
                     }
 
                     Some(task_type) = q.recv() => {
+                        // Count the task as in flight while it waits for a permit, so that a
+                        // drain does not see an empty queue and consider the pool idle
+                        let in_flight = inner.tasks.token();
                         let inner = inner.clone();
                         let permit = semaphore.clone().acquire_owned().await;
 
                         tokio::spawn(async move {
                             inner.handle_task(task_type).await;
                             drop(permit);
+                            drop(in_flight);
                         });
                     }
                 }
@@ -195,56 +310,189 @@ where
     }
 
     /// Returns the admission semaphore for external backpressure control.
+    ///
+    /// It holds a permit for every instance which is ready, being prepared or parked, and not yet
+    /// taken by an admitted request, so that requests are admitted for parked instances too and
+    /// the demand they create prepares those instances.
     pub fn admission_semaphore(&self) -> Arc<Semaphore> {
         self.inner().admission_semaphore.clone()
     }
 
+    /// Returns a snapshot of the pool's saturation: how many instances are ready, how many are
+    /// handed out and how much work is waiting to be done.
+    pub fn stats(&self) -> PoolNoodleStats {
+        let inner = self.inner();
+
+        PoolNoodleStats {
+            ready_len: inner.ready_len(),
+            work_queue_len: inner.work_queue_len(),
+            active: inner.active.load(Ordering::Relaxed),
+            pool_size: inner.pool_size,
+            max_concurrency: inner.max_concurrency,
+        }
+    }
+
+    /// Gracefully shuts the pool down, so that no instances are orphaned when the process exits.
+    ///
+    /// No new instances are prepared, and no more requests are admitted, once draining starts.
+    /// Every ready instance is terminated and cleaned, and this waits until queued tasks have
+    /// finished and every instance which is handed out has been returned and cleaned. Cancel the
+    /// shutdown token only after this resolves, since the main loop is what cleans returned
+    /// instances.
+    ///
+    /// Every ready instance is terminated and cleaned even if some fail, and the first failure is
+    /// returned.
+    pub async fn drain(&self) -> Result<(), E> {
+        let inner = self.inner();
+        info!("draining pool");
+        inner.draining.store(true, Ordering::SeqCst);
+
+        // Parked instances are already cleaned, so only the requests admitted for them are
+        // withdrawn
+        while inner.parked.pop().is_some() {
+            inner.admission_semaphore.forget_permits(1);
+        }
+
+        let mut first_err = None;
+        loop {
+            while let Some(instance) = inner.pop_any_ready() {
+                metric!(counter.pool_noodle.ready = -1);
+                if let Err(err) = inner.retire(instance).await {
+                    warn!(
+                        "PoolNoodle: failed to retire instance while draining: {}",
+                        err
+                    );
+                    first_err.get_or_insert(err);
+                }
+            }
+
+            // Instances which finished spawning just as the drain started may still have been
+            // made ready, so only stop once nothing is ready and nothing else is going on
+            if inner.is_idle() && inner.ready_len() == 0 {
+                break;
+            }
+            sleep(DRAIN_POLL_INTERVAL).await;
+        }
+
+        info!("pool drained");
+        first_err.map_or(Ok(()), Err)
+    }
+
     /// This will attempt to get a ready, healthy instance from the pool.
     /// If there are no instances, it will give the main loop a chance to fill the pool and try
     /// again. It will throw an error if there are no available instances after enough retries.
     pub async fn get(&self) -> Result<LifeGuard<I, E, S>, E> {
+        self.get_with_timeout(self.retry_timeout()).await
+    }
+
+    /// Like [`Self::get`], but waits up to `timeout` for a ready instance, rather than for the
+    /// configured number of retries.
+    pub async fn get_with_timeout(&self, timeout: Duration) -> Result<LifeGuard<I, E, S>, E> {
+        self.get_inner(None, timeout).await
+    }
+
+    /// Like [`Self::get`], but prefers an instance from the warm sub-pool dedicated to `key`, if
+    /// one is configured, before falling back to the general pool.
+    pub async fn get_for(&self, key: &str) -> Result<LifeGuard<I, E, S>, E> {
+        self.get_inner(Some(key), self.retry_timeout()).await
+    }
+
+    /// Like [`Self::get`], but returns `Ok(None)` immediately rather than waiting when no healthy
+    /// instance is ready, so callers with other work to fall back on never block.
+    pub async fn try_get(&self) -> Result<Option<LifeGuard<I, E, S>>, E> {
+        let inner = self.inner();
+
+        let Some(mut instance) = inner.pop_ready(None) else {
+            return Ok(None);
+        };
+        metric!(counter.pool_noodle.ready = -1);
+        inner.unpark().await;
+
+        match instance.ensure_healthy().await {
+            Ok(_) => {
+                metric!(counter.pool_noodle.active = 1);
+                inner.owe_admission(instance.id());
+                Ok(Some(LifeGuard::new(
+                    Some(instance),
+                    inner.queue_tx.clone(),
+                    inner.spec.clone(),
+                    inner.active.clone(),
+                )))
+            }
+            Err(_) => {
+                debug!("PoolNoodle: not healthy, cleaning up.");
+                inner.discard(instance).await;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn get_inner(
+        &self,
+        key: Option<&str>,
+        wait_for: Duration,
+    ) -> Result<LifeGuard<I, E, S>, E> {
+        let inner = self.inner();
         metric!(counter.pool_noodle.get_requests = 1);
+        inner.waiting.fetch_add(1, Ordering::SeqCst);
+        let result = self.acquire(key, Instant::now() + wait_for).await;
+        inner.waiting.fetch_sub(1, Ordering::SeqCst);
+        metric!(counter.pool_noodle.get_requests = -1);
+        // Top the pool back up now that an instance has been taken, or stop if we gave up
+        inner.unpark().await;
+        result
+    }
+
+    /// Waits until a healthy ready instance is available, discarding any unhealthy ones.
+    ///
+    /// Only the wait for a ready instance is bounded by `deadline`. An instance which has been
+    /// taken from a ready queue is always either handed out or discarded, so that it is cleaned
+    /// and prepared again rather than lost.
+    async fn acquire(&self, key: Option<&str>, deadline: Instant) -> Result<LifeGuard<I, E, S>, E> {
         let inner = self.inner();
+        let mut attempt = 0;
 
-        let max_retries = self.inner().retry_limit; // Set the maximum number of retries
-        let mut retries = 0;
         loop {
-            if retries >= max_retries {
-                metric!(counter.pool_noodle.get_requests = -1);
-                return Err(PoolNoodleError::ExecutionPoolStarved);
-            }
-            match inner.ready_queue.pop() {
-                Some(mut instance) => {
-                    metric!(counter.pool_noodle.ready = -1);
-                    // Try to ensure the item is healthy
-                    match &mut instance.ensure_healthy().await {
-                        Ok(_) => {
-                            metric!(counter.pool_noodle.get_requests = -1);
-                            metric!(counter.pool_noodle.active = 1);
-                            return Ok(LifeGuard::new(
-                                Some(instance),
-                                inner.queue_tx.clone(),
-                                inner.spec.clone(),
-                            ));
-                        }
-                        Err(_) => {
-                            debug!("PoolNoodle: not healthy, cleaning up and getting a new one.");
-                            drop(instance);
-                        }
-                    }
+            // Make sure instances are being prepared for this request
+            inner.unpark().await;
+            let Some(mut instance) = inner.pop_ready(key) else {
+                if Instant::now() >= deadline {
+                    return Err(PoolNoodleError::ExecutionPoolStarved);
                 }
-                _ => {
-                    retries += 1;
-                    debug!(
-                        "Failed to get from pool, retry ({} of {})",
-                        retries, max_retries
-                    );
-                    sleep(Duration::from_millis(100)).await;
+                debug!("Failed to get from pool, retrying");
+                sleep_until(deadline.min(Instant::now() + inner.backoff.delay(attempt))).await;
+                attempt = attempt.saturating_add(1);
+                continue;
+            };
+            metric!(counter.pool_noodle.ready = -1);
+
+            // Try to ensure the item is healthy
+            match instance.ensure_healthy().await {
+                Ok(_) => {
+                    metric!(counter.pool_noodle.active = 1);
+                    inner.owe_admission(instance.id());
+                    return Ok(LifeGuard::new(
+                        Some(instance),
+                        inner.queue_tx.clone(),
+                        inner.spec.clone(),
+                        inner.active.clone(),
+                    ));
+                }
+                Err(_) => {
+                    debug!("PoolNoodle: not healthy, cleaning up and getting a new one.");
+                    inner.discard(instance).await;
                 }
             }
         }
     }
 
+    /// How long [`Self::get`] waits for a ready instance, from the configured retry limit and
+    /// backoff.
+    fn retry_timeout(&self) -> Duration {
+        let inner = self.inner();
+        inner.backoff.total(inner.retry_limit)
+    }
+
     async fn check_health(&mut self) -> Result<(), E> {
         info!("verifying instance lifecycle health");
         let id = 0;
@@ -277,16 +525,76 @@ pub(crate) struct PoolNoodleInner<I, S>
 where
     S: Spec,
 {
+    backoff: BackoffStrategy,
     check_health: bool,
     max_concurrency: u32,
     pool_size: u32,
     ready_queue: ArrayQueue<I>,
+    warm_pools: Vec<WarmPool<I>>,
     retry_limit: u32,
     shutdown_token: CancellationToken,
     spec: S,
+    draining: AtomicBool,
+    tasks: TaskTracker,
+    min_ready: u32,
+    /// Ids of general pool instances which have been cleaned but not prepared, while there is no
+    /// need for more ready instances.
+    parked: ArrayQueue<u32>,
+    preparing: AtomicU32,
+    waiting: AtomicU32,
+    /// Whether each instance, indexed by id, has been handed out since a request was last
+    /// admitted for it. Instances which are recycled without being handed out, such as unhealthy
+    /// ones, are still covered by the request admitted for them before.
+    admission_owed: Vec<AtomicBool>,
     queue_rx: Mutex<Receiver<PoolNoodleTaskType<I, S>>>,
     queue_tx: Sender<PoolNoodleTaskType<I, S>>,
     admission_semaphore: Arc<Semaphore>,
+    active: Arc<AtomicU32>,
+}
+
+#[derive(Debug)]
+struct WarmPool<I> {
+    key: String,
+    ids: RangeInclusive<u32>,
+    ready_queue: ArrayQueue<I>,
+}
+
+impl<I> WarmPool<I> {
+    /// Dedicates the highest instance IDs to the configured warm pools, returning the number of
+    /// instances left for the general pool alongside the warm pools.
+    ///
+    /// At least one instance is always left for the general pool, so warm pools which do not fit
+    /// are skipped.
+    fn carve(pool_size: u32, configs: Vec<WarmPoolConfig>) -> (u32, Vec<Self>) {
+        let mut general_size = pool_size;
+        let mut warm_pools = Vec::with_capacity(configs.len());
+        for config in configs {
+            if config.size == 0 {
+                continue;
+            }
+            if config.size >= general_size {
+                warn!(
+                    "not enough instances for a warm pool of size {} for {}, skipping",
+                    config.size, config.key
+                );
+                continue;
+            }
+
+            let ids = (general_size - config.size + 1)..=general_size;
+            general_size -= config.size;
+            info!(
+                "dedicating a warm pool of size {} for {}",
+                config.size, config.key
+            );
+            warm_pools.push(Self {
+                key: config.key,
+                ids,
+                ready_queue: ArrayQueue::new(config.size as usize),
+            });
+        }
+
+        (general_size, warm_pools)
+    }
 }
 
 impl<I, E, S> PoolNoodleInner<I, S>
@@ -301,17 +609,37 @@ where
             config.pool_size, config.max_concurrency
         );
         let (queue_tx, queue_rx) = mpsc::channel(config.pool_size as usize);
+        let (general_size, warm_pools) = WarmPool::carve(config.pool_size, config.warm_pools);
+        let min_ready = config.min_ready.unwrap_or(general_size).min(general_size);
+        if min_ready < general_size {
+            info!(
+                "keeping {} of {} general pool instances ready when idle",
+                min_ready, general_size
+            );
+        }
         Self {
+            backoff: config.backoff,
             check_health: config.check_health,
             max_concurrency: config.max_concurrency,
             pool_size: config.pool_size,
-            ready_queue: ArrayQueue::new(config.pool_size as usize),
+            ready_queue: ArrayQueue::new(general_size.max(1) as usize),
+            warm_pools,
             retry_limit: config.retry_limit,
             shutdown_token: config.shutdown_token,
             spec: config.spec,
+            draining: AtomicBool::new(false),
+            tasks: TaskTracker::new(),
+            min_ready,
+            parked: ArrayQueue::new(general_size.max(1) as usize),
+            preparing: AtomicU32::new(0),
+            waiting: AtomicU32::new(0),
+            admission_owed: (0..=config.pool_size)
+                .map(|_| AtomicBool::new(true))
+                .collect(),
             queue_rx: queue_rx.into(),
             queue_tx,
             admission_semaphore: Arc::new(Semaphore::new(0)),
+            active: Arc::new(AtomicU32::new(0)),
         }
     }
 
@@ -333,7 +661,7 @@ where
         loop {
             match task.clean().await {
                 Ok(_) => {
-                    self.push_prepare_task_to_work_queue(id).await;
+                    self.prepare_or_park(id).await;
                     break;
                 }
                 Err(e) => {
@@ -354,10 +682,27 @@ where
         }
     }
 
-    async fn handle_drop(&self, task: PoolNoodleTask<I, S>) {
+    async fn handle_drop(&self, mut task: PoolNoodleTask<I, S>) {
         metric!(counter.pool_noodle.task.drop = -1);
         let id = task.id();
-        match task.terminate().await {
+        // A reusable instance goes back to the ready queue, where it must pass its health check
+        // again before being handed out
+        if !self.is_draining() {
+            if let Some(instance) = task.take_reusable_instance() {
+                debug!("PoolNoodle: reusing instance: {}", id);
+                self.push_to_ready_queue(id, instance).await;
+                self.admit(id);
+                return;
+            }
+        }
+        // While draining, the pool is going away rather than recycling the instance, so let it
+        // finish its work instead of cutting it off
+        let result = if self.is_draining() {
+            task.terminate_graceful(DRAIN_TERMINATION_GRACE).await
+        } else {
+            task.terminate().await
+        };
+        match result {
             Ok(_) => {
                 self.push_clean_task_to_work_queue(id).await;
             }
@@ -374,17 +719,20 @@ where
         match &task.prepare().await {
             Ok(_) => match task.spawn().await {
                 Ok(instance) => {
-                    self.push_to_ready_queue(instance).await;
+                    self.finish_preparing(id);
+                    self.push_to_ready_queue(id, instance).await;
                 }
                 Err(e) => {
                     warn!("PoolNoodle: failed to start instance: {}", id);
                     warn!("{}", e);
+                    self.finish_preparing(id);
                     self.push_clean_task_to_work_queue(id).await;
                 }
             },
             Err(e) => {
                 warn!("PoolNoodle: failed to ready instance: {}", id);
                 warn!("{}", e);
+                self.finish_preparing(id);
                 self.push_clean_task_to_work_queue(id).await;
             }
         }
@@ -398,21 +746,184 @@ where
         metric!(counter.pool_noodle.task.clean = 1);
     }
 
+    /// Hands an instance which was taken from a ready queue, but not handed out, back to the main
+    /// loop to be terminated and cleaned like a returned one.
+    async fn discard(&self, instance: I) {
+        let id = instance.id();
+        let task =
+            PoolNoodleTaskType::Drop(PoolNoodleTask::new(Some(instance), id, self.spec.clone()));
+        if self.queue_tx.send(task).await.is_err() {
+            warn!("failed to push instance to drop: {}", id);
+        };
+        metric!(counter.pool_noodle.task.drop = 1);
+    }
+
     async fn push_prepare_task_to_work_queue(&self, id: u32) {
+        if self.is_draining() {
+            debug!("PoolNoodle: draining, not preparing instance: {}", id);
+            return;
+        }
         let task = PoolNoodleTaskType::Prepare(PoolNoodleTask::new(None, id, self.spec.clone()));
+        if !self.is_warm(id) {
+            self.preparing.fetch_add(1, Ordering::SeqCst);
+        }
         if self.queue_tx.send(task).await.is_err() {
             warn!("failed to push instance to prepare: {}", id);
+            self.finish_preparing(id);
         };
         metric!(counter.pool_noodle.task.prepare = 1);
     }
 
-    async fn push_to_ready_queue(&self, instance: I) {
+    /// Prepares a cleaned instance if more ready instances are needed, otherwise parks it until
+    /// they are. Warm pool instances are always prepared.
+    ///
+    /// Either way the instance can now serve a request, so a request is admitted for it unless
+    /// one already was.
+    async fn prepare_or_park(&self, id: u32) {
+        if self.is_draining() {
+            debug!("PoolNoodle: draining, not admitting instance: {}", id);
+            return;
+        }
+        if self.is_warm(id) || self.needs_ready() {
+            self.push_prepare_task_to_work_queue(id).await;
+        } else if self.parked.push(id).is_err() {
+            warn!("failed to park instance: {}", id);
+            return;
+        } else {
+            debug!("PoolNoodle: parked instance: {}", id);
+        }
+        self.admit(id);
+    }
+
+    /// Admits a request for an instance, if it has been handed out since one last was.
+    fn admit(&self, id: u32) {
+        if !self.is_draining()
+            && self
+                .admission_owed
+                .get(id as usize)
+                .is_some_and(|owed| owed.swap(false, Ordering::SeqCst))
+        {
+            self.admission_semaphore.add_permits(1);
+        }
+    }
+
+    /// Marks an instance which is being handed out, and so used up the request admitted for it,
+    /// as owing a new one once it can serve another request.
+    fn owe_admission(&self, id: u32) {
+        if let Some(owed) = self.admission_owed.get(id as usize) {
+            owed.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Prepares parked instances until there are enough ready, or being made ready, to cover
+    /// `min_ready` and any waiting requests.
+    async fn unpark(&self) {
+        while self.needs_ready() {
+            let Some(id) = self.parked.pop() else {
+                break;
+            };
+            debug!("PoolNoodle: unparking instance: {}", id);
+            self.push_prepare_task_to_work_queue(id).await;
+        }
+    }
+
+    /// Stops counting a general pool instance as being prepared, whether or not it succeeded.
+    ///
+    /// This happens before the instance is made ready or cleaned, so that it is briefly counted as
+    /// neither rather than both, which at worst prepares one more instance than needed.
+    fn finish_preparing(&self, id: u32) {
+        if !self.is_warm(id) {
+            self.preparing.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    fn is_warm(&self, id: u32) -> bool {
+        self.warm_pools.iter().any(|pool| pool.ids.contains(&id))
+    }
+
+    /// Whether the general pool has fewer instances ready, or being prepared, than `min_ready` or
+    /// the number of waiting requests.
+    fn needs_ready(&self) -> bool {
+        let supply = self.ready_queue.len() as u32 + self.preparing.load(Ordering::SeqCst);
+        let demand = self.min_ready.max(self.waiting.load(Ordering::SeqCst));
+        supply < demand
+    }
+
+    /// Pops a ready instance, preferring the warm sub-pool for `key` if there is one.
+    fn pop_ready(&self, key: Option<&str>) -> Option<I> {
+        key.and_then(|key| self.warm_pools.iter().find(|pool| pool.key == key))
+            .and_then(|pool| pool.ready_queue.pop())
+            .or_else(|| self.ready_queue.pop())
+    }
+
+    /// Pops a ready instance from any of the ready queues.
+    fn pop_any_ready(&self) -> Option<I> {
+        self.ready_queue.pop().or_else(|| {
+            self.warm_pools
+                .iter()
+                .find_map(|pool| pool.ready_queue.pop())
+        })
+    }
+
+    fn ready_len(&self) -> usize {
+        self.ready_queue.len()
+            + self
+                .warm_pools
+                .iter()
+                .map(|pool| pool.ready_queue.len())
+                .sum::<usize>()
+    }
+
+    fn work_queue_len(&self) -> usize {
+        self.queue_tx.max_capacity() - self.queue_tx.capacity()
+    }
+
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Whether there are no queued or running tasks and no instances handed out.
+    fn is_idle(&self) -> bool {
+        self.work_queue_len() == 0
+            && self.tasks.is_empty()
+            && self.active.load(Ordering::SeqCst) == 0
+    }
+
+    /// Terminates and cleans a ready instance without preparing a replacement, withdrawing the
+    /// request admitted for it.
+    async fn retire(&self, instance: I) -> Result<(), E> {
         let id = instance.id();
-        if self.ready_queue.push(instance).is_err() {
+        self.admission_semaphore.forget_permits(1);
+        PoolNoodleTask::new(Some(instance), id, self.spec.clone())
+            .terminate()
+            .await?;
+        self.spec
+            .clean(id)
+            .await
+            .map_err(|err| PoolNoodleError::InstanceClean(err))
+    }
+
+    /// Returns the ready queue which the instance with the given ID belongs in.
+    fn ready_queue_for(&self, id: u32) -> &ArrayQueue<I> {
+        self.warm_pools
+            .iter()
+            .find(|pool| pool.ids.contains(&id))
+            .map_or(&self.ready_queue, |pool| &pool.ready_queue)
+    }
+
+    async fn push_to_ready_queue(&self, id: u32, instance: I) {
+        if self.is_draining() {
+            debug!("PoolNoodle: draining, retiring instance: {}", id);
+            if let Err(err) = self.retire(instance).await {
+                warn!("PoolNoodle: failed to retire instance: {}", id);
+                warn!("{}", err);
+            }
+            return;
+        }
+        if self.ready_queue_for(id).push(instance).is_err() {
             warn!("failed to push to ready queue: {}", id);
         }
         metric!(counter.pool_noodle.ready = 1);
-        self.admission_semaphore.add_permits(1);
     }
 }
 
@@ -434,7 +945,9 @@ mod tests {
     use super::*;
     use crate::instance::SpecBuilder;
 
-    pub struct DummyInstance {}
+    pub struct DummyInstance {
+        id: u32,
+    }
 
     #[derive(Clone)]
     pub struct DummyInstanceSpec {}
@@ -453,8 +966,8 @@ mod tests {
             Ok(())
         }
 
-        async fn spawn(&self, _id: u32) -> result::Result<Self::Instance, Self::Error> {
-            Ok(DummyInstance {})
+        async fn spawn(&self, id: u32) -> result::Result<Self::Instance, Self::Error> {
+            Ok(DummyInstance { id })
         }
     }
     #[derive(Builder, Default, Clone)]
@@ -489,9 +1002,82 @@ mod tests {
         }
 
         fn id(&self) -> u32 {
-            0
+            self.id
+        }
+    }
+    /// Counts the instances it spawns. When `flaky`, the first instance is unhealthy, and when
+    /// `reusable`, instances are returned to the pool rather than replaced.
+    #[derive(Clone, Default)]
+    pub struct CountingInstanceSpec {
+        spawns: Arc<AtomicU32>,
+        flaky: bool,
+        reusable: bool,
+    }
+    #[async_trait]
+    impl Spec for CountingInstanceSpec {
+        type Instance = CountingInstance;
+        type Error = DummyInstanceError;
+
+        async fn clean(&self, _id: u32) -> result::Result<(), Self::Error> {
+            Ok(())
+        }
+        async fn prepare(&self, _id: u32) -> result::Result<(), Self::Error> {
+            Ok(())
+        }
+        async fn setup(&mut self) -> result::Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn spawn(&self, id: u32) -> result::Result<Self::Instance, Self::Error> {
+            let first = self.spawns.fetch_add(1, Ordering::SeqCst) == 0;
+            Ok(CountingInstance {
+                id,
+                healthy: !(self.flaky && first),
+                reusable: self.reusable,
+            })
+        }
+    }
+
+    pub struct CountingInstance {
+        id: u32,
+        healthy: bool,
+        reusable: bool,
+    }
+    #[async_trait]
+    impl Instance for CountingInstance {
+        type SpecBuilder = DummyInstanceBuilder;
+        type Error = DummyInstanceError;
+
+        async fn terminate(&mut self) -> result::Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn ensure_healthy(&mut self) -> result::Result<(), Self::Error> {
+            if self.healthy {
+                Ok(())
+            } else {
+                Err(DummyInstanceError {})
+            }
+        }
+
+        fn is_reusable(&self) -> bool {
+            self.reusable
+        }
+
+        fn id(&self) -> u32 {
+            self.id
         }
     }
+
+    /// Admits a request the way naxum does, taking a permit from the admission semaphore for good.
+    async fn admit(semaphore: &Semaphore) {
+        timeout(Duration::from_secs(5), semaphore.acquire())
+            .await
+            .expect("timed out waiting to be admitted")
+            .expect("admission semaphore closed")
+            .forget();
+    }
+
     #[tokio::test]
     async fn pool_noodle_lifecycle() {
         let shutdown_token = CancellationToken::new();
@@ -499,12 +1085,15 @@ mod tests {
         let spec = DummyInstanceSpec {};
 
         let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
             check_health: false,
             max_concurrency: 10,
+            min_ready: None,
             pool_size: 3,
             retry_limit: 3,
             shutdown_token: shutdown_token.clone(),
             spec,
+            warm_pools: Vec::new(),
         };
         let mut pool = PoolNoodle::new(config).await;
         pool.run().expect("failed to start");
@@ -534,12 +1123,15 @@ mod tests {
         let spec = DummyInstanceSpec {};
 
         let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
             check_health: false,
             max_concurrency: 10,
+            min_ready: None,
             pool_size,
             retry_limit: 3,
             shutdown_token: shutdown_token.clone(),
             spec,
+            warm_pools: Vec::new(),
         };
         let mut pool = PoolNoodle::new(config).await;
 
@@ -584,4 +1176,412 @@ mod tests {
 
         shutdown_token.cancel();
     }
+
+    #[tokio::test]
+    async fn warm_pool_serves_hot_requests() {
+        let shutdown_token = CancellationToken::new();
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: None,
+            pool_size: 4,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: DummyInstanceSpec {},
+            warm_pools: vec![WarmPoolConfig {
+                key: "hot".to_string(),
+                size: 2,
+            }],
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        pool.run().expect("failed to start");
+
+        // give the pool time to create all instances
+        sleep(Duration::from_millis(500)).await;
+
+        // the highest ids are dedicated to the warm pool
+        let hot_a = pool
+            .get_for("hot")
+            .await
+            .expect("should get a hot instance");
+        let hot_b = pool
+            .get_for("hot")
+            .await
+            .expect("should get a hot instance");
+        let mut hot_ids = vec![hot_a.id(), hot_b.id()];
+        hot_ids.sort();
+        assert_eq!(vec![3, 4], hot_ids);
+
+        // cold requests, including for keys without a warm pool, use the general pool
+        let cold_a = pool.get().await.expect("should get a cold instance");
+        let cold_b = pool
+            .get_for("cold")
+            .await
+            .expect("should get a cold instance");
+        let mut cold_ids = vec![cold_a.id(), cold_b.id()];
+        cold_ids.sort();
+        assert_eq!(vec![1, 2], cold_ids);
+
+        // once the warm pool is exhausted, hot requests fall back to the general pool
+        drop(cold_a);
+        let fallback = pool
+            .get_for("hot")
+            .await
+            .expect("should fall back to the general pool");
+        assert!(fallback.id() <= 2, "expected a general pool instance");
+
+        drop(hot_a);
+        drop(hot_b);
+        drop(cold_b);
+        drop(fallback);
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn get_with_timeout_gives_up_at_the_deadline() {
+        let shutdown_token = CancellationToken::new();
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: None,
+            pool_size: 1,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: DummyInstanceSpec {},
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        pool.run().expect("failed to start");
+
+        // give the pool time to create its only instance, then take it
+        sleep(Duration::from_millis(500)).await;
+        let instance = pool
+            .get_with_timeout(Duration::from_secs(1))
+            .await
+            .expect("should be able to get an instance");
+
+        let wait_for = Duration::from_millis(300);
+        let started = std::time::Instant::now();
+        let result = pool.get_with_timeout(wait_for).await;
+        let elapsed = started.elapsed();
+
+        assert!(matches!(result, Err(PoolNoodleError::ExecutionPoolStarved)));
+        assert!(elapsed >= wait_for, "gave up early, after {elapsed:?}");
+        assert!(
+            elapsed < wait_for + Duration::from_millis(200),
+            "gave up late, after {elapsed:?}"
+        );
+
+        drop(instance);
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn unhealthy_instances_are_cleaned_and_prepared_again() {
+        let shutdown_token = CancellationToken::new();
+        let spec = CountingInstanceSpec {
+            flaky: true,
+            ..Default::default()
+        };
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: None,
+            pool_size: 1,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: spec.clone(),
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        let semaphore = pool.admission_semaphore();
+        pool.run().expect("failed to start");
+
+        // the only instance is unhealthy, so its id has to be recycled for the get to succeed
+        admit(&semaphore).await;
+        let instance = pool
+            .get_with_timeout(Duration::from_secs(2))
+            .await
+            .expect("should get a replacement instance");
+        assert_eq!(1, instance.id());
+        assert_eq!(2, spec.spawns.load(Ordering::SeqCst));
+        // the replacement serves the request already admitted, so no other one is
+        assert_eq!(0, semaphore.available_permits());
+
+        drop(instance);
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(1, semaphore.available_permits());
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn reusable_instances_are_handed_out_again() {
+        let shutdown_token = CancellationToken::new();
+        let spec = CountingInstanceSpec {
+            reusable: true,
+            ..Default::default()
+        };
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: None,
+            pool_size: 1,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: spec.clone(),
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        let semaphore = pool.admission_semaphore();
+        pool.run().expect("failed to start");
+
+        for _ in 0..3 {
+            admit(&semaphore).await;
+            let instance = pool
+                .get_with_timeout(Duration::from_secs(2))
+                .await
+                .expect("should be able to get an instance");
+            assert_eq!(1, instance.id());
+            drop(instance);
+        }
+        assert_eq!(1, spec.spawns.load(Ordering::SeqCst));
+        // each reuse admits one more request, never more than there are instances
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(1, semaphore.available_permits());
+
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn try_get_does_not_wait_for_an_empty_pool() {
+        let shutdown_token = CancellationToken::new();
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: None,
+            pool_size: 2,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: DummyInstanceSpec {},
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        pool.run().expect("failed to start");
+
+        // give the pool time to create all instances
+        sleep(Duration::from_millis(500)).await;
+
+        let a = pool
+            .try_get()
+            .await
+            .expect("should not error")
+            .expect("should get an instance");
+        let b = pool
+            .try_get()
+            .await
+            .expect("should not error")
+            .expect("should get an instance");
+        assert!(
+            pool.try_get().await.expect("should not error").is_none(),
+            "expected no instance from an exhausted pool"
+        );
+
+        drop(a);
+        drop(b);
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn stats_track_ready_and_active_instances() {
+        let shutdown_token = CancellationToken::new();
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: None,
+            pool_size: 3,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: DummyInstanceSpec {},
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+
+        let stats = pool.stats();
+        assert_eq!(0, stats.ready_len);
+        assert_eq!(0, stats.active);
+        assert_eq!(3, stats.pool_size);
+        assert_eq!(10, stats.max_concurrency);
+
+        pool.run().expect("failed to start");
+
+        // give the pool time to create all instances
+        sleep(Duration::from_millis(500)).await;
+        let ready_before = pool.stats().ready_len;
+        assert_eq!(3, ready_before);
+
+        let a = pool.get().await.expect("should be able to get an instance");
+        let b = pool.get().await.expect("should be able to get an instance");
+
+        let stats = pool.stats();
+        assert_eq!(2, stats.active);
+        assert_eq!(ready_before - 2, stats.ready_len);
+
+        drop(a);
+        drop(b);
+        assert_eq!(0, pool.stats().active);
+
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn min_ready_keeps_a_smaller_floor_and_bursts_on_demand() {
+        let shutdown_token = CancellationToken::new();
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: Some(1),
+            pool_size: 3,
+            retry_limit: 10,
+            shutdown_token: shutdown_token.clone(),
+            spec: DummyInstanceSpec {},
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        let semaphore = pool.admission_semaphore();
+        pool.run().expect("failed to start");
+
+        // give the pool time to clean all instances, only preparing the floor
+        sleep(Duration::from_millis(500)).await;
+        assert_eq!(1, pool.stats().ready_len);
+        // parked instances are admitted too, or demand could never exceed the floor
+        assert_eq!(3, semaphore.available_permits());
+
+        // demand beyond the floor is admitted and met, up to the pool size
+        let mut requests = Vec::new();
+        for _ in 0..3 {
+            admit(&semaphore).await;
+            requests.push(tokio::spawn({
+                let pool = pool.clone();
+                async move { pool.get().await }
+            }));
+        }
+        let mut held = Vec::new();
+        for request in requests {
+            held.push(
+                request
+                    .await
+                    .expect("request task panicked")
+                    .expect("should be able to get an instance"),
+            );
+        }
+        assert_eq!(3, pool.stats().active);
+        assert_eq!(0, semaphore.available_permits());
+
+        // once the burst is over, returned instances are not prepared beyond the floor, but are
+        // admitted again
+        drop(held);
+        sleep(Duration::from_millis(500)).await;
+        let stats = pool.stats();
+        assert_eq!(0, stats.active);
+        assert_eq!(1, stats.ready_len);
+        assert_eq!(3, semaphore.available_permits());
+
+        shutdown_token.cancel();
+    }
+
+    #[tokio::test]
+    async fn drain_retires_ready_instances_and_waits_for_active_ones() {
+        let shutdown_token = CancellationToken::new();
+
+        let config = PoolNoodleConfig {
+            backoff: BackoffStrategy::default(),
+            check_health: false,
+            max_concurrency: 10,
+            min_ready: Some(1),
+            pool_size: 3,
+            retry_limit: 3,
+            shutdown_token: shutdown_token.clone(),
+            spec: DummyInstanceSpec {},
+            warm_pools: Vec::new(),
+        };
+        let mut pool = PoolNoodle::new(config).await;
+        let semaphore = pool.admission_semaphore();
+        pool.run().expect("failed to start");
+
+        // give the pool time to create all instances
+        sleep(Duration::from_millis(500)).await;
+        admit(&semaphore).await;
+        let held = pool.get().await.expect("should be able to get an instance");
+        sleep(Duration::from_millis(200)).await;
+        // one instance is held, one replaced the floor and one is still parked
+        assert_eq!(2, semaphore.available_permits());
+
+        let drain = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.drain().await }
+        });
+
+        // the drain waits for the held instance to be returned
+        sleep(Duration::from_millis(200)).await;
+        assert!(!drain.is_finished());
+        assert_eq!(0, pool.stats().ready_len);
+
+        drop(held);
+        timeout(Duration::from_secs(5), drain)
+            .await
+            .expect("drain should finish once the instance is returned")
+            .expect("drain task panicked")
+            .expect("failed to drain");
+
+        // nothing is prepared to replace the drained instances, nor admitted for them
+        sleep(Duration::from_millis(200)).await;
+        let stats = pool.stats();
+        assert_eq!(0, stats.ready_len);
+        assert_eq!(0, stats.work_queue_len);
+        assert_eq!(0, stats.active);
+        assert_eq!(0, semaphore.available_permits());
+
+        shutdown_token.cancel();
+    }
+
+    #[test]
+    fn exponential_backoff_doubles_up_to_max() {
+        let backoff = BackoffStrategy::Exponential {
+            base: Duration::from_millis(10),
+            max: Duration::from_millis(50),
+        };
+
+        let delays: Vec<Duration> = (0..5).map(|attempt| backoff.delay(attempt)).collect();
+        assert_eq!(
+            vec![
+                Duration::from_millis(10),
+                Duration::from_millis(20),
+                Duration::from_millis(40),
+                Duration::from_millis(50),
+                Duration::from_millis(50),
+            ],
+            delays
+        );
+        assert_eq!(Duration::from_millis(170), backoff.total(5));
+        // attempts far beyond the point of overflowing stay at the max
+        assert_eq!(Duration::from_millis(50), backoff.delay(64));
+
+        let fixed = BackoffStrategy::default();
+        assert_eq!(GET_RETRY_INTERVAL, fixed.delay(7));
+        assert_eq!(GET_RETRY_INTERVAL * 3, fixed.total(3));
+    }
 }
@@ -1,6 +1,7 @@
 use std::{
     fmt::Display,
     result,
+    time::Duration,
 };
 
 use crate::{
@@ -43,6 +44,19 @@ where
         self.id
     }
 
+    /// Takes the instance out of the task if it can be handed out again.
+    pub fn take_reusable_instance(&mut self) -> Option<I> {
+        if self
+            .instance
+            .as_ref()
+            .is_some_and(|instance| instance.is_reusable())
+        {
+            self.instance.take()
+        } else {
+            None
+        }
+    }
+
     pub async fn clean(&self) -> Result<(), E> {
         self.spec
             .clean(self.id)
@@ -73,4 +87,14 @@ where
         }
         Err(PoolNoodleError::InstanceNotFound)
     }
+
+    pub async fn terminate_graceful(self, grace: Duration) -> Result<(), E> {
+        if let Some(mut instance) = self.instance {
+            return instance
+                .terminate_graceful(grace)
+                .await
+                .map_err(|err| PoolNoodleError::InstanceTerminate(err));
+        }
+        Err(PoolNoodleError::InstanceNotFound)
+    }
 }
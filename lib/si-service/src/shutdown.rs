@@ -29,6 +29,9 @@ use tokio_util::{
 /// See [`graceful`] for more details.
 #[derive(Debug, Error)]
 pub enum ShutdownError {
+    /// When the timeout to wait for a single group to shut down has been exceeded
+    #[error("graceful shutdown timeout elapsed for {0}: {1:?}")]
+    GroupTimeoutElapsed(String, Duration),
     /// When a main handle returns an error
     #[error("main handle returned an error: {0}")]
     Handle(#[source] Box<dyn error::Error + Send + Sync + 'static>),
@@ -93,11 +96,31 @@ where
 #[derive(Debug)]
 pub struct GracefulShutdown<TelemetryFut, HanErr> {
     main_handle: Option<JoinHandle<Result<(), HanErr>>>,
-    groups: Vec<(TaskTracker, CancellationToken)>,
+    groups: Vec<ShutdownGroup>,
     telemetry_guard: Option<TelemetryFut>,
     timeout: Option<Duration>,
 }
 
+/// A related [`TaskTracker`] and [`CancellationToken`] which are shut down together.
+#[derive(Debug)]
+struct ShutdownGroup {
+    name: Option<String>,
+    tracker: TaskTracker,
+    token: CancellationToken,
+    timeout: Option<Duration>,
+}
+
+impl ShutdownGroup {
+    fn unnamed(tracker: TaskTracker, token: CancellationToken) -> Self {
+        Self {
+            name: None,
+            tracker,
+            token,
+            timeout: None,
+        }
+    }
+}
+
 impl<TelemetryFut, E, HanErr> Default for GracefulShutdown<TelemetryFut, HanErr>
 where
     TelemetryFut: Future<Output = Result<(), E>>,
@@ -120,8 +143,32 @@ where
     HanErr: error::Error + Send + Sync + 'static,
 {
     /// Adds a shutdown group, consisting of a related [`TaskTracker`] and [`CancellationToken`].
+    ///
+    /// Groups are shut down in the order they are added, with each group's tasks finishing before
+    /// the next group is cancelled.
     pub fn group(mut self, tracker: TaskTracker, token: CancellationToken) -> Self {
-        self.groups.extend([(tracker, token)]);
+        self.groups.push(ShutdownGroup::unnamed(tracker, token));
+        self
+    }
+
+    /// Adds a named shutdown group, as [`Self::group`] does, which is named in shutdown logging.
+    ///
+    /// If the group's tasks have not finished within `timeout` of it being cancelled, shutdown
+    /// moves on to the next group rather than letting this one use up the overall timeout, and
+    /// [`Self::wait`] returns [`ShutdownError::GroupTimeoutElapsed`] once shutdown is complete.
+    pub fn named_group(
+        mut self,
+        name: impl Into<String>,
+        tracker: TaskTracker,
+        token: CancellationToken,
+        timeout: impl Into<Option<Duration>>,
+    ) -> Self {
+        self.groups.push(ShutdownGroup {
+            name: Some(name.into()),
+            tracker,
+            token,
+            timeout: timeout.into(),
+        });
         self
     }
 
@@ -131,7 +178,11 @@ where
     where
         I: IntoIterator<Item = (TaskTracker, CancellationToken)>,
     {
-        self.groups.extend(shutdown_groups);
+        self.groups.extend(
+            shutdown_groups
+                .into_iter()
+                .map(|(tracker, token)| ShutdownGroup::unnamed(tracker, token)),
+        );
         self
     }
 
@@ -206,23 +257,13 @@ where
             }
         };
 
-        let total = groups.len();
-        let mut current: usize = 1;
-
-        let await_groups = async {
-            for (tracker, token) in groups {
-                debug!("performing graceful shutdown for group(s) {current}/{total}");
-                tracker.close();
-                token.cancel();
-                tracker.wait().await;
-                current = current.saturating_add(1);
-            }
-        };
+        let await_groups = shutdown_groups(groups);
 
         // Wait for all tasks to finish
-        match timeout {
-            Some(timeout) => {
-                if let Err(_elapsed) = time::timeout(timeout, await_groups).await {
+        let timed_out_groups = match timeout {
+            Some(timeout) => match time::timeout(timeout, await_groups).await {
+                Ok(timed_out_groups) => timed_out_groups,
+                Err(_elapsed) => {
                     warn!("graceful shutdown timeout exceeded; completing shutdown anyway");
                     if let Some(telemetry_guard) = telemetry_guard {
                         warn!("performing graceful shutdown for telemetry guard");
@@ -230,11 +271,9 @@ where
                     }
                     return Err(ShutdownError::TimeoutElapsed(timeout));
                 }
-            }
-            None => {
-                await_groups.await;
-            }
-        }
+            },
+            None => await_groups.await,
+        };
 
         if let Some(telemetry_guard) = telemetry_guard {
             debug!("performing graceful shutdown for telemetry guard");
@@ -243,8 +282,140 @@ where
 
         info!("graceful shutdown complete.");
         match maybe_handle_result {
-            Some(handle_result) => handle_result,
-            None => Ok(()),
+            Some(Err(err)) => Err(err),
+            Some(Ok(())) | None => match timed_out_groups.into_iter().next() {
+                Some((name, timeout)) => Err(ShutdownError::GroupTimeoutElapsed(name, timeout)),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+/// Shuts down each group in turn, waiting for one group's tasks to finish before cancelling the
+/// next, and returns the names and timeouts of any groups which did not finish in time.
+async fn shutdown_groups(groups: Vec<ShutdownGroup>) -> Vec<(String, Duration)> {
+    let total = groups.len();
+    let mut timed_out_groups = Vec::new();
+
+    for (index, group) in groups.into_iter().enumerate() {
+        let current = index.saturating_add(1);
+        let name = group.name.unwrap_or_else(|| "unnamed group".to_string());
+        debug!("performing graceful shutdown for {name} ({current}/{total})");
+
+        group.tracker.close();
+        group.token.cancel();
+        match group.timeout {
+            Some(timeout) => {
+                if let Err(_elapsed) = time::timeout(timeout, group.tracker.wait()).await {
+                    warn!(
+                        "graceful shutdown timeout of {timeout:?} exceeded for {name}; moving on \
+                        to the next group"
+                    );
+                    timed_out_groups.push((name, timeout));
+                    continue;
+                }
+            }
+            None => group.tracker.wait().await,
         }
+        debug!("graceful shutdown complete for {name}");
+    }
+
+    timed_out_groups
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        Mutex,
+    };
+
+    use super::*;
+
+    /// Spawns a task onto a new group which, once cancelled, waits `delay` and then records that
+    /// it has shut down.
+    fn group(
+        name: &'static str,
+        delay: Duration,
+        shut_down: &Arc<Mutex<Vec<&'static str>>>,
+    ) -> (TaskTracker, CancellationToken) {
+        let tracker = TaskTracker::new();
+        let token = CancellationToken::new();
+        let shut_down = shut_down.clone();
+        let cancelled = token.clone();
+        tracker.spawn(async move {
+            cancelled.cancelled().await;
+            time::sleep(delay).await;
+            shut_down.lock().expect("lock poisoned").push(name);
+        });
+        (tracker, token)
+    }
+
+    fn named(
+        name: &'static str,
+        (tracker, token): (TaskTracker, CancellationToken),
+        timeout: Option<Duration>,
+    ) -> ShutdownGroup {
+        ShutdownGroup {
+            name: Some(name.to_string()),
+            tracker,
+            token,
+            timeout,
+        }
+    }
+
+    #[tokio::test]
+    async fn groups_shut_down_in_order() {
+        let shut_down = Arc::new(Mutex::new(Vec::new()));
+        // The first group is the slowest, so would finish last if groups were not shut down in
+        // order
+        let groups = vec![
+            named(
+                "first",
+                group("first", Duration::from_millis(100), &shut_down),
+                None,
+            ),
+            named(
+                "second",
+                group("second", Duration::from_millis(10), &shut_down),
+                None,
+            ),
+            named("third", group("third", Duration::ZERO, &shut_down), None),
+        ];
+
+        let timed_out_groups = shutdown_groups(groups).await;
+
+        assert!(timed_out_groups.is_empty());
+        assert_eq!(
+            vec!["first", "second", "third"],
+            *shut_down.lock().expect("lock poisoned")
+        );
+    }
+
+    #[tokio::test]
+    async fn timed_out_group_does_not_hold_up_later_groups() {
+        let shut_down = Arc::new(Mutex::new(Vec::new()));
+        let groups = vec![
+            named(
+                "stuck",
+                group("stuck", Duration::from_secs(60), &shut_down),
+                Some(Duration::from_millis(50)),
+            ),
+            named(
+                "telemetry",
+                group("telemetry", Duration::ZERO, &shut_down),
+                Some(Duration::from_secs(5)),
+            ),
+        ];
+
+        let timed_out_groups = time::timeout(Duration::from_secs(5), shutdown_groups(groups))
+            .await
+            .expect("shutdown should not wait for the stuck group");
+
+        assert_eq!(
+            vec![("stuck".to_string(), Duration::from_millis(50))],
+            timed_out_groups
+        );
+        assert_eq!(vec!["telemetry"], *shut_down.lock().expect("lock poisoned"));
     }
 }
@@ -39,6 +39,11 @@ impl Args {
         self.vars.iter().any(|v| v == &skip_ident)
     }
 
+    /// Check if a specific option has been given
+    pub(crate) fn has_var(&self, name: &str) -> bool {
+        self.vars.iter().any(|v| v == name)
+    }
+
     pub(crate) fn should_enable_server(&self, server_name: &str) -> bool {
         let skip_ident = format!("enable_{server_name}");
         self.vars.iter().any(|v| v == &skip_ident)
@@ -392,6 +397,11 @@ pub fn dal_test(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// * `nw: &WorkspaceSignup`: a reference to the full "new-workspace" data structure,
 ///   created for this test
 ///
+/// # Router Options
+///
+/// * `rate_limit_workspaces` - Build the `router: Router` argument with workspace rate limits of
+///   one change set apply and one func execution per window, which are otherwise off
+///
 /// # Customized Tokio Runtime
 ///
 /// The attribute uses a similar strategy to the stock `#[tokio::test]` attribute, except that this
@@ -39,13 +39,13 @@ use crate::{
 };
 
 pub(crate) fn expand(item: ItemFn, args: Args) -> TokenStream {
-    let fn_setup = fn_setup(item.sig.inputs.iter());
+    let fn_setup = fn_setup(item.sig.inputs.iter(), &args);
 
     expand_test(item, args, fn_setup)
 }
 
-fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> SdfTestFnSetup {
-    let mut expander = SdfTestFnSetupExpander::new();
+fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>, args: &Args) -> SdfTestFnSetup {
+    let mut expander = SdfTestFnSetupExpander::new(args.has_var("rate_limit_workspaces"));
 
     for param in params {
         match param {
@@ -268,10 +268,12 @@ struct SdfTestFnSetupExpander {
     auth_token: Option<Rc<Ident>>,
     auth_token_ref: Option<Rc<Ident>>,
     spicedb_client: Option<Rc<Ident>>,
+
+    rate_limit_workspaces: bool,
 }
 
 impl SdfTestFnSetupExpander {
-    fn new() -> Self {
+    fn new(rate_limit_workspaces: bool) -> Self {
         Self {
             code: TokenStream::new(),
             args: Punctuated::new(),
@@ -305,6 +307,7 @@ impl SdfTestFnSetupExpander {
             auth_token: None,
             auth_token_ref: None,
             spicedb_client: None,
+            rate_limit_workspaces,
         }
     }
 
@@ -409,6 +412,16 @@ impl SdfTestFnSetupExpander {
         let crdt_multiplexer_client = crdt_multiplexer_client.as_ref();
         let spicedb_client = self.setup_spicedb_client();
         let audit_database_context = self.setup_audit_database_context();
+        let workspace_rate_limits = if self.rate_limit_workspaces {
+            quote! {
+                ::sdf_server::middleware::WorkspaceRateLimits {
+                    apply: Some(1),
+                    execution: Some(1),
+                }
+            }
+        } else {
+            quote! { ::sdf_server::middleware::WorkspaceRateLimits::default() }
+        };
 
         let var = Ident::new("router", Span::call_site());
         self.code_extend(quote! {
@@ -435,6 +448,7 @@ impl SdfTestFnSetupExpander {
                     #cancellation_token.clone(),
                     #spicedb_client,
                     #audit_database_context,
+                    #workspace_rate_limits,
                 ).into_inner()
             };
         });
@@ -49,6 +49,7 @@ use futures::{
     },
 };
 use parking_lot::RwLock;
+use telemetry_utils::monotonic;
 use thiserror::Error;
 use thread_priority::{
     ThreadPriority,
@@ -60,7 +61,10 @@ use tokio::{
     task::JoinSet,
 };
 use tokio_util::sync::CancellationToken;
-use tracing::warn;
+use tracing::{
+    info,
+    warn,
+};
 
 mod parent;
 
@@ -81,9 +85,17 @@ pub use parent::{
 /// preventing the slow down of the main Tokio runtime. If such work requires tasks to be spawned
 /// back on the original Tokio runtime (referred to here as the "parent" Tokio runtime), this can
 /// be accomplished by using [`spawn_on_parent`].
+///
+/// # Task Input Size Limit
+///
+/// An optional maximum task input size can be set with
+/// [`with_max_task_input_size`](Self::with_max_task_input_size). Tasks spawned with
+/// [`spawn_with_input_size`](Self::spawn_with_input_size) whose input exceeds this limit are
+/// rejected rather than run, so that a single oversized task can't exhaust the process' memory.
 #[derive(Clone)]
 pub struct DedicatedExecutor {
     state: Arc<RwLock<State>>,
+    max_task_input_size: Option<usize>,
 }
 
 impl fmt::Debug for DedicatedExecutor {
@@ -196,9 +208,65 @@ impl DedicatedExecutor {
 
         Ok(Self {
             state: Arc::new(RwLock::new(state)),
+            max_task_input_size: None,
         })
     }
 
+    /// Sets the maximum input size, in bytes, of tasks spawned with
+    /// [`spawn_with_input_size`](Self::spawn_with_input_size).
+    ///
+    /// A value of `None` (the default) places no limit on the task input size.
+    pub fn with_max_task_input_size(
+        mut self,
+        max_task_input_size: impl Into<Option<usize>>,
+    ) -> Self {
+        self.max_task_input_size = max_task_input_size.into();
+        self
+    }
+
+    /// Returns the maximum input size, in bytes, of tasks spawned with
+    /// [`spawn_with_input_size`](Self::spawn_with_input_size), if set.
+    pub fn max_task_input_size(&self) -> Option<usize> {
+        self.max_task_input_size
+    }
+
+    /// Runs the [`Future`] (and any tasks it spawns) on the `DedicatedExecutor`, provided the size
+    /// of its input (in bytes) is within the executor's maximum task input size.
+    ///
+    /// If the input is over the limit, the task is not run and
+    /// [`DedicatedExecutorError::TaskInputTooLarge`] is returned.
+    ///
+    /// # Cancellation
+    ///
+    /// If the returned `Future` is dropped then the task is immediately aborted.
+    pub fn spawn_with_input_size<T>(
+        &self,
+        input_size: usize,
+        task: T,
+    ) -> impl Future<Output = Result<T::Output, DedicatedExecutorError>> + use<T>
+    where
+        T: Future + Send + 'static,
+        T::Output: Send + 'static,
+    {
+        if let Some(limit) = self.max_task_input_size.filter(|limit| input_size > *limit) {
+            warn!(
+                input_size,
+                limit, "rejecting dedicated executor task with input over size limit",
+            );
+            monotonic!(
+                dedicated_executor.task_rejected = 1,
+                reason = "input_too_large"
+            );
+            return futures::future::err(DedicatedExecutorError::TaskInputTooLarge {
+                size: input_size,
+                limit,
+            })
+            .boxed();
+        }
+
+        self.spawn(task).boxed()
+    }
+
     /// Runs the [`Future`] (and any tasks it spawns) on the `DedicatedExecutor`.
     ///
     /// # Cancellation
@@ -299,6 +367,14 @@ impl DedicatedExecutor {
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum DedicatedExecutorError {
+    /// When a task's input exceeds the executor's maximum task input size
+    #[error("task input size of {size} bytes exceeds limit of {limit} bytes")]
+    TaskInputTooLarge {
+        /// The size of the rejected task's input, in bytes
+        size: usize,
+        /// The configured maximum task input size, in bytes
+        limit: usize,
+    },
     /// When a task panics
     #[error("task panicked: {0}")]
     TaskPanicked(String),
@@ -560,7 +636,10 @@ mod tests {
 
         match executor_task.await.unwrap_err() {
             DedicatedExecutorError::TaskPanicked(msg) => assert_eq!("oh noes!", msg),
-            DedicatedExecutorError::WorkerGone => panic!("unexpected error"),
+            DedicatedExecutorError::TaskInputTooLarge { .. }
+            | DedicatedExecutorError::WorkerGone => {
+                panic!("unexpected error")
+            }
         }
     }
 
@@ -578,7 +657,10 @@ mod tests {
 
         match executor_task.await.unwrap_err() {
             DedicatedExecutorError::TaskPanicked(msg) => assert_eq!("1, 2", msg),
-            DedicatedExecutorError::WorkerGone => panic!("unexpected error"),
+            DedicatedExecutorError::TaskInputTooLarge { .. }
+            | DedicatedExecutorError::WorkerGone => {
+                panic!("unexpected error")
+            }
         }
     }
 
@@ -596,7 +678,10 @@ mod tests {
 
         match executor_task.await.unwrap_err() {
             DedicatedExecutorError::TaskPanicked(msg) => assert_eq!("unknown internal error", msg),
-            DedicatedExecutorError::WorkerGone => panic!("unexpected error"),
+            DedicatedExecutorError::TaskInputTooLarge { .. }
+            | DedicatedExecutorError::WorkerGone => {
+                panic!("unexpected error")
+            }
         }
     }
 
@@ -641,6 +726,36 @@ mod tests {
         executor.join().await.expect("join errored");
     }
 
+    #[tokio::test]
+    async fn task_over_input_size_limit_is_rejected() {
+        let executor = exec().with_max_task_input_size(10);
+
+        let executor_task = executor.spawn_with_input_size(11, async { 11 });
+
+        match executor_task.await.unwrap_err() {
+            DedicatedExecutorError::TaskInputTooLarge { size, limit } => {
+                assert_eq!(11, size);
+                assert_eq!(10, limit);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+
+        let executor_task = executor.spawn_with_input_size(10, async { 10 });
+        assert_eq!(executor_task.await.expect("task errored"), 10);
+
+        executor.join().await.expect("join errored");
+    }
+
+    #[tokio::test]
+    async fn task_input_size_is_unlimited_by_default() {
+        let executor = exec();
+
+        let executor_task = executor.spawn_with_input_size(usize::MAX, async { 42 });
+        assert_eq!(executor_task.await.expect("task errored"), 42);
+
+        executor.join().await.expect("join errored");
+    }
+
     #[tokio::test]
     async fn executor_join() {
         let executor = exec();
@@ -0,0 +1,342 @@
+//! A circuit breaker which fast-fails requests to veritech while it appears to be unavailable.
+//!
+//! The breaker starts out closed, letting every request through. After
+//! [`failure_threshold`](CircuitBreakerConfig::failure_threshold) consecutive failures it opens
+//! and rejects requests for the [`cool_down`](CircuitBreakerConfig::cool_down) period. Once the
+//! cool-down has elapsed it is half-open: a single probe request is let through, which closes the
+//! breaker if it succeeds or opens it again if it fails.
+//!
+//! A request which is cancelled before it has an outcome, such as when its caller goes away, says
+//! nothing about veritech's health and so is counted as neither.
+
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+    },
+    time::Duration,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use telemetry::prelude::*;
+use tokio::time::Instant;
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_COOL_DOWN_SECS: u64 = 30;
+
+/// Configuration for a [`CircuitBreaker`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// The number of consecutive failures after which the breaker opens.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long the breaker stays open before letting a probe request through.
+    #[serde(default = "default_cool_down", with = "duration_secs")]
+    pub cool_down: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            cool_down: default_cool_down(),
+        }
+    }
+}
+
+fn default_failure_threshold() -> u32 {
+    DEFAULT_FAILURE_THRESHOLD
+}
+
+fn default_cool_down() -> Duration {
+    Duration::from_secs(DEFAULT_COOL_DOWN_SECS)
+}
+
+mod duration_secs {
+    use std::time::Duration;
+
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_secs())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+/// Tracks the outcome of requests and decides whether new requests may be sent.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Arc<Mutex<State>>,
+}
+
+impl CircuitBreaker {
+    /// Creates a new, closed [`CircuitBreaker`].
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(State::Closed {
+                consecutive_failures: 0,
+            })),
+        }
+    }
+
+    /// Returns a [`CircuitBreakerPermit`] if a request may be sent, or `None` if the breaker is
+    /// open (or half-open with a probe already in flight).
+    ///
+    /// The outcome of the request should be recorded on the permit. A permit dropped without an
+    /// outcome, for example when the request is cancelled, counts as neither a success nor a
+    /// failure.
+    pub fn try_acquire(&self) -> Option<CircuitBreakerPermit> {
+        let mut state = self.lock_state();
+        match *state {
+            State::Closed { .. } => {}
+            State::Open { until } if Instant::now() >= until => {
+                debug!("veritech circuit breaker cool-down elapsed, sending probe request");
+                *state = State::HalfOpen;
+            }
+            State::Open { .. } | State::HalfOpen => return None,
+        }
+
+        Some(CircuitBreakerPermit {
+            breaker: self.clone(),
+            recorded: false,
+        })
+    }
+
+    /// Returns `true` if the breaker is currently rejecting requests.
+    pub fn is_open(&self) -> bool {
+        !matches!(*self.lock_state(), State::Closed { .. })
+    }
+
+    fn record_success(&self) {
+        let mut state = self.lock_state();
+        if *state == State::HalfOpen {
+            info!("veritech circuit breaker probe succeeded, closing");
+        }
+        *state = State::Closed {
+            consecutive_failures: 0,
+        };
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.lock_state();
+        let consecutive_failures = match *state {
+            State::Closed {
+                consecutive_failures,
+            } => consecutive_failures.saturating_add(1),
+            // A failed probe (or a straggler from before the breaker opened) re-opens it
+            State::Open { .. } | State::HalfOpen => self.config.failure_threshold,
+        };
+
+        if consecutive_failures >= self.config.failure_threshold {
+            warn!(
+                consecutive_failures,
+                cool_down_secs = self.config.cool_down.as_secs(),
+                "veritech circuit breaker opening",
+            );
+            *state = State::Open {
+                until: Instant::now() + self.config.cool_down,
+            };
+        } else {
+            *state = State::Closed {
+                consecutive_failures,
+            };
+        }
+    }
+
+    fn record_cancelled(&self) {
+        let mut state = self.lock_state();
+        // A cancelled probe leaves the cool-down elapsed, so that the next request probes instead
+        if *state == State::HalfOpen {
+            *state = State::Open {
+                until: Instant::now(),
+            };
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Permission to send a single request while the [`CircuitBreaker`] is closed or half-open.
+#[derive(Debug)]
+pub struct CircuitBreakerPermit {
+    breaker: CircuitBreaker,
+    recorded: bool,
+}
+
+impl CircuitBreakerPermit {
+    /// Records that the request succeeded.
+    pub fn success(mut self) {
+        self.recorded = true;
+        self.breaker.record_success();
+    }
+
+    /// Records that the request failed.
+    pub fn failure(mut self) {
+        self.recorded = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for CircuitBreakerPermit {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.breaker.record_cancelled();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(cool_down: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cool_down,
+        })
+    }
+
+    #[test]
+    fn opens_after_threshold_failures() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        for _ in 0..2 {
+            breaker
+                .try_acquire()
+                .expect("breaker should be closed")
+                .failure();
+            assert!(!breaker.is_open());
+        }
+        breaker
+            .try_acquire()
+            .expect("breaker should be closed")
+            .failure();
+
+        assert!(breaker.is_open());
+        assert!(breaker.try_acquire().is_none());
+    }
+
+    #[test]
+    fn success_resets_consecutive_failures() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        for _ in 0..2 {
+            breaker
+                .try_acquire()
+                .expect("breaker should be closed")
+                .failure();
+        }
+        breaker
+            .try_acquire()
+            .expect("breaker should be closed")
+            .success();
+        for _ in 0..2 {
+            breaker
+                .try_acquire()
+                .expect("breaker should be closed")
+                .failure();
+        }
+
+        assert!(!breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn recovers_via_successful_probe() {
+        let breaker = breaker(Duration::from_millis(50));
+        for _ in 0..3 {
+            breaker
+                .try_acquire()
+                .expect("breaker should be closed")
+                .failure();
+        }
+        assert!(breaker.try_acquire().is_none());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Only a single probe is let through while half-open
+        let probe = breaker.try_acquire().expect("probe should be let through");
+        assert!(breaker.try_acquire().is_none());
+
+        probe.success();
+        assert!(!breaker.is_open());
+        assert!(breaker.try_acquire().is_some());
+    }
+
+    #[tokio::test]
+    async fn failed_probe_reopens() {
+        let breaker = breaker(Duration::from_millis(50));
+        for _ in 0..3 {
+            breaker
+                .try_acquire()
+                .expect("breaker should be closed")
+                .failure();
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        breaker
+            .try_acquire()
+            .expect("probe should be let through")
+            .failure();
+        assert!(breaker.is_open());
+        assert!(breaker.try_acquire().is_none());
+    }
+
+    #[tokio::test]
+    async fn cancelled_requests_are_not_failures() {
+        let breaker = breaker(Duration::from_millis(50));
+
+        for _ in 0..5 {
+            drop(breaker.try_acquire().expect("breaker should be closed"));
+        }
+        assert!(!breaker.is_open());
+
+        for _ in 0..3 {
+            breaker
+                .try_acquire()
+                .expect("breaker should be closed")
+                .failure();
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // A cancelled probe lets the next request probe instead
+        drop(breaker.try_acquire().expect("probe should be let through"));
+        let probe = breaker
+            .try_acquire()
+            .expect("another probe should be let through");
+        assert!(breaker.try_acquire().is_none());
+
+        probe.success();
+        assert!(!breaker.is_open());
+    }
+}
@@ -1,3 +1,8 @@
+pub use circuit_breaker::{
+    CircuitBreaker,
+    CircuitBreakerConfig,
+    CircuitBreakerPermit,
+};
 use cyclone_core::CycloneRequestable;
 pub use cyclone_core::{
     ActionRunRequest,
@@ -41,6 +46,7 @@ use serde::{
     de::DeserializeOwned,
 };
 use si_data_nats::{
+    HeaderMap,
     NatsClient,
     Subject,
     jetstream,
@@ -53,6 +59,7 @@ use tokio_util::sync::CancellationToken;
 use veritech_core::{
     FINAL_MESSAGE_HEADER_KEY,
     GetNatsSubjectFor,
+    POOL_KEY_HEADER_KEY,
     reply_mailbox_for_output,
     reply_mailbox_for_result,
 };
@@ -61,9 +68,13 @@ pub use veritech_core::{
     encrypt_value_tree,
 };
 
+mod circuit_breaker;
+
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ClientError {
+    #[error("veritech circuit breaker is open, not sending request")]
+    CircuitOpen,
     #[error("failed to serialize json message")]
     JSONSerialize(#[source] serde_json::Error),
     #[error("nats error")]
@@ -96,12 +107,54 @@ enum RequestMode {
 pub struct Client {
     nats: NatsClient,
     context: jetstream::Context,
+    circuit_breaker: Option<CircuitBreaker>,
+    pool_key: Option<String>,
 }
 
 impl Client {
     pub fn new(nats: NatsClient) -> Self {
         let context = jetstream::new(nats.clone());
-        Self { nats, context }
+        Self {
+            nats,
+            context,
+            circuit_breaker: None,
+            pool_key: None,
+        }
+    }
+
+    /// Creates a new [`Client`] with a circuit breaker if one is configured. See
+    /// [`Client::with_circuit_breaker`].
+    pub fn new_with_circuit_breaker(
+        nats: NatsClient,
+        circuit_breaker: Option<CircuitBreakerConfig>,
+    ) -> Self {
+        let client = Self::new(nats);
+        match circuit_breaker {
+            Some(config) => client.with_circuit_breaker(config),
+            None => client,
+        }
+    }
+
+    /// Fast-fails requests with [`ClientError::CircuitOpen`] while veritech appears to be
+    /// unavailable, rather than queuing them up.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Sends requests with the name of the function they execute, so that veritech can serve
+    /// them from a warm pool dedicated to that function if one is configured.
+    pub fn with_pool_key(mut self, pool_key: impl Into<String>) -> Self {
+        self.pool_key = Some(pool_key.into());
+        self
+    }
+
+    fn request_headers(&self) -> HeaderMap {
+        let mut headers = propagation::empty_injected_headers();
+        if let Some(pool_key) = &self.pool_key {
+            headers.insert(POOL_KEY_HEADER_KEY, pool_key.as_str());
+        }
+        headers
     }
 
     fn nats_subject_prefix(&self) -> Option<&str> {
@@ -277,6 +330,40 @@ impl Client {
         output_tx: Option<mpsc::Sender<OutputStream>>,
         request: &R,
         request_mode: RequestMode,
+    ) -> ClientResult<FunctionResult<R::Response>> {
+        let permit = match &self.circuit_breaker {
+            Some(circuit_breaker) => Some(
+                circuit_breaker
+                    .try_acquire()
+                    .ok_or(ClientError::CircuitOpen)?,
+            ),
+            None => None,
+        };
+
+        let result = self
+            .execute_request_inner(subject, output_tx, request, request_mode)
+            .await;
+
+        // Only transport level failures count against the breaker. A function which ran and
+        // failed is still a sign of a healthy veritech.
+        if let Some(permit) = permit {
+            match result {
+                Ok(_) => permit.success(),
+                Err(_) => permit.failure(),
+            }
+        }
+
+        result
+    }
+
+    async fn execute_request_inner<
+        R: Serialize + CycloneRequestable<Response: DeserializeOwned>,
+    >(
+        &self,
+        subject: Subject,
+        output_tx: Option<mpsc::Sender<OutputStream>>,
+        request: &R,
+        request_mode: RequestMode,
     ) -> ClientResult<FunctionResult<R::Response>> {
         // Subscribe to responses and send the request. These unsubscribe when dropped.
         let (root_subscriber, mut result_subscriber, output_subscriber) =
@@ -364,13 +451,13 @@ impl Client {
                     .publish_with_reply_and_headers(
                         subject,
                         reply_mailbox_root,
-                        propagation::empty_injected_headers(),
+                        self.request_headers(),
                         msg.into(),
                     )
                     .await?
             }
             RequestMode::Jetstream => {
-                let mut headers = propagation::empty_injected_headers();
+                let mut headers = self.request_headers();
                 header::insert_reply_inbox(&mut headers, &reply_mailbox_root);
 
                 self.context
@@ -75,6 +75,7 @@ fn nats_prefix() -> String {
 
 async fn veritech_server_for_uds_cyclone(
     subject_prefix: String,
+    max_concurrent_executions: Option<u32>,
     shutdown_token: CancellationToken,
 ) -> Server {
     let mut config_file = veritech_server::ConfigFile::default_local_uds();
@@ -98,6 +99,7 @@ async fn veritech_server_for_uds_cyclone(
         .crypto(config_file.crypto)
         .healthcheck_pool(false)
         .heartbeat_app(false)
+        .max_concurrent_executions(max_concurrent_executions)
         .build()
         .expect("failed to build spec");
     let (server, _disabled_heartbeat_app) = Server::from_config(config, shutdown_token)
@@ -111,9 +113,16 @@ async fn client(subject_prefix: String) -> Client {
 }
 
 async fn run_veritech_server_for_uds_cyclone(subject_prefix: String) -> JoinHandle<()> {
+    run_veritech_server_for_uds_cyclone_with_max_concurrent_executions(subject_prefix, None).await
+}
+
+async fn run_veritech_server_for_uds_cyclone_with_max_concurrent_executions(
+    subject_prefix: String,
+    max_concurrent_executions: Option<u32>,
+) -> JoinHandle<()> {
     let shutdown_token = CancellationToken::new();
     tokio::spawn(
-        veritech_server_for_uds_cyclone(subject_prefix, shutdown_token)
+        veritech_server_for_uds_cyclone(subject_prefix, max_concurrent_executions, shutdown_token)
             .await
             .run(),
     )
@@ -653,3 +662,97 @@ async fn backpressure_queues_messages_in_nats() {
         "all requests should eventually succeed with backpressure (got {success_count}/{num_requests})"
     );
 }
+
+#[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 4))]
+async fn max_concurrent_executions_caps_node_concurrency() {
+    let max_concurrent_executions = 2;
+    let prefix = nats_prefix();
+    run_veritech_server_for_uds_cyclone_with_max_concurrent_executions(
+        prefix.clone(),
+        Some(max_concurrent_executions),
+    )
+    .await;
+    let client = client(prefix).await;
+
+    // Each execution reports when it ran, so that overlapping executions can be counted
+    let timed_code = base64_encode(
+        r#"async function timed(input) {
+            const started = Date.now();
+            await new Promise(resolve => setTimeout(resolve, 500));
+            return [started, Date.now()];
+        }"#,
+    );
+
+    // Submit more work than the cap from several workspaces at once
+    let num_requests = POOL_SIZE * 2;
+    let request_handles: Vec<_> = (0..num_requests)
+        .map(|i| {
+            let client = client.clone();
+            let code = timed_code.clone();
+            tokio::spawn(async move {
+                let (tx, _rx) = mpsc::channel(64);
+
+                let request = ResolverFunctionRequest {
+                    execution_id: format!("max-concurrency-test-{i}"),
+                    handler: "timed".to_string(),
+                    component: ResolverFunctionComponent {
+                        data: ComponentView {
+                            properties: serde_json::json!({}),
+                            kind: ComponentKind::Standard,
+                        },
+                        parents: vec![],
+                    },
+                    response_type: ResolverFunctionResponseType::Array,
+                    code_base64: code,
+                    before: vec![],
+                };
+
+                client
+                    .execute_resolver_function(
+                        tx,
+                        &request,
+                        &format!("{WORKSPACE_ID}-{i}"),
+                        CHANGE_SET_ID,
+                    )
+                    .await
+            })
+        })
+        .collect();
+
+    let mut runs = Vec::new();
+    for result in join_all(request_handles).await {
+        match result
+            .expect("request task should not panic")
+            .expect("failed to execute resolver function")
+        {
+            FunctionResult::Success(success) => {
+                let run: (u64, u64) =
+                    serde_json::from_value(success.data).expect("run should be a time range");
+                runs.push(run);
+            }
+            FunctionResult::Failure(failure) => {
+                panic!("function did not succeed and should have: {failure:?}")
+            }
+        }
+    }
+
+    // Every execution completed, but never more than the cap at any one time
+    assert_eq!(num_requests as usize, runs.len());
+    let max_overlapping = runs
+        .iter()
+        .map(|(started, _)| {
+            runs.iter()
+                .filter(|(other_started, other_finished)| {
+                    other_started <= started && started < other_finished
+                })
+                .count()
+        })
+        .max()
+        .expect("there should be runs");
+    assert!(
+        max_overlapping <= max_concurrent_executions as usize,
+        "{max_overlapping} executions overlapped, more than the cap of \
+         {max_concurrent_executions}"
+    );
+}
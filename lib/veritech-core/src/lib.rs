@@ -51,6 +51,9 @@ const INCOMING_SUBJECT: &str = "veritech.requests.*.*.*";
 const SUBJECT_PREFIX: &str = "veritech.requests";
 
 pub const FINAL_MESSAGE_HEADER_KEY: &str = "X-Final-Message";
+/// Names the function a request executes, so that veritech can serve it from a warm pool of
+/// instances dedicated to that function.
+pub const POOL_KEY_HEADER_KEY: &str = "X-Pool-Key";
 
 // NOTE(nick,fletcher): we can probably take this type formalization a step further, but this is
 // essentially the "FuncRunId" from the "dal".
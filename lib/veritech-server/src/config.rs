@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     env,
     net::{
         SocketAddr,
@@ -120,6 +121,15 @@ pub struct Config {
 
     #[builder(default = "default_consumer_max_deliver()")]
     consumer_max_deliver: i64,
+
+    #[builder(default)]
+    max_concurrent_executions: Option<u32>,
+
+    #[builder(default)]
+    pool_min_ready: Option<u32>,
+
+    #[builder(default)]
+    warm_pools: BTreeMap<String, u32>,
 }
 
 impl StandardConfig for Config {
@@ -198,6 +208,24 @@ impl Config {
     pub fn consumer_max_deliver(&self) -> i64 {
         self.consumer_max_deliver
     }
+
+    /// Gets the config's node-wide maximum number of concurrent function executions, if set.
+    /// This caps the size of the cyclone pool, which requests are only admitted for, so when unset
+    /// the pool size is the limit.
+    pub fn max_concurrent_executions(&self) -> Option<u32> {
+        self.max_concurrent_executions
+    }
+
+    /// Gets the config's number of cyclone instances to keep ready when idle, if set. When unset,
+    /// the whole pool is kept ready.
+    pub fn pool_min_ready(&self) -> Option<u32> {
+        self.pool_min_ready
+    }
+
+    /// Gets the config's warm pool sizes, keyed by the name of the function each warm pool serves.
+    pub fn warm_pools(&self) -> &BTreeMap<String, u32> {
+        &self.warm_pools
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -225,6 +253,12 @@ pub struct ConfigFile {
     pool_get_retry_limit: u32,
     #[serde(default = "default_consumer_max_deliver")]
     consumer_max_deliver: i64,
+    #[serde(default)]
+    max_concurrent_executions: Option<u32>,
+    #[serde(default)]
+    pool_min_ready: Option<u32>,
+    #[serde(default)]
+    warm_pools: BTreeMap<String, u32>,
 }
 
 impl Default for ConfigFile {
@@ -248,6 +282,9 @@ impl ConfigFile {
             service_endpoints: default_service_endpoints_config(),
             pool_get_retry_limit: default_pool_get_retry_limit(),
             consumer_max_deliver: default_consumer_max_deliver(),
+            max_concurrent_executions: None,
+            pool_min_ready: None,
+            warm_pools: BTreeMap::new(),
         }
     }
 
@@ -265,6 +302,9 @@ impl ConfigFile {
             service_endpoints: default_service_endpoints_config(),
             pool_get_retry_limit: default_pool_get_retry_limit(),
             consumer_max_deliver: default_consumer_max_deliver(),
+            max_concurrent_executions: None,
+            pool_min_ready: None,
+            warm_pools: BTreeMap::new(),
         }
     }
 }
@@ -296,6 +336,9 @@ impl TryFrom<ConfigFile> for Config {
         config.service_endpoints(value.service_endpoints);
         config.pool_get_retry_limit(value.pool_get_retry_limit);
         config.consumer_max_deliver(value.consumer_max_deliver);
+        config.max_concurrent_executions(value.max_concurrent_executions);
+        config.pool_min_ready(value.pool_min_ready);
+        config.warm_pools(value.warm_pools);
 
         config.build().map_err(Into::into)
     }
@@ -381,6 +424,12 @@ pub enum CycloneConfig {
         resolver: bool,
         #[serde(default = "default_enable_endpoint")]
         action: bool,
+        #[serde(default = "default_enable_endpoint")]
+        validation: bool,
+        #[serde(default = "default_enable_endpoint")]
+        schema_variant_definition: bool,
+        #[serde(default = "default_enable_endpoint")]
+        management: bool,
         #[serde(default)]
         pool_size: u32,
         #[serde(default)]
@@ -417,6 +466,9 @@ impl CycloneConfig {
             ping: default_enable_endpoint(),
             resolver: default_enable_endpoint(),
             action: default_enable_endpoint(),
+            validation: default_enable_endpoint(),
+            schema_variant_definition: default_enable_endpoint(),
+            management: default_enable_endpoint(),
             pool_size: default_pool_size(),
             connect_timeout: default_connect_timeout(),
             create_firecracker_setup_scripts: default_create_firecracker_setup_scripts(),
@@ -528,6 +580,9 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 ping,
                 resolver,
                 action,
+                validation,
+                schema_variant_definition,
+                management,
                 pool_size,
                 connect_timeout,
                 create_firecracker_setup_scripts,
@@ -561,6 +616,15 @@ impl TryFrom<CycloneConfig> for CycloneSpec {
                 if action {
                     builder.action();
                 }
+                if validation {
+                    builder.validation();
+                }
+                if schema_variant_definition {
+                    builder.schema_variant_definition();
+                }
+                if management {
+                    builder.management();
+                }
                 builder.pool_size(pool_size);
                 builder.connect_timeout(connect_timeout);
                 builder.create_firecracker_setup_scripts(create_firecracker_setup_scripts);
@@ -57,6 +57,7 @@ use tokio::sync::{
 };
 use veritech_core::{
     ExecutionId,
+    POOL_KEY_HEADER_KEY,
     VeritechRequest,
     VeritechRequestError,
     VeritechValueDecryptError,
@@ -145,6 +146,11 @@ pub async fn process_request_inner(
 ) -> HandlerResult<()> {
     let span = Span::current();
 
+    let pool_key = maybe_headers
+        .as_ref()
+        .and_then(|headers| headers.get(POOL_KEY_HEADER_KEY))
+        .map(|value| value.to_string());
+
     let reply_subject = match maybe_headers
         .and_then(|headers| headers.get(header::REPLY_INBOX).map(|v| v.to_string()))
     {
@@ -195,21 +201,23 @@ pub async fn process_request_inner(
 
     match veritech_request {
         VeritechRequest::ActionRun(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, reply_subject, pool_key.as_deref()).await?
         }
         VeritechRequest::Management(request) => {
-            dispatch_request(state, *request, reply_subject).await?
+            dispatch_request(state, *request, reply_subject, pool_key.as_deref()).await?
         }
         VeritechRequest::Resolver(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, reply_subject, pool_key.as_deref()).await?
         }
         VeritechRequest::SchemaVariantDefinition(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, reply_subject, pool_key.as_deref()).await?
         }
         VeritechRequest::Validation(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(state, request, reply_subject, pool_key.as_deref()).await?
+        }
+        VeritechRequest::Debug(request) => {
+            dispatch_request(state, request, reply_subject, pool_key.as_deref()).await?
         }
-        VeritechRequest::Debug(request) => dispatch_request(state, request, reply_subject).await?,
         // Kill requests do not get handled here
         VeritechRequest::KillExecution(_) => {
             return Err(HandlerError::InvalidIncomingSubject(subject));
@@ -223,6 +231,7 @@ async fn dispatch_request<Request>(
     state: AppState,
     mut request: Request,
     reply_mailbox: Subject,
+    pool_key: Option<&str>,
 ) -> HandlerResult<()>
 where
     Request: CycloneRequestable + DecryptRequest + Serialize + Clone + Send + Sync,
@@ -230,7 +239,13 @@ where
     HandlerError: From<ExecutionError<<Request as CycloneRequestable>::Response>>,
 {
     let span = current_span_for_instrument_at!("info");
-    let mut client = match state.cyclone_pool.get().await {
+
+    // Frequently-executed functions may have a warm pool of instances dedicated to them
+    let maybe_client = match pool_key {
+        Some(pool_key) => state.cyclone_pool.get_for(pool_key).await,
+        None => state.cyclone_pool.get().await,
+    };
+    let mut client = match maybe_client {
         Ok(client) => client,
         Err(err) => {
             if let PoolNoodleError::ExecutionPoolStarved = err {
@@ -68,6 +68,8 @@ pub enum ServerError {
     NatsSubscribe(Subject, #[source] NatsError),
     #[error("naxum error: {0}")]
     Naxum(#[source] io::Error),
+    #[error("subject prefix check error: {0}")]
+    PrefixCheck(#[from] nats_std::prefix_check::PrefixCheckError),
     #[error("veritech decryption key error: {0}")]
     VeritechDecryptionKey(#[from] si_crypto::VeritechDecryptionKeyError),
     #[error("wrong cyclone spec type for {0} spec: {1:?}")]
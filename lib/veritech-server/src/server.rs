@@ -15,6 +15,10 @@ use futures::{
     StreamExt,
     join,
 };
+use nats_std::prefix_check::{
+    self,
+    PrefixCheckResponder,
+};
 use naxum::{
     MessageHead,
     ServiceBuilder,
@@ -54,7 +58,10 @@ use si_pool_noodle::{
         LocalUdsInstance,
         LocalUdsInstanceSpec,
     },
-    pool_noodle::PoolNoodleConfig,
+    pool_noodle::{
+        PoolNoodleConfig,
+        WarmPoolConfig,
+    },
 };
 use telemetry::prelude::*;
 use telemetry_utils::metric;
@@ -104,6 +111,9 @@ pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
     kill_inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
+    prefix_check_responder: PrefixCheckResponder,
+    cyclone_pool: PoolNoodle<LocalUdsInstance, LocalUdsInstanceSpec>,
+    pool_shutdown_token: CancellationToken,
     shutdown_token: CancellationToken,
 }
 
@@ -169,12 +179,28 @@ impl Server {
                 unimplemented!("get ready for a surprise!!")
             }
             CycloneSpec::LocalUds(spec) => {
+                // The pool is shut down separately, once it has been drained, as returned
+                // instances are only cleaned while it is running
+                let pool_shutdown_token = CancellationToken::new();
                 let pool_config = PoolNoodleConfig {
                     check_health: config.healthcheck_pool(),
-                    pool_size: spec.pool_size,
+                    min_ready: config.pool_min_ready(),
+                    // Requests are only admitted for instances in the pool, so its size is the
+                    // node-wide ceiling on concurrent executions
+                    pool_size: config
+                        .max_concurrent_executions()
+                        .map_or(spec.pool_size, |max| spec.pool_size.min(max.max(1))),
                     retry_limit: config.pool_get_retry_limit(),
-                    shutdown_token: token.clone(),
+                    shutdown_token: pool_shutdown_token.clone(),
                     spec: spec.clone(),
+                    warm_pools: config
+                        .warm_pools()
+                        .iter()
+                        .map(|(func_name, size)| WarmPoolConfig {
+                            key: func_name.clone(),
+                            size: *size,
+                        })
+                        .collect(),
                     ..Default::default()
                 };
 
@@ -195,7 +221,7 @@ impl Server {
 
                 let inner_future = Self::build_app(
                     metadata.clone(),
-                    cyclone_pool,
+                    cyclone_pool.clone(),
                     Arc::new(decryption_key),
                     config.cyclone_client_execution_timeout(),
                     config.consumer_max_deliver(),
@@ -214,6 +240,13 @@ impl Server {
                 )
                 .await?;
 
+                let prefix_check_responder = PrefixCheckResponder::new(
+                    nats.clone(),
+                    prefix_check::service::VERITECH,
+                    token.clone(),
+                )
+                .await?;
+
                 let maybe_heartbeat_app = if config.heartbeat_app() {
                     Some(HeartbeatApp::new(
                         nats,
@@ -231,6 +264,9 @@ impl Server {
                         metadata,
                         inner: inner_future,
                         kill_inner: kill_inner_future,
+                        prefix_check_responder,
+                        cyclone_pool,
+                        pool_shutdown_token,
                         shutdown_token: token,
                     },
                     maybe_heartbeat_app,
@@ -247,6 +283,7 @@ impl Server {
     }
 
     pub async fn try_run(self) -> ServerResult<()> {
+        tokio::spawn(self.prefix_check_responder.run());
         let (inner_result, kill_inner_result) =
             join!(tokio::spawn(self.inner), tokio::spawn(self.kill_inner));
 
@@ -260,6 +297,13 @@ impl Server {
         }
         kill_inner_result?.map_err(ServerError::Naxum)?;
 
+        // Every in-flight execution has finished by now, so no jails are orphaned by stopping the
+        // pool once it has been drained
+        if let Err(err) = self.cyclone_pool.drain().await {
+            warn!(si.error.message = ?err, "error while draining cyclone pool");
+        }
+        self.pool_shutdown_token.cancel();
+
         info!("veritech main loop shutdown complete");
         Ok(())
     }